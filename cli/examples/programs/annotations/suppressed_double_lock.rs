@@ -0,0 +1,6 @@
+fn main() {
+    let data = std::sync::Mutex::new(0);
+    let _d1 = data.lock();
+    // granite: allow(deadlock)
+    let _d2 = data.lock(); // cannot lock, since d1 is still active
+}
@@ -0,0 +1,12 @@
+// granite: allow(deadlock)
+fn unrelated() {
+    let data = std::sync::Mutex::new(0);
+    let _d1 = data.lock();
+}
+
+fn main() {
+    unrelated();
+    let data = std::sync::Mutex::new(0);
+    let _d1 = data.lock();
+    let _d2 = data.lock(); // cannot lock, since d1 is still active
+}
@@ -0,0 +1,12 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn main() {
+    let flag = AtomicUsize::new(0);
+
+    // The exchange always succeeds since nobody else touches the flag
+    let _ = flag.compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire);
+
+    while flag.load(Ordering::Acquire) == 0 {
+        std::hint::spin_loop();
+    }
+}
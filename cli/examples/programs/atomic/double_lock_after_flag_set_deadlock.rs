@@ -0,0 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+fn main() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let flag_clone = Arc::clone(&flag);
+    let mutex = Mutex::new(0);
+
+    let thread = std::thread::spawn(move || {
+        flag_clone.store(true, Ordering::Release);
+    });
+
+    while !flag.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+    thread.join().unwrap();
+
+    // The deadlock after the spin-wait must still be found
+    let _guard = mutex.lock().unwrap();
+    let _second_guard = mutex.lock().unwrap();
+}
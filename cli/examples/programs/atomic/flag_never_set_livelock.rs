@@ -0,0 +1,11 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn main() {
+    let flag = AtomicBool::new(false);
+
+    // Nobody sets the flag, so the thread spins forever.
+    // This is a livelock, not a deadlock: The loop can always make another iteration.
+    while !flag.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+}
@@ -0,0 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+fn main() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let flag_clone = Arc::clone(&flag);
+
+    let thread = std::thread::spawn(move || {
+        flag_clone.store(true, Ordering::Release);
+    });
+
+    // The main thread spins until the other thread sets the flag
+    while !flag.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+    thread.join().unwrap();
+}
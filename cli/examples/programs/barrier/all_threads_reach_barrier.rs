@@ -0,0 +1,12 @@
+fn main() {
+    let original_barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+    let copy_barrier = original_barrier.clone();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        copy_barrier.wait();
+    });
+
+    // Both threads reach the barrier, so both of them are released
+    original_barrier.wait();
+    let _join_result = thread_join_handle.join();
+}
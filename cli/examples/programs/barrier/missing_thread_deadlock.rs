@@ -0,0 +1,12 @@
+fn main() {
+    let original_barrier = std::sync::Arc::new(std::sync::Barrier::new(3));
+    let copy_barrier = original_barrier.clone();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        copy_barrier.wait();
+    });
+
+    // The barrier waits for a third thread that never arrives
+    original_barrier.wait();
+    let _join_result = thread_join_handle.join();
+}
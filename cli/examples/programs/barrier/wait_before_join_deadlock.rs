@@ -0,0 +1,12 @@
+fn main() {
+    let original_barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+    let copy_barrier = original_barrier.clone();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        copy_barrier.wait();
+    });
+
+    // The main thread joins the other thread before reaching the barrier
+    let _join_result = thread_join_handle.join();
+    original_barrier.wait();
+}
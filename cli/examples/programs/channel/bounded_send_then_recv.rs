@@ -0,0 +1,13 @@
+fn main() {
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+
+    let thread_join_handle = std::thread::spawn(move || {
+        let _first = sender.send(1);
+        // Blocks until the main thread receives the first message
+        let _second = sender.send(2);
+    });
+
+    let _first = receiver.recv();
+    let _second = receiver.recv();
+    let _join_result = thread_join_handle.join();
+}
@@ -0,0 +1,15 @@
+fn main() {
+    let (first_sender, first_receiver) = std::sync::mpsc::channel();
+    let (second_sender, second_receiver) = std::sync::mpsc::channel();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        // Waits for the main thread before answering
+        let _message = first_receiver.recv();
+        let _result = second_sender.send(1);
+    });
+
+    // Waits for the other thread before sending
+    let _message = second_receiver.recv();
+    let _result = first_sender.send(2);
+    let _join_result = thread_join_handle.join();
+}
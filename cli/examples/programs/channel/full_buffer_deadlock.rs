@@ -0,0 +1,8 @@
+fn main() {
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+
+    let _first = sender.send(1);
+    // The buffer is full and the receiver is in the same thread: This blocks forever
+    let _second = sender.send(2);
+    let _message = receiver.recv();
+}
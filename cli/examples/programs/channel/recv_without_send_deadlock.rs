@@ -0,0 +1,6 @@
+fn main() {
+    let (_sender, receiver) = std::sync::mpsc::channel::<i32>();
+
+    // No message is ever sent and the sender is still alive
+    let _message = receiver.recv();
+}
@@ -0,0 +1,11 @@
+fn main() {
+    let (sender, receiver) = std::sync::mpsc::sync_channel(0);
+
+    let thread_join_handle = std::thread::spawn(move || {
+        // Blocks until the main thread receives the message
+        let _result = sender.send(42);
+    });
+
+    let _message = receiver.recv();
+    let _join_result = thread_join_handle.join();
+}
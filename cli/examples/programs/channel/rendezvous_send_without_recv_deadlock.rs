@@ -0,0 +1,8 @@
+fn main() {
+    let (sender, receiver) = std::sync::mpsc::sync_channel(0);
+
+    // A rendezvous channel has no buffer: The send blocks until the message is received,
+    // but the receiver is in the same thread
+    let _result = sender.send(42);
+    let _message = receiver.recv();
+}
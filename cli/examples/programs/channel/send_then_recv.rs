@@ -0,0 +1,11 @@
+fn main() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        let _result = sender.send(42);
+    });
+
+    // The message sent by the other thread is eventually received
+    let _message = receiver.recv();
+    let _join_result = thread_join_handle.join();
+}
@@ -0,0 +1,19 @@
+//! Same as `wait.rs`, but the thread wakes the waiting thread with `notify_all`.
+fn main() {
+    let pair = std::sync::Arc::new((std::sync::Mutex::new(false), std::sync::Condvar::new()));
+    let pair2 = std::sync::Arc::clone(&pair);
+
+    std::thread::spawn(move || {
+        let (lock, cvar) = &*pair2;
+        let mut started = lock.lock().unwrap();
+        *started = true;
+        // We notify every thread waiting on the condvar that the value has changed.
+        cvar.notify_all();
+    });
+
+    let (lock, cvar) = &*pair;
+    let mut started = lock.lock().unwrap();
+    while !*started {
+        started = cvar.wait(started).unwrap();
+    }
+}
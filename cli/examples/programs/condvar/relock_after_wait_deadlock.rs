@@ -0,0 +1,27 @@
+//! The waiting thread is notified, but it cannot lock the mutex again after the wait,
+//! since the notifying thread keeps it locked while it waits for the resource held by the main thread.
+fn main() {
+    let state = std::sync::Arc::new((
+        std::sync::Mutex::new(false),
+        std::sync::Condvar::new(),
+        std::sync::Mutex::new(0),
+    ));
+    let state2 = std::sync::Arc::clone(&state);
+
+    let (lock, cvar, resource) = &*state;
+    let _resource = resource.lock().unwrap();
+    let mut started = lock.lock().unwrap();
+
+    std::thread::spawn(move || {
+        let (lock, cvar, resource) = &*state2;
+        let mut started = lock.lock().unwrap();
+        *started = true;
+        cvar.notify_one();
+        // The mutex `lock` is still locked here.
+        let _resource = resource.lock().unwrap();
+    });
+
+    while !*started {
+        started = cvar.wait(started).unwrap();
+    }
+}
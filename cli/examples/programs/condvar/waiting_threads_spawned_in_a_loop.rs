@@ -0,0 +1,22 @@
+//! Several threads spawned in a loop wait on the same condition variable until the main thread wakes them all.
+//! The program is deadlock-free, but the waiting threads share the single call to `wait`
+//! and only one of them waits at a time without the FIFO queue. The second thread then blocks
+//! before it starts waiting while it holds the mutex, which is reported as a deadlock.
+fn main() {
+    let pair = std::sync::Arc::new((std::sync::Mutex::new(false), std::sync::Condvar::new()));
+
+    for _ in 0..2 {
+        let pair = std::sync::Arc::clone(&pair);
+        std::thread::spawn(move || {
+            let (lock, cvar) = &*pair;
+            let mut started = lock.lock().unwrap();
+            while !*started {
+                started = cvar.wait(started).unwrap();
+            }
+        });
+    }
+
+    let (lock, cvar) = &*pair;
+    *lock.lock().unwrap() = true;
+    cvar.notify_all();
+}
@@ -0,0 +1,13 @@
+use std::sync::Mutex;
+
+fn main() {
+    let offset = 1;
+    let data = Mutex::new(0);
+    let add = |amount: i32| {
+        let total = amount + offset;
+        let mut guard = data.lock().unwrap();
+        let current = data.lock().unwrap(); // cannot lock, since the guard is still active
+        *guard = total + *current;
+    };
+    add(1);
+}
@@ -0,0 +1,16 @@
+use std::sync::Mutex;
+
+pub mod worker {
+    use super::Mutex;
+
+    pub fn run() {
+        let data = Mutex::new(0);
+        let _guard = data.lock().unwrap();
+        let _second_guard = data.lock().unwrap(); // deadlock
+    }
+}
+
+pub fn run_safely() {
+    let data = Mutex::new(0);
+    let _guard = data.lock().unwrap();
+}
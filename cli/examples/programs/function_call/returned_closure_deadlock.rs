@@ -0,0 +1,14 @@
+use std::sync::{Arc, Mutex};
+
+fn make_worker() -> impl Fn() {
+    let data = Arc::new(Mutex::new(0));
+    move || {
+        let _d1 = data.lock().unwrap();
+        let _d2 = data.lock().unwrap(); // cannot lock, since d1 is still active
+    }
+}
+
+fn main() {
+    let worker = make_worker();
+    worker();
+}
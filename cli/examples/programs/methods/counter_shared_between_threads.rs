@@ -0,0 +1,25 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+struct Counter {
+    inner: Mutex<i32>,
+}
+
+impl Counter {
+    fn increment(&self) {
+        let mut value = self.inner.lock().unwrap();
+        *value += 1;
+    }
+}
+
+fn main() {
+    let counter = Arc::new(Counter {
+        inner: Mutex::new(0),
+    });
+    let thread_counter = Arc::clone(&counter);
+    let handle = thread::spawn(move || {
+        thread_counter.increment();
+    });
+    counter.increment();
+    handle.join().unwrap();
+}
@@ -0,0 +1,28 @@
+use std::sync::Mutex;
+
+trait Store {
+    fn add(&self, amount: i32);
+}
+
+struct Account {
+    balance: Mutex<i32>,
+}
+
+impl Store for Account {
+    fn add(&self, amount: i32) {
+        let mut balance = self.balance.lock().unwrap();
+        let bonus = self.balance.lock().unwrap(); // cannot lock, since the guard is still active
+        *balance += amount + *bonus;
+    }
+}
+
+fn deposit<T: Store>(store: &T, amount: i32) {
+    store.add(amount);
+}
+
+fn main() {
+    let account = Account {
+        balance: Mutex::new(0),
+    };
+    deposit(&account, 1);
+}
@@ -0,0 +1,23 @@
+use std::sync::Mutex;
+
+struct Account {
+    balance: Mutex<i32>,
+}
+
+impl Account {
+    fn balance(&self) -> i32 {
+        *self.balance.lock().unwrap()
+    }
+
+    fn deposit(&self, amount: i32) {
+        let mut balance = self.balance.lock().unwrap();
+        *balance = amount + self.balance(); // cannot lock, since the guard is still active
+    }
+}
+
+fn main() {
+    let account = Account {
+        balance: Mutex::new(0),
+    };
+    account.deposit(1);
+}
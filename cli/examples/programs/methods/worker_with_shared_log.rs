@@ -0,0 +1,27 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+struct Worker {
+    id: usize,
+    log: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Worker {
+    fn record(&self) {
+        self.log.lock().unwrap().push(self.id);
+    }
+}
+
+fn main() {
+    let worker = Worker {
+        id: 0,
+        log: Arc::new(Mutex::new(Vec::new())),
+    };
+    let other = Worker {
+        id: 1,
+        log: Arc::clone(&worker.log),
+    };
+    let handle = thread::spawn(move || other.record());
+    worker.record();
+    handle.join().unwrap();
+}
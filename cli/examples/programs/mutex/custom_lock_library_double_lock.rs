@@ -0,0 +1,34 @@
+// A mutex of a library that is not supported by default.
+// It is only modelled if the library is passed with `--lock-library=my_sync<T>`.
+#[allow(dead_code)]
+mod my_sync {
+    pub struct Mutex<T> {
+        data: std::cell::UnsafeCell<T>,
+    }
+
+    pub struct MutexGuard<'a, T> {
+        mutex: &'a Mutex<T>,
+    }
+
+    impl<T> Mutex<T> {
+        pub fn new(value: T) -> Self {
+            Self {
+                data: std::cell::UnsafeCell::new(value),
+            }
+        }
+
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            MutexGuard { mutex: self }
+        }
+    }
+
+    impl<T> Drop for MutexGuard<'_, T> {
+        fn drop(&mut self) {}
+    }
+}
+
+fn main() {
+    let data = my_sync::Mutex::new(0);
+    let _d1 = data.lock();
+    let _d2 = data.lock(); // cannot lock, since d1 is still active
+}
@@ -0,0 +1,15 @@
+#![feature(exclusive_wrapper, sync_unsafe_cell)]
+
+use std::cell::SyncUnsafeCell;
+use std::sync::{Exclusive, Mutex};
+
+fn main() {
+    let mut exclusive = Exclusive::new(Mutex::new(0));
+    let data = exclusive.get_mut();
+    let _d1 = data.lock();
+
+    let cell = SyncUnsafeCell::new(Mutex::new(0));
+    let data = unsafe { &*cell.get() };
+    let _d2 = data.lock();
+    let _d3 = data.lock(); // cannot lock, since d2 is still active
+}
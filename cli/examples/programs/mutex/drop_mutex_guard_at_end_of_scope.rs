@@ -0,0 +1,10 @@
+use std::sync::Mutex;
+
+fn main() {
+    let data = Mutex::new(0);
+    {
+        let mut guard = data.lock().unwrap();
+        *guard += 1;
+    } // the guard is dropped here
+    let _guard = data.lock().unwrap(); // can lock, since the first guard went out of scope
+}
@@ -0,0 +1,17 @@
+use std::sync::{Mutex, MutexGuard};
+
+struct Transaction<'a> {
+    balance: MutexGuard<'a, i32>,
+    amount: i32,
+}
+
+fn main() {
+    let data = Mutex::new(0);
+    let mut transaction = Transaction {
+        balance: data.lock().unwrap(),
+        amount: 1,
+    };
+    *transaction.balance += transaction.amount;
+    drop(transaction);
+    let _guard = data.lock().unwrap(); // can lock, since the transaction released the guard
+}
@@ -0,0 +1,48 @@
+// A minimal stand-in for the `lock_api` crate, on which `parking_lot` is built,
+// since the example programs cannot depend on other crates.
+// The names of the functions and types match the ones in `lock_api`,
+// e.g. `lock_api::Mutex::<R, T>::lock`, so they are modelled like the real crate.
+#[allow(dead_code)]
+mod lock_api {
+    pub struct Mutex<R, T> {
+        raw: R,
+        data: std::cell::UnsafeCell<T>,
+    }
+
+    pub struct MutexGuard<'a, R, T> {
+        mutex: &'a Mutex<R, T>,
+    }
+
+    impl<R: Default, T> Mutex<R, T> {
+        pub fn new(value: T) -> Self {
+            Self {
+                raw: R::default(),
+                data: std::cell::UnsafeCell::new(value),
+            }
+        }
+    }
+
+    impl<R, T> Mutex<R, T> {
+        // Unlike `std::sync::Mutex::lock`, it returns the guard directly.
+        pub fn lock(&self) -> MutexGuard<'_, R, T> {
+            MutexGuard { mutex: self }
+        }
+    }
+
+    impl<R, T> Drop for MutexGuard<'_, R, T> {
+        fn drop(&mut self) {}
+    }
+}
+
+mod parking_lot {
+    #[derive(Default)]
+    pub struct RawMutex;
+
+    pub type Mutex<T> = crate::lock_api::Mutex<RawMutex, T>;
+}
+
+fn main() {
+    let data = parking_lot::Mutex::new(0);
+    let _d1 = data.lock();
+    let _d2 = data.lock(); // cannot lock, since d1 is still active
+}
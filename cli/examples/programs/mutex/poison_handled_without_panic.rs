@@ -0,0 +1,18 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data: Arc<Mutex<Vec<i32>>> = Arc::new(Mutex::new(Vec::new()));
+    let worker_data = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        let _values = worker_data.lock().unwrap(); // does not panic, the mutex is never poisoned
+    });
+    let _result = handle.join();
+    match data.lock() {
+        Ok(_values) => {}
+        Err(_poisoned) => {
+            // Deadlock, but only reachable if the mutex is poisoned
+            let _values = data.lock();
+        }
+    };
+}
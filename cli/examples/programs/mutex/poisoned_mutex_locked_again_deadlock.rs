@@ -0,0 +1,19 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data: Arc<Mutex<Vec<i32>>> = Arc::new(Mutex::new(Vec::new()));
+    let worker_data = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        let values = worker_data.lock().unwrap();
+        let _first = values[0]; // panics, since the vector is empty. The mutex is poisoned
+    });
+    let _result = handle.join();
+    match data.lock() {
+        Ok(_values) => {}
+        Err(_poisoned) => {
+            // The guard inside the `PoisonError` still holds the lock
+            let _values = data.lock();
+        }
+    };
+}
@@ -0,0 +1,10 @@
+//! The address of a mutex is obtained by transmuting a reference to it, which requires `unsafe` code.
+//! The mutex is only locked once, so the program is deadlock-free
+//! as long as the mutex is not used through the address.
+fn main() {
+    let mutex = std::sync::Mutex::new(0);
+    let address: usize = unsafe { std::mem::transmute(&mutex) };
+
+    let _guard = mutex.lock().unwrap();
+    println!("The mutex is at {address:#x}");
+}
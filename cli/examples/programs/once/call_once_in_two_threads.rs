@@ -0,0 +1,14 @@
+use std::sync::{Arc, Once};
+
+fn main() {
+    let once = Arc::new(Once::new());
+    let once_clone = once.clone();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        once_clone.call_once(|| println!("initialized by the spawned thread"));
+    });
+
+    // Only one of the threads runs the initialization, the other waits or skips it
+    once.call_once(|| println!("initialized by the main thread"));
+    let _join_result = thread_join_handle.join();
+}
@@ -0,0 +1,14 @@
+use std::sync::{Mutex, Once};
+
+fn main() {
+    let once = Once::new();
+    let mutex = Mutex::new(0);
+
+    // The initialization runs the first time and is skipped the second time
+    once.call_once(|| println!("initialized"));
+    once.call_once(|| println!("never printed"));
+
+    // The deadlock after the initialization must still be found
+    let _guard = mutex.lock().unwrap();
+    let _second_guard = mutex.lock().unwrap();
+}
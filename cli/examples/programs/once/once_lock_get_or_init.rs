@@ -0,0 +1,13 @@
+use std::sync::{Arc, OnceLock};
+
+fn main() {
+    let cell = Arc::new(OnceLock::new());
+    let cell_clone = cell.clone();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        let _value = cell_clone.get_or_init(|| 1);
+    });
+
+    let _value = cell.get_or_init(|| 2);
+    let _join_result = thread_join_handle.join();
+}
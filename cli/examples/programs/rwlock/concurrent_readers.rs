@@ -0,0 +1,12 @@
+fn main() {
+    let original_data = std::sync::Arc::new(std::sync::RwLock::new(0));
+    let copy_data = original_data.clone();
+
+    let thread_join_handle = std::thread::spawn(move || {
+        let _data = copy_data.read();
+    });
+
+    // Both threads may hold a read lock at the same time
+    let _data = original_data.read();
+    let _join_result = thread_join_handle.join();
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let data = std::sync::RwLock::new(0);
+    let reader = data.read();
+    // Drop the read lock manually before the write
+    std::mem::drop(reader);
+    let _writer = data.write();
+}
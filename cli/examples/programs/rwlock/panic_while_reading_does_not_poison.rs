@@ -0,0 +1,20 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+fn main() {
+    let data: Arc<RwLock<Vec<i32>>> = Arc::new(RwLock::new(Vec::new()));
+    let worker_data = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        let values = worker_data.read().unwrap();
+        let _first = values[0]; // panics, but a read guard does not poison the lock
+    });
+    let _result = handle.join();
+    match data.write() {
+        // The read guard was released while unwinding, so the lock can be written
+        Ok(_values) => {}
+        Err(_poisoned) => {
+            // Deadlock, but only reachable if the lock is poisoned
+            let _values = data.read();
+        }
+    };
+}
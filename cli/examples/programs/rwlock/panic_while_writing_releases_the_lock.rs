@@ -0,0 +1,13 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+fn main() {
+    let data: Arc<RwLock<Vec<i32>>> = Arc::new(RwLock::new(Vec::new()));
+    let worker_data = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        let values = worker_data.write().unwrap();
+        let _first = values[0]; // panics, since the vector is empty. The guard is dropped while unwinding
+    });
+    let _result = handle.join();
+    let _values = data.read(); // can lock, since the lock was released (and poisoned) by the panic
+}
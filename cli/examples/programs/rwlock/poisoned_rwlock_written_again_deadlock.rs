@@ -0,0 +1,19 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+fn main() {
+    let data: Arc<RwLock<Vec<i32>>> = Arc::new(RwLock::new(Vec::new()));
+    let worker_data = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        let values = worker_data.write().unwrap();
+        let _first = values[0]; // panics, since the vector is empty. The lock is poisoned
+    });
+    let _result = handle.join();
+    match data.write() {
+        Ok(_values) => {}
+        Err(_poisoned) => {
+            // The guard inside the `PoisonError` still holds the lock
+            let _values = data.read();
+        }
+    };
+}
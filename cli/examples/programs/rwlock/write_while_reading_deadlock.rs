@@ -0,0 +1,5 @@
+fn main() {
+    let data = std::sync::RwLock::new(0);
+    let _reader = data.read();
+    let _writer = data.write(); // cannot lock, since the reader is still active
+}
@@ -0,0 +1,15 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn make_task() -> Box<dyn FnOnce() + Send> {
+    let data = Arc::new(Mutex::new(0));
+    Box::new(move || {
+        let _d1 = data.lock().unwrap();
+        let _d2 = data.lock().unwrap(); // cannot lock, since d1 is still active
+    })
+}
+
+fn main() {
+    let handle = thread::spawn(make_task());
+    handle.join().unwrap();
+}
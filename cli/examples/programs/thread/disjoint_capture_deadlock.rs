@@ -0,0 +1,21 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+struct State {
+    data: Arc<Mutex<i32>>,
+    step: i32,
+}
+
+fn main() {
+    let state = State {
+        data: Arc::new(Mutex::new(0)),
+        step: 1,
+    };
+    let data = Arc::clone(&state.data);
+    let _guard = data.lock().unwrap();
+    // In edition 2021, the closure captures the fields `state.data` and `state.step`, not `state`.
+    let handle = thread::spawn(move || {
+        *state.data.lock().unwrap() += state.step;
+    });
+    handle.join().unwrap(); // cannot join, since the thread waits for the guard held here
+}
@@ -0,0 +1,17 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data = Arc::new(Mutex::new(0));
+    let _guard = data.lock().unwrap();
+
+    let outer_data = Arc::clone(&data);
+    let outer = thread::spawn(move || {
+        let inner_data = Arc::clone(&outer_data);
+        let inner = thread::spawn(move || {
+            let _inner_guard = inner_data.lock().unwrap(); // cannot lock, since main holds the lock
+        });
+        inner.join().unwrap();
+    });
+    outer.join().unwrap(); // waits forever for the inner thread
+}
@@ -0,0 +1,13 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data: Arc<Mutex<Vec<i32>>> = Arc::new(Mutex::new(Vec::new()));
+    let worker_data = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        let values = worker_data.lock().unwrap();
+        let _first = values[0]; // panics, since the vector is empty. The guard is dropped while unwinding
+    });
+    let _result = handle.join();
+    let _values = data.lock(); // can lock, since the mutex was released (and poisoned) by the panic
+}
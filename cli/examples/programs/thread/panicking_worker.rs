@@ -0,0 +1,10 @@
+use std::thread;
+
+fn main() {
+    let handle = thread::spawn(|| {
+        let values: Vec<i32> = Vec::new();
+        let _first = values[0]; // panics, since the vector is empty
+    });
+    let result = handle.join();
+    assert!(result.is_err());
+}
@@ -0,0 +1,15 @@
+use std::thread;
+
+struct SendPtr(*const i32);
+
+unsafe impl Send for SendPtr {}
+
+fn main() {
+    static VALUE: i32 = 42;
+    let pointer = SendPtr(&VALUE);
+    let handle = thread::spawn(move || {
+        let pointer = pointer;
+        let _value = unsafe { *pointer.0 };
+    });
+    handle.join().unwrap();
+}
@@ -0,0 +1,15 @@
+//! Several workers running the same code are spawned in a loop.
+//! Every worker locks the shared mutex and releases it, so the program is deadlock-free.
+fn main() {
+    let data = std::sync::Arc::new(std::sync::Mutex::new(0));
+
+    for _ in 0..4 {
+        let data = std::sync::Arc::clone(&data);
+        std::thread::spawn(move || {
+            let mut guard = data.lock().unwrap();
+            *guard += 1;
+        });
+    }
+
+    let _guard = data.lock().unwrap();
+}
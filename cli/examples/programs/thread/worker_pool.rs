@@ -0,0 +1,15 @@
+//! A pool of workers spawned in a loop takes the jobs from a queue protected by a mutex.
+//! Every worker locks the queue in a loop until it is empty, so the program is deadlock-free.
+fn main() {
+    let jobs = std::sync::Arc::new(std::sync::Mutex::new(vec![1, 2, 3, 4]));
+
+    for _ in 0..4 {
+        let jobs = std::sync::Arc::clone(&jobs);
+        std::thread::spawn(move || loop {
+            let job = jobs.lock().unwrap().pop();
+            if job.is_none() {
+                break;
+            }
+        });
+    }
+}
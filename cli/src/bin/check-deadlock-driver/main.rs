@@ -0,0 +1,131 @@
+// This feature gate is necessary to access the internal crates of the compiler.
+// It has existed for a long time and since the compiler internals will never be stabilized,
+// the situation will probably stay like this.
+// <https://doc.rust-lang.org/unstable-book/language-features/rustc-private.html>
+#![feature(rustc_private)]
+
+//! A `rustc`-compatible driver that translates every crate with a `main` function to a Petri net
+//! as a side effect of the compilation.
+//!
+//! It can be used as the compiler in an existing build:
+//! `RUSTC=cargo-check-deadlock-driver cargo build` or
+//! `RUSTC_WORKSPACE_WRAPPER=cargo-check-deadlock-driver cargo build`.
+//!
+//! The output is configured through environment variables:
+//! - `CARGO_CHECK_DEADLOCK_OUTPUT_FOLDER`: The folder for the output files. Defaults to the current folder.
+//! - `CARGO_CHECK_DEADLOCK_FORMATS`: Comma-separated list of output formats. Defaults to `lola`.
+//! - `CARGO_CHECK_DEADLOCK_ENTRY_FUNCTION`: The path of the function where the translation starts,
+//!   e.g. `my_mod::worker`. Defaults to the `main` function.
+//!
+//! The output files are named after the crate, e.g. `my_crate.lola`.
+
+#[path = "../check-deadlock/output_format.rs"]
+mod output_format;
+
+use clap::ValueEnum;
+use log::info;
+use std::path::{Path, PathBuf};
+
+use cargo_check_deadlock_core::Translation;
+use cargo_check_deadlock_translator::driver::{run_with_translation, run_without_translation};
+use cargo_check_deadlock_translator::{default_cache_folder, Options};
+use output_format::OutputFormat;
+
+/// Environment variable that sets the folder for the output files.
+const OUTPUT_FOLDER_VAR: &str = "CARGO_CHECK_DEADLOCK_OUTPUT_FOLDER";
+/// Environment variable that sets the comma-separated list of output formats.
+const FORMATS_VAR: &str = "CARGO_CHECK_DEADLOCK_FORMATS";
+/// Environment variable that sets the path of the function where the translation starts.
+const ENTRY_FUNCTION_VAR: &str = "CARGO_CHECK_DEADLOCK_ENTRY_FUNCTION";
+
+fn main() {
+    env_logger::init();
+
+    let mut args: Vec<String> = std::env::args().collect();
+    // As `RUSTC_WORKSPACE_WRAPPER`, Cargo passes the path to `rustc` as the first argument.
+    if args.get(1).is_some_and(|arg| {
+        Path::new(arg)
+            .file_stem()
+            .is_some_and(|stem| stem == "rustc")
+    }) {
+        args.remove(1);
+    }
+
+    let result = if should_translate() {
+        let output_folder =
+            std::env::var(OUTPUT_FOLDER_VAR).map_or_else(|_| PathBuf::from("."), PathBuf::from);
+        let formats = match parse_formats() {
+            Ok(formats) => formats,
+            Err(err_str) => {
+                eprintln!("{err_str}");
+                std::process::exit(1);
+            }
+        };
+        // The crate root is the only source file passed to the compiler.
+        let source_path = args
+            .iter()
+            .find(|arg| {
+                Path::new(arg)
+                    .extension()
+                    .is_some_and(|extension| extension == "rs")
+            })
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        let layout_cache =
+            default_cache_folder().map(|folder| folder.join(output_format::LAYOUT_CACHE_SUBFOLDER));
+
+        let options = Options {
+            entry_function: std::env::var(ENTRY_FUNCTION_VAR).ok(),
+            ..Options::default()
+        };
+
+        run_with_translation(
+            args,
+            options,
+            |crate_name: &str, translation: Translation| {
+                for format in &formats {
+                    if let Err(err) = format.create_output_file(
+                        &translation,
+                        &source_path,
+                        crate_name,
+                        &output_folder,
+                        layout_cache.as_deref(),
+                    ) {
+                        eprintln!("Output file for crate {crate_name} could not be created: {err}");
+                    }
+                }
+                info!("Created the output files for crate {crate_name}");
+            },
+        )
+    } else {
+        run_without_translation(args)
+    };
+
+    if let Err(err_str) = result {
+        eprintln!("{err_str}");
+        std::process::exit(1);
+    }
+}
+
+/// Checks whether the crate being compiled should be translated.
+/// Inside a Cargo build, only the packages of the workspace are translated, not their dependencies.
+/// Outside of Cargo, every crate is translated.
+fn should_translate() -> bool {
+    std::env::var_os("CARGO").is_none() || std::env::var_os("CARGO_PRIMARY_PACKAGE").is_some()
+}
+
+/// Parses the output formats from the environment variable.
+/// Defaults to the `LoLA` format if the variable is not set.
+fn parse_formats() -> Result<Vec<OutputFormat>, String> {
+    let Ok(formats) = std::env::var(FORMATS_VAR) else {
+        return Ok(vec![OutputFormat::Lola]);
+    };
+    formats
+        .split(',')
+        .map(|format| {
+            OutputFormat::from_str(format.trim(), true)
+                .map_err(|_| format!("Invalid output format in {FORMATS_VAR}: {format}"))
+        })
+        .collect()
+}
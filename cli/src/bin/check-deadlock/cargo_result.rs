@@ -0,0 +1,37 @@
+/// Possible outcomes of running the `cargo check-deadlock` command.
+pub enum CargoResult {
+    /// A successful translation containing the result of the deadlock analysis
+    DeadlockAnalysis(String),
+    /// A successful translation with deadlocks that are not recorded in the baseline
+    NewFindings(String),
+    /// A successful translation without deadlock analysis
+    SimpleTranslation,
+    /// The plan of the translation computed by a dry run
+    DryRun(String),
+    /// The report of the lint mode without findings
+    Lint(String),
+    /// The report of the lint mode with at least one finding
+    LintFindings(String),
+    /// The source file was not found
+    SourceFileNotFound(String),
+    /// The output folder was not found
+    OutputFolderNotFound(String),
+    /// A plugin could not be loaded
+    PluginError(String),
+    /// The translation failed
+    TranslationError(String),
+    /// The translated net violates a structural invariant
+    SelfCheckError(String),
+    /// The net cannot reproduce an execution of the program
+    CrossCheckError(String),
+    /// Failure when writing the output files
+    OutputGenerationError(String),
+    /// Failure when reading or writing the baseline
+    BaselineError(String),
+    /// Failure when drawing the terminal user interface or reading the keys
+    TerminalError(String),
+    /// The model checker could not be run or did not give an answer
+    ModelCheckerError(String),
+    /// The translation was interrupted and only the partial outputs were written
+    Interrupted(String),
+}
@@ -0,0 +1,1179 @@
+use std::collections::BTreeMap;
+
+use clap::{ArgGroup, Parser, ValueEnum};
+use log::{info, warn};
+
+use crate::cargo_result::CargoResult;
+use crate::cli::Subcommand;
+use crate::crate_root::find_crate_root;
+use crate::output_format::{OutputFormat, LAYOUT_CACHE_SUBFOLDER};
+
+use cargo_check_deadlock_core::analysis::reachability::{
+    find_deadlock_within_bounds, DeadlockResult,
+};
+use cargo_check_deadlock_core::analysis::self_check;
+use cargo_check_deadlock_core::data_structures::dot_style::DotStyle;
+use cargo_check_deadlock_core::data_structures::identifiers::IdentifierFormat;
+use cargo_check_deadlock_core::data_structures::profiler::EXPORT_FRAME;
+use cargo_check_deadlock_core::model_checker::lola::{self, MarkingPattern};
+use cargo_check_deadlock_core::naming::scheme::{HashedShort, NamingScheme, NumericIds};
+use cargo_check_deadlock_core::passes::{CollapseGotoChains, PassManager, Reduce};
+use cargo_check_deadlock_core::report::baseline::Baseline;
+use cargo_check_deadlock_core::report::findings::{
+    deadlock_finding, sort_by_severity, suppressed_places, unverified_assumptions, Finding,
+    FindingKind,
+};
+use cargo_check_deadlock_core::report::interactions::InteractionMatrix;
+use cargo_check_deadlock_core::report::localization::localize_deadlock;
+use cargo_check_deadlock_core::report::snippets::{finding_labels, render_snippet};
+use cargo_check_deadlock_core::report::trace::MarkingTrace;
+use cargo_check_deadlock_core::{NetData, PetriNet, Translation};
+use cargo_check_deadlock_translator::{
+    default_cache_folder, default_lock_libraries, interruption_requested, load_plugin,
+    request_interruption, CondvarWakeup, Granularity, LockLibrary, Options, DEFAULT_MAX_CALL_DEPTH,
+    DEFAULT_THREAD_INSTANCES,
+};
+
+/// Extension of the file with the profile in the collapsed stack format.
+const PROFILE_EXTENSION: &str = "folded";
+
+/// Extension of the file with the reduction rules applied to the net.
+const REDUCTION_LOG_EXTENSION: &str = "reductions.txt";
+
+/// Extension of the file with the trace to a deadlock in the witness format of the Model Checking Contest.
+const TRACE_MCC_EXTENSION: &str = "trace.xml";
+
+/// Extension of the file with the markings of the trace to a deadlock in CSV format.
+const TRACE_CSV_EXTENSION: &str = "trace.csv";
+
+/// Suffix appended to the filename of the output files of an interrupted translation, e.g. `net-partial.lola`.
+const PARTIAL_SUFFIX: &str = "-partial";
+
+/// Number of bytes in a megabyte, used to convert the `--max-memory` argument.
+const BYTES_PER_MEGABYTE: usize = 1024 * 1024;
+
+/// Maximum number of deadlocks of each category (new or known) listed in the result of the analysis.
+const MAX_FINDINGS: usize = 20;
+
+/// Maximum depth of nested function calls translated with `--preset small-scope`.
+const SMALL_SCOPE_MAX_CALL_DEPTH: usize = 8;
+
+/// Number of instances of a thread spawned in a loop with `--preset small-scope`.
+/// Two instances still find the deadlocks between workers that run the same code.
+const SMALL_SCOPE_THREAD_INSTANCES: usize = 2;
+
+/// Capacity of the buffer of the unbounded channels with `--preset small-scope`.
+const SMALL_SCOPE_CHANNEL_BOUND: usize = 2;
+
+/// Number of executions of the header of every loop with `--preset small-scope`,
+/// e.g. two iterations of a `while` loop and the check of the condition that leaves it.
+const SMALL_SCOPE_LOOP_BOUND: usize = 3;
+
+/// Maximum number of markings visited by the internal search for deadlocks with `--preset small-scope`.
+const SMALL_SCOPE_MAX_STATES: usize = 100_000;
+
+/// Dialects of PNML that can be selected with `--pnml-dialect`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum PnmlDialectArg {
+    /// Strictly standard PNML
+    Standard,
+    /// PNML accepted by the editor `WoPeD`
+    Woped,
+    /// PNML accepted by the editor Snoopy
+    Snoopy,
+    /// Standard PNML with the control flow of every spawned thread in a nested page
+    Pages,
+    /// Standard PNML with the positions of the nodes computed by Graphviz
+    Layout,
+}
+
+/// Wakeup policies of `Condvar::notify_one` that can be selected with `--condvar-wakeup`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum CondvarWakeupArg {
+    /// Any waiting thread may be woken
+    Any,
+    /// The waiting threads are woken in the order they started waiting
+    Fifo,
+}
+
+/// Granularities of the translation that can be selected with `--granularity`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum GranularityArg {
+    /// Every statement is a transition
+    Statement,
+    /// Every basic block is a place
+    Block,
+    /// Only the branches, calls and synchronization of every function are modelled
+    Function,
+}
+
+/// Naming schemes for the identifiers in the `LoLA` and PNML files that can be selected with `--naming`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum NamingArg {
+    /// The labels of the net, e.g. `std_sync_Mutex_T_lock_0_CALL`
+    Verbose,
+    /// The labels truncated to 32 characters and ending with a hash of the full label
+    Hashed,
+    /// `P<index>` for the places and `T<index>` for the transitions
+    Numeric,
+}
+
+impl NamingArg {
+    /// Returns the naming scheme selected, if it is not the labels themselves.
+    fn scheme(self) -> Option<Box<dyn NamingScheme>> {
+        match self {
+            Self::Verbose => None,
+            Self::Hashed => Some(Box::new(HashedShort::default())),
+            Self::Numeric => Some(Box::new(NumericIds)),
+        }
+    }
+}
+
+/// Presets that configure several options at once, selected with `--preset`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum PresetArg {
+    /// Small bounds for the threads, the calls, the loops and the channels,
+    /// followed by an exhaustive search for deadlocks without `LoLA`
+    SmallScope,
+}
+
+/// Convert a Rust source code file into a Petri net and export
+/// the resulting net in one of the supported formats.
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(group(ArgGroup::new("reduction").args(["reduce", "collapse_gotos"])))]
+pub struct Args {
+    /// The path to the Rust source code file to read.
+    /// It may also be a folder with the crate root, i.e. `src/main.rs`, `main.rs`, `src/lib.rs` or `lib.rs`.
+    /// The modules declared with `mod foo;` in sibling files and folders are translated too.
+    #[arg(required = true)]
+    path: Option<std::path::PathBuf>,
+
+    /// Filename for the resulting net.
+    /// The output files contain this filename followed by an extension depending on the format.
+    #[arg(long, default_value = "net")]
+    filename: String,
+
+    /// The path to a valid folder where the output files should be created.
+    /// If not specified, the current working directory is used.
+    #[arg(long, default_value = ".")]
+    output_folder: std::path::PathBuf,
+
+    /// If set, outputs the Petri net in DOT format.
+    #[arg(long)]
+    dot: bool,
+
+    /// If set, the DOT output groups the places of the basic blocks of every function
+    /// and the transitions that leave them in a cluster with its own background color.
+    /// It also applies to the SVG image.
+    #[arg(long)]
+    dot_clusters: bool,
+
+    /// An option of the style of the DOT output given as `key=value`. Can be given several times.
+    /// The keys are `rankdir` (`TB`, `LR`, `BT` or `RL`), `highlight` (`true` or `false`) for `PROGRAM_PANIC`
+    /// and the places of the mutexes, `sync-transitions` (`true` or `false`) for the transitions
+    /// connected to a synchronization primitive, `panic-color`, `mutex-color` and `sync-color` (a Graphviz color)
+    /// and `clusters` (`true` or `false`). It also applies to the SVG image.
+    #[arg(long = "dot-option", value_name = "KEY=VALUE", value_parser = parse_dot_option)]
+    dot_options: Vec<String>,
+
+    /// If set, outputs the Petri net as an SVG image drawn by Graphviz, which must be installed.
+    /// The layout is cached, so drawing a net with the same places, transitions and arcs again is fast.
+    #[arg(long)]
+    svg: bool,
+
+    /// If set, outputs the Petri net as a Mermaid flowchart,
+    /// which can be pasted into Markdown documentation and GitHub issues.
+    #[arg(long)]
+    mermaid: bool,
+
+    /// If set, outputs the Petri net as a standalone LaTeX document with a TikZ picture,
+    /// with one column per function, for inclusion in papers and theses.
+    #[arg(long)]
+    tikz: bool,
+
+    /// If set, outputs the Petri net in PNML format.
+    #[arg(long)]
+    pnml: bool,
+
+    /// The dialect of the PNML output. The editors WoPeD and Snoopy reject strictly standard files
+    /// in different ways, so the output can be adapted to them. Implies `--pnml`.
+    #[arg(long, value_enum, value_name = "DIALECT")]
+    pnml_dialect: Option<PnmlDialectArg>,
+
+    /// If set, the DOT and the standard PNML outputs include the span of the source code
+    /// that produced every place and transition: as a tooltip in DOT, which Graphviz keeps in SVG images,
+    /// and as tool-specific data in PNML. The JSON output always includes the spans.
+    #[arg(long)]
+    spans: bool,
+
+    /// The naming scheme for the identifiers of the places and transitions in the `LoLA` and PNML files.
+    /// `LoLA` and some PNML tools reject the very long labels of the generic functions of the standard library.
+    /// With `hashed` or `numeric`, a JSON table from the identifiers to the labels is written next to the net.
+    /// The results of the analysis always use the labels.
+    #[arg(long, value_enum, value_name = "SCHEME", default_value_t = NamingArg::Verbose)]
+    naming: NamingArg,
+
+    /// If set, outputs the Petri net in the format of the APT toolkit.
+    #[arg(long)]
+    apt: bool,
+
+    /// If set, outputs the Petri net in the `.g` format of Petrify.
+    /// Nets with weighted arcs, e.g. from read-write locks, cannot be written in this format.
+    #[arg(long)]
+    petrify: bool,
+
+    /// If set, outputs the Petri net in the `.net` format of the TINA toolbox.
+    #[arg(long)]
+    tina: bool,
+
+    /// If set, outputs the Petri net in the `.net` and `.def` formats of GreatSPN,
+    /// with every transition exponential with rate 1 for the stochastic and performance analyses.
+    #[arg(long)]
+    greatspn: bool,
+
+    /// If set, outputs a folder in the layout of the Model Checking Contest with the net in PNML format
+    /// (`model.pnml`) and the deadlock formula and the property templates as XML property sets.
+    #[arg(long)]
+    mcc_bundle: bool,
+
+    /// If set, outputs the Petri net in JSON format.
+    /// The JSON file can be analyzed without the compiler, e.g. in the browser playground.
+    #[arg(long)]
+    json: bool,
+
+    /// If set, outputs a copy of the source code where every line is preceded by comments
+    /// listing the transitions and the synchronization primitives it produced.
+    #[arg(long)]
+    annotated_source: bool,
+
+    /// If set, outputs a report of the percentage of user-defined functions and lines per module
+    /// that were translated instead of abstracted as foreign function calls.
+    #[arg(long)]
+    coverage: bool,
+
+    /// If set, prints a table of the synchronization primitives used by every thread
+    /// in the order of first use and outputs it in JSON format.
+    /// Locks used in opposite order by two threads are listed below the table.
+    #[arg(long)]
+    interactions: bool,
+
+    /// If set, outputs a JSON file that maps every basic block of every translated function
+    /// to the places and transitions of the net, the kind of its terminator and its span.
+    /// The mapping refers to the net before the reductions.
+    #[arg(long)]
+    mapping: bool,
+
+    /// If set, outputs a JSON file that maps the label of every place and transition
+    /// to the function, the file, the line and the MIR statement or terminator that produced it.
+    /// This keeps the labels of the net short but explainable.
+    #[arg(long)]
+    label_map: bool,
+
+    /// If set, the file in LoLA format starts with comments that map the places to the source code,
+    /// and the deadlock formula is written to a file with comments that explain it.
+    /// This makes the files self-explanatory when they are shared.
+    #[arg(long)]
+    lola_comments: bool,
+
+    /// If set, outputs companion `.formula` files for `LoLA` with canned properties, one per file:
+    /// deadlock freedom, reachability of `PROGRAM_END`, unreachability of `PROGRAM_PANIC`
+    /// and a lost-signal check for every condition variable.
+    #[arg(long)]
+    formulas: bool,
+
+    /// If set, outputs a checklist of properties for every synchronization primitive,
+    /// e.g. that a mutex is always released, in `LoLA` and SMV syntax.
+    #[arg(long)]
+    property_templates: bool,
+
+    /// The path of the function where the translation starts, e.g. `my_mod::worker`.
+    /// By default, the translation starts at the `main` function.
+    /// If set, the source code is compiled as a library, so it does not need a `main` function.
+    #[arg(long, value_name = "PATH")]
+    entry_function: Option<String>,
+
+    /// If set, the source code is compiled as a library and every exported function is translated.
+    /// Each function starts in its own place, branching off the program start place,
+    /// so a deadlock in any of them is reported.
+    #[arg(long, conflicts_with = "entry_function")]
+    lib: bool,
+
+    /// The path of a function run by a thread spawned at program start, e.g. `ffi::on_event`.
+    /// Can be given several times. This models callbacks invoked by foreign code, which run concurrently
+    /// without a visible call to `std::thread::spawn`. The arguments of the function are not modelled.
+    #[arg(
+        long = "also-run",
+        value_name = "FN_PATH",
+        conflicts_with = "cross_check"
+    )]
+    also_run: Vec<String>,
+
+    /// The edition of Rust used to compile the source code file. Defaults to the edition 2015, like `rustc`.
+    #[arg(long, value_name = "EDITION", value_parser = ["2015", "2018", "2021"])]
+    edition: Option<String>,
+
+    /// A configuration option for conditional compilation, e.g. `feature="std"`. Can be given several times.
+    #[arg(long = "cfg", value_name = "SPEC")]
+    cfgs: Vec<String>,
+
+    /// An additional argument passed as is to the compiler, e.g. `--extern=rand=librand.rlib`
+    /// or `-Zcrate-attr=feature(let_chains)`. Can be given several times.
+    /// This allows to translate source code that uses external crates or feature gates.
+    #[arg(long = "rustc-arg", value_name = "ARG", allow_hyphen_values = true)]
+    rustc_args: Vec<String>,
+
+    /// The maximum depth of nested function calls translated.
+    /// Calls to functions beyond this depth are modelled as calls to foreign functions.
+    #[arg(long, default_value_t = DEFAULT_MAX_CALL_DEPTH)]
+    max_call_depth: usize,
+
+    /// If set, self-recursive calls in tail position are modelled as a loop back to the start of the function.
+    /// By default, they are modelled as a single transition.
+    #[arg(long)]
+    recursion_as_loop: bool,
+
+    /// The maximum number of instances of a thread spawned in a loop that run concurrently.
+    /// The loop iterations after the last instance started do not start a new thread.
+    #[arg(
+        long,
+        default_value_t = DEFAULT_THREAD_INSTANCES,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    thread_instances: usize,
+
+    /// The number of workers of a pool, i.e. of a thread spawned in a loop that waits for work
+    /// in a loop on `Receiver::recv` or `Mutex::lock`. The pool is modelled with this many workers
+    /// regardless of the iterations of the loop. By default, the workers are bounded by `--thread-instances`.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    worker_pool_size: Option<usize>,
+
+    /// If set, a panic in a thread ends it in a separate place and joining it is modelled as a separate transition,
+    /// i.e. `join` returning `Err`. By default, a panic ends the thread like a normal return.
+    #[arg(long)]
+    propagate_thread_panics: bool,
+
+    /// If set, a panic while holding a mutex guard or the write guard of a read-write lock poisons the lock.
+    /// Later calls to `lock`, `read` or `write` on it can then take the `Err(PoisonError)` path,
+    /// e.g. the `Err` arm of a `match` or the panic of `unwrap`. By default, poisoning is not modelled.
+    #[arg(long)]
+    model_poisoning: bool,
+
+    /// If set, the branches on values that depend on the command line arguments or the environment variables
+    /// are tagged as controlled by the environment (`_ENV_FROM_BB<N>_TO_BB<M>`).
+    /// Every branch is modelled either way, but the tag tells the choices of the inputs apart from the scheduling.
+    #[arg(long)]
+    env_branches: bool,
+
+    /// The thread woken by `Condvar::notify_one` when several threads wait on the same condition variable.
+    /// Some starvation findings only appear or disappear under a specific policy.
+    /// Only one call to `wait` is supported per condition variable. With `any`, a single thread waits at a time.
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t = CondvarWakeupArg::Any)]
+    condvar_wakeup: CondvarWakeupArg,
+
+    /// How much of the structure of the program is modelled in the net.
+    /// `statement` adds a transition for every statement, which links more of the net to the source code.
+    /// `function` merges the basic blocks joined by a goto, which shrinks the state space of large programs.
+    /// By default, every basic block is a place like with `block`. The cross-check only supports the default.
+    #[arg(long, value_enum, value_name = "LEVEL", conflicts_with = "cross_check")]
+    granularity: Option<GranularityArg>,
+
+    /// A preset that configures several options at once.
+    /// `small-scope` translates with small bounds for the thread instances, the call depth, the iterations
+    /// of every loop and the capacity of unbounded channels, and then searches all the reachable markings
+    /// for a deadlock with the internal model checker instead of `LoLA`. Most deadlocks already show up
+    /// in such a small scope, so this gives quick feedback. A marking where a thread only waits for a bound,
+    /// e.g. to send on a full channel, is not reported as a deadlock, since the program would continue.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PRESET",
+        conflicts_with_all = ["max_call_depth", "thread_instances", "baseline", "cross_check"]
+    )]
+    preset: Option<PresetArg>,
+
+    /// A library whose `Mutex` and `RwLock` are modelled like the ones in `std::sync`, in addition to `std::sync`
+    /// and `lock_api` (used by `parking_lot`). Given as the path to the module with the lock types followed by
+    /// their generic parameters as they appear in the names of their functions, e.g. `my_crate::sync<T>`.
+    /// Can be given several times.
+    #[arg(long = "lock-library", value_name = "PATH<GENERICS>", value_parser = parse_lock_library)]
+    lock_libraries: Vec<LockLibrary>,
+
+    /// The path to a dynamic library that models additional synchronization primitives.
+    /// Can be given several times. See the `plugin` module of `cargo-check-deadlock-core` for the interface.
+    #[arg(long = "plugin", value_name = "PATH")]
+    plugins: Vec<std::path::PathBuf>,
+
+    /// If set, the classification of the functions of the standard library and the layouts of the net
+    /// are not persisted between runs.
+    /// By default, it is cached per toolchain in `$XDG_CACHE_HOME/cargo-check-deadlock` to speed up the translation.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// If set, outputs the time spent translating each function and in each phase of the translation
+    /// (MIR query, visiting, net insertion, export) in the collapsed stack format.
+    /// The file can be converted to a flamegraph, e.g. with `inferno-flamegraph` or `flamegraph.pl`.
+    #[arg(long)]
+    profile: bool,
+
+    /// The maximum memory in megabytes used to store the arcs of the net during the translation.
+    /// The arcs beyond this limit are stored in a temporary file, which allows to translate
+    /// programs whose nets do not fit in memory at the cost of a slower translation.
+    #[arg(long, value_name = "MEGABYTES")]
+    max_memory: Option<usize>,
+
+    /// The path of a function that is not translated, e.g. `my_mod::parse`. Can be given several times.
+    /// The calls to it are modelled as calls to foreign functions.
+    /// A function whose translation fails is skipped automatically and listed in the coverage report.
+    #[arg(long = "skip-function", value_name = "PATH")]
+    skipped_functions: Vec<String>,
+
+    /// The maximum time in seconds spent translating a single function.
+    /// A function that exceeds it is skipped like with `--skip-function` and the translation starts again.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::builder::RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    function_time_budget: Option<u64>,
+
+    /// If set, the structural invariants of the net are checked after the translation,
+    /// e.g. that every transition has an input and an output place.
+    /// A violation indicates a bug in the translator and is reported with its source code span.
+    #[arg(long)]
+    self_check: bool,
+
+    /// If set, an execution of the deterministic part of the program is replayed on the net after the translation.
+    /// The threads run in a fixed order for a bounded number of steps.
+    /// A step that the net cannot reproduce indicates a bug in the translator and is reported with its source code span.
+    #[arg(long)]
+    cross_check: bool,
+
+    /// If set, the net is reduced before the export by fusing series places and transitions
+    /// and removing self-loops. The reduced net has the same deadlocks but far fewer places and transitions.
+    /// Only the places of the basic blocks are removed, the places of the synchronization primitives are kept.
+    #[arg(long)]
+    reduce: bool,
+
+    /// If set, every chain of goto terminators and statements in the net is collapsed into the transition that enters it
+    /// before the export. This is a lighter simplification than `--reduce`, which already includes it.
+    #[arg(long, conflicts_with = "reduce")]
+    collapse_gotos: bool,
+
+    /// If set, a file with the reduction rules applied to the net is written,
+    /// listing the places and transitions removed by every rule and the properties it preserves.
+    /// It justifies that the verdict on the reduced net holds for the original net.
+    /// It requires `--reduce` or `--collapse-gotos`.
+    #[arg(long, requires = "reduction")]
+    reduction_log: bool,
+
+    /// If set, the reachability analysis to find deadlocks is skipped.
+    #[arg(long, conflicts_with = "trace")]
+    skip_analysis: bool,
+
+    /// If set, the path to the first deadlock found by the analysis is written as a trace of markings
+    /// in the witness format of the Model Checking Contest (`.trace.xml`), referencing the identifiers
+    /// of the PNML output, and as a CSV file with one row per marking (`.trace.csv`).
+    #[arg(long)]
+    trace: bool,
+
+    /// If set, prints the plan of the translation without building the net:
+    /// The entry points, the functions that would be translated, the threads that would be spawned,
+    /// the synchronization primitives detected and an estimate of the size of the net.
+    /// No output files are written and the analysis is skipped.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// The path to a JSON file with the deadlocks acknowledged in a previous run.
+    /// They are still listed in the result, but only new deadlocks make the command fail.
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<std::path::PathBuf>,
+
+    /// If set, the deadlocks found are written to the file given with `--baseline`
+    /// instead of being compared to it.
+    #[arg(long, requires = "baseline")]
+    update_baseline: bool,
+
+    /// Verbosity flag.
+    #[clap(flatten)]
+    verbose: clap_verbosity_flag::Verbosity,
+
+    /// Optional subcommand. If present, the arguments above are not used.
+    #[command(subcommand)]
+    command: Option<Subcommand>,
+}
+
+impl Args {
+    pub fn exec(&self) -> CargoResult {
+        // Initialize an `env_logger` with the clap verbosity flag entered by the user.
+        env_logger::Builder::new()
+            .filter_level(self.verbose.log_level_filter())
+            .init();
+
+        if let Some(command) = &self.command {
+            return command.exec();
+        }
+        let path = self
+            .path
+            .as_ref()
+            .expect("BUG: The path should be required when there is no subcommand");
+
+        // Double check that the file exists before starting the compiler
+        // to generate an error message independent of the rustc output.
+        info!(
+            "Checking that the source code file at {} exists...",
+            path.to_string_lossy()
+        );
+        if !path.exists() {
+            let err_str = format!(
+                "Source code file at {} does not exist",
+                &path.to_string_lossy()
+            );
+            return CargoResult::SourceFileNotFound(err_str);
+        };
+        let crate_root = match find_crate_root(path) {
+            Ok(crate_root) => crate_root,
+            Err(err_str) => return CargoResult::SourceFileNotFound(err_str),
+        };
+        let path = &crate_root.path;
+        info!("Translating the crate with root {}", path.to_string_lossy());
+
+        // Double check that the output folder exists before starting the compiler
+        // to generate an error message as soon as possible.
+        info!(
+            "Checking that the output folder at {} exists...",
+            self.output_folder.to_string_lossy()
+        );
+        if !self.output_folder.exists() {
+            let err_str = format!(
+                "Output folder at {} does not exist",
+                &self.output_folder.to_string_lossy()
+            );
+            return CargoResult::OutputFolderNotFound(err_str);
+        };
+
+        let mut plugins = Vec::new();
+        for plugin_path in &self.plugins {
+            info!("Loading the plugin at {}...", plugin_path.to_string_lossy());
+            match load_plugin(plugin_path) {
+                Ok(plugin) => plugins.push(plugin),
+                Err(err_str) => return CargoResult::PluginError(err_str),
+            }
+        }
+
+        info!("Starting the translation...");
+        let options = Options {
+            entry_function: self.entry_function.clone(),
+            // A library crate without an entry function is translated like with `--lib`.
+            library: self.lib || (crate_root.is_library && self.entry_function.is_none()),
+            max_call_depth: match self.preset {
+                Some(PresetArg::SmallScope) => SMALL_SCOPE_MAX_CALL_DEPTH,
+                None => self.max_call_depth,
+            },
+            recursion_as_loop: self.recursion_as_loop,
+            thread_instances: match self.preset {
+                Some(PresetArg::SmallScope) => SMALL_SCOPE_THREAD_INSTANCES,
+                None => self.thread_instances,
+            },
+            worker_pool_size: self.worker_pool_size,
+            propagate_thread_panics: self.propagate_thread_panics,
+            model_poisoning: self.model_poisoning,
+            condvar_wakeup: match self.condvar_wakeup {
+                CondvarWakeupArg::Any => CondvarWakeup::Any,
+                CondvarWakeupArg::Fifo => CondvarWakeup::Fifo,
+            },
+            granularity: match self.granularity {
+                Some(GranularityArg::Statement) => Granularity::Statement,
+                Some(GranularityArg::Block) | None => Granularity::Block,
+                Some(GranularityArg::Function) => Granularity::Function,
+            },
+            channel_bound: self
+                .preset
+                .map(|PresetArg::SmallScope| SMALL_SCOPE_CHANNEL_BOUND),
+            loop_bound: self
+                .preset
+                .map(|PresetArg::SmallScope| SMALL_SCOPE_LOOP_BOUND),
+            lock_libraries: default_lock_libraries()
+                .into_iter()
+                .chain(self.lock_libraries.iter().cloned())
+                .collect(),
+            plugins,
+            classification_cache: if self.no_cache {
+                None
+            } else {
+                default_cache_folder()
+            },
+            profile: self.profile,
+            max_arc_memory: self
+                .max_memory
+                .map(|megabytes| megabytes.saturating_mul(BYTES_PER_MEGABYTE)),
+            rustc_args: self.rustc_args(),
+            cross_check: self.cross_check,
+            block_mapping: self.mapping,
+            label_map: self.label_map,
+            environment_branches: self.env_branches,
+            skipped_functions: self.skipped_functions.clone(),
+            also_run: self.also_run.clone(),
+            function_time_budget: self
+                .function_time_budget
+                .map(std::time::Duration::from_secs),
+        };
+        if self.dry_run {
+            return match cargo_check_deadlock_translator::plan(path.clone(), options) {
+                Ok(plan) => CargoResult::DryRun(plan.to_string()),
+                Err(err_str) => CargoResult::TranslationError(err_str.to_string()),
+            };
+        }
+        install_interruption_handler();
+        let mut translation = match cargo_check_deadlock_translator::run(path.clone(), options) {
+            Ok(translation) => translation,
+            Err(err_str) => {
+                return CargoResult::TranslationError(err_str.to_string());
+            }
+        };
+        info!(
+            "Found synchronization primitives:\n{}",
+            translation.registry
+        );
+        for collision in translation.net.label_collisions() {
+            warn!("{collision}");
+        }
+
+        if self.self_check {
+            info!("Checking the structural invariants of the net...");
+            if let Err(err_str) = self_check::check_structure(&translation.net.data()) {
+                return CargoResult::SelfCheckError(err_str);
+            }
+        }
+        if let Some(report) = &translation.cross_check {
+            if report.is_mismatch() {
+                return CargoResult::CrossCheckError(report.to_string());
+            }
+            println!("{report}");
+        }
+        let mut passes = PassManager::new();
+        if self.reduce {
+            passes.add(Box::new(Reduce));
+        } else if self.collapse_gotos {
+            passes.add(Box::new(CollapseGotoChains));
+        }
+        let mut reduction_log = None;
+        if !passes.is_empty() {
+            let data = translation.net.data();
+            let mut reduced = data.clone();
+            let log = passes
+                .run_on_data_with_log(&mut reduced)
+                .expect("BUG: The reductions should apply to every translated net");
+            info!(
+                "Reduced the net from {} places and {} transitions to {} places and {} transitions",
+                data.places.len(),
+                data.transitions.len(),
+                reduced.places.len(),
+                reduced.transitions.len()
+            );
+            for (rule, count) in log.rule_counts() {
+                info!("Applied the {rule} {count} times");
+            }
+            translation.net =
+                PetriNet::from_data(&reduced).expect("BUG: The reduced net should be valid");
+            reduction_log = Some(log);
+        }
+
+        translation.net.set_naming_scheme(self.naming.scheme());
+        translation.net.set_dot_style(self.dot_style());
+
+        let mut formats = Vec::new();
+        if self.dot && self.spans {
+            formats.push(OutputFormat::DotSpans);
+        } else if self.dot {
+            formats.push(OutputFormat::Dot);
+        }
+        if self.svg {
+            formats.push(OutputFormat::Svg);
+        }
+        if self.mermaid {
+            formats.push(OutputFormat::Mermaid);
+        }
+        if self.tikz {
+            formats.push(OutputFormat::Tikz);
+        }
+        match self.pnml_dialect {
+            Some(PnmlDialectArg::Woped) => formats.push(OutputFormat::PnmlWoped),
+            Some(PnmlDialectArg::Snoopy) => formats.push(OutputFormat::PnmlSnoopy),
+            Some(PnmlDialectArg::Standard) if self.spans => formats.push(OutputFormat::PnmlSpans),
+            Some(PnmlDialectArg::Standard) => formats.push(OutputFormat::Pnml),
+            Some(PnmlDialectArg::Pages) => formats.push(OutputFormat::PnmlPages),
+            Some(PnmlDialectArg::Layout) => formats.push(OutputFormat::PnmlLayout),
+            None if self.pnml && self.spans => formats.push(OutputFormat::PnmlSpans),
+            None if self.pnml => formats.push(OutputFormat::Pnml),
+            None => {}
+        }
+        if self.apt {
+            formats.push(OutputFormat::Apt);
+        }
+        if self.petrify {
+            formats.push(OutputFormat::Petrify);
+        }
+        if self.tina {
+            formats.push(OutputFormat::Tina);
+        }
+        if self.greatspn {
+            formats.push(OutputFormat::GreatSpn);
+            formats.push(OutputFormat::GreatSpnDef);
+        }
+        if self.mcc_bundle {
+            formats.push(OutputFormat::MccBundle);
+        }
+        if self.json {
+            formats.push(OutputFormat::Json);
+        }
+        if self.annotated_source {
+            formats.push(OutputFormat::AnnotatedSource);
+        }
+        if self.coverage {
+            formats.push(OutputFormat::Coverage);
+        }
+        if self.interactions {
+            formats.push(OutputFormat::Interactions);
+        }
+        if self.mapping {
+            formats.push(OutputFormat::BlockMapping);
+        }
+        if self.label_map {
+            formats.push(OutputFormat::LabelMap);
+        }
+        if self.naming != NamingArg::Verbose {
+            formats.push(OutputFormat::NameTable);
+        }
+        if self.property_templates {
+            formats.push(OutputFormat::LolaProperties);
+            formats.push(OutputFormat::SmvProperties);
+        }
+        if self.formulas {
+            formats.push(OutputFormat::CompanionFormulas);
+        }
+        // Always generate the file in LoLA format for the deadlock analysis
+        if self.lola_comments {
+            formats.push(OutputFormat::AnnotatedLola);
+            formats.push(OutputFormat::Formula);
+        } else {
+            formats.push(OutputFormat::Lola);
+        }
+
+        let layout_cache = if self.no_cache {
+            None
+        } else {
+            default_cache_folder().map(|folder| folder.join(LAYOUT_CACHE_SUBFOLDER))
+        };
+        // The output files of an interrupted translation are marked as partial.
+        let filename = if translation.interrupted {
+            format!("{}{PARTIAL_SUFFIX}", self.filename)
+        } else {
+            self.filename.clone()
+        };
+        let mut profile = std::mem::take(&mut translation.profile);
+        profile.enter(EXPORT_FRAME);
+        for format in formats {
+            profile.enter(&format.to_string());
+            if let Err(err_str) = format.create_output_file(
+                &translation,
+                path,
+                &filename,
+                &self.output_folder,
+                layout_cache.as_deref(),
+            ) {
+                return CargoResult::OutputGenerationError(err_str.to_string());
+            }
+            profile.exit();
+        }
+        profile.exit();
+
+        if self.interactions {
+            print!("{}", InteractionMatrix::new(&translation));
+        }
+
+        let result = if translation.interrupted {
+            CargoResult::Interrupted(format!(
+                "The translation was interrupted after translating {} functions. \
+                The calls translated afterwards were abstracted, so the net with {} places only models part of the program. \
+                The partial outputs were written to {} and the deadlock analysis was skipped.",
+                translation.translation_order.len(),
+                translation.net.place_labels().len(),
+                self.output_folder.join(format!("{filename}.*")).to_string_lossy()
+            ))
+        } else if self.skip_analysis {
+            CargoResult::SimpleTranslation
+        } else if self.preset == Some(PresetArg::SmallScope) {
+            self.run_small_scope_analysis(&translation)
+        } else {
+            self.run_analysis(&translation)
+        };
+        let result = append_assumptions(result, &translation);
+
+        if let Some(log) = reduction_log.filter(|_| self.reduction_log) {
+            let mut filepath = self.output_folder.clone();
+            filepath.push(&filename);
+            filepath.set_extension(REDUCTION_LOG_EXTENSION);
+            info!("Creating reduction log {}...", filepath.to_string_lossy());
+            if let Err(err_str) = std::fs::write(filepath, format!("{log}\n")) {
+                return CargoResult::OutputGenerationError(err_str.to_string());
+            }
+        }
+
+        if self.profile {
+            let mut filepath = self.output_folder.clone();
+            filepath.push(&filename);
+            filepath.set_extension(PROFILE_EXTENSION);
+            info!("Creating profile file {}...", filepath.to_string_lossy());
+            if let Err(err_str) = std::fs::File::create(filepath)
+                .and_then(|mut file| profile.write_collapsed_stacks(&mut file))
+            {
+                return CargoResult::OutputGenerationError(err_str.to_string());
+            }
+        }
+        result
+    }
+
+    /// Runs the model checker `LoLA` on the output file in `LoLA` format.
+    ///
+    /// If deadlocks are suppressed in the source code or a baseline is given, the deadlocks are listed one by one.
+    /// The deadlocks where a thread waits in suppressed code or that are recorded in the baseline
+    /// do not make the analysis fail, but they are still listed in the result.
+    /// The new and the known deadlocks are each sorted by severity, the most severe first.
+    fn run_analysis(&self, translation: &Translation) -> CargoResult {
+        let mut filepath = self.output_folder.clone();
+        filepath.push(&self.filename);
+        filepath.set_extension(OutputFormat::Lola.to_string());
+
+        if translation.suppressions.is_empty() && self.baseline.is_none() {
+            let Some(path) = lola::find_deadlock_path(&filepath) else {
+                return CargoResult::DeadlockAnalysis(
+                    "The program is deadlock-free according to the model checker `LoLA`"
+                        .to_string(),
+                );
+            };
+            let net = translation.net.data();
+            let path = translation.net.lola_identifiers().labels_of(&path);
+            let finding = deadlock_finding(path, translation, &net)
+                .expect("BUG: The witness path found by `LoLA` should be fireable in the net");
+            if let Err(err_str) = self.write_trace(translation, &net, &finding.path) {
+                return CargoResult::OutputGenerationError(err_str);
+            }
+            let mut message =
+                "Deadlock can be reached according to the model checker `LoLA`".to_string();
+            message.push_str(&render_findings(&[finding], translation, &net));
+            return CargoResult::DeadlockAnalysis(message);
+        }
+
+        let baseline = match self.read_baseline() {
+            Ok(baseline) => baseline,
+            Err(err_str) => return CargoResult::BaselineError(err_str),
+        };
+        let net = translation.net.data();
+        let mut exclusions = baseline.patterns(translation, &net, FindingKind::Deadlock);
+        let suppressed = suppressed_places(translation, &net, FindingKind::Deadlock);
+        if !suppressed.is_empty() {
+            exclusions.push(vec![suppressed]);
+        }
+        let mut new_findings = find_deadlocks(&filepath, translation, &net, exclusions);
+        let mut known_findings: Vec<Finding> = find_deadlocks(
+            &filepath,
+            translation,
+            &net,
+            new_findings.iter().map(Finding::pattern).collect(),
+        )
+        .into_iter()
+        .map(|mut finding| {
+            finding.acknowledged = baseline.contains(&finding);
+            finding
+        })
+        .collect();
+        sort_by_severity(&mut new_findings);
+        sort_by_severity(&mut known_findings);
+
+        let all_findings = [new_findings.as_slice(), known_findings.as_slice()].concat();
+        if let Some(finding) = all_findings.first() {
+            if let Err(err_str) = self.write_trace(translation, &net, &finding.path) {
+                return CargoResult::OutputGenerationError(err_str);
+            }
+        }
+        if self.update_baseline {
+            let baseline_path = self
+                .baseline
+                .as_ref()
+                .expect("BUG: `--update-baseline` should require `--baseline`");
+            info!(
+                "Writing the baseline to {}...",
+                baseline_path.to_string_lossy()
+            );
+            if let Err(err) = std::fs::write(
+                baseline_path,
+                Baseline::from_findings(&all_findings).to_json(),
+            ) {
+                return CargoResult::BaselineError(err.to_string());
+            }
+        }
+
+        let mut message = if new_findings.is_empty() {
+            "The program is deadlock-free according to the model checker `LoLA`".to_string()
+        } else {
+            "Deadlock can be reached according to the model checker `LoLA`".to_string()
+        };
+        if new_findings.is_empty() && !known_findings.is_empty() {
+            message.push_str(", except for suppressed or acknowledged deadlocks");
+        }
+        message.push_str(&render_findings(&all_findings, translation, &net));
+
+        if new_findings.is_empty() || self.baseline.is_none() || self.update_baseline {
+            CargoResult::DeadlockAnalysis(message)
+        } else {
+            CargoResult::NewFindings(message)
+        }
+    }
+
+    /// Reads the baseline given with `--baseline`.
+    /// Returns an empty baseline if none was given or if it is being updated.
+    fn read_baseline(&self) -> Result<Baseline, String> {
+        match &self.baseline {
+            Some(path) if !self.update_baseline => {
+                let json = std::fs::read_to_string(path).map_err(|err| {
+                    format!(
+                        "Could not read the baseline at {}: {err}",
+                        path.to_string_lossy()
+                    )
+                })?;
+                Baseline::from_json(&json)
+            }
+            _ => Ok(Baseline::default()),
+        }
+    }
+
+    /// Searches all the reachable markings of the net for a deadlock with the internal model checker.
+    /// Visits at most `SMALL_SCOPE_MAX_STATES` markings.
+    /// The markings where a thread only waits for one of the bounds of the small scope are not deadlocks.
+    ///
+    /// # Panics
+    ///
+    /// If the path to the deadlock cannot be fired in the net, then the function panics.
+    fn run_small_scope_analysis(&self, translation: &Translation) -> CargoResult {
+        let net = translation.net.data();
+        info!("Searching the reachable markings for a deadlock...");
+        match find_deadlock_within_bounds(&net, SMALL_SCOPE_MAX_STATES, &translation.bounded_places) {
+            Ok(DeadlockResult::Deadlock { path }) => {
+                let finding = deadlock_finding(path, translation, &net)
+                    .expect("BUG: The path found by the internal search should be fireable in the net");
+                if let Err(err_str) = self.write_trace(translation, &net, &finding.path) {
+                    return CargoResult::OutputGenerationError(err_str);
+                }
+                let mut message = "Deadlock can be reached within the small scope".to_string();
+                message.push_str(&render_findings(&[finding], translation, &net));
+                CargoResult::DeadlockAnalysis(message)
+            }
+            Ok(DeadlockResult::DeadlockFree { states }) => CargoResult::DeadlockAnalysis(format!(
+                "The program is deadlock-free within the small scope: All the {states} reachable markings were visited"
+            )),
+            Ok(DeadlockResult::BoundReached { states, place, .. }) => CargoResult::DeadlockAnalysis(format!(
+                "The program is deadlock-free within the small scope: All the {states} reachable markings were visited. \
+                 Only the bounds of the small scope stop the program, e.g. `{place}`, where the program would continue"
+            )),
+            Ok(DeadlockResult::StateLimitReached { states }) => CargoResult::DeadlockAnalysis(format!(
+                "No deadlock found in the first {states} markings within the small scope. Run without `--preset` for the full search with `LoLA`"
+            )),
+            Ok(DeadlockResult::CapacityExceeded { place, path }) => CargoResult::TranslationError(
+                format!(
+                    "The place `{place}` exceeds its capacity after firing the transitions: {}",
+                    path.join(", ")
+                ),
+            ),
+            Err(err_str) => CargoResult::TranslationError(err_str),
+        }
+    }
+
+    /// Writes the path to a deadlock as a trace of markings if `--trace` was given.
+    ///
+    /// # Errors
+    ///
+    /// If the path cannot be fired in the net or the files cannot be written, then an error is returned.
+    fn write_trace(
+        &self,
+        translation: &Translation,
+        net: &NetData,
+        path: &[String],
+    ) -> Result<(), String> {
+        if !self.trace {
+            return Ok(());
+        }
+        let trace = MarkingTrace::replay(net, path)?;
+        let filepath = |extension: &str| {
+            let mut filepath = self.output_folder.clone();
+            filepath.push(&self.filename);
+            filepath.set_extension(extension);
+            info!("Creating trace file {}...", filepath.to_string_lossy());
+            filepath
+        };
+
+        let identifiers = translation.net.identifiers(IdentifierFormat::Pnml);
+        std::fs::File::create(filepath(TRACE_MCC_EXTENSION))
+            .and_then(|mut file| trace.write_mcc_witness(&identifiers, &mut file))
+            .map_err(|err| err.to_string())?;
+        std::fs::File::create(filepath(TRACE_CSV_EXTENSION))
+            .and_then(|mut file| trace.write_csv(&mut file))
+            .map_err(|err| err.to_string())
+    }
+
+    /// Returns the arguments for the compiler given with `--edition`, `--cfg` and `--rustc-arg`, in this order.
+    fn rustc_args(&self) -> Vec<String> {
+        let edition = self
+            .edition
+            .iter()
+            .map(|edition| format!("--edition={edition}"));
+        let cfgs = self.cfgs.iter().map(|cfg| format!("--cfg={cfg}"));
+        edition
+            .chain(cfgs)
+            .chain(self.rustc_args.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the style of the DOT output given with `--dot-clusters` and `--dot-option`, in this order.
+    fn dot_style(&self) -> DotStyle {
+        let mut style = DotStyle {
+            clusters: self.dot_clusters,
+            ..DotStyle::default()
+        };
+        for option in &self.dot_options {
+            style
+                .set_option(option)
+                .expect("BUG: The DOT options should be validated when parsing the arguments");
+        }
+        style
+    }
+}
+
+/// Checks that a `--dot-option` argument is a valid option of the style of the DOT output.
+fn parse_dot_option(option: &str) -> Result<String, String> {
+    DotStyle::default().set_option(option)?;
+    Ok(option.to_string())
+}
+
+/// Parses a `--lock-library` argument, e.g. `my_crate::sync<T>`, into the path and the generic parameters.
+fn parse_lock_library(argument: &str) -> Result<LockLibrary, String> {
+    match argument.find('<') {
+        Some(start) if start > 0 && argument.ends_with('>') => Ok(LockLibrary::new(
+            &argument[..start],
+            &argument[start..],
+        )),
+        _ => Err(format!(
+            "expected the path to the module with the lock types followed by their generic parameters, e.g. `my_crate::sync<T>`, found `{argument}`"
+        )),
+    }
+}
+
+/// Installs a handler for Ctrl-C that stops the translation at the next safe point.
+/// Pressing Ctrl-C a second time exits immediately.
+/// If the handler cannot be installed, e.g. because another one exists, Ctrl-C keeps its default behavior.
+fn install_interruption_handler() {
+    let result = ctrlc::set_handler(|| {
+        if interruption_requested() {
+            std::process::exit(130);
+        }
+        eprintln!(
+            "Interrupted: Finishing the translation with the remaining calls abstracted. Press Ctrl-C again to exit immediately."
+        );
+        request_interruption();
+    });
+    if let Err(err) = result {
+        warn!("Could not install the handler for Ctrl-C: {err}");
+    }
+}
+
+/// Appends the assumptions of the verdict that the translation could not verify
+/// to the result of the deadlock analysis, one note per line.
+pub fn append_assumptions(result: CargoResult, translation: &Translation) -> CargoResult {
+    let notes =
+        unverified_assumptions(translation)
+            .iter()
+            .fold(String::new(), |mut notes, assumption| {
+                notes.push_str("\nNote: ");
+                notes.push_str(assumption);
+                notes
+            });
+    match result {
+        CargoResult::DeadlockAnalysis(message) => CargoResult::DeadlockAnalysis(message + &notes),
+        CargoResult::NewFindings(message) => CargoResult::NewFindings(message + &notes),
+        result => result,
+    }
+}
+
+/// Renders the findings as annotated snippets of the source code, each one preceded by an empty line.
+/// The title of every finding includes its severity.
+/// The source files are read from the paths in the spans, relative to the current working directory.
+///
+/// # Panics
+///
+/// If the path of a finding cannot be fired in the net, then the function panics.
+pub fn render_findings(findings: &[Finding], translation: &Translation, net: &NetData) -> String {
+    let mut sources: BTreeMap<String, String> = BTreeMap::new();
+    let mut output = String::new();
+    for finding in findings {
+        let labels = finding_labels(finding, translation, net)
+            .expect("BUG: The path of the finding should be fireable in the net");
+        for label in &labels {
+            if !sources.contains_key(&label.span.filename) {
+                if let Ok(source_code) = std::fs::read_to_string(&label.span.filename) {
+                    sources.insert(label.span.filename.clone(), source_code);
+                }
+            }
+        }
+        output.push_str("\n\n");
+        let title = format!("{finding} ({})", finding.severity());
+        output.push_str(&render_snippet(&title, &labels, &sources));
+        let localization = localize_deadlock(finding, translation, net)
+            .expect("BUG: The path of the finding should be fireable in the net");
+        output.push('\n');
+        output.push_str(&localization.to_string());
+    }
+    output
+}
+
+/// Lists the deadlocks whose marking does not match any of the exclusions, one by one.
+/// After each deadlock is found, the markings where the threads are blocked at the same places are excluded.
+/// Stops after `MAX_FINDINGS` deadlocks.
+///
+/// # Panics
+///
+/// If the witness path of `LoLA` cannot be fired in the net, then the function panics.
+fn find_deadlocks(
+    filepath: &std::path::PathBuf,
+    translation: &Translation,
+    net: &NetData,
+    mut exclusions: Vec<MarkingPattern>,
+) -> Vec<Finding> {
+    // The places in the formula and the transitions in the witness path are names in the `LoLA` file.
+    let identifiers = translation.net.lola_identifiers();
+    let mut findings = Vec::new();
+    while findings.len() < MAX_FINDINGS {
+        let names: Vec<MarkingPattern> = exclusions
+            .iter()
+            .map(|pattern| {
+                pattern
+                    .iter()
+                    .map(|group| identifiers.identifiers_of(group))
+                    .collect()
+            })
+            .collect();
+        let Some(path) = lola::find_deadlock_path_excluding(filepath, &names) else {
+            break;
+        };
+        let path = identifiers.labels_of(&path);
+        let finding = deadlock_finding(path, translation, net)
+            .expect("BUG: The witness path found by `LoLA` should be fireable in the net");
+        exclusions.push(finding.pattern());
+        findings.push(finding);
+    }
+    findings
+}
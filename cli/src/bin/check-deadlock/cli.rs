@@ -0,0 +1,71 @@
+use clap::Parser;
+
+use crate::cargo_result::CargoResult;
+use crate::check_deadlock::Args;
+use crate::compare::CompareArgs;
+use crate::demo::DemoArgs;
+use crate::lint::LintArgs;
+use crate::net_diff::NetDiffArgs;
+use crate::project::ProjectArgs;
+use crate::trace::TraceArgs;
+use crate::tui::TuiArgs;
+use crate::verify::VerifyArgs;
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "cargo", author, version, long_about = None)]
+#[command(
+    about = "Convert a Rust source code file into a Petri net and analyze the net to find deadlocks."
+)]
+pub enum Command {
+    CheckDeadlock(Args),
+}
+
+/// The optional subcommands of `cargo check-deadlock`.
+#[derive(Debug, clap::Subcommand)]
+pub enum Subcommand {
+    /// Compare the nets of two versions of a program and, optionally, their deadlock verdicts.
+    Compare(CompareArgs),
+    /// Translate and analyze one of the example programs with a classic concurrency bug.
+    Demo(DemoArgs),
+    /// Find common deadlock patterns directly on the MIR, without building the net.
+    Lint(LintArgs),
+    /// Draw the differences between two nets exported to JSON as a DOT graph.
+    NetDiff(NetDiffArgs),
+    /// Translate and analyze a binary or library of a Cargo project, including its dependencies.
+    Project(ProjectArgs),
+    /// Print the interleaving of a witness path of `LoLA` as annotated source code.
+    Trace(TraceArgs),
+    /// Explore the net of a program in the terminal and play the token game.
+    Tui(TuiArgs),
+    /// Translate a program, run `LoLA` on the net with the deadlock formula and print the verdict.
+    Verify(VerifyArgs),
+}
+
+impl Subcommand {
+    pub fn exec(&self) -> CargoResult {
+        match self {
+            Self::Compare(args) => args.exec(),
+            Self::Demo(args) => args.exec(),
+            Self::Lint(args) => args.exec(),
+            Self::NetDiff(args) => args.exec(),
+            Self::Project(args) => args.exec(),
+            Self::Trace(args) => args.exec(),
+            Self::Tui(args) => args.exec(),
+            Self::Verify(args) => args.exec(),
+        }
+    }
+}
+
+impl Command {
+    pub fn exec(self) -> CargoResult {
+        match self {
+            Self::CheckDeadlock(args) => args.exec(),
+        }
+    }
+}
+
+#[test]
+fn verify_app() {
+    use clap::CommandFactory;
+    Command::command().debug_assert()
+}
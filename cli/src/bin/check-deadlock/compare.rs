@@ -0,0 +1,167 @@
+//! Comparison of two versions of a program, e.g. before and after a concurrency-sensitive patch.
+//!
+//! Both versions are translated and the transitions of their nets are compared function by function,
+//! see `cargo_check_deadlock_core::report::net_diff`. With `--check`, both nets are also analyzed
+//! with `LoLA` and the change of the verdict is reported. A deadlock that only the new version has
+//! makes the command fail, like a new finding with a baseline.
+
+use clap::Parser;
+use log::info;
+
+use crate::cargo_result::CargoResult;
+use crate::check_deadlock::render_findings;
+use crate::output_format::OutputFormat;
+
+use cargo_check_deadlock_core::model_checker::lola;
+use cargo_check_deadlock_core::report::findings::deadlock_finding;
+use cargo_check_deadlock_core::report::net_diff::NetDiff;
+use cargo_check_deadlock_core::Translation;
+use cargo_check_deadlock_translator::Options;
+
+/// Compare the nets of two versions of a program and, optionally, their deadlock verdicts.
+#[derive(Debug, Parser)]
+pub struct CompareArgs {
+    /// The path to the old version of the Rust source code file.
+    old: std::path::PathBuf,
+
+    /// The path to the new version of the Rust source code file.
+    new: std::path::PathBuf,
+
+    /// If set, both versions are analyzed with `LoLA` and the change of the verdict is reported.
+    /// The command fails if only the new version can reach a deadlock.
+    #[arg(long)]
+    check: bool,
+
+    /// The path to a valid folder where the files in `LoLA` format should be created,
+    /// named `compare_old.lola` and `compare_new.lola`.
+    /// If not specified, the current working directory is used.
+    #[arg(long, default_value = ".")]
+    output_folder: std::path::PathBuf,
+
+    /// The path of the function where the translation starts in both versions, e.g. `my_mod::worker`.
+    /// If not specified, the translation starts at the `main` function.
+    #[arg(long, value_name = "PATH")]
+    entry_function: Option<String>,
+}
+
+/// The verdict of the analysis of one version of the program.
+enum Verdict {
+    DeadlockFree,
+    /// A deadlock is reachable. The message shows the source code of the threads involved.
+    Deadlock(String),
+}
+
+impl CompareArgs {
+    pub fn exec(&self) -> CargoResult {
+        for path in [&self.old, &self.new] {
+            if !path.exists() {
+                let err_str = format!(
+                    "Source code file at {} does not exist",
+                    &path.to_string_lossy()
+                );
+                return CargoResult::SourceFileNotFound(err_str);
+            }
+        }
+        if !self.output_folder.exists() {
+            let err_str = format!(
+                "Output folder at {} does not exist",
+                &self.output_folder.to_string_lossy()
+            );
+            return CargoResult::OutputFolderNotFound(err_str);
+        };
+
+        let old = match self.translate(&self.old) {
+            Ok(translation) => translation,
+            Err(err_str) => return CargoResult::TranslationError(err_str),
+        };
+        let new = match self.translate(&self.new) {
+            Ok(translation) => translation,
+            Err(err_str) => return CargoResult::TranslationError(err_str),
+        };
+        println!("{}", NetDiff::new(&old.net.data(), &new.net.data()));
+
+        if !self.check {
+            return CargoResult::SimpleTranslation;
+        }
+        let old_verdict = match self.analyze(&old, &self.old, "compare_old") {
+            Ok(verdict) => verdict,
+            Err(err_str) => return CargoResult::OutputGenerationError(err_str),
+        };
+        let new_verdict = match self.analyze(&new, &self.new, "compare_new") {
+            Ok(verdict) => verdict,
+            Err(err_str) => return CargoResult::OutputGenerationError(err_str),
+        };
+        let lola = "according to the model checker `LoLA`";
+        match (old_verdict, new_verdict) {
+            (Verdict::DeadlockFree, Verdict::DeadlockFree) => {
+                CargoResult::DeadlockAnalysis(format!("Both versions are deadlock-free {lola}"))
+            }
+            (Verdict::Deadlock(_), Verdict::DeadlockFree) => CargoResult::DeadlockAnalysis(
+                format!("The new version fixes the deadlock of the old version {lola}"),
+            ),
+            (Verdict::Deadlock(_), Verdict::Deadlock(finding)) => CargoResult::DeadlockAnalysis(
+                format!("Deadlock can be reached in both versions {lola}{finding}"),
+            ),
+            (Verdict::DeadlockFree, Verdict::Deadlock(finding)) => CargoResult::NewFindings(
+                format!("The new version introduces a deadlock {lola}{finding}"),
+            ),
+        }
+    }
+
+    /// Translates one version of the program.
+    ///
+    /// # Errors
+    ///
+    /// If the translation fails, then an error with the corresponding description is returned.
+    fn translate(&self, path: &std::path::Path) -> Result<Translation, String> {
+        info!("Translating {}...", path.to_string_lossy());
+        let options = Options {
+            entry_function: self.entry_function.clone(),
+            ..Options::default()
+        };
+        cargo_check_deadlock_translator::run(path.to_path_buf(), options)
+            .map_err(|err_str| err_str.to_string())
+    }
+
+    /// Writes the net of one version to the file `filename.lola` in the output folder and runs `LoLA` on it.
+    ///
+    /// # Errors
+    ///
+    /// If the file in `LoLA` format cannot be created, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the witness path found by `LoLA` cannot be fired in the net, then the function panics.
+    fn analyze(
+        &self,
+        translation: &Translation,
+        source_path: &std::path::Path,
+        filename: &str,
+    ) -> Result<Verdict, String> {
+        OutputFormat::Lola
+            .create_output_file(
+                translation,
+                source_path,
+                filename,
+                &self.output_folder,
+                None,
+            )
+            .map_err(|err| err.to_string())?;
+        let mut filepath = self.output_folder.clone();
+        filepath.push(filename);
+        filepath.set_extension(OutputFormat::Lola.to_string());
+
+        let Some(path) = lola::find_deadlock_path(&filepath) else {
+            return Ok(Verdict::DeadlockFree);
+        };
+        let net = translation.net.data();
+        let path = translation.net.lola_identifiers().labels_of(&path);
+        let finding = deadlock_finding(path, translation, &net)
+            .expect("BUG: The witness path found by `LoLA` should be fireable in the net");
+        Ok(Verdict::Deadlock(render_findings(
+            &[finding],
+            translation,
+            &net,
+        )))
+    }
+}
@@ -0,0 +1,112 @@
+//! Submodule for finding the crate root, i.e. the file that is passed to the compiler.
+//!
+//! The compiler resolves the `mod foo;` declarations starting from the crate root,
+//! looking for `foo.rs` or `foo/mod.rs` next to it. Therefore, a crate whose modules live in
+//! sibling files and folders is translated completely by pointing the compiler at its root.
+//! The path given by the user may be the crate root itself or a folder that contains it.
+
+use std::path::{Path, PathBuf};
+
+/// The candidates for the crate root in a folder, in order of preference,
+/// and whether they are compiled as a library.
+/// A binary is preferred over a library, like `cargo run` does in a package with both.
+const CRATE_ROOT_CANDIDATES: [(&str, bool); 4] = [
+    ("src/main.rs", false),
+    ("main.rs", false),
+    ("src/lib.rs", true),
+    ("lib.rs", true),
+];
+
+/// The root file of the crate to translate.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CrateRoot {
+    /// The path to the root file of the crate.
+    pub path: PathBuf,
+    /// Whether the crate root is the root of a library, i.e. it is named `lib.rs`.
+    pub is_library: bool,
+}
+
+/// Finds the crate root for the given path.
+/// A file is the crate root itself. A folder is searched for `src/main.rs`, `main.rs`,
+/// `src/lib.rs` or `lib.rs`, in this order.
+///
+/// # Errors
+///
+/// If the path does not exist, then an error is returned.
+/// If the path is a folder without any of the crate roots, then an error is returned.
+pub fn find_crate_root(path: &Path) -> Result<CrateRoot, String> {
+    if path.is_file() {
+        return Ok(CrateRoot {
+            path: path.to_path_buf(),
+            is_library: false,
+        });
+    }
+    if !path.is_dir() {
+        return Err(format!(
+            "Source code file at {} does not exist",
+            path.to_string_lossy()
+        ));
+    }
+    CRATE_ROOT_CANDIDATES
+        .iter()
+        .map(|(candidate, is_library)| CrateRoot {
+            path: path.join(candidate),
+            is_library: *is_library,
+        })
+        .find(|crate_root| crate_root.path.is_file())
+        .ok_or_else(|| {
+            format!(
+                "No crate root (src/main.rs, main.rs, src/lib.rs or lib.rs) found in the folder at {}",
+                path.to_string_lossy()
+            )
+        })
+}
+
+#[cfg(test)]
+mod crate_root_tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn find_crate_root_accepts_a_file() {
+        let file = assert_fs::NamedTempFile::new("program.rs").unwrap();
+        file.write_str("fn main() {}").unwrap();
+
+        let crate_root = find_crate_root(file.path()).unwrap();
+
+        assert_eq!(crate_root.path, file.path());
+        assert!(!crate_root.is_library);
+    }
+
+    #[test]
+    fn find_crate_root_prefers_the_binary_in_a_folder() {
+        let folder = assert_fs::TempDir::new().unwrap();
+        folder
+            .child("src/lib.rs")
+            .write_str("mod counter;")
+            .unwrap();
+        folder.child("src/counter.rs").write_str("").unwrap();
+
+        let crate_root = find_crate_root(folder.path()).unwrap();
+        assert_eq!(crate_root.path, folder.path().join("src/lib.rs"));
+        assert!(crate_root.is_library);
+
+        folder
+            .child("src/main.rs")
+            .write_str("fn main() {}")
+            .unwrap();
+        let crate_root = find_crate_root(folder.path()).unwrap();
+        assert_eq!(crate_root.path, folder.path().join("src/main.rs"));
+        assert!(!crate_root.is_library);
+    }
+
+    #[test]
+    fn find_crate_root_fails_without_a_crate_root() {
+        let folder = assert_fs::TempDir::new().unwrap();
+        folder.child("src/counter.rs").write_str("").unwrap();
+
+        let err = find_crate_root(folder.path()).unwrap_err();
+
+        assert!(err.starts_with("No crate root"));
+    }
+}
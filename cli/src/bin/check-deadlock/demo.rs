@@ -0,0 +1,161 @@
+//! A gallery of example programs with classic concurrency bugs shipped in the binary.
+//!
+//! Each demo is translated and analyzed like a regular program.
+//! If a deadlock is found, the counterexample is printed step by step
+//! together with the line of the source code that produced each transition.
+
+use clap::{Parser, ValueEnum};
+use log::info;
+
+use crate::cargo_result::CargoResult;
+use crate::output_format::OutputFormat;
+
+use cargo_check_deadlock_core::model_checker::lola;
+use cargo_check_deadlock_core::Translation;
+use cargo_check_deadlock_translator::Options;
+
+/// Translate and analyze one of the example programs with a classic concurrency bug.
+#[derive(Debug, Parser)]
+pub struct DemoArgs {
+    /// The name of the example program.
+    #[arg(value_enum)]
+    name: Demo,
+
+    /// The path to a valid folder where the example program and the output files should be created.
+    /// If not specified, the current working directory is used.
+    #[arg(long, default_value = ".")]
+    output_folder: std::path::PathBuf,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+/// The example programs included in the binary
+pub enum Demo {
+    /// A thread waits on a condition variable that is never notified
+    Deadlock,
+    /// A thread notifies a condition variable before anyone waits on it
+    LostSignal,
+    /// A thread locks the same mutex twice
+    DoubleLock,
+    /// Two threads lock two mutexes in opposite order (A then B, B then A)
+    Abba,
+}
+
+impl Demo {
+    /// Returns the source code of the example program.
+    const fn source_code(self) -> &'static str {
+        match self {
+            Self::Deadlock => {
+                include_str!("../../../examples/programs/condvar/infinite_wait_deadlock.rs")
+            }
+            Self::LostSignal => {
+                include_str!("../../../examples/programs/condvar/self_notify_lost_signal.rs")
+            }
+            Self::DoubleLock => {
+                include_str!("../../../examples/programs/mutex/double_lock_deadlock.rs")
+            }
+            Self::Abba => include_str!("../../../examples/programs/thread/dating_philosophers.rs"),
+        }
+    }
+
+    /// Returns a short explanation of the bug in the example program.
+    const fn explanation(self) -> &'static str {
+        match self {
+            Self::Deadlock => "The main thread waits on a condition variable, but no other thread ever calls `notify_one`. The wait blocks forever.",
+            Self::LostSignal => "The main thread calls `notify_one` before waiting on the condition variable. Nobody is waiting yet, so the signal is lost and the wait blocks forever.",
+            Self::DoubleLock => "The main thread locks the mutex and, while still holding the guard, tries to lock it again. `std::sync::Mutex` is not reentrant, so the second lock blocks forever.",
+            Self::Abba => "Each thread locks one mutex and then tries to lock the other one, in opposite order. If both threads take their first lock, each one waits for the other forever.",
+        }
+    }
+}
+
+impl std::fmt::Display for Demo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Deadlock => write!(f, "deadlock"),
+            Self::LostSignal => write!(f, "lost_signal"),
+            Self::DoubleLock => write!(f, "double_lock"),
+            Self::Abba => write!(f, "abba"),
+        }
+    }
+}
+
+impl DemoArgs {
+    pub fn exec(&self) -> CargoResult {
+        if !self.output_folder.exists() {
+            let err_str = format!(
+                "Output folder at {} does not exist",
+                &self.output_folder.to_string_lossy()
+            );
+            return CargoResult::OutputFolderNotFound(err_str);
+        };
+
+        // The compiler needs a file to read the source code from.
+        let filename = format!("demo_{}", self.name);
+        let mut source_path = self.output_folder.clone();
+        source_path.push(&filename);
+        source_path.set_extension("rs");
+        let source_code = self.name.source_code();
+        info!(
+            "Writing the example program to {}...",
+            source_path.to_string_lossy()
+        );
+        if let Err(err) = std::fs::write(&source_path, source_code) {
+            return CargoResult::OutputGenerationError(err.to_string());
+        }
+
+        println!("Example program `{}`:\n", self.name);
+        println!("{source_code}");
+        println!("{}\n", self.name.explanation());
+
+        let translation =
+            match cargo_check_deadlock_translator::run(source_path.clone(), Options::default()) {
+                Ok(translation) => translation,
+                Err(err_str) => {
+                    return CargoResult::TranslationError(err_str.to_string());
+                }
+            };
+
+        let format = OutputFormat::Lola;
+        if let Err(err_str) = format.create_output_file(
+            &translation,
+            &source_path,
+            &filename,
+            &self.output_folder,
+            None,
+        ) {
+            return CargoResult::OutputGenerationError(err_str.to_string());
+        }
+
+        let mut net_path = self.output_folder.clone();
+        net_path.push(&filename);
+        net_path.set_extension(OutputFormat::Lola.to_string());
+
+        let Some(path) = lola::find_deadlock_path(&net_path) else {
+            let message = "The program is deadlock-free according to the model checker `LoLA`";
+            return CargoResult::DeadlockAnalysis(message.to_string());
+        };
+        let path = translation.net.lola_identifiers().labels_of(&path);
+
+        println!("Counterexample found by the model checker `LoLA`:");
+        print_walkthrough(&path, &translation, source_code);
+        let message = "Deadlock can be reached according to the model checker `LoLA`";
+        CargoResult::DeadlockAnalysis(message.to_string())
+    }
+}
+
+/// Prints the transitions of the counterexample in order.
+/// Transitions that originate from a line in the source code are followed by the line.
+fn print_walkthrough(path: &[String], translation: &Translation, source_code: &str) {
+    let lines: Vec<&str> = source_code.lines().collect();
+    let spans = translation.net.transition_spans();
+    for (step, transition) in path.iter().enumerate() {
+        println!("  {}. {transition}", step + 1);
+        if let Some(span) = spans.get(transition.as_str()) {
+            // Lines in a `SpanInfo` are 1-based.
+            if let Some(line) = span.start_line.checked_sub(1).and_then(|i| lines.get(i)) {
+                println!("       line {}: {}", span.start_line, line.trim());
+            }
+        }
+    }
+    println!("  The program cannot make any progress after the last step.\n");
+}
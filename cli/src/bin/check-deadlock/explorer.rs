@@ -0,0 +1,415 @@
+//! The state of the interactive explorer of the net, independent of the terminal.
+//!
+//! The transitions of the net are grouped by the translated function that produced them,
+//! using the span of the function definition: A transition belongs to the innermost function
+//! whose definition contains the line of the transition, e.g. to the closure and not to `main`.
+//! The transitions without a span, like the ones that start and end the program, are listed under
+//! a pseudo-function `(program)`.
+//!
+//! The token game is played with the `Simulation` of the internal analyses,
+//! and the quick queries run the internal search for deadlocks, so `LoLA` is not needed.
+
+use cargo_check_deadlock_core::analysis::reachability::{find_deadlock, DeadlockResult};
+use cargo_check_deadlock_core::analysis::simulation::Simulation;
+use cargo_check_deadlock_core::{NetData, SpanInfo, Translation};
+
+/// The maximum number of markings visited by the query `deadlock`.
+const MAX_STATES: usize = 100_000;
+
+/// The name of the pseudo-function for the transitions that do not belong to any function.
+const PROGRAM_FUNCTION: &str = "(program)";
+
+/// The help shown for the query `help` or an unknown query.
+const QUERY_HELP: [&str; 5] = [
+    "deadlock       search the reachable markings for a deadlock",
+    "find <text>    list the transitions whose label contains the text",
+    "line <number>  list the transitions produced by the line of the source code",
+    "tokens <place> show the tokens in the place in the current marking",
+    "help           show this help",
+];
+
+/// A translated function with the transitions it produced.
+pub struct ExploredFunction {
+    /// The name of the function, e.g. `main::{closure#0}`.
+    pub name: String,
+    /// The span of the function definition, if known.
+    pub span: Option<SpanInfo>,
+    /// The indices of the transitions produced by the function, in the order of the net.
+    pub transitions: Vec<usize>,
+}
+
+/// The outcome of a quick query.
+#[derive(Debug, PartialEq, Eq)]
+pub struct QueryOutcome {
+    /// The lines of text that answer the query.
+    pub lines: Vec<String>,
+    /// The indices of the transitions selected by the query, if the query selects transitions.
+    pub transitions: Option<Vec<usize>>,
+}
+
+impl QueryOutcome {
+    /// Creates an outcome that only shows text.
+    fn text(lines: Vec<String>) -> Self {
+        Self {
+            lines,
+            transitions: None,
+        }
+    }
+}
+
+pub struct Explorer {
+    /// The net being explored.
+    net: NetData,
+    /// The translated functions, in the order they were translated, followed by `(program)`.
+    functions: Vec<ExploredFunction>,
+    /// The token game on the net.
+    simulation: Simulation,
+    /// The lines of the source code of the program.
+    source_lines: Vec<String>,
+}
+
+impl Explorer {
+    /// Creates the explorer for the result of the translation of the given source code.
+    ///
+    /// # Errors
+    ///
+    /// If the net is not valid, e.g. an arc refers to an unknown place, then an error is returned.
+    pub fn from_translation(translation: &Translation, source_code: &str) -> Result<Self, String> {
+        let functions = translation
+            .translation_order
+            .iter()
+            .map(|name| {
+                let span = translation
+                    .coverage
+                    .get(name)
+                    .map(|function| function.span.clone());
+                (name.clone(), span)
+            })
+            .collect();
+        Self::new(translation.net.data(), functions, source_code)
+    }
+
+    /// Creates the explorer for the net and the translated functions with the span of their definition.
+    ///
+    /// # Errors
+    ///
+    /// If the net is not valid, e.g. an arc refers to an unknown place, then an error is returned.
+    pub fn new(
+        net: NetData,
+        functions: Vec<(String, Option<SpanInfo>)>,
+        source_code: &str,
+    ) -> Result<Self, String> {
+        let simulation = Simulation::new(&net)?;
+        let mut functions: Vec<ExploredFunction> = functions
+            .into_iter()
+            .map(|(name, span)| ExploredFunction {
+                name,
+                span,
+                transitions: Vec::new(),
+            })
+            .collect();
+        let mut program_transitions = Vec::new();
+        for (index, transition) in net.transitions.iter().enumerate() {
+            let owner = transition
+                .span
+                .as_ref()
+                .and_then(|span| innermost_function(&functions, span));
+            match owner {
+                Some(function) => functions[function].transitions.push(index),
+                None => program_transitions.push(index),
+            }
+        }
+        functions.push(ExploredFunction {
+            name: PROGRAM_FUNCTION.to_string(),
+            span: None,
+            transitions: program_transitions,
+        });
+
+        Ok(Self {
+            net,
+            functions,
+            simulation,
+            source_lines: source_code.lines().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Returns the translated functions, followed by the pseudo-function `(program)`.
+    pub fn functions(&self) -> &[ExploredFunction] {
+        &self.functions
+    }
+
+    /// Returns the net being explored.
+    pub const fn net(&self) -> &NetData {
+        &self.net
+    }
+
+    /// Returns the lines of the source code of the program.
+    pub fn source_lines(&self) -> &[String] {
+        &self.source_lines
+    }
+
+    /// Returns the transitions fired so far in the simulation, in order.
+    pub fn history(&self) -> &[String] {
+        self.simulation.history()
+    }
+
+    /// Checks whether the transition can fire in the current marking.
+    pub fn is_enabled(&self, transition: usize) -> bool {
+        let label = &self.net.transitions[transition].label;
+        self.simulation
+            .enabled_transitions()
+            .contains(&label.as_str())
+    }
+
+    /// Checks whether the current marking is a deadlock.
+    pub fn is_deadlock(&self) -> bool {
+        self.simulation.is_deadlock()
+    }
+
+    /// Fires the transition in the simulation.
+    ///
+    /// # Errors
+    ///
+    /// If the transition is not enabled or exceeds the capacity of a place, then an error is returned.
+    pub fn fire(&mut self, transition: usize) -> Result<(), String> {
+        let label = self.net.transitions[transition].label.clone();
+        self.simulation.fire(&label)
+    }
+
+    /// Goes back to the initial marking.
+    pub fn reset(&mut self) {
+        self.simulation.reset();
+    }
+
+    /// Returns the places with at least one token in the current marking and their tokens.
+    pub fn marked_places(&self) -> Vec<(&str, usize)> {
+        self.net
+            .places
+            .iter()
+            .zip(self.simulation.marking())
+            .filter(|(_, tokens)| **tokens > 0)
+            .map(|(place, tokens)| (place.label.as_str(), *tokens))
+            .collect()
+    }
+
+    /// Returns the indices of the transitions produced by the line of the source code (1-based).
+    pub fn transitions_at_line(&self, line: usize) -> Vec<usize> {
+        self.net
+            .transitions
+            .iter()
+            .enumerate()
+            .filter(|(_, transition)| {
+                transition
+                    .span
+                    .as_ref()
+                    .is_some_and(|span| span.start_line <= line && line <= span.end_line)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Runs a quick query typed by the user.
+    pub fn query(&self, query: &str) -> QueryOutcome {
+        let (command, argument) = query
+            .trim()
+            .split_once(' ')
+            .map_or((query.trim(), ""), |(command, argument)| {
+                (command, argument.trim())
+            });
+        match (command, argument) {
+            ("deadlock", "") => QueryOutcome::text(self.query_deadlock()),
+            ("find", text) if !text.is_empty() => {
+                let transitions: Vec<usize> = self
+                    .net
+                    .transitions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, transition)| transition.label.contains(text))
+                    .map(|(index, _)| index)
+                    .collect();
+                QueryOutcome {
+                    lines: vec![format!(
+                        "{} transitions contain `{text}`",
+                        transitions.len()
+                    )],
+                    transitions: Some(transitions),
+                }
+            }
+            ("line", number) => match number.parse::<usize>() {
+                Ok(line) => {
+                    let transitions = self.transitions_at_line(line);
+                    QueryOutcome {
+                        lines: vec![format!(
+                            "{} transitions produced by line {line}",
+                            transitions.len()
+                        )],
+                        transitions: Some(transitions),
+                    }
+                }
+                Err(_) => QueryOutcome::text(vec![format!("Invalid line number `{number}`")]),
+            },
+            ("tokens", place) if !place.is_empty() => {
+                let tokens = self
+                    .net
+                    .places
+                    .iter()
+                    .position(|candidate| candidate.label == place)
+                    .map(|index| self.simulation.marking()[index]);
+                QueryOutcome::text(vec![tokens.map_or_else(
+                    || format!("The place `{place}` does not exist"),
+                    |tokens| format!("The place `{place}` has {tokens} tokens"),
+                )])
+            }
+            ("help", "") => QueryOutcome::text(QUERY_HELP.map(ToString::to_string).to_vec()),
+            _ => {
+                let mut lines = vec![format!("Unknown query `{query}`. Available queries:")];
+                lines.extend(QUERY_HELP.map(ToString::to_string));
+                QueryOutcome::text(lines)
+            }
+        }
+    }
+
+    /// Searches the reachable markings from the initial marking for a deadlock.
+    fn query_deadlock(&self) -> Vec<String> {
+        match find_deadlock(&self.net, MAX_STATES) {
+            Ok(DeadlockResult::Deadlock { path }) => {
+                let mut lines = vec![format!("Deadlock reachable in {} steps:", path.len())];
+                lines.extend(path);
+                lines
+            }
+            Ok(DeadlockResult::CapacityExceeded { place, path }) => {
+                let mut lines = vec![format!(
+                    "The place `{place}` exceeds its capacity after {} steps:",
+                    path.len()
+                )];
+                lines.extend(path);
+                lines
+            }
+            Ok(
+                DeadlockResult::DeadlockFree { states }
+                | DeadlockResult::BoundReached { states, .. },
+            ) => {
+                vec![format!("No deadlock in the {states} reachable markings")]
+            }
+            Ok(DeadlockResult::StateLimitReached { states }) => vec![format!(
+                "No deadlock found in the first {states} markings. Use `LoLA` for the full search"
+            )],
+            Err(err_str) => vec![err_str],
+        }
+    }
+}
+
+/// Returns the index of the function with the smallest definition that contains the span, if any.
+fn innermost_function(functions: &[ExploredFunction], span: &SpanInfo) -> Option<usize> {
+    functions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, function)| {
+            let definition = function.span.as_ref()?;
+            let contains = definition.filename == span.filename
+                && definition.start_line <= span.start_line
+                && span.end_line <= definition.end_line;
+            contains.then_some((index, definition.end_line - definition.start_line))
+        })
+        .min_by_key(|(_, lines)| *lines)
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod explorer_tests {
+    use super::*;
+    use cargo_check_deadlock_core::data_structures::net_builder::NetBuilder;
+
+    const SOURCE_CODE: &str = "\
+fn main() {
+    let data = std::sync::Mutex::new(0);
+    let handle = std::thread::spawn(|| {
+        compute();
+    });
+    handle.join().unwrap();
+}";
+
+    fn span(start_line: usize, end_line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line,
+            start_column: 1,
+            end_line,
+            end_column: 1,
+        }
+    }
+
+    /// A net where `main` creates a mutex, then its closure computes something and the program ends.
+    fn explorer() -> Explorer {
+        let net = NetBuilder::new()
+            .place("PROGRAM_START", 1)
+            .place("main_BB1", 0)
+            .place("main_closure_0_BB1", 0)
+            .place("PROGRAM_END", 0)
+            .transition_with_span("std_sync_Mutex_T_new_0_CALL", span(2, 2))
+            .transition_with_span("compute_0_CALL", span(4, 4))
+            .transition("PROGRAM_RETURN")
+            .input("PROGRAM_START", "std_sync_Mutex_T_new_0_CALL")
+            .output("std_sync_Mutex_T_new_0_CALL", "main_BB1")
+            .input("main_BB1", "compute_0_CALL")
+            .output("compute_0_CALL", "main_closure_0_BB1")
+            .input("main_closure_0_BB1", "PROGRAM_RETURN")
+            .output("PROGRAM_RETURN", "PROGRAM_END")
+            .build();
+        let functions = vec![
+            ("main".to_string(), Some(span(1, 7))),
+            ("main::{closure#0}".to_string(), Some(span(3, 5))),
+        ];
+        Explorer::new(net, functions, SOURCE_CODE).unwrap()
+    }
+
+    #[test]
+    fn transitions_belong_to_the_innermost_function() {
+        let explorer = explorer();
+        let functions = explorer.functions();
+
+        assert_eq!(functions.len(), 3);
+        assert_eq!(functions[0].transitions, [0]);
+        assert_eq!(functions[1].transitions, [1]);
+        assert_eq!(functions[2].name, "(program)");
+        assert_eq!(functions[2].transitions, [2]);
+        assert_eq!(explorer.transitions_at_line(4), [1]);
+    }
+
+    #[test]
+    fn firing_transitions_updates_the_marking() {
+        let mut explorer = explorer();
+        assert!(explorer.is_enabled(0));
+        assert!(explorer.fire(1).is_err());
+
+        explorer.fire(0).unwrap();
+        explorer.fire(1).unwrap();
+
+        assert_eq!(explorer.marked_places(), [("main_closure_0_BB1", 1)]);
+        assert_eq!(
+            explorer.query("tokens main_closure_0_BB1").lines,
+            ["The place `main_closure_0_BB1` has 1 tokens"]
+        );
+        explorer.reset();
+        assert_eq!(explorer.marked_places(), [("PROGRAM_START", 1)]);
+    }
+
+    #[test]
+    fn queries_select_transitions_and_search_deadlocks() {
+        let explorer = explorer();
+
+        assert_eq!(
+            explorer.query("find CALL"),
+            QueryOutcome {
+                lines: vec!["2 transitions contain `CALL`".to_string()],
+                transitions: Some(vec![0, 1]),
+            }
+        );
+        assert_eq!(explorer.query("line 2").transitions, Some(vec![0]));
+        assert_eq!(
+            explorer.query("deadlock").lines,
+            ["No deadlock in the 4 reachable markings"]
+        );
+        assert!(explorer.query("livelock").lines[0].starts_with("Unknown query"));
+    }
+}
@@ -0,0 +1,57 @@
+//! A fast mode that only runs the structural detectors, for quick feedback in editors.
+//!
+//! The lock operations are read from the MIR representation of every function without building
+//! the Petri net, see `cargo_check_deadlock_core::analysis::lint`. Each finding is printed on its own line,
+//! starting with the location in the source code. The command fails if a pattern is found,
+//! like a new finding with a baseline.
+
+use clap::Parser;
+use log::info;
+
+use crate::cargo_result::CargoResult;
+use crate::crate_root::find_crate_root;
+
+use cargo_check_deadlock_translator::Options;
+
+/// Find double locks, lock-order inversions, guards held across `.await` and condition variables
+/// used with different mutexes without building the net.
+#[derive(Debug, Parser)]
+pub struct LintArgs {
+    /// The path to the Rust source code file or to the folder of the crate to lint.
+    path: std::path::PathBuf,
+
+    /// An additional argument passed as is to the compiler, e.g. `--edition=2021`. Can be given several times.
+    #[arg(long = "rustc-arg", value_name = "ARG", allow_hyphen_values = true)]
+    rustc_args: Vec<String>,
+}
+
+impl LintArgs {
+    pub fn exec(&self) -> CargoResult {
+        if !self.path.exists() {
+            let err_str = format!(
+                "Source code file at {} does not exist",
+                &self.path.to_string_lossy()
+            );
+            return CargoResult::SourceFileNotFound(err_str);
+        }
+        let crate_root = match find_crate_root(&self.path) {
+            Ok(crate_root) => crate_root,
+            Err(err_str) => return CargoResult::SourceFileNotFound(err_str),
+        };
+
+        info!(
+            "Linting the crate with root {}",
+            crate_root.path.to_string_lossy()
+        );
+        let options = Options {
+            library: crate_root.is_library,
+            rustc_args: self.rustc_args.clone(),
+            ..Options::default()
+        };
+        match cargo_check_deadlock_translator::lint(crate_root.path, &options) {
+            Ok(report) if report.is_empty() => CargoResult::Lint(report.to_string()),
+            Ok(report) => CargoResult::LintFindings(report.to_string()),
+            Err(err_str) => CargoResult::TranslationError(err_str.to_string()),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// This feature gate is necessary to access the internal crates of the compiler.
+// It has existed for a long time and since the compiler internals will never be stabilized,
+// the situation will probably stay like this.
+// <https://doc.rust-lang.org/unstable-book/language-features/rustc-private.html>
+#![feature(rustc_private)]
+
+mod cargo_result;
+mod check_deadlock;
+mod cli;
+mod compare;
+mod crate_root;
+mod demo;
+mod explorer;
+mod lint;
+mod net_diff;
+mod output_format;
+mod project;
+mod trace;
+mod tui;
+mod verify;
+
+use clap::Parser;
+
+use cargo_result::CargoResult::{
+    BaselineError, CrossCheckError, DeadlockAnalysis, DryRun, Interrupted, Lint, LintFindings,
+    ModelCheckerError, NewFindings, OutputFolderNotFound, OutputGenerationError, PluginError,
+    SelfCheckError, SimpleTranslation, SourceFileNotFound, TerminalError, TranslationError,
+};
+
+fn main() {
+    let args = cli::Command::parse();
+
+    match args.exec() {
+        SourceFileNotFound(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(1);
+        }
+        OutputFolderNotFound(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(2);
+        }
+        TranslationError(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(3);
+        }
+        OutputGenerationError(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(4);
+        }
+        PluginError(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(5);
+        }
+        SelfCheckError(err_str) => {
+            eprintln!("The translated net violates a structural invariant:\n{err_str}");
+            std::process::exit(6);
+        }
+        BaselineError(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(7);
+        }
+        CrossCheckError(err_str) => {
+            eprintln!("The net cannot reproduce an execution of the program:\n{err_str}");
+            std::process::exit(10);
+        }
+        TerminalError(err_str) => {
+            eprintln!("The terminal user interface failed: {err_str}");
+            std::process::exit(9);
+        }
+        ModelCheckerError(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(11);
+        }
+        Interrupted(message) => {
+            eprintln!("{message}");
+            // The exit code of a process terminated by `SIGINT`.
+            std::process::exit(130);
+        }
+        NewFindings(message) => {
+            println!("Result: {message}");
+            std::process::exit(8);
+        }
+        DeadlockAnalysis(message) => {
+            println!("Result: {message}");
+        }
+        DryRun(plan) => {
+            println!("{plan}");
+        }
+        Lint(report) => {
+            println!("{report}");
+        }
+        LintFindings(report) => {
+            println!("{report}");
+            std::process::exit(8);
+        }
+        SimpleTranslation => {}
+    }
+}
@@ -0,0 +1,58 @@
+//! Visual comparison of two nets exported to JSON, e.g. before and after a concurrency-sensitive patch.
+//!
+//! The nets are loaded with `NetData::from_json` and drawn together as a single DOT graph,
+//! see `cargo_check_deadlock_core::report::net_diff_dot`. Unlike `compare`, the programs are not translated,
+//! so the nets can come from an earlier run, another machine or another version of the tool.
+
+use clap::Parser;
+use log::info;
+
+use crate::cargo_result::CargoResult;
+
+use cargo_check_deadlock_core::report::net_diff_dot::net_diff_dot;
+use cargo_check_deadlock_core::NetData;
+
+/// Draw the differences between two nets exported to JSON as a DOT graph.
+#[derive(Debug, Parser)]
+pub struct NetDiffArgs {
+    /// The path to the JSON export of the old net.
+    old: std::path::PathBuf,
+
+    /// The path to the JSON export of the new net.
+    new: std::path::PathBuf,
+
+    /// The path of the DOT file to write. If not specified, the graph is printed to the standard output.
+    #[arg(long, value_name = "PATH")]
+    output: Option<std::path::PathBuf>,
+}
+
+impl NetDiffArgs {
+    pub fn exec(&self) -> CargoResult {
+        let old = match read_net(&self.old) {
+            Ok(net) => net,
+            Err(err_str) => return CargoResult::SourceFileNotFound(err_str),
+        };
+        let new = match read_net(&self.new) {
+            Ok(net) => net,
+            Err(err_str) => return CargoResult::SourceFileNotFound(err_str),
+        };
+
+        let dot = net_diff_dot(&old, &new);
+        let Some(output) = &self.output else {
+            print!("{dot}");
+            return CargoResult::SimpleTranslation;
+        };
+        info!("Writing the graph to {}...", output.to_string_lossy());
+        match std::fs::write(output, dot) {
+            Ok(()) => CargoResult::SimpleTranslation,
+            Err(err) => CargoResult::OutputGenerationError(err.to_string()),
+        }
+    }
+}
+
+/// Reads the net exported to JSON at the given path.
+fn read_net(path: &std::path::Path) -> Result<NetData, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|err| format!("Net at {} cannot be read: {err}", path.to_string_lossy()))?;
+    NetData::from_json(&json).map_err(|err_str| format!("{}: {err_str}", path.to_string_lossy()))
+}
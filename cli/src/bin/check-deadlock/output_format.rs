@@ -0,0 +1,262 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use log::info;
+
+use cargo_check_deadlock_core::data_structures::interchange_formats::write_greatspn_def;
+use cargo_check_deadlock_core::data_structures::layout::Layout;
+use cargo_check_deadlock_core::data_structures::pnml_dialect::PnmlDialect;
+use cargo_check_deadlock_core::graphviz;
+use cargo_check_deadlock_core::report::annotated_source::{annotate_source, annotated_filenames};
+use cargo_check_deadlock_core::report::coverage::coverage_report;
+use cargo_check_deadlock_core::report::interactions::InteractionMatrix;
+use cargo_check_deadlock_core::report::lola_comments::{
+    companion_formula_files, formula_file, lola_header,
+};
+use cargo_check_deadlock_core::report::mcc_bundle::write_mcc_bundle;
+use cargo_check_deadlock_core::report::pnml_pages::pnml_with_thread_pages;
+use cargo_check_deadlock_core::report::property_templates::{lola_properties, smv_properties};
+use cargo_check_deadlock_core::Translation;
+
+/// The subfolder of the cache folder where the layouts computed by Graphviz are stored.
+pub const LAYOUT_CACHE_SUBFOLDER: &str = "layouts";
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+/// Possible file formats for the output files
+pub enum OutputFormat {
+    /// Petri Net Markup Language - <https://www.pnml.org/>
+    Pnml,
+    /// PNML in the dialect accepted by the editor `WoPeD` - <https://woped.dhbw-karlsruhe.de/>
+    PnmlWoped,
+    /// PNML in the dialect accepted by the editor Snoopy - <https://www-dssz.informatik.tu-cottbus.de/DSSZ/Software/Snoopy>
+    PnmlSnoopy,
+    /// PNML with the control flow of every spawned thread in a nested page
+    PnmlPages,
+    /// PNML with the positions of the nodes computed by Graphviz - <https://graphviz.org/>
+    PnmlLayout,
+    /// PNML with the span of the source code of every node as tool-specific data
+    PnmlSpans,
+    /// `LoLA` - A Low Level Petri Net Analyzer - <https://theo.informatik.uni-rostock.de/theo-forschung/tools/lola/>
+    Lola,
+    /// `LoLA` format preceded by comments that map the places to the source code
+    AnnotatedLola,
+    /// Deadlock formula for `LoLA` preceded by comments that explain it
+    Formula,
+    /// One formula file for `LoLA` per canned property, e.g. `net.program_end_reachable.formula`
+    CompanionFormulas,
+    /// Property templates for every synchronization primitive in `LoLA` syntax
+    LolaProperties,
+    /// Property templates for every synchronization primitive in SMV syntax - <https://nusmv.fbk.eu/>
+    SmvProperties,
+    /// DOT (graph description language) - <https://graphviz.org/>
+    Dot,
+    /// DOT with the span of the source code of every node as its tooltip
+    DotSpans,
+    /// SVG image of the net drawn by Graphviz - <https://graphviz.org/>
+    Svg,
+    /// Mermaid flowchart that can be pasted into Markdown - <https://mermaid.js.org/>
+    Mermaid,
+    /// Standalone `LaTeX` document with a `TikZ` picture of the net - <https://tikz.dev/library-petri>
+    Tikz,
+    /// Format of the APT toolkit for the synthesis and analysis of Petri nets - <https://github.com/CvO-Theory/apt>
+    Apt,
+    /// `.g` format of Petrify - <https://www.cs.upc.edu/~jordicf/petrify/>
+    Petrify,
+    /// `.net` format of the TINA toolbox - <https://projects.laas.fr/tina/>
+    Tina,
+    /// `.net` format of `GreatSPN` with exponential transitions of rate 1 - <https://github.com/greatspn/SOURCES>
+    GreatSpn,
+    /// `.def` file of `GreatSPN` without performance measures, read together with the `.net` file
+    GreatSpnDef,
+    /// Folder with the net and the properties in the layout of the Model Checking Contest - <https://mcc.lip6.fr/>
+    MccBundle,
+    /// Copy of the source code annotated with the transitions and synchronization primitives per line
+    AnnotatedSource,
+    /// JSON description of the net, e.g. to load it in the browser playground
+    Json,
+    /// Summary of the user-defined functions and lines translated versus abstracted per module
+    Coverage,
+    /// JSON matrix of the synchronization primitives used by every thread, in order of first use
+    Interactions,
+    /// JSON mapping from the basic blocks of every translated function to the places and transitions of the net
+    BlockMapping,
+    /// JSON map from the label of every place and transition to the function, line and MIR text that produced it
+    LabelMap,
+    /// JSON table from the identifiers in the `LoLA` file to the labels of the net, for the shorter naming schemes
+    NameTable,
+}
+
+impl OutputFormat {
+    /// Converts the result of the translation to an output file named `filename` in the given output folder.
+    /// The MCC bundle is written to a folder named like the file instead.
+    /// The companion formulas are written to one file per property, named like the file followed by the property.
+    /// The path to the source code file is only used by the formats that include the source code.
+    /// The layouts computed by Graphviz are reused from the given cache folder, if any, see `graphviz::layout`.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be created, then the function returns an error.
+    /// If the source code file cannot be read, then the function returns an error.
+    /// If Graphviz cannot compute the layout or draw the net, then the function returns an error.
+    /// If the Petri net cannot be written to the file, then the function returns an error.
+    pub fn create_output_file(
+        self,
+        translation: &Translation,
+        source_path: &std::path::Path,
+        filename: &str,
+        output_folder: &std::path::Path,
+        layout_cache: Option<&std::path::Path>,
+    ) -> Result<(), std::io::Error> {
+        let mut filepath = output_folder.to_path_buf();
+        filepath.push(filename);
+        filepath.set_extension(self.to_string());
+
+        if self == Self::MccBundle {
+            info!("Creating output folder {}...", filepath.to_string_lossy());
+            return write_mcc_bundle(translation, &filepath);
+        }
+        if self == Self::CompanionFormulas {
+            for (property, contents) in companion_formula_files(translation) {
+                let mut filepath = output_folder.to_path_buf();
+                filepath.push(format!("{filename}.{property}.formula"));
+                info!("Creating output file {}...", filepath.to_string_lossy());
+                std::fs::write(filepath, contents)?;
+            }
+            return Ok(());
+        }
+        info!("Creating output file {}...", filepath.to_string_lossy());
+        let mut file = std::fs::File::create(filepath)?;
+        match self {
+            Self::Dot => translation.net.to_dot(&mut file),
+            Self::DotSpans => translation.net.to_dot_with_spans(&mut file),
+            Self::Svg => {
+                let (dot, layout) = graphviz_layout(translation, layout_cache)?;
+                let svg = graphviz::render_svg(&dot, &layout).map_err(std::io::Error::other)?;
+                file.write_all(svg.as_bytes())
+            }
+            Self::Mermaid => translation.net.to_mermaid(&mut file),
+            Self::Tikz => translation.net.to_tikz(&mut file),
+            Self::Lola => translation.net.to_lola(&mut file),
+            Self::AnnotatedLola => {
+                file.write_all(lola_header(translation).as_bytes())?;
+                translation.net.to_lola(&mut file)
+            }
+            Self::Formula => file.write_all(formula_file().as_bytes()),
+            Self::LolaProperties => file.write_all(lola_properties(translation).as_bytes()),
+            Self::SmvProperties => file.write_all(smv_properties(translation).as_bytes()),
+            Self::Pnml => translation.net.to_pnml(&mut file),
+            Self::PnmlSpans => translation.net.to_pnml_with_spans(&mut file),
+            Self::PnmlWoped => translation
+                .net
+                .to_pnml_dialect(&mut file, PnmlDialect::Woped),
+            Self::PnmlSnoopy => translation
+                .net
+                .to_pnml_dialect(&mut file, PnmlDialect::Snoopy),
+            Self::PnmlPages => file.write_all(pnml_with_thread_pages(translation)?.as_bytes()),
+            Self::PnmlLayout => {
+                let (_, layout) = graphviz_layout(translation, layout_cache)?;
+                translation.net.to_pnml_with_layout(&mut file, &layout)
+            }
+            Self::Apt => translation.net.to_apt(&mut file),
+            Self::Petrify => translation.net.to_petrify(&mut file),
+            Self::Tina => translation.net.to_tina(&mut file),
+            Self::GreatSpn => translation.net.to_greatspn(&mut file),
+            Self::GreatSpnDef => write_greatspn_def(&mut file),
+            Self::MccBundle => panic!("BUG: The MCC bundle should be written as a folder"),
+            Self::CompanionFormulas => {
+                panic!("BUG: The companion formulas should be written to one file per property")
+            }
+            Self::Json => file.write_all(translation.net.data().to_json().as_bytes()),
+            Self::AnnotatedSource => {
+                let root_filename = source_path.to_string_lossy();
+                for filename in annotated_filenames(&root_filename, translation) {
+                    // The files of other crates, e.g. expanded from their macros, may not be readable.
+                    let source_code = match std::fs::read_to_string(&filename) {
+                        Ok(source_code) => source_code,
+                        Err(_) if filename != root_filename => continue,
+                        Err(err) => return Err(err),
+                    };
+                    if filename != root_filename {
+                        writeln!(file, "\n// file: {filename}\n")?;
+                    }
+                    file.write_all(
+                        annotate_source(&source_code, &filename, translation).as_bytes(),
+                    )?;
+                }
+                Ok(())
+            }
+            Self::Coverage => {
+                let report = coverage_report(&translation.coverage, &translation.translation_order);
+                file.write_all(report.as_bytes())
+            }
+            Self::Interactions => {
+                file.write_all(InteractionMatrix::new(translation).to_json().as_bytes())
+            }
+            Self::BlockMapping => {
+                let mapping = translation
+                    .block_mapping
+                    .as_ref()
+                    .expect("BUG: The block mapping should be recorded when it is exported");
+                file.write_all(mapping.to_json().as_bytes())
+            }
+            Self::LabelMap => {
+                let label_map = translation
+                    .label_map
+                    .as_ref()
+                    .expect("BUG: The label map should be recorded when it is exported");
+                file.write_all(label_map.to_json().as_bytes())
+            }
+            Self::NameTable => {
+                file.write_all(translation.net.lola_identifiers().to_json().as_bytes())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Dot | Self::DotSpans => write!(f, "dot"),
+            Self::Svg => write!(f, "svg"),
+            Self::Mermaid => write!(f, "mmd"),
+            Self::Tikz => write!(f, "tex"),
+            Self::Lola | Self::AnnotatedLola => write!(f, "lola"),
+            Self::Formula => write!(f, "formula"),
+            Self::CompanionFormulas => write!(f, "formulas"),
+            Self::LolaProperties => write!(f, "properties.lola"),
+            Self::SmvProperties => write!(f, "properties.smv"),
+            Self::Pnml
+            | Self::PnmlWoped
+            | Self::PnmlSnoopy
+            | Self::PnmlPages
+            | Self::PnmlLayout
+            | Self::PnmlSpans => write!(f, "pnml"),
+            Self::Apt => write!(f, "apt"),
+            Self::Petrify => write!(f, "g"),
+            Self::Tina => write!(f, "net"),
+            Self::GreatSpn => write!(f, "gspn.net"),
+            Self::GreatSpnDef => write!(f, "gspn.def"),
+            Self::MccBundle => write!(f, "mcc"),
+            Self::Json => write!(f, "json"),
+            Self::AnnotatedSource => write!(f, "annotated.rs"),
+            Self::Coverage => write!(f, "coverage.txt"),
+            Self::Interactions => write!(f, "interactions.json"),
+            Self::BlockMapping => write!(f, "mapping.json"),
+            Self::LabelMap => write!(f, "map.json"),
+            Self::NameTable => write!(f, "names.json"),
+        }
+    }
+}
+
+/// Returns the net in DOT format and its layout computed by Graphviz or read from the cache folder.
+fn graphviz_layout(
+    translation: &Translation,
+    layout_cache: Option<&std::path::Path>,
+) -> Result<(String, Layout), std::io::Error> {
+    let mut dot = Vec::new();
+    translation.net.to_dot(&mut dot)?;
+    let dot = String::from_utf8(dot).map_err(std::io::Error::other)?;
+    let layout = graphviz::layout(&translation.net.data(), &dot, layout_cache)
+        .map_err(std::io::Error::other)?;
+    Ok((dot, layout))
+}
@@ -0,0 +1,384 @@
+//! Translation of a Cargo project instead of a single source code file.
+//!
+//! The target to translate is resolved from the output of `cargo metadata`.
+//! Then `cargo check` builds the dependencies and compiles the target with `cargo-check-deadlock-driver`
+//! as `RUSTC_WORKSPACE_WRAPPER`, so the translator receives the same `--extern` and `--edition` flags
+//! as `rustc` in a normal build. The driver writes the output files named after the crate,
+//! e.g. `my_crate.lola`, which is then analyzed like the output of a single file.
+//!
+//! The build uses its own target folder inside the target folder of the project.
+//! The artifacts of the package are removed before every run, since Cargo would otherwise skip
+//! the compilation of an unchanged package and the driver would not translate it again.
+
+use clap::{Parser, ValueEnum};
+use log::info;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cargo_result::CargoResult;
+use crate::output_format::OutputFormat;
+
+use cargo_check_deadlock_core::model_checker::lola;
+
+/// Name of the folder for the build inside the target folder of the project.
+const TARGET_SUBFOLDER: &str = "check-deadlock";
+
+/// Translate and analyze a binary or library of a Cargo project.
+#[derive(Debug, Parser)]
+pub struct ProjectArgs {
+    /// The path to the `Cargo.toml` of the project.
+    /// If not specified, Cargo looks for it in the current working directory and its parents.
+    #[arg(long, value_name = "PATH")]
+    manifest_path: Option<PathBuf>,
+
+    /// The package that contains the target. Required if the workspace has several packages with binaries.
+    #[arg(long, short)]
+    package: Option<String>,
+
+    /// The binary to translate. Required if the package has several binaries.
+    #[arg(long, value_name = "NAME")]
+    bin: Option<String>,
+
+    /// The path of the function where the translation starts, e.g. `my_mod::worker`.
+    /// If set, the library of the package is translated when the package has no binary.
+    #[arg(long, value_name = "PATH")]
+    entry_function: Option<String>,
+
+    /// The path to a valid folder where the output files should be created.
+    /// If not specified, the current working directory is used.
+    #[arg(long, default_value = ".")]
+    output_folder: PathBuf,
+
+    /// The output formats, separated by commas. The `LoLA` format is always created for the analysis.
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "lola")]
+    formats: Vec<OutputFormat>,
+
+    /// If set, the reachability analysis to find deadlocks is skipped.
+    #[arg(long)]
+    skip_analysis: bool,
+}
+
+/// A target of a Cargo package that can be translated.
+#[derive(Debug, PartialEq, Eq)]
+struct Target {
+    /// The name of the package, used to select it in `cargo check`.
+    package: String,
+    /// The name of the binary, or `None` for the library of the package.
+    bin: Option<String>,
+    /// The name of the crate, i.e. the name of the target with dashes replaced by underscores.
+    crate_name: String,
+}
+
+impl ProjectArgs {
+    pub fn exec(&self) -> CargoResult {
+        if !self.output_folder.exists() {
+            let err_str = format!(
+                "Output folder at {} does not exist",
+                &self.output_folder.to_string_lossy()
+            );
+            return CargoResult::OutputFolderNotFound(err_str);
+        }
+
+        info!("Reading the metadata of the Cargo project...");
+        let metadata = match self.cargo_metadata() {
+            Ok(metadata) => metadata,
+            Err(err_str) => return CargoResult::TranslationError(err_str),
+        };
+        let target = match resolve_target(
+            &metadata,
+            self.package.as_deref(),
+            self.bin.as_deref(),
+            self.entry_function.is_some(),
+        ) {
+            Ok(target) => target,
+            Err(err_str) => return CargoResult::TranslationError(err_str),
+        };
+        info!(
+            "Translating the crate {} of the project...",
+            target.crate_name
+        );
+        let target_folder = metadata["target_directory"]
+            .as_str()
+            .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+            .join(TARGET_SUBFOLDER);
+        if let Err(err_str) = self.cargo_check(&target, &target_folder) {
+            return CargoResult::TranslationError(err_str);
+        }
+
+        if self.skip_analysis {
+            return CargoResult::SimpleTranslation;
+        }
+        let mut filepath = self.output_folder.clone();
+        filepath.push(&target.crate_name);
+        filepath.set_extension(OutputFormat::Lola.to_string());
+        if !filepath.exists() {
+            return CargoResult::TranslationError(format!(
+                "The crate {} was not translated: It has no function where the translation starts",
+                target.crate_name
+            ));
+        }
+        info!("Running the reachability analysis...");
+        let message = if lola::check_deadlock(&filepath) {
+            "Deadlock can be reached according to the model checker `LoLA`"
+        } else {
+            "The program is deadlock-free according to the model checker `LoLA`"
+        };
+        CargoResult::DeadlockAnalysis(message.to_string())
+    }
+
+    /// Runs `cargo metadata` for the packages of the workspace and parses its output.
+    fn cargo_metadata(&self) -> Result<serde_json::Value, String> {
+        let mut cmd = Command::new(cargo());
+        cmd.args(["metadata", "--format-version=1", "--no-deps"]);
+        if let Some(manifest_path) = &self.manifest_path {
+            cmd.arg("--manifest-path").arg(manifest_path);
+        }
+        let output = cmd
+            .output()
+            .map_err(|err| format!("Could not run `cargo metadata`: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Could not read the metadata of the Cargo project:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        serde_json::from_slice(&output.stdout)
+            .map_err(|err| format!("Could not parse the output of `cargo metadata`: {err}"))
+    }
+
+    /// Runs `cargo check` on the target with the driver as `RUSTC_WORKSPACE_WRAPPER`.
+    /// The previous artifacts of the package are removed first so that the target is always compiled.
+    fn cargo_check(&self, target: &Target, target_folder: &Path) -> Result<(), String> {
+        let output_folder = self
+            .output_folder
+            .canonicalize()
+            .map_err(|err| format!("Could not resolve the output folder: {err}"))?;
+        let mut formats: Vec<String> = self
+            .formats
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .map(|value| value.get_name().to_string())
+            .collect();
+        if !self.formats.contains(&OutputFormat::Lola) {
+            formats.push(OutputFormat::Lola.to_string());
+        }
+
+        let mut clean = self.cargo_command("clean", target_folder);
+        clean.arg("--package").arg(&target.package);
+        run(&mut clean, "clean")?;
+
+        let mut check = self.cargo_command("check", target_folder);
+        check.arg("--package").arg(&target.package);
+        match &target.bin {
+            Some(bin) => check.arg("--bin").arg(bin),
+            None => check.arg("--lib"),
+        };
+        check
+            .env("RUSTC_WORKSPACE_WRAPPER", driver_path()?)
+            .env("CARGO_CHECK_DEADLOCK_OUTPUT_FOLDER", output_folder)
+            .env("CARGO_CHECK_DEADLOCK_FORMATS", formats.join(","));
+        if let Some(entry_function) = &self.entry_function {
+            check.env("CARGO_CHECK_DEADLOCK_ENTRY_FUNCTION", entry_function);
+        }
+        run(&mut check, "check")
+    }
+
+    /// Returns a `cargo` command for the subcommand in the project with the given target folder.
+    fn cargo_command(&self, subcommand: &str, target_folder: &Path) -> Command {
+        let mut cmd = Command::new(cargo());
+        cmd.arg(subcommand).arg("--target-dir").arg(target_folder);
+        if let Some(manifest_path) = &self.manifest_path {
+            cmd.arg("--manifest-path").arg(manifest_path);
+        }
+        cmd
+    }
+}
+
+/// Returns the `cargo` executable that invoked this program, or `cargo` from the `PATH`.
+fn cargo() -> std::ffi::OsString {
+    std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into())
+}
+
+/// Returns the path of `cargo-check-deadlock-driver`, which is installed next to this program.
+fn driver_path() -> Result<PathBuf, String> {
+    let current_exe = std::env::current_exe()
+        .map_err(|err| format!("Could not find the path of the executable: {err}"))?;
+    Ok(current_exe.with_file_name(format!(
+        "cargo-check-deadlock-driver{}",
+        std::env::consts::EXE_SUFFIX
+    )))
+}
+
+/// Runs the `cargo` command and returns an error if it fails.
+/// The output of Cargo is shown to the user as it is.
+fn run(cmd: &mut Command, subcommand: &str) -> Result<(), String> {
+    let status = cmd
+        .status()
+        .map_err(|err| format!("Could not run `cargo {subcommand}`: {err}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`cargo {subcommand}` failed with {status}"))
+    }
+}
+
+/// Resolves the target to translate from the metadata of the workspace.
+/// The binary is selected by name, or else it is the only binary of the package or of the workspace.
+/// If there is no binary and `allow_library` is set, the library of the package is selected.
+fn resolve_target(
+    metadata: &serde_json::Value,
+    package: Option<&str>,
+    bin: Option<&str>,
+    allow_library: bool,
+) -> Result<Target, String> {
+    let packages: Vec<&serde_json::Value> = metadata["packages"]
+        .as_array()
+        .map(|packages| {
+            packages
+                .iter()
+                .filter(|candidate| package.is_none_or(|name| candidate["name"] == name))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(name) = package.filter(|_| packages.is_empty()) {
+        return Err(format!("The package `{name}` is not in the workspace"));
+    }
+
+    let targets_of_kind = |kind: &str| -> Vec<(String, String)> {
+        packages
+            .iter()
+            .flat_map(|candidate| {
+                let package_name = candidate["name"].as_str().unwrap_or_default();
+                candidate["targets"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(move |target| {
+                        target["kind"]
+                            .as_array()
+                            .is_some_and(|kinds| kinds.iter().any(|k| k == kind))
+                    })
+                    .filter_map(move |target| {
+                        Some((
+                            package_name.to_string(),
+                            target["name"].as_str()?.to_string(),
+                        ))
+                    })
+            })
+            .collect()
+    };
+
+    let binaries: Vec<(String, String)> = targets_of_kind("bin")
+        .into_iter()
+        .filter(|(_, name)| bin.is_none_or(|bin| name == bin))
+        .collect();
+    match binaries.as_slice() {
+        [(package, name)] => {
+            return Ok(Target {
+                package: package.clone(),
+                bin: Some(name.clone()),
+                crate_name: name.replace('-', "_"),
+            })
+        }
+        [] => {}
+        _ => {
+            let names: Vec<&str> = binaries.iter().map(|(_, name)| name.as_str()).collect();
+            return Err(format!(
+                "Several binaries found: {}. Select one with `--bin` or `--package`",
+                names.join(", ")
+            ));
+        }
+    }
+    if let Some(bin) = bin {
+        return Err(format!("The binary `{bin}` is not in the workspace"));
+    }
+
+    let libraries = if allow_library {
+        targets_of_kind("lib")
+    } else {
+        Vec::new()
+    };
+    match libraries.as_slice() {
+        [(package, name)] => Ok(Target {
+            package: package.clone(),
+            bin: None,
+            crate_name: name.replace('-', "_"),
+        }),
+        [] if allow_library => Err(
+            "No binary or library found in the workspace: Select a package with `--package`"
+                .to_string(),
+        ),
+        [] => Err(
+            "No binary found in the workspace: Use `--entry-function` to translate a library"
+                .to_string(),
+        ),
+        _ => Err("Several libraries found: Select one with `--package`".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod project_tests {
+    use super::*;
+
+    fn metadata() -> serde_json::Value {
+        serde_json::json!({
+            "packages": [
+                {
+                    "name": "server",
+                    "targets": [
+                        { "name": "server", "kind": ["lib"] },
+                        { "name": "server-cli", "kind": ["bin"] },
+                        { "name": "migrate", "kind": ["bin"] }
+                    ]
+                },
+                {
+                    "name": "worker-pool",
+                    "targets": [{ "name": "worker-pool", "kind": ["lib"] }]
+                }
+            ],
+            "target_directory": "/project/target"
+        })
+    }
+
+    #[test]
+    fn resolve_target_selects_the_binary_by_name() {
+        let target = resolve_target(&metadata(), None, Some("server-cli"), false).unwrap();
+
+        assert_eq!(
+            target,
+            Target {
+                package: "server".to_string(),
+                bin: Some("server-cli".to_string()),
+                crate_name: "server_cli".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_target_rejects_several_binaries() {
+        let result = resolve_target(&metadata(), Some("server"), None, false);
+
+        assert_eq!(
+            result,
+            Err(
+                "Several binaries found: server-cli, migrate. Select one with `--bin` or `--package`"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn resolve_target_selects_the_library_with_an_entry_function() {
+        let target = resolve_target(&metadata(), Some("worker-pool"), None, true).unwrap();
+
+        assert_eq!(
+            target,
+            Target {
+                package: "worker-pool".to_string(),
+                bin: None,
+                crate_name: "worker_pool".to_string(),
+            }
+        );
+        assert!(resolve_target(&metadata(), Some("worker-pool"), None, false).is_err());
+    }
+}
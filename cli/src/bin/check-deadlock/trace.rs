@@ -0,0 +1,94 @@
+//! Maps a counterexample of the model checker back to the source code of the program.
+//!
+//! The program is translated again to recover the spans of the transitions, then the witness path
+//! is replayed on the net, see `cargo_check_deadlock_core::report::source_trace`.
+//! The witness path is the file written by `lola --path`, one transition per line.
+//! The transitions may be given by their identifiers in the `LoLA` file or by their labels.
+
+use clap::Parser;
+use log::info;
+use std::collections::BTreeMap;
+
+use crate::cargo_result::CargoResult;
+use crate::crate_root::find_crate_root;
+
+use cargo_check_deadlock_core::model_checker::lola::parse_witness_path;
+use cargo_check_deadlock_core::report::source_trace::{render_source_trace, source_steps};
+use cargo_check_deadlock_translator::Options;
+
+/// Print the interleaving of a witness path of `LoLA` as annotated source code.
+#[derive(Debug, Parser)]
+pub struct TraceArgs {
+    /// The path to the Rust source code file or to the folder of the crate that was verified.
+    path: std::path::PathBuf,
+
+    /// The path to the witness path written by `LoLA`, one transition per line.
+    witness: std::path::PathBuf,
+
+    /// The path of the function where the translation starts, e.g. `my_mod::worker`.
+    /// It must be the same as in the run that produced the witness path.
+    #[arg(long, value_name = "PATH")]
+    entry_function: Option<String>,
+}
+
+impl TraceArgs {
+    pub fn exec(&self) -> CargoResult {
+        if !self.path.exists() {
+            let err_str = format!(
+                "Source code file at {} does not exist",
+                &self.path.to_string_lossy()
+            );
+            return CargoResult::SourceFileNotFound(err_str);
+        }
+        let crate_root = match find_crate_root(&self.path) {
+            Ok(crate_root) => crate_root,
+            Err(err_str) => return CargoResult::SourceFileNotFound(err_str),
+        };
+        let witness = match std::fs::read_to_string(&self.witness) {
+            Ok(witness) => witness,
+            Err(err) => {
+                let err_str = format!(
+                    "Witness path at {} cannot be read: {err}",
+                    &self.witness.to_string_lossy()
+                );
+                return CargoResult::SourceFileNotFound(err_str);
+            }
+        };
+
+        info!("Starting the translation...");
+        let options = Options {
+            entry_function: self.entry_function.clone(),
+            library: crate_root.is_library || self.entry_function.is_some(),
+            ..Options::default()
+        };
+        let translation = match cargo_check_deadlock_translator::run(crate_root.path, options) {
+            Ok(translation) => translation,
+            Err(err_str) => return CargoResult::TranslationError(err_str.to_string()),
+        };
+
+        let net = translation.net.data();
+        let path = translation
+            .net
+            .lola_identifiers()
+            .labels_of(&parse_witness_path(&witness));
+        let steps = match source_steps(&path, &translation, &net) {
+            Ok(steps) => steps,
+            Err(err_str) => {
+                return CargoResult::ModelCheckerError(format!(
+                    "The witness path cannot be replayed on the net: {err_str}"
+                ))
+            }
+        };
+
+        let mut sources: BTreeMap<String, String> = BTreeMap::new();
+        for span in steps.iter().filter_map(|step| step.span.as_ref()) {
+            if !sources.contains_key(&span.filename) {
+                if let Ok(source_code) = std::fs::read_to_string(&span.filename) {
+                    sources.insert(span.filename.clone(), source_code);
+                }
+            }
+        }
+        print!("{}", render_source_trace(&steps, &sources));
+        CargoResult::SimpleTranslation
+    }
+}
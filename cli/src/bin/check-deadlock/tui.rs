@@ -0,0 +1,410 @@
+//! A terminal user interface to explore the net of a program, e.g. on a remote machine
+//! where graphical tools to view the net are not available.
+//!
+//! The screen shows the translated functions, the transitions of the selected function,
+//! the source code around the selected transition and the current marking of the simulation.
+//! The state lives in the `Explorer`, this module only handles the keys and draws the screen.
+
+use clap::Parser;
+use log::info;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::cargo_result::CargoResult;
+use crate::explorer::Explorer;
+
+use cargo_check_deadlock_translator::Options;
+
+/// The keys available in every pane, shown until the first action.
+const KEY_HELP: [&str; 6] = [
+    "Tab: next pane   Up/Down: move   q: quit",
+    "Functions: select the function to list its transitions",
+    "Transitions: Enter fires the transition, the source code follows the selection",
+    "Source: Enter lists the transitions produced by the line",
+    "r: reset the simulation   :: run a quick query, e.g. `:deadlock` or `:find MUTEX`",
+    "Transitions marked with * are enabled in the current marking",
+];
+
+/// Explore the net of a program in the terminal and play the token game.
+#[derive(Debug, Parser)]
+pub struct TuiArgs {
+    /// The path to the Rust source code file to translate.
+    path: std::path::PathBuf,
+
+    /// The path of the function where the translation starts, e.g. `my_mod::worker`.
+    /// If not specified, the translation starts at the `main` function.
+    #[arg(long, value_name = "PATH")]
+    entry_function: Option<String>,
+}
+
+impl TuiArgs {
+    pub fn exec(&self) -> CargoResult {
+        if !self.path.exists() {
+            let err_str = format!(
+                "Source code file at {} does not exist",
+                &self.path.to_string_lossy()
+            );
+            return CargoResult::SourceFileNotFound(err_str);
+        }
+        let source_code = match std::fs::read_to_string(&self.path) {
+            Ok(source_code) => source_code,
+            Err(err) => return CargoResult::SourceFileNotFound(err.to_string()),
+        };
+
+        info!("Starting the translation...");
+        let options = Options {
+            entry_function: self.entry_function.clone(),
+            ..Options::default()
+        };
+        let translation = match cargo_check_deadlock_translator::run(self.path.clone(), options) {
+            Ok(translation) => translation,
+            Err(err_str) => return CargoResult::TranslationError(err_str.to_string()),
+        };
+        let explorer = match Explorer::from_translation(&translation, &source_code) {
+            Ok(explorer) => explorer,
+            Err(err_str) => return CargoResult::TranslationError(err_str),
+        };
+
+        let mut terminal = ratatui::init();
+        let result = App::new(explorer).run(&mut terminal);
+        ratatui::restore();
+        match result {
+            Ok(()) => CargoResult::SimpleTranslation,
+            Err(err) => CargoResult::TerminalError(err.to_string()),
+        }
+    }
+}
+
+/// The pane that receives the keys.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Functions,
+    Transitions,
+    Source,
+}
+
+struct App {
+    /// The state of the exploration.
+    explorer: Explorer,
+    /// The pane that receives the keys.
+    focus: Focus,
+    /// The selected function.
+    functions: ListState,
+    /// The selected transition in the list of transitions.
+    transitions: ListState,
+    /// The transitions listed instead of the ones of the selected function, with the title of the list.
+    /// They are selected by a quick query or by a line of the source code.
+    selection: Option<(String, Vec<usize>)>,
+    /// The line of the source code under the cursor (1-based).
+    source_line: usize,
+    /// The quick query being typed, if any.
+    input: Option<String>,
+    /// The text shown in the bottom pane, e.g. the answer to the last query.
+    output: Vec<String>,
+}
+
+impl App {
+    fn new(explorer: Explorer) -> Self {
+        Self {
+            explorer,
+            focus: Focus::Functions,
+            functions: ListState::default().with_selected(Some(0)),
+            transitions: ListState::default().with_selected(Some(0)),
+            selection: None,
+            source_line: 1,
+            input: None,
+            output: KEY_HELP.map(ToString::to_string).to_vec(),
+        }
+    }
+
+    /// Draws the screen and handles the keys until the user quits.
+    fn run(mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        self.follow_transition();
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(input) = &mut self.input {
+                match key.code {
+                    KeyCode::Enter => {
+                        let query = std::mem::take(input);
+                        self.input = None;
+                        self.run_query(&query);
+                    }
+                    KeyCode::Esc => self.input = None,
+                    KeyCode::Backspace => {
+                        input.pop();
+                    }
+                    KeyCode::Char(c) => input.push(c),
+                    _ => {}
+                }
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Tab => {
+                    self.focus = match self.focus {
+                        Focus::Functions => Focus::Transitions,
+                        Focus::Transitions => Focus::Source,
+                        Focus::Source => Focus::Functions,
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+                KeyCode::Enter => self.activate(),
+                KeyCode::Char('r') => {
+                    self.explorer.reset();
+                    self.output = vec!["The simulation is back at the initial marking".to_string()];
+                }
+                KeyCode::Char(':') => self.input = Some(String::new()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the indices of the transitions in the list of transitions.
+    fn listed_transitions(&self) -> &[usize] {
+        if let Some((_, transitions)) = &self.selection {
+            return transitions;
+        }
+        let function = self.functions.selected().unwrap_or_default();
+        &self.explorer.functions()[function].transitions
+    }
+
+    /// Returns the index of the selected transition in the net, if any.
+    fn selected_transition(&self) -> Option<usize> {
+        let position = self.transitions.selected()?;
+        self.listed_transitions().get(position).copied()
+    }
+
+    /// Moves the cursor of the focused pane up or down.
+    fn move_cursor(&mut self, delta: isize) {
+        match self.focus {
+            Focus::Functions => {
+                let len = self.explorer.functions().len();
+                let selected = move_index(self.functions.selected(), delta, len);
+                self.functions.select(Some(selected));
+                self.selection = None;
+                self.transitions.select(Some(0));
+                if let Some(span) = &self.explorer.functions()[selected].span {
+                    self.source_line = span.start_line;
+                }
+            }
+            Focus::Transitions => {
+                let len = self.listed_transitions().len();
+                let selected = move_index(self.transitions.selected(), delta, len);
+                self.transitions.select(Some(selected));
+                self.follow_transition();
+            }
+            Focus::Source => {
+                let len = self.explorer.source_lines().len();
+                let selected = move_index(self.source_line.checked_sub(1), delta, len);
+                self.source_line = selected + 1;
+            }
+        }
+    }
+
+    /// Moves the cursor in the source code to the start of the selected transition, if it has a span.
+    fn follow_transition(&mut self) {
+        let span = self
+            .selected_transition()
+            .and_then(|transition| self.explorer.net().transitions[transition].span.as_ref());
+        if let Some(span) = span {
+            self.source_line = span.start_line;
+        }
+    }
+
+    /// Handles the key `Enter` in the focused pane.
+    fn activate(&mut self) {
+        match self.focus {
+            Focus::Functions => self.focus = Focus::Transitions,
+            Focus::Transitions => {
+                let Some(transition) = self.selected_transition() else {
+                    return;
+                };
+                let label = self.explorer.net().transitions[transition].label.clone();
+                self.output = match self.explorer.fire(transition) {
+                    Ok(()) if self.explorer.is_deadlock() => {
+                        vec![
+                            format!("Fired {label}"),
+                            "The marking is a deadlock".to_string(),
+                        ]
+                    }
+                    Ok(()) => vec![format!("Fired {label}")],
+                    Err(err_str) => vec![err_str],
+                };
+            }
+            Focus::Source => {
+                let transitions = self.explorer.transitions_at_line(self.source_line);
+                let title = format!("Line {}", self.source_line);
+                self.select_transitions(title, transitions);
+            }
+        }
+    }
+
+    /// Runs the quick query and shows its answer.
+    fn run_query(&mut self, query: &str) {
+        let outcome = self.explorer.query(query);
+        self.output = outcome.lines;
+        if let Some(transitions) = outcome.transitions {
+            self.select_transitions(format!(":{query}"), transitions);
+        }
+    }
+
+    /// Lists the given transitions instead of the ones of the selected function.
+    fn select_transitions(&mut self, title: String, transitions: Vec<usize>) {
+        self.selection = Some((title, transitions));
+        self.transitions.select(Some(0));
+        self.focus = Focus::Transitions;
+        self.follow_transition();
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, bottom] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(10)]).areas(frame.area());
+        let [functions, transitions, source] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(35),
+            Constraint::Percentage(40),
+        ])
+        .areas(main);
+        let [marking, output] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(bottom);
+
+        self.draw_functions(frame, functions);
+        self.draw_transitions(frame, transitions);
+        self.draw_source(frame, source);
+        self.draw_marking(frame, marking);
+        self.draw_output(frame, output);
+    }
+
+    /// Returns the block around a pane, highlighted if the pane has the focus.
+    fn block(&self, title: String, pane: Focus) -> Block<'static> {
+        let block = Block::bordered().title(title);
+        if self.focus == pane {
+            block.border_style(Style::new().fg(Color::Cyan))
+        } else {
+            block
+        }
+    }
+
+    fn draw_functions(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .explorer
+            .functions()
+            .iter()
+            .map(|function| {
+                ListItem::new(format!(
+                    "{} ({})",
+                    function.name,
+                    function.transitions.len()
+                ))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(self.block("Functions".to_string(), Focus::Functions))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.functions);
+    }
+
+    fn draw_transitions(&mut self, frame: &mut Frame, area: Rect) {
+        let title = match &self.selection {
+            Some((title, _)) => format!("Transitions of {title}"),
+            None => "Transitions".to_string(),
+        };
+        let items: Vec<ListItem> = self
+            .listed_transitions()
+            .iter()
+            .map(|transition| {
+                let label = &self.explorer.net().transitions[*transition].label;
+                if self.explorer.is_enabled(*transition) {
+                    ListItem::new(format!("* {label}")).style(Style::new().fg(Color::Green))
+                } else {
+                    ListItem::new(format!("  {label}"))
+                }
+            })
+            .collect();
+        let list = List::new(items)
+            .block(self.block(title, Focus::Transitions))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.transitions);
+    }
+
+    fn draw_source(&self, frame: &mut Frame, area: Rect) {
+        let span = self
+            .selected_transition()
+            .and_then(|transition| self.explorer.net().transitions[transition].span.as_ref());
+        let title = span.map_or_else(|| "Source".to_string(), |span| format!("Source {span}"));
+        let lines: Vec<Line> = self
+            .explorer
+            .source_lines()
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let number = index + 1;
+                let mut style = Style::new();
+                if span.is_some_and(|span| span.start_line <= number && number <= span.end_line) {
+                    style = style.fg(Color::Yellow);
+                }
+                if self.focus == Focus::Source && number == self.source_line {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Line::from(vec![
+                    Span::styled(format!("{number:>4} "), Style::new().fg(Color::DarkGray)),
+                    Span::styled(text.as_str(), style),
+                ])
+            })
+            .collect();
+        // Keep the cursor in the middle of the pane.
+        let scroll = self
+            .source_line
+            .saturating_sub(usize::from(area.height / 2));
+        let paragraph = Paragraph::new(lines)
+            .block(self.block(title, Focus::Source))
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+        frame.render_widget(paragraph, area);
+    }
+
+    fn draw_marking(&self, frame: &mut Frame, area: Rect) {
+        let title = format!("Marking after {} steps", self.explorer.history().len());
+        let items: Vec<ListItem> = self
+            .explorer
+            .marked_places()
+            .into_iter()
+            .map(|(place, tokens)| ListItem::new(format!("{place}: {tokens}")))
+            .collect();
+        frame.render_widget(List::new(items).block(Block::bordered().title(title)), area);
+    }
+
+    fn draw_output(&self, frame: &mut Frame, area: Rect) {
+        let mut lines: Vec<Line> = Vec::new();
+        if let Some(input) = &self.input {
+            lines.push(Line::styled(
+                format!(":{input}"),
+                Style::new().fg(Color::Cyan),
+            ));
+        }
+        lines.extend(self.output.iter().map(|line| Line::from(line.as_str())));
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Query")),
+            area,
+        );
+    }
+}
+
+/// Moves the index up or down in a list of the given length, staying inside the list.
+fn move_index(index: Option<usize>, delta: isize, len: usize) -> usize {
+    index
+        .unwrap_or_default()
+        .saturating_add_signed(delta)
+        .min(len.saturating_sub(1))
+}
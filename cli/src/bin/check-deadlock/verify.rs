@@ -0,0 +1,148 @@
+//! A one-command deadlock check with a locally installed `LoLA`.
+//!
+//! The program is translated, the net is written in `LoLA` format and the deadlock formula
+//! is written to a `.formula` file next to it, so the same check can be repeated by hand.
+//! Then the `lola` binary is run on both files and its output is parsed into a verdict.
+//! The deadlocks in code marked with a suppression comment are excluded from the formula.
+//! The verdict is followed by the assumptions that the translation could not verify, e.g. the skipped functions.
+
+use clap::Parser;
+use log::info;
+
+use crate::cargo_result::CargoResult;
+use crate::check_deadlock::{append_assumptions, render_findings};
+use crate::crate_root::find_crate_root;
+use crate::output_format::OutputFormat;
+
+use cargo_check_deadlock_core::model_checker::lola::{self, LolaAnswer};
+use cargo_check_deadlock_core::report::findings::{
+    deadlock_finding, suppressed_places, FindingKind,
+};
+use cargo_check_deadlock_core::Translation;
+use cargo_check_deadlock_translator::Options;
+
+/// Translate a program, run `LoLA` on the net with the deadlock formula and print the verdict.
+#[derive(Debug, Parser)]
+pub struct VerifyArgs {
+    /// The path to the Rust source code file or to the folder of the crate to verify.
+    path: std::path::PathBuf,
+
+    /// The path to a valid folder where the `.lola` and `.formula` files should be created.
+    /// If not specified, the current working directory is used.
+    #[arg(long, default_value = ".")]
+    output_folder: std::path::PathBuf,
+
+    /// The path of the function where the translation starts, e.g. `my_mod::worker`.
+    /// If not specified, the translation starts at the `main` function.
+    #[arg(long, value_name = "PATH")]
+    entry_function: Option<String>,
+
+    /// The `lola` binary to run. If not specified, it is looked up in the $PATH.
+    #[arg(long, value_name = "PATH", default_value = "lola")]
+    lola: std::path::PathBuf,
+}
+
+impl VerifyArgs {
+    pub fn exec(&self) -> CargoResult {
+        if !self.path.exists() {
+            let err_str = format!(
+                "Source code file at {} does not exist",
+                &self.path.to_string_lossy()
+            );
+            return CargoResult::SourceFileNotFound(err_str);
+        }
+        let crate_root = match find_crate_root(&self.path) {
+            Ok(crate_root) => crate_root,
+            Err(err_str) => return CargoResult::SourceFileNotFound(err_str),
+        };
+        if !self.output_folder.exists() {
+            let err_str = format!(
+                "Output folder at {} does not exist",
+                &self.output_folder.to_string_lossy()
+            );
+            return CargoResult::OutputFolderNotFound(err_str);
+        }
+
+        info!("Starting the translation...");
+        let options = Options {
+            entry_function: self.entry_function.clone(),
+            library: crate_root.is_library || self.entry_function.is_some(),
+            ..Options::default()
+        };
+        let translation =
+            match cargo_check_deadlock_translator::run(crate_root.path.clone(), options) {
+                Ok(translation) => translation,
+                Err(err_str) => return CargoResult::TranslationError(err_str.to_string()),
+            };
+
+        let filename = crate_root.path.file_stem().map_or_else(
+            || "net".to_string(),
+            |stem| stem.to_string_lossy().to_string(),
+        );
+        if let Err(err) = OutputFormat::Lola.create_output_file(
+            &translation,
+            &crate_root.path,
+            &filename,
+            &self.output_folder,
+            None,
+        ) {
+            return CargoResult::OutputGenerationError(err.to_string());
+        }
+        let net_filepath = self
+            .output_folder
+            .join(&filename)
+            .with_extension(OutputFormat::Lola.to_string());
+        let formula_filepath = net_filepath.with_extension("formula");
+        info!(
+            "Writing the deadlock formula to {}...",
+            formula_filepath.to_string_lossy()
+        );
+        if let Err(err) = std::fs::write(&formula_filepath, deadlock_formula(&translation)) {
+            return CargoResult::OutputGenerationError(err.to_string());
+        }
+
+        info!("Running `{}`...", self.lola.to_string_lossy());
+        let run = match lola::verify(&self.lola, &net_filepath, &formula_filepath) {
+            Ok(run) => run,
+            Err(err_str) => return CargoResult::ModelCheckerError(err_str),
+        };
+        if let Some(markings) = run.markings {
+            println!("`LoLA` explored {markings} markings");
+        }
+        let result = match run.answer {
+            LolaAnswer::No => CargoResult::DeadlockAnalysis(
+                "The program is deadlock-free according to the model checker `LoLA`".to_string(),
+            ),
+            LolaAnswer::Yes => {
+                let mut message =
+                    "Deadlock can be reached according to the model checker `LoLA`".to_string();
+                if let Some(path) = run.path {
+                    let net = translation.net.data();
+                    let path = translation.net.lola_identifiers().labels_of(&path);
+                    let finding = deadlock_finding(path, &translation, &net).expect(
+                        "BUG: The witness path found by `LoLA` should be fireable in the net",
+                    );
+                    message.push_str(&render_findings(&[finding], &translation, &net));
+                }
+                CargoResult::DeadlockAnalysis(message)
+            }
+            LolaAnswer::Unknown => CargoResult::ModelCheckerError(
+                "The model checker `LoLA` could not decide whether a deadlock can be reached"
+                    .to_string(),
+            ),
+        };
+        append_assumptions(result, &translation)
+    }
+}
+
+/// Returns the deadlock formula in the syntax of `LoLA`, excluding the suppressed deadlocks.
+/// The places are referenced by their names in the `LoLA` file.
+fn deadlock_formula(translation: &Translation) -> String {
+    let net = translation.net.data();
+    let suppressed = suppressed_places(translation, &net, FindingKind::Deadlock);
+    if suppressed.is_empty() {
+        return lola::DEADLOCK_FORMULA.to_string();
+    }
+    let identifiers = translation.net.lola_identifiers();
+    lola::deadlock_formula_excluding(&[vec![identifiers.identifiers_of(&suppressed)]])
+}
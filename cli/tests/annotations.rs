@@ -0,0 +1,131 @@
+//! Tests for the annotations in the comments of the source code and the baseline of known findings.
+//!
+//! Check that suppressed and acknowledged deadlocks are still listed in the result,
+//! that a suppression does not hide the deadlocks in other code
+//! and that only new deadlocks make the command fail when a baseline is given.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+mod utils;
+
+#[test]
+fn suppressed_double_lock_is_listed_as_suppressed() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/annotations/suppressed_double_lock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test");
+
+    cmd.assert().success().stdout(
+        predicate::str::contains(
+            "Result: The program is deadlock-free according to the model checker `LoLA`, except for suppressed or acknowledged deadlocks",
+        )
+        .and(predicate::str::contains("suppressed_double_lock.rs:5:"))
+        .and(predicate::str::contains("suppressed_double_lock.rs:4:1)"))
+        .and(predicate::str::contains(
+            "(suppressed by the annotation at ",
+        )),
+    );
+}
+
+#[test]
+fn suppression_in_other_function_does_not_hide_the_deadlock() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/annotations/suppression_in_other_function.rs",
+        true,
+    );
+}
+
+/// Runs the deadlock analysis on the program with the given baseline and extra arguments.
+fn check_with_baseline(
+    source_code_file: &str,
+    baseline: &std::path::Path,
+    args: &[&str],
+) -> Command {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(source_code_file)
+        .arg(format!(
+            "--output-folder={}",
+            baseline
+                .parent()
+                .expect("The baseline should be in a folder")
+                .to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg(format!("--baseline={}", baseline.to_string_lossy()))
+        .args(args);
+    cmd
+}
+
+#[test]
+fn deadlocks_in_the_baseline_are_acknowledged() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let baseline = temp_dir.path().join("findings.json");
+
+    check_with_baseline(
+        "./examples/programs/mutex/double_lock_deadlock.rs",
+        &baseline,
+        &["--update-baseline"],
+    )
+    .assert()
+    .success();
+    let json = std::fs::read_to_string(&baseline).expect("Could not read the baseline");
+    assert!(json.contains("\"kind\": \"deadlock\""));
+
+    check_with_baseline(
+        "./examples/programs/mutex/double_lock_deadlock.rs",
+        &baseline,
+        &[],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Result: The program is deadlock-free according to the model checker `LoLA`, except for suppressed or acknowledged deadlocks",
+    ))
+    .stdout(predicate::str::contains("(acknowledged in the baseline)"));
+}
+
+#[test]
+fn new_deadlocks_fail_the_command() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let baseline = temp_dir.path().join("findings.json");
+    std::fs::write(&baseline, "{\"findings\": []}").expect("Could not write the baseline");
+
+    check_with_baseline(
+        "./examples/programs/mutex/double_lock_deadlock.rs",
+        &baseline,
+        &[],
+    )
+    .assert()
+    .code(8)
+    .stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+}
+
+#[test]
+fn invalid_baseline_is_an_error() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let baseline = temp_dir.path().join("findings.json");
+    std::fs::write(&baseline, "[]").expect("Could not write the baseline");
+
+    check_with_baseline(
+        "./examples/programs/mutex/double_lock_deadlock.rs",
+        &baseline,
+        &[],
+    )
+    .assert()
+    .code(7)
+    .stderr(predicate::str::contains("Invalid baseline"));
+}
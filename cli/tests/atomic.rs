@@ -0,0 +1,63 @@
+//! Tests for the translation of `std::sync::atomic::AtomicBool` and `std::sync::atomic::AtomicUsize`.
+//!
+//! Check that spin-waits on a flag are modelled precisely enough
+//! to avoid spurious deadlocks and that the deadlocks after them are still found.
+
+mod utils;
+
+mod compare_exchange_sets_flag {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/compare_exchange_sets_flag.rs",
+        "./examples/results/atomic/compare_exchange_sets_flag/"
+    );
+}
+
+mod double_lock_after_flag_set_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/double_lock_after_flag_set_deadlock.rs",
+        "./examples/results/atomic/double_lock_after_flag_set_deadlock/"
+    );
+}
+
+mod flag_never_set_livelock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/flag_never_set_livelock.rs",
+        "./examples/results/atomic/flag_never_set_livelock/"
+    );
+}
+
+mod spin_wait_flag {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_flag.rs",
+        "./examples/results/atomic/spin_wait_flag/"
+    );
+}
+
+#[test]
+fn spin_wait_flag_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder("./examples/programs/atomic/spin_wait_flag.rs", false);
+}
+
+#[test]
+fn double_lock_after_flag_set_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/atomic/double_lock_after_flag_set_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn compare_exchange_sets_flag_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/atomic/compare_exchange_sets_flag.rs",
+        false,
+    );
+}
+
+#[test]
+fn flag_never_set_is_a_livelock_not_a_deadlock() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/atomic/flag_never_set_livelock.rs",
+        false,
+    );
+}
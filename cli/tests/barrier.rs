@@ -0,0 +1,51 @@
+//! Tests for the translation of `std::sync::Barrier`.
+//!
+//! Check that the model checker `LoLA` finds the deadlocks caused by barriers
+//! that are never reached by all the participating threads.
+
+mod utils;
+
+mod all_threads_reach_barrier {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/barrier/all_threads_reach_barrier.rs",
+        "./examples/results/barrier/all_threads_reach_barrier/"
+    );
+}
+
+mod missing_thread_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/barrier/missing_thread_deadlock.rs",
+        "./examples/results/barrier/missing_thread_deadlock/"
+    );
+}
+
+mod wait_before_join_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/barrier/wait_before_join_deadlock.rs",
+        "./examples/results/barrier/wait_before_join_deadlock/"
+    );
+}
+
+#[test]
+fn all_threads_reach_barrier_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/barrier/all_threads_reach_barrier.rs",
+        false,
+    );
+}
+
+#[test]
+fn missing_thread_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/barrier/missing_thread_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn wait_before_join_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/barrier/wait_before_join_deadlock.rs",
+        true,
+    );
+}
@@ -0,0 +1,111 @@
+//! Tests for the translation of `std::sync::mpsc` channels.
+//!
+//! Check that the model checker `LoLA` finds the deadlocks caused by threads
+//! waiting for messages that are never sent or for space in a full buffer.
+
+mod utils;
+
+mod bounded_send_then_recv {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/bounded_send_then_recv.rs",
+        "./examples/results/channel/bounded_send_then_recv/"
+    );
+}
+
+mod crossed_recv_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/crossed_recv_deadlock.rs",
+        "./examples/results/channel/crossed_recv_deadlock/"
+    );
+}
+
+mod full_buffer_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/full_buffer_deadlock.rs",
+        "./examples/results/channel/full_buffer_deadlock/"
+    );
+}
+
+mod recv_without_send_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/recv_without_send_deadlock.rs",
+        "./examples/results/channel/recv_without_send_deadlock/"
+    );
+}
+
+mod rendezvous_send_then_recv {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/rendezvous_send_then_recv.rs",
+        "./examples/results/channel/rendezvous_send_then_recv/"
+    );
+}
+
+mod rendezvous_send_without_recv_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/rendezvous_send_without_recv_deadlock.rs",
+        "./examples/results/channel/rendezvous_send_without_recv_deadlock/"
+    );
+}
+
+mod send_then_recv {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/channel/send_then_recv.rs",
+        "./examples/results/channel/send_then_recv/"
+    );
+}
+
+#[test]
+fn send_then_recv_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/send_then_recv.rs",
+        false,
+    );
+}
+
+#[test]
+fn recv_without_send_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/recv_without_send_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn crossed_recv_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/crossed_recv_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn bounded_send_then_recv_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/bounded_send_then_recv.rs",
+        false,
+    );
+}
+
+#[test]
+fn send_to_full_buffer_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/full_buffer_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn rendezvous_send_then_recv_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/rendezvous_send_then_recv.rs",
+        false,
+    );
+}
+
+#[test]
+fn rendezvous_send_without_recv_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/channel/rendezvous_send_without_recv_deadlock.rs",
+        true,
+    );
+}
@@ -0,0 +1,143 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use std::process::Command; // Run programs
+
+mod utils;
+
+mod infinite_wait_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/infinite_wait_deadlock.rs",
+        "./examples/results/condvar/infinite_wait_deadlock/"
+    );
+}
+
+mod notify_all {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/notify_all.rs",
+        "./examples/results/condvar/notify_all/"
+    );
+}
+
+mod producer_consumer {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/producer_consumer.rs",
+        "./examples/results/condvar/producer_consumer/"
+    );
+}
+
+mod self_notify_lost_signal {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/self_notify_lost_signal.rs",
+        "./examples/results/condvar/self_notify_lost_signal/"
+    );
+}
+
+mod set_condition_before_wait {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/set_condition_before_wait.rs",
+        "./examples/results/condvar/set_condition_before_wait/"
+    );
+}
+
+mod wait {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/wait.rs",
+        "./examples/results/condvar/wait/"
+    );
+}
+
+mod wait_while {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/condvar/wait_while.rs",
+        "./examples/results/condvar/wait_while/"
+    );
+}
+
+#[test]
+fn notify_all_wakes_the_waiting_thread() {
+    utils::assert_lola_result_in_temp_folder("./examples/programs/condvar/notify_all.rs", false);
+}
+
+#[test]
+fn notify_all_before_wait_is_lost() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/condvar/self_notify_all_lost_broadcast.rs",
+        true,
+    );
+}
+
+#[test]
+fn waiting_thread_blocks_when_locking_the_mutex_again() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/condvar/relock_after_wait_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn fifo_wakeup_wakes_the_waiting_thread() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/condvar/notify_all.rs",
+        &["--condvar-wakeup=fifo"],
+        false,
+    );
+}
+
+#[test]
+fn fifo_wakeup_lets_several_threads_wait() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/condvar/waiting_threads_spawned_in_a_loop.rs",
+        &["--condvar-wakeup=fifo"],
+        false,
+    );
+}
+
+#[test]
+fn any_wakeup_lets_one_thread_wait_at_a_time() {
+    // A false deadlock caused by the limit of a single waiting thread without the queue.
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/condvar/waiting_threads_spawned_in_a_loop.rs",
+        true,
+    );
+}
+
+#[test]
+fn fifo_wakeup_loses_the_signal_without_waiting_threads() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/condvar/self_notify_lost_signal.rs",
+        &["--condvar-wakeup=fifo"],
+        true,
+    );
+}
+
+#[test]
+fn fifo_wakeup_declares_the_positions_of_the_queue_safe() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/condvar/notify_all.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--condvar-wakeup=fifo")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let lola = std::fs::read_to_string(temp_dir.path().join("net.lola"))
+        .expect("Could not read the LoLA output");
+    let safe = lola
+        .lines()
+        .find(|line| line.starts_with("    SAFE : "))
+        .expect("The places with a capacity of one should be declared as SAFE");
+    for place in [
+        "CONDVAR_0_WAIT_ENABLED",
+        "CONDVAR_0_QUEUE_0",
+        "CONDVAR_0_QUEUE_3",
+        "CONDVAR_0_QUEUE_3_FREE",
+    ] {
+        assert!(safe.contains(place), "{place} should be declared as SAFE");
+    }
+    assert!(lola.contains("    SAFE 4 : CONDVAR_0_WAITING;\n"));
+    assert!(!safe.contains("CONDVAR_0_NOTIFY"));
+}
@@ -0,0 +1,50 @@
+//! Tests for the example programs included in the binary.
+
+use assert_cmd::prelude::*; // Add methods on commands
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+#[test]
+fn demo_double_lock_prints_counterexample() {
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("demo")
+        .arg("double-lock")
+        .arg(format!(
+            "--output-folder={}",
+            output_folder.path().display()
+        ));
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Counterexample found by the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains("line 3: let _d1 = data.lock();"))
+        .stdout(predicate::str::contains(
+            "Result: Deadlock can be reached according to the model checker `LoLA`",
+        ));
+}
+
+#[test]
+fn demo_name_is_not_valid() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock").arg("demo").arg("livelock");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "invalid value 'livelock' for '<NAME>'",
+    ));
+}
+
+#[test]
+fn demo_conflicts_with_arguments() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("--dot")
+        .arg("demo")
+        .arg("abba");
+
+    cmd.assert().failure();
+}
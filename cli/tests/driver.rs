@@ -0,0 +1,117 @@
+//! Tests for the `rustc`-compatible driver.
+
+use assert_cmd::prelude::*; // Add methods on commands
+use assert_fs::prelude::*; // Used for creating temp files
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+#[test]
+fn driver_prints_rustc_version() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock-driver").expect("Command not found");
+    cmd.arg("-vV");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("rustc"))
+        .stdout(predicate::str::contains("commit-date"));
+}
+
+#[test]
+fn driver_translates_crate_with_main_function() {
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    let file = output_folder.child("driver_crate.rs");
+    file.write_str(
+        "fn main() { let data = std::sync::Mutex::new(0); *data.lock().unwrap() += 1; }",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock-driver").expect("Command not found");
+    cmd.env_remove("CARGO")
+        .env("CARGO_CHECK_DEADLOCK_OUTPUT_FOLDER", output_folder.path())
+        .env("CARGO_CHECK_DEADLOCK_FORMATS", "lola,json")
+        .arg(file.path())
+        .arg("--crate-type=bin")
+        .arg(format!("--out-dir={}", output_folder.path().display()));
+    cmd.assert().success();
+
+    output_folder
+        .child("driver_crate.lola")
+        .assert(predicate::str::contains("MUTEX_0"));
+    output_folder
+        .child("driver_crate.json")
+        .assert(predicate::str::contains("PROGRAM_START"));
+    // The compilation itself still produces the executable.
+    output_folder
+        .child("driver_crate")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn driver_skips_crate_without_main_function() {
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    let file = output_folder.child("driver_library.rs");
+    file.write_str("pub fn add(a: u32, b: u32) -> u32 { a + b }")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock-driver").expect("Command not found");
+    cmd.env_remove("CARGO")
+        .env("CARGO_CHECK_DEADLOCK_OUTPUT_FOLDER", output_folder.path())
+        .arg(file.path())
+        .arg("--crate-type=lib")
+        .arg(format!("--out-dir={}", output_folder.path().display()));
+    cmd.assert().success();
+
+    output_folder
+        .child("driver_library.lola")
+        .assert(predicate::path::missing());
+}
+
+#[test]
+fn driver_rejects_invalid_format() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock-driver").expect("Command not found");
+    cmd.env_remove("CARGO")
+        .env("CARGO_CHECK_DEADLOCK_FORMATS", "csv")
+        .arg(file.path());
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid output format in CARGO_CHECK_DEADLOCK_FORMATS: csv",
+    ));
+}
+
+#[test]
+fn driver_translates_cargo_package_as_workspace_wrapper() {
+    let package_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    package_folder
+        .child("Cargo.toml")
+        .write_str(
+            "[package]\nname = \"wrapped_package\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .expect("Could not write test file contents");
+    package_folder
+        .child("src/main.rs")
+        .write_str("fn main() { let data = std::sync::Mutex::new(0); *data.lock().unwrap() += 1; }")
+        .expect("Could not write test file contents");
+    let output_folder = package_folder.child("petri_nets");
+    output_folder
+        .create_dir_all()
+        .expect("Could not create the output folder");
+
+    let mut cmd = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cmd.current_dir(package_folder.path())
+        .env(
+            "RUSTC_WORKSPACE_WRAPPER",
+            assert_cmd::cargo::cargo_bin("cargo-check-deadlock-driver"),
+        )
+        .env("CARGO_CHECK_DEADLOCK_OUTPUT_FOLDER", output_folder.path())
+        .env("CARGO_TARGET_DIR", package_folder.path().join("target"))
+        .args(["build", "--offline", "--quiet"]);
+    cmd.assert().success();
+
+    output_folder
+        .child("wrapped_package.lola")
+        .assert(predicate::str::contains("MUTEX_0"));
+}
@@ -0,0 +1,121 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+mod utils;
+
+mod diverging {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/function_call/diverging.rs",
+        "./examples/results/function_call/diverging/"
+    );
+}
+
+mod find_even {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/function_call/find_even.rs",
+        "./examples/results/function_call/find_even/"
+    );
+}
+
+mod empty_function {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/function_call/empty_function.rs",
+        "./examples/results/function_call/empty_function/"
+    );
+}
+
+mod in_a_loop {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/function_call/in_a_loop.rs",
+        "./examples/results/function_call/in_a_loop/"
+    );
+}
+
+mod two_calls_same_function {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/function_call/two_calls_same_function.rs",
+        "./examples/results/function_call/two_calls_same_function/"
+    );
+}
+
+#[test]
+fn closure_returned_from_function_is_translated() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/function_call/returned_closure_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn closure_capturing_by_reference_is_translated() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/function_call/closure_capture_by_reference_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn entry_function_is_translated_instead_of_main() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    for (entry_function, expected_result) in [
+        (
+            "worker::run",
+            "Result: Deadlock can be reached according to the model checker `LoLA`",
+        ),
+        (
+            "crate::run_safely",
+            "Result: The program is deadlock-free according to the model checker `LoLA`",
+        ),
+    ] {
+        let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+        cmd.arg("check-deadlock")
+            .arg("./examples/programs/function_call/library_without_main.rs")
+            .arg(format!(
+                "--output-folder={}",
+                temp_dir.path().to_string_lossy()
+            ))
+            .arg("--filename=deadlock_test")
+            .arg(format!("--entry-function={entry_function}"));
+
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(expected_result));
+    }
+}
+
+#[test]
+fn unknown_entry_function_is_rejected() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/function_call/library_without_main.rs")
+        .arg("--entry-function=worker::missing");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "The entry function given with `--entry-function` was not found in the source code",
+    ));
+}
+
+#[test]
+fn lib_mode_translates_every_exported_function() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/function_call/library_without_main.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("--lib");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+    let lola = std::fs::read_to_string(temp_dir.path().join("deadlock_test.lola"))
+        .expect("Could not read output file to string");
+    assert!(lola.contains("worker_run_ENTRY"));
+    assert!(lola.contains("run_safely_ENTRY"));
+}
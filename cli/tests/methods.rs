@@ -0,0 +1,37 @@
+//! Tests for the translation of `struct`s with methods that access their synchronization variables.
+//!
+//! Check that the sync variables are passed through `self` between method calls and threads.
+
+mod utils;
+
+#[test]
+fn counter_shared_between_threads_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/methods/counter_shared_between_threads.rs",
+        false,
+    );
+}
+
+#[test]
+fn nested_method_double_lock_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/methods/nested_method_double_lock_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn worker_with_shared_log_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/methods/worker_with_shared_log.rs",
+        false,
+    );
+}
+
+#[test]
+fn trait_method_called_through_generic_parameter_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/methods/generic_trait_double_lock_deadlock.rs",
+        true,
+    );
+}
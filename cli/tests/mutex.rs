@@ -0,0 +1,167 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+mod utils;
+
+mod double_lock_deadlock_in_function {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_deadlock_in_function.rs",
+        "./examples/results/mutex/double_lock_deadlock_in_function/"
+    );
+}
+
+mod double_lock_deadlock_with_arc {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_deadlock_with_arc.rs",
+        "./examples/results/mutex/double_lock_deadlock_with_arc/"
+    );
+}
+
+mod double_lock_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_deadlock.rs",
+        "./examples/results/mutex/double_lock_deadlock/"
+    );
+}
+
+mod drop_mutex_guard_in_arc_manually {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/drop_mutex_guard_in_arc_manually.rs",
+        "./examples/results/mutex/drop_mutex_guard_in_arc_manually/"
+    );
+}
+
+mod drop_mutex_guard_manually {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/drop_mutex_guard_manually.rs",
+        "./examples/results/mutex/drop_mutex_guard_manually/"
+    );
+}
+
+mod parking_lot_double_lock_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/parking_lot_double_lock_deadlock.rs",
+        "./examples/results/mutex/parking_lot_double_lock_deadlock/"
+    );
+}
+
+mod two_threads_sharing_mutex {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/two_threads_sharing_mutex.rs",
+        "./examples/results/mutex/two_threads_sharing_mutex/"
+    );
+}
+
+#[test]
+fn mutex_guard_dropped_at_end_of_scope_releases_the_mutex() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/mutex/drop_mutex_guard_at_end_of_scope.rs",
+        false,
+    );
+}
+
+#[test]
+fn struct_holding_mutex_guard_releases_the_mutex_when_dropped() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/mutex/drop_struct_holding_mutex_guard.rs",
+        false,
+    );
+}
+
+#[test]
+fn mutex_inside_pass_through_wrappers_is_tracked() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/mutex/double_lock_deadlock_in_wrappers.rs",
+        true,
+    );
+}
+
+#[test]
+fn poisoned_mutex_takes_the_err_path_of_lock() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/mutex/poisoned_mutex_locked_again_deadlock.rs",
+        &["--model-poisoning"],
+        true,
+    );
+}
+
+#[test]
+fn err_path_of_lock_is_not_taken_without_poisoning() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/mutex/poison_handled_without_panic.rs",
+        &["--model-poisoning"],
+        false,
+    );
+    // Without the flag, the `Err` arm is always reachable
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/mutex/poison_handled_without_panic.rs",
+        true,
+    );
+}
+
+#[test]
+fn transmuted_mutex_is_listed_as_unverified_assumption() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/transmute_mutex_reference.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test");
+
+    cmd.assert().success().stdout(
+        predicate::str::contains(
+            "Result: The program is deadlock-free according to the model checker `LoLA`",
+        )
+        .and(predicate::str::contains(
+            "Note: The verdict assumes that the mutex MUTEX_0 created at ",
+        )),
+    );
+}
+
+#[test]
+fn parking_lot_mutex_is_modelled_like_the_std_mutex() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/mutex/parking_lot_double_lock_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn mutex_of_a_lock_library_passed_in_the_arguments_is_modelled() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/custom_lock_library_double_lock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("--lock-library=my_sync<T>");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+
+    // Without the argument, the calls are translated like any other function
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/mutex/custom_lock_library_double_lock.rs",
+        false,
+    );
+}
+
+#[test]
+fn lock_library_without_generic_parameters_is_not_valid() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/custom_lock_library_double_lock.rs")
+        .arg("--lock-library=my_sync");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "invalid value 'my_sync' for '--lock-library <PATH<GENERICS>>'",
+    ));
+}
@@ -0,0 +1,51 @@
+//! Tests for the translation of `std::sync::Once` and `std::sync::OnceLock`.
+//!
+//! Check that the one-time initialization does not introduce spurious deadlocks
+//! and that the deadlocks after it are still found.
+
+mod utils;
+
+mod call_once_in_two_threads {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/call_once_in_two_threads.rs",
+        "./examples/results/once/call_once_in_two_threads/"
+    );
+}
+
+mod double_lock_after_call_once_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/double_lock_after_call_once_deadlock.rs",
+        "./examples/results/once/double_lock_after_call_once_deadlock/"
+    );
+}
+
+mod once_lock_get_or_init {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/once_lock_get_or_init.rs",
+        "./examples/results/once/once_lock_get_or_init/"
+    );
+}
+
+#[test]
+fn call_once_in_two_threads_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/once/call_once_in_two_threads.rs",
+        false,
+    );
+}
+
+#[test]
+fn once_lock_get_or_init_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/once/once_lock_get_or_init.rs",
+        false,
+    );
+}
+
+#[test]
+fn double_lock_after_call_once_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/once/double_lock_after_call_once_deadlock.rs",
+        true,
+    );
+}
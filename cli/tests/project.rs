@@ -0,0 +1,91 @@
+//! Tests for the translation of Cargo projects.
+
+use assert_cmd::prelude::*; // Add methods on commands
+use assert_fs::prelude::*; // Used for creating temp files
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+/// Creates a Cargo project with a binary that uses a path dependency and another module.
+/// The binary locks a mutex twice in another module, which is a deadlock.
+fn create_project(folder: &assert_fs::TempDir) {
+    folder
+        .child("counter/Cargo.toml")
+        .write_str("[package]\nname = \"counter\"\nversion = \"0.1.0\"\nedition = \"2021\"\n")
+        .expect("Could not write test file contents");
+    folder
+        .child("counter/src/lib.rs")
+        .write_str("pub fn initial_value() -> u32 {\n    0\n}\n")
+        .expect("Could not write test file contents");
+    folder
+        .child("app/Cargo.toml")
+        .write_str(
+            "[package]\nname = \"double-lock-app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\ncounter = { path = \"../counter\" }\n",
+        )
+        .expect("Could not write test file contents");
+    folder
+        .child("app/src/main.rs")
+        .write_str(
+            "mod locking;\n\n\
+             fn main() {\n    let data = std::sync::Mutex::new(counter::initial_value());\n    locking::lock_twice(&data);\n}\n",
+        )
+        .expect("Could not write test file contents");
+    folder
+        .child("app/src/locking.rs")
+        .write_str(
+            "pub fn lock_twice(data: &std::sync::Mutex<u32>) {\n    \
+             let _first = data.lock();\n    let _second = data.lock();\n}\n",
+        )
+        .expect("Could not write test file contents");
+}
+
+#[test]
+fn project_with_dependency_is_translated_and_analyzed() {
+    let project_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    create_project(&project_folder);
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("project")
+        .arg("--manifest-path")
+        .arg(project_folder.child("app/Cargo.toml").path())
+        .arg(format!(
+            "--output-folder={}",
+            output_folder.path().display()
+        ))
+        .arg("--formats=lola,json");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+    output_folder
+        .child("double_lock_app.lola")
+        .assert(predicate::path::exists());
+    output_folder
+        .child("double_lock_app.json")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn project_without_binary_requires_entry_function() {
+    let project_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    create_project(&project_folder);
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("project")
+        .arg("--manifest-path")
+        .arg(project_folder.child("counter/Cargo.toml").path())
+        .arg(format!(
+            "--output-folder={}",
+            project_folder.path().display()
+        ));
+
+    cmd.assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "No binary found in the workspace: Use `--entry-function` to translate a library",
+        ));
+}
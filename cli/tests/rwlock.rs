@@ -0,0 +1,104 @@
+//! Tests for the translation of `std::sync::RwLock`.
+//!
+//! Check that the model checker `LoLA` finds the deadlocks caused by read-write locks
+//! and that concurrent readers do not produce false deadlocks.
+//! The guards dropped while unwinding release the lock and, with `--model-poisoning`, a write guard poisons it.
+
+mod utils;
+
+mod concurrent_readers {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/rwlock/concurrent_readers.rs",
+        "./examples/results/rwlock/concurrent_readers/"
+    );
+}
+
+mod drop_read_guard_before_write {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/rwlock/drop_read_guard_before_write.rs",
+        "./examples/results/rwlock/drop_read_guard_before_write/"
+    );
+}
+
+mod panic_while_reading_does_not_poison {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/rwlock/panic_while_reading_does_not_poison.rs",
+        "./examples/results/rwlock/panic_while_reading_does_not_poison/"
+    );
+}
+
+mod panic_while_writing_releases_the_lock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/rwlock/panic_while_writing_releases_the_lock.rs",
+        "./examples/results/rwlock/panic_while_writing_releases_the_lock/"
+    );
+}
+
+mod poisoned_rwlock_written_again_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/rwlock/poisoned_rwlock_written_again_deadlock.rs",
+        "./examples/results/rwlock/poisoned_rwlock_written_again_deadlock/"
+    );
+}
+
+mod write_while_reading_deadlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/rwlock/write_while_reading_deadlock.rs",
+        "./examples/results/rwlock/write_while_reading_deadlock/"
+    );
+}
+
+#[test]
+fn concurrent_readers_are_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/rwlock/concurrent_readers.rs",
+        false,
+    );
+}
+
+#[test]
+fn write_while_reading_deadlocks() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/rwlock/write_while_reading_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn drop_read_guard_before_write_is_deadlock_free() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/rwlock/drop_read_guard_before_write.rs",
+        false,
+    );
+}
+
+#[test]
+fn write_guard_dropped_while_unwinding_releases_the_lock() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/rwlock/panic_while_writing_releases_the_lock.rs",
+        false,
+    );
+}
+
+#[test]
+fn poisoned_rwlock_takes_the_err_path_of_write() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/rwlock/poisoned_rwlock_written_again_deadlock.rs",
+        &["--model-poisoning"],
+        true,
+    );
+}
+
+#[test]
+fn read_guard_dropped_while_unwinding_does_not_poison_the_lock() {
+    utils::assert_lola_result_in_temp_folder_with_args(
+        "./examples/programs/rwlock/panic_while_reading_does_not_poison.rs",
+        &["--model-poisoning"],
+        false,
+    );
+    // Without the flag, the `Err` arm is always reachable
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/rwlock/panic_while_reading_does_not_poison.rs",
+        true,
+    );
+}
@@ -0,0 +1,1894 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use assert_fs::prelude::*; // Used for creating temp files
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+#[test]
+fn file_does_not_exist() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+
+    cmd.arg("check-deadlock")
+        .arg("test/file/doesnt/exist")
+        .arg("--pnml");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Source code file at test/file/doesnt/exist does not exist",
+    ));
+}
+
+#[test]
+fn output_folder_does_not_exist() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder=test/folder/doesnt/exist");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Output folder at test/folder/doesnt/exist does not exist",
+    ));
+}
+
+#[test]
+fn format_is_not_valid() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock").arg(file.path()).arg("--csv");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "unexpected argument '--csv' found",
+    ));
+}
+
+#[test]
+fn generates_lola_output_by_default() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=does_not_generate_output_by_default")
+        .arg("--skip-analysis");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+    // Check that the default output exists
+    if !std::path::Path::new("./does_not_generate_output_by_default.lola").exists() {
+        panic!("Should generate a .lola file by default");
+    }
+    std::fs::remove_file("./does_not_generate_output_by_default.lola")
+        .expect("Could not delete output file");
+
+    // Check that the other formats were not generated
+    if std::path::Path::new("./does_not_generate_output_by_default.dot").exists() {
+        panic!("Should not generate a .dot file by default");
+    }
+    if std::path::Path::new("./does_not_generate_output_by_default.pnml").exists() {
+        panic!("Should not generate a .pnml file by default");
+    }
+}
+
+#[test]
+fn max_call_depth_is_not_valid() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--max-call-depth=deep");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "invalid value 'deep' for '--max-call-depth <MAX_CALL_DEPTH>'",
+    ));
+}
+
+#[test]
+fn max_call_depth_abstracts_deeper_calls() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn inner() {}\nfn outer() { inner(); }\nfn main() { outer(); }\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=max_call_depth_abstracts_deeper_calls")
+        .arg("--max-call-depth=2")
+        .arg("--coverage")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let report = std::fs::read_to_string("./max_call_depth_abstracts_deeper_calls.coverage.txt")
+        .expect("Could not read coverage report to string");
+    assert!(report.contains("functions translated 2/3"));
+    assert!(report.contains("abstracted: inner"));
+
+    std::fs::remove_file("./max_call_depth_abstracts_deeper_calls.coverage.txt")
+        .expect("Could not delete output file");
+    std::fs::remove_file("./max_call_depth_abstracts_deeper_calls.lola")
+        .expect("Could not delete output file");
+}
+
+#[test]
+fn skip_function_abstracts_the_call() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn inner() {}\nfn outer() { inner(); }\nfn main() { outer(); }\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=skip_function_abstracts_the_call")
+        .arg("--skip-function=outer")
+        .arg("--coverage")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let report = std::fs::read_to_string("./skip_function_abstracts_the_call.coverage.txt")
+        .expect("Could not read coverage report to string");
+    assert!(report.contains("abstracted: outer"));
+    assert!(report.contains("skipped: outer: listed in the skipped functions"));
+    assert!(!report.contains("inner"));
+
+    std::fs::remove_file("./skip_function_abstracts_the_call.coverage.txt")
+        .expect("Could not delete output file");
+    std::fs::remove_file("./skip_function_abstracts_the_call.lola")
+        .expect("Could not delete output file");
+}
+
+#[test]
+fn unsupported_function_is_skipped_and_noted_in_the_result() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn spin() {\n    unsafe { std::arch::asm!(\"nop\") }\n}\nfn main() {\n    spin();\n}\n",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Result: The program is deadlock-free according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "Note: The verdict assumes that the skipped function spin does not use synchronization primitives (not implemented: TerminatorKind::InlineAsm not implemented yet)",
+        ));
+}
+
+#[test]
+fn function_time_budget_must_be_positive() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--function-time-budget=0");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "invalid value '0' for '--function-time-budget",
+    ));
+}
+
+#[test]
+fn mutual_recursion_is_abstracted() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn is_even(n: u32) -> bool { if n == 0 { true } else { is_odd(n - 1) } }\n\
+         fn is_odd(n: u32) -> bool { if n == 0 { false } else { is_even(n - 1) } }\n\
+         fn main() { is_even(4); }\n",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=mutual_recursion_is_abstracted")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let net = std::fs::read_to_string("./mutual_recursion_is_abstracted.lola")
+        .expect("Could not read output file to string");
+    assert!(net.contains("is_even_0_RECURSIVE_CALL"));
+
+    std::fs::remove_file("./mutual_recursion_is_abstracted.lola")
+        .expect("Could not delete output file");
+}
+
+#[test]
+fn recursion_as_loop_models_tail_call_as_loop() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn count_down(n: u32) { if n == 0 { return; } count_down(n - 1) }\n\
+         fn main() { count_down(3); }\n",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=recursion_as_loop_models_tail_call_as_loop")
+        .arg("--recursion-as-loop")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let net = std::fs::read_to_string("./recursion_as_loop_models_tail_call_as_loop.lola")
+        .expect("Could not read output file to string");
+    assert!(net.contains("count_down_0_RECURSIVE_LOOP"));
+    assert!(!net.contains("count_down_0_RECURSIVE_CALL"));
+
+    std::fs::remove_file("./recursion_as_loop_models_tail_call_as_loop.lola")
+        .expect("Could not delete output file");
+}
+
+/// Translates the given source code with the given extra arguments
+/// and returns the contents of the resulting `LoLA` file.
+fn translate_to_lola(source_code: &str, filename: &str, args: &[&str]) -> String {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(source_code)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!("--filename={filename}"))
+        .args(args)
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let output_path = format!("./{filename}.lola");
+    let net = std::fs::read_to_string(&output_path).expect("Could not read output file to string");
+    std::fs::remove_file(&output_path).expect("Could not delete output file");
+    net
+}
+
+const THREADS_AND_MUTEXES: &str = "use std::sync::{Arc, Mutex};\n\
+    fn main() {\n\
+        let data = Arc::new(Mutex::new(0));\n\
+        let other = Arc::new(Mutex::new(0));\n\
+        let data_clone = data.clone();\n\
+        let other_clone = other.clone();\n\
+        let handle = std::thread::spawn(move || { *data_clone.lock().unwrap() += 1; *other_clone.lock().unwrap() += 1; });\n\
+        *other.lock().unwrap() += 1;\n\
+        *data.lock().unwrap() += 1;\n\
+        handle.join().unwrap();\n\
+    }\n";
+
+#[test]
+fn translation_is_deterministic() {
+    let first = translate_to_lola(
+        THREADS_AND_MUTEXES,
+        "translation_is_deterministic_first",
+        &[],
+    );
+    let second = translate_to_lola(
+        THREADS_AND_MUTEXES,
+        "translation_is_deterministic_second",
+        &[],
+    );
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn unrelated_function_does_not_change_the_net() {
+    let with_unrelated_function =
+        format!("{THREADS_AND_MUTEXES}fn unrelated() {{ println!(\"unrelated\"); }}\n");
+
+    let original = translate_to_lola(THREADS_AND_MUTEXES, "unrelated_function_original", &[]);
+    let modified = translate_to_lola(&with_unrelated_function, "unrelated_function_modified", &[]);
+
+    assert_eq!(original, modified);
+}
+
+#[test]
+fn max_memory_does_not_change_the_net() {
+    let in_memory = translate_to_lola(THREADS_AND_MUTEXES, "max_memory_in_memory", &[]);
+    let on_disk = translate_to_lola(
+        THREADS_AND_MUTEXES,
+        "max_memory_on_disk",
+        &["--max-memory=0"],
+    );
+
+    assert_eq!(in_memory, on_disk);
+}
+
+#[test]
+fn self_check_accepts_translated_net() {
+    // `translate_to_lola` asserts that the command succeeds, i.e. that no invariant is violated.
+    translate_to_lola(THREADS_AND_MUTEXES, "self_check", &["--self-check"]);
+}
+
+#[test]
+fn lola_comments_explain_net_and_formula() {
+    let with_comments = translate_to_lola(
+        THREADS_AND_MUTEXES,
+        "lola_comments_explain_net_and_formula",
+        &["--lola-comments"],
+    );
+    let without_comments = translate_to_lola(THREADS_AND_MUTEXES, "lola_comments_none", &[]);
+
+    let (header, net) = with_comments
+        .split_once("}\n")
+        .expect("The net should start with a comment");
+    assert!(header.starts_with('{'));
+    assert!(header.contains("MUTEX_0: mutex created at"));
+    assert!(header.contains("THREAD_0: thread created at"));
+    assert_eq!(net, without_comments);
+
+    let formula = std::fs::read_to_string("./lola_comments_explain_net_and_formula.formula")
+        .expect("Could not read output file to string");
+    assert!(formula.starts_with('{'));
+    assert!(formula.ends_with("EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))\n"));
+    std::fs::remove_file("./lola_comments_explain_net_and_formula.formula")
+        .expect("Could not delete output file");
+}
+
+#[test]
+fn apt_and_petrify_outputs_contain_the_net() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(THREADS_AND_MUTEXES)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=apt_and_petrify_outputs_contain_the_net")
+        .arg("--apt")
+        .arg("--petrify")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let apt = std::fs::read_to_string("./apt_and_petrify_outputs_contain_the_net.apt")
+        .expect("Could not read output file to string");
+    assert!(apt.contains(".flows"));
+    assert!(apt.contains(".initial_marking {PROGRAM_START, "));
+    assert!(apt.contains("MUTEX_0"));
+
+    let petrify = std::fs::read_to_string("./apt_and_petrify_outputs_contain_the_net.g")
+        .expect("Could not read output file to string");
+    assert!(petrify.contains(".graph"));
+    assert!(petrify.contains(".capacity "));
+    assert!(petrify.contains("MUTEX_0=1"));
+
+    for extension in ["apt", "g", "lola"] {
+        std::fs::remove_file(format!(
+            "./apt_and_petrify_outputs_contain_the_net.{extension}"
+        ))
+        .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn greatspn_output_contains_the_net_and_the_definitions() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--greatspn")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    temp_dir.child("net.gspn.net").assert(
+        predicate::str::starts_with("|0|\n|\nf 0 ")
+            .and(predicate::str::contains("\nPROGRAM_START 1 "))
+            .and(predicate::str::contains(" 1.000000e+00 1 0 ")),
+    );
+    temp_dir
+        .child("net.gspn.def")
+        .assert(predicate::str::starts_with("|256\n"));
+}
+
+#[test]
+fn mcc_bundle_contains_the_model_and_the_properties() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--mcc-bundle")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let bundle = temp_dir.child("net.mcc");
+    bundle
+        .child("model.pnml")
+        .assert(predicate::str::contains("<pnml"));
+    bundle.child("iscolored").assert("FALSE\n");
+    bundle
+        .child("ReachabilityDeadlock.xml")
+        .assert(predicate::str::contains("<deadlock/>"));
+    bundle.child("ReachabilityCardinality.xml").assert(
+        predicate::str::contains("<id>MUTEX_0_EXCLUSIVE</id>")
+            .and(predicate::str::contains("<all-paths><globally>")),
+    );
+    bundle
+        .child("CTLCardinality.xml")
+        .assert(predicate::str::contains("<id>MUTEX_0_RELEASED</id>"));
+}
+
+#[test]
+fn mermaid_output_contains_the_flowchart() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--mermaid")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    temp_dir.child("net.mmd").assert(
+        predicate::str::starts_with("graph TD\n")
+            .and(predicate::str::contains("((\"PROGRAM_START (1)\"))"))
+            .and(predicate::str::contains(" --> ")),
+    );
+}
+
+#[test]
+fn tikz_output_contains_the_picture() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--tikz")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    temp_dir.child("net.tex").assert(
+        predicate::str::starts_with("\\documentclass[tikz]{standalone}\n")
+            .and(predicate::str::contains(
+                "\\node[font=\\bfseries] at (5, -1) {main};",
+            ))
+            .and(predicate::str::contains("label=right:{PROGRAM\\_START}"))
+            .and(predicate::str::contains("\\draw[->] "))
+            .and(predicate::str::ends_with("\\end{document}\n")),
+    );
+}
+
+#[test]
+fn weighted_arcs_are_labeled_in_every_format() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/rwlock/write_while_reading_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--dot")
+        .arg("--pnml")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    // A call to `write` takes all the tokens of the read-write lock at once.
+    // The marking of the place has four tokens too, so the weight is checked on the arc itself.
+    temp_dir.child("net.lola").assert(
+        predicate::str::is_match(r"TRANSITION \S*write\S*\n  CONSUME\n[^;]*RWLOCK_0 : 4[,;]")
+            .expect("The regex should be valid"),
+    );
+    temp_dir.child("net.pnml").assert(
+        predicate::str::is_match(
+            r#"<arc source="RWLOCK_0" target="\S*write\S*" id="[^"]*">\s*<name>\s*<text>[^<]*</text>\s*</name>\s*<inscription>\s*<text>4</text>\s*</inscription>"#,
+        )
+        .expect("The regex should be valid"),
+    );
+    temp_dir.child("net.dot").assert(
+        predicate::str::is_match(r#"RWLOCK_0 -> \S*write\S* \[label="4"\];"#)
+            .expect("The regex should be valid"),
+    );
+}
+
+#[test]
+fn tina_output_contains_the_net() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(THREADS_AND_MUTEXES)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=tina_output_contains_the_net")
+        .arg("--tina")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let tina = std::fs::read_to_string("./tina_output_contains_the_net.net")
+        .expect("Could not read output file to string");
+    assert!(tina.contains("pl PROGRAM_START (1)"));
+    assert!(tina.contains("pl MUTEX_0 (1)"));
+    assert!(tina.contains("\ntr "));
+
+    for extension in ["net", "lola"] {
+        std::fs::remove_file(format!("./tina_output_contains_the_net.{extension}"))
+            .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn pnml_dialect_adapts_the_output_to_woped() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(THREADS_AND_MUTEXES)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=pnml_dialect_adapts_the_output_to_woped")
+        .arg("--pnml-dialect=woped")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let pnml = std::fs::read_to_string("./pnml_dialect_adapts_the_output_to_woped.pnml")
+        .expect("Could not read output file to string");
+    assert!(pnml.contains("http://www.informatik.hu-berlin.de/top/pntd/ptNetb"));
+    assert!(pnml.contains("<position "));
+    assert!(!pnml.contains("<page"));
+
+    for extension in ["pnml", "lola"] {
+        std::fs::remove_file(format!(
+            "./pnml_dialect_adapts_the_output_to_woped.{extension}"
+        ))
+        .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn pnml_dialect_pages_nests_the_threads() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(THREADS_AND_MUTEXES)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=pnml_dialect_pages_nests_the_threads")
+        .arg("--pnml-dialect=pages")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let pnml = std::fs::read_to_string("./pnml_dialect_pages_nests_the_threads.pnml")
+        .expect("Could not read output file to string");
+    assert!(pnml.contains("<page id=\"THREAD_0_PAGE\">"));
+    assert!(pnml.contains("<referencePlace "));
+
+    for extension in ["pnml", "lola"] {
+        std::fs::remove_file(format!(
+            "./pnml_dialect_pages_nests_the_threads.{extension}"
+        ))
+        .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn svg_and_pnml_layout_reuse_the_cached_layout() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(THREADS_AND_MUTEXES)
+        .expect("Could not write test file contents");
+    let cache_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+        cmd.env("XDG_CACHE_HOME", cache_folder.path())
+            .arg("check-deadlock")
+            .arg(file.path())
+            .arg("--filename=svg_and_pnml_layout_reuse_the_cached_layout")
+            .arg("--svg")
+            .arg("--pnml-dialect=layout")
+            .arg("--skip-analysis");
+        cmd.assert().success();
+    }
+
+    let layouts = std::fs::read_dir(cache_folder.path().join("cargo-check-deadlock/layouts"))
+        .expect("Could not read the cache folder")
+        .count();
+    assert_eq!(layouts, 1);
+
+    let svg = std::fs::read_to_string("./svg_and_pnml_layout_reuse_the_cached_layout.svg")
+        .expect("Could not read output file to string");
+    assert!(svg.contains("<svg"));
+    assert!(svg.contains("MUTEX_0"));
+
+    let pnml = std::fs::read_to_string("./svg_and_pnml_layout_reuse_the_cached_layout.pnml")
+        .expect("Could not read output file to string");
+    assert!(pnml.contains("<position "));
+
+    for extension in ["svg", "pnml", "lola"] {
+        std::fs::remove_file(format!(
+            "./svg_and_pnml_layout_reuse_the_cached_layout.{extension}"
+        ))
+        .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn unknown_pnml_dialect_is_rejected() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/basic/hello_world.rs")
+        .arg("--pnml-dialect=tina");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'tina'"));
+}
+
+#[test]
+fn property_templates_cover_every_mutex() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(THREADS_AND_MUTEXES)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=property_templates_cover_every_mutex")
+        .arg("--property-templates")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let lola = std::fs::read_to_string("./property_templates_cover_every_mutex.properties.lola")
+        .expect("Could not read output file to string");
+    assert!(lola.contains("AG (MUTEX_0 <= 1)"));
+    assert!(lola.contains("AG (MUTEX_1 <= 1)"));
+    assert!(lola.contains("THREAD_0_TERMINATES"));
+
+    let smv = std::fs::read_to_string("./property_templates_cover_every_mutex.properties.smv")
+        .expect("Could not read output file to string");
+    assert!(smv.contains("LTLSPEC NAME MUTEX_0_EXCLUSIVE := G (MUTEX_0 <= 1);"));
+
+    for extension in ["properties.lola", "properties.smv", "lola"] {
+        std::fs::remove_file(format!(
+            "./property_templates_cover_every_mutex.{extension}"
+        ))
+        .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn property_annotations_add_formulas_to_the_templates() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "use std::sync::Mutex;\n\
+        fn main() {\n\
+            let data = Mutex::new(0);\n\
+            // granite: property LOCKED: eventually reachable\n\
+            let _guard = data.lock().unwrap();\n\
+        }\n",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=property_annotations_add_formulas_to_the_templates")
+        .arg("--property-templates")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let lola = std::fs::read_to_string(
+        "./property_annotations_add_formulas_to_the_templates.properties.lola",
+    )
+    .expect("Could not read output file to string");
+    assert!(lola.contains("LOCKED"));
+    assert!(lola.contains("AF (PROPERTY_LOCKED_REACHED = 1)"));
+
+    for extension in ["properties.lola", "properties.smv", "lola"] {
+        std::fs::remove_file(format!(
+            "./property_annotations_add_formulas_to_the_templates.{extension}"
+        ))
+        .expect("Could not delete output file");
+    }
+}
+
+#[test]
+fn json_output_contains_the_net() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=json_output_contains_the_net")
+        .arg("--json")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let net = std::fs::read_to_string("./json_output_contains_the_net.json")
+        .expect("Could not read output file to string");
+    assert!(net.contains("\"places\""));
+    assert!(net.contains("PROGRAM_START"));
+
+    std::fs::remove_file("./json_output_contains_the_net.json")
+        .expect("Could not delete output file");
+    std::fs::remove_file("./json_output_contains_the_net.lola")
+        .expect("Could not delete output file");
+}
+
+#[test]
+fn plugin_does_not_exist() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--plugin=test/plugin/doesnt/exist.so")
+        .arg("--skip-analysis");
+    cmd.assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains(
+            "Plugin at test/plugin/doesnt/exist.so could not be loaded",
+        ));
+}
+
+#[test]
+fn profile_output_contains_collapsed_stacks() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--filename=profile_output_contains_collapsed_stacks")
+        .arg("--profile")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let profile = std::fs::read_to_string("./profile_output_contains_collapsed_stacks.folded")
+        .expect("Could not read output file to string");
+    assert!(profile
+        .lines()
+        .any(|line| line.starts_with("translation;main;mir_query ")));
+    assert!(profile
+        .lines()
+        .any(|line| line.starts_with("translation;main;visit ")));
+    assert!(profile.lines().any(|line| line.starts_with("export;lola ")));
+
+    std::fs::remove_file("./profile_output_contains_collapsed_stacks.folded")
+        .expect("Could not delete output file");
+    std::fs::remove_file("./profile_output_contains_collapsed_stacks.lola")
+        .expect("Could not delete output file");
+}
+
+#[test]
+fn deadlock_result_shows_the_source_code() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test");
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("3 |     let _d1 = data.lock();")
+            .and(predicate::str::contains("4 |     let _d2 = data.lock();"))
+            .and(predicate::str::contains(
+                "^ blocked here, waiting for the mutex `MUTEX_0`",
+            ))
+            .and(predicate::str::contains(
+                "- the mutex `MUTEX_0` is taken here",
+            ))
+            .and(predicate::str::contains(
+                "waits for the mutex `MUTEX_0` held by `main` (cycle)",
+            )),
+    );
+    temp_dir
+        .child("deadlock_test.path")
+        .assert(predicate::path::missing());
+}
+
+#[test]
+fn interactions_show_the_locks_used_in_opposite_order() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/dating_philosophers.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--interactions")
+        .arg("--skip-analysis");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Thread    MUTEX_0  MUTEX_1").and(
+            predicate::str::contains(
+                "MUTEX_0 and MUTEX_1 are locked in opposite order by THREAD_0 and THREAD_1",
+            ),
+        ));
+    temp_dir
+        .child("test.interactions.json")
+        .assert(predicate::str::contains("\"inversions\""));
+}
+
+#[test]
+fn mapping_lists_the_nodes_of_every_basic_block() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--mapping")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.mapping.json").assert(
+        predicate::str::contains("\"function\": \"main\"")
+            .and(predicate::str::contains("\"terminator\": \"Call\""))
+            .and(predicate::str::contains("\"PROGRAM_START\"")),
+    );
+}
+
+#[test]
+fn also_run_spawns_a_thread_at_program_start() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/function_call/empty_function.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--also-run=call")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.lola").assert(
+        predicate::str::contains("ALSO_RUN_SPAWN")
+            .and(predicate::str::contains("ALSO_RUN_STARTED"))
+            .and(predicate::str::contains("THREAD_0_START")),
+    );
+}
+
+#[test]
+fn unknown_also_run_function_is_rejected() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/function_call/empty_function.rs")
+        .arg("--also-run=missing");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "A function given with `--also-run` was not found in the source code",
+    ));
+}
+
+#[test]
+fn also_run_conflicts_with_cross_check() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/function_call/empty_function.rs")
+        .arg("--also-run=call")
+        .arg("--cross-check");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "the argument '--also-run <FN_PATH>' cannot be used with '--cross-check'",
+    ));
+}
+
+#[test]
+fn label_map_explains_the_labels_of_the_net() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--label-map")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.map.json").assert(
+        predicate::str::contains("\"function\": \"main\"")
+            .and(predicate::str::contains("double_lock_deadlock.rs"))
+            .and(predicate::str::contains("\"mir\": \"")),
+    );
+}
+
+#[test]
+fn numeric_naming_writes_the_table_of_names() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--naming=numeric")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.lola").assert(
+        predicate::str::contains("P0")
+            .and(predicate::str::contains("TRANSITION T0"))
+            .and(predicate::str::contains("PROGRAM_START").not()),
+    );
+    temp_dir
+        .child("test.names.json")
+        .assert(predicate::str::contains("\"PROGRAM_START\""));
+}
+
+#[test]
+fn nondet_marker_is_a_free_choice() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("nondet.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "macro_rules! granite_nondet_bool {\n    () => {\n        granite_nondet_bool()\n    };\n}\n\n\
+        #[inline(never)]\nfn granite_nondet_bool() -> bool {\n    std::hint::black_box(false)\n}\n\n\
+        fn main() {\n    if granite_nondet_bool!() {\n        println!(\"request\");\n    }\n}\n",
+    )
+    .expect("Could not write test file contents");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.lola").assert(
+        predicate::str::contains("granite_nondet_bool_0_NONDET")
+            .and(predicate::str::contains("main_NONDET_FROM_BB"))
+            .and(predicate::str::contains("main_SWITCH_INT").not()),
+    );
+}
+
+#[test]
+fn env_branches_tags_the_branches_on_the_arguments() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("arguments.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn main() {\n    let verbose = std::env::args().count() > 1;\n    \
+        if verbose {\n        println!(\"verbose\");\n    }\n}\n",
+    )
+    .expect("Could not write test file contents");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--env-branches")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir
+        .child("test.lola")
+        .assert(predicate::str::contains("main_ENV_FROM_BB"));
+}
+
+#[test]
+fn env_branches_tags_the_branches_on_the_arguments_of_a_closure() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("closure_arguments.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn main() {\n    let check = |label: &str, count: usize| {\n        \
+        if count > 1 {\n            println!(\"{label}\");\n        }\n    };\n    \
+        check(\"verbose\", std::env::args().count());\n}\n",
+    )
+    .expect("Could not write test file contents");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--env-branches")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.lola").assert(
+        predicate::str::is_match(r"closure_0_\S*_ENV_FROM_BB").expect("The regex should be valid"),
+    );
+}
+
+#[test]
+fn env_branches_does_not_tag_the_branches_on_constant_arguments_of_a_closure() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("closure_constant_arguments.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn main() {\n    let count = std::env::args().count();\n    \
+        let check = |label: &str, count: usize| {\n        \
+        if count > 1 {\n            println!(\"{label}\");\n        }\n    };\n    \
+        check(\"verbose\", 2);\n    println!(\"{count}\");\n}\n",
+    )
+    .expect("Could not write test file contents");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--env-branches")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir
+        .child("test.lola")
+        .assert(predicate::str::contains("_ENV_FROM_BB").not());
+}
+
+#[test]
+fn spans_annotate_the_nodes_in_dot_and_pnml() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--dot")
+        .arg("--pnml")
+        .arg("--json")
+        .arg("--spans")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.dot").assert(
+        predicate::str::contains("tooltip=\"")
+            .and(predicate::str::contains("double_lock_deadlock.rs:")),
+    );
+    temp_dir
+        .child("test.pnml")
+        .assert(
+            predicate::str::contains("<span file=\"").and(predicate::str::contains(
+                "double_lock_deadlock.rs\" start-line=",
+            )),
+        );
+    temp_dir
+        .child("test.json")
+        .assert(predicate::str::contains("\"span\": {"));
+}
+
+#[test]
+fn dot_clusters_group_the_nodes_of_every_function() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--dot")
+        .arg("--dot-clusters")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.dot").assert(
+        predicate::str::contains("subgraph \"cluster_main\" {")
+            .and(predicate::str::contains("fillcolor=")),
+    );
+}
+
+#[test]
+fn dot_options_style_the_special_nodes() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--dot")
+        .arg("--dot-option=rankdir=LR")
+        .arg("--dot-option=highlight=true")
+        .arg("--dot-option=sync-transitions=true")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.dot").assert(
+        predicate::str::contains("rankdir=\"LR\";")
+            .and(predicate::str::contains(
+                "MUTEX_0 [shape=\"circle\" xlabel=\"MUTEX_0\" label=\"•\" style=\"filled\" fillcolor=\"orange\"];",
+            ))
+            .and(predicate::str::contains(
+                "std_sync_Mutex_T_lock_0_CALL [shape=\"box\" xlabel=\"\" label=\"std_sync_Mutex_T_lock_0_CALL\" color=\"blue\" penwidth=\"2\"];",
+            )),
+    );
+}
+
+#[test]
+fn invalid_dot_option_is_rejected() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--dot-option=rankdir=diagonal");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Unknown rank direction `diagonal`",
+    ));
+}
+
+#[test]
+fn reduction_log_lists_the_rules_applied() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=test")
+        .arg("--reduce")
+        .arg("--reduction-log")
+        .arg("--skip-analysis");
+
+    cmd.assert().success();
+    temp_dir.child("test.reductions.txt").assert(
+        predicate::str::starts_with("Pass `reduce`: ")
+            .and(predicate::str::contains("fusion of series"))
+            .and(predicate::str::contains("holds for the original net.")),
+    );
+}
+
+#[test]
+fn reduction_log_without_reduction_is_rejected() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--reduction-log")
+        .arg("--skip-analysis");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--reduce|--collapse-gotos"));
+}
+
+#[test]
+fn cross_check_replays_the_execution_on_the_net() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--cross-check")
+        .arg("--skip-analysis");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Cross-check passed: The net fired",
+    ));
+}
+
+#[test]
+fn cross_check_does_not_confirm_a_block_on_an_untracked_primitive() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/channel/recv_without_send_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--cross-check")
+        .arg("--skip-analysis");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Every thread is blocked, but the net blocks a thread on `CHANNEL_0`, whose state the interpreter does not track",
+    ));
+}
+
+#[test]
+fn small_scope_preset_finds_the_deadlock_without_lola() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--preset=small-scope");
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("Result: Deadlock can be reached within the small scope").and(
+            predicate::str::contains("^ blocked here, waiting for the mutex `MUTEX_0`"),
+        ),
+    );
+}
+
+#[test]
+fn trace_exports_the_path_to_the_deadlock() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--preset=small-scope")
+        .arg("--trace");
+    cmd.assert().success();
+
+    temp_dir.child("net.trace.xml").assert(
+        predicate::str::starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<trace>\n")
+            .and(predicate::str::contains("  <transition id=\""))
+            .and(predicate::str::contains("  <state>\n    <place id=\"")),
+    );
+    temp_dir.child("net.trace.csv").assert(
+        predicate::str::starts_with("step,transition,")
+            .and(predicate::str::contains(",PROGRAM_START"))
+            .and(predicate::str::contains("\n0,,"))
+            .and(predicate::str::contains("\n1,")),
+    );
+}
+
+#[test]
+fn trace_conflicts_with_skip_analysis() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--trace")
+        .arg("--skip-analysis");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn small_scope_preset_bounds_the_channels() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/channel/send_then_recv.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--preset=small-scope");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The program is deadlock-free within the small scope",
+    ));
+    temp_dir
+        .child("net.lola")
+        .assert(predicate::str::contains("CHANNEL_0_FREE_SLOTS"));
+}
+
+#[test]
+fn small_scope_preset_does_not_report_a_deadlock_caused_by_the_channel_bound() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn main() {\n    let (sender, _receiver) = std::sync::mpsc::channel();\n    let _first = sender.send(1);\n    let _second = sender.send(2);\n    let _third = sender.send(3);\n}\n",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--preset=small-scope");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The program is deadlock-free within the small scope: All the 4 reachable markings were visited. Only the bounds of the small scope stop the program, e.g. `CHANNEL_0_FREE_SLOTS`, where the program would continue",
+    ));
+}
+
+#[test]
+fn small_scope_preset_bounds_the_loops() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str(
+        "fn main() {\n    let mutex = std::sync::Mutex::new(0);\n    loop {\n        let _guard = mutex.lock();\n    }\n}\n",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--preset=small-scope");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Only the bounds of the small scope stop the program, e.g. `main_BB1_LOOP_BOUND`",
+    ));
+    temp_dir
+        .child("net.lola")
+        .assert(predicate::str::contains("main_BB1_LOOP_ITERATION"));
+}
+
+#[test]
+fn small_scope_preset_conflicts_with_the_bounds() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--preset=small-scope")
+        .arg("--thread-instances=4");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn reduce_keeps_the_deadlock() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--reduce");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+    temp_dir.child("net.lola").assert(
+        predicate::str::contains("MUTEX_0").and(predicate::str::contains("main_BB5").not()),
+    );
+}
+
+#[test]
+fn collapse_gotos_conflicts_with_reduce() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--collapse-gotos")
+        .arg("--reduce");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn granularity_statement_adds_the_statements() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--granularity=statement");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+    temp_dir
+        .child("net.lola")
+        .assert(predicate::str::contains("main_BLOCK_1_STATEMENT_0"));
+}
+
+#[test]
+fn granularity_function_keeps_the_deadlock() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--granularity=function");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+}
+
+#[test]
+fn granularity_conflicts_with_cross_check() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--granularity=statement")
+        .arg("--cross-check");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn compare_lists_the_transitions_that_changed() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("compare")
+        .arg("./examples/programs/mutex/drop_mutex_guard_manually.rs")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs");
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("Net of the old version: ")
+            .and(predicate::str::contains("Function `main`:"))
+            .and(predicate::str::contains("\n  - "))
+            .and(predicate::str::contains("\n  + ")),
+    );
+}
+
+#[test]
+fn compare_check_fails_on_a_new_deadlock() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("compare")
+        .arg("./examples/programs/mutex/drop_mutex_guard_manually.rs")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--check")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ));
+
+    cmd.assert().code(8).stdout(predicate::str::contains(
+        "Result: The new version introduces a deadlock according to the model checker `LoLA`",
+    ));
+    temp_dir
+        .child("compare_old.lola")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn crate_folder_translates_the_modules_in_other_files() {
+    let crate_folder =
+        assert_fs::TempDir::new().expect("Could not create temporary crate folder for test");
+    crate_folder
+        .child("src/main.rs")
+        .write_str("mod locking;\n\nfn main() {\n    locking::lock_twice();\n}\n")
+        .expect("Could not write test file contents");
+    crate_folder
+        .child("src/locking.rs")
+        .write_str(
+            "pub fn lock_twice() {\n    let data = std::sync::Mutex::new(0);\n    let _d1 = data.lock();\n    let _d2 = data.lock();\n}\n",
+        )
+        .expect("Could not write test file contents");
+    let output_folder =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(crate_folder.path())
+        .arg(format!(
+            "--output-folder={}",
+            output_folder.path().to_string_lossy()
+        ))
+        .arg("--annotated-source");
+    cmd.assert().success().stdout(
+        predicate::str::contains("4 |     let _d2 = data.lock();").and(predicate::str::contains(
+            "^ blocked here, waiting for the mutex `MUTEX_0`",
+        )),
+    );
+
+    let annotated = std::fs::read_to_string(output_folder.path().join("net.annotated.rs"))
+        .expect("Could not read output file to string");
+    assert!(annotated.starts_with("mod locking;"));
+    assert!(annotated.contains("locking.rs"));
+    assert!(annotated.contains("// sync: MUTEX_0 created"));
+}
+
+#[test]
+fn crate_folder_without_crate_root() {
+    let crate_folder =
+        assert_fs::TempDir::new().expect("Could not create temporary crate folder for test");
+    crate_folder
+        .child("src/locking.rs")
+        .write_str("pub fn lock_twice() {}\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock").arg(crate_folder.path());
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("No crate root"));
+}
+
+#[test]
+fn dry_run_prints_the_plan_without_output_files() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/shared_counter.rs")
+        .arg("--filename=dry_run_does_not_generate_output")
+        .arg("--dry-run");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Entry points: main"))
+        .stdout(predicate::str::contains("Threads spawned (2):"))
+        .stdout(predicate::str::contains("mutex created at"))
+        .stdout(predicate::str::contains("Estimated net size:"));
+
+    if std::path::Path::new("./dry_run_does_not_generate_output.lola").exists() {
+        panic!("Should not generate a .lola file in a dry run");
+    }
+}
+
+#[test]
+fn tui_file_does_not_exist() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("tui")
+        .arg("test/file/doesnt/exist.rs");
+
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Source code file at test/file/doesnt/exist.rs does not exist",
+        ));
+}
+
+#[test]
+fn edition_and_cfg_are_passed_to_the_compiler() {
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    let file = output_folder.child("edition_2021.rs");
+    // `TryFrom` is only in the prelude since the edition 2021, and `main` only exists with the `cfg`.
+    file.write_str("#[cfg(translate)]\nfn main() {\n    let _ = u8::try_from(1u32);\n}\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            output_folder.path().display()
+        ))
+        .arg("--edition=2021")
+        .arg("--cfg=translate")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    output_folder
+        .child("net.lola")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn rustc_arg_is_passed_to_the_compiler() {
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+    let file = output_folder.child("edition_2021.rs");
+    file.write_str("fn main() {\n    let _ = u8::try_from(1u32);\n}\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            output_folder.path().display()
+        ))
+        .arg("--rustc-arg")
+        .arg("--edition=2021")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+}
+
+#[test]
+fn rustc_arg_is_not_valid() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--rustc-arg=--not-a-rustc-flag")
+        .arg("--skip-analysis");
+    cmd.assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Invalid arguments for the compiler",
+        ));
+}
+
+#[test]
+fn lint_reports_a_double_lock() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("lint")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs");
+
+    cmd.assert().code(8).stdout(
+        predicate::str::contains(
+            "double_lock_deadlock.rs:4:15: double lock: `data` is locked in `main` while the guard acquired at ",
+        )
+        .and(predicate::str::contains(
+            "1 structural deadlock pattern(s) found",
+        )),
+    );
+}
+
+#[test]
+fn lint_accepts_a_guard_dropped_manually() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("lint")
+        .arg("./examples/programs/mutex/drop_mutex_guard_manually.rs");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "No structural deadlock patterns found",
+    ));
+}
+
+#[test]
+fn verify_writes_the_formula_and_prints_the_verdict() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("verify")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--lola=./assets/lola")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ));
+
+    cmd.assert()
+        .success()
+        .stdout(
+            predicate::str::contains("`LoLA` explored ").and(predicate::str::contains(
+                "Result: Deadlock can be reached according to the model checker `LoLA`",
+            )),
+        );
+    temp_dir
+        .child("double_lock_deadlock.formula")
+        .assert("EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))");
+}
+
+#[test]
+fn verify_fails_if_lola_is_not_found() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("verify")
+        .arg("./examples/programs/mutex/drop_mutex_guard_manually.rs")
+        .arg("--lola=./assets/missing-lola")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ));
+
+    cmd.assert().code(11).stderr(predicate::str::contains(
+        "`./assets/missing-lola` was not found",
+    ));
+}
+
+#[test]
+fn net_diff_colors_the_added_and_removed_elements() {
+    let old = assert_fs::NamedTempFile::new("old.json").expect("Could not create temporary file");
+    old.write_str(
+        r#"{"places": [{"label": "MUTEX_0", "marking": 1}], "transitions": [{"label": "main_LOCK_0"}],
+        "arcs": [{"place": "MUTEX_0", "transition": "main_LOCK_0", "direction": "place_to_transition"}]}"#,
+    )
+    .expect("Could not write test file contents");
+    let new = assert_fs::NamedTempFile::new("new.json").expect("Could not create temporary file");
+    new.write_str(
+        r#"{"places": [{"label": "MUTEX_1", "marking": 1}], "transitions": [{"label": "main_LOCK_0"}],
+        "arcs": [{"place": "MUTEX_1", "transition": "main_LOCK_0", "direction": "place_to_transition"}]}"#,
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("net-diff")
+        .arg(old.path())
+        .arg(new.path());
+
+    cmd.assert().success().stdout(
+        predicate::str::starts_with("digraph petrinet {")
+            .and(predicate::str::contains(
+                "MUTEX_0 -> main_LOCK_0 [color=\"red\"",
+            ))
+            .and(predicate::str::contains(
+                "MUTEX_1 -> main_LOCK_0 [color=\"green3\"",
+            ))
+            .and(predicate::str::contains(
+                "main_LOCK_0 [shape=\"box\" xlabel=\"\" label=\"main_LOCK_0\" color=\"gray70\"",
+            )),
+    );
+}
+
+#[test]
+fn net_diff_rejects_an_invalid_net() {
+    let old = assert_fs::NamedTempFile::new("old.json").expect("Could not create temporary file");
+    old.write_str("not a net")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("net-diff")
+        .arg(old.path())
+        .arg(old.path());
+
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "Invalid JSON description of a net",
+    ));
+}
+
+#[test]
+fn formulas_are_written_next_to_the_net() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/condvar/self_notify_lost_signal.rs")
+        .arg("--formulas")
+        .arg("--skip-analysis")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ));
+    cmd.assert().success();
+
+    temp_dir
+        .child("net.deadlock.formula")
+        .assert(predicate::str::ends_with(
+            "EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))\n",
+        ));
+    temp_dir
+        .child("net.program_end_reachable.formula")
+        .assert(predicate::str::ends_with("EF (PROGRAM_END > 0)\n"));
+    temp_dir
+        .child("net.program_panic_unreachable.formula")
+        .assert(predicate::str::ends_with("AG (PROGRAM_PANIC = 0)\n"));
+    temp_dir
+        .child("net.condvar_0_lost_signal.formula")
+        .assert(predicate::str::ends_with(
+            "EF FIREABLE(CONDVAR_0_LOST_SIGNAL)\n",
+        ));
+}
+
+#[test]
+fn trace_rejects_a_witness_path_that_cannot_be_replayed() {
+    let witness =
+        assert_fs::NamedTempFile::new("deadlock.path").expect("Could not create temporary file");
+    witness
+        .write_str("main_MISSING_TRANSITION\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("trace")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg(witness.path());
+
+    cmd.assert().code(11).stderr(predicate::str::contains(
+        "The transition `main_MISSING_TRANSITION` does not exist",
+    ));
+}
+
+#[test]
+fn trace_fails_if_the_witness_path_does_not_exist() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("trace")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("./missing.path");
+
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "Witness path at ./missing.path cannot be read",
+    ));
+}
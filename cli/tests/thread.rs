@@ -0,0 +1,197 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use assert_fs::prelude::*; // Used for checking temp files
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+mod utils;
+
+mod dating_philosophers {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/thread/dating_philosophers.rs",
+        "./examples/results/thread/dating_philosophers/"
+    );
+}
+
+mod detached {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/thread/detached.rs",
+        "./examples/results/thread/detached/"
+    );
+}
+
+mod dining_philosophers {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/thread/dining_philosophers.rs",
+        "./examples/results/thread/dining_philosophers/"
+    );
+}
+
+mod diverging {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/thread/diverging.rs",
+        "./examples/results/thread/diverging/"
+    );
+}
+
+mod shared_counter {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/thread/shared_counter.rs",
+        "./examples/results/thread/shared_counter/"
+    );
+}
+
+mod spawn_with_empty_closure {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/thread/spawn_with_empty_closure.rs",
+        "./examples/results/thread/spawn_with_empty_closure/"
+    );
+}
+
+#[test]
+fn threads_spawned_in_a_loop_are_bounded() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/spawn_in_loop.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("--thread-instances=3");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The program is deadlock-free according to the model checker `LoLA`",
+    ));
+    temp_dir.child("deadlock_test.lola").assert(
+        predicate::str::contains("THREAD_0_INSTANCES : 3")
+            .and(predicate::str::contains("THREAD_0_SPAWN_SKIP")),
+    );
+}
+
+#[test]
+fn workers_of_a_pool_are_recognized() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/worker_pool.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("--worker-pool-size=3")
+        .arg("-vv");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Result: The program is deadlock-free according to the model checker `LoLA`",
+        ))
+        .stderr(predicate::str::contains(
+            "note: worker of a pool waiting on `std::sync::Mutex::<T>::lock` in a loop, modelled with 3 instances",
+        ));
+    temp_dir
+        .child("deadlock_test.lola")
+        .assert(predicate::str::contains("THREAD_0_INSTANCES : 3"));
+}
+
+#[test]
+fn boxed_closure_passed_to_spawn_is_translated() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/thread/boxed_closure_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn raw_pointer_captured_by_thread_is_reported() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/raw_pointer_capture.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("-v");
+
+    cmd.assert().success().stderr(predicate::str::contains(
+        "The thread 0 captures `pointer`, which contains a raw pointer",
+    ));
+}
+
+#[test]
+fn threads_spawned_by_threads_are_translated() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/nested_spawn_deadlock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+    temp_dir.child("deadlock_test.lola").assert(
+        predicate::str::contains("THREAD_0_START").and(predicate::str::contains("THREAD_1_START")),
+    );
+}
+
+#[test]
+fn thread_panics_are_propagated_to_join() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/panicking_worker.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("--propagate-thread-panics");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The program is deadlock-free according to the model checker `LoLA`",
+    ));
+    temp_dir.child("deadlock_test.lola").assert(
+        predicate::str::contains("THREAD_0_PANIC")
+            .and(predicate::str::contains("THREAD_0_JOIN_ERR")),
+    );
+}
+
+#[test]
+fn fields_captured_by_thread_closure_are_linked() {
+    utils::assert_lola_result_in_temp_folder(
+        "./examples/programs/thread/disjoint_capture_deadlock.rs",
+        true,
+    );
+}
+
+#[test]
+fn lock_held_by_panicking_thread_is_released() {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/panic_while_holding_lock.rs")
+        .arg(format!(
+            "--output-folder={}",
+            temp_dir.path().to_string_lossy()
+        ))
+        .arg("--filename=deadlock_test")
+        .arg("--propagate-thread-panics");
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The program is deadlock-free according to the model checker `LoLA`",
+    ));
+}
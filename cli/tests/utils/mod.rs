@@ -62,6 +62,28 @@ pub fn assert_lola_result(
     source_code_file: &str,
     output_folder: &str,
     output_should_have_deadlock: bool,
+) {
+    assert_lola_result_with_args(
+        source_code_file,
+        output_folder,
+        &[],
+        output_should_have_deadlock,
+    );
+}
+
+/// Asserts the result of the model checker `LoLA` like `assert_lola_result`,
+/// passing the given extra arguments to `cargo-check-deadlock`.
+///
+/// # Panics
+///
+/// If the command `cargo-check-deadlock` is not found, then the function panics.
+/// If the command `cargo-check-deadlock` fails to complete execution successfully, then the function panics.
+#[allow(dead_code)]
+pub fn assert_lola_result_with_args(
+    source_code_file: &str,
+    output_folder: &str,
+    args: &[&str],
+    output_should_have_deadlock: bool,
 ) {
     let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
 
@@ -69,7 +91,8 @@ pub fn assert_lola_result(
     cmd.arg("check-deadlock")
         .arg(source_code_file)
         .arg(format!("--output-folder={output_folder}"))
-        .arg("--filename=deadlock_test");
+        .arg("--filename=deadlock_test")
+        .args(args);
 
     if output_should_have_deadlock {
         cmd.assert().success().stdout(predicate::str::contains(
@@ -85,6 +108,47 @@ pub fn assert_lola_result(
     std::fs::remove_file(output_filename).expect("Could not delete output file");
 }
 
+/// Asserts the result of the model checker `LoLA` like `assert_lola_result`,
+/// writing the output files to a temporary folder that is deleted afterwards.
+///
+/// # Panics
+///
+/// If the temporary folder cannot be created, then the function panics.
+/// If the command `cargo-check-deadlock` is not found, then the function panics.
+/// If the command `cargo-check-deadlock` fails to complete execution successfully, then the function panics.
+#[allow(dead_code)]
+pub fn assert_lola_result_in_temp_folder(
+    source_code_file: &str,
+    output_should_have_deadlock: bool,
+) {
+    assert_lola_result_in_temp_folder_with_args(source_code_file, &[], output_should_have_deadlock);
+}
+
+/// Asserts the result of the model checker `LoLA` like `assert_lola_result_in_temp_folder`,
+/// passing the given extra arguments to `cargo-check-deadlock`, e.g. `--model-poisoning`.
+///
+/// # Panics
+///
+/// If the temporary folder cannot be created, then the function panics.
+/// If the command `cargo-check-deadlock` is not found, then the function panics.
+/// If the command `cargo-check-deadlock` fails to complete execution successfully, then the function panics.
+#[allow(dead_code)]
+pub fn assert_lola_result_in_temp_folder_with_args(
+    source_code_file: &str,
+    args: &[&str],
+    output_should_have_deadlock: bool,
+) {
+    let temp_dir =
+        assert_fs::TempDir::new().expect("Could not create temporary output folder for test");
+    let output_folder = format!("{}/", temp_dir.path().to_string_lossy());
+    assert_lola_result_with_args(
+        source_code_file,
+        &output_folder,
+        args,
+        output_should_have_deadlock,
+    );
+}
+
 /// This macro generates the test code for the three supported file formats.
 /// It saves a considerable amount of boilerplate.
 ///
@@ -0,0 +1,154 @@
+//! Module that implements the internal analyses of the Petri net.
+//!
+//! The analyses operate on the plain description of the net (`NetData`)
+//! and do not require an external model checker.
+//! They are meant for small nets, e.g. in the browser playground,
+//! where running `LoLA` is not possible.
+//! The `reduction` submodule shrinks the net before it is handed to a model checker.
+//! The `lint` submodule is the exception: Its detectors run on the lock operations recorded in the MIR
+//! representation, without a net.
+
+pub mod cross_check;
+pub mod lint;
+pub mod reachability;
+pub mod reduction;
+pub mod self_check;
+pub mod simulation;
+
+use std::collections::HashMap;
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC};
+
+/// Labels of the places that model the end states of the program.
+/// A marking where one of these places has a token is not considered a deadlock,
+/// like in the formula used for `LoLA`.
+pub const FINAL_PLACES: [&str; 2] = [PROGRAM_END, PROGRAM_PANIC];
+
+/// A marking of the net, i.e. the number of tokens in each place, indexed like `NetData::places`.
+pub type Marking = Vec<usize>;
+
+/// A representation of the net with indices instead of labels, suited for firing transitions quickly.
+struct IndexedNet {
+    /// The labels of the transitions, indexed like `NetData::transitions`.
+    transition_labels: Vec<String>,
+    /// For each transition, the places from which it consumes tokens and how many.
+    consumed: Vec<Vec<(usize, usize)>>,
+    /// For each transition, the places in which it produces tokens and how many.
+    produced: Vec<Vec<(usize, usize)>>,
+    /// The labels of the places, indexed like `NetData::places`.
+    place_labels: Vec<String>,
+    /// The capacity of each place, if any.
+    capacities: Vec<Option<usize>>,
+    /// The indices of the places that model the end states of the program.
+    final_places: Vec<usize>,
+    /// The initial marking of the net.
+    initial_marking: Marking,
+}
+
+impl IndexedNet {
+    /// Creates the indexed representation of the net.
+    ///
+    /// # Errors
+    ///
+    /// If an arc refers to a place or a transition that does not exist, then an error is returned.
+    fn new(net: &NetData) -> Result<Self, String> {
+        let place_index: HashMap<&str, usize> = net
+            .places
+            .iter()
+            .enumerate()
+            .map(|(index, place)| (place.label.as_str(), index))
+            .collect();
+        let transition_index: HashMap<&str, usize> = net
+            .transitions
+            .iter()
+            .enumerate()
+            .map(|(index, transition)| (transition.label.as_str(), index))
+            .collect();
+
+        let mut consumed = vec![Vec::new(); net.transitions.len()];
+        let mut produced = vec![Vec::new(); net.transitions.len()];
+        for arc in &net.arcs {
+            let place = *place_index
+                .get(arc.place.as_str())
+                .ok_or_else(|| format!("The arc refers to an unknown place `{}`", arc.place))?;
+            let transition = *transition_index
+                .get(arc.transition.as_str())
+                .ok_or_else(|| {
+                    format!(
+                        "The arc refers to an unknown transition `{}`",
+                        arc.transition
+                    )
+                })?;
+            let weights = match arc.direction {
+                ArcDirection::PlaceToTransition => &mut consumed[transition],
+                ArcDirection::TransitionToPlace => &mut produced[transition],
+            };
+            // Repeated arcs add up their weights.
+            if let Some((_, weight)) = weights.iter_mut().find(|(index, _)| *index == place) {
+                *weight += arc.weight;
+            } else {
+                weights.push((place, arc.weight));
+            }
+        }
+
+        Ok(Self {
+            transition_labels: net
+                .transitions
+                .iter()
+                .map(|transition| transition.label.clone())
+                .collect(),
+            consumed,
+            produced,
+            place_labels: net.places.iter().map(|place| place.label.clone()).collect(),
+            capacities: net.places.iter().map(|place| place.capacity).collect(),
+            final_places: FINAL_PLACES
+                .iter()
+                .filter_map(|label| place_index.get(label).copied())
+                .collect(),
+            initial_marking: net.places.iter().map(|place| place.marking).collect(),
+        })
+    }
+
+    /// Checks whether the transition can fire in the given marking.
+    fn is_enabled(&self, transition: usize, marking: &[usize]) -> bool {
+        self.consumed[transition]
+            .iter()
+            .all(|(place, weight)| marking[*place] >= *weight)
+    }
+
+    /// Returns the indices of the transitions that can fire in the given marking.
+    fn enabled_transitions(&self, marking: &[usize]) -> Vec<usize> {
+        (0..self.transition_labels.len())
+            .filter(|transition| self.is_enabled(*transition, marking))
+            .collect()
+    }
+
+    /// Returns the marking reached after firing the transition in the given marking.
+    /// The transition must be enabled in the marking.
+    fn fire(&self, transition: usize, marking: &[usize]) -> Marking {
+        let mut next = marking.to_vec();
+        for (place, weight) in &self.consumed[transition] {
+            next[*place] -= weight;
+        }
+        for (place, weight) in &self.produced[transition] {
+            next[*place] += weight;
+        }
+        next
+    }
+
+    /// Returns the index of the first place that holds more tokens than its capacity in the given marking, if any.
+    fn exceeded_capacity(&self, marking: &[usize]) -> Option<usize> {
+        self.capacities
+            .iter()
+            .zip(marking)
+            .position(|(capacity, tokens)| capacity.is_some_and(|capacity| *tokens > capacity))
+    }
+
+    /// Checks whether the marking is a deadlock, i.e. no transition is enabled
+    /// and the program did not reach one of its end states.
+    fn is_deadlock(&self, marking: &[usize]) -> bool {
+        self.final_places.iter().all(|place| marking[*place] == 0)
+            && self.enabled_transitions(marking).is_empty()
+    }
+}
@@ -0,0 +1,634 @@
+//! Submodule that replays an execution of the program on the net, as a test oracle for the translation.
+//!
+//! The translator interprets the deterministic subset of the program for a bounded schedule
+//! and reports every move of a thread from a basic block to the next one as a move of its control token
+//! between the places of the two blocks. The `Replay` fires the transitions that connect the places in the net.
+//! A move may need several transitions, e.g. a call to `Condvar::wait` first releases the mutex
+//! and then waits for the notification, so the paths of at most `MAX_PATH_LENGTH` transitions are tried,
+//! the shortest ones first.
+//! A transition that produces no token in a place without a capacity parks the control token
+//! in a synchronization primitive, e.g. the condition variable while waiting. The thread continues with a transition
+//! that gives back to the primitive what the parking transition took, e.g. the notification received.
+//!
+//! If a path exists but is not enabled, the net blocks the thread, e.g. waiting for a mutex.
+//! The thread is blocked at a place or, if the control token was parked, at the parking transition.
+//! If no path exists, the net cannot reproduce the execution of the program, which is a bug in the translator.
+//!
+//! The replay does not decide whether the program blocks: The interpreter derives it from the execution,
+//! e.g. a mutex locked by another thread, and compares it with the net. A move that only the net blocks,
+//! or that the net fires although the program is blocked, is also a bug in the translator.
+
+use std::collections::HashMap;
+
+use super::{IndexedNet, Marking};
+use crate::data_structures::net_data::NetData;
+use crate::data_structures::span_info::SpanInfo;
+
+/// The maximum number of transitions fired to move the control token from one place to the next.
+pub const MAX_PATH_LENGTH: usize = 4;
+
+/// The result of moving the control token of a thread.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The transitions between the places were fired.
+    Fired,
+    /// The thread is blocked at the given place. The transitions before it were fired.
+    Blocked { at: String },
+    /// The net has no path between the places.
+    Missing,
+}
+
+/// The position of the control token of a thread in the net.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// The control token is in the place with the given index.
+    Place(usize),
+    /// The transition with the given index parked the control token in a synchronization primitive.
+    Parked(usize),
+}
+
+/// A sequence of transitions, each one together with the position of the control token after firing it.
+type Path = Vec<(usize, Position)>;
+
+/// The replay of an execution on the net, starting at the initial marking.
+pub struct Replay {
+    /// The net on which the execution is replayed.
+    net: IndexedNet,
+    /// The index of every place, by label.
+    place_index: HashMap<String, usize>,
+    /// The index of every transition, by label.
+    transition_index: HashMap<String, usize>,
+    /// For each place, the transitions that consume a token from it.
+    consumers: Vec<Vec<usize>>,
+    /// For each place, the transitions that produce a token in it.
+    producers: Vec<Vec<usize>>,
+    /// For each place, whether it holds the control token of a thread, see `with_control_places`.
+    control_places: Vec<bool>,
+    /// The current marking.
+    marking: Marking,
+    /// The labels of the transitions fired so far, in order.
+    history: Vec<String>,
+}
+
+impl Replay {
+    /// Creates a new replay on the net starting at the initial marking.
+    ///
+    /// # Errors
+    ///
+    /// If the net is not valid, e.g. an arc refers to an unknown place, then an error is returned.
+    pub fn new(net: &NetData) -> Result<Self, String> {
+        let net = IndexedNet::new(net)?;
+        let mut consumers = vec![Vec::new(); net.place_labels.len()];
+        for (transition, consumed) in net.consumed.iter().enumerate() {
+            for (place, _) in consumed {
+                consumers[*place].push(transition);
+            }
+        }
+        let mut producers = vec![Vec::new(); net.place_labels.len()];
+        for (transition, produced) in net.produced.iter().enumerate() {
+            for (place, _) in produced {
+                producers[*place].push(transition);
+            }
+        }
+        Ok(Self {
+            place_index: net
+                .place_labels
+                .iter()
+                .enumerate()
+                .map(|(index, label)| (label.clone(), index))
+                .collect(),
+            transition_index: net
+                .transition_labels
+                .iter()
+                .enumerate()
+                .map(|(index, label)| (label.clone(), index))
+                .collect(),
+            control_places: vec![false; net.place_labels.len()],
+            consumers,
+            producers,
+            marking: net.initial_marking.clone(),
+            history: Vec::new(),
+            net,
+        })
+    }
+
+    /// Sets the places that hold the control token of a thread, e.g. the places of the basic blocks.
+    /// A path never consumes from them except from the place where it starts, so that the move of a thread
+    /// does not fire the move of another one, e.g. the `recv` of the thread waiting for a message.
+    /// The labels that are not in the net are ignored.
+    #[must_use]
+    pub fn with_control_places<'a>(mut self, labels: impl IntoIterator<Item = &'a str>) -> Self {
+        for label in labels {
+            if let Some(place) = self.place_index.get(label) {
+                self.control_places[*place] = true;
+            }
+        }
+        self
+    }
+
+    /// Returns the labels of the transitions fired so far, in order.
+    #[must_use]
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Moves the control token of a thread from `from` to the place `to`.
+    /// `from` is a place or the transition that parked the control token.
+    ///
+    /// Among the paths between the positions, the first one that is enabled is fired, see `paths`.
+    /// If none is enabled, the longest enabled prefix of a path is fired and the thread is blocked
+    /// at the position where it stopped. The next call must continue from that position.
+    pub fn step(&mut self, from: &str, to: &str) -> StepOutcome {
+        if from == to {
+            return StepOutcome::Fired;
+        }
+        let (Some(from), Some(&to)) = (self.position(from), self.place_index.get(to)) else {
+            return StepOutcome::Missing;
+        };
+        let Some((enabled, fired, marking, position)) = self.find_move(from, to) else {
+            return StepOutcome::Missing;
+        };
+        self.commit(&fired, marking);
+        if enabled {
+            StepOutcome::Fired
+        } else {
+            StepOutcome::Blocked {
+                at: self.label(position),
+            }
+        }
+    }
+
+    /// Checks whether the net can move the control token of a thread from `from` to the place `to`
+    /// in the current marking, without firing any transition.
+    #[must_use]
+    pub fn can_step(&self, from: &str, to: &str) -> bool {
+        if from == to {
+            return true;
+        }
+        let (Some(from), Some(&to)) = (self.position(from), self.place_index.get(to)) else {
+            return false;
+        };
+        self.find_move(from, to)
+            .is_some_and(|(enabled, _, _, _)| enabled)
+    }
+
+    /// Finds the path that moves the control token from `from` to the place `to`, see `step`.
+    ///
+    /// Returns whether the whole path is enabled, the transitions fired, the marking reached
+    /// and the position of the control token, or `None` if there is no path.
+    fn find_move(
+        &self,
+        from: Position,
+        to: usize,
+    ) -> Option<(bool, Vec<usize>, Marking, Position)> {
+        let paths = self.paths(from, to);
+        if paths.is_empty() {
+            return None;
+        }
+        // The longest enabled prefix found so far: The fired transitions, the marking and the position reached.
+        let mut blocked = (Vec::new(), self.marking.clone(), from);
+        for path in &paths {
+            let (fired, marking, position) = self.fire_prefix(from, path);
+            if fired.len() == path.len() {
+                return Some((true, fired, marking, position));
+            }
+            if fired.len() > blocked.0.len() {
+                blocked = (fired, marking, position);
+            }
+        }
+        let (fired, marking, position) = blocked;
+        Some((false, fired, marking, position))
+    }
+
+    /// Fires the transitions of the path starting at `from` until one is not enabled
+    /// or exceeds the capacity of a place.
+    /// Returns the transitions fired, the marking reached and the position of the control token.
+    fn fire_prefix(
+        &self,
+        from: Position,
+        path: &[(usize, Position)],
+    ) -> (Vec<usize>, Marking, Position) {
+        let mut marking = self.marking.clone();
+        let mut fired = Vec::new();
+        let mut position = from;
+        for (transition, next_position) in path {
+            if !self.net.is_enabled(*transition, &marking) {
+                break;
+            }
+            let next_marking = self.net.fire(*transition, &marking);
+            if self.net.exceeded_capacity(&next_marking).is_some() {
+                break;
+            }
+            marking = next_marking;
+            fired.push(*transition);
+            position = *next_position;
+        }
+        (fired, marking, position)
+    }
+
+    /// Returns the position with the given label, i.e. a place or the transition that parked the control token.
+    fn position(&self, label: &str) -> Option<Position> {
+        self.place_index
+            .get(label)
+            .map(|place| Position::Place(*place))
+            .or_else(|| {
+                self.transition_index
+                    .get(label)
+                    .map(|transition| Position::Parked(*transition))
+            })
+    }
+
+    /// Returns the label of the place or of the parking transition of the position.
+    fn label(&self, position: Position) -> String {
+        match position {
+            Position::Place(place) => self.net.place_labels[place].clone(),
+            Position::Parked(transition) => self.net.transition_labels[transition].clone(),
+        }
+    }
+
+    /// Returns the transitions that move the control token from the position,
+    /// each one together with the position of the control token after firing it.
+    ///
+    /// From a place, these are the transitions that consume from it.
+    /// From a parking transition, these are the transitions that give back a token to a place with a capacity
+    /// that the parking transition took without giving it back, e.g. the notification of a condition variable
+    /// that enables waiting on it again.
+    /// The transitions that consume the control token of another thread are excluded.
+    fn successors(&self, position: Position) -> Vec<(usize, Position)> {
+        let transitions: Vec<usize> = match position {
+            Position::Place(place) => self.consumers[place].clone(),
+            Position::Parked(parking) => self.net.consumed[parking]
+                .iter()
+                .filter(|(place, _)| {
+                    self.net.capacities[*place].is_some()
+                        && !self.net.produced[parking]
+                            .iter()
+                            .any(|(produced, _)| produced == place)
+                })
+                .flat_map(|(place, _)| {
+                    self.producers[*place]
+                        .iter()
+                        .copied()
+                        .filter(move |transition| {
+                            !self.net.consumed[*transition]
+                                .iter()
+                                .any(|(consumed, _)| consumed == place)
+                        })
+                })
+                .collect(),
+        };
+        let mut successors = Vec::new();
+        for transition in transitions {
+            let consumes_other_control = self.net.consumed[transition].iter().any(|(place, _)| {
+                self.control_places[*place] && position != Position::Place(*place)
+            });
+            if consumes_other_control {
+                continue;
+            }
+            // The places with a capacity model synchronization primitives, e.g. a mutex,
+            // so the control token never goes there.
+            let places: Vec<usize> = self.net.produced[transition]
+                .iter()
+                .map(|(place, _)| *place)
+                .filter(|place| self.net.capacities[*place].is_none())
+                .collect();
+            if places.is_empty() {
+                successors.push((transition, Position::Parked(transition)));
+            }
+            successors.extend(
+                places
+                    .into_iter()
+                    .map(|place| (transition, Position::Place(place))),
+            );
+        }
+        successors
+    }
+
+    /// Returns the paths from `from` to the place `to` with at most `MAX_PATH_LENGTH` transitions,
+    /// the shortest ones first. A longer path is needed when a shorter one is not enabled,
+    /// e.g. `Condvar::wait_while` skips the wait if the condition is set and waits otherwise.
+    /// In a path, each transition moves the control token from the position reached by the previous one,
+    /// see `successors`.
+    fn paths(&self, from: Position, to: usize) -> Vec<Path> {
+        let mut found = Vec::new();
+        let mut paths: Vec<Path> = vec![Vec::new()];
+        for _ in 0..MAX_PATH_LENGTH {
+            let mut next_paths = Vec::new();
+            for path in &paths {
+                let position = path.last().map_or(from, |(_, position)| *position);
+                for successor in self.successors(position) {
+                    let mut next_path = path.clone();
+                    next_path.push(successor);
+                    next_paths.push(next_path);
+                }
+            }
+            found.extend(
+                next_paths
+                    .iter()
+                    .filter(|path| {
+                        path.last()
+                            .is_some_and(|(_, position)| *position == Position::Place(to))
+                    })
+                    .cloned(),
+            );
+            paths = next_paths;
+        }
+        found
+    }
+
+    /// Replaces the marking and records the fired transitions in the history.
+    fn commit(&mut self, fired: &[usize], marking: Marking) {
+        self.marking = marking;
+        self.history.extend(
+            fired
+                .iter()
+                .map(|transition| self.net.transition_labels[*transition].clone()),
+        );
+    }
+}
+
+/// How the cross-check of the translation ended.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CrossCheckVerdict {
+    /// Every thread of the execution finished.
+    Completed,
+    /// Every unfinished thread of the execution is blocked in the program and in the net.
+    Deadlock,
+    /// The interpreter reached code outside of the deterministic subset that it supports.
+    Stopped {
+        reason: String,
+        span: Option<SpanInfo>,
+    },
+    /// The execution reached the maximum number of steps.
+    StepLimit,
+    /// The net has no path for a step of the execution, i.e. the translation is not sound.
+    Mismatch {
+        from: String,
+        to: String,
+        span: Option<SpanInfo>,
+    },
+    /// The net blocks a step of the execution that the program makes.
+    NetBlocked {
+        from: String,
+        to: String,
+        span: Option<SpanInfo>,
+    },
+    /// The net fires a step of the execution on which the program is blocked.
+    ProgramBlocked {
+        from: String,
+        to: String,
+        span: Option<SpanInfo>,
+    },
+}
+
+/// The result of the cross-check of the translation against an execution of the program.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CrossCheckReport {
+    /// How the cross-check ended.
+    pub verdict: CrossCheckVerdict,
+    /// The number of steps of the execution replayed on the net.
+    pub steps: usize,
+    /// The labels of the transitions fired on the net, in order.
+    pub fired: Vec<String>,
+}
+
+impl CrossCheckReport {
+    /// Checks whether the net failed to reproduce the execution.
+    #[must_use]
+    pub const fn is_mismatch(&self) -> bool {
+        matches!(
+            self.verdict,
+            CrossCheckVerdict::Mismatch { .. }
+                | CrossCheckVerdict::NetBlocked { .. }
+                | CrossCheckVerdict::ProgramBlocked { .. }
+        )
+    }
+
+    /// Writes the failure of the cross-check with the span of the code that caused the step,
+    /// followed by the transitions fired before it.
+    fn write_failure(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        failure: &str,
+        span: Option<&SpanInfo>,
+        consequence: &str,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "Cross-check failed after {} steps: {failure}",
+            self.steps
+        )?;
+        if let Some(span) = span {
+            write!(f, " for the code at {span}")?;
+        }
+        write!(f, "{consequence}. The transitions fired before are:")?;
+        for transition in &self.fired {
+            write!(f, "\n  {transition}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CrossCheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let steps = self.steps;
+        let transitions = self.fired.len();
+        match &self.verdict {
+            CrossCheckVerdict::Completed => write!(
+                f,
+                "Cross-check passed: The net fired {transitions} transitions for the {steps} steps of the execution until every thread finished"
+            ),
+            CrossCheckVerdict::Deadlock => write!(
+                f,
+                "Cross-check passed: The net fired {transitions} transitions for the {steps} steps of the execution until every thread was blocked"
+            ),
+            CrossCheckVerdict::Stopped { reason, span } => {
+                write!(
+                    f,
+                    "Cross-check passed for the first {steps} steps of the execution ({transitions} transitions). The interpreter stopped"
+                )?;
+                if let Some(span) = span {
+                    write!(f, " at {span}")?;
+                }
+                write!(f, ": {reason}")
+            }
+            CrossCheckVerdict::StepLimit => write!(
+                f,
+                "Cross-check passed for the first {steps} steps of the execution ({transitions} transitions). The interpreter reached the maximum number of steps"
+            ),
+            CrossCheckVerdict::Mismatch { from, to, span } => self.write_failure(
+                f,
+                &format!("The net has no transitions from `{from}` to `{to}`"),
+                span.as_ref(),
+                "",
+            ),
+            CrossCheckVerdict::NetBlocked { from, to, span } => self.write_failure(
+                f,
+                &format!("The net blocks the move from `{from}` to `{to}`"),
+                span.as_ref(),
+                ", but the program continues",
+            ),
+            CrossCheckVerdict::ProgramBlocked { from, to, span } => self.write_failure(
+                f,
+                &format!("The net fires the move from `{from}` to `{to}`"),
+                span.as_ref(),
+                ", but the program is blocked",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod cross_check_tests {
+    use super::*;
+    use crate::data_structures::net_builder::NetBuilder;
+
+    /// A thread that locks a mutex in `BB0`, then waits on a condition variable in `BB1`,
+    /// which parks its control token and releases the mutex until a notification arrives, and then returns.
+    /// Another thread notifies the condition variable in `BB3`.
+    fn net() -> NetData {
+        NetBuilder::new()
+            .place("BB0", 1)
+            .place("BB1", 0)
+            .bounded_place("WAIT_ENABLED", 1, 1)
+            .place("BB2", 0)
+            .bounded_place("MUTEX", 1, 1)
+            .place("NOTIFIED", 0)
+            .place("BB3", 1)
+            .place("BB4", 0)
+            .transition("LOCK")
+            .input("BB0", "LOCK")
+            .input("MUTEX", "LOCK")
+            .output("LOCK", "BB1")
+            .transition("WAIT")
+            .input("BB1", "WAIT")
+            .input("WAIT_ENABLED", "WAIT")
+            .output("WAIT", "MUTEX")
+            .transition("WAKE_UP")
+            .input("NOTIFIED", "WAKE_UP")
+            .input("MUTEX", "WAKE_UP")
+            .output("WAKE_UP", "WAIT_ENABLED")
+            .output("WAKE_UP", "BB2")
+            .transition("NOTIFY")
+            .input("BB3", "NOTIFY")
+            .output("NOTIFY", "NOTIFIED")
+            .output("NOTIFY", "BB4")
+            .build()
+    }
+
+    #[test]
+    fn step_fires_the_transitions_between_the_places() {
+        let mut replay = Replay::new(&net()).unwrap();
+
+        assert_eq!(replay.step("BB0", "BB0"), StepOutcome::Fired);
+        assert_eq!(replay.step("BB0", "BB1"), StepOutcome::Fired);
+        assert_eq!(replay.history(), ["LOCK"]);
+    }
+
+    #[test]
+    fn can_step_does_not_fire_the_transitions() {
+        let mut replay = Replay::new(&net()).unwrap();
+
+        assert!(replay.can_step("BB0", "BB1"));
+        assert!(replay.history().is_empty());
+        replay.step("BB0", "BB1");
+        // The notification never arrives.
+        assert!(!replay.can_step("BB1", "BB2"));
+        assert_eq!(replay.history(), ["LOCK"]);
+    }
+
+    #[test]
+    fn step_fires_the_enabled_prefix_when_blocked() {
+        let mut replay = Replay::new(&net()).unwrap();
+        replay.step("BB0", "BB1");
+
+        assert_eq!(
+            replay.step("BB1", "BB2"),
+            StepOutcome::Blocked {
+                at: "WAIT".to_string()
+            }
+        );
+        assert_eq!(replay.history(), ["LOCK", "WAIT"]);
+        // The mutex was released by the wait, so another thread may lock it now.
+        assert_eq!(replay.marking[4], 1);
+    }
+
+    #[test]
+    fn step_continues_from_the_parking_transition() {
+        let mut replay = Replay::new(&net()).unwrap();
+        replay.step("BB0", "BB1");
+        replay.step("BB1", "BB2");
+
+        assert_eq!(replay.step("BB3", "BB4"), StepOutcome::Fired);
+        assert_eq!(replay.step("WAIT", "BB2"), StepOutcome::Fired);
+        assert_eq!(replay.history(), ["LOCK", "WAIT", "NOTIFY", "WAKE_UP"]);
+    }
+
+    #[test]
+    fn step_does_not_fire_the_move_of_another_thread() {
+        // A rendezvous: The sender in `BB0` waits until the receiver in `BB2` takes the message.
+        let net = NetBuilder::new()
+            .place("BB0", 1)
+            .place("WAITING", 0)
+            .place("BB1", 0)
+            .place("BUFFER", 0)
+            .place("RECEIVED", 0)
+            .place("BB2", 1)
+            .place("BB3", 0)
+            .transition("SEND")
+            .input("BB0", "SEND")
+            .output("SEND", "WAITING")
+            .output("SEND", "BUFFER")
+            .transition("LEAVE")
+            .input("WAITING", "LEAVE")
+            .input("RECEIVED", "LEAVE")
+            .output("LEAVE", "BB1")
+            .transition("RECV")
+            .input("BB2", "RECV")
+            .input("BUFFER", "RECV")
+            .output("RECV", "RECEIVED")
+            .output("RECV", "BB3")
+            .build();
+        let mut replay = Replay::new(&net)
+            .unwrap()
+            .with_control_places(["BB0", "BB1", "BB2", "BB3"]);
+
+        assert_eq!(
+            replay.step("BB0", "BB1"),
+            StepOutcome::Blocked {
+                at: "WAITING".to_string()
+            }
+        );
+        assert_eq!(replay.step("BB2", "BB3"), StepOutcome::Fired);
+        assert_eq!(replay.step("WAITING", "BB1"), StepOutcome::Fired);
+        assert_eq!(replay.history(), ["SEND", "RECV", "LEAVE"]);
+    }
+
+    #[test]
+    fn block_only_in_the_net_is_a_mismatch() {
+        let report = CrossCheckReport {
+            verdict: CrossCheckVerdict::NetBlocked {
+                from: "BB0".to_string(),
+                to: "BB1".to_string(),
+                span: None,
+            },
+            steps: 1,
+            fired: vec!["START".to_string()],
+        };
+
+        assert!(report.is_mismatch());
+        assert_eq!(
+            report.to_string(),
+            "Cross-check failed after 1 steps: The net blocks the move from `BB0` to `BB1`, but the program continues. The transitions fired before are:\n  START"
+        );
+    }
+
+    #[test]
+    fn step_without_path_is_missing() {
+        let mut replay = Replay::new(&net()).unwrap();
+
+        assert_eq!(replay.step("BB1", "BB0"), StepOutcome::Missing);
+        assert_eq!(replay.step("BB0", "UNKNOWN"), StepOutcome::Missing);
+        assert!(replay.history().is_empty());
+    }
+}
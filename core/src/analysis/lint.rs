@@ -0,0 +1,434 @@
+//! Submodule that implements the structural detectors of the lint mode.
+//!
+//! The lint mode does not build the Petri net. The translator walks the MIR representation of every
+//! function once and records the lock operations in the order of the control flow, see `LockEvent`.
+//! The detectors then look for patterns that are known to cause deadlocks:
+//!
+//! - A double lock: A lock is acquired again in the same function while a guard of it is still held.
+//! - A lock-order inversion: Two locks are acquired in one order in a function and in the opposite order in another one.
+//! - A guard held across an `.await`: The guard is stored in the state of the future while it is suspended.
+//! - A condition variable used with different mutexes.
+//!
+//! The locks are identified by the expression that names them in the source code, e.g. `self.data`.
+//! Aliases, e.g. a clone of an `Arc` stored in a variable with a different name, are not followed
+//! and the branches of the control flow are not told apart. The findings are therefore hints,
+//! not a verdict: The translation to a Petri net and the model checker are needed for that.
+
+use std::collections::HashMap;
+
+use crate::data_structures::span_info::SpanInfo;
+
+/// The kind of access to a lock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockAccess {
+    /// A lock of a mutex or a write lock of a read-write lock.
+    Exclusive,
+    /// A read lock of a read-write lock.
+    Shared,
+}
+
+/// An operation on a lock recorded in a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LockEvent {
+    /// The lock is acquired and a guard for it is created.
+    Acquire {
+        lock: String,
+        access: LockAccess,
+        span: SpanInfo,
+    },
+    /// A guard for the lock is dropped.
+    Release { lock: String },
+    /// The condition variable is waited on with a guard for the lock.
+    Wait {
+        condvar: String,
+        lock: String,
+        span: SpanInfo,
+    },
+    /// A value whose type contains a guard lives across a suspension point of the future.
+    HeldAcrossAwait { type_name: String, span: SpanInfo },
+}
+
+/// The lock operations recorded in a function, in the order of the control flow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FunctionEvents {
+    /// The name of the function, e.g. `main::{closure#0}`.
+    pub function: String,
+    /// The operations on locks, in the order of the control flow.
+    pub events: Vec<LockEvent>,
+}
+
+/// The kind of pattern found by a detector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintKind {
+    DoubleLock,
+    LockOrderInversion,
+    GuardAcrossAwait,
+    CondvarMutexMismatch,
+}
+
+impl std::fmt::Display for LintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DoubleLock => write!(f, "double lock"),
+            Self::LockOrderInversion => write!(f, "lock-order inversion"),
+            Self::GuardAcrossAwait => write!(f, "guard held across await"),
+            Self::CondvarMutexMismatch => write!(f, "condvar-mutex mismatch"),
+        }
+    }
+}
+
+/// A pattern found by one of the detectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintFinding {
+    /// The kind of pattern.
+    pub kind: LintKind,
+    /// The span where the pattern shows, e.g. the second lock of a double lock.
+    pub span: SpanInfo,
+    /// The explanation of the finding.
+    pub message: String,
+}
+
+impl std::fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.span, self.kind, self.message)
+    }
+}
+
+/// The findings of the lint mode, sorted by span.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LintReport {
+    /// The patterns found, sorted by span.
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Runs all the detectors on the lock operations of the functions.
+    #[must_use]
+    pub fn new(functions: &[FunctionEvents]) -> Self {
+        let mut findings = Vec::new();
+        let mut acquisition_orders = Vec::new();
+        for function in functions {
+            findings.extend(double_locks_and_guards_across_await(
+                function,
+                &mut acquisition_orders,
+            ));
+        }
+        findings.extend(lock_order_inversions(&acquisition_orders));
+        findings.extend(condvar_mutex_mismatches(functions));
+        findings.sort_by(|a, b| (&a.span, a.kind).cmp(&(&b.span, b.kind)));
+        Self { findings }
+    }
+
+    /// Checks whether no pattern was found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+impl std::fmt::Display for LintReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "No structural deadlock patterns found");
+        }
+        for finding in &self.findings {
+            writeln!(f, "{finding}")?;
+        }
+        write!(
+            f,
+            "{} structural deadlock pattern(s) found",
+            self.findings.len()
+        )
+    }
+}
+
+/// A lock acquired while another one was held, in a given function.
+struct AcquisitionOrder {
+    function: String,
+    held: String,
+    acquired: String,
+    span: SpanInfo,
+}
+
+/// Replays the lock operations of the function keeping track of the guards held.
+/// Reports the locks acquired again while a guard is held and the guards held across an `.await`.
+/// Records the pairs of locks acquired while the other one was held in `acquisition_orders`.
+fn double_locks_and_guards_across_await(
+    function: &FunctionEvents,
+    acquisition_orders: &mut Vec<AcquisitionOrder>,
+) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut held: Vec<(&str, LockAccess, &SpanInfo)> = Vec::new();
+    for event in &function.events {
+        match event {
+            LockEvent::Acquire { lock, access, span } => {
+                let previous = held.iter().find(|(held_lock, held_access, _)| {
+                    held_lock == lock
+                        && (*held_access == LockAccess::Exclusive
+                            || *access == LockAccess::Exclusive)
+                });
+                if let Some((_, _, previous_span)) = previous {
+                    findings.push(LintFinding {
+                        kind: LintKind::DoubleLock,
+                        span: span.clone(),
+                        message: format!(
+                            "`{lock}` is locked in `{}` while the guard acquired at {previous_span} is still held",
+                            function.function
+                        ),
+                    });
+                }
+                for (held_lock, _, _) in &held {
+                    if held_lock != lock {
+                        acquisition_orders.push(AcquisitionOrder {
+                            function: function.function.clone(),
+                            held: (*held_lock).to_string(),
+                            acquired: lock.clone(),
+                            span: span.clone(),
+                        });
+                    }
+                }
+                held.push((lock, *access, span));
+            }
+            LockEvent::Release { lock } => {
+                if let Some(index) = held.iter().rposition(|(held_lock, _, _)| held_lock == lock) {
+                    held.remove(index);
+                }
+            }
+            LockEvent::Wait { .. } => {}
+            LockEvent::HeldAcrossAwait { type_name, span } => findings.push(LintFinding {
+                kind: LintKind::GuardAcrossAwait,
+                span: span.clone(),
+                message: format!(
+                    "a value of type `{type_name}` is held across an `.await` in `{}`. The lock stays taken while the future is suspended",
+                    function.function
+                ),
+            }),
+        }
+    }
+    findings
+}
+
+/// Reports the pairs of locks that are acquired in opposite orders, once per pair.
+fn lock_order_inversions(acquisition_orders: &[AcquisitionOrder]) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    for (index, first) in acquisition_orders.iter().enumerate() {
+        let inverse = acquisition_orders[index + 1..]
+            .iter()
+            .find(|second| second.held == first.acquired && second.acquired == first.held);
+        let Some(second) = inverse else {
+            continue;
+        };
+        let already_reported = acquisition_orders[..index]
+            .iter()
+            .any(|other| other.held == first.acquired && other.acquired == first.held);
+        if already_reported {
+            continue;
+        }
+        findings.push(LintFinding {
+            kind: LintKind::LockOrderInversion,
+            span: second.span.clone(),
+            message: format!(
+                "`{}` is locked while holding `{}` in `{}`, but `{}` is locked while holding `{}` in `{}` at {}",
+                second.acquired,
+                second.held,
+                second.function,
+                first.acquired,
+                first.held,
+                first.function,
+                first.span
+            ),
+        });
+    }
+    findings
+}
+
+/// Reports the waits on a condition variable with a different lock than the first wait on it.
+fn condvar_mutex_mismatches(functions: &[FunctionEvents]) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut first_waits: HashMap<&str, (&str, &SpanInfo)> = HashMap::new();
+    for function in functions {
+        for event in &function.events {
+            let LockEvent::Wait {
+                condvar,
+                lock,
+                span,
+            } = event
+            else {
+                continue;
+            };
+            let (first_lock, first_span) =
+                *first_waits.entry(condvar).or_insert((lock.as_str(), span));
+            if first_lock != lock {
+                findings.push(LintFinding {
+                    kind: LintKind::CondvarMutexMismatch,
+                    span: span.clone(),
+                    message: format!(
+                        "`{condvar}` is waited on with a guard of `{lock}`, but with a guard of `{first_lock}` at {first_span}"
+                    ),
+                });
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use super::*;
+
+    fn span(line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: line,
+            start_column: 5,
+            end_line: line,
+            end_column: 20,
+        }
+    }
+
+    fn acquire(lock: &str, access: LockAccess, line: usize) -> LockEvent {
+        LockEvent::Acquire {
+            lock: lock.to_string(),
+            access,
+            span: span(line),
+        }
+    }
+
+    fn release(lock: &str) -> LockEvent {
+        LockEvent::Release {
+            lock: lock.to_string(),
+        }
+    }
+
+    fn wait(condvar: &str, lock: &str, line: usize) -> LockEvent {
+        LockEvent::Wait {
+            condvar: condvar.to_string(),
+            lock: lock.to_string(),
+            span: span(line),
+        }
+    }
+
+    fn function(name: &str, events: Vec<LockEvent>) -> FunctionEvents {
+        FunctionEvents {
+            function: name.to_string(),
+            events,
+        }
+    }
+
+    fn kinds(report: &LintReport) -> Vec<LintKind> {
+        report.findings.iter().map(|finding| finding.kind).collect()
+    }
+
+    #[test]
+    fn double_lock_is_found_while_the_guard_is_held() {
+        let report = LintReport::new(&[function(
+            "main",
+            vec![
+                acquire("data", LockAccess::Exclusive, 3),
+                acquire("data", LockAccess::Exclusive, 4),
+            ],
+        )]);
+
+        assert_eq!(kinds(&report), [LintKind::DoubleLock]);
+        assert_eq!(report.findings[0].span, span(4));
+        assert!(report.findings[0].message.contains("main.rs:3:5"));
+    }
+
+    #[test]
+    fn lock_after_release_is_not_a_double_lock() {
+        let report = LintReport::new(&[function(
+            "main",
+            vec![
+                acquire("data", LockAccess::Exclusive, 3),
+                release("data"),
+                acquire("data", LockAccess::Exclusive, 5),
+            ],
+        )]);
+
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn two_read_locks_are_not_a_double_lock() {
+        let report = LintReport::new(&[function(
+            "main",
+            vec![
+                acquire("data", LockAccess::Shared, 3),
+                acquire("data", LockAccess::Shared, 4),
+            ],
+        )]);
+
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn opposite_lock_orders_are_reported_once() {
+        let report = LintReport::new(&[
+            function(
+                "first",
+                vec![
+                    acquire("a", LockAccess::Exclusive, 3),
+                    acquire("b", LockAccess::Exclusive, 4),
+                ],
+            ),
+            function(
+                "second",
+                vec![
+                    acquire("b", LockAccess::Exclusive, 8),
+                    acquire("a", LockAccess::Exclusive, 9),
+                ],
+            ),
+            function(
+                "third",
+                vec![
+                    acquire("b", LockAccess::Exclusive, 12),
+                    acquire("a", LockAccess::Exclusive, 13),
+                ],
+            ),
+        ]);
+
+        assert_eq!(kinds(&report), [LintKind::LockOrderInversion]);
+        assert_eq!(
+            report.findings[0].message,
+            "`a` is locked while holding `b` in `second`, but `b` is locked while holding `a` in `first` at main.rs:4:5"
+        );
+    }
+
+    #[test]
+    fn condvar_waited_on_with_two_mutexes_is_a_mismatch() {
+        let report = LintReport::new(&[
+            function("consumer", vec![wait("cvar", "state", 5)]),
+            function("other_consumer", vec![wait("cvar", "other_state", 9)]),
+        ]);
+
+        assert_eq!(kinds(&report), [LintKind::CondvarMutexMismatch]);
+        assert_eq!(report.findings[0].span, span(9));
+    }
+
+    #[test]
+    fn display_lists_the_findings_sorted_by_span() {
+        let report = LintReport::new(&[function(
+            "main::{closure#0}",
+            vec![
+                LockEvent::HeldAcrossAwait {
+                    type_name: "std::sync::MutexGuard<'_, i32>".to_string(),
+                    span: span(7),
+                },
+                acquire("data", LockAccess::Exclusive, 3),
+                acquire("data", LockAccess::Exclusive, 4),
+            ],
+        )]);
+
+        assert_eq!(
+            report.to_string(),
+            "\
+main.rs:4:5: double lock: `data` is locked in `main::{closure#0}` while the guard acquired at main.rs:3:5 is still held
+main.rs:7:5: guard held across await: a value of type `std::sync::MutexGuard<'_, i32>` is held across an `.await` in `main::{closure#0}`. The lock stays taken while the future is suspended
+2 structural deadlock pattern(s) found"
+        );
+        assert_eq!(
+            LintReport::default().to_string(),
+            "No structural deadlock patterns found"
+        );
+    }
+}
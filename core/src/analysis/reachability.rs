@@ -0,0 +1,287 @@
+//! Submodule that implements an explicit-state search for deadlocks.
+//!
+//! The reachable markings are explored in breadth-first order starting from the initial marking.
+//! The first deadlock found is therefore reached through a shortest path of transitions.
+//! The search stops after visiting a maximum number of markings,
+//! since the state space of a net may be very large or even infinite.
+//!
+//! The state space may also be made finite with places that bound the net but not the program,
+//! e.g. a capacity for a channel that is unbounded in the program. A marking where no transition is enabled
+//! but a transition only lacks the tokens of such a place is not a deadlock of the program,
+//! since the program would continue, so the search skips it.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+
+use super::{IndexedNet, Marking};
+use crate::data_structures::net_data::NetData;
+
+/// The outcome of the search for deadlocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum DeadlockResult {
+    /// A deadlock is reachable by firing the transitions in the path, in order.
+    Deadlock { path: Vec<String> },
+    /// The place exceeds its capacity after firing the transitions in the path, in order.
+    /// This indicates a bug in the model of the program, not in the program itself.
+    CapacityExceeded { place: String, path: Vec<String> },
+    /// No deadlock is reachable. All the reachable markings were visited.
+    DeadlockFree { states: usize },
+    /// No deadlock is reachable, but the bounds stop the program after firing the transitions in the path,
+    /// since a transition only lacks the tokens of the bounded place. All the reachable markings were visited.
+    BoundReached {
+        states: usize,
+        place: String,
+        path: Vec<String>,
+    },
+    /// The maximum number of markings was visited before completing the search.
+    StateLimitReached { states: usize },
+}
+
+/// Searches the reachable markings of the net for a deadlock.
+/// Visits at most `max_states` markings.
+///
+/// # Errors
+///
+/// If the net is not valid, e.g. an arc refers to an unknown place, then an error is returned.
+pub fn find_deadlock(net: &NetData, max_states: usize) -> Result<DeadlockResult, String> {
+    find_deadlock_within_bounds(net, max_states, &[])
+}
+
+/// Searches the reachable markings of the net for a deadlock, where the places in `bounds`
+/// bound the net but not the program. Visits at most `max_states` markings.
+/// The labels in `bounds` that are not in the net are ignored.
+///
+/// # Errors
+///
+/// If the net is not valid, e.g. an arc refers to an unknown place, then an error is returned.
+pub fn find_deadlock_within_bounds(
+    net: &NetData,
+    max_states: usize,
+    bounds: &[String],
+) -> Result<DeadlockResult, String> {
+    let net = IndexedNet::new(net)?;
+    let bounded: Vec<bool> = net
+        .place_labels
+        .iter()
+        .map(|label| bounds.contains(label))
+        .collect();
+    // The first marking where only the bounds stop the program: The bounded place and the index of the marking.
+    let mut bound_reached: Option<(usize, usize)> = None;
+
+    // For every visited marking, the index of the previous marking and the transition fired to reach it.
+    let mut predecessors: Vec<Option<(usize, usize)>> = vec![None];
+    let mut visited: HashMap<Marking, usize> = HashMap::from([(net.initial_marking.clone(), 0)]);
+    let mut queue: VecDeque<(usize, Marking)> = VecDeque::from([(0, net.initial_marking.clone())]);
+
+    while let Some((index, marking)) = queue.pop_front() {
+        if let Some(place) = net.exceeded_capacity(&marking) {
+            return Ok(DeadlockResult::CapacityExceeded {
+                place: net.place_labels[place].clone(),
+                path: reconstruct_path(&net, &predecessors, index),
+            });
+        }
+        if net.is_deadlock(&marking) {
+            match waiting_for_bound(&net, &marking, &bounded) {
+                Some(place) => {
+                    bound_reached.get_or_insert((place, index));
+                }
+                None => {
+                    return Ok(DeadlockResult::Deadlock {
+                        path: reconstruct_path(&net, &predecessors, index),
+                    });
+                }
+            }
+        }
+        for transition in net.enabled_transitions(&marking) {
+            let next_marking = net.fire(transition, &marking);
+            if visited.contains_key(&next_marking) {
+                continue;
+            }
+            if visited.len() >= max_states {
+                return Ok(DeadlockResult::StateLimitReached {
+                    states: visited.len(),
+                });
+            }
+            let next_index = predecessors.len();
+            predecessors.push(Some((index, transition)));
+            visited.insert(next_marking.clone(), next_index);
+            queue.push_back((next_index, next_marking));
+        }
+    }
+
+    Ok(match bound_reached {
+        Some((place, index)) => DeadlockResult::BoundReached {
+            states: visited.len(),
+            place: net.place_labels[place].clone(),
+            path: reconstruct_path(&net, &predecessors, index),
+        },
+        None => DeadlockResult::DeadlockFree {
+            states: visited.len(),
+        },
+    })
+}
+
+/// Returns the index of a bounded place whose tokens are the only ones missing
+/// for a transition to be enabled in the given marking, if any.
+fn waiting_for_bound(net: &IndexedNet, marking: &[usize], bounded: &[bool]) -> Option<usize> {
+    net.consumed.iter().find_map(|consumed| {
+        let mut missing = consumed
+            .iter()
+            .filter(|(place, weight)| marking[*place] < *weight)
+            .map(|(place, _)| *place)
+            .peekable();
+        let place = *missing.peek()?;
+        missing.all(|place| bounded[place]).then_some(place)
+    })
+}
+
+/// Returns the labels of the transitions fired from the initial marking to reach the marking with the given index.
+fn reconstruct_path(
+    net: &IndexedNet,
+    predecessors: &[Option<(usize, usize)>],
+    mut index: usize,
+) -> Vec<String> {
+    let mut path = Vec::new();
+    while let Some((previous, transition)) = predecessors[index] {
+        path.push(net.transition_labels[transition].clone());
+        index = previous;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod reachability_tests {
+    use super::*;
+    use crate::data_structures::net_builder::{arc, place, transition, NetBuilder};
+    use crate::data_structures::net_data::{ArcDirection, PlaceData};
+
+    /// A net where the program either ends or gets stuck in the place `STUCK`.
+    fn net_with_choice() -> NetData {
+        NetBuilder::new()
+            .place("PROGRAM_START", 1)
+            .place("PROGRAM_END", 0)
+            .place("STUCK", 0)
+            .transition("FINISH")
+            .transition("BLOCK")
+            .input("PROGRAM_START", "FINISH")
+            .output("FINISH", "PROGRAM_END")
+            .input("PROGRAM_START", "BLOCK")
+            .output("BLOCK", "STUCK")
+            .build()
+    }
+
+    #[test]
+    fn find_deadlock_returns_path_to_deadlock() {
+        let net = net_with_choice();
+
+        let result = find_deadlock(&net, 100).unwrap();
+
+        assert_eq!(
+            result,
+            DeadlockResult::Deadlock {
+                path: vec!["BLOCK".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn find_deadlock_ignores_program_end() {
+        let mut net = net_with_choice();
+        net.transitions.pop();
+        net.arcs.truncate(2);
+
+        let result = find_deadlock(&net, 100).unwrap();
+
+        assert_eq!(result, DeadlockResult::DeadlockFree { states: 2 });
+    }
+
+    #[test]
+    fn find_deadlock_stops_at_state_limit() {
+        let net = net_with_choice();
+
+        let result = find_deadlock(&net, 1).unwrap();
+
+        assert_eq!(result, DeadlockResult::StateLimitReached { states: 1 });
+    }
+
+    #[test]
+    fn find_deadlock_within_bounds_skips_a_deadlock_caused_by_a_bound() {
+        let mut net = net_with_choice();
+        net.places.push(place("FREE_SLOTS", 0));
+        net.transitions.push(transition("SEND"));
+        net.arcs
+            .push(arc("STUCK", "SEND", ArcDirection::PlaceToTransition));
+        net.arcs
+            .push(arc("FREE_SLOTS", "SEND", ArcDirection::PlaceToTransition));
+
+        let result = find_deadlock_within_bounds(&net, 100, &["FREE_SLOTS".to_string()]).unwrap();
+
+        assert_eq!(
+            result,
+            DeadlockResult::BoundReached {
+                states: 3,
+                place: "FREE_SLOTS".to_string(),
+                path: vec!["BLOCK".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn find_deadlock_within_bounds_reports_a_deadlock_not_caused_by_a_bound() {
+        let mut net = net_with_choice();
+        net.places.push(place("FREE_SLOTS", 0));
+        net.places.push(place("MUTEX_0", 0));
+        net.transitions.push(transition("SEND"));
+        for input in ["STUCK", "FREE_SLOTS", "MUTEX_0"] {
+            net.arcs
+                .push(arc(input, "SEND", ArcDirection::PlaceToTransition));
+        }
+
+        let result = find_deadlock_within_bounds(&net, 100, &["FREE_SLOTS".to_string()]).unwrap();
+
+        assert_eq!(
+            result,
+            DeadlockResult::Deadlock {
+                path: vec!["BLOCK".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn find_deadlock_with_unknown_place_returns_error() {
+        let mut net = net_with_choice();
+        net.arcs
+            .push(arc("MISSING", "BLOCK", ArcDirection::PlaceToTransition));
+
+        let result = find_deadlock(&net, 100);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "The arc refers to an unknown place `MISSING`"
+        );
+    }
+
+    #[test]
+    fn find_deadlock_reports_exceeded_capacity() {
+        let mut net = net_with_choice();
+        net.places.push(PlaceData {
+            capacity: Some(1),
+            ..place("MUTEX_0", 1)
+        });
+        net.arcs
+            .push(arc("MUTEX_0", "BLOCK", ArcDirection::TransitionToPlace));
+
+        let result = find_deadlock(&net, 100).unwrap();
+
+        assert_eq!(
+            result,
+            DeadlockResult::CapacityExceeded {
+                place: "MUTEX_0".to_string(),
+                path: vec!["BLOCK".to_string()]
+            }
+        );
+    }
+}
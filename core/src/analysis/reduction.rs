@@ -0,0 +1,751 @@
+//! Submodule that shrinks the net with the classic reduction rules of Murata before the export.
+//! See T. Murata, "Petri nets: Properties, analysis and applications", Proceedings of the IEEE, 1989.
+//!
+//! Every rule removes places or transitions that add no behavior to the net,
+//! so that the model checker explores far fewer markings.
+//! The rules preserve the reachability of the dead markings, i.e. the net has a deadlock
+//! if and only if the reduced net has one, and the tokens in the final places:
+//!
+//! - Fusion of series places: A transition whose only input is a place `p1` and whose only output
+//!   is a place `p2` is removed if it is the only transition that consumes from `p1`.
+//!   The place `p1` is merged into `p2`, i.e. the transitions that produced in `p1` produce in `p2`.
+//! - Fusion of series transitions: An empty place `p` produced by a single transition `t1`
+//!   and consumed by a single transition `t2` whose only input is `p` is removed.
+//!   The transition `t2` is merged into `t1`, i.e. `t1` produces the outputs of `t2`.
+//! - Elimination of self-loop places: A place that every transition connected to it gives back
+//!   the tokens it consumes and whose marking is enough for all of them never disables a transition.
+//!   It is only removed if the transitions keep other inputs and outputs.
+//! - Elimination of self-loop transitions: A transition that gives back the tokens it consumes
+//!   does not change the marking. It is removed if another transition consumes a subset of its inputs,
+//!   since that one is enabled whenever it is.
+//!
+//! Only the places of the basic blocks are removed or merged, see `function_of_place_label`.
+//! The places of the synchronization primitives and the end states of the program keep their labels,
+//! so the reports and the formulas that refer to them remain valid.
+//! The rules are applied until none of them applies anymore.
+//!
+//! `collapse_goto_chains` is a lighter simplification that only applies the fusion of series transitions
+//! to the transitions of goto terminators and of statements, which add no behavior but a step to every execution.
+//! A chain of such transitions is collapsed into the transition that enters it.
+//!
+//! Every rule that fires is recorded as a `ReductionStep` with the places and transitions it removed
+//! and the class of properties it preserves, see `reduce_with_steps` and `collapse_goto_chains_with_steps`.
+//! The steps justify that the verdict of the deadlock analysis on the reduced net holds for the original net.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::data_structures::net_data::{ArcData, ArcDirection, NetData, PlaceData, TransitionData};
+use crate::naming::basic_block::{
+    function_of_place_label, is_goto_transition_label, is_statement_transition_label,
+};
+
+/// The weights of the arcs of a transition, by index of the place.
+type Weights = BTreeMap<usize, usize>;
+
+/// The reduction rules described in the module documentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReductionRule {
+    FusionOfSeriesPlaces,
+    FusionOfSeriesTransitions,
+    SelfLoopPlace,
+    SelfLoopTransition,
+}
+
+impl ReductionRule {
+    /// Returns the class of properties of the net that the rule preserves.
+    #[must_use]
+    pub fn preserved_properties(self) -> PropertyClass {
+        match self {
+            Self::FusionOfSeriesPlaces | Self::FusionOfSeriesTransitions => {
+                PropertyClass::DeadMarkings
+            }
+            Self::SelfLoopPlace | Self::SelfLoopTransition => PropertyClass::ReachableMarkings,
+        }
+    }
+}
+
+impl std::fmt::Display for ReductionRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FusionOfSeriesPlaces => write!(f, "fusion of series places"),
+            Self::FusionOfSeriesTransitions => write!(f, "fusion of series transitions"),
+            Self::SelfLoopPlace => write!(f, "elimination of self-loop places"),
+            Self::SelfLoopTransition => write!(f, "elimination of self-loop transitions"),
+        }
+    }
+}
+
+/// The classes of properties of the net preserved by the reduction rules.
+/// Both include the deadlocks, which is what the analysis checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PropertyClass {
+    /// The reachability of the dead markings and of the tokens in the final places.
+    /// The intermediate markings removed by a fusion are not preserved.
+    DeadMarkings,
+    /// The reachable markings of the places that remain, which include the dead markings.
+    ReachableMarkings,
+}
+
+impl std::fmt::Display for PropertyClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DeadMarkings => write!(f, "dead markings and final places"),
+            Self::ReachableMarkings => write!(f, "reachable markings of the remaining places"),
+        }
+    }
+}
+
+/// A reduction rule that fired and the nodes it removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReductionStep {
+    /// The rule applied.
+    pub rule: ReductionRule,
+    /// The labels of the places removed.
+    pub removed_places: Vec<String>,
+    /// The labels of the transitions removed.
+    pub removed_transitions: Vec<String>,
+    /// The label of the node that takes over the removed nodes: the place or the transition they were merged into,
+    /// or the transition enabled whenever the removed self-loop transition is. `None` for the self-loop places.
+    pub kept: Option<String>,
+}
+
+impl std::fmt::Display for ReductionStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: removed", self.rule)?;
+        let removed: Vec<String> = self
+            .removed_places
+            .iter()
+            .map(|label| format!("place {label}"))
+            .chain(
+                self.removed_transitions
+                    .iter()
+                    .map(|label| format!("transition {label}")),
+            )
+            .collect();
+        write!(f, " {}", removed.join(", "))?;
+        match (&self.kept, self.rule) {
+            (Some(kept), ReductionRule::SelfLoopTransition) => {
+                write!(f, ", {kept} is enabled whenever it is")?;
+            }
+            (Some(kept), _) => write!(f, ", merged into {kept}")?,
+            (None, _) => {}
+        }
+        write!(f, " (preserves {})", self.rule.preserved_properties())
+    }
+}
+
+/// A representation of the net with indices where places and transitions can be removed.
+struct ReducibleNet {
+    /// The places of the net, or `None` if the place was removed.
+    places: Vec<Option<PlaceData>>,
+    /// The transitions of the net, or `None` if the transition was removed.
+    transitions: Vec<Option<TransitionData>>,
+    /// For each transition, the places from which it consumes tokens and how many.
+    inputs: Vec<Weights>,
+    /// For each transition, the places in which it produces tokens and how many.
+    outputs: Vec<Weights>,
+    /// For each place, the transitions that consume tokens from it.
+    consumers: Vec<BTreeSet<usize>>,
+    /// For each place, the transitions that produce tokens in it.
+    producers: Vec<BTreeSet<usize>>,
+    /// The rules that fired, in order.
+    steps: Vec<ReductionStep>,
+}
+
+impl ReducibleNet {
+    /// Creates the representation of the net.
+    ///
+    /// # Errors
+    ///
+    /// If an arc refers to a place or a transition that does not exist, then an error is returned.
+    fn new(net: &NetData) -> Result<Self, String> {
+        let place_index: HashMap<&str, usize> = net
+            .places
+            .iter()
+            .enumerate()
+            .map(|(index, place)| (place.label.as_str(), index))
+            .collect();
+        let transition_index: HashMap<&str, usize> = net
+            .transitions
+            .iter()
+            .enumerate()
+            .map(|(index, transition)| (transition.label.as_str(), index))
+            .collect();
+
+        let mut inputs = vec![Weights::new(); net.transitions.len()];
+        let mut outputs = vec![Weights::new(); net.transitions.len()];
+        let mut consumers = vec![BTreeSet::new(); net.places.len()];
+        let mut producers = vec![BTreeSet::new(); net.places.len()];
+        for arc in &net.arcs {
+            let place = *place_index
+                .get(arc.place.as_str())
+                .ok_or_else(|| format!("The arc refers to an unknown place `{}`", arc.place))?;
+            let transition = *transition_index
+                .get(arc.transition.as_str())
+                .ok_or_else(|| {
+                    format!(
+                        "The arc refers to an unknown transition `{}`",
+                        arc.transition
+                    )
+                })?;
+            // Repeated arcs add up their weights.
+            match arc.direction {
+                ArcDirection::PlaceToTransition => {
+                    *inputs[transition].entry(place).or_default() += arc.weight;
+                    consumers[place].insert(transition);
+                }
+                ArcDirection::TransitionToPlace => {
+                    *outputs[transition].entry(place).or_default() += arc.weight;
+                    producers[place].insert(transition);
+                }
+            }
+        }
+
+        Ok(Self {
+            places: net.places.iter().cloned().map(Some).collect(),
+            transitions: net.transitions.iter().cloned().map(Some).collect(),
+            inputs,
+            outputs,
+            consumers,
+            producers,
+            steps: Vec::new(),
+        })
+    }
+
+    /// Returns the label of the place. It must not have been removed.
+    fn place_label(&self, place: usize) -> String {
+        self.places[place]
+            .as_ref()
+            .expect("BUG: The place should not have been removed")
+            .label
+            .clone()
+    }
+
+    /// Returns the label of the transition. It must not have been removed.
+    fn transition_label(&self, transition: usize) -> String {
+        self.transitions[transition]
+            .as_ref()
+            .expect("BUG: The transition should not have been removed")
+            .label
+            .clone()
+    }
+
+    /// Checks whether the place may be removed or merged into another place,
+    /// i.e. it is the place of a basic block without a capacity.
+    fn is_reducible(&self, place: usize) -> bool {
+        self.places[place].as_ref().is_some_and(|data| {
+            data.capacity.is_none() && function_of_place_label(&data.label).is_some()
+        })
+    }
+
+    /// Checks whether the transition is the transition of a goto terminator or of a statement.
+    fn is_goto(&self, transition: usize) -> bool {
+        self.transitions[transition].as_ref().is_some_and(|data| {
+            is_goto_transition_label(&data.label) || is_statement_transition_label(&data.label)
+        })
+    }
+
+    /// Returns the only place in the weights if it has weight one.
+    fn single_place(weights: &Weights) -> Option<usize> {
+        match weights.iter().collect::<Vec<_>>()[..] {
+            [(place, 1)] => Some(*place),
+            _ => None,
+        }
+    }
+
+    /// Removes the transition and its arcs.
+    fn remove_transition(&mut self, transition: usize) {
+        for place in std::mem::take(&mut self.inputs[transition]).into_keys() {
+            self.consumers[place].remove(&transition);
+        }
+        for place in std::mem::take(&mut self.outputs[transition]).into_keys() {
+            self.producers[place].remove(&transition);
+        }
+        self.transitions[transition] = None;
+    }
+
+    /// Applies the fusion of series places to the transition if possible.
+    /// Returns whether the net changed.
+    fn fuse_series_places(&mut self, transition: usize) -> bool {
+        let (Some(first), Some(second)) = (
+            Self::single_place(&self.inputs[transition]),
+            Self::single_place(&self.outputs[transition]),
+        ) else {
+            return false;
+        };
+        let second_is_unbounded = self.places[second]
+            .as_ref()
+            .is_some_and(|data| data.capacity.is_none());
+        if first == second
+            || !second_is_unbounded
+            || !self.is_reducible(first)
+            || self.consumers[first].len() != 1
+        {
+            return false;
+        }
+
+        self.steps.push(ReductionStep {
+            rule: ReductionRule::FusionOfSeriesPlaces,
+            removed_places: vec![self.place_label(first)],
+            removed_transitions: vec![self.transition_label(transition)],
+            kept: Some(self.place_label(second)),
+        });
+        self.remove_transition(transition);
+        for producer in std::mem::take(&mut self.producers[first]) {
+            let weight = self.outputs[producer]
+                .remove(&first)
+                .expect("BUG: The producer of a place should have an arc to it");
+            *self.outputs[producer].entry(second).or_default() += weight;
+            self.producers[second].insert(producer);
+        }
+        let first_data = self.places[first]
+            .take()
+            .expect("BUG: A reducible place should not have been removed");
+        let second_data = self.places[second]
+            .as_mut()
+            .expect("BUG: The output place of a transition should not have been removed");
+        second_data.marking = second_data.marking.saturating_add(first_data.marking);
+        true
+    }
+
+    /// Applies the fusion of series transitions to the place if possible.
+    /// Returns whether the net changed.
+    fn fuse_series_transitions(&mut self, place: usize) -> bool {
+        if !self.is_reducible(place)
+            || self.places[place]
+                .as_ref()
+                .is_some_and(|data| data.marking > 0)
+            || self.producers[place].len() != 1
+            || self.consumers[place].len() != 1
+        {
+            return false;
+        }
+        let first = *self.producers[place]
+            .first()
+            .expect("BUG: The place should have one producer");
+        let second = *self.consumers[place]
+            .first()
+            .expect("BUG: The place should have one consumer");
+        if first == second
+            || self.outputs[first].get(&place) != Some(&1)
+            || Self::single_place(&self.inputs[second]) != Some(place)
+            || self.outputs[second].is_empty()
+        {
+            return false;
+        }
+
+        self.steps.push(ReductionStep {
+            rule: ReductionRule::FusionOfSeriesTransitions,
+            removed_places: vec![self.place_label(place)],
+            removed_transitions: vec![self.transition_label(second)],
+            kept: Some(self.transition_label(first)),
+        });
+        let second_outputs = self.outputs[second].clone();
+        let second_data = self.transitions[second].clone();
+        self.remove_transition(second);
+        self.outputs[first].remove(&place);
+        self.producers[place].remove(&first);
+        for (output, weight) in second_outputs {
+            *self.outputs[first].entry(output).or_default() += weight;
+            self.producers[output].insert(first);
+        }
+        let first_data = self.transitions[first]
+            .as_mut()
+            .expect("BUG: The producer of a place should not have been removed");
+        if first_data.span.is_none() {
+            first_data.span = second_data.and_then(|data| data.span);
+        }
+        self.places[place] = None;
+        true
+    }
+
+    /// Applies the elimination of self-loop places to the place if possible.
+    /// Returns whether the net changed.
+    fn remove_self_loop_place(&mut self, place: usize) -> bool {
+        if !self.is_reducible(place) {
+            return false;
+        }
+        let marking = self.places[place].as_ref().map_or(0, |data| data.marking);
+        let is_self_loop = self.consumers[place]
+            .union(&self.producers[place])
+            .all(|transition| {
+                let consumed = self.inputs[*transition].get(&place);
+                consumed == self.outputs[*transition].get(&place)
+                    && consumed.is_some_and(|weight| *weight <= marking)
+                    && self.inputs[*transition].len() > 1
+                    && self.outputs[*transition].len() > 1
+            });
+        if !is_self_loop {
+            return false;
+        }
+
+        self.steps.push(ReductionStep {
+            rule: ReductionRule::SelfLoopPlace,
+            removed_places: vec![self.place_label(place)],
+            removed_transitions: Vec::new(),
+            kept: None,
+        });
+        for transition in std::mem::take(&mut self.consumers[place]) {
+            self.inputs[transition].remove(&place);
+            self.outputs[transition].remove(&place);
+        }
+        self.producers[place].clear();
+        self.places[place] = None;
+        true
+    }
+
+    /// Applies the elimination of self-loop transitions to the transition if possible.
+    /// Returns whether the net changed.
+    fn remove_self_loop_transition(&mut self, transition: usize) -> bool {
+        let inputs = &self.inputs[transition];
+        if inputs.is_empty() || *inputs != self.outputs[transition] {
+            return false;
+        }
+        let weaker_transition = inputs
+            .keys()
+            .flat_map(|place| &self.consumers[*place])
+            .filter(|other| **other != transition)
+            .find(|other| {
+                self.inputs[**other].iter().all(|(place, weight)| {
+                    inputs.get(place).is_some_and(|consumed| weight <= consumed)
+                })
+            });
+        let Some(weaker_transition) = weaker_transition.copied() else {
+            return false;
+        };
+
+        self.steps.push(ReductionStep {
+            rule: ReductionRule::SelfLoopTransition,
+            removed_places: Vec::new(),
+            removed_transitions: vec![self.transition_label(transition)],
+            kept: Some(self.transition_label(weaker_transition)),
+        });
+        self.remove_transition(transition);
+        true
+    }
+
+    /// Returns the plain description of the net without the removed places and transitions.
+    /// The arcs are listed per transition, first the inputs and then the outputs.
+    fn data(self) -> NetData {
+        let mut arcs = Vec::new();
+        for (transition, data) in self.transitions.iter().enumerate() {
+            let Some(data) = data else {
+                continue;
+            };
+            let weights = [
+                (&self.inputs[transition], ArcDirection::PlaceToTransition),
+                (&self.outputs[transition], ArcDirection::TransitionToPlace),
+            ];
+            for (weights, direction) in weights {
+                for (place, weight) in weights {
+                    let place = &self.places[*place]
+                        .as_ref()
+                        .expect("BUG: An arc should not refer to a removed place")
+                        .label;
+                    arcs.push(ArcData {
+                        place: place.clone(),
+                        transition: data.label.clone(),
+                        direction,
+                        weight: *weight,
+                    });
+                }
+            }
+        }
+        NetData {
+            places: self.places.into_iter().flatten().collect(),
+            transitions: self.transitions.into_iter().flatten().collect(),
+            arcs,
+        }
+    }
+}
+
+/// Returns the net reduced with the rules described in the module documentation.
+///
+/// # Errors
+///
+/// If an arc refers to a place or a transition that does not exist, then an error is returned.
+pub fn reduce(net: &NetData) -> Result<NetData, String> {
+    reduce_with_steps(net).map(|(reduced, _)| reduced)
+}
+
+/// Returns the net reduced like `reduce` and the rules that fired, in order.
+///
+/// # Errors
+///
+/// If an arc refers to a place or a transition that does not exist, then an error is returned.
+pub fn reduce_with_steps(net: &NetData) -> Result<(NetData, Vec<ReductionStep>), String> {
+    let mut reducible = ReducibleNet::new(net)?;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for transition in 0..reducible.transitions.len() {
+            changed |= reducible.fuse_series_places(transition);
+        }
+        for place in 0..reducible.places.len() {
+            changed |= reducible.fuse_series_transitions(place);
+            changed |= reducible.remove_self_loop_place(place);
+        }
+        for transition in 0..reducible.transitions.len() {
+            changed |= reducible.remove_self_loop_transition(transition);
+        }
+    }
+    let steps = std::mem::take(&mut reducible.steps);
+    Ok((reducible.data(), steps))
+}
+
+/// Returns the net where every transition of a goto terminator or a statement that follows
+/// another transition in a linear chain is merged into it, see the module documentation.
+///
+/// # Errors
+///
+/// If an arc refers to a place or a transition that does not exist, then an error is returned.
+pub fn collapse_goto_chains(net: &NetData) -> Result<NetData, String> {
+    collapse_goto_chains_with_steps(net).map(|(collapsed, _)| collapsed)
+}
+
+/// Returns the net with the chains collapsed like `collapse_goto_chains` and the fusions applied, in order.
+///
+/// # Errors
+///
+/// If an arc refers to a place or a transition that does not exist, then an error is returned.
+pub fn collapse_goto_chains_with_steps(
+    net: &NetData,
+) -> Result<(NetData, Vec<ReductionStep>), String> {
+    let mut reducible = ReducibleNet::new(net)?;
+    // A fusion does not change the number of producers and consumers of the other places,
+    // so a single pass collapses the chains completely.
+    for place in 0..reducible.places.len() {
+        let consumer = reducible.consumers[place].first().copied();
+        if consumer.is_some_and(|transition| reducible.is_goto(transition)) {
+            reducible.fuse_series_transitions(place);
+        }
+    }
+    let steps = std::mem::take(&mut reducible.steps);
+    Ok((reducible.data(), steps))
+}
+
+#[cfg(test)]
+mod reduction_tests {
+    use super::*;
+    use crate::data_structures::net_builder::{arc, place, transition, NetBuilder};
+
+    fn labels<T>(nodes: &[T], label: impl Fn(&T) -> &str) -> Vec<&str> {
+        nodes.iter().map(label).collect()
+    }
+
+    #[test]
+    fn reduce_fuses_a_chain_of_basic_blocks() {
+        let net = NetBuilder::new()
+            .place("PROGRAM_START", 1)
+            .place("PROGRAM_END", 0)
+            .place("main_BB1", 0)
+            .place("main_BB2", 0)
+            .transition("main_START")
+            .transition("main_GOTO_1")
+            .transition("main_RETURN")
+            .input("PROGRAM_START", "main_START")
+            .output("main_START", "main_BB1")
+            .input("main_BB1", "main_GOTO_1")
+            .output("main_GOTO_1", "main_BB2")
+            .input("main_BB2", "main_RETURN")
+            .output("main_RETURN", "PROGRAM_END")
+            .build();
+
+        let reduced = reduce(&net).unwrap();
+
+        assert_eq!(
+            labels(&reduced.places, |place| &place.label),
+            ["PROGRAM_START", "PROGRAM_END"]
+        );
+        assert_eq!(
+            labels(&reduced.transitions, |transition| &transition.label),
+            ["main_START"]
+        );
+        assert_eq!(
+            reduced.arcs,
+            [
+                arc(
+                    "PROGRAM_START",
+                    "main_START",
+                    ArcDirection::PlaceToTransition
+                ),
+                arc("PROGRAM_END", "main_START", ArcDirection::TransitionToPlace),
+            ]
+        );
+    }
+
+    #[test]
+    fn reduce_keeps_the_places_of_synchronization_primitives() {
+        // Locking the mutex twice: The second lock is a deadlock.
+        let net = NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("MUTEX_0", 1)
+            .place("main_BB1", 0)
+            .place("main_BB2", 0)
+            .transition("main_LOCK_0")
+            .transition("main_LOCK_1")
+            .input("main_BB0", "main_LOCK_0")
+            .input("MUTEX_0", "main_LOCK_0")
+            .output("main_LOCK_0", "main_BB1")
+            .input("MUTEX_0", "main_LOCK_1")
+            .input("main_BB1", "main_LOCK_1")
+            .output("main_LOCK_1", "main_BB2")
+            .build();
+
+        let reduced = reduce(&net).unwrap();
+
+        assert_eq!(reduced, net);
+    }
+
+    #[test]
+    fn reduce_fuses_series_transitions() {
+        let net = NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("MUTEX_0", 1)
+            .place("main_BB1", 0)
+            .place("main_BB2", 0)
+            .place("main_BB3", 0)
+            .transition("main_LOCK_0")
+            .transition("main_SWITCH_1")
+            .input("main_BB0", "main_LOCK_0")
+            .input("MUTEX_0", "main_LOCK_0")
+            .output("main_LOCK_0", "main_BB1")
+            .input("main_BB1", "main_SWITCH_1")
+            .output("main_SWITCH_1", "main_BB2")
+            .output("main_SWITCH_1", "main_BB3")
+            .build();
+
+        let reduced = reduce(&net).unwrap();
+
+        assert_eq!(
+            labels(&reduced.transitions, |transition| &transition.label),
+            ["main_LOCK_0"]
+        );
+        assert_eq!(
+            labels(&reduced.places, |place| &place.label),
+            ["main_BB0", "MUTEX_0", "main_BB2", "main_BB3"]
+        );
+    }
+
+    #[test]
+    fn reduce_removes_self_loop_transitions_only_if_another_one_is_enabled() {
+        let net = NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("PROGRAM_END", 0)
+            .transition("main_SPIN")
+            .transition("main_SPIN_EXIT")
+            .input("main_BB0", "main_SPIN")
+            .output("main_SPIN", "main_BB0")
+            .input("main_BB0", "main_SPIN_EXIT")
+            .output("main_SPIN_EXIT", "PROGRAM_END")
+            .build();
+
+        // Once the loop is removed, the exit is fused with the end of the program.
+        let reduced = reduce(&net).unwrap();
+        assert!(reduced.transitions.is_empty());
+        assert_eq!(reduced.places, [place("PROGRAM_END", 1)]);
+
+        // Without the exit, the loop never ends: Removing it would introduce a deadlock.
+        let endless = NetData {
+            transitions: vec![transition("main_SPIN")],
+            arcs: net.arcs[..2].to_vec(),
+            ..net
+        };
+        assert_eq!(reduce(&endless).unwrap(), endless);
+    }
+
+    #[test]
+    fn reduce_with_steps_records_the_rules_that_fired() {
+        let net = NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("PROGRAM_END", 0)
+            .transition("main_SPIN")
+            .transition("main_SPIN_EXIT")
+            .input("main_BB0", "main_SPIN")
+            .output("main_SPIN", "main_BB0")
+            .input("main_BB0", "main_SPIN_EXIT")
+            .output("main_SPIN_EXIT", "PROGRAM_END")
+            .build();
+
+        let (reduced, steps) = reduce_with_steps(&net).unwrap();
+
+        assert_eq!(reduced, reduce(&net).unwrap());
+        assert_eq!(
+            steps.iter().map(|step| step.rule).collect::<Vec<_>>(),
+            [
+                ReductionRule::SelfLoopTransition,
+                ReductionRule::FusionOfSeriesPlaces
+            ]
+        );
+        assert_eq!(
+            steps[0].to_string(),
+            "elimination of self-loop transitions: removed transition main_SPIN, \
+            main_SPIN_EXIT is enabled whenever it is (preserves reachable markings of the remaining places)"
+        );
+        assert_eq!(
+            steps[1].to_string(),
+            "fusion of series places: removed place main_BB0, transition main_SPIN_EXIT, \
+            merged into PROGRAM_END (preserves dead markings and final places)"
+        );
+    }
+
+    #[test]
+    fn collapse_goto_chains_keeps_the_first_transition() {
+        let net = NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("main_BB1", 0)
+            .place("main_BB2", 0)
+            .place("main_BB3", 0)
+            .transition("main_GOTO_0")
+            .transition("main_GOTO_1")
+            .transition("main_GOTO_2")
+            .input("main_BB0", "main_GOTO_0")
+            .output("main_GOTO_0", "main_BB1")
+            .input("main_BB1", "main_GOTO_1")
+            .output("main_GOTO_1", "main_BB2")
+            .input("main_BB2", "main_GOTO_2")
+            .output("main_GOTO_2", "main_BB3")
+            .build();
+
+        let collapsed = collapse_goto_chains(&net).unwrap();
+
+        assert_eq!(
+            collapsed.arcs,
+            [
+                arc("main_BB0", "main_GOTO_0", ArcDirection::PlaceToTransition),
+                arc("main_BB3", "main_GOTO_0", ArcDirection::TransitionToPlace),
+            ]
+        );
+        assert_eq!(
+            labels(&collapsed.places, |place| &place.label),
+            ["main_BB0", "main_BB3"]
+        );
+    }
+
+    #[test]
+    fn collapse_goto_chains_keeps_the_other_transitions() {
+        let net = NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("main_BB1", 0)
+            .place("PROGRAM_END", 0)
+            .transition("main_GOTO_0")
+            .transition("main_RETURN")
+            .input("main_BB0", "main_GOTO_0")
+            .output("main_GOTO_0", "main_BB1")
+            .input("main_BB1", "main_RETURN")
+            .output("main_RETURN", "PROGRAM_END")
+            .build();
+
+        assert_eq!(collapse_goto_chains(&net).unwrap(), net);
+    }
+
+    #[test]
+    fn reduce_fails_for_an_unknown_place() {
+        let net = NetBuilder::new()
+            .transition("main_RETURN")
+            .input("main_BB0", "main_RETURN")
+            .build();
+
+        assert!(reduce(&net).is_err());
+    }
+}
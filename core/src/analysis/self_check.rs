@@ -0,0 +1,239 @@
+//! Submodule that implements a check of the structural invariants of the net built by the translator.
+//!
+//! The invariants hold for every net produced by a correct translation, regardless of the program:
+//! - Every transition has at least one input place and one output place.
+//!   The only exception are diverging calls, which have no output place since the function does not return.
+//! - No place holds more tokens than its capacity in the initial marking
+//!   and no transition produces more tokens than the capacity of a place in a single firing.
+//! - The start places are not dangling: `PROGRAM_START` holds the initial token,
+//!   the start place of every thread is marked by a transition,
+//!   and every start place is consumed by a transition.
+//! - No transition consumes a token from the end places of the program.
+//!
+//! A violation is a bug in the translator, not in the program.
+//! Every violation is reported with the offending element and the span of the source code that produced it, if any.
+
+use std::collections::HashMap;
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::span_info::SpanInfo;
+use crate::naming::function::is_diverging_call_transition_label;
+use crate::naming::thread::is_start_place_label;
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC, PROGRAM_START};
+
+/// The arcs connected to a place or a transition, grouped by direction.
+#[derive(Default)]
+struct Connections<'a> {
+    /// The labels of the elements connected by an incoming arc, with repetitions.
+    inputs: Vec<&'a str>,
+    /// The labels of the elements connected by an outgoing arc, with repetitions.
+    outputs: Vec<&'a str>,
+}
+
+/// Checks the structural invariants of the net.
+///
+/// # Errors
+///
+/// If an invariant is violated, then an error listing every violation, one per line, is returned.
+pub fn check_structure(net: &NetData) -> Result<(), String> {
+    let mut places: HashMap<&str, Connections> = HashMap::new();
+    let mut transitions: HashMap<&str, Connections> = HashMap::new();
+    for arc in &net.arcs {
+        let place = places.entry(arc.place.as_str()).or_default();
+        let transition = transitions.entry(arc.transition.as_str()).or_default();
+        match arc.direction {
+            ArcDirection::PlaceToTransition => {
+                place.outputs.push(&arc.transition);
+                transition.inputs.push(&arc.place);
+            }
+            ArcDirection::TransitionToPlace => {
+                place.inputs.push(&arc.transition);
+                transition.outputs.push(&arc.place);
+            }
+        }
+    }
+    let spans: HashMap<&str, &SpanInfo> = net
+        .transitions
+        .iter()
+        .filter_map(|transition| Some((transition.label.as_str(), transition.span.as_ref()?)))
+        .collect();
+    let describe = |label: &str| {
+        spans.get(label).map_or_else(
+            || format!("`{label}`"),
+            |span| format!("`{label}` ({span})"),
+        )
+    };
+
+    let empty = Connections::default();
+    let mut violations = Vec::new();
+    for transition in &net.transitions {
+        let label = transition.label.as_str();
+        let connections = transitions.get(label).unwrap_or(&empty);
+        if connections.inputs.is_empty() {
+            violations.push(format!(
+                "The transition {} has no input place",
+                describe(label)
+            ));
+        }
+        if connections.outputs.is_empty() && !is_diverging_call_transition_label(label) {
+            violations.push(format!(
+                "The transition {} has no output place",
+                describe(label)
+            ));
+        }
+    }
+
+    for place in &net.places {
+        let label = place.label.as_str();
+        let connections = places.get(label).unwrap_or(&empty);
+        if let Some(capacity) = place.capacity {
+            if place.marking > capacity {
+                violations.push(format!(
+                    "The place `{label}` holds {} tokens initially but its capacity is {capacity}",
+                    place.marking
+                ));
+            }
+            let mut produced: HashMap<&str, usize> = HashMap::new();
+            for transition in &connections.inputs {
+                *produced.entry(transition).or_default() += 1;
+            }
+            for (transition, tokens) in produced {
+                if tokens > capacity {
+                    violations.push(format!(
+                        "The transition {} produces {tokens} tokens in the place `{label}` but its capacity is {capacity}",
+                        describe(transition)
+                    ));
+                }
+            }
+        }
+
+        if label == PROGRAM_START && place.marking == 0 {
+            violations.push(format!("The place `{label}` has no initial token"));
+        }
+        if is_start_place_label(label) && connections.inputs.is_empty() {
+            violations.push(format!(
+                "The place `{label}` is not marked by any transition"
+            ));
+        }
+        if (label == PROGRAM_START || is_start_place_label(label)) && connections.outputs.is_empty()
+        {
+            violations.push(format!(
+                "The place `{label}` is not consumed by any transition"
+            ));
+        }
+        if label == PROGRAM_END || label == PROGRAM_PANIC {
+            for transition in &connections.outputs {
+                violations.push(format!(
+                    "The transition {} consumes a token from the end place `{label}`",
+                    describe(transition)
+                ));
+            }
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod self_check_tests {
+    use super::*;
+    use crate::data_structures::net_builder::{arc, place, transition, NetBuilder};
+
+    fn span() -> SpanInfo {
+        SpanInfo {
+            filename: "src/main.rs".to_string(),
+            start_line: 2,
+            start_column: 5,
+            end_line: 2,
+            end_column: 10,
+        }
+    }
+
+    /// A net where `main` locks and unlocks a mutex.
+    fn net_with_mutex() -> NetData {
+        NetBuilder::new()
+            .place("PROGRAM_START", 1)
+            .place("PROGRAM_END", 0)
+            .bounded_place("MUTEX_0", 1, 1)
+            .place("main_BB1", 0)
+            .transition_with_span("LOCK", span())
+            .transition_with_span("UNLOCK", span())
+            .input("PROGRAM_START", "LOCK")
+            .input("MUTEX_0", "LOCK")
+            .output("LOCK", "main_BB1")
+            .input("main_BB1", "UNLOCK")
+            .output("UNLOCK", "MUTEX_0")
+            .output("UNLOCK", "PROGRAM_END")
+            .build()
+    }
+
+    #[test]
+    fn check_structure_accepts_valid_net() {
+        assert_eq!(check_structure(&net_with_mutex()), Ok(()));
+    }
+
+    #[test]
+    fn check_structure_accepts_diverging_call_without_output() {
+        let mut net = net_with_mutex();
+        net.transitions
+            .push(transition("std_process_abort_DIVERGING_CALL"));
+        net.arcs.push(arc(
+            "main_BB1",
+            "std_process_abort_DIVERGING_CALL",
+            ArcDirection::PlaceToTransition,
+        ));
+
+        assert_eq!(check_structure(&net), Ok(()));
+    }
+
+    #[test]
+    fn check_structure_reports_transition_without_output_and_its_span() {
+        let mut net = net_with_mutex();
+        net.arcs.pop();
+        net.arcs.pop();
+
+        assert_eq!(
+            check_structure(&net),
+            Err("The transition `UNLOCK` (src/main.rs:2:5) has no output place".to_string())
+        );
+    }
+
+    #[test]
+    fn check_structure_reports_exceeded_capacity() {
+        let mut net = net_with_mutex();
+        net.arcs
+            .push(arc("MUTEX_0", "UNLOCK", ArcDirection::TransitionToPlace));
+
+        assert_eq!(
+            check_structure(&net),
+            Err("The transition `UNLOCK` (src/main.rs:2:5) produces 2 tokens in the place `MUTEX_0` but its capacity is 1".to_string())
+        );
+    }
+
+    #[test]
+    fn check_structure_reports_dangling_thread_start_place() {
+        let mut net = net_with_mutex();
+        net.places.push(place("THREAD_0_START", 0));
+
+        assert_eq!(
+            check_structure(&net),
+            Err("The place `THREAD_0_START` is not marked by any transition\nThe place `THREAD_0_START` is not consumed by any transition".to_string())
+        );
+    }
+
+    #[test]
+    fn check_structure_reports_consumed_end_place() {
+        let mut net = net_with_mutex();
+        net.arcs
+            .push(arc("PROGRAM_END", "LOCK", ArcDirection::PlaceToTransition));
+
+        assert_eq!(
+            check_structure(&net),
+            Err("The transition `LOCK` (src/main.rs:2:5) consumes a token from the end place `PROGRAM_END`".to_string())
+        );
+    }
+}
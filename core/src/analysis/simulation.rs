@@ -0,0 +1,189 @@
+//! Submodule that implements the token game on the net.
+//!
+//! The simulation starts at the initial marking and fires one enabled transition at a time,
+//! chosen by the user. It keeps the history of fired transitions to replay a counterexample step by step.
+
+use super::{IndexedNet, Marking};
+use crate::data_structures::net_data::NetData;
+
+pub struct Simulation {
+    /// The net being simulated.
+    net: IndexedNet,
+    /// The current marking.
+    marking: Marking,
+    /// The labels of the transitions fired so far, in order.
+    history: Vec<String>,
+}
+
+impl Simulation {
+    /// Creates a new simulation of the net starting at the initial marking.
+    ///
+    /// # Errors
+    ///
+    /// If the net is not valid, e.g. an arc refers to an unknown place, then an error is returned.
+    pub fn new(net: &NetData) -> Result<Self, String> {
+        let net = IndexedNet::new(net)?;
+        let marking = net.initial_marking.clone();
+        Ok(Self {
+            net,
+            marking,
+            history: Vec::new(),
+        })
+    }
+
+    /// Returns the current marking, indexed like the places of the net.
+    #[must_use]
+    pub fn marking(&self) -> &[usize] {
+        &self.marking
+    }
+
+    /// Returns the labels of the transitions fired so far, in order.
+    #[must_use]
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Returns the labels of the transitions that can fire in the current marking.
+    #[must_use]
+    pub fn enabled_transitions(&self) -> Vec<&str> {
+        self.net
+            .enabled_transitions(&self.marking)
+            .into_iter()
+            .map(|transition| self.net.transition_labels[transition].as_str())
+            .collect()
+    }
+
+    /// Fires the transition with the given label.
+    ///
+    /// # Errors
+    ///
+    /// If the transition does not exist or is not enabled in the current marking, then an error is returned.
+    /// If firing the transition makes a place exceed its capacity, then an error is returned
+    /// and the transition is not fired.
+    pub fn fire(&mut self, label: &str) -> Result<(), String> {
+        let transition = self
+            .net
+            .transition_labels
+            .iter()
+            .position(|transition_label| transition_label == label)
+            .ok_or_else(|| format!("The transition `{label}` does not exist"))?;
+        if !self.net.is_enabled(transition, &self.marking) {
+            return Err(format!("The transition `{label}` is not enabled"));
+        }
+        let next_marking = self.net.fire(transition, &self.marking);
+        if let Some(place) = self.net.exceeded_capacity(&next_marking) {
+            return Err(format!(
+                "Firing the transition `{label}` exceeds the capacity of the place `{}`",
+                self.net.place_labels[place]
+            ));
+        }
+        self.marking = next_marking;
+        self.history.push(label.to_string());
+        Ok(())
+    }
+
+    /// Checks whether the current marking is a deadlock.
+    #[must_use]
+    pub fn is_deadlock(&self) -> bool {
+        self.net.is_deadlock(&self.marking)
+    }
+
+    /// Goes back to the initial marking and clears the history.
+    pub fn reset(&mut self) {
+        self.marking = self.net.initial_marking.clone();
+        self.history.clear();
+    }
+}
+
+#[cfg(test)]
+mod simulation_tests {
+    use super::*;
+    use crate::data_structures::net_data::{ArcData, ArcDirection, PlaceData, TransitionData};
+
+    /// A net with a single transition from `PROGRAM_START` to `PROGRAM_END`.
+    fn net_with_one_transition() -> NetData {
+        NetData {
+            places: vec![
+                PlaceData {
+                    label: "PROGRAM_START".to_string(),
+                    marking: 1,
+                    capacity: None,
+                    span: None,
+                },
+                PlaceData {
+                    label: "PROGRAM_END".to_string(),
+                    marking: 0,
+                    capacity: None,
+                    span: None,
+                },
+            ],
+            transitions: vec![TransitionData {
+                label: "RUN".to_string(),
+                span: None,
+            }],
+            arcs: vec![
+                ArcData {
+                    place: "PROGRAM_START".to_string(),
+                    transition: "RUN".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+                ArcData {
+                    place: "PROGRAM_END".to_string(),
+                    transition: "RUN".to_string(),
+                    direction: ArcDirection::TransitionToPlace,
+                    weight: 1,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn simulation_fire_moves_token() {
+        let mut simulation = Simulation::new(&net_with_one_transition()).unwrap();
+        assert_eq!(simulation.enabled_transitions(), vec!["RUN"]);
+
+        simulation.fire("RUN").unwrap();
+
+        assert_eq!(simulation.marking(), &[0, 1]);
+        assert_eq!(simulation.history(), &["RUN".to_string()]);
+        assert!(simulation.enabled_transitions().is_empty());
+        assert!(!simulation.is_deadlock());
+    }
+
+    #[test]
+    fn simulation_fire_disabled_transition_returns_error() {
+        let mut simulation = Simulation::new(&net_with_one_transition()).unwrap();
+        simulation.fire("RUN").unwrap();
+
+        let result = simulation.fire("RUN");
+
+        assert_eq!(result.unwrap_err(), "The transition `RUN` is not enabled");
+    }
+
+    #[test]
+    fn simulation_reset_restores_initial_marking() {
+        let mut simulation = Simulation::new(&net_with_one_transition()).unwrap();
+        simulation.fire("RUN").unwrap();
+
+        simulation.reset();
+
+        assert_eq!(simulation.marking(), &[1, 0]);
+        assert!(simulation.history().is_empty());
+    }
+
+    #[test]
+    fn simulation_fire_exceeding_capacity_returns_error() {
+        let mut net = net_with_one_transition();
+        net.places[1].capacity = Some(0);
+        let mut simulation = Simulation::new(&net).unwrap();
+
+        let result = simulation.fire("RUN");
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Firing the transition `RUN` exceeds the capacity of the place `PROGRAM_END`"
+        );
+        assert_eq!(simulation.marking(), &[1, 0]);
+    }
+}
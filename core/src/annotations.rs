@@ -0,0 +1,437 @@
+//! Module that parses the annotations written by the user in comments of the source code
+//! and applies them to the Petri net.
+//!
+//! An annotation is a line comment on a line of its own that starts with `granite:`, e.g.:
+//!
+//! ```text
+//! // granite: property DATA_RECEIVED: eventually reachable
+//! let data = rx.recv().unwrap();
+//! ```
+//!
+//! The following annotations are supported:
+//!
+//! - `property [NAME:] eventually reachable`: Every execution reaches the code after the annotation.
+//! - `property [NAME:] possibly reachable`: Some execution reaches the code after the annotation.
+//! - `property [NAME:] never reachable`: No execution reaches the code after the annotation.
+//! - `allow(deadlock)`: Deadlocks where a thread is blocked in the code after the annotation
+//!   are reported as suppressed.
+//!
+//! The name is optional and defaults to the line of the annotation, e.g. `LINE_12`.
+//! The code after the annotation are the transitions produced by the first line that follows it
+//! and produced any transition.
+//!
+//! Every property adds two complementary marker places to the net, `PROPERTY_<NAME>_NOT_REACHED`
+//! and `PROPERTY_<NAME>_REACHED`. The first time one of the marked transitions fires,
+//! it moves the token from the former to the latter. Every marked transition is duplicated
+//! so that the copy fires instead of it once the code was reached, which keeps the behavior
+//! of the net unchanged and the marker places safe.
+//! The formulas for the properties are generated with the rest of the property templates,
+//! see `crate::report::property_templates`.
+//!
+//! A suppression applies to the item, block or statement that starts on the line after it,
+//! i.e. up to the matching closing brace or to the end of the statement.
+//! The braces are counted without parsing the code, so braces in string literals may confuse it.
+//! The suppressions are matched against the findings in `crate::report::findings`.
+
+use log::warn;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, PetriNet,
+};
+use crate::data_structures::span_info::SpanInfo;
+use crate::naming::property::{default_name, marker_place_labels, repeated_transition_label};
+use crate::report::findings::FindingKind;
+
+/// The prefix of every annotation after the comment delimiter.
+const ANNOTATION_PREFIX: &str = "granite:";
+
+/// How often the code after an annotation must be reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reachability {
+    /// Every execution reaches the code.
+    Eventually,
+    /// At least one execution reaches the code.
+    Possibly,
+    /// No execution reaches the code.
+    Never,
+}
+
+impl std::fmt::Display for Reachability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Eventually => write!(f, "eventually"),
+            Self::Possibly => write!(f, "possibly"),
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// An annotation written in a comment of the source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Annotation {
+    /// A property about the reachability of the code after the annotation.
+    /// The name is `None` if the user did not give one.
+    Property {
+        name: Option<String>,
+        reachability: Reachability,
+    },
+    /// A suppression of the findings of the given kind in the code after the annotation.
+    Allow(FindingKind),
+}
+
+/// A property annotated in the source code and applied to the net.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnnotatedProperty {
+    /// The name of the property, e.g. `DATA_RECEIVED` or `LINE_12`.
+    pub name: String,
+    /// How often the code after the annotation must be reached.
+    pub reachability: Reachability,
+    /// The label of the marker place that has a token once the code after the annotation was reached.
+    pub marker: String,
+    /// The location of the annotation in the source code.
+    pub span: SpanInfo,
+}
+
+/// A suppression annotated in the source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suppression {
+    /// The kind of findings suppressed.
+    pub kind: FindingKind,
+    /// The location of the suppressed code, starting at the annotation.
+    pub span: SpanInfo,
+}
+
+impl Suppression {
+    /// Checks whether the suppression applies to the code at the given span.
+    #[must_use]
+    pub fn covers(&self, span: &SpanInfo) -> bool {
+        span.filename == self.span.filename
+            && (self.span.start_line..=self.span.end_line).contains(&span.start_line)
+    }
+}
+
+/// Parses the annotation in a line of the source code.
+/// Returns `None` if the line is not an annotation.
+///
+/// # Errors
+///
+/// If the line is an annotation but it is malformed, then an error describing the problem is returned.
+#[must_use]
+pub fn parse_line(line: &str) -> Option<Result<Annotation, String>> {
+    let text = line
+        .trim_start()
+        .strip_prefix("//")?
+        .trim_start()
+        .strip_prefix(ANNOTATION_PREFIX)?
+        .trim();
+    if let Some(property) = text.strip_prefix("property ") {
+        return Some(parse_property(property.trim()));
+    }
+    if let Some(kind) = text
+        .strip_prefix("allow(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return Some(parse_allow(kind.trim()));
+    }
+    Some(Err(format!("Unknown annotation `{text}`")))
+}
+
+/// Parses the kind of findings in a suppression annotation `allow(KIND)`.
+fn parse_allow(kind: &str) -> Result<Annotation, String> {
+    FindingKind::from_name(kind)
+        .map(Annotation::Allow)
+        .ok_or_else(|| format!("Unknown finding `{kind}`: Expected `deadlock`"))
+}
+
+/// Parses the text of a property annotation after the keyword `property`.
+fn parse_property(text: &str) -> Result<Annotation, String> {
+    let (name, condition) = match text.split_once(':') {
+        Some((name, condition)) => {
+            let name = name.trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!(
+                    "Invalid property name `{name}`: Use only letters, digits and underscores"
+                ));
+            }
+            (Some(name.to_string()), condition)
+        }
+        None => (None, text),
+    };
+    let reachability = match condition.split_whitespace().collect::<Vec<_>>()[..] {
+        ["eventually", "reachable"] => Reachability::Eventually,
+        ["possibly", "reachable"] => Reachability::Possibly,
+        ["never", "reachable"] => Reachability::Never,
+        _ => {
+            return Err(format!(
+                "Invalid property `{}`: Expected `eventually reachable`, `possibly reachable` or `never reachable`",
+                condition.trim()
+            ))
+        }
+    };
+    Ok(Annotation::Property { name, reachability })
+}
+
+/// Parses the annotations in the source code.
+/// Returns the annotations or the parsing errors together with their line (1-based).
+#[must_use]
+pub fn parse_annotations(source_code: &str) -> Vec<(usize, Result<Annotation, String>)> {
+    source_code
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index + 1, parse_line(line)?)))
+        .collect()
+}
+
+/// Applies the property annotations in the source code of the file `filename` to the net.
+/// Adds the marker places for every property and returns the properties applied.
+///
+/// Malformed annotations, properties whose name is already in use
+/// and properties not followed by any code in the net are skipped with a warning.
+///
+/// # Panics
+///
+/// If the initial token or the capacity of the marker places cannot be set, then the function panics.
+pub fn apply_property_annotations(
+    source_code: &str,
+    filename: &str,
+    net: &mut PetriNet,
+) -> Vec<AnnotatedProperty> {
+    let mut properties = Vec::new();
+    for (line, annotation) in parse_annotations(source_code) {
+        let (name, reachability) = match annotation {
+            Ok(Annotation::Property { name, reachability }) => {
+                (name.unwrap_or_else(|| default_name(line)), reachability)
+            }
+            Ok(Annotation::Allow(_)) => continue,
+            Err(message) => {
+                warn!("Ignoring the annotation at {filename}:{line}: {message}");
+                continue;
+            }
+        };
+        let (not_reached_label, reached_label) = marker_place_labels(&name);
+        if net.place_labels().contains(&reached_label.as_str()) {
+            warn!("Ignoring the property at {filename}:{line}: The name {name} is already in use");
+            continue;
+        }
+        let transitions = net.first_transitions_after_line(filename, line);
+        if transitions.is_empty() {
+            warn!("Ignoring the property at {filename}:{line}: No translated code follows it");
+            continue;
+        }
+
+        let not_reached = net.add_place(&not_reached_label);
+        let reached = net.add_place(&reached_label);
+        net.add_token(&not_reached, 1).expect(
+            "BUG: Adding the initial token to the marker place should not cause an overflow",
+        );
+        for place in [&not_reached, &reached] {
+            net.set_capacity(place, 1)
+                .expect("BUG: Setting the capacity of the marker places should not fail");
+        }
+        for transition in &transitions {
+            let repeated = net.duplicate_transition(
+                transition,
+                &repeated_transition_label(transition.label(), &name),
+            );
+            // The original transition fires the first time, the copy every time after that.
+            add_arc_place_transition(net, &not_reached, transition);
+            add_arc_transition_place(net, transition, &reached);
+            add_arc_place_transition(net, &reached, &repeated);
+            add_arc_transition_place(net, &repeated, &reached);
+        }
+
+        properties.push(AnnotatedProperty {
+            name,
+            reachability,
+            marker: reached_label,
+            span: SpanInfo {
+                filename: filename.to_string(),
+                start_line: line,
+                start_column: 1,
+                end_line: line,
+                end_column: 1,
+            },
+        });
+    }
+    properties
+}
+
+/// Returns the suppressions annotated in the source code of the file `filename`.
+///
+/// Suppressions not followed by any code are skipped with a warning.
+/// Malformed annotations are skipped silently since `apply_property_annotations` warns about them.
+#[must_use]
+pub fn parse_suppressions(source_code: &str, filename: &str) -> Vec<Suppression> {
+    let lines: Vec<&str> = source_code.lines().collect();
+    let mut suppressions = Vec::new();
+    for (line, annotation) in parse_annotations(source_code) {
+        let Ok(Annotation::Allow(kind)) = annotation else {
+            continue;
+        };
+        let Some(end_line) = end_of_next_item(&lines, line) else {
+            warn!("Ignoring the suppression at {filename}:{line}: No code follows it");
+            continue;
+        };
+        suppressions.push(Suppression {
+            kind,
+            span: SpanInfo {
+                filename: filename.to_string(),
+                start_line: line,
+                start_column: 1,
+                end_line,
+                end_column: 1,
+            },
+        });
+    }
+    suppressions
+}
+
+/// Returns the last line (1-based) of the item, block or statement that follows the given line,
+/// i.e. the line of the matching closing brace or of the semicolon that ends the statement.
+/// Returns `None` if the source code or the enclosing block ends before.
+fn end_of_next_item(lines: &[&str], line: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, text) in lines.iter().enumerate().skip(line) {
+        let code = text.split_once("//").map_or(*text, |(code, _)| code);
+        for character in code.chars() {
+            match character {
+                '{' => depth += 1,
+                // The closing brace of the enclosing block, nothing follows the annotation.
+                '}' if depth == 0 => return None,
+                '}' if depth == 1 => return Some(index + 1),
+                '}' => depth -= 1,
+                ';' if depth == 0 => return Some(index + 1),
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod annotations_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::connect_places;
+
+    #[test]
+    fn parse_line_ignores_other_comments_and_code() {
+        assert_eq!(
+            parse_line("let x = 1; // granite: property never reachable"),
+            None
+        );
+        assert_eq!(parse_line("// a regular comment"), None);
+        assert_eq!(parse_line("/// granite: property never reachable"), None);
+    }
+
+    #[test]
+    fn parse_line_reads_the_name_and_the_reachability() {
+        assert_eq!(
+            parse_line("    // granite: property DATA_RECEIVED: eventually reachable"),
+            Some(Ok(Annotation::Property {
+                name: Some("DATA_RECEIVED".to_string()),
+                reachability: Reachability::Eventually,
+            }))
+        );
+        assert_eq!(
+            parse_line("//granite: property possibly   reachable"),
+            Some(Ok(Annotation::Property {
+                name: None,
+                reachability: Reachability::Possibly,
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_line_rejects_malformed_annotations() {
+        assert!(matches!(
+            parse_line("// granite: property sometimes reachable"),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            parse_line("// granite: property bad name: never reachable"),
+            Some(Err(_))
+        ));
+        assert!(matches!(parse_line("// granite: ignore"), Some(Err(_))));
+        assert!(matches!(
+            parse_line("// granite: allow(livelock)"),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn parse_line_reads_suppressions() {
+        assert_eq!(
+            parse_line("// granite: allow(deadlock)"),
+            Some(Ok(Annotation::Allow(FindingKind::Deadlock)))
+        );
+    }
+
+    #[test]
+    fn parse_suppressions_covers_the_next_block_or_statement() {
+        let source_code = "\
+// granite: allow(deadlock)
+fn worker() {
+    if true {
+        let _guard = lock();
+    }
+}
+fn main() {
+    // granite: allow(deadlock)
+    let _guard = data
+        .lock();
+    worker();
+}
+";
+        let suppressions = parse_suppressions(source_code, "main.rs");
+
+        let lines: Vec<_> = suppressions
+            .iter()
+            .map(|suppression| (suppression.span.start_line, suppression.span.end_line))
+            .collect();
+        assert_eq!(lines, [(1, 6), (8, 10)]);
+        let call = SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: 11,
+            ..SpanInfo::default()
+        };
+        assert!(!suppressions[1].covers(&call));
+        assert!(suppressions[0].covers(&SpanInfo {
+            start_line: 4,
+            ..call
+        }));
+    }
+
+    #[test]
+    fn apply_property_annotations_marks_the_next_line() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("START");
+        let end = net.add_place("END");
+        net.set_current_span(Some(SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: 3,
+            ..SpanInfo::default()
+        }));
+        connect_places(&mut net, &start, &end, "CALL");
+        let source_code = "fn main() {\n    // granite: property never reachable\n    call();\n}\n";
+
+        let properties = apply_property_annotations(source_code, "main.rs", &mut net);
+
+        assert_eq!(properties.len(), 1);
+        assert_eq!(properties[0].name, "LINE_2");
+        assert_eq!(properties[0].reachability, Reachability::Never);
+        assert_eq!(properties[0].marker, "PROPERTY_LINE_2_REACHED");
+        let data = net.data();
+        assert_eq!(data.transitions[1].label, "CALL_LINE_2_AGAIN");
+        assert_eq!(data.places[2].marking, 1);
+        assert_eq!(data.places[3].marking, 0);
+    }
+
+    #[test]
+    fn apply_property_annotations_skips_annotations_without_code() {
+        let mut net = PetriNet::new();
+        let source_code = "fn main() {\n    // granite: property eventually reachable\n}\n";
+
+        assert!(apply_property_annotations(source_code, "main.rs", &mut net).is_empty());
+        assert!(net.place_labels().is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+//! Module that implements the data structures that hold the result of the translation.
+//! These do not depend on rustc internals.
+
+pub mod arc_store;
+pub mod block_mapping;
+pub mod coverage;
+pub mod dot_format;
+pub mod dot_style;
+pub mod function_classification;
+pub mod identifiers;
+pub mod interchange_formats;
+pub mod label_map;
+pub mod label_registry;
+pub mod layout;
+pub mod lola_format;
+pub mod mermaid;
+#[cfg(any(test, feature = "test-util"))]
+pub mod net_builder;
+pub mod net_data;
+pub mod petri_net_interface;
+pub mod pnml_dialect;
+pub mod pnml_format;
+pub mod profiler;
+pub mod span_info;
+pub mod sync_registry;
+pub mod tikz;
+pub mod translation_plan;
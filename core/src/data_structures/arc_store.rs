@@ -0,0 +1,354 @@
+//! Module that implements the storage of the arcs of the `PetriNet`.
+//!
+//! The arcs are the dominant memory consumer of large nets: Every function call adds several of them.
+//! They are only appended during the translation and read sequentially once the net is built,
+//! which makes them a good candidate to move out of memory.
+//!
+//! By default, the `ArcStore` keeps every arc in memory.
+//! With a memory limit, at most the given number of bytes of arcs are kept in memory.
+//! When the limit is reached, the arcs in memory are appended to a temporary file
+//! in a compact binary format and the memory is reused.
+//! Reading the arcs back is slower, so the limit trades throughput for memory.
+//!
+//! The temporary file is deleted when the store is dropped.
+//!
+//! The `wasm32` targets have no file system, so the limit is ignored there and every arc is kept in memory.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufReader, Read, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::data_structures::net_data::ArcDirection;
+
+/// The size in bytes of an arc in the temporary file:
+/// The index of the place and the transition and the weight as `u64` and the direction as a single byte.
+#[cfg(not(target_arch = "wasm32"))]
+const ENCODED_ARC_SIZE: usize = 25;
+
+/// A counter to create a unique temporary file for every store in the process.
+#[cfg(not(target_arch = "wasm32"))]
+static SPILL_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// An arc between a place and a transition identified by their index in the `PetriNet`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StoredArc {
+    pub place: usize,
+    pub transition: usize,
+    pub direction: ArcDirection,
+    pub weight: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StoredArc {
+    /// Encodes the arc in the binary format of the temporary file.
+    fn encode(self) -> [u8; ENCODED_ARC_SIZE] {
+        let mut bytes = [0; ENCODED_ARC_SIZE];
+        bytes[0..8].copy_from_slice(&(self.place as u64).to_le_bytes());
+        bytes[8..16].copy_from_slice(&(self.transition as u64).to_le_bytes());
+        bytes[16..24].copy_from_slice(&(self.weight as u64).to_le_bytes());
+        bytes[24] = match self.direction {
+            ArcDirection::PlaceToTransition => 0,
+            ArcDirection::TransitionToPlace => 1,
+        };
+        bytes
+    }
+
+    /// Decodes an arc from the binary format of the temporary file.
+    fn decode(bytes: &[u8; ENCODED_ARC_SIZE]) -> Self {
+        let number = |range: std::ops::Range<usize>| {
+            let mut number = [0; 8];
+            number.copy_from_slice(&bytes[range]);
+            usize::try_from(u64::from_le_bytes(number))
+                .expect("BUG: The numbers of a spilled arc should fit in a `usize`")
+        };
+        Self {
+            place: number(0..8),
+            transition: number(8..16),
+            weight: number(16..24),
+            direction: if bytes[24] == 0 {
+                ArcDirection::PlaceToTransition
+            } else {
+                ArcDirection::TransitionToPlace
+            },
+        }
+    }
+}
+
+/// The temporary file where the arcs that do not fit in memory are stored.
+#[cfg(not(target_arch = "wasm32"))]
+struct SpillFile {
+    path: PathBuf,
+    file: std::fs::File,
+    /// The number of arcs in the file.
+    len: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        // The file is only a cache of the arcs: Failing to delete it is not an error.
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The storage of the arcs of the `PetriNet` in the order they were added.
+#[derive(Default)]
+pub struct ArcStore {
+    /// The arcs in memory, added after the ones in the temporary file.
+    buffer: Vec<StoredArc>,
+    /// The maximum number of arcs in memory. `None` if the arcs are never moved to disk.
+    #[cfg(not(target_arch = "wasm32"))]
+    max_buffered: Option<usize>,
+    /// The temporary file, created when the limit is reached for the first time.
+    #[cfg(not(target_arch = "wasm32"))]
+    spill_file: Option<SpillFile>,
+}
+
+impl ArcStore {
+    /// Creates a new store that keeps every arc in memory.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new store that keeps at most `max_memory` bytes of arcs in memory.
+    /// The rest of the arcs are moved to a temporary file.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn with_memory_limit(max_memory: usize) -> Self {
+        let max_buffered = (max_memory / std::mem::size_of::<StoredArc>()).max(1);
+        Self {
+            max_buffered: Some(max_buffered),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a new store that keeps every arc in memory, since the `wasm32` targets have no file system.
+    #[cfg(target_arch = "wasm32")]
+    #[must_use]
+    pub fn with_memory_limit(_max_memory: usize) -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of arcs in the store.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buffer.len() + self.spilled_len()
+    }
+
+    /// Returns the number of arcs in the temporary file.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spilled_len(&self) -> usize {
+        self.spill_file
+            .as_ref()
+            .map_or(0, |spill_file| spill_file.len)
+    }
+
+    /// Returns the number of arcs in the temporary file, which does not exist on the `wasm32` targets.
+    #[cfg(target_arch = "wasm32")]
+    #[allow(clippy::unused_self)]
+    fn spilled_len(&self) -> usize {
+        0
+    }
+
+    /// Checks whether the store is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends an arc to the store.
+    /// If the memory limit is reached, the arcs in memory are moved to the temporary file.
+    ///
+    /// # Panics
+    ///
+    /// If the temporary file cannot be created or written, then the function panics.
+    pub fn push(&mut self, arc: StoredArc) {
+        self.buffer.push(arc);
+        #[cfg(not(target_arch = "wasm32"))]
+        if self
+            .max_buffered
+            .is_some_and(|max_buffered| self.buffer.len() >= max_buffered)
+        {
+            self.spill()
+                .unwrap_or_else(|err| panic!("ERROR: Could not move the arcs to disk: {err}"));
+        }
+    }
+
+    /// Returns an iterator over the arcs in the order they were added.
+    ///
+    /// # Panics
+    ///
+    /// If the temporary file cannot be read, then the iterator panics.
+    #[must_use]
+    pub fn iter(&self) -> ArcIter<'_> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let reader = self.spill_file.as_ref().map(|spill_file| {
+            let file = std::fs::File::open(&spill_file.path).unwrap_or_else(|err| {
+                panic!("ERROR: Could not read the arcs stored on disk: {err}")
+            });
+            (BufReader::new(file), spill_file.len)
+        });
+        ArcIter {
+            #[cfg(not(target_arch = "wasm32"))]
+            reader,
+            buffer: self.buffer.iter(),
+        }
+    }
+
+    /// Appends the arcs in memory to the temporary file and clears the buffer.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spill(&mut self) -> Result<(), std::io::Error> {
+        if self.spill_file.is_none() {
+            let path = std::env::temp_dir().join(format!(
+                "cargo-check-deadlock-arcs-{}-{}.bin",
+                std::process::id(),
+                SPILL_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            let file = std::fs::File::create(&path)?;
+            self.spill_file = Some(SpillFile { path, file, len: 0 });
+        }
+        let spill_file = self
+            .spill_file
+            .as_mut()
+            .expect("BUG: The temporary file should have been created");
+
+        let mut bytes = Vec::with_capacity(self.buffer.len() * ENCODED_ARC_SIZE);
+        for arc in &self.buffer {
+            bytes.extend_from_slice(&arc.encode());
+        }
+        spill_file.file.write_all(&bytes)?;
+        spill_file.len += self.buffer.len();
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a ArcStore {
+    type Item = StoredArc;
+    type IntoIter = ArcIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the arcs of an `ArcStore`.
+/// It reads the arcs from the temporary file first and then the arcs in memory.
+pub struct ArcIter<'a> {
+    /// The reader of the temporary file and the number of arcs left to read from it.
+    #[cfg(not(target_arch = "wasm32"))]
+    reader: Option<(BufReader<std::fs::File>, usize)>,
+    /// The arcs in memory.
+    buffer: std::slice::Iter<'a, StoredArc>,
+}
+
+impl Iterator for ArcIter<'_> {
+    type Item = StoredArc;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some((reader, remaining)) = &mut self.reader {
+            if *remaining > 0 {
+                *remaining -= 1;
+                let mut bytes = [0; ENCODED_ARC_SIZE];
+                reader.read_exact(&mut bytes).unwrap_or_else(|err| {
+                    panic!("ERROR: Could not read the arcs stored on disk: {err}")
+                });
+                return Some(StoredArc::decode(&bytes));
+            }
+        }
+        self.buffer.next().copied()
+    }
+}
+
+#[cfg(test)]
+mod arc_store_tests {
+    use super::*;
+
+    fn arc(index: usize) -> StoredArc {
+        StoredArc {
+            place: index,
+            transition: index + 1,
+            direction: if index < 5 {
+                ArcDirection::PlaceToTransition
+            } else {
+                ArcDirection::TransitionToPlace
+            },
+            weight: index % 3 + 1,
+        }
+    }
+
+    #[test]
+    fn arc_store_new_is_empty() {
+        let store = ArcStore::new();
+
+        assert!(store.is_empty());
+        assert_eq!(store.iter().next(), None);
+    }
+
+    #[test]
+    fn arc_store_in_memory_returns_arcs_in_order() {
+        let mut store = ArcStore::new();
+        for index in 0..10 {
+            store.push(arc(index));
+        }
+
+        assert_eq!(store.len(), 10);
+        assert_eq!(store.spilled_len(), 0);
+        assert_eq!(
+            store.iter().collect::<Vec<_>>(),
+            (0..10).map(arc).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn arc_store_with_memory_limit_returns_arcs_in_order() {
+        let mut store = ArcStore::with_memory_limit(4 * std::mem::size_of::<StoredArc>());
+        for index in 0..10 {
+            store.push(arc(index));
+        }
+
+        assert_eq!(store.len(), 10);
+        #[cfg(not(target_arch = "wasm32"))]
+        assert!(store.buffer.len() < 4);
+        assert_eq!(
+            store.iter().collect::<Vec<_>>(),
+            (0..10).map(arc).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn arc_store_deletes_temporary_file_on_drop() {
+        let mut store = ArcStore::with_memory_limit(0);
+        store.push(arc(0));
+        let path = store
+            .spill_file
+            .as_ref()
+            .expect("The arc should have been moved to disk")
+            .path
+            .clone();
+        assert!(path.exists());
+
+        drop(store);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn stored_arc_encoding_round_trip_is_lossless() {
+        let arc = StoredArc {
+            place: usize::MAX,
+            transition: 42,
+            direction: ArcDirection::TransitionToPlace,
+            weight: 4,
+        };
+
+        assert_eq!(StoredArc::decode(&arc.encode()), arc);
+    }
+}
@@ -0,0 +1,118 @@
+//! Module that implements the mapping between the basic blocks of the MIR functions and the nodes of the net.
+//!
+//! For every basic block of every translated function instance, the mapping lists the places and transitions
+//! added to the net while translating the block, together with the kind of its terminator and its span.
+//! The nodes added while translating a function called from the block belong to the blocks of the callee,
+//! except for the transitions that model the call itself.
+//! The start place of a block belongs to the block even if it was added by the terminator of another block.
+//!
+//! The mapping refers to the net built by the translator. It is not updated by the post-processing passes,
+//! e.g. the reductions, which remove or merge places and transitions.
+//!
+//! It does not depend on rustc internals and can be exported to JSON, so that other analyses
+//! can be built on top of the translation without modifying it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_structures::span_info::SpanInfo;
+
+/// The nodes of the net that model a basic block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockNodes {
+    /// The index of the basic block in the MIR body, e.g. `3` for `bb3`.
+    pub block: usize,
+    /// The kind of the terminator of the block, e.g. `Call` or `SwitchInt`.
+    pub terminator: String,
+    /// The span of the terminator in the source code, if any.
+    pub span: Option<SpanInfo>,
+    /// The labels of the places of the block, starting with its start place.
+    pub places: Vec<String>,
+    /// The labels of the transitions of the block, in the order they were added.
+    pub transitions: Vec<String>,
+}
+
+/// The basic blocks of a translated function instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionNodes {
+    /// The name of the function instance, e.g. `main` or `compute`.
+    pub function: String,
+    /// The basic blocks in the order they were translated.
+    pub blocks: Vec<BlockNodes>,
+}
+
+/// The mapping between the basic blocks of the translated functions and the nodes of the net.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMapping {
+    /// The function instances in the order their translation finished.
+    pub functions: Vec<FunctionNodes>,
+}
+
+impl BlockMapping {
+    /// Returns the representation of the mapping in JSON.
+    ///
+    /// # Panics
+    ///
+    /// If the mapping cannot be serialized, then the function panics. This should never happen.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("BUG: The block mapping should be serializable to JSON")
+    }
+
+    /// Returns the function instance and the basic block whose nodes include the transition, if any.
+    #[must_use]
+    pub fn block_of_transition(&self, label: &str) -> Option<(&FunctionNodes, &BlockNodes)> {
+        self.functions.iter().find_map(|function| {
+            function
+                .blocks
+                .iter()
+                .find(|block| {
+                    block
+                        .transitions
+                        .iter()
+                        .any(|transition| transition == label)
+                })
+                .map(|block| (function, block))
+        })
+    }
+}
+
+#[cfg(test)]
+mod block_mapping_tests {
+    use super::*;
+
+    fn mapping() -> BlockMapping {
+        BlockMapping {
+            functions: vec![FunctionNodes {
+                function: "main".to_string(),
+                blocks: vec![BlockNodes {
+                    block: 0,
+                    terminator: "Call".to_string(),
+                    span: None,
+                    places: vec!["main_BB0".to_string()],
+                    transitions: vec!["std_sync_Mutex_T_lock_0_CALL".to_string()],
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn block_of_transition_finds_the_block() {
+        let mapping = mapping();
+        let (function, block) = mapping
+            .block_of_transition("std_sync_Mutex_T_lock_0_CALL")
+            .unwrap();
+
+        assert_eq!(function.function, "main");
+        assert_eq!(block.block, 0);
+        assert!(mapping.block_of_transition("main_GOTO_0").is_none());
+    }
+
+    #[test]
+    fn block_mapping_json_lists_the_nodes() {
+        let json = mapping().to_json();
+
+        assert!(json.contains("\"terminator\": \"Call\""));
+        assert!(json.contains("\"places\": [\n"));
+    }
+}
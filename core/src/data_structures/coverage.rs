@@ -0,0 +1,195 @@
+//! Module that implements the bookkeeping of which user-defined functions
+//! were translated and which were abstracted during the translation.
+//!
+//! A user-defined function is fully translated when its MIR body is visited.
+//! It is abstracted when its calls are replaced by a simple transition,
+//! the same representation used for foreign function calls.
+//! A function that is translated at least once counts as translated,
+//! even if some of its calls were abstracted.
+//!
+//! A function whose translation failed or exceeded its time budget is skipped:
+//! Its calls are abstracted and the reason is recorded for the report.
+//!
+//! It does not depend on rustc internals and can be used freely after the translation.
+
+use std::collections::BTreeMap;
+
+use crate::data_structures::span_info::SpanInfo;
+
+/// How a user-defined function was handled by the translation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FunctionStatus {
+    /// The MIR body of the function was translated.
+    Translated,
+    /// The calls to the function were modelled as a foreign function call.
+    Abstracted,
+}
+
+impl std::fmt::Display for FunctionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Translated => write!(f, "translated"),
+            Self::Abstracted => write!(f, "abstracted"),
+        }
+    }
+}
+
+/// A user-defined function reached during the translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCoverage {
+    /// The name of the function, e.g. `utils::compute`.
+    pub name: String,
+    /// The name of the module where the function is defined, e.g. `utils`.
+    pub module: String,
+    /// The span of the whole function definition in the source code.
+    pub span: SpanInfo,
+    /// How the function was handled by the translation.
+    pub status: FunctionStatus,
+}
+
+impl FunctionCoverage {
+    /// Returns the number of source code lines spanned by the function definition.
+    #[must_use]
+    pub const fn lines(&self) -> usize {
+        self.span.end_line - self.span.start_line + 1
+    }
+}
+
+#[derive(Default)]
+pub struct Coverage {
+    functions: BTreeMap<String, FunctionCoverage>,
+    /// The reason why each skipped function was skipped, by name of the function.
+    skipped: BTreeMap<String, String>,
+}
+
+impl Coverage {
+    /// Creates a new empty coverage.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a user-defined function was reached with the given status.
+    /// A function recorded as translated keeps this status even if it is recorded as abstracted later.
+    pub fn record(&mut self, name: &str, module: &str, span: SpanInfo, status: FunctionStatus) {
+        if let Some(function) = self.functions.get_mut(name) {
+            if status == FunctionStatus::Translated {
+                function.status = status;
+            }
+            return;
+        }
+        self.functions.insert(
+            name.to_string(),
+            FunctionCoverage {
+                name: name.to_string(),
+                module: module.to_string(),
+                span,
+                status,
+            },
+        );
+    }
+
+    /// Records that the translation of a function was skipped for the given reason.
+    pub fn record_skipped(&mut self, name: &str, reason: &str) {
+        self.skipped.insert(name.to_string(), reason.to_string());
+    }
+
+    /// Returns an iterator over the names of the skipped functions and the reasons, sorted by name.
+    pub fn skipped(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.skipped
+            .iter()
+            .map(|(name, reason)| (name.as_str(), reason.as_str()))
+    }
+
+    /// Returns the function with the given name, if it was recorded.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&FunctionCoverage> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the recorded functions sorted by name.
+    pub fn iter(&self) -> std::collections::btree_map::Values<'_, String, FunctionCoverage> {
+        self.functions.values()
+    }
+
+    /// Returns the number of recorded functions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Checks whether no function was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a Coverage {
+    type Item = &'a FunctionCoverage;
+    type IntoIter = std::collections::btree_map::Values<'a, String, FunctionCoverage>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod coverage_tests {
+    use super::*;
+
+    fn span_with_lines(start_line: usize, end_line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line,
+            start_column: 1,
+            end_line,
+            end_column: 2,
+        }
+    }
+
+    #[test]
+    fn coverage_new_is_empty() {
+        let coverage = Coverage::new();
+
+        assert!(coverage.is_empty());
+        assert_eq!(coverage.len(), 0);
+    }
+
+    #[test]
+    fn coverage_record_same_function_twice_keeps_one_entry() {
+        let mut coverage = Coverage::new();
+        let span = span_with_lines(1, 3);
+        coverage.record("main", "crate", span.clone(), FunctionStatus::Translated);
+        coverage.record("main", "crate", span, FunctionStatus::Translated);
+
+        assert_eq!(coverage.len(), 1);
+        assert_eq!(coverage.get("main").unwrap().lines(), 3);
+    }
+
+    #[test]
+    fn coverage_record_translated_overrides_abstracted() {
+        let mut coverage = Coverage::new();
+        let span = span_with_lines(5, 10);
+        coverage.record("foo", "crate", span.clone(), FunctionStatus::Abstracted);
+        coverage.record("foo", "crate", span, FunctionStatus::Translated);
+
+        assert_eq!(
+            coverage.get("foo").unwrap().status,
+            FunctionStatus::Translated
+        );
+    }
+
+    #[test]
+    fn coverage_record_abstracted_does_not_override_translated() {
+        let mut coverage = Coverage::new();
+        let span = span_with_lines(5, 10);
+        coverage.record("foo", "crate", span.clone(), FunctionStatus::Translated);
+        coverage.record("foo", "crate", span, FunctionStatus::Abstracted);
+
+        assert_eq!(
+            coverage.get("foo").unwrap().status,
+            FunctionStatus::Translated
+        );
+    }
+}
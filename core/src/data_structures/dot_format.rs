@@ -0,0 +1,175 @@
+//! Module that writes a `NetData` in the DOT format of Graphviz.
+//!
+//! The places are drawn as circles with a bullet per token and the transitions as boxes:
+//!
+//! ```text
+//! digraph petrinet {
+//!     MUTEX_0 [shape="circle" xlabel="MUTEX_0" label="•"];
+//!     main_BB1 [shape="circle" xlabel="main_BB1" label=""];
+//!     lock [shape="box" xlabel="" label="lock"];
+//!     MUTEX_0 -> lock;
+//!     main_BB1 -> lock;
+//!     RWLOCK_0 -> write [label="4"];
+//! }
+//! ```
+//!
+//! The arcs with a weight greater than one are labelled with it.
+//! Optionally, the span of the source code that produced every node is added as its tooltip,
+//! which Graphviz keeps in the SVG output, where it is shown when hovering over the node.
+//!
+//! The places and the transitions are written sorted by their labels,
+//! followed by the arcs from places to transitions and the arcs from transitions to places.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::data_structures::identifiers::{escape_dot, IdentifierTable};
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::span_info::SpanInfo;
+
+/// Writes the net in DOT format to the given writer, optionally with the spans as tooltips.
+/// The nodes are named with their identifiers in the table and labelled with their original labels.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_dot<T: Write>(
+    net: &NetData,
+    identifiers: &IdentifierTable,
+    with_spans: bool,
+    writer: &mut T,
+) -> Result<(), std::io::Error> {
+    let tooltip = |span: Option<&SpanInfo>| match span {
+        Some(span) if with_spans => format!(" tooltip=\"{}\"", escape_dot(&span.to_string())),
+        _ => String::new(),
+    };
+    writeln!(writer, "digraph petrinet {{")?;
+
+    let mut places: Vec<_> = net.places.iter().collect();
+    places.sort_unstable_by(|a, b| a.label.cmp(&b.label));
+    for place in places {
+        writeln!(
+            writer,
+            "    {} [shape=\"circle\" xlabel=\"{}\" label=\"{}\"{}];",
+            identifiers.identifier(&place.label),
+            escape_dot(&place.label),
+            "•".repeat(place.marking),
+            tooltip(place.span.as_ref())
+        )?;
+    }
+    let mut transitions: Vec<_> = net.transitions.iter().collect();
+    transitions.sort_unstable_by(|a, b| a.label.cmp(&b.label));
+    for transition in transitions {
+        writeln!(
+            writer,
+            "    {} [shape=\"box\" xlabel=\"\" label=\"{}\"{}];",
+            identifiers.identifier(&transition.label),
+            escape_dot(&transition.label),
+            tooltip(transition.span.as_ref())
+        )?;
+    }
+
+    let mut inputs: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    let mut outputs: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for arc in &net.arcs {
+        let (edges, edge) = match arc.direction {
+            ArcDirection::PlaceToTransition => {
+                (&mut inputs, (arc.place.as_str(), arc.transition.as_str()))
+            }
+            ArcDirection::TransitionToPlace => {
+                (&mut outputs, (arc.transition.as_str(), arc.place.as_str()))
+            }
+        };
+        *edges.entry(edge).or_default() += arc.weight;
+    }
+    for ((source, target), weight) in inputs.into_iter().chain(outputs) {
+        let source = identifiers.identifier(source);
+        let target = identifiers.identifier(target);
+        if weight == 1 {
+            writeln!(writer, "    {source} -> {target};")?;
+        } else {
+            writeln!(writer, "    {source} -> {target} [label=\"{weight}\"];")?;
+        }
+    }
+    writeln!(writer, "}}")
+}
+
+#[cfg(test)]
+mod dot_format_tests {
+    use super::*;
+    use crate::data_structures::identifiers::IdentifierFormat;
+    use crate::data_structures::lola_format::parse_lola;
+
+    /// Writes the net in DOT format with the identifiers of the labels.
+    fn dot(net: &NetData, with_spans: bool) -> String {
+        let labels = net.places.iter().map(|place| place.label.as_str()).chain(
+            net.transitions
+                .iter()
+                .map(|transition| transition.label.as_str()),
+        );
+        let identifiers = IdentifierTable::new(IdentifierFormat::Dot, labels);
+        let mut output = Vec::new();
+        write_dot(net, &identifiers, with_spans, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn write_dot_labels_only_the_weighted_arcs() {
+        let net = parse_lola(
+            "PLACE RWLOCK_0, main_BB1, main_BB2;\nMARKING RWLOCK_0 : 4, main_BB1 : 1;\n\
+             TRANSITION write CONSUME RWLOCK_0 : 4, main_BB1 : 1; PRODUCE main_BB2 : 1;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            dot(&net, false),
+            "\
+digraph petrinet {
+    RWLOCK_0 [shape=\"circle\" xlabel=\"RWLOCK_0\" label=\"••••\"];
+    main_BB1 [shape=\"circle\" xlabel=\"main_BB1\" label=\"•\"];
+    main_BB2 [shape=\"circle\" xlabel=\"main_BB2\" label=\"\"];
+    write [shape=\"box\" xlabel=\"\" label=\"write\"];
+    RWLOCK_0 -> write [label=\"4\"];
+    main_BB1 -> write;
+    write -> main_BB2;
+}
+"
+        );
+    }
+
+    #[test]
+    fn write_dot_quotes_illegal_identifiers_and_escapes_the_labels() {
+        let mut net =
+            parse_lola("PLACE a;\nMARKING ;\nTRANSITION t CONSUME a : 1; PRODUCE ;").unwrap();
+        net.transitions[0].label = "new_display'_0".to_string();
+        net.arcs[0].transition = "new_display'_0".to_string();
+
+        let dot = dot(&net, false);
+
+        assert!(dot.contains(
+            "    \"new_display'_0\" [shape=\"box\" xlabel=\"\" label=\"new_display'_0\"];\n"
+        ));
+        assert!(dot.contains("    a -> \"new_display'_0\";\n"));
+    }
+
+    #[test]
+    fn write_dot_adds_tooltips_only_to_nodes_with_span() {
+        let mut net =
+            parse_lola("PLACE a, b;\nMARKING a : 1;\nTRANSITION t CONSUME a : 1; PRODUCE b : 1;")
+                .unwrap();
+        net.transitions[0].span = Some(SpanInfo {
+            filename: "src/main.rs".to_string(),
+            start_line: 3,
+            start_column: 15,
+            end_line: 3,
+            end_column: 26,
+        });
+
+        let dot = dot(&net, true);
+
+        assert!(dot.contains(
+            "    t [shape=\"box\" xlabel=\"\" label=\"t\" tooltip=\"src/main.rs:3:15\"];\n"
+        ));
+        assert!(dot.contains("    a [shape=\"circle\" xlabel=\"a\" label=\"•\"];\n"));
+    }
+}
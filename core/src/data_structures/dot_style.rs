@@ -0,0 +1,471 @@
+//! Module that implements the optional styling of the net in DOT format.
+//!
+//! `netcrab` writes every node at the top level of the graph, in alphabetical order and without colors,
+//! which makes the nets of large programs hard to read. The `DotStyle` set on the `PetriNet`
+//! adds the following to the output of `PetriNet::to_dot`:
+//!
+//! - Clusters: The places of the basic blocks of every function and the transitions that leave them
+//!   are grouped in a `subgraph cluster_<function>` with its own background color, so the functions and the threads
+//!   can be told apart at a glance. The other nodes, e.g. the places of the mutexes, stay at the top level.
+//!   The function of a transition is the function of its first input place that belongs to a basic block,
+//!   like in the columns of the `TikZ` picture, see `tikz`.
+//! - Rank direction: The direction in which Graphviz lays out the graph, e.g. `LR` for left to right.
+//! - Highlighting: `PROGRAM_PANIC` and the places of the mutexes are filled with a color.
+//! - Synchronization: The transitions connected to a place of a synchronization primitive
+//!   are drawn with a thick border in a color, so the calls that may block stand out from the control flow.
+//!
+//! The style is set from the command line with `--dot-clusters` and `--dot-option key=value`, see `DotStyle::set_option`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::data_structures::identifiers::escape_dot;
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::naming::basic_block::function_of_place_label;
+use crate::naming::{is_sync_place_label, mutex, PROGRAM_PANIC};
+
+/// The background colors of the clusters, assigned in the order of the functions and then repeated.
+/// They are light enough for the black labels of the nodes.
+const CLUSTER_COLORS: [&str; 8] = [
+    "lightblue",
+    "lightyellow",
+    "honeydew",
+    "mistyrose",
+    "lavender",
+    "lightcyan",
+    "papayawhip",
+    "lightgrey",
+];
+
+/// The directions of the ranks supported by Graphviz: top to bottom (the default), left to right,
+/// bottom to top and right to left.
+const RANK_DIRECTIONS: [&str; 4] = ["TB", "LR", "BT", "RL"];
+
+/// The fill color of `PROGRAM_PANIC` with `highlight=true`.
+const DEFAULT_PANIC_COLOR: &str = "lightcoral";
+
+/// The fill color of the places of the mutexes with `highlight=true`.
+const DEFAULT_MUTEX_COLOR: &str = "orange";
+
+/// The border color of the transitions connected to a synchronization primitive with `sync-transitions=true`.
+const DEFAULT_SYNC_COLOR: &str = "blue";
+
+/// The keys accepted by `DotStyle::set_option`.
+const OPTION_KEYS: [&str; 7] = [
+    "rankdir",
+    "highlight",
+    "panic-color",
+    "mutex-color",
+    "sync-transitions",
+    "sync-color",
+    "clusters",
+];
+
+/// The optional styling of the net in DOT format. The default is the plain output of `netcrab`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DotStyle {
+    /// Whether the nodes of every function are grouped in a cluster, see `add_clusters_to_dot`.
+    pub clusters: bool,
+    /// The direction of the ranks, one of `RANK_DIRECTIONS`. If not set, Graphviz lays out the graph top to bottom.
+    pub rankdir: Option<String>,
+    /// The fill color of `PROGRAM_PANIC`, if highlighted.
+    pub panic_color: Option<String>,
+    /// The fill color of the places of the mutexes, e.g. `MUTEX_0`, if highlighted.
+    pub mutex_color: Option<String>,
+    /// The border color of the transitions connected to a place of a synchronization primitive, if highlighted.
+    pub sync_color: Option<String>,
+}
+
+impl DotStyle {
+    /// Checks whether the style changes the output of `netcrab`.
+    #[must_use]
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Sets an option of the style given as `key=value`. The supported options are:
+    ///
+    /// - `rankdir=TB|LR|BT|RL`: The direction of the ranks.
+    /// - `highlight=true|false`: Whether `PROGRAM_PANIC` and the places of the mutexes are filled with the default colors.
+    /// - `panic-color=<color>` and `mutex-color=<color>`: Fill the places with the given Graphviz color.
+    /// - `sync-transitions=true|false`: Whether the transitions connected to a synchronization primitive
+    ///   are drawn with a thick border in the default color.
+    /// - `sync-color=<color>`: Draw the border of these transitions with the given Graphviz color.
+    /// - `clusters=true|false`: Whether the nodes of every function are grouped in a cluster.
+    ///
+    /// The options are applied in order, so a later option overrides an earlier one.
+    ///
+    /// # Errors
+    ///
+    /// If the option is not of the form `key=value`, the key is unknown or the value is not valid for the key,
+    /// then an error is returned.
+    pub fn set_option(&mut self, option: &str) -> Result<(), String> {
+        let Some((key, value)) = option.split_once('=') else {
+            return Err(format!(
+                "The DOT option `{option}` should have the form `key=value`"
+            ));
+        };
+        match key {
+            "rankdir" => {
+                if !RANK_DIRECTIONS.contains(&value) {
+                    return Err(format!(
+                        "Unknown rank direction `{value}`, expected one of: {}",
+                        RANK_DIRECTIONS.join(", ")
+                    ));
+                }
+                self.rankdir = Some(value.to_string());
+            }
+            "highlight" => {
+                let highlight = parse_flag(key, value)?;
+                self.panic_color = highlight.then(|| DEFAULT_PANIC_COLOR.to_string());
+                self.mutex_color = highlight.then(|| DEFAULT_MUTEX_COLOR.to_string());
+            }
+            "panic-color" => self.panic_color = Some(parse_color(key, value)?),
+            "mutex-color" => self.mutex_color = Some(parse_color(key, value)?),
+            "sync-transitions" => {
+                self.sync_color = parse_flag(key, value)?.then(|| DEFAULT_SYNC_COLOR.to_string());
+            }
+            "sync-color" => self.sync_color = Some(parse_color(key, value)?),
+            "clusters" => self.clusters = parse_flag(key, value)?,
+            _ => {
+                return Err(format!(
+                    "Unknown DOT option `{key}`, expected one of: {}",
+                    OPTION_KEYS.join(", ")
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the attributes added to the nodes of the net in DOT format, by label.
+    /// Only the nodes highlighted by the style are included.
+    #[must_use]
+    pub fn node_attributes<'net>(&self, net: &'net NetData) -> BTreeMap<&'net str, String> {
+        let mut attributes = BTreeMap::new();
+        for place in &net.places {
+            let color = if place.label == PROGRAM_PANIC {
+                self.panic_color.as_ref()
+            } else if mutex::is_place_label(&place.label) {
+                self.mutex_color.as_ref()
+            } else {
+                None
+            };
+            if let Some(color) = color {
+                let color = escape_dot(color);
+                attributes.insert(
+                    place.label.as_str(),
+                    format!("style=\"filled\" fillcolor=\"{color}\""),
+                );
+            }
+        }
+        if let Some(color) = &self.sync_color {
+            let color = escape_dot(color);
+            for arc in net
+                .arcs
+                .iter()
+                .filter(|arc| is_sync_place_label(&arc.place))
+            {
+                attributes
+                    .entry(arc.transition.as_str())
+                    .or_insert_with(|| format!("color=\"{color}\" penwidth=\"2\""));
+            }
+        }
+        attributes
+    }
+}
+
+/// Parses the value of an option that is either `true` or `false`.
+fn parse_flag(key: &str, value: &str) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!(
+            "The DOT option `{key}` expects `true` or `false`, found `{value}`"
+        )),
+    }
+}
+
+/// Parses the value of an option that is a Graphviz color, e.g. `red` or `#ff0000`.
+/// The color is not checked against the names known to Graphviz, which warns about unknown colors.
+fn parse_color(key: &str, value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err(format!("The DOT option `{key}` expects a color"));
+    }
+    Ok(value.to_string())
+}
+
+/// Returns the sanitized name of the function of every place and transition that belongs to one, by label.
+#[must_use]
+pub fn functions_of_nodes(net: &NetData) -> BTreeMap<&str, &str> {
+    let mut functions: BTreeMap<&str, &str> = net
+        .places
+        .iter()
+        .filter_map(|place| Some((place.label.as_str(), function_of_place_label(&place.label)?)))
+        .collect();
+    for arc in &net.arcs {
+        if arc.direction != ArcDirection::PlaceToTransition {
+            continue;
+        }
+        if let Some(function) = function_of_place_label(&arc.place) {
+            functions.entry(arc.transition.as_str()).or_insert(function);
+        }
+    }
+    functions
+}
+
+/// Sets the direction of the ranks of a net in DOT format, e.g. `rankdir="LR";` after the opening line of the graph.
+#[must_use]
+pub fn add_rankdir_to_dot(dot: &str, rankdir: &str) -> String {
+    let Some((header, body)) = dot.split_once('\n') else {
+        return dot.to_string();
+    };
+    format!("{header}\n    rankdir=\"{}\";\n{body}", escape_dot(rankdir))
+}
+
+/// Adds attributes to the declarations of the nodes of a net in DOT format.
+///
+/// For example, `PROGRAM_PANIC [shape="circle" xlabel="PROGRAM_PANIC" label="" style="filled" fillcolor="red"];`.
+/// The attributes are indexed by the identifiers of the nodes in DOT.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn add_node_attributes_to_dot(dot: &str, attributes: &BTreeMap<&str, String>) -> String {
+    let mut output = String::with_capacity(dot.len());
+    for line in dot.lines() {
+        let trimmed = line.trim_start();
+        let node_attributes = trimmed
+            .match_indices(" [")
+            .find_map(|(index, _)| attributes.get(&trimmed[..index]));
+        if let (Some(node_attributes), Some(node)) = (node_attributes, line.strip_suffix("];")) {
+            writeln!(output, "{node} {node_attributes}];")
+                .expect("BUG: Writing to a string should not fail");
+        } else {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Moves the declarations of the nodes of every function into a cluster with its own background color.
+///
+/// `functions` contains the function of every node, indexed by its identifier in DOT.
+/// The clusters are written after the other nodes and before the arcs, in the order of their first node.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn add_clusters_to_dot(dot: &str, functions: &BTreeMap<&str, &str>) -> String {
+    let mut clusters: Vec<(&str, Vec<&str>)> = Vec::new();
+    let mut output = String::with_capacity(dot.len());
+    let mut written = false;
+    for line in dot.lines() {
+        let trimmed = line.trim_start();
+        let function = trimmed
+            .match_indices(" [")
+            .find_map(|(index, _)| functions.get(&trimmed[..index]));
+        if let Some(function) = function {
+            match clusters.iter_mut().find(|(name, _)| name == function) {
+                Some((_, lines)) => lines.push(line),
+                None => clusters.push((function, vec![line])),
+            }
+            continue;
+        }
+        if !written && (trimmed.contains(" -> ") || trimmed == "}") {
+            write_clusters(&mut output, &clusters);
+            written = true;
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Writes the clusters with the lines of their nodes, indented one more level.
+fn write_clusters(output: &mut String, clusters: &[(&str, Vec<&str>)]) {
+    for (index, (function, lines)) in clusters.iter().enumerate() {
+        let function = escape_dot(function);
+        let color = CLUSTER_COLORS[index % CLUSTER_COLORS.len()];
+        writeln!(output, "    subgraph \"cluster_{function}\" {{")
+            .and_then(|()| writeln!(output, "        style=\"filled\";"))
+            .and_then(|()| writeln!(output, "        fillcolor=\"{color}\";"))
+            .and_then(|()| writeln!(output, "        label=\"{function}\";"))
+            .expect("BUG: Writing to a string should not fail");
+        for line in lines {
+            writeln!(output, "    {line}").expect("BUG: Writing to a string should not fail");
+        }
+        output.push_str("    }\n");
+    }
+}
+
+#[cfg(test)]
+mod dot_style_tests {
+    use super::*;
+    use crate::data_structures::net_data::{ArcData, PlaceData, TransitionData};
+
+    #[test]
+    fn functions_of_nodes_follow_the_basic_blocks() {
+        let net = NetData {
+            places: vec![
+                PlaceData {
+                    label: "main_BB1".to_string(),
+                    marking: 1,
+                    capacity: None,
+                    span: None,
+                },
+                PlaceData {
+                    label: "MUTEX_0".to_string(),
+                    marking: 1,
+                    capacity: Some(1),
+                    span: None,
+                },
+            ],
+            transitions: vec![TransitionData {
+                label: "std_sync_Mutex_T_lock_0_CALL".to_string(),
+                span: None,
+            }],
+            arcs: vec![
+                ArcData {
+                    place: "MUTEX_0".to_string(),
+                    transition: "std_sync_Mutex_T_lock_0_CALL".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+                ArcData {
+                    place: "main_BB1".to_string(),
+                    transition: "std_sync_Mutex_T_lock_0_CALL".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+            ],
+        };
+
+        assert_eq!(
+            functions_of_nodes(&net),
+            BTreeMap::from([
+                ("main_BB1", "main"),
+                ("std_sync_Mutex_T_lock_0_CALL", "main")
+            ])
+        );
+    }
+
+    #[test]
+    fn add_clusters_to_dot_groups_the_nodes_before_the_arcs() {
+        let dot = "\
+digraph petrinet {
+    MUTEX_0 [shape=\"circle\" xlabel=\"MUTEX_0\" label=\"•\"];
+    main_BB1 [shape=\"circle\" xlabel=\"main_BB1\" label=\"•\"];
+    main_LOCK_0 [shape=\"box\" xlabel=\"\" label=\"main_LOCK_0\"];
+    main_BB1 -> main_LOCK_0;
+}
+";
+        let functions = BTreeMap::from([("main_BB1", "main"), ("main_LOCK_0", "main")]);
+
+        assert_eq!(
+            add_clusters_to_dot(dot, &functions),
+            "\
+digraph petrinet {
+    MUTEX_0 [shape=\"circle\" xlabel=\"MUTEX_0\" label=\"•\"];
+    subgraph \"cluster_main\" {
+        style=\"filled\";
+        fillcolor=\"lightblue\";
+        label=\"main\";
+        main_BB1 [shape=\"circle\" xlabel=\"main_BB1\" label=\"•\"];
+        main_LOCK_0 [shape=\"box\" xlabel=\"\" label=\"main_LOCK_0\"];
+    }
+    main_BB1 -> main_LOCK_0;
+}
+"
+        );
+    }
+
+    #[test]
+    fn set_option_parses_the_keys_and_values() {
+        let mut style = DotStyle::default();
+        style.set_option("rankdir=LR").unwrap();
+        style.set_option("highlight=true").unwrap();
+        style.set_option("mutex-color=#ffcc00").unwrap();
+
+        assert_eq!(style.rankdir.as_deref(), Some("LR"));
+        assert_eq!(style.panic_color.as_deref(), Some(DEFAULT_PANIC_COLOR));
+        assert_eq!(style.mutex_color.as_deref(), Some("#ffcc00"));
+        assert!(style.sync_color.is_none());
+        assert!(style.set_option("rankdir=diagonal").is_err());
+        assert!(style.set_option("highlight=yes").is_err());
+        assert!(style.set_option("shape=box").is_err());
+        assert!(style.set_option("rankdir").is_err());
+    }
+
+    #[test]
+    fn node_attributes_highlight_the_panic_the_mutexes_and_the_sync_transitions() {
+        let mut style = DotStyle::default();
+        style.set_option("highlight=true").unwrap();
+        style.set_option("sync-transitions=true").unwrap();
+        let place = |label: &str| PlaceData {
+            label: label.to_string(),
+            marking: 0,
+            capacity: None,
+            span: None,
+        };
+        let net = NetData {
+            places: vec![place("PROGRAM_PANIC"), place("MUTEX_0"), place("main_BB1")],
+            transitions: vec![],
+            arcs: vec![
+                ArcData {
+                    place: "MUTEX_0".to_string(),
+                    transition: "main_LOCK_0".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+                ArcData {
+                    place: "main_BB1".to_string(),
+                    transition: "main_GOTO_1".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+            ],
+        };
+
+        assert_eq!(
+            style.node_attributes(&net),
+            BTreeMap::from([
+                (
+                    "PROGRAM_PANIC",
+                    "style=\"filled\" fillcolor=\"lightcoral\"".to_string()
+                ),
+                (
+                    "MUTEX_0",
+                    "style=\"filled\" fillcolor=\"orange\"".to_string()
+                ),
+                ("main_LOCK_0", "color=\"blue\" penwidth=\"2\"".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn add_rankdir_and_attributes_to_dot() {
+        let dot = "\
+digraph petrinet {
+    MUTEX_0 [shape=\"circle\" xlabel=\"MUTEX_0\" label=\"•\"];
+    main_LOCK_0 [shape=\"box\" xlabel=\"\" label=\"main_LOCK_0\"];
+    MUTEX_0 -> main_LOCK_0;
+}
+";
+        let attributes = BTreeMap::from([("MUTEX_0", "fillcolor=\"orange\"".to_string())]);
+
+        assert_eq!(
+            add_rankdir_to_dot(&add_node_attributes_to_dot(dot, &attributes), "LR"),
+            "\
+digraph petrinet {
+    rankdir=\"LR\";
+    MUTEX_0 [shape=\"circle\" xlabel=\"MUTEX_0\" label=\"•\" fillcolor=\"orange\"];
+    main_LOCK_0 [shape=\"box\" xlabel=\"\" label=\"main_LOCK_0\"];
+    MUTEX_0 -> main_LOCK_0;
+}
+"
+        );
+    }
+}
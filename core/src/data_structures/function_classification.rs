@@ -0,0 +1,178 @@
+//! Module that implements the cache for the classification of functions
+//! that are not defined in the crate being translated, e.g. the functions of the standard library.
+//!
+//! Every function call is classified to select the handler in the translator:
+//! a `panic!`-related function, a supported synchronization function, a foreign function
+//! or a function whose MIR representation is translated.
+//! The classification requires the fully qualified name of the function, which the compiler
+//! resolves from the definition path on every call. Programs with many calls to the standard library
+//! spend a noticeable share of the translation doing this resolution over and over again.
+//!
+//! The functions of other crates do not change as long as the toolchain stays the same,
+//! so their classification is stored in a `ClassificationCache` and persisted between runs.
+//! The cache is identified by a toolchain string. A cache for a different toolchain is discarded.
+//! The functions are identified by a key that is stable across compiler sessions,
+//! e.g. the hash of the definition path.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The handler selected by the translator for a call to the function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionClass {
+    /// A function that starts a panic, i.e. an unwind of the stack.
+    Panic,
+    /// One of the supported synchronization or multithreading functions.
+    Sync,
+    /// A function that is abstracted as a foreign function call.
+    Foreign,
+    /// A function whose MIR representation is translated.
+    Translated,
+}
+
+/// The name and the class of a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassifiedFunction {
+    /// The fully qualified name of the function, e.g. `std::sync::Mutex::<T>::lock`.
+    pub name: String,
+    /// The class of the function.
+    pub class: FunctionClass,
+}
+
+/// The classification of the functions for a specific toolchain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassificationCache {
+    /// The toolchain for which the classification is valid.
+    toolchain: String,
+    /// The classified functions indexed by their stable key.
+    functions: BTreeMap<String, ClassifiedFunction>,
+}
+
+impl ClassificationCache {
+    /// Creates a new empty cache for the given toolchain.
+    #[must_use]
+    pub fn new(toolchain: &str) -> Self {
+        Self {
+            toolchain: toolchain.to_string(),
+            functions: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the toolchain for which the classification is valid.
+    #[must_use]
+    pub fn toolchain(&self) -> &str {
+        &self.toolchain
+    }
+
+    /// Returns the classification of the function with the given key, if it is cached.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&ClassifiedFunction> {
+        self.functions.get(key)
+    }
+
+    /// Adds the classification of the function with the given key to the cache.
+    pub fn insert(&mut self, key: String, function: ClassifiedFunction) {
+        self.functions.insert(key, function);
+    }
+
+    /// Returns the number of functions in the cache.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Checks whether the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Serializes the cache to a JSON string.
+    ///
+    /// # Panics
+    ///
+    /// If the cache cannot be serialized, then the function panics.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("BUG: The classification cache should be serializable")
+    }
+
+    /// Deserializes a cache from a JSON string.
+    /// If the cache belongs to another toolchain, an empty cache for the given toolchain is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the string is not a valid JSON description of a cache, then an error is returned.
+    pub fn from_json(json: &str, toolchain: &str) -> Result<Self, String> {
+        let cache: Self = serde_json::from_str(json)
+            .map_err(|err| format!("Invalid JSON description of a classification cache: {err}"))?;
+        if cache.toolchain == toolchain {
+            Ok(cache)
+        } else {
+            Ok(Self::new(toolchain))
+        }
+    }
+}
+
+#[cfg(test)]
+mod function_classification_tests {
+    use super::*;
+
+    const TOOLCHAIN: &str = "rustc 1.85.0-nightly (d4025ee45 2024-12-12)";
+
+    fn lock() -> ClassifiedFunction {
+        ClassifiedFunction {
+            name: "std::sync::Mutex::<T>::lock".to_string(),
+            class: FunctionClass::Sync,
+        }
+    }
+
+    #[test]
+    fn cache_new_is_empty() {
+        let cache = ClassificationCache::new(TOOLCHAIN);
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.toolchain(), TOOLCHAIN);
+    }
+
+    #[test]
+    fn cache_get_returns_inserted_function() {
+        let mut cache = ClassificationCache::new(TOOLCHAIN);
+        cache.insert("0123".to_string(), lock());
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("0123"), Some(&lock()));
+        assert_eq!(cache.get("4567"), None);
+    }
+
+    #[test]
+    fn cache_json_round_trip_is_lossless() {
+        let mut cache = ClassificationCache::new(TOOLCHAIN);
+        cache.insert("0123".to_string(), lock());
+
+        let json = cache.to_json();
+
+        assert_eq!(ClassificationCache::from_json(&json, TOOLCHAIN), Ok(cache));
+    }
+
+    #[test]
+    fn cache_from_json_for_other_toolchain_is_empty() {
+        let mut cache = ClassificationCache::new(TOOLCHAIN);
+        cache.insert("0123".to_string(), lock());
+
+        let json = cache.to_json();
+        let other_toolchain = "rustc 1.86.0-nightly (a4cb3c831 2025-01-29)";
+        let cache = ClassificationCache::from_json(&json, other_toolchain).unwrap();
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.toolchain(), other_toolchain);
+    }
+
+    #[test]
+    fn cache_from_invalid_json_returns_error() {
+        let result = ClassificationCache::from_json("{\"functions\": 1}", TOOLCHAIN);
+
+        assert!(result.is_err());
+    }
+}
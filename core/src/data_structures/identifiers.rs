@@ -0,0 +1,350 @@
+//! Module that converts the labels of the places and transitions to legal identifiers in each output format.
+//!
+//! Every format has its own rules for identifiers:
+//! - `LoLA` forbids the characters `,;:(){}`, whitespace and quotes in names, as well as its keywords.
+//! - DOT accepts unquoted identifiers made of letters, digits and underscores not starting with a digit.
+//!   Any other identifier must be quoted and escaped.
+//! - PNML uses the labels as XML identifiers, which must be `NCName`s: Letters, digits, `.`, `-` and `_`,
+//!   starting with a letter or an underscore.
+//!
+//! Instead of sanitizing the labels once for the strictest combination of rules,
+//! each exporter legalizes the labels for its own format when the net is written.
+//! The labels of the translation are already legal in most cases, so they are left unchanged.
+//!
+//! An `IdentifierTable` keeps the mapping in both directions, e.g. to translate the labels in a formula
+//! to the identifiers of the `LoLA` file and the witness path found by `LoLA` back to the labels.
+//! Two labels that legalize to the same identifier are told apart by appending the suffix `__N`
+//! to the label before legalizing it, like in the `LabelRegistry`.
+//!
+//! The table may also start from other names than the labels, e.g. the short names given by a `NamingScheme`,
+//! see `naming::scheme`. The names are legalized and told apart in the same way.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The keywords of `LoLA`, which cannot be used as names of places or transitions.
+const LOLA_KEYWORDS: [&str; 22] = [
+    "PLACE",
+    "MARKING",
+    "TRANSITION",
+    "CONSUME",
+    "PRODUCE",
+    "SAFE",
+    "STRONG",
+    "WEAK",
+    "FAIR",
+    "AND",
+    "OR",
+    "NOT",
+    "TRUE",
+    "FALSE",
+    "DEADLOCK",
+    "INITIAL",
+    "FIREABLE",
+    "EF",
+    "AG",
+    "AF",
+    "EG",
+    "EX",
+];
+
+/// The keywords of DOT, which are case-insensitive and must be quoted when used as identifiers.
+const DOT_KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+
+/// An output format with its own rules for the identifiers of the nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdentifierFormat {
+    Lola,
+    Dot,
+    Pnml,
+}
+
+impl IdentifierFormat {
+    /// Converts the label to a legal identifier in the format.
+    /// Labels that are already legal are returned unchanged.
+    #[must_use]
+    pub fn legalize(self, label: &str) -> String {
+        match self {
+            Self::Lola => legalize_lola(label),
+            Self::Dot => legalize_dot(label),
+            Self::Pnml => legalize_pnml(label),
+        }
+    }
+}
+
+/// The mapping between the labels of the net and their identifiers in an output format.
+pub struct IdentifierTable {
+    /// The identifier of every label.
+    identifiers: HashMap<String, String>,
+    /// The label of every identifier.
+    labels: HashMap<String, String>,
+}
+
+impl IdentifierTable {
+    /// Creates the table for the labels in the given format.
+    /// The labels that are already legal keep their identifier.
+    /// The collisions after legalizing the other labels are resolved in the order of the labels.
+    ///
+    /// # Panics
+    ///
+    /// If every suffix is already used, which is impossible, then the function panics.
+    pub fn new<'a>(format: IdentifierFormat, labels: impl IntoIterator<Item = &'a str>) -> Self {
+        Self::with_names(
+            format,
+            labels.into_iter().map(|label| (label, label.to_string())),
+        )
+    }
+
+    /// Creates the table for the labels in the given format, starting from the given name of every label
+    /// instead of the label itself. The names that are legal and unique are used as identifiers.
+    /// The collisions after legalizing the other names are resolved in the order of the labels.
+    ///
+    /// # Panics
+    ///
+    /// If every suffix is already used, which is impossible, then the function panics.
+    pub fn with_names<'a>(
+        format: IdentifierFormat,
+        names: impl IntoIterator<Item = (&'a str, String)>,
+    ) -> Self {
+        let mut identifiers: HashMap<String, String> = HashMap::new();
+        let mut used: HashSet<String> = HashSet::new();
+        let mut illegal: Vec<(&str, String)> = Vec::new();
+        for (label, name) in names {
+            if format.legalize(&name) == name && !used.contains(&name) {
+                used.insert(name.clone());
+                identifiers.insert(label.to_string(), name);
+            } else {
+                illegal.push((label, name));
+            }
+        }
+        for (label, name) in illegal {
+            let mut identifier = format.legalize(&name);
+            if !used.insert(identifier.clone()) {
+                // The identifier itself is used, so at most `len - 1` of the first `len` suffixes are used.
+                identifier = (1..=used.len())
+                    .map(|number| format.legalize(&format!("{name}__{number}")))
+                    .find(|candidate| !used.contains(candidate))
+                    .expect("BUG: There should be an unused suffix for every identifier");
+                used.insert(identifier.clone());
+            }
+            identifiers.insert(label.to_string(), identifier);
+        }
+        let labels = identifiers
+            .iter()
+            .map(|(label, identifier)| (identifier.clone(), label.clone()))
+            .collect();
+        Self {
+            identifiers,
+            labels,
+        }
+    }
+
+    /// Checks whether the label is in the table.
+    #[must_use]
+    pub fn contains(&self, label: &str) -> bool {
+        self.identifiers.contains_key(label)
+    }
+
+    /// Returns the identifier of the label.
+    /// Labels that are not in the table are returned unchanged.
+    #[must_use]
+    pub fn identifier<'a>(&'a self, label: &'a str) -> &'a str {
+        self.identifiers.get(label).map_or(label, String::as_str)
+    }
+
+    /// Returns the label of the identifier.
+    /// Identifiers that are not in the table are returned unchanged.
+    #[must_use]
+    pub fn label<'a>(&'a self, identifier: &'a str) -> &'a str {
+        self.labels
+            .get(identifier)
+            .map_or(identifier, String::as_str)
+    }
+
+    /// Converts the labels to their identifiers.
+    #[must_use]
+    pub fn identifiers_of(&self, labels: &[String]) -> Vec<String> {
+        labels
+            .iter()
+            .map(|label| self.identifier(label).to_string())
+            .collect()
+    }
+
+    /// Converts the identifiers back to their labels.
+    #[must_use]
+    pub fn labels_of(&self, identifiers: &[String]) -> Vec<String> {
+        identifiers
+            .iter()
+            .map(|identifier| self.label(identifier).to_string())
+            .collect()
+    }
+
+    /// Returns the representation of the table in JSON, an object with the label of every identifier,
+    /// sorted by identifier.
+    ///
+    /// # Panics
+    ///
+    /// If the table cannot be serialized, then the function panics. This should never happen.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let labels: BTreeMap<&str, &str> = self
+            .labels
+            .iter()
+            .map(|(identifier, label)| (identifier.as_str(), label.as_str()))
+            .collect();
+        serde_json::to_string_pretty(&labels)
+            .expect("BUG: The identifier table should be serializable to JSON")
+    }
+
+    /// Returns the pairs of label and identifier where the identifier differs from the label.
+    pub fn renamed(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.identifiers
+            .iter()
+            .filter(|(label, identifier)| label != identifier)
+            .map(|(label, identifier)| (label.as_str(), identifier.as_str()))
+    }
+}
+
+/// Replaces the characters forbidden in the names of `LoLA` with underscores.
+/// Keywords get an underscore appended.
+fn legalize_lola(label: &str) -> String {
+    let mut identifier: String = label
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || matches!(c, ',' | ';' | ':' | '(' | ')' | '{' | '}' | '"') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if identifier.is_empty() || LOLA_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Quotes the label if it is not a legal unquoted identifier in DOT.
+/// Quotes and backslashes inside the quoted identifier are escaped.
+fn legalize_dot(label: &str) -> String {
+    let is_unquoted_identifier = label
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !DOT_KEYWORDS
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(label));
+    if is_unquoted_identifier {
+        label.to_string()
+    } else {
+        format!("\"{}\"", escape_dot(label))
+    }
+}
+
+/// Escapes the quotes and backslashes of a string for a quoted string in DOT.
+#[must_use]
+pub fn escape_dot(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Replaces the characters that are not allowed in an XML `NCName` with underscores.
+/// Labels that do not start with a letter or an underscore get an underscore prepended.
+fn legalize_pnml(label: &str) -> String {
+    let mut identifier: String = label
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !identifier
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+    {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+#[cfg(test)]
+mod identifiers_tests {
+    use super::*;
+
+    #[test]
+    fn legal_labels_are_unchanged_in_every_format() {
+        for format in [
+            IdentifierFormat::Lola,
+            IdentifierFormat::Dot,
+            IdentifierFormat::Pnml,
+        ] {
+            assert_eq!(format.legalize("main_BB1"), "main_BB1");
+            assert_eq!(format.legalize("MUTEX_0_POISONED"), "MUTEX_0_POISONED");
+        }
+    }
+
+    #[test]
+    fn each_format_applies_its_own_rules() {
+        let label = "core_fmt_rt_Argument__'___new_display_0_CALL";
+
+        assert_eq!(IdentifierFormat::Lola.legalize(label), label);
+        assert_eq!(
+            IdentifierFormat::Dot.legalize(label),
+            format!("\"{label}\"")
+        );
+        assert_eq!(
+            IdentifierFormat::Pnml.legalize(label),
+            "core_fmt_rt_Argument______new_display_0_CALL"
+        );
+    }
+
+    #[test]
+    fn keywords_and_forbidden_characters_are_legalized() {
+        assert_eq!(IdentifierFormat::Lola.legalize("foo(a, b)"), "foo_a__b_");
+        assert_eq!(IdentifierFormat::Lola.legalize("SAFE"), "SAFE_");
+        assert_eq!(IdentifierFormat::Dot.legalize("Node"), "\"Node\"");
+        assert_eq!(
+            IdentifierFormat::Dot.legalize("say \"hi\""),
+            "\"say \\\"hi\\\"\""
+        );
+        assert_eq!(IdentifierFormat::Pnml.legalize("0_CALL"), "_0_CALL");
+    }
+
+    #[test]
+    fn table_keeps_legal_labels_and_resolves_collisions() {
+        let table = IdentifierTable::new(IdentifierFormat::Pnml, ["a'b", "a_b", "a#b"]);
+
+        assert_eq!(table.identifier("a_b"), "a_b");
+        assert_eq!(table.identifier("a'b"), "a_b__1");
+        assert_eq!(table.identifier("a#b"), "a_b__2");
+        assert_eq!(table.label("a_b__1"), "a'b");
+        assert_eq!(
+            table.labels_of(&["a_b".to_string(), "a_b__2".to_string()]),
+            ["a_b", "a#b"]
+        );
+        assert_eq!(table.renamed().count(), 2);
+    }
+
+    #[test]
+    fn table_with_names_resolves_the_collisions_of_the_names() {
+        let table = IdentifierTable::with_names(
+            IdentifierFormat::Lola,
+            [
+                ("main_BB1", "P0".to_string()),
+                ("main_BB2", "P0".to_string()),
+                ("main_BB3", "P0__1".to_string()),
+            ],
+        );
+
+        assert_eq!(table.identifier("main_BB1"), "P0");
+        assert_eq!(table.identifier("main_BB3"), "P0__1");
+        assert_eq!(table.identifier("main_BB2"), "P0__2");
+        assert_eq!(table.label("P0__2"), "main_BB2");
+        assert!(table
+            .to_json()
+            .starts_with("{\n  \"P0\": \"main_BB1\",\n  \"P0__1\": \"main_BB3\""));
+    }
+}
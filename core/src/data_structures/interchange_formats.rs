@@ -0,0 +1,555 @@
+//! Module that implements the export of the `NetData` to interchange formats
+//! used by academic tools for the synthesis and structural analysis of Petri nets.
+//!
+//! - The format of the APT toolkit, see <https://github.com/CvO-Theory/apt>.
+//!   Weighted arcs are written with their multiplicity, e.g. `{4*RWLOCK_0}`.
+//! - The `.g` format of Petrify, see <https://www.cs.upc.edu/~jordicf/petrify/>.
+//!   The transitions are declared as dummy transitions since they do not model signals.
+//!   The format does not support weighted arcs, so nets containing them cannot be exported.
+//! - The `.net` format of the TINA toolbox, see <https://projects.laas.fr/tina/>.
+//!   Weighted arcs are written with their multiplicity, e.g. `RWLOCK_0*4`.
+//!   The format has no capacities, which are not needed since the places are bounded by construction.
+//! - The `.net` and `.def` formats of `GreatSPN`, see <https://github.com/greatspn/SOURCES>.
+//!   Every transition is exponential with rate 1, so that the stochastic and performance
+//!   analyses of `GreatSPN` can be run on the net right away. The rates can be adjusted in the editor.
+//!   The nodes are arranged in two columns, places on the left and transitions on the right.
+//!
+//! The places without arcs are not written in the `.g` format, because Petrify
+//! only knows the places that appear in the graph. They do not affect the behavior of the net.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Error, ErrorKind, Write};
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+
+/// The places connected to a transition with the weight of the arcs,
+/// in the order in which the arcs were added.
+#[derive(Default)]
+struct TransitionArcs<'a> {
+    preset: Vec<(&'a str, usize)>,
+    postset: Vec<(&'a str, usize)>,
+}
+
+/// Groups the arcs of the net by transition.
+/// Repeated arcs between the same place and transition are merged into one arc with the sum of their weights.
+fn group_arcs(net: &NetData) -> Vec<TransitionArcs<'_>> {
+    let indices: HashMap<&str, usize> = net
+        .transitions
+        .iter()
+        .enumerate()
+        .map(|(index, transition)| (transition.label.as_str(), index))
+        .collect();
+    let mut grouped: Vec<TransitionArcs> = net
+        .transitions
+        .iter()
+        .map(|_| TransitionArcs::default())
+        .collect();
+
+    for arc in &net.arcs {
+        let index = indices[arc.transition.as_str()];
+        let places = match arc.direction {
+            ArcDirection::PlaceToTransition => &mut grouped[index].preset,
+            ArcDirection::TransitionToPlace => &mut grouped[index].postset,
+        };
+        match places.iter_mut().find(|(place, _)| *place == arc.place) {
+            Some((_, multiplicity)) => *multiplicity += arc.weight,
+            None => places.push((arc.place.as_str(), arc.weight)),
+        }
+    }
+    grouped
+}
+
+/// Formats a place with its multiplicity in the APT format, e.g. `PLACE` or `4*PLACE`.
+fn apt_multiset_element(place: &str, multiplicity: usize) -> String {
+    if multiplicity == 1 {
+        place.to_string()
+    } else {
+        format!("{multiplicity}*{place}")
+    }
+}
+
+/// Writes the net in the format of the APT toolkit to the given writer.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_apt<T: Write>(net: &NetData, writer: &mut T) -> Result<(), Error> {
+    writeln!(writer, "// Petri net generated by cargo-check-deadlock")?;
+    writeln!(writer, ".type PN")?;
+
+    writeln!(writer, "\n.places")?;
+    for place in &net.places {
+        writeln!(writer, "{}", place.label)?;
+    }
+
+    writeln!(writer, "\n.transitions")?;
+    for transition in &net.transitions {
+        writeln!(writer, "{}", transition.label)?;
+    }
+
+    writeln!(writer, "\n.flows")?;
+    for (transition, arcs) in net.transitions.iter().zip(group_arcs(net)) {
+        let format_set = |places: &[(&str, usize)]| {
+            places
+                .iter()
+                .map(|(place, multiplicity)| apt_multiset_element(place, *multiplicity))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        writeln!(
+            writer,
+            "{}: {{{}}} -> {{{}}}",
+            transition.label,
+            format_set(&arcs.preset),
+            format_set(&arcs.postset)
+        )?;
+    }
+
+    let initial_marking: Vec<String> = net
+        .places
+        .iter()
+        .filter(|place| place.marking > 0)
+        .map(|place| apt_multiset_element(&place.label, place.marking))
+        .collect();
+    writeln!(
+        writer,
+        "\n.initial_marking {{{}}}",
+        initial_marking.join(", ")
+    )
+}
+
+/// Writes the net in the `.g` format of Petrify to the given writer.
+///
+/// # Errors
+///
+/// If the net contains an arc with a multiplicity greater than one, then an error is returned.
+/// If writing to the writer fails, then an error is returned.
+pub fn write_petrify<T: Write>(net: &NetData, writer: &mut T) -> Result<(), Error> {
+    let grouped = group_arcs(net);
+    let mut outputs: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (transition, arcs) in net.transitions.iter().zip(&grouped) {
+        for (place, multiplicity) in arcs.preset.iter().chain(&arcs.postset) {
+            if *multiplicity > 1 {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "The Petrify format does not support weighted arcs, but the transition {} is connected to the place {place} by an arc of weight {multiplicity}",
+                        transition.label
+                    ),
+                ));
+            }
+        }
+        for (place, _) in &arcs.preset {
+            outputs.entry(place).or_default().push(&transition.label);
+        }
+    }
+
+    writeln!(writer, "# Petri net generated by cargo-check-deadlock")?;
+    writeln!(writer, ".model net")?;
+    let transitions: Vec<&str> = net
+        .transitions
+        .iter()
+        .map(|transition| transition.label.as_str())
+        .collect();
+    writeln!(writer, ".dummy {}", transitions.join(" "))?;
+
+    writeln!(writer, ".graph")?;
+    let mut connected_places: HashSet<&str> = HashSet::new();
+    for place in &net.places {
+        if let Some(transitions) = outputs.get(place.label.as_str()) {
+            writeln!(writer, "{} {}", place.label, transitions.join(" "))?;
+        }
+    }
+    for (transition, arcs) in net.transitions.iter().zip(&grouped) {
+        if arcs.postset.is_empty() {
+            continue;
+        }
+        let places: Vec<&str> = arcs.postset.iter().map(|(place, _)| *place).collect();
+        writeln!(writer, "{} {}", transition.label, places.join(" "))?;
+        connected_places.extend(places);
+    }
+    connected_places.extend(outputs.keys());
+
+    let connected = |label: &str| connected_places.contains(label);
+    let capacities: Vec<String> = net
+        .places
+        .iter()
+        .filter(|place| connected(&place.label))
+        .filter_map(|place| Some(format!("{}={}", place.label, place.capacity?)))
+        .collect();
+    if !capacities.is_empty() {
+        writeln!(writer, ".capacity {}", capacities.join(" "))?;
+    }
+
+    let initial_marking: Vec<String> = net
+        .places
+        .iter()
+        .filter(|place| place.marking > 0 && connected(&place.label))
+        .map(|place| {
+            if place.marking == 1 {
+                place.label.clone()
+            } else {
+                format!("{}={}", place.label, place.marking)
+            }
+        })
+        .collect();
+    writeln!(writer, ".marking {{ {} }}", initial_marking.join(" "))?;
+    writeln!(writer, ".end")
+}
+
+/// Formats a label as a name in the TINA format.
+/// Names with characters other than letters, digits, `_` and `'` are enclosed in braces,
+/// escaping the braces and backslashes inside.
+fn tina_name(label: &str) -> String {
+    if !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'')
+    {
+        return label.to_string();
+    }
+    let mut name = String::from("{");
+    for c in label.chars() {
+        if matches!(c, '{' | '}' | '\\') {
+            name.push('\\');
+        }
+        name.push(c);
+    }
+    name.push('}');
+    name
+}
+
+/// Formats a place with its multiplicity in the TINA format, e.g. `PLACE` or `PLACE*4`.
+fn tina_arc(place: &str, multiplicity: usize) -> String {
+    if multiplicity == 1 {
+        tina_name(place)
+    } else {
+        format!("{}*{multiplicity}", tina_name(place))
+    }
+}
+
+/// Writes the net in the `.net` format of the TINA toolbox to the given writer.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_tina<T: Write>(net: &NetData, writer: &mut T) -> Result<(), Error> {
+    writeln!(writer, "# Petri net generated by cargo-check-deadlock")?;
+    writeln!(writer, "net net")?;
+
+    for place in &net.places {
+        if place.marking > 0 {
+            writeln!(writer, "pl {} ({})", tina_name(&place.label), place.marking)?;
+        } else {
+            writeln!(writer, "pl {}", tina_name(&place.label))?;
+        }
+    }
+
+    for (transition, arcs) in net.transitions.iter().zip(group_arcs(net)) {
+        let mut line = format!("tr {}", tina_name(&transition.label));
+        for (place, multiplicity) in &arcs.preset {
+            line.push(' ');
+            line.push_str(&tina_arc(place, *multiplicity));
+        }
+        line.push_str(" ->");
+        for (place, multiplicity) in &arcs.postset {
+            line.push(' ');
+            line.push_str(&tina_arc(place, *multiplicity));
+        }
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Formats a label as a name in the `GreatSPN` format, which does not allow spaces or special characters.
+fn greatspn_name(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Writes the net in the `.net` format of `GreatSPN` to the given writer.
+/// `GreatSPN` reads it together with a `.def` file with the same name, see `write_greatspn_def`.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_greatspn<T: Write>(net: &NetData, writer: &mut T) -> Result<(), Error> {
+    // The places are referenced by their position in the file, starting at one.
+    let place_numbers: HashMap<&str, usize> = net
+        .places
+        .iter()
+        .enumerate()
+        .map(|(index, place)| (place.label.as_str(), index + 1))
+        .collect();
+    let write_arcs = |writer: &mut T, arcs: &[(&str, usize)]| -> Result<(), Error> {
+        for (place, multiplicity) in arcs {
+            writeln!(writer, "  {multiplicity} {} 0 0", place_numbers[place])?;
+        }
+        Ok(())
+    };
+
+    writeln!(writer, "|0|")?;
+    writeln!(writer, "|")?;
+    writeln!(
+        writer,
+        "f 0 {} 0 {} 0 0 0",
+        net.places.len(),
+        net.transitions.len()
+    )?;
+    for (row, place) in net.places.iter().enumerate() {
+        let y = row + 1;
+        writeln!(
+            writer,
+            "{} {} 1.0 {y}.0 1.5 {y}.0 0",
+            greatspn_name(&place.label),
+            place.marking
+        )?;
+    }
+    for (row, (transition, arcs)) in net.transitions.iter().zip(group_arcs(net)).enumerate() {
+        let y = row + 1;
+        writeln!(
+            writer,
+            "{} 1.000000e+00 1 0 {} 0 3.0 {y}.0 3.5 {y}.0 3.5 {y}.0 0",
+            greatspn_name(&transition.label),
+            arcs.preset.len()
+        )?;
+        write_arcs(writer, &arcs.preset)?;
+        writeln!(writer, "{}", arcs.postset.len())?;
+        write_arcs(writer, &arcs.postset)?;
+        // No inhibitor arcs.
+        writeln!(writer, "0")?;
+    }
+    Ok(())
+}
+
+/// Writes the `.def` file of `GreatSPN` that accompanies the `.net` file written by `write_greatspn`.
+/// It declares no performance measures, which can be added in the editor.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_greatspn_def<T: Write>(writer: &mut T) -> Result<(), Error> {
+    writeln!(writer, "|256")?;
+    writeln!(writer, "%")?;
+    writeln!(writer, "|")
+}
+
+#[cfg(test)]
+mod interchange_formats_tests {
+    use super::*;
+    use crate::data_structures::net_builder::{arc, place, NetBuilder};
+
+    /// A lock followed by an unlock of a mutex.
+    fn mutex_net() -> NetData {
+        NetBuilder::new()
+            .place("PROGRAM_START", 1)
+            .place("main_BB1", 0)
+            .place("PROGRAM_END", 0)
+            .bounded_place("MUTEX_0", 1, 1)
+            .transition("lock")
+            .transition("unlock")
+            .input("PROGRAM_START", "lock")
+            .input("MUTEX_0", "lock")
+            .output("lock", "main_BB1")
+            .input("main_BB1", "unlock")
+            .output("unlock", "MUTEX_0")
+            .output("unlock", "PROGRAM_END")
+            .build()
+    }
+
+    fn to_string<F>(write: F) -> Result<String, Error>
+    where
+        F: FnOnce(&mut Vec<u8>) -> Result<(), Error>,
+    {
+        let mut output = Vec::new();
+        write(&mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn write_apt_lists_flows_and_initial_marking() {
+        let net = mutex_net();
+        let apt = to_string(|output| write_apt(&net, output)).unwrap();
+
+        assert_eq!(
+            apt,
+            "\
+// Petri net generated by cargo-check-deadlock
+.type PN
+
+.places
+PROGRAM_START
+main_BB1
+PROGRAM_END
+MUTEX_0
+
+.transitions
+lock
+unlock
+
+.flows
+lock: {PROGRAM_START, MUTEX_0} -> {main_BB1}
+unlock: {main_BB1} -> {MUTEX_0, PROGRAM_END}
+
+.initial_marking {PROGRAM_START, MUTEX_0}
+"
+        );
+    }
+
+    #[test]
+    fn write_apt_merges_repeated_arcs() {
+        let mut net = NetBuilder::new()
+            .bounded_place("RWLOCK_0", 4, 4)
+            .transition("write")
+            .build();
+        net.arcs = vec![arc("RWLOCK_0", "write", ArcDirection::PlaceToTransition); 4];
+        let apt = to_string(|output| write_apt(&net, output)).unwrap();
+
+        assert!(apt.contains("write: {4*RWLOCK_0} -> {}\n"));
+        assert!(apt.ends_with(".initial_marking {4*RWLOCK_0}\n"));
+    }
+
+    #[test]
+    fn write_petrify_lists_graph_capacity_and_marking() {
+        let net = mutex_net();
+        let petrify = to_string(|output| write_petrify(&net, output)).unwrap();
+
+        assert_eq!(
+            petrify,
+            "\
+# Petri net generated by cargo-check-deadlock
+.model net
+.dummy lock unlock
+.graph
+PROGRAM_START lock
+main_BB1 unlock
+MUTEX_0 lock
+lock main_BB1
+unlock MUTEX_0 PROGRAM_END
+.capacity MUTEX_0=1
+.marking { PROGRAM_START MUTEX_0 }
+.end
+"
+        );
+    }
+
+    #[test]
+    fn write_petrify_rejects_weighted_arcs() {
+        let net = NetBuilder::new()
+            .bounded_place("RWLOCK_0", 4, 4)
+            .transition("write")
+            .weighted_arc("RWLOCK_0", "write", ArcDirection::PlaceToTransition, 4)
+            .build();
+        let err = to_string(|output| write_petrify(&net, output)).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("weighted arcs"));
+    }
+
+    #[test]
+    fn write_petrify_omits_places_without_arcs() {
+        let mut net = mutex_net();
+        net.places.push(place("CHANNEL_0_BUFFER", 2));
+        let petrify = to_string(|output| write_petrify(&net, output)).unwrap();
+
+        assert!(!petrify.contains("CHANNEL_0_BUFFER"));
+    }
+
+    #[test]
+    fn write_tina_lists_places_and_transitions() {
+        let net = mutex_net();
+        let tina = to_string(|output| write_tina(&net, output)).unwrap();
+
+        assert_eq!(
+            tina,
+            "\
+# Petri net generated by cargo-check-deadlock
+net net
+pl PROGRAM_START (1)
+pl main_BB1
+pl PROGRAM_END
+pl MUTEX_0 (1)
+tr lock PROGRAM_START MUTEX_0 -> main_BB1
+tr unlock main_BB1 -> MUTEX_0 PROGRAM_END
+"
+        );
+    }
+
+    #[test]
+    fn write_tina_merges_repeated_arcs() {
+        let mut net = NetBuilder::new()
+            .bounded_place("RWLOCK_0", 4, 4)
+            .transition("write")
+            .build();
+        net.arcs = vec![arc("RWLOCK_0", "write", ArcDirection::PlaceToTransition); 4];
+        let tina = to_string(|output| write_tina(&net, output)).unwrap();
+
+        assert!(tina.contains("pl RWLOCK_0 (4)\n"));
+        assert!(tina.ends_with("tr write RWLOCK_0*4 ->\n"));
+    }
+
+    #[test]
+    fn tina_name_quotes_special_characters() {
+        assert_eq!(tina_name("main_BB1"), "main_BB1");
+        assert_eq!(
+            tina_name("std::sync::Mutex::lock"),
+            "{std::sync::Mutex::lock}"
+        );
+        assert_eq!(tina_name("a{b}"), "{a\\{b\\}}");
+    }
+
+    #[test]
+    fn write_greatspn_numbers_the_places() {
+        let net = mutex_net();
+        let greatspn = to_string(|output| write_greatspn(&net, output)).unwrap();
+
+        assert_eq!(
+            greatspn,
+            "\
+|0|
+|
+f 0 4 0 2 0 0 0
+PROGRAM_START 1 1.0 1.0 1.5 1.0 0
+main_BB1 0 1.0 2.0 1.5 2.0 0
+PROGRAM_END 0 1.0 3.0 1.5 3.0 0
+MUTEX_0 1 1.0 4.0 1.5 4.0 0
+lock 1.000000e+00 1 0 2 0 3.0 1.0 3.5 1.0 3.5 1.0 0
+  1 1 0 0
+  1 4 0 0
+1
+  1 2 0 0
+0
+unlock 1.000000e+00 1 0 1 0 3.0 2.0 3.5 2.0 3.5 2.0 0
+  1 2 0 0
+2
+  1 4 0 0
+  1 3 0 0
+0
+"
+        );
+    }
+
+    #[test]
+    fn write_greatspn_merges_repeated_arcs() {
+        let mut net = NetBuilder::new()
+            .bounded_place("RWLOCK_0", 4, 4)
+            .transition("write")
+            .build();
+        net.arcs = vec![arc("RWLOCK_0", "write", ArcDirection::PlaceToTransition); 4];
+        let greatspn = to_string(|output| write_greatspn(&net, output)).unwrap();
+
+        assert!(greatspn
+            .contains("write 1.000000e+00 1 0 1 0 3.0 1.0 3.5 1.0 3.5 1.0 0\n  4 1 0 0\n0\n0\n"));
+    }
+
+    #[test]
+    fn greatspn_name_replaces_special_characters() {
+        assert_eq!(greatspn_name("main_BB1"), "main_BB1");
+        assert_eq!(
+            greatspn_name("std::sync::Mutex::lock"),
+            "std__sync__Mutex__lock"
+        );
+    }
+}
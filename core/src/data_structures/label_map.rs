@@ -0,0 +1,95 @@
+//! Module that implements the map from the labels of the net to the code that produced them.
+//!
+//! The labels generated by the translator, e.g. `main_BB7` or `std_sync_Mutex_T_lock_0_CALL`,
+//! keep the net compact but say little about the program.
+//! The label map explains every label with the function being translated when the node was added,
+//! the file and the line of the source code and the text of the MIR statement or terminator,
+//! e.g. `_5 = Mutex::<i32>::lock(move _6) -> [return: bb2, unwind: bb7]`.
+//!
+//! The nodes added outside of the body of a function, e.g. `PROGRAM_START`,
+//! are not included. Like the block mapping, the map refers to the net built by the translator
+//! and is not updated by the post-processing passes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The origin of a place or a transition in the source code.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelEntry {
+    /// The name of the function instance being translated, e.g. `main` or `compute`.
+    pub function: String,
+    /// The path of the source code file.
+    pub file: String,
+    /// The line in the source code file, 1-based.
+    pub line: usize,
+    /// The text of the MIR statement or terminator that was being translated.
+    pub mir: String,
+}
+
+/// The map from the labels of the places and transitions to their origin.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LabelMap {
+    /// The origin of every node, indexed by its label.
+    pub labels: BTreeMap<String, LabelEntry>,
+}
+
+impl LabelMap {
+    /// Adds the origin of the node with the given label, unless it is already known.
+    /// The nodes of a called function are recorded by its own statements before the call itself ends,
+    /// so the innermost origin is kept.
+    pub fn insert(&mut self, label: String, entry: &LabelEntry) {
+        self.labels.entry(label).or_insert_with(|| entry.clone());
+    }
+
+    /// Returns the origin of the node with the given label, if known.
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<&LabelEntry> {
+        self.labels.get(label)
+    }
+
+    /// Returns the representation of the map in JSON, an object indexed by label.
+    ///
+    /// # Panics
+    ///
+    /// If the map cannot be serialized, then the function panics. This should never happen.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("BUG: The label map should be serializable to JSON")
+    }
+}
+
+#[cfg(test)]
+mod label_map_tests {
+    use super::*;
+
+    fn entry(mir: &str) -> LabelEntry {
+        LabelEntry {
+            function: "main".to_string(),
+            file: "src/main.rs".to_string(),
+            line: 3,
+            mir: mir.to_string(),
+        }
+    }
+
+    #[test]
+    fn insert_keeps_the_first_origin() {
+        let mut map = LabelMap::default();
+        map.insert("main_BB1".to_string(), &entry("goto -> bb1"));
+        map.insert("main_BB1".to_string(), &entry("return"));
+
+        assert_eq!(map.get("main_BB1").unwrap().mir, "goto -> bb1");
+        assert!(map.get("main_BB2").is_none());
+    }
+
+    #[test]
+    fn label_map_json_is_indexed_by_label() {
+        let mut map = LabelMap::default();
+        map.insert("main_BB1".to_string(), &entry("goto -> bb1"));
+
+        assert!(map
+            .to_json()
+            .starts_with("{\n  \"main_BB1\": {\n    \"function\": \"main\""));
+    }
+}
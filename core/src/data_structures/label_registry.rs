@@ -0,0 +1,154 @@
+//! Module that keeps track of the labels of the places and transitions in the Petri net.
+//!
+//! The labels identify the nodes of the net in every output format,
+//! so two nodes with the same label would be merged into one when the net is exported.
+//! This happens in pathological cases, e.g. when two monomorphizations of a generic function
+//! sanitize to the same string.
+//!
+//! Places and transitions share the same namespace, since the identifiers in PNML are unique per document.
+//! A label that is already used is resolved by appending the suffix `__N`, where `N` is the first number
+//! starting from 1 that results in an unused label. The resolution depends only on the order
+//! in which the labels are reserved, so the translation of the same program always produces the same labels.
+//! Every collision is recorded so that it can be reported to the user.
+
+use std::collections::HashSet;
+
+/// The kind of node in the Petri net that a label belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Place,
+    Transition,
+}
+
+impl std::fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Place => write!(f, "place"),
+            Self::Transition => write!(f, "transition"),
+        }
+    }
+}
+
+/// A label that was requested for a node although it was already in use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabelCollision {
+    /// The kind of node that requested the label.
+    pub kind: NodeKind,
+    /// The label requested for the node.
+    pub label: String,
+    /// The label assigned to the node instead.
+    pub resolved: String,
+}
+
+impl std::fmt::Display for LabelCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The label `{}` of a {} is already used in the net: Renamed to `{}`",
+            self.label, self.kind, self.resolved
+        )
+    }
+}
+
+/// The registry of the labels used in the Petri net.
+#[derive(Default)]
+pub struct LabelRegistry {
+    /// The labels reserved so far.
+    used: HashSet<String>,
+    /// The collisions resolved so far, in the order they happened.
+    collisions: Vec<LabelCollision>,
+}
+
+impl LabelRegistry {
+    /// Creates a new empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves the label for a new node of the given kind.
+    /// Returns the label itself if it is unused, otherwise the label with the first free suffix `__N`.
+    ///
+    /// # Panics
+    ///
+    /// If every suffix is already used, which is impossible, then the function panics.
+    pub fn reserve(&mut self, label: &str, kind: NodeKind) -> String {
+        if self.used.insert(label.to_string()) {
+            return label.to_string();
+        }
+        // The label itself is used, so at most `len - 1` of the first `len` suffixes are used.
+        let resolved = (1..=self.used.len())
+            .map(|number| format!("{label}__{number}"))
+            .find(|candidate| !self.used.contains(candidate))
+            .expect("BUG: There should be an unused suffix for every label");
+        self.used.insert(resolved.clone());
+        self.collisions.push(LabelCollision {
+            kind,
+            label: label.to_string(),
+            resolved: resolved.clone(),
+        });
+        resolved
+    }
+
+    /// Returns the collisions resolved so far, in the order they happened.
+    #[must_use]
+    pub fn collisions(&self) -> &[LabelCollision] {
+        &self.collisions
+    }
+}
+
+#[cfg(test)]
+mod label_registry_tests {
+    use super::*;
+
+    #[test]
+    fn reserve_returns_unused_labels_unchanged() {
+        let mut registry = LabelRegistry::new();
+
+        assert_eq!(
+            registry.reserve("PROGRAM_START", NodeKind::Place),
+            "PROGRAM_START"
+        );
+        assert_eq!(
+            registry.reserve("MAIN_CALL_0", NodeKind::Transition),
+            "MAIN_CALL_0"
+        );
+        assert!(registry.collisions().is_empty());
+    }
+
+    #[test]
+    fn reserve_appends_the_first_free_suffix() {
+        let mut registry = LabelRegistry::new();
+        registry.reserve("FOO_CALL_0", NodeKind::Transition);
+        registry.reserve("FOO_CALL_0__1", NodeKind::Transition);
+
+        assert_eq!(
+            registry.reserve("FOO_CALL_0", NodeKind::Transition),
+            "FOO_CALL_0__2"
+        );
+        assert_eq!(
+            registry.reserve("FOO_CALL_0", NodeKind::Transition),
+            "FOO_CALL_0__3"
+        );
+    }
+
+    #[test]
+    fn reserve_records_collisions_between_places_and_transitions() {
+        let mut registry = LabelRegistry::new();
+        registry.reserve("NODE", NodeKind::Place);
+        registry.reserve("NODE", NodeKind::Transition);
+
+        assert_eq!(
+            registry.collisions(),
+            [LabelCollision {
+                kind: NodeKind::Transition,
+                label: "NODE".to_string(),
+                resolved: "NODE__1".to_string(),
+            }]
+        );
+        assert_eq!(
+            registry.collisions()[0].to_string(),
+            "The label `NODE` of a transition is already used in the net: Renamed to `NODE__1`"
+        );
+    }
+}
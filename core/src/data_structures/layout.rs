@@ -0,0 +1,285 @@
+//! Module that implements the layout of the net computed by Graphviz.
+//!
+//! The layout is the position of every node, parsed from the output of `dot -Tplain`.
+//! Computing the layout is by far the slowest step when exporting a large net as an image,
+//! so the layout is reused as long as the structure of the net does not change:
+//! The `structure_hash` only covers the labels of the nodes and the arcs, not the markings
+//! or the spans, so editing the source code without changing the net keeps the layout.
+//!
+//! A layout is applied to the DOT output by pinning every node to its position,
+//! which `neato -n2` renders without computing the layout again.
+//! The positions are in points, with the origin at the bottom left corner like in Graphviz.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+
+/// The number of points in an inch, the unit of the output of `dot -Tplain`.
+const POINTS_PER_INCH: f64 = 72.0;
+
+/// The offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The positions of the nodes of the net.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Layout {
+    /// The width of the drawing in points.
+    pub width: f64,
+    /// The height of the drawing in points.
+    pub height: f64,
+    /// The position of the center of every node in points, by label.
+    pub positions: BTreeMap<String, (f64, f64)>,
+}
+
+impl Layout {
+    /// Parses the layout from the output of `dot -Tplain`.
+    ///
+    /// # Errors
+    ///
+    /// If a `graph` or `node` statement is malformed, then an error is returned.
+    pub fn from_plain(plain: &str) -> Result<Self, String> {
+        let mut layout = Self::default();
+        for line in plain.lines() {
+            let tokens = plain_tokens(line);
+            let number = |index: usize| -> Result<f64, String> {
+                tokens
+                    .get(index)
+                    .and_then(|token| token.parse::<f64>().ok())
+                    .map(|value| value * POINTS_PER_INCH)
+                    .ok_or_else(|| format!("Malformed line in the layout of Graphviz: {line}"))
+            };
+            match tokens.first().map(String::as_str) {
+                Some("graph") => {
+                    layout.width = number(2)?;
+                    layout.height = number(3)?;
+                }
+                Some("node") => {
+                    let name = tokens.get(1).ok_or_else(|| {
+                        format!("Malformed line in the layout of Graphviz: {line}")
+                    })?;
+                    layout
+                        .positions
+                        .insert(name.clone(), (number(2)?, number(3)?));
+                }
+                _ => {}
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Checks whether the layout contains a position for every node of the net.
+    #[must_use]
+    pub fn covers(&self, net: &NetData) -> bool {
+        net.places
+            .iter()
+            .map(|place| &place.label)
+            .chain(net.transitions.iter().map(|transition| &transition.label))
+            .all(|label| self.positions.contains_key(label))
+    }
+
+    /// Returns the position of the node with the origin at the top left corner, as used by the editors.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn top_down_position(&self, label: &str) -> Option<(i64, i64)> {
+        self.positions
+            .get(label)
+            .map(|(x, y)| (x.round() as i64, (self.height - y).round() as i64))
+    }
+
+    /// Pins every node of the net in DOT format to its position in the layout.
+    /// The nodes are recognized by the lines of the form `name [attributes];` written by `netcrab`.
+    /// Nodes without a position are left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// If writing to the output string fails, then the function panics. This should never happen.
+    #[must_use]
+    pub fn pin_dot(&self, dot: &str) -> String {
+        let mut output = String::with_capacity(dot.len());
+        for line in dot.lines() {
+            let node = line
+                .split_once(" [")
+                .filter(|(name, _)| !name.contains(" -> "));
+            let position = node.and_then(|(name, _)| self.positions.get(&unquote(name.trim())));
+            match (node, position) {
+                (Some((name, attributes)), Some((x, y))) => {
+                    write!(output, "{name} [pos=\"{x:.2},{y:.2}!\" {attributes}")
+                        .expect("BUG: Writing to a string should not fail");
+                }
+                _ => output.push_str(line),
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Returns a hash of the structure of the net, i.e. the labels of the nodes and the arcs.
+///
+/// The markings and the spans do not change the layout, so they are not included.
+/// The hash is stable between runs and versions of the compiler, unlike `std::hash::DefaultHasher`.
+#[must_use]
+pub fn structure_hash(net: &NetData) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write = |bytes: &[u8]| {
+        // Every field ends with a byte that does not appear in UTF-8, so the fields cannot be confused.
+        for byte in bytes.iter().chain(&[0xff]) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+    for place in &net.places {
+        write(place.label.as_bytes());
+    }
+    for transition in &net.transitions {
+        write(transition.label.as_bytes());
+    }
+    for arc in &net.arcs {
+        write(arc.place.as_bytes());
+        write(arc.transition.as_bytes());
+        write(match arc.direction {
+            ArcDirection::PlaceToTransition => b"<",
+            ArcDirection::TransitionToPlace => b">",
+        });
+    }
+    hash
+}
+
+/// Splits a line of the output of `dot -Tplain` into tokens.
+/// Quoted tokens are unquoted and unescaped.
+fn plain_tokens(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => {}
+            '"' => {
+                let mut token = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => token.extend(chars.next()),
+                        '"' => break,
+                        _ => token.push(c),
+                    }
+                }
+                tokens.push(token);
+            }
+            _ => {
+                let mut token = c.to_string();
+                while let Some(c) = chars.next_if(|c| *c != ' ') {
+                    token.push(c);
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    tokens
+}
+
+/// Removes the quotes and the escapes of a quoted identifier in DOT.
+/// Unquoted identifiers are returned unchanged.
+fn unquote(name: &str) -> String {
+    let Some(inner) = name
+        .strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+    else {
+        return name.to_string();
+    };
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unquoted.extend(chars.next());
+        } else {
+            unquoted.push(c);
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+    use crate::data_structures::net_data::{ArcData, PlaceData, TransitionData};
+
+    const PLAIN: &str = "\
+graph 1 2.5 3
+node PROGRAM_START 1.25 2.5 0.5 0.5 \"\" solid circle black lightgrey
+node \"a'b\" 1.25 0.5 1.2 0.5 \"a'b\" solid box black lightgrey
+edge PROGRAM_START \"a'b\" 4 1.25 2.2 1.25 1.9 1.25 1.4 1.25 1.1 solid black
+stop
+";
+
+    fn net(transition: &str) -> NetData {
+        NetData {
+            places: vec![PlaceData {
+                label: "PROGRAM_START".to_string(),
+                marking: 1,
+                capacity: None,
+                span: None,
+            }],
+            transitions: vec![TransitionData {
+                label: transition.to_string(),
+                span: None,
+            }],
+            arcs: vec![ArcData {
+                place: "PROGRAM_START".to_string(),
+                transition: transition.to_string(),
+                direction: ArcDirection::PlaceToTransition,
+                weight: 1,
+            }],
+        }
+    }
+
+    #[test]
+    fn from_plain_reads_the_positions_in_points() {
+        let layout = Layout::from_plain(PLAIN).unwrap();
+
+        assert_eq!((layout.width, layout.height), (180.0, 216.0));
+        assert_eq!(layout.positions["PROGRAM_START"], (90.0, 180.0));
+        assert_eq!(layout.positions["a'b"], (90.0, 36.0));
+        assert_eq!(layout.top_down_position("a'b"), Some((90, 180)));
+        assert!(layout.covers(&net("a'b")));
+        assert!(!layout.covers(&net("main_RETURN")));
+    }
+
+    #[test]
+    fn pin_dot_adds_the_positions_to_the_nodes() {
+        let layout = Layout::from_plain(PLAIN).unwrap();
+        let dot = "\
+digraph petrinet {
+    PROGRAM_START [shape=\"circle\" xlabel=\"PROGRAM_START\" label=\"•\"];
+    \"a'b\" [shape=\"box\" xlabel=\"\" label=\"a'b\"];
+    PROGRAM_START -> \"a'b\";
+}
+";
+
+        assert_eq!(
+            layout.pin_dot(dot),
+            "\
+digraph petrinet {
+    PROGRAM_START [pos=\"90.00,180.00!\" shape=\"circle\" xlabel=\"PROGRAM_START\" label=\"•\"];
+    \"a'b\" [pos=\"90.00,36.00!\" shape=\"box\" xlabel=\"\" label=\"a'b\"];
+    PROGRAM_START -> \"a'b\";
+}
+"
+        );
+    }
+
+    #[test]
+    fn structure_hash_ignores_markings_but_not_labels() {
+        let mut marked = net("main_RETURN");
+        marked.places[0].marking = 0;
+
+        assert_eq!(structure_hash(&net("main_RETURN")), structure_hash(&marked));
+        assert_ne!(
+            structure_hash(&net("main_RETURN")),
+            structure_hash(&net("main_GOTO_0"))
+        );
+    }
+}
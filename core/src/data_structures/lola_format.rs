@@ -0,0 +1,415 @@
+//! Module that writes a `NetData` in the `LoLA` format and reads it back.
+//!
+//! It writes and accepts the following subset of the format:
+//!
+//! ```text
+//! PLACE
+//!     SAFE : MUTEX_0;
+//!     main_BB0,
+//!     main_BB1;
+//!
+//! MARKING
+//!     MUTEX_0 : 1,
+//!     main_BB0 : 1;
+//!
+//! TRANSITION lock
+//!   CONSUME
+//!     MUTEX_0 : 1,
+//!     main_BB0 : 1;
+//!   PRODUCE
+//!     main_BB1 : 1;
+//! ```
+//!
+//! The places with a capacity are declared as `SAFE`, one list per capacity, and read back with it.
+//! This documents the invariants in the output file and allows `LoLA`
+//! to use a more compact representation of the markings, which speeds up the search.
+//! The number after every place in a transition is the weight of the arc.
+//! The places missing from the `MARKING` section start without tokens.
+//!
+//! The places and the transitions are written sorted by their names, and so are the arcs of every transition.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use crate::data_structures::identifiers::IdentifierTable;
+use crate::data_structures::net_data::{ArcData, ArcDirection, NetData, PlaceData, TransitionData};
+
+/// The arcs of a transition indexed by the name of the place, with their weight.
+type WeightedPlaces<'a> = BTreeMap<&'a str, usize>;
+
+/// Writes the net in the `LoLA` format to the given writer.
+/// The places and transitions are named with their identifiers in the table.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_lola<T: Write>(
+    net: &NetData,
+    identifiers: &IdentifierTable,
+    writer: &mut T,
+) -> Result<(), std::io::Error> {
+    let mut places: Vec<(&str, &PlaceData)> = net
+        .places
+        .iter()
+        .map(|place| (identifiers.identifier(&place.label), place))
+        .collect();
+    places.sort_unstable_by_key(|(name, _)| *name);
+
+    writeln!(writer, "PLACE")?;
+    let mut safe_places: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    let mut other_places = Vec::new();
+    for (name, place) in &places {
+        match place.capacity {
+            Some(capacity) => safe_places.entry(capacity).or_default().push(name),
+            None => other_places.push(name.to_string()),
+        }
+    }
+    for (capacity, names) in safe_places {
+        let names = names.join(", ");
+        if capacity == 1 {
+            writeln!(writer, "    SAFE : {names};")?;
+        } else {
+            writeln!(writer, "    SAFE {capacity} : {names};")?;
+        }
+    }
+    write_list(writer, &other_places)?;
+    writeln!(writer)?;
+
+    writeln!(writer, "MARKING")?;
+    let markings: Vec<String> = places
+        .iter()
+        .map(|(name, place)| format!("{name} : {}", place.marking))
+        .collect();
+    write_list(writer, &markings)?;
+    writeln!(writer)?;
+
+    let mut arcs: HashMap<&str, (WeightedPlaces, WeightedPlaces)> = HashMap::new();
+    for arc in &net.arcs {
+        let (consumed, produced) = arcs.entry(arc.transition.as_str()).or_default();
+        let places = match arc.direction {
+            ArcDirection::PlaceToTransition => consumed,
+            ArcDirection::TransitionToPlace => produced,
+        };
+        *places
+            .entry(identifiers.identifier(&arc.place))
+            .or_default() += arc.weight;
+    }
+    let mut transitions: Vec<(&str, &str)> = net
+        .transitions
+        .iter()
+        .map(|transition| {
+            (
+                identifiers.identifier(&transition.label),
+                transition.label.as_str(),
+            )
+        })
+        .collect();
+    transitions.sort_unstable();
+    let no_arcs = (BTreeMap::new(), BTreeMap::new());
+    for (name, label) in transitions {
+        writeln!(writer, "TRANSITION {name}")?;
+        let (consumed, produced) = arcs.get(label).unwrap_or(&no_arcs);
+        for (keyword, places) in [("CONSUME", consumed), ("PRODUCE", produced)] {
+            let places: Vec<String> = places
+                .iter()
+                .map(|(place, weight)| format!("{place} : {weight}"))
+                .collect();
+            if places.is_empty() {
+                writeln!(writer, "  {keyword};")?;
+            } else {
+                writeln!(writer, "  {keyword}")?;
+                write_list(writer, &places)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the items one per line, separated by commas and terminated by a semicolon.
+fn write_list<T: Write>(writer: &mut T, items: &[String]) -> Result<(), std::io::Error> {
+    for (index, item) in items.iter().enumerate() {
+        let separator = if index + 1 == items.len() { ';' } else { ',' };
+        writeln!(writer, "    {item}{separator}")?;
+    }
+    Ok(())
+}
+
+/// Splits the text into words and the punctuation characters `,`, `;` and `:`.
+fn tokenize(lola: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    for word in lola.split_whitespace() {
+        let mut rest = word;
+        while let Some(index) = rest.find([',', ';', ':']) {
+            if index > 0 {
+                tokens.push(&rest[..index]);
+            }
+            tokens.push(&rest[index..=index]);
+            rest = &rest[index + 1..];
+        }
+        if !rest.is_empty() {
+            tokens.push(rest);
+        }
+    }
+    tokens
+}
+
+/// Parses a number of tokens or a multiplicity.
+fn parse_number(token: Option<&str>) -> Result<usize, String> {
+    let token = token.ok_or("Unexpected end of the file, expected a number")?;
+    token
+        .parse()
+        .map_err(|_| format!("Expected a number, found `{token}`"))
+}
+
+/// Consumes the next token and checks that it is the expected one.
+fn expect<'a>(tokens: &mut impl Iterator<Item = &'a str>, expected: &str) -> Result<(), String> {
+    match tokens.next() {
+        Some(token) if token == expected => Ok(()),
+        Some(token) => Err(format!("Expected `{expected}`, found `{token}`")),
+        None => Err(format!("Unexpected end of the file, expected `{expected}`")),
+    }
+}
+
+/// Parses a list of `name : number` separated by commas and terminated by a semicolon.
+fn parse_weighted_list<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Result<Vec<(&'a str, usize)>, String> {
+    let mut list = Vec::new();
+    if tokens.peek() == Some(&";") {
+        tokens.next();
+        return Ok(list);
+    }
+    loop {
+        let name = tokens
+            .next()
+            .ok_or("Unexpected end of the file, expected a name")?;
+        expect(tokens, ":")?;
+        list.push((name, parse_number(tokens.next())?));
+        match tokens.next() {
+            Some(",") => {}
+            Some(";") => return Ok(list),
+            Some(token) => return Err(format!("Expected `,` or `;`, found `{token}`")),
+            None => return Err("Unexpected end of the file, expected `;`".to_string()),
+        }
+    }
+}
+
+/// Parses the `PLACE` section until the `MARKING` keyword, returning the places with their capacity.
+fn parse_places<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Result<Vec<PlaceData>, String> {
+    let mut places = Vec::new();
+    let mut capacity = None;
+    while let Some(token) = tokens.next() {
+        match token {
+            "MARKING" => return Ok(places),
+            "SAFE" => {
+                capacity = Some(if tokens.peek() == Some(&":") {
+                    1
+                } else {
+                    parse_number(tokens.next())?
+                });
+                expect(tokens, ":")?;
+            }
+            "," => {}
+            ";" => capacity = None,
+            label => places.push(PlaceData {
+                label: label.to_string(),
+                marking: 0,
+                capacity,
+                span: None,
+            }),
+        }
+    }
+    Err("Unexpected end of the file, expected `MARKING`".to_string())
+}
+
+/// Reads a net in the `LoLA` format.
+///
+/// # Errors
+///
+/// If the text does not follow the format, then an error is returned.
+/// If the marking or an arc refers to a place that was not declared, then an error is returned.
+pub fn parse_lola(lola: &str) -> Result<NetData, String> {
+    let tokens = tokenize(lola);
+    let mut tokens = tokens.into_iter().peekable();
+    expect(&mut tokens, "PLACE")?;
+    let mut places = parse_places(&mut tokens)?;
+    let place_index: HashMap<String, usize> = places
+        .iter()
+        .enumerate()
+        .map(|(index, place)| (place.label.clone(), index))
+        .collect();
+    let index_of = |label: &str| {
+        place_index
+            .get(label)
+            .copied()
+            .ok_or_else(|| format!("The place `{label}` is not declared"))
+    };
+
+    for (label, marking) in parse_weighted_list(&mut tokens)? {
+        places[index_of(label)?].marking = marking;
+    }
+
+    let mut transitions = Vec::new();
+    let mut arcs = Vec::new();
+    while let Some(token) = tokens.next() {
+        if token != "TRANSITION" {
+            return Err(format!("Expected `TRANSITION`, found `{token}`"));
+        }
+        let label = tokens
+            .next()
+            .ok_or("Unexpected end of the file, expected the name of a transition")?;
+        for (keyword, direction) in [
+            ("CONSUME", ArcDirection::PlaceToTransition),
+            ("PRODUCE", ArcDirection::TransitionToPlace),
+        ] {
+            expect(&mut tokens, keyword)?;
+            for (place, weight) in parse_weighted_list(&mut tokens)? {
+                index_of(place)?;
+                arcs.push(ArcData {
+                    place: place.to_string(),
+                    transition: label.to_string(),
+                    direction,
+                    weight,
+                });
+            }
+        }
+        transitions.push(TransitionData {
+            label: label.to_string(),
+            span: None,
+        });
+    }
+
+    Ok(NetData {
+        places,
+        transitions,
+        arcs,
+    })
+}
+
+#[cfg(test)]
+mod lola_format_tests {
+    use super::*;
+    use crate::data_structures::identifiers::IdentifierFormat;
+
+    #[test]
+    fn parse_lola_reads_places_marking_and_transitions() {
+        let lola = "\
+PLACE
+    SAFE : MUTEX_0;
+    SAFE 4 : RWLOCK_0;
+    main_BB0,
+    main_BB1;
+
+MARKING
+    MUTEX_0 : 1,
+    RWLOCK_0 : 4,
+    main_BB0 : 1;
+
+TRANSITION lock
+  CONSUME
+    MUTEX_0 : 1,
+    RWLOCK_0 : 4,
+    main_BB0 : 1;
+  PRODUCE
+    main_BB1 : 1;
+";
+        let net = parse_lola(lola).unwrap();
+
+        let places: Vec<_> = net
+            .places
+            .iter()
+            .map(|place| (place.label.as_str(), place.marking, place.capacity))
+            .collect();
+        assert_eq!(
+            places,
+            [
+                ("MUTEX_0", 1, Some(1)),
+                ("RWLOCK_0", 4, Some(4)),
+                ("main_BB0", 1, None),
+                ("main_BB1", 0, None),
+            ]
+        );
+        assert_eq!(net.transitions.len(), 1);
+        assert_eq!(net.arcs.len(), 4);
+        assert_eq!(
+            net.arcs
+                .iter()
+                .find(|arc| arc.place == "RWLOCK_0")
+                .map(|arc| arc.weight),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn write_lola_declares_safe_places_and_weights() {
+        let lola = "\
+PLACE
+    SAFE : MUTEX_0;
+    SAFE 4 : RWLOCK_0;
+    main_BB0,
+    main_BB1;
+
+MARKING
+    MUTEX_0 : 1,
+    RWLOCK_0 : 4,
+    main_BB0 : 1,
+    main_BB1 : 0;
+
+TRANSITION lock
+  CONSUME
+    MUTEX_0 : 1,
+    RWLOCK_0 : 4,
+    main_BB0 : 1;
+  PRODUCE
+    main_BB1 : 1;
+TRANSITION stop
+  CONSUME
+    main_BB1 : 1;
+  PRODUCE;
+";
+        let net = parse_lola(lola).unwrap();
+        let identifiers = IdentifierTable::new(
+            IdentifierFormat::Lola,
+            net.places.iter().map(|place| place.label.as_str()),
+        );
+        let mut output = Vec::new();
+
+        write_lola(&net, &identifiers, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), lola);
+    }
+
+    #[test]
+    fn write_lola_sorts_the_places_by_their_names() {
+        let mut net =
+            parse_lola("PLACE b, a;\nMARKING b : 1;\nTRANSITION t CONSUME b : 2; PRODUCE a : 1;")
+                .unwrap();
+        net.places[0].label = "b b".to_string();
+        net.arcs[0].place = "b b".to_string();
+        let identifiers = IdentifierTable::new(IdentifierFormat::Lola, ["b b", "a", "t"]);
+        let mut output = Vec::new();
+
+        write_lola(&net, &identifiers, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("PLACE\n    a,\n    b_b;\n"));
+        assert!(output.contains("  CONSUME\n    b_b : 2;\n"));
+    }
+
+    #[test]
+    fn parse_lola_accepts_empty_sections() {
+        let net = parse_lola("PLACE a;\nMARKING ;\nTRANSITION t CONSUME ; PRODUCE a : 1;").unwrap();
+
+        assert_eq!(net.places[0].marking, 0);
+        assert_eq!(net.arcs.len(), 1);
+    }
+
+    #[test]
+    fn parse_lola_rejects_undeclared_places() {
+        let err = parse_lola("PLACE a;\nMARKING b : 1;").unwrap_err();
+
+        assert_eq!(err, "The place `b` is not declared");
+    }
+}
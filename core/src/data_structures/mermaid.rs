@@ -0,0 +1,105 @@
+//! Module that implements the export of the `NetData` to a Mermaid flowchart.
+//! See <https://mermaid.js.org/syntax/flowchart.html>.
+//!
+//! Mermaid diagrams are rendered by GitHub and by many Markdown editors,
+//! so the net can be pasted into the documentation or an issue inside of a `mermaid` code block.
+//!
+//! The places are drawn as circles and the transitions as boxes, like in the DOT output.
+//! The nodes are identified by their position in the net, e.g. `P0` or `T3`, and show the label as text,
+//! so no label needs to be converted to a legal identifier.
+//! The initial marking is shown next to the label of the place and the multiplicity of an arc as its text.
+
+use std::collections::HashMap;
+use std::io::{Error, Write};
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+
+/// Escapes the characters of a label that end a quoted text in Mermaid.
+fn escape_mermaid(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
+
+/// Writes the net as a Mermaid flowchart from top to bottom to the given writer.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_mermaid<T: Write>(net: &NetData, writer: &mut T) -> Result<(), Error> {
+    writeln!(writer, "graph TD")?;
+    let mut places = HashMap::new();
+    for (index, place) in net.places.iter().enumerate() {
+        let label = escape_mermaid(&place.label);
+        if place.marking > 0 {
+            writeln!(writer, "    P{index}((\"{label} ({})\"))", place.marking)?;
+        } else {
+            writeln!(writer, "    P{index}((\"{label}\"))")?;
+        }
+        places.insert(place.label.as_str(), index);
+    }
+    let mut transitions = HashMap::new();
+    for (index, transition) in net.transitions.iter().enumerate() {
+        writeln!(
+            writer,
+            "    T{index}[\"{}\"]",
+            escape_mermaid(&transition.label)
+        )?;
+        transitions.insert(transition.label.as_str(), index);
+    }
+
+    // Repeated arcs are drawn once with the sum of their weights, in the order of their first appearance.
+    let mut arcs: Vec<((usize, usize, ArcDirection), usize)> = Vec::new();
+    for arc in &net.arcs {
+        let key = (
+            places[arc.place.as_str()],
+            transitions[arc.transition.as_str()],
+            arc.direction,
+        );
+        match arcs.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, multiplicity)) => *multiplicity += arc.weight,
+            None => arcs.push((key, arc.weight)),
+        }
+    }
+    for ((place, transition, direction), multiplicity) in arcs {
+        let (source, target) = match direction {
+            ArcDirection::PlaceToTransition => (format!("P{place}"), format!("T{transition}")),
+            ArcDirection::TransitionToPlace => (format!("T{transition}"), format!("P{place}")),
+        };
+        if multiplicity == 1 {
+            writeln!(writer, "    {source} --> {target}")?;
+        } else {
+            writeln!(writer, "    {source} -->|{multiplicity}| {target}")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod mermaid_tests {
+    use super::*;
+    use crate::data_structures::net_builder::NetBuilder;
+
+    #[test]
+    fn write_mermaid_draws_places_transitions_and_arcs() {
+        let net = NetBuilder::new()
+            .bounded_place("RWLOCK_0", 4, 4)
+            .place("say \"hi\"", 0)
+            .transition("write")
+            .weighted_arc("RWLOCK_0", "write", ArcDirection::PlaceToTransition, 4)
+            .output("write", "say \"hi\"")
+            .build();
+        let mut output = Vec::new();
+        write_mermaid(&net, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\
+graph TD
+    P0((\"RWLOCK_0 (4)\"))
+    P1((\"say #quot;hi#quot;\"))
+    T0[\"write\"]
+    P0 -->|4| T0
+    T0 --> P1
+"
+        );
+    }
+}
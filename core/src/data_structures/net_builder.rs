@@ -0,0 +1,125 @@
+//! Module that implements a builder of small nets for the tests.
+//!
+//! The nodes are added in the order of the calls, without spans unless one is given explicitly,
+//! and the arcs have a weight of one unless one is given explicitly.
+//! The functions `place`, `transition` and `arc` create single nodes and arcs,
+//! e.g. to modify a net after building it or to compare the result of a pass.
+//!
+//! The module is compiled for the tests of this crate and with the feature `test-util`,
+//! which the other crates of the workspace enable in their development dependencies.
+
+use crate::data_structures::net_data::{ArcData, ArcDirection, NetData, PlaceData, TransitionData};
+use crate::data_structures::span_info::SpanInfo;
+
+/// A builder of a `NetData`.
+#[derive(Default)]
+pub struct NetBuilder {
+    net: NetData,
+}
+
+impl NetBuilder {
+    /// Creates a new builder of an empty net.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a place with the given initial marking and without a capacity.
+    #[must_use]
+    pub fn place(mut self, label: &str, marking: usize) -> Self {
+        self.net.places.push(place(label, marking));
+        self
+    }
+
+    /// Adds a place with the given initial marking and capacity.
+    #[must_use]
+    pub fn bounded_place(mut self, label: &str, marking: usize, capacity: usize) -> Self {
+        self.net.places.push(PlaceData {
+            capacity: Some(capacity),
+            ..place(label, marking)
+        });
+        self
+    }
+
+    /// Adds a transition that was not produced by a span of the source code.
+    #[must_use]
+    pub fn transition(mut self, label: &str) -> Self {
+        self.net.transitions.push(transition(label));
+        self
+    }
+
+    /// Adds a transition that was produced by the given span of the source code.
+    #[must_use]
+    pub fn transition_with_span(mut self, label: &str, span: SpanInfo) -> Self {
+        self.net.transitions.push(TransitionData {
+            span: Some(span),
+            ..transition(label)
+        });
+        self
+    }
+
+    /// Adds an arc from a place to a transition with weight one.
+    #[must_use]
+    pub fn input(self, place: &str, transition: &str) -> Self {
+        self.weighted_arc(place, transition, ArcDirection::PlaceToTransition, 1)
+    }
+
+    /// Adds an arc from a transition to a place with weight one.
+    #[must_use]
+    pub fn output(self, transition: &str, place: &str) -> Self {
+        self.weighted_arc(place, transition, ArcDirection::TransitionToPlace, 1)
+    }
+
+    /// Adds an arc between a place and a transition with the given direction and weight.
+    #[must_use]
+    pub fn weighted_arc(
+        mut self,
+        place: &str,
+        transition: &str,
+        direction: ArcDirection,
+        weight: usize,
+    ) -> Self {
+        self.net.arcs.push(ArcData {
+            weight,
+            ..arc(place, transition, direction)
+        });
+        self
+    }
+
+    /// Returns the net built.
+    #[must_use]
+    pub fn build(self) -> NetData {
+        self.net
+    }
+}
+
+/// Creates a place with the given initial marking, without a capacity and without a span.
+#[must_use]
+pub fn place(label: &str, marking: usize) -> PlaceData {
+    PlaceData {
+        label: label.to_string(),
+        marking,
+        capacity: None,
+        span: None,
+    }
+}
+
+/// Creates a transition without a span.
+#[must_use]
+pub fn transition(label: &str) -> TransitionData {
+    TransitionData {
+        label: label.to_string(),
+        span: None,
+    }
+}
+
+/// Creates an arc between a place and a transition with the given direction and weight one.
+#[must_use]
+pub fn arc(place: &str, transition: &str, direction: ArcDirection) -> ArcData {
+    ArcData {
+        place: place.to_string(),
+        transition: transition.to_string(),
+        direction,
+        weight: 1,
+    }
+}
@@ -0,0 +1,189 @@
+//! Module that implements a plain description of a Petri net.
+//!
+//! The `NetData` lists the places with their initial marking, the transitions
+//! with the span of the source code that produced them and the weighted arcs between them.
+//! It is produced by the `PetriNet` from its arena once the net is built and
+//! it can be serialized to JSON and deserialized again without losing information.
+//!
+//! The internal analyses operate on this description,
+//! so that they can also run on nets loaded from a JSON file.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_structures::span_info::SpanInfo;
+
+/// A place of the net.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaceData {
+    /// The label of the place, unique among the places of the net.
+    pub label: String,
+    /// The number of tokens in the place in the initial marking.
+    pub marking: usize,
+    /// The maximum number of tokens that the place may hold, if it is bounded by construction.
+    /// For instance, the place of a mutex never holds more than one token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<usize>,
+    /// The span of the source code that produced the place, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanInfo>,
+}
+
+/// A transition of the net.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionData {
+    /// The label of the transition, unique among the transitions of the net.
+    pub label: String,
+    /// The span of the source code that produced the transition, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanInfo>,
+}
+
+/// The direction of an arc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArcDirection {
+    /// The transition consumes a token from the place.
+    PlaceToTransition,
+    /// The transition produces a token in the place.
+    TransitionToPlace,
+}
+
+/// An arc of the net.
+/// Every pair of place and transition is connected by at most one arc in each direction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArcData {
+    /// The label of the place connected by the arc.
+    pub place: String,
+    /// The label of the transition connected by the arc.
+    pub transition: String,
+    /// The direction of the arc.
+    pub direction: ArcDirection,
+    /// The number of tokens consumed or produced when the transition fires.
+    #[serde(default = "unit_weight", skip_serializing_if = "is_unit_weight")]
+    pub weight: usize,
+}
+
+/// Returns the weight of an arc without an explicit weight in JSON.
+const fn unit_weight() -> usize {
+    1
+}
+
+/// Checks whether the weight is the default one, which is not serialized.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_unit_weight(weight: &usize) -> bool {
+    *weight == 1
+}
+
+/// A plain description of a Petri net.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetData {
+    /// The places in the order they were added.
+    pub places: Vec<PlaceData>,
+    /// The transitions in the order they were added.
+    pub transitions: Vec<TransitionData>,
+    /// The arcs in the order they were added.
+    pub arcs: Vec<ArcData>,
+}
+
+impl NetData {
+    /// Serializes the net to a JSON string.
+    ///
+    /// # Panics
+    ///
+    /// If the net cannot be serialized, then the function panics.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("BUG: The net should be serializable to JSON")
+    }
+
+    /// Deserializes a net from a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// If the string is not a valid JSON description of a net, then an error is returned.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json)
+            .map_err(|err| format!("Invalid JSON description of a net: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod net_data_tests {
+    use super::*;
+
+    fn net_with_one_transition() -> NetData {
+        NetData {
+            places: vec![
+                PlaceData {
+                    label: "START".to_string(),
+                    marking: 1,
+                    capacity: None,
+                    span: None,
+                },
+                PlaceData {
+                    label: "END".to_string(),
+                    marking: 0,
+                    capacity: None,
+                    span: None,
+                },
+            ],
+            transitions: vec![TransitionData {
+                label: "STEP".to_string(),
+                span: Some(SpanInfo {
+                    filename: "main.rs".to_string(),
+                    start_line: 2,
+                    start_column: 5,
+                    end_line: 2,
+                    end_column: 10,
+                }),
+            }],
+            arcs: vec![
+                ArcData {
+                    place: "START".to_string(),
+                    transition: "STEP".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+                ArcData {
+                    place: "END".to_string(),
+                    transition: "STEP".to_string(),
+                    direction: ArcDirection::TransitionToPlace,
+                    weight: 3,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn net_data_json_round_trip_is_lossless() {
+        let net = net_with_one_transition();
+
+        let json = net.to_json();
+
+        assert_eq!(NetData::from_json(&json).unwrap(), net);
+    }
+
+    #[test]
+    fn net_data_from_json_without_span_is_valid() {
+        let json = r#"{
+            "places": [{"label": "START", "marking": 1}],
+            "transitions": [{"label": "STEP"}],
+            "arcs": [{"place": "START", "transition": "STEP", "direction": "place_to_transition"}]
+        }"#;
+
+        let net = NetData::from_json(json).unwrap();
+
+        assert_eq!(net.transitions[0].span, None);
+        assert_eq!(net.arcs[0].direction, ArcDirection::PlaceToTransition);
+        assert_eq!(net.arcs[0].weight, 1);
+    }
+
+    #[test]
+    fn net_data_from_invalid_json_returns_error() {
+        let result = NetData::from_json("{\"places\": 1}");
+
+        assert!(result
+            .unwrap_err()
+            .starts_with("Invalid JSON description of a net"));
+    }
+}
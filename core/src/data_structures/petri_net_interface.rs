@@ -0,0 +1,1456 @@
+//! Module that defines the Petri net built during the translation
+//! and some functions to use as wrappers around its methods.
+//! This ensures a proper error handling when adding arcs to the net.
+//!
+//! The `PetriNet` is an arena: Places, transitions and arcs are appended to vectors
+//! and referenced by their index, together with the source code span that was being translated
+//! when each place or transition was added to the net.
+//! Adding an element does not perform any lookup or validation, which keeps the overhead per insertion
+//! low for very large translations.
+//!
+//! The arcs are kept in an `ArcStore`, which can move them to disk to limit the memory usage.
+//! Every arc has a weight, i.e. the number of tokens that the transition consumes or produces.
+//! An arc is added at most once between the same nodes in the same direction, the weight is used instead.
+//! Since adding an arc does not look up the existing ones, a duplicate arc is reported by `validate`.
+//!
+//! A place may be annotated with a capacity, i.e. the maximum number of tokens it holds by construction.
+//! The capacities are exported to PNML as tool-specific data, declared as `SAFE` places in `LoLA`
+//! and enforced by the internal analyses,
+//! which catches modelling errors such as a mutex place that accumulates tokens.
+//!
+//! Every net has a unique identifier that is stored in the references to its places and transitions,
+//! so that a reference to a node of another net is rejected instead of pointing to an unrelated node.
+//!
+//! The labels of the places and transitions are reserved in a `LabelRegistry`,
+//! which renames the nodes whose label is already used instead of merging them on export.
+//!
+//! The labels are converted to legal identifiers for each output format when the net is exported,
+//! see the module `identifiers`.
+//!
+//! The net is validated once at the end, when it is converted to the plain description `NetData`
+//! to export or to analyze it. Every output format is written from the `NetData`.
+//!
+//! The `PetriNet` does not depend on the library `netcrab`.
+//! A net built with `netcrab` can be converted to a `PetriNet` and back, see `from_netcrab` and `to_netcrab`,
+//! e.g. to annotate its places with capacities or to run the internal analyses on it.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::data_structures::arc_store::{ArcStore, StoredArc};
+use crate::data_structures::dot_format::write_dot;
+use crate::data_structures::dot_style::{
+    add_clusters_to_dot, add_node_attributes_to_dot, add_rankdir_to_dot, functions_of_nodes,
+    DotStyle,
+};
+use crate::data_structures::identifiers::{IdentifierFormat, IdentifierTable};
+use crate::data_structures::interchange_formats::{
+    write_apt, write_greatspn, write_petrify, write_tina,
+};
+use crate::data_structures::label_registry::{LabelCollision, LabelRegistry, NodeKind};
+use crate::data_structures::layout::Layout;
+use crate::data_structures::lola_format::write_lola;
+use crate::data_structures::mermaid::write_mermaid;
+use crate::data_structures::net_data::{ArcData, ArcDirection, NetData, PlaceData, TransitionData};
+use crate::data_structures::pnml_dialect::{adapt_pnml, position_nodes, PnmlDialect};
+use crate::data_structures::pnml_format::write_pnml;
+use crate::data_structures::span_info::SpanInfo;
+use crate::data_structures::tikz::write_tikz;
+use crate::naming::scheme::NamingScheme;
+
+/// The identifier of the next `PetriNet` created.
+static NEXT_NET_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A reference to a place of the `PetriNet`.
+/// It is cheap to clone since the label is shared with the net.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PlaceRef {
+    /// The identifier of the net that contains the place.
+    net: usize,
+    /// The index of the place in the arena.
+    index: usize,
+    /// The label of the place.
+    label: Arc<str>,
+}
+
+impl PlaceRef {
+    /// Returns the label of the place.
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl std::fmt::Display for PlaceRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// A reference to a transition of the `PetriNet`.
+/// It is cheap to clone since the label is shared with the net.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TransitionRef {
+    /// The identifier of the net that contains the transition.
+    net: usize,
+    /// The index of the transition in the arena.
+    index: usize,
+    /// The label of the transition.
+    label: Arc<str>,
+}
+
+impl TransitionRef {
+    /// Returns the label of the transition.
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl std::fmt::Display for TransitionRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// The number of places and transitions in the net at some point of the translation.
+/// Two node counts delimit the nodes added in between, since the nodes are never removed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NodeCounts {
+    /// The number of places.
+    pub places: usize,
+    /// The number of transitions.
+    pub transitions: usize,
+}
+
+/// A place stored in the arena.
+struct ArenaPlace {
+    label: Arc<str>,
+    marking: usize,
+    capacity: Option<usize>,
+    span: Option<SpanInfo>,
+}
+
+/// A transition stored in the arena.
+struct ArenaTransition {
+    label: Arc<str>,
+    span: Option<SpanInfo>,
+}
+
+/// The Petri net model of the program.
+pub struct PetriNet {
+    /// The identifier of the net, unique in the process.
+    id: usize,
+    /// The places in the order they were added.
+    places: Vec<ArenaPlace>,
+    /// The transitions in the order they were added.
+    transitions: Vec<ArenaTransition>,
+    /// The arcs in the order they were added.
+    arcs: ArcStore,
+    /// The span of the source code currently being translated, if any.
+    current_span: Option<SpanInfo>,
+    /// The labels used by the places and transitions.
+    labels: LabelRegistry,
+    /// The naming scheme for the identifiers in the `LoLA` and PNML files, if any.
+    naming: Option<Box<dyn NamingScheme>>,
+    /// The optional styling of the DOT output.
+    dot_style: DotStyle,
+}
+
+impl Default for PetriNet {
+    fn default() -> Self {
+        Self {
+            id: NEXT_NET_ID.fetch_add(1, Ordering::Relaxed),
+            places: Vec::new(),
+            transitions: Vec::new(),
+            arcs: ArcStore::default(),
+            current_span: None,
+            labels: LabelRegistry::default(),
+            naming: None,
+            dot_style: DotStyle::default(),
+        }
+    }
+}
+
+impl PetriNet {
+    /// Creates a new empty Petri net.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty Petri net that keeps at most `max_memory` bytes of arcs in memory.
+    /// The rest of the arcs are moved to a temporary file, see `ArcStore`.
+    #[must_use]
+    pub fn with_arc_memory_limit(max_memory: usize) -> Self {
+        Self {
+            arcs: ArcStore::with_memory_limit(max_memory),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a new place with the given label to the net.
+    /// If a span of the source code is currently set, it is recorded as the origin of the place.
+    /// If the label is already used, the place is renamed, see `LabelRegistry::reserve`.
+    /// Returns a reference to the new place.
+    pub fn add_place(&mut self, label: &str) -> PlaceRef {
+        let label = Arc::from(self.labels.reserve(label, NodeKind::Place));
+        let index = self.places.len();
+        self.places.push(ArenaPlace {
+            label: Arc::clone(&label),
+            marking: 0,
+            capacity: None,
+            span: self.current_span.clone(),
+        });
+        PlaceRef {
+            net: self.id,
+            index,
+            label,
+        }
+    }
+
+    /// Adds a new transition with the given label to the net.
+    /// If a span of the source code is currently set, it is recorded as the origin of the transition.
+    /// If the label is already used, the transition is renamed, see `LabelRegistry::reserve`.
+    /// Returns a reference to the new transition.
+    pub fn add_transition(&mut self, label: &str) -> TransitionRef {
+        let label = Arc::from(self.labels.reserve(label, NodeKind::Transition));
+        let index = self.transitions.len();
+        self.transitions.push(ArenaTransition {
+            label: Arc::clone(&label),
+            span: self.current_span.clone(),
+        });
+        TransitionRef {
+            net: self.id,
+            index,
+            label,
+        }
+    }
+
+    /// Adds the given number of tokens to a place of the net.
+    /// Returns the new number of tokens in the place.
+    ///
+    /// # Errors
+    ///
+    /// If the place does not belong to the net, then an error is returned.
+    /// If the number of tokens in the place overflows, then an error is returned.
+    pub fn add_token(&mut self, place_ref: &PlaceRef, tokens: usize) -> Result<usize, &str> {
+        let place = self.get_place_mut(place_ref)?;
+        place.marking = place
+            .marking
+            .checked_add(tokens)
+            .ok_or("Overflow when adding tokens to the place")?;
+        Ok(place.marking)
+    }
+
+    /// Sets the maximum number of tokens that a place of the net may hold.
+    ///
+    /// # Errors
+    ///
+    /// If the place does not belong to the net, then an error is returned.
+    pub fn set_capacity(&mut self, place_ref: &PlaceRef, capacity: usize) -> Result<(), &str> {
+        let place = self.get_place_mut(place_ref)?;
+        place.capacity = Some(capacity);
+        Ok(())
+    }
+
+    /// Returns the place of the arena referenced by the `PlaceRef`.
+    ///
+    /// # Errors
+    ///
+    /// If the place does not belong to the net, then an error is returned.
+    fn get_place_mut(&mut self, place_ref: &PlaceRef) -> Result<&mut ArenaPlace, &'static str> {
+        if place_ref.net != self.id {
+            return Err("The place does not belong to the net");
+        }
+        self.places
+            .get_mut(place_ref.index)
+            .ok_or("The place does not belong to the net")
+    }
+
+    /// Checks that the place and the transition of an arc belong to the net.
+    ///
+    /// # Panics
+    ///
+    /// If the place or the transition belongs to another net, then the function panics.
+    fn check_arc_nodes(&self, place_ref: &PlaceRef, transition_ref: &TransitionRef) {
+        assert!(
+            place_ref.net == self.id && transition_ref.net == self.id,
+            "BUG: The arc between `{place_ref}` and `{transition_ref}` connects nodes of another net"
+        );
+    }
+
+    /// Sets the span of the source code being translated.
+    /// Every place and transition added afterwards is recorded as originating from this span.
+    /// Setting it to `None` stops recording the origin of the nodes.
+    pub fn set_current_span(&mut self, span: Option<SpanInfo>) {
+        self.current_span = span;
+    }
+
+    /// Returns the mapping between the transition labels and the span of the source code that produced them.
+    /// Transitions that were not produced by a specific part of the source code are not included.
+    #[must_use]
+    pub fn transition_spans(&self) -> BTreeMap<&str, &SpanInfo> {
+        self.transitions
+            .iter()
+            .filter_map(|transition| Some((&*transition.label, transition.span.as_ref()?)))
+            .collect()
+    }
+
+    /// Returns the transitions produced by the first line after `line` in the file `filename`
+    /// that produced any transition, i.e., the transitions that model the code following that line.
+    /// If no later line produced transitions, then an empty vector is returned.
+    #[must_use]
+    pub fn first_transitions_after_line(&self, filename: &str, line: usize) -> Vec<TransitionRef> {
+        let start_line = |transition: &ArenaTransition| {
+            transition
+                .span
+                .as_ref()
+                .filter(|span| span.filename == filename && span.start_line > line)
+                .map(|span| span.start_line)
+        };
+        let Some(first_line) = self.transitions.iter().filter_map(start_line).min() else {
+            return Vec::new();
+        };
+        self.transitions
+            .iter()
+            .enumerate()
+            .filter(|(_, transition)| start_line(transition) == Some(first_line))
+            .map(|(index, transition)| TransitionRef {
+                net: self.id,
+                index,
+                label: Arc::clone(&transition.label),
+            })
+            .collect()
+    }
+
+    /// Adds a copy of the transition with the given label to the net.
+    /// The copy has the same arcs and the same span as the original transition,
+    /// so both model the same step of the program.
+    /// Returns a reference to the new transition.
+    pub fn duplicate_transition(
+        &mut self,
+        transition_ref: &TransitionRef,
+        label: &str,
+    ) -> TransitionRef {
+        assert!(
+            transition_ref.net == self.id,
+            "BUG: The transition `{transition_ref}` to duplicate belongs to another net"
+        );
+        let span = self.transitions[transition_ref.index].span.clone();
+        let arcs: Vec<StoredArc> = self
+            .arcs
+            .iter()
+            .filter(|arc| arc.transition == transition_ref.index)
+            .collect();
+
+        let label = Arc::from(self.labels.reserve(label, NodeKind::Transition));
+        let index = self.transitions.len();
+        self.transitions.push(ArenaTransition {
+            label: Arc::clone(&label),
+            span,
+        });
+        for arc in arcs {
+            self.arcs.push(StoredArc {
+                transition: index,
+                ..arc
+            });
+        }
+        TransitionRef {
+            net: self.id,
+            index,
+            label,
+        }
+    }
+
+    /// Returns the labels that were already used when a place or transition was added,
+    /// together with the label assigned to the node instead.
+    #[must_use]
+    pub fn label_collisions(&self) -> &[LabelCollision] {
+        self.labels.collisions()
+    }
+
+    /// Returns the labels of the places in the order they were added.
+    #[must_use]
+    pub fn place_labels(&self) -> Vec<&str> {
+        self.places.iter().map(|place| &*place.label).collect()
+    }
+
+    /// Returns the number of places and transitions in the net.
+    #[must_use]
+    pub fn node_counts(&self) -> NodeCounts {
+        NodeCounts {
+            places: self.places.len(),
+            transitions: self.transitions.len(),
+        }
+    }
+
+    /// Returns the labels of the places and of the transitions added between the `start` and `end` node counts,
+    /// skipping the ones added between the node counts of an `excluded` range.
+    #[must_use]
+    pub fn labels_added(
+        &self,
+        start: NodeCounts,
+        end: NodeCounts,
+        excluded: &[(NodeCounts, NodeCounts)],
+    ) -> (Vec<String>, Vec<String>) {
+        let places = (start.places..end.places)
+            .filter(|index| {
+                !excluded
+                    .iter()
+                    .any(|(from, to)| (from.places..to.places).contains(index))
+            })
+            .map(|index| self.places[index].label.to_string())
+            .collect();
+        let transitions = (start.transitions..end.transitions)
+            .filter(|index| {
+                !excluded
+                    .iter()
+                    .any(|(from, to)| (from.transitions..to.transitions).contains(index))
+            })
+            .map(|index| self.transitions[index].label.to_string())
+            .collect();
+        (places, transitions)
+    }
+
+    /// Checks that every arc connects a place and a transition of the net with a positive weight,
+    /// that no arc is duplicated and that the initial marking respects the capacity of the places.
+    ///
+    /// # Errors
+    ///
+    /// If an arc references a place or a transition outside of the arena, then an error is returned.
+    /// If an arc has a weight of zero, then an error is returned.
+    /// If an arc was added more than once between the same nodes in the same direction,
+    /// then an error is returned.
+    /// If a place holds more tokens than its capacity in the initial marking, then an error is returned.
+    pub fn validate(&self) -> Result<(), String> {
+        for place in &self.places {
+            if let Some(capacity) = place.capacity.filter(|capacity| place.marking > *capacity) {
+                return Err(format!(
+                    "The place {} holds {} tokens but its capacity is {capacity}",
+                    place.label, place.marking
+                ));
+            }
+        }
+        let mut arcs = HashSet::new();
+        for arc in &self.arcs {
+            if arc.place >= self.places.len() {
+                return Err(format!("An arc references the missing place {}", arc.place));
+            }
+            if arc.transition >= self.transitions.len() {
+                return Err(format!(
+                    "An arc references the missing transition {}",
+                    arc.transition
+                ));
+            }
+            if arc.weight == 0 {
+                return Err(format!(
+                    "The arc between {} and {} has a weight of zero",
+                    self.places[arc.place].label, self.transitions[arc.transition].label
+                ));
+            }
+            if !arcs.insert((arc.place, arc.transition, arc.direction)) {
+                return Err(format!(
+                    "The arc between {} and {} was added more than once in the same direction",
+                    self.places[arc.place].label, self.transitions[arc.transition].label
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a net from its plain description, e.g. read from a JSON file.
+    /// The spans of the places and transitions, the capacities of the places and the weights of the arcs are kept.
+    ///
+    /// # Errors
+    ///
+    /// If an arc refers to a place or a transition that does not exist, then an error is returned.
+    /// If two places or two transitions have the same label, then an error is returned.
+    pub fn from_data(data: &NetData) -> Result<Self, String> {
+        let mut net = Self::new();
+        let mut places = HashMap::new();
+        for place in &data.places {
+            let place_ref = net.add_place(&place.label);
+            if *place_ref.label() != place.label {
+                return Err(format!("The place `{}` is declared twice", place.label));
+            }
+            net.places[place_ref.index].marking = place.marking;
+            net.places[place_ref.index].capacity = place.capacity;
+            net.places[place_ref.index].span.clone_from(&place.span);
+            places.insert(&*place.label, place_ref);
+        }
+        let mut transitions = HashMap::new();
+        for transition in &data.transitions {
+            net.set_current_span(transition.span.clone());
+            let transition_ref = net.add_transition(&transition.label);
+            if *transition_ref.label() != transition.label {
+                return Err(format!(
+                    "The transition `{}` is declared twice",
+                    transition.label
+                ));
+            }
+            transitions.insert(&*transition.label, transition_ref);
+        }
+        net.set_current_span(None);
+
+        for arc in &data.arcs {
+            let place_ref = places
+                .get(arc.place.as_str())
+                .ok_or_else(|| format!("The arc refers to an unknown place `{}`", arc.place))?;
+            let transition_ref = transitions.get(arc.transition.as_str()).ok_or_else(|| {
+                format!(
+                    "The arc refers to an unknown transition `{}`",
+                    arc.transition
+                )
+            })?;
+            match arc.direction {
+                ArcDirection::PlaceToTransition => {
+                    add_weighted_arc_place_transition(
+                        &mut net,
+                        place_ref,
+                        transition_ref,
+                        arc.weight,
+                    );
+                }
+                ArcDirection::TransitionToPlace => {
+                    add_weighted_arc_transition_place(
+                        &mut net,
+                        transition_ref,
+                        place_ref,
+                        arc.weight,
+                    );
+                }
+            }
+        }
+        net.validate()?;
+        Ok(net)
+    }
+
+    /// Creates a net from a net built with `netcrab`.
+    /// The places with their marking, the transitions and the arcs are read through the API of `netcrab`.
+    /// The nodes are added in the order of their labels and every arc has a weight of one.
+    #[must_use]
+    pub fn from_netcrab(net: &netcrab::petri_net::PetriNet) -> Self {
+        let mut result = Self::new();
+        let mut transitions = HashMap::new();
+        for (transition_ref, _) in net.transitions_iter() {
+            let label = transition_ref.label();
+            transitions.insert(label.clone(), result.add_transition(label));
+        }
+        for (place_ref, place) in net.places_iter() {
+            let new_place = result.add_place(place_ref.label());
+            result.places[new_place.index].marking = place.marking();
+            for transition_ref in place.get_postset() {
+                add_arc_place_transition(
+                    &mut result,
+                    &new_place,
+                    &transitions[transition_ref.label()],
+                );
+            }
+            for transition_ref in place.get_preset() {
+                add_arc_transition_place(
+                    &mut result,
+                    &transitions[transition_ref.label()],
+                    &new_place,
+                );
+            }
+        }
+        result
+    }
+
+    /// Converts the net to the net implemented in `netcrab`, labelled with the original labels.
+    /// `netcrab` has no weights, so every arc is added once regardless of its weight.
+    /// The capacities of the places and the spans of the nodes are not kept either.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid or `netcrab` rejects an element, then the function panics.
+    #[must_use]
+    pub fn to_netcrab(&self) -> netcrab::petri_net::PetriNet {
+        let data = self.data();
+        let mut net = netcrab::petri_net::PetriNet::new();
+        let mut places = HashMap::new();
+        for place in &data.places {
+            let place_ref = net.add_place(&place.label);
+            if place.marking > 0 {
+                net.add_token(&place_ref, place.marking)
+                    .unwrap_or_else(|_| {
+                        panic!(
+                            "BUG: Adding the initial marking to `{}` should not fail",
+                            place.label
+                        )
+                    });
+            }
+            places.insert(&*place.label, place_ref);
+        }
+        let mut transitions = HashMap::new();
+        for transition in &data.transitions {
+            let transition_ref = net.add_transition(&transition.label);
+            transitions.insert(&*transition.label, transition_ref);
+        }
+        for arc in &data.arcs {
+            let place_ref = &places[arc.place.as_str()];
+            let transition_ref = &transitions[arc.transition.as_str()];
+            let result = match arc.direction {
+                ArcDirection::PlaceToTransition => {
+                    net.add_arc_place_transition(place_ref, transition_ref)
+                }
+                ArcDirection::TransitionToPlace => {
+                    net.add_arc_transition_place(transition_ref, place_ref)
+                }
+            };
+            result.unwrap_or_else(|_| {
+                panic!(
+                    "BUG: Adding an arc between `{place_ref}` and `{transition_ref}` should not fail"
+                );
+            });
+        }
+        net
+    }
+
+    /// Returns the plain description of the net, e.g. to export it to JSON or to analyze it.
+    /// The arcs are listed in the order they were added.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    #[must_use]
+    pub fn data(&self) -> NetData {
+        self.expect_valid();
+        NetData {
+            places: self
+                .places
+                .iter()
+                .map(|place| PlaceData {
+                    label: place.label.to_string(),
+                    marking: place.marking,
+                    capacity: place.capacity,
+                    span: place.span.clone(),
+                })
+                .collect(),
+            transitions: self
+                .transitions
+                .iter()
+                .map(|transition| TransitionData {
+                    label: transition.label.to_string(),
+                    span: transition.span.clone(),
+                })
+                .collect(),
+            arcs: self
+                .arcs
+                .iter()
+                .map(|arc| ArcData {
+                    place: self.places[arc.place].label.to_string(),
+                    transition: self.transitions[arc.transition].label.to_string(),
+                    direction: arc.direction,
+                    weight: arc.weight,
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the net in DOT format to the given writer, see `dot_format`.
+    /// The identifiers that are not legal in DOT are quoted.
+    /// The arcs with a weight greater than one are labelled with it.
+    /// The style set with `set_dot_style` is applied last.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_dot<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        self.write_dot(writer, false)
+    }
+
+    /// Writes the net in DOT format to the given writer, with the span of the source code
+    /// that produced every node as its tooltip.
+    /// Graphviz keeps the tooltips in the SVG output, where they are shown when hovering over a node.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_dot_with_spans<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+    ) -> Result<(), std::io::Error> {
+        self.write_dot(writer, true)
+    }
+
+    /// Writes the net in DOT format to the given writer, optionally with the spans as tooltips.
+    fn write_dot<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+        with_spans: bool,
+    ) -> Result<(), std::io::Error> {
+        let identifiers = self.identifiers(IdentifierFormat::Dot);
+        let data = self.data();
+        if self.dot_style.is_plain() {
+            return write_dot(&data, &identifiers, with_spans, writer);
+        }
+        let mut dot = Vec::new();
+        write_dot(&data, &identifiers, with_spans, &mut dot)?;
+        let dot = String::from_utf8(dot).expect("BUG: The DOT output should be valid UTF-8");
+        writer.write_all(self.add_style_to_dot(&dot, &data, &identifiers).as_bytes())
+    }
+
+    /// Applies the style set with `set_dot_style` to the net in DOT format:
+    /// first the attributes of the nodes, then the direction of the ranks and the clusters.
+    fn add_style_to_dot(&self, dot: &str, data: &NetData, identifiers: &IdentifierTable) -> String {
+        let attributes: BTreeMap<&str, String> = self
+            .dot_style
+            .node_attributes(data)
+            .into_iter()
+            .map(|(label, attributes)| (identifiers.identifier(label), attributes))
+            .collect();
+        let mut dot = if attributes.is_empty() {
+            dot.to_string()
+        } else {
+            add_node_attributes_to_dot(dot, &attributes)
+        };
+        if let Some(rankdir) = &self.dot_style.rankdir {
+            dot = add_rankdir_to_dot(&dot, rankdir);
+        }
+        if self.dot_style.clusters {
+            let functions: BTreeMap<&str, &str> = functions_of_nodes(data)
+                .into_iter()
+                .map(|(label, function)| (identifiers.identifier(label), function))
+                .collect();
+            dot = add_clusters_to_dot(&dot, &functions);
+        }
+        dot
+    }
+
+    /// Writes the net in `LoLA` format to the given writer, see `lola_format`.
+    /// The labels are converted to legal names in `LoLA`, see `lola_identifiers`.
+    /// The places with a capacity are declared as `SAFE` and the arcs carry their weight.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_lola<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_lola(&self.data(), &self.lola_identifiers(), writer)
+    }
+
+    /// Writes the net in PNML format to the given writer, see `pnml_format`.
+    /// The labels are converted to XML identifiers, but the names of the nodes keep the original labels.
+    /// The weights of the arcs are their inscriptions and the capacities of the places are added as tool-specific data.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_pnml<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        self.write_pnml(writer, false)
+    }
+
+    /// Writes the net in PNML format to the given writer, with the span of the source code
+    /// that produced every node added as tool-specific data next to the capacity.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_pnml_with_spans<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+    ) -> Result<(), std::io::Error> {
+        self.write_pnml(writer, true)
+    }
+
+    /// Writes the net in PNML format to the given writer, optionally with the spans as tool-specific data.
+    fn write_pnml<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+        with_spans: bool,
+    ) -> Result<(), std::io::Error> {
+        let identifiers = self.identifiers(IdentifierFormat::Pnml);
+        write_pnml(&self.data(), &identifiers, with_spans, writer)
+    }
+
+    /// Writes the net in the given dialect of PNML to the given writer.
+    /// See the module `pnml_dialect` for the differences between the dialects.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_pnml_dialect<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+        dialect: PnmlDialect,
+    ) -> Result<(), std::io::Error> {
+        if dialect == PnmlDialect::Standard {
+            return self.to_pnml(writer);
+        }
+        let mut pnml = Vec::new();
+        self.to_pnml(&mut pnml)?;
+        let pnml = String::from_utf8(pnml).expect("BUG: The PNML output should be valid UTF-8");
+        writer.write_all(adapt_pnml(&pnml, dialect).as_bytes())
+    }
+
+    /// Writes the net in PNML format to the given writer, with the position of every node in the layout.
+    /// The positions have the origin at the top left corner, as expected by the editors.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_pnml_with_layout<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+        layout: &Layout,
+    ) -> Result<(), std::io::Error> {
+        let identifiers = self.identifiers(IdentifierFormat::Pnml);
+        let mut pnml = Vec::new();
+        self.to_pnml(&mut pnml)?;
+        let pnml = String::from_utf8(pnml).expect("BUG: The PNML output should be valid UTF-8");
+        let pnml = position_nodes(&pnml, |id| layout.top_down_position(identifiers.label(id)));
+        writer.write_all(pnml.as_bytes())
+    }
+
+    /// Writes the net in the format of the APT toolkit to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_apt<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_apt(&self.data(), writer)
+    }
+
+    /// Writes the net in the `.g` format of Petrify to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// If the net contains weighted arcs, which the format does not support, then an error is returned.
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_petrify<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_petrify(&self.data(), writer)
+    }
+
+    /// Writes the net in the `.net` format of the TINA toolbox to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_tina<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_tina(&self.data(), writer)
+    }
+
+    /// Writes the net in the `.net` format of `GreatSPN` to the given writer.
+    /// The accompanying `.def` file is written by `interchange_formats::write_greatspn_def`.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_greatspn<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_greatspn(&self.data(), writer)
+    }
+
+    /// Writes the net as a Mermaid flowchart to the given writer, e.g. to paste it into Markdown.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_mermaid<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_mermaid(&self.data(), writer)
+    }
+
+    /// Writes the net as a standalone `LaTeX` document with a `TikZ` picture to the given writer,
+    /// e.g. to include it in a paper.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the net is not valid, then the function panics.
+    pub fn to_tikz<T: std::io::Write>(&self, writer: &mut T) -> Result<(), std::io::Error> {
+        write_tikz(&self.data(), writer)
+    }
+
+    /// Sets the naming scheme for the identifiers in the `LoLA` and PNML files, see `naming::scheme`.
+    /// `None` restores the labels as identifiers. The scheme is not kept by `from_data`.
+    pub fn set_naming_scheme(&mut self, scheme: Option<Box<dyn NamingScheme>>) {
+        self.naming = scheme;
+    }
+
+    /// Sets the optional styling of the DOT output, see `dot_style`. The style is not kept by `from_data`.
+    pub fn set_dot_style(&mut self, style: DotStyle) {
+        self.dot_style = style;
+    }
+
+    /// Returns the mapping between the labels of the places and transitions and their identifiers in the format.
+    /// The identifiers in the `LoLA` and PNML formats follow the naming scheme, if one is set.
+    #[must_use]
+    pub fn identifiers(&self, format: IdentifierFormat) -> IdentifierTable {
+        let places = self.places.iter().map(|place| &*place.label);
+        let transitions = self.transitions.iter().map(|transition| &*transition.label);
+        let (Some(scheme), IdentifierFormat::Lola | IdentifierFormat::Pnml) =
+            (&self.naming, format)
+        else {
+            return IdentifierTable::new(format, places.chain(transitions));
+        };
+        let place_names = places
+            .enumerate()
+            .map(|(index, label)| (label, scheme.rename(label, NodeKind::Place, index)));
+        let transition_names = transitions
+            .enumerate()
+            .map(|(index, label)| (label, scheme.rename(label, NodeKind::Transition, index)));
+        IdentifierTable::with_names(format, place_names.chain(transition_names))
+    }
+
+    /// Returns the mapping between the labels and the names in the `LoLA` file written by `to_lola`.
+    /// It converts the places in a formula to names and the transitions in a witness path back to labels.
+    #[must_use]
+    pub fn lola_identifiers(&self) -> IdentifierTable {
+        self.identifiers(IdentifierFormat::Lola)
+    }
+
+    /// Panics if the net is not valid.
+    fn expect_valid(&self) {
+        if let Err(err_str) = self.validate() {
+            panic!("BUG: The Petri net should be valid: {err_str}");
+        }
+    }
+}
+
+/// Adds an arc from a place to a transition with weight one.
+#[inline]
+pub fn add_arc_place_transition(
+    net: &mut PetriNet,
+    place_ref: &PlaceRef,
+    transition_ref: &TransitionRef,
+) {
+    add_weighted_arc_place_transition(net, place_ref, transition_ref, 1);
+}
+
+/// Adds an arc from a transition to a place with weight one.
+#[inline]
+pub fn add_arc_transition_place(
+    net: &mut PetriNet,
+    transition_ref: &TransitionRef,
+    place_ref: &PlaceRef,
+) {
+    add_weighted_arc_transition_place(net, transition_ref, place_ref, 1);
+}
+
+/// Adds an arc from a place to a transition that consumes `weight` tokens when the transition fires.
+///
+/// # Panics
+///
+/// If the place or the transition belongs to another net, then the function panics.
+#[inline]
+pub fn add_weighted_arc_place_transition(
+    net: &mut PetriNet,
+    place_ref: &PlaceRef,
+    transition_ref: &TransitionRef,
+    weight: usize,
+) {
+    net.check_arc_nodes(place_ref, transition_ref);
+    net.arcs.push(StoredArc {
+        place: place_ref.index,
+        transition: transition_ref.index,
+        direction: ArcDirection::PlaceToTransition,
+        weight,
+    });
+}
+
+/// Adds an arc from a transition to a place that produces `weight` tokens when the transition fires.
+///
+/// # Panics
+///
+/// If the place or the transition belongs to another net, then the function panics.
+#[inline]
+pub fn add_weighted_arc_transition_place(
+    net: &mut PetriNet,
+    transition_ref: &TransitionRef,
+    place_ref: &PlaceRef,
+    weight: usize,
+) {
+    net.check_arc_nodes(place_ref, transition_ref);
+    net.arcs.push(StoredArc {
+        place: place_ref.index,
+        transition: transition_ref.index,
+        direction: ArcDirection::TransitionToPlace,
+        weight,
+    });
+}
+
+/// Connects two places through a new transition created for this purpose.
+/// Returns the new transition created with the given label.
+pub fn connect_places(
+    net: &mut PetriNet,
+    start_place: &PlaceRef,
+    end_place: &PlaceRef,
+    transition_label: &str,
+) -> TransitionRef {
+    let transition = net.add_transition(transition_label);
+    add_arc_place_transition(net, start_place, &transition);
+    add_arc_transition_place(net, &transition, end_place);
+    transition
+}
+
+#[cfg(test)]
+mod petri_net_interface_tests {
+    use super::*;
+
+    #[test]
+    fn petri_net_from_data_keeps_the_net() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("PROGRAM_START");
+        net.add_token(&start, 1).unwrap();
+        let mutex = net.add_place("MUTEX_0");
+        net.add_token(&mutex, 1).unwrap();
+        net.set_capacity(&mutex, 1).unwrap();
+        let end = net.add_place("PROGRAM_END");
+        let transition = connect_places(&mut net, &start, &end, "lock");
+        add_weighted_arc_place_transition(&mut net, &mutex, &transition, 2);
+
+        let data = net.data();
+        assert_eq!(PetriNet::from_data(&data).unwrap().data(), data);
+    }
+
+    #[test]
+    fn naming_scheme_applies_to_lola_and_pnml() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("PROGRAM_START");
+        let end = net.add_place("PROGRAM_END");
+        connect_places(&mut net, &start, &end, "main_RETURN");
+        net.set_naming_scheme(Some(Box::new(crate::naming::scheme::NumericIds)));
+
+        let lola = net.lola_identifiers();
+        assert_eq!(lola.identifier("PROGRAM_END"), "P1");
+        assert_eq!(lola.identifier("main_RETURN"), "T0");
+        assert_eq!(lola.labels_of(&["T0".to_string()]), ["main_RETURN"]);
+        assert_eq!(
+            net.identifiers(IdentifierFormat::Pnml)
+                .identifier("PROGRAM_START"),
+            "P0"
+        );
+        assert_eq!(
+            net.identifiers(IdentifierFormat::Dot)
+                .identifier("PROGRAM_START"),
+            "PROGRAM_START"
+        );
+    }
+
+    #[test]
+    fn petri_net_from_data_rejects_unknown_places() {
+        let mut data = PetriNet::new().data();
+        data.transitions.push(TransitionData {
+            label: "lock".to_string(),
+            span: None,
+        });
+        data.arcs.push(ArcData {
+            place: "MUTEX_0".to_string(),
+            transition: "lock".to_string(),
+            direction: ArcDirection::PlaceToTransition,
+            weight: 1,
+        });
+
+        let err = PetriNet::from_data(&data).err().unwrap();
+        assert_eq!(err, "The arc refers to an unknown place `MUTEX_0`");
+    }
+
+    #[test]
+    fn petri_net_add_token_returns_new_marking() {
+        let mut net = PetriNet::new();
+        let place = net.add_place("PROGRAM_START");
+
+        assert_eq!(net.add_token(&place, 1), Ok(1));
+        assert_eq!(net.add_token(&place, 2), Ok(3));
+    }
+
+    #[test]
+    fn petri_net_add_token_overflow_returns_error() {
+        let mut net = PetriNet::new();
+        let place = net.add_place("PROGRAM_START");
+        net.add_token(&place, usize::MAX).unwrap();
+
+        assert!(net.add_token(&place, 1).is_err());
+    }
+
+    #[test]
+    fn petri_net_data_lists_elements_in_insertion_order() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("START");
+        let end = net.add_place("END");
+        net.add_token(&start, 1).unwrap();
+        connect_places(&mut net, &start, &end, "CALL");
+
+        assert_eq!(
+            net.data(),
+            NetData {
+                places: vec![
+                    PlaceData {
+                        label: "START".to_string(),
+                        marking: 1,
+                        capacity: None,
+                        span: None,
+                    },
+                    PlaceData {
+                        label: "END".to_string(),
+                        marking: 0,
+                        capacity: None,
+                        span: None,
+                    },
+                ],
+                transitions: vec![TransitionData {
+                    label: "CALL".to_string(),
+                    span: None,
+                }],
+                arcs: vec![
+                    ArcData {
+                        place: "START".to_string(),
+                        transition: "CALL".to_string(),
+                        direction: ArcDirection::PlaceToTransition,
+                        weight: 1,
+                    },
+                    ArcData {
+                        place: "END".to_string(),
+                        transition: "CALL".to_string(),
+                        direction: ArcDirection::TransitionToPlace,
+                        weight: 1,
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn labels_added_skips_the_excluded_ranges() {
+        let mut net = PetriNet::new();
+        net.add_place("BEFORE");
+        let start = net.node_counts();
+        net.add_place("main_BB0");
+        net.add_transition("main_CALL_0");
+        let nested_start = net.node_counts();
+        net.add_place("callee_BB0");
+        net.add_transition("callee_RETURN");
+        let nested_end = net.node_counts();
+        net.add_transition("main_GOTO_0");
+        let end = net.node_counts();
+
+        assert_eq!(
+            net.labels_added(start, end, &[(nested_start, nested_end)]),
+            (
+                vec!["main_BB0".to_string()],
+                vec!["main_CALL_0".to_string(), "main_GOTO_0".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn petri_net_renames_nodes_with_a_label_already_used() {
+        let mut net = PetriNet::new();
+        let first = net.add_place("BB1");
+        let second = net.add_place("BB1");
+        connect_places(&mut net, &first, &second, "BB1");
+
+        assert_eq!(net.place_labels(), ["BB1", "BB1__1"]);
+        assert_eq!(net.data().transitions[0].label, "BB1__2");
+        assert_eq!(net.label_collisions().len(), 2);
+    }
+
+    #[test]
+    fn first_transitions_after_line_skips_lines_without_transitions() {
+        let span = |line: usize| SpanInfo {
+            filename: "src/main.rs".to_string(),
+            start_line: line,
+            ..SpanInfo::default()
+        };
+        let mut net = PetriNet::new();
+        for (label, line) in [("A", 2), ("B", 5), ("C", 5), ("D", 7)] {
+            net.set_current_span(Some(span(line)));
+            net.add_transition(label);
+        }
+
+        let labels = |transitions: Vec<TransitionRef>| -> Vec<String> {
+            transitions.iter().map(|t| t.label().to_string()).collect()
+        };
+        assert_eq!(
+            labels(net.first_transitions_after_line("src/main.rs", 3)),
+            ["B", "C"]
+        );
+        assert_eq!(
+            labels(net.first_transitions_after_line("src/main.rs", 5)),
+            ["D"]
+        );
+        assert!(net
+            .first_transitions_after_line("src/main.rs", 7)
+            .is_empty());
+        assert!(net.first_transitions_after_line("src/lib.rs", 0).is_empty());
+    }
+
+    #[test]
+    fn duplicate_transition_copies_the_arcs() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("START");
+        let end = net.add_place("END");
+        let call = connect_places(&mut net, &start, &end, "CALL");
+
+        net.duplicate_transition(&call, "CALL_AGAIN");
+
+        let arcs = net.data().arcs;
+        assert_eq!(arcs.len(), 4);
+        assert_eq!(arcs[2].place, "START");
+        assert_eq!(arcs[2].transition, "CALL_AGAIN");
+        assert_eq!(arcs[3].place, "END");
+        assert_eq!(arcs[3].direction, ArcDirection::TransitionToPlace);
+    }
+
+    #[test]
+    #[should_panic(expected = "connects nodes of another net")]
+    fn add_arc_rejects_place_of_other_net() {
+        let mut other_net = PetriNet::new();
+        other_net.add_place("A");
+        let place_of_other_net = other_net.add_place("B");
+        let mut net = PetriNet::new();
+        net.add_place("A");
+        net.add_place("B");
+        let transition = net.add_transition("T");
+        add_arc_place_transition(&mut net, &place_of_other_net, &transition);
+    }
+
+    #[test]
+    fn add_token_rejects_place_of_other_net() {
+        let mut other_net = PetriNet::new();
+        let place_of_other_net = other_net.add_place("A");
+        let mut net = PetriNet::new();
+        net.add_place("A");
+
+        assert_eq!(
+            net.add_token(&place_of_other_net, 1),
+            Err("The place does not belong to the net")
+        );
+    }
+
+    #[test]
+    fn petri_net_validate_rejects_marking_above_capacity() {
+        let mut net = PetriNet::new();
+        let mutex = net.add_place("MUTEX_0");
+        net.set_capacity(&mutex, 1).unwrap();
+        net.add_token(&mutex, 1).unwrap();
+        assert_eq!(net.validate(), Ok(()));
+
+        net.add_token(&mutex, 1).unwrap();
+
+        assert_eq!(
+            net.validate(),
+            Err("The place MUTEX_0 holds 2 tokens but its capacity is 1".to_string())
+        );
+    }
+
+    #[test]
+    fn from_data_keeps_the_spans_of_the_places() {
+        let mut net = PetriNet::new();
+        net.set_current_span(Some(SpanInfo::default()));
+        let start = net.add_place("START");
+        net.set_current_span(None);
+        let end = net.add_place("END");
+        connect_places(&mut net, &start, &end, "RETURN");
+
+        let data = PetriNet::from_data(&net.data()).unwrap().data();
+        assert_eq!(data.places[0].span, Some(SpanInfo::default()));
+        assert_eq!(data.places[1].span, None);
+        assert_eq!(data.transitions[0].span, None);
+    }
+
+    #[test]
+    fn petri_net_validate_rejects_duplicate_arc() {
+        let mut net = PetriNet::new();
+        let rwlock = net.add_place("RWLOCK_0");
+        let end = net.add_place("main_BB1");
+        let write = net.add_transition("write");
+        add_weighted_arc_place_transition(&mut net, &rwlock, &write, 3);
+        add_arc_transition_place(&mut net, &write, &end);
+        add_arc_place_transition(&mut net, &end, &write);
+        assert_eq!(net.validate(), Ok(()));
+
+        add_arc_place_transition(&mut net, &rwlock, &write);
+
+        assert_eq!(
+            net.validate(),
+            Err(
+                "The arc between RWLOCK_0 and write was added more than once in the same direction"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn petri_net_validate_rejects_arc_with_weight_zero() {
+        let mut net = PetriNet::new();
+        let place = net.add_place("A");
+        let transition = net.add_transition("T");
+        add_weighted_arc_place_transition(&mut net, &place, &transition, 0);
+
+        assert_eq!(
+            net.validate(),
+            Err("The arc between A and T has a weight of zero".to_string())
+        );
+    }
+
+    #[test]
+    fn to_dot_labels_the_weighted_arcs_and_quotes_illegal_identifiers() {
+        let mut net = PetriNet::new();
+        let rwlock = net.add_place("RWLOCK_0");
+        net.add_token(&rwlock, 4).unwrap();
+        let end = net.add_place("main_BB1");
+        let write = net.add_transition("new_display'_0_CALL");
+        add_weighted_arc_place_transition(&mut net, &rwlock, &write, 4);
+        add_arc_transition_place(&mut net, &write, &end);
+        let mut dot = Vec::new();
+
+        net.to_dot(&mut dot).unwrap();
+
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            "\
+digraph petrinet {
+    RWLOCK_0 [shape=\"circle\" xlabel=\"RWLOCK_0\" label=\"••••\"];
+    main_BB1 [shape=\"circle\" xlabel=\"main_BB1\" label=\"\"];
+    \"new_display'_0_CALL\" [shape=\"box\" xlabel=\"\" label=\"new_display'_0_CALL\"];
+    RWLOCK_0 -> \"new_display'_0_CALL\" [label=\"4\"];
+    \"new_display'_0_CALL\" -> main_BB1;
+}
+"
+        );
+    }
+
+    #[test]
+    fn to_lola_writes_the_weight_of_the_arcs() {
+        let mut net = PetriNet::new();
+        let rwlock = net.add_place("RWLOCK_0");
+        net.add_token(&rwlock, 4).unwrap();
+        net.set_capacity(&rwlock, 4).unwrap();
+        let end = net.add_place("main_BB1");
+        let write = net.add_transition("main_WRITE");
+        add_weighted_arc_place_transition(&mut net, &rwlock, &write, 4);
+        add_arc_transition_place(&mut net, &write, &end);
+        let mut lola = Vec::new();
+
+        net.to_lola(&mut lola).unwrap();
+
+        assert_eq!(
+            String::from_utf8(lola).unwrap(),
+            "\
+PLACE
+    SAFE 4 : RWLOCK_0;
+    main_BB1;
+
+MARKING
+    RWLOCK_0 : 4,
+    main_BB1 : 0;
+
+TRANSITION main_WRITE
+  CONSUME
+    RWLOCK_0 : 4;
+  PRODUCE
+    main_BB1 : 1;
+"
+        );
+    }
+
+    #[test]
+    fn to_pnml_writes_the_weight_as_the_inscription_of_the_arc() {
+        let mut net = PetriNet::new();
+        let rwlock = net.add_place("RWLOCK_0");
+        net.add_token(&rwlock, 4).unwrap();
+        let end = net.add_place("main_BB1");
+        let write = net.add_transition("main_WRITE");
+        add_weighted_arc_place_transition(&mut net, &rwlock, &write, 4);
+        add_arc_transition_place(&mut net, &write, &end);
+        let mut pnml = Vec::new();
+
+        net.to_pnml(&mut pnml).unwrap();
+
+        let pnml = String::from_utf8(pnml).unwrap();
+        assert!(pnml.contains(
+            "      <arc source=\"RWLOCK_0\" target=\"main_WRITE\" id=\"(RWLOCK_0, main_WRITE)\">
+        <name>
+          <text>(RWLOCK_0, main_WRITE)</text>
+        </name>
+        <inscription>
+          <text>4</text>
+        </inscription>
+      </arc>"
+        ));
+        assert!(pnml.contains(
+            "      <arc source=\"main_WRITE\" target=\"main_BB1\" id=\"(main_WRITE, main_BB1)\">
+        <name>
+          <text>(main_WRITE, main_BB1)</text>
+        </name>
+        <inscription>
+          <text>1</text>
+        </inscription>
+      </arc>"
+        ));
+    }
+
+    #[test]
+    fn to_netcrab_and_from_netcrab_keep_the_nodes_and_the_arcs() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("PROGRAM_START");
+        net.add_token(&start, 1).unwrap();
+        let end = net.add_place("PROGRAM_END");
+        connect_places(&mut net, &start, &end, "main_RETURN");
+
+        let data = PetriNet::from_netcrab(&net.to_netcrab()).data();
+
+        let mut places: Vec<_> = data
+            .places
+            .iter()
+            .map(|place| (&*place.label, place.marking))
+            .collect();
+        places.sort_unstable();
+        assert_eq!(places, [("PROGRAM_END", 0), ("PROGRAM_START", 1)]);
+        assert_eq!(data.transitions[0].label, "main_RETURN");
+        assert_eq!(data.arcs.len(), 2);
+    }
+}
@@ -0,0 +1,260 @@
+//! Module that adapts the PNML output to the dialects accepted by popular Petri net editors.
+//!
+//! The PNML written by `netcrab` follows the standard strictly, but editors reject it
+//! in different ways:
+//!
+//! - `WoPeD` <https://woped.dhbw-karlsruhe.de/> expects the older net type of the
+//!   Humboldt-Universität zu Berlin without a namespace on the root element, does not
+//!   support pages, and needs a position and a dimension for every node.
+//! - Snoopy <https://www-dssz.informatik.tu-cottbus.de/DSSZ/Software/Snoopy> keeps the
+//!   standard grammar, but needs a name for the net and a position for every node.
+//!
+//! Both editors reject arc identifiers that are not valid XML identifiers,
+//! so the arcs are renamed to `a0`, `a1`, etc. in both dialects.
+//! The nodes are laid out on a simple grid since the net has no layout information.
+//! A layout computed by Graphviz can be added to any PNML output with `position_nodes`.
+
+use std::fmt::Write;
+
+/// The PNML dialects that can be written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PnmlDialect {
+    /// Strictly standard PNML for place/transition nets.
+    #[default]
+    Standard,
+    /// PNML accepted by the editor `WoPeD`.
+    Woped,
+    /// PNML accepted by the editor Snoopy.
+    Snoopy,
+}
+
+/// The net type expected by `WoPeD`.
+const WOPED_NET_TYPE: &str = "http://www.informatik.hu-berlin.de/top/pntd/ptNetb";
+/// The number of nodes per row in the grid layout.
+const GRID_COLUMNS: usize = 10;
+/// The distance between two nodes in the grid layout.
+const GRID_SPACING: usize = 100;
+/// The size of a node in `WoPeD`.
+const WOPED_NODE_SIZE: usize = 40;
+
+/// Returns the value of the attribute `name` in the XML element of the line, if any.
+fn attribute<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(&format!(" {name}=\""))? + name.len() + 3;
+    let length = line[start..].find('"')?;
+    Some(&line[start..start + length])
+}
+
+/// Replaces the value of the attribute `name` in the XML element of the line.
+/// If the attribute is not present, the line is returned unchanged.
+fn replace_attribute(line: &str, name: &str, value: &str) -> String {
+    attribute(line, name).map_or_else(
+        || line.to_string(),
+        |old_value| {
+            line.replacen(
+                &format!(" {name}=\"{old_value}\""),
+                &format!(" {name}=\"{value}\""),
+                1,
+            )
+        },
+    )
+}
+
+/// Returns the position of the `index`-th node of a kind in the grid layout.
+/// Places and transitions are laid out in alternating rows to keep them apart.
+const fn grid_position(index: usize, is_transition: bool) -> (usize, usize) {
+    let row = 2 * (index / GRID_COLUMNS) + if is_transition { 1 } else { 0 };
+    (
+        GRID_SPACING * (index % GRID_COLUMNS) + GRID_SPACING / 2,
+        GRID_SPACING * row + GRID_SPACING / 2,
+    )
+}
+
+/// Adapts a net in standard PNML format to the given dialect.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn adapt_pnml(pnml: &str, dialect: PnmlDialect) -> String {
+    if dialect == PnmlDialect::Standard {
+        return pnml.to_string();
+    }
+    let mut output = String::with_capacity(pnml.len());
+    let mut places = 0;
+    let mut transitions = 0;
+    let mut arcs = 0;
+
+    for line in pnml.lines() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+
+        if trimmed.starts_with("<pnml") && dialect == PnmlDialect::Woped {
+            output.push_str(indent);
+            output.push_str("<pnml>\n");
+            continue;
+        }
+        if trimmed.starts_with("<page") || trimmed.starts_with("</page>") {
+            if dialect == PnmlDialect::Woped {
+                continue;
+            }
+        } else if trimmed.starts_with("<net ") {
+            let line = match dialect {
+                PnmlDialect::Woped => replace_attribute(line, "type", WOPED_NET_TYPE),
+                _ => line.to_string(),
+            };
+            output.push_str(&line);
+            output.push('\n');
+            if dialect == PnmlDialect::Snoopy {
+                let name = attribute(line.as_str(), "id").unwrap_or("net");
+                writeln!(
+                    output,
+                    "{indent}  <name>\n{indent}    <text>{name}</text>\n{indent}  </name>"
+                )
+                .expect("BUG: Writing to a string should not fail");
+            }
+            continue;
+        } else if trimmed.starts_with("<arc ") {
+            output.push_str(&replace_attribute(line, "id", &format!("a{arcs}")));
+            output.push('\n');
+            arcs += 1;
+            continue;
+        }
+
+        output.push_str(line);
+        output.push('\n');
+
+        let position = if trimmed.starts_with("<place ") {
+            places += 1;
+            grid_position(places - 1, false)
+        } else if trimmed.starts_with("<transition ") {
+            transitions += 1;
+            grid_position(transitions - 1, true)
+        } else {
+            continue;
+        };
+        let (x, y) = position;
+        let mut graphics = format!("{indent}    <position x=\"{x}\" y=\"{y}\"/>\n");
+        if dialect == PnmlDialect::Woped {
+            writeln!(
+                graphics,
+                "{indent}    <dimension x=\"{WOPED_NODE_SIZE}\" y=\"{WOPED_NODE_SIZE}\"/>"
+            )
+            .expect("BUG: Writing to a string should not fail");
+        }
+        writeln!(
+            output,
+            "{indent}  <graphics>\n{graphics}{indent}  </graphics>"
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    output
+}
+
+/// Adds the position returned by `position` for the identifier of every place and transition
+/// to the net in PNML format. Nodes without a position are left unchanged.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn position_nodes(pnml: &str, position: impl Fn(&str) -> Option<(i64, i64)>) -> String {
+    let mut output = String::with_capacity(pnml.len());
+    for line in pnml.lines() {
+        output.push_str(line);
+        output.push('\n');
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with("<place ") && !trimmed.starts_with("<transition ") {
+            continue;
+        }
+        let Some((x, y)) = attribute(line, "id").and_then(&position) else {
+            continue;
+        };
+        let indent = &line[..line.len() - trimmed.len()];
+        writeln!(
+            output,
+            "{indent}  <graphics>\n{indent}    <position x=\"{x}\" y=\"{y}\"/>\n{indent}  </graphics>"
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    output
+}
+
+#[cfg(test)]
+mod pnml_dialect_tests {
+    use super::*;
+
+    const PNML: &str = "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">
+  <net id=\"net0\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">
+    <page id=\"page0\">
+      <place id=\"PROGRAM_START\">
+        <initialMarking>
+          <text>1</text>
+        </initialMarking>
+      </place>
+      <transition id=\"main_0_CALL\">
+      </transition>
+      <arc source=\"PROGRAM_START\" target=\"main_0_CALL\" id=\"(PROGRAM_START, main_0_CALL)\">
+      </arc>
+    </page>
+  </net>
+</pnml>
+";
+
+    #[test]
+    fn standard_dialect_is_unchanged() {
+        assert_eq!(adapt_pnml(PNML, PnmlDialect::Standard), PNML);
+    }
+
+    #[test]
+    fn woped_dialect_removes_namespace_and_pages() {
+        let woped = adapt_pnml(PNML, PnmlDialect::Woped);
+
+        assert!(woped.contains("\n<pnml>\n"));
+        assert!(woped.contains(&format!("type=\"{WOPED_NET_TYPE}\"")));
+        assert!(!woped.contains("<page"));
+        assert!(!woped.contains("</page>"));
+        assert!(woped.contains("<dimension x=\"40\" y=\"40\"/>"));
+    }
+
+    #[test]
+    fn snoopy_dialect_names_the_net_and_positions_the_nodes() {
+        let snoopy = adapt_pnml(PNML, PnmlDialect::Snoopy);
+
+        assert!(snoopy.contains("<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">"));
+        assert!(snoopy.contains("<page id=\"page0\">"));
+        assert!(snoopy.contains("    <name>\n      <text>net0</text>\n    </name>\n"));
+        assert_eq!(snoopy.matches("<position x=\"50\" y=\"50\"/>").count(), 1);
+        assert_eq!(snoopy.matches("<position x=\"50\" y=\"150\"/>").count(), 1);
+        assert!(!snoopy.contains("<dimension"));
+    }
+
+    #[test]
+    fn dialects_rename_arcs_to_valid_identifiers() {
+        for dialect in [PnmlDialect::Woped, PnmlDialect::Snoopy] {
+            let pnml = adapt_pnml(PNML, dialect);
+
+            assert!(
+                pnml.contains("<arc source=\"PROGRAM_START\" target=\"main_0_CALL\" id=\"a0\">")
+            );
+        }
+    }
+
+    #[test]
+    fn position_nodes_adds_the_given_positions() {
+        let pnml = position_nodes(PNML, |id| (id == "main_0_CALL").then_some((90, 180)));
+
+        assert!(pnml.contains(
+            "<transition id=\"main_0_CALL\">\n        <graphics>\n          <position x=\"90\" y=\"180\"/>\n"
+        ));
+        assert_eq!(pnml.matches("<graphics>").count(), 1);
+    }
+
+    #[test]
+    fn grid_position_alternates_rows_of_places_and_transitions() {
+        assert_eq!(grid_position(0, false), (50, 50));
+        assert_eq!(grid_position(0, true), (50, 150));
+        assert_eq!(grid_position(11, false), (150, 250));
+    }
+}
@@ -0,0 +1,288 @@
+//! Module that writes a `NetData` in the PNML format for place/transition nets.
+//!
+//! The nodes are identified by their identifiers in the PNML format and named with their original labels.
+//! The weight of every arc is written as its inscription:
+//!
+//! ```xml
+//! <arc source="RWLOCK_0" target="write" id="(RWLOCK_0, write)">
+//!   <name>
+//!     <text>(RWLOCK_0, write)</text>
+//!   </name>
+//!   <inscription>
+//!     <text>4</text>
+//!   </inscription>
+//! </arc>
+//! ```
+//!
+//! PNML for place/transition nets has no element for the capacity of the places
+//! or for the span of the source code that produced the nodes, so they are added
+//! as tool-specific data at the end of the node, which other tools ignore:
+//!
+//! ```xml
+//! <toolspecific tool="cargo-check-deadlock" version="1">
+//!   <capacity>1</capacity>
+//!   <span file="src/main.rs" start-line="3" start-column="15" end-line="3" end-column="26"/>
+//! </toolspecific>
+//! ```
+//!
+//! The places and the transitions are written sorted by their identifiers,
+//! followed by the arcs from places to transitions and the arcs from transitions to places.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::data_structures::identifiers::IdentifierTable;
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::span_info::SpanInfo;
+
+/// The name of the tool in the tool-specific data of the PNML output.
+const PNML_TOOL: &str = "cargo-check-deadlock";
+/// The version of the format of the tool-specific data in the PNML output.
+const PNML_TOOL_VERSION: &str = "1";
+
+/// Writes the net in PNML format to the given writer, optionally with the spans as tool-specific data.
+/// The capacities of the places are always written as tool-specific data.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_pnml<T: Write>(
+    net: &NetData,
+    identifiers: &IdentifierTable,
+    with_spans: bool,
+    writer: &mut T,
+) -> Result<(), std::io::Error> {
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        writer,
+        "<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">"
+    )?;
+    writeln!(
+        writer,
+        "  <net id=\"net0\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">"
+    )?;
+    writeln!(writer, "    <page id=\"page0\">")?;
+
+    let mut places: Vec<_> = net
+        .places
+        .iter()
+        .map(|place| (identifiers.identifier(&place.label), place))
+        .collect();
+    places.sort_unstable_by_key(|(id, _)| *id);
+    for (id, place) in places {
+        writeln!(writer, "      <place id=\"{id}\">")?;
+        write_name(writer, &place.label)?;
+        if place.marking > 0 {
+            writeln!(writer, "        <initialMarking>")?;
+            writeln!(writer, "          <text>{}</text>", place.marking)?;
+            writeln!(writer, "        </initialMarking>")?;
+        }
+        let span = place.span.as_ref().filter(|_| with_spans);
+        write_tool_data(writer, place.capacity, span)?;
+        writeln!(writer, "      </place>")?;
+    }
+
+    let mut transitions: Vec<_> = net
+        .transitions
+        .iter()
+        .map(|transition| (identifiers.identifier(&transition.label), transition))
+        .collect();
+    transitions.sort_unstable_by_key(|(id, _)| *id);
+    for (id, transition) in transitions {
+        writeln!(writer, "      <transition id=\"{id}\">")?;
+        write_name(writer, &transition.label)?;
+        let span = transition.span.as_ref().filter(|_| with_spans);
+        write_tool_data(writer, None, span)?;
+        writeln!(writer, "      </transition>")?;
+    }
+
+    let mut inputs: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    let mut outputs: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for arc in &net.arcs {
+        let place = identifiers.identifier(&arc.place);
+        let transition = identifiers.identifier(&arc.transition);
+        let (arcs, edge) = match arc.direction {
+            ArcDirection::PlaceToTransition => (&mut inputs, (place, transition)),
+            ArcDirection::TransitionToPlace => (&mut outputs, (transition, place)),
+        };
+        *arcs.entry(edge).or_default() += arc.weight;
+    }
+    for ((source, target), weight) in inputs.into_iter().chain(outputs) {
+        let id = format!("({source}, {target})");
+        writeln!(
+            writer,
+            "      <arc source=\"{source}\" target=\"{target}\" id=\"{id}\">"
+        )?;
+        writeln!(writer, "        <name>")?;
+        writeln!(writer, "          <text>{id}</text>")?;
+        writeln!(writer, "        </name>")?;
+        writeln!(writer, "        <inscription>")?;
+        writeln!(writer, "          <text>{weight}</text>")?;
+        writeln!(writer, "        </inscription>")?;
+        writeln!(writer, "      </arc>")?;
+    }
+
+    writeln!(writer, "    </page>")?;
+    writeln!(writer, "  </net>")?;
+    write!(writer, "</pnml>")
+}
+
+/// Writes the name of a node, which is its original label.
+fn write_name<T: Write>(writer: &mut T, label: &str) -> Result<(), std::io::Error> {
+    writeln!(writer, "        <name>")?;
+    writeln!(writer, "          <text>{}</text>", escape_xml(label))?;
+    writeln!(writer, "        </name>")
+}
+
+/// Writes the capacity and the span of a node as tool-specific data.
+/// Nodes with neither a capacity nor a span get no tool-specific data.
+fn write_tool_data<T: Write>(
+    writer: &mut T,
+    capacity: Option<usize>,
+    span: Option<&SpanInfo>,
+) -> Result<(), std::io::Error> {
+    if capacity.is_none() && span.is_none() {
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "        <toolspecific tool=\"{PNML_TOOL}\" version=\"{PNML_TOOL_VERSION}\">"
+    )?;
+    if let Some(capacity) = capacity {
+        writeln!(writer, "          <capacity>{capacity}</capacity>")?;
+    }
+    if let Some(span) = span {
+        writeln!(
+            writer,
+            "          <span file=\"{}\" start-line=\"{}\" start-column=\"{}\" end-line=\"{}\" end-column=\"{}\"/>",
+            escape_xml(&span.filename),
+            span.start_line,
+            span.start_column,
+            span.end_line,
+            span.end_column
+        )?;
+    }
+    writeln!(writer, "        </toolspecific>")
+}
+
+/// Escapes the characters that are not allowed in the text or the attributes of an XML element.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod pnml_format_tests {
+    use super::*;
+    use crate::data_structures::identifiers::IdentifierFormat;
+    use crate::data_structures::lola_format::parse_lola;
+
+    /// Writes the net in PNML format with the identifiers of the labels.
+    fn pnml(net: &NetData, with_spans: bool) -> String {
+        let labels = net.places.iter().map(|place| place.label.as_str()).chain(
+            net.transitions
+                .iter()
+                .map(|transition| transition.label.as_str()),
+        );
+        let identifiers = IdentifierTable::new(IdentifierFormat::Pnml, labels);
+        let mut output = Vec::new();
+        write_pnml(net, &identifiers, with_spans, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn write_pnml_writes_the_weight_as_the_inscription() {
+        let net = parse_lola(
+            "PLACE SAFE 4 : RWLOCK_0; main_BB1;\nMARKING RWLOCK_0 : 4;\n\
+             TRANSITION write CONSUME RWLOCK_0 : 4; PRODUCE main_BB1 : 1;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            pnml(&net, false),
+            "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">
+  <net id=\"net0\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">
+    <page id=\"page0\">
+      <place id=\"RWLOCK_0\">
+        <name>
+          <text>RWLOCK_0</text>
+        </name>
+        <initialMarking>
+          <text>4</text>
+        </initialMarking>
+        <toolspecific tool=\"cargo-check-deadlock\" version=\"1\">
+          <capacity>4</capacity>
+        </toolspecific>
+      </place>
+      <place id=\"main_BB1\">
+        <name>
+          <text>main_BB1</text>
+        </name>
+      </place>
+      <transition id=\"write\">
+        <name>
+          <text>write</text>
+        </name>
+      </transition>
+      <arc source=\"RWLOCK_0\" target=\"write\" id=\"(RWLOCK_0, write)\">
+        <name>
+          <text>(RWLOCK_0, write)</text>
+        </name>
+        <inscription>
+          <text>4</text>
+        </inscription>
+      </arc>
+      <arc source=\"write\" target=\"main_BB1\" id=\"(write, main_BB1)\">
+        <name>
+          <text>(write, main_BB1)</text>
+        </name>
+        <inscription>
+          <text>1</text>
+        </inscription>
+      </arc>
+    </page>
+  </net>
+</pnml>"
+        );
+    }
+
+    #[test]
+    fn write_pnml_keeps_the_original_labels_as_names() {
+        let mut net =
+            parse_lola("PLACE a;\nMARKING ;\nTRANSITION t CONSUME a : 1; PRODUCE ;").unwrap();
+        net.transitions[0].label = "new_display'_0_CALL".to_string();
+        net.arcs[0].transition = "new_display'_0_CALL".to_string();
+
+        let pnml = pnml(&net, false);
+
+        assert!(pnml.contains(
+            "      <transition id=\"new_display__0_CALL\">\n        <name>\n          <text>new_display'_0_CALL</text>\n"
+        ));
+        assert!(pnml.contains("<arc source=\"a\" target=\"new_display__0_CALL\""));
+    }
+
+    #[test]
+    fn write_pnml_adds_the_spans_only_when_requested() {
+        let mut net =
+            parse_lola("PLACE a;\nMARKING a : 1;\nTRANSITION t CONSUME a : 1; PRODUCE ;").unwrap();
+        net.transitions[0].span = Some(SpanInfo {
+            filename: "src/<main>.rs".to_string(),
+            start_line: 3,
+            start_column: 15,
+            end_line: 3,
+            end_column: 26,
+        });
+
+        assert!(!pnml(&net, false).contains("<toolspecific"));
+        assert!(pnml(&net, true).contains(
+            "        <toolspecific tool=\"cargo-check-deadlock\" version=\"1\">
+          <span file=\"src/&lt;main&gt;.rs\" start-line=\"3\" start-column=\"15\" end-line=\"3\" end-column=\"26\"/>
+        </toolspecific>
+      </transition>"
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+//! Module that implements a simple self-profiler for the translation.
+//!
+//! The time is measured for a stack of named frames, e.g. the translated functions
+//! and the phases of the translation within them (MIR query, visiting, net insertion, export).
+//! When a frame is exited, its self time, i.e. the time not spent in nested frames,
+//! is added to the total of the stack of frames that leads to it.
+//!
+//! The result is written in the collapsed stack format used by flamegraph tools:
+//! One line per stack with the frames separated by semicolons, followed by the self time in microseconds.
+//! <https://github.com/brendangregg/FlameGraph#2-fold-stacks>
+//!
+//! A disabled profiler ignores every call, so it can be kept in place without measurable overhead.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Name of the frame for the whole translation.
+pub const TRANSLATION_FRAME: &str = "translation";
+/// Name of the frame for the query of the MIR body of a function to the compiler.
+pub const MIR_QUERY_FRAME: &str = "mir_query";
+/// Name of the frame for visiting the MIR body of a function.
+pub const VISIT_FRAME: &str = "visit";
+/// Name of the frame for adding places, transitions and arcs to the net.
+pub const NET_INSERTION_FRAME: &str = "net_insertion";
+/// Name of the frame for writing the output files.
+pub const EXPORT_FRAME: &str = "export";
+
+/// A frame currently on the stack of the profiler.
+struct Frame {
+    /// The name of the frame.
+    name: String,
+    /// The moment the frame was entered.
+    start: Instant,
+    /// The time spent in nested frames so far.
+    children: Duration,
+}
+
+/// The profiler that records the time spent in each stack of frames.
+#[derive(Default)]
+pub struct Profiler {
+    /// Whether the profiler records anything.
+    enabled: bool,
+    /// The frames entered and not exited yet.
+    stack: Vec<Frame>,
+    /// The self time of every stack of frames, indexed by the collapsed stack.
+    totals: BTreeMap<String, Duration>,
+}
+
+impl Profiler {
+    /// Creates a new `Profiler` that records the time spent in each frame.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a new `Profiler` that ignores every call.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether the profiler records anything.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enters a new frame with the given name nested in the current frame.
+    /// Semicolons in the name are replaced with underscores since they separate the frames.
+    pub fn enter(&mut self, name: &str) {
+        if !self.enabled {
+            return;
+        }
+        self.stack.push(Frame {
+            name: name.replace(';', "_"),
+            start: Instant::now(),
+            children: Duration::ZERO,
+        });
+    }
+
+    /// Exits the current frame and records its self time.
+    ///
+    /// # Panics
+    ///
+    /// If no frame was entered, then the function panics.
+    pub fn exit(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        let collapsed_stack = self.collapsed_stack();
+        let frame = self
+            .stack
+            .pop()
+            .expect("BUG: The profiler should not exit a frame that was not entered");
+        let elapsed = frame.start.elapsed();
+        *self.totals.entry(collapsed_stack).or_default() += elapsed.saturating_sub(frame.children);
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children += elapsed;
+        }
+    }
+
+    /// Returns the self time recorded for the given collapsed stack, e.g. `translation;main;visit`.
+    #[must_use]
+    pub fn get_total(&self, collapsed_stack: &str) -> Option<Duration> {
+        self.totals.get(collapsed_stack).copied()
+    }
+
+    /// Writes the self time of every stack of frames in the collapsed stack format.
+    /// The stacks are sorted alphabetically and the time is given in microseconds.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    pub fn write_collapsed_stacks<T: std::io::Write>(
+        &self,
+        writer: &mut T,
+    ) -> Result<(), std::io::Error> {
+        for (stack, total) in &self.totals {
+            writeln!(writer, "{stack} {}", total.as_micros())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the frames on the stack separated by semicolons.
+    fn collapsed_stack(&self) -> String {
+        self.stack
+            .iter()
+            .map(|frame| frame.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(";")
+    }
+}
+
+#[cfg(test)]
+mod profiler_tests {
+    use super::*;
+
+    #[test]
+    fn profiler_disabled_records_nothing() {
+        let mut profiler = Profiler::disabled();
+        profiler.enter("translation");
+        profiler.exit();
+
+        assert!(!profiler.is_enabled());
+        assert_eq!(profiler.get_total("translation"), None);
+    }
+
+    #[test]
+    fn profiler_records_nested_frames() {
+        let mut profiler = Profiler::new();
+        profiler.enter("translation");
+        profiler.enter("main");
+        profiler.exit();
+        profiler.exit();
+
+        assert!(profiler.get_total("translation").is_some());
+        assert!(profiler.get_total("translation;main").is_some());
+        assert_eq!(profiler.get_total("main"), None);
+    }
+
+    #[test]
+    fn profiler_self_time_excludes_nested_frames() {
+        let mut profiler = Profiler::new();
+        profiler.enter("translation");
+        profiler.enter("main");
+        std::thread::sleep(Duration::from_millis(20));
+        profiler.exit();
+        profiler.exit();
+
+        let parent = profiler.get_total("translation").unwrap();
+        let child = profiler.get_total("translation;main").unwrap();
+        assert!(child >= Duration::from_millis(20));
+        assert!(parent < child);
+    }
+
+    #[test]
+    fn profiler_accumulates_repeated_stacks() {
+        let mut profiler = Profiler::new();
+        profiler.enter("export");
+        std::thread::sleep(Duration::from_millis(5));
+        profiler.exit();
+        profiler.enter("export");
+        std::thread::sleep(Duration::from_millis(5));
+        profiler.exit();
+
+        assert!(profiler.get_total("export").unwrap() >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn profiler_writes_one_line_per_stack() {
+        let mut profiler = Profiler::new();
+        profiler.enter("translation");
+        profiler.enter("std::iter::Iterator::map;closure");
+        profiler.exit();
+        profiler.exit();
+
+        let mut output = Vec::new();
+        profiler.write_collapsed_stacks(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let stacks: Vec<&str> = output
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().0)
+            .collect();
+
+        assert_eq!(
+            stacks,
+            vec![
+                "translation",
+                "translation;std::iter::Iterator::map_closure"
+            ]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Module that implements a compiler-independent representation of a source code span.
+//!
+//! It stores the location of a piece of source code as plain strings and numbers.
+//! This allows keeping the information after the compiler session has ended,
+//! e.g., to generate reports once the translation has finished.
+
+use serde::{Deserialize, Serialize};
+
+/// A location in the source code, delimited by a start and an end position.
+/// Lines and columns are 1-based, as printed by the compiler in its diagnostics.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SpanInfo {
+    /// The path of the source code file.
+    pub filename: String,
+    /// The line where the span starts.
+    pub start_line: usize,
+    /// The column where the span starts.
+    pub start_column: usize,
+    /// The line where the span ends.
+    pub end_line: usize,
+    /// The column where the span ends.
+    pub end_column: usize,
+}
+
+impl std::fmt::Display for SpanInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.filename, self.start_line, self.start_column
+        )
+    }
+}
+
+#[cfg(test)]
+mod span_info_tests {
+    use super::*;
+
+    #[test]
+    fn span_info_display_shows_start_position() {
+        let span = SpanInfo {
+            filename: "src/main.rs".to_string(),
+            start_line: 3,
+            start_column: 15,
+            end_line: 3,
+            end_column: 26,
+        };
+
+        assert_eq!(span.to_string(), "src/main.rs:3:15");
+    }
+}
@@ -0,0 +1,318 @@
+//! Module that implements a registry of the synchronization primitives
+//! discovered during the translation.
+//!
+//! Every mutex, read-write lock, condition variable, barrier, channel, one-time initialization, atomic variable and thread found in the code is registered
+//! together with the span where it was created and the label used for it in the Petri net.
+//! Every supported function call that operates on a registered primitive
+//! (e.g. `lock`, `read`, `wait`, `notify_one`, `send` or `join`) is recorded as a call site of the primitive.
+//! The notes of a primitive explain how the translation modelled it, e.g. a thread recognized as the worker of a pool.
+//! A primitive that may be aliased by `unsafe` code, e.g. through a transmute, is marked with unknown aliasing.
+//!
+//! The registry is returned as part of the translation result.
+//! It does not depend on rustc internals and can be used freely after the translation.
+
+use crate::data_structures::span_info::SpanInfo;
+
+/// The kinds of synchronization primitives tracked by the translation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SyncKind {
+    Mutex,
+    RwLock,
+    Condvar,
+    Barrier,
+    Channel,
+    Once,
+    Atomic,
+    Thread,
+}
+
+impl std::fmt::Display for SyncKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mutex => write!(f, "mutex"),
+            Self::RwLock => write!(f, "read-write lock"),
+            Self::Condvar => write!(f, "condition variable"),
+            Self::Barrier => write!(f, "barrier"),
+            Self::Channel => write!(f, "channel"),
+            Self::Once => write!(f, "one-time initialization"),
+            Self::Atomic => write!(f, "atomic variable"),
+            Self::Thread => write!(f, "thread"),
+        }
+    }
+}
+
+/// A function call that uses a synchronization primitive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallSite {
+    /// The name of the function called, e.g. `std::sync::Mutex::<T>::lock`.
+    pub function_name: String,
+    /// The name of the function where the call happens, as used in the Petri net labels.
+    pub caller_name: String,
+    /// The span of the function call in the source code.
+    pub span: SpanInfo,
+}
+
+/// A synchronization primitive discovered during the translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyncObject {
+    /// The kind of synchronization primitive.
+    pub kind: SyncKind,
+    /// The label that identifies the primitive in the Petri net, e.g. `MUTEX_0`.
+    pub label: String,
+    /// The span of the function call that created the primitive.
+    pub span: SpanInfo,
+    /// The function calls that use the primitive, in the order they were translated.
+    pub call_sites: Vec<CallSite>,
+    /// The notes about how the primitive was modelled, in the order they were added.
+    pub notes: Vec<String>,
+    /// The spans of the `unsafe` operations that may alias the primitive through a place that is not tracked.
+    /// If not empty, the calls on the primitive through the alias are missing in the net.
+    pub unknown_aliasing: Vec<SpanInfo>,
+}
+
+#[derive(Default)]
+pub struct SyncRegistry {
+    objects: Vec<SyncObject>,
+}
+
+impl SyncRegistry {
+    /// Creates a new empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new synchronization primitive without call sites.
+    ///
+    /// # Panics
+    ///
+    /// If a primitive with the same label was already registered, then the function panics.
+    pub fn add(&mut self, kind: SyncKind, label: &str, span: SpanInfo) {
+        assert!(
+            self.get(label).is_none(),
+            "BUG: The synchronization primitive `{label}` should only be registered once"
+        );
+        self.objects.push(SyncObject {
+            kind,
+            label: label.to_string(),
+            span,
+            call_sites: Vec::new(),
+            notes: Vec::new(),
+            unknown_aliasing: Vec::new(),
+        });
+    }
+
+    /// Adds a call site to the synchronization primitive with the given label.
+    ///
+    /// # Panics
+    ///
+    /// If there is no primitive registered with the given label, then the function panics.
+    pub fn add_call_site(
+        &mut self,
+        label: &str,
+        function_name: &str,
+        caller_name: &str,
+        span: SpanInfo,
+    ) {
+        let object = self
+            .objects
+            .iter_mut()
+            .find(|object| object.label == label)
+            .unwrap_or_else(|| {
+                panic!("BUG: The synchronization primitive `{label}` should be registered")
+            });
+        object.call_sites.push(CallSite {
+            function_name: function_name.to_string(),
+            caller_name: caller_name.to_string(),
+            span,
+        });
+    }
+
+    /// Adds a note about how the synchronization primitive with the given label was modelled.
+    ///
+    /// # Panics
+    ///
+    /// If there is no primitive registered with the given label, then the function panics.
+    pub fn add_note(&mut self, label: &str, note: &str) {
+        let object = self
+            .objects
+            .iter_mut()
+            .find(|object| object.label == label)
+            .unwrap_or_else(|| {
+                panic!("BUG: The synchronization primitive `{label}` should be registered")
+            });
+        object.notes.push(note.to_string());
+    }
+
+    /// Marks the synchronization primitive with the given label as possibly aliased
+    /// by the `unsafe` operation at the given span. Every span is recorded once.
+    ///
+    /// # Panics
+    ///
+    /// If there is no primitive registered with the given label, then the function panics.
+    pub fn mark_unknown_aliasing(&mut self, label: &str, span: SpanInfo) {
+        let object = self
+            .objects
+            .iter_mut()
+            .find(|object| object.label == label)
+            .unwrap_or_else(|| {
+                panic!("BUG: The synchronization primitive `{label}` should be registered")
+            });
+        if !object.unknown_aliasing.contains(&span) {
+            object.unknown_aliasing.push(span);
+        }
+    }
+
+    /// Returns the synchronization primitive with the given label, if it was registered.
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<&SyncObject> {
+        self.objects.iter().find(|object| object.label == label)
+    }
+
+    /// Returns an iterator over the synchronization primitives in the order they were discovered.
+    pub fn iter(&self) -> std::slice::Iter<'_, SyncObject> {
+        self.objects.iter()
+    }
+
+    /// Returns the number of synchronization primitives of the given kind.
+    #[must_use]
+    pub fn count(&self, kind: SyncKind) -> usize {
+        self.objects
+            .iter()
+            .filter(|object| object.kind == kind)
+            .count()
+    }
+
+    /// Returns the total number of synchronization primitives registered.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Checks whether no synchronization primitive was registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a SyncRegistry {
+    type Item = &'a SyncObject;
+    type IntoIter = std::slice::Iter<'a, SyncObject>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Display for SyncRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for object in &self.objects {
+            writeln!(
+                f,
+                "{} {} created at {}",
+                object.kind, object.label, object.span
+            )?;
+            for note in &object.notes {
+                writeln!(f, "    note: {note}")?;
+            }
+            for span in &object.unknown_aliasing {
+                writeln!(f, "    unknown aliasing through `unsafe` code at {span}")?;
+            }
+            for call_site in &object.call_sites {
+                writeln!(
+                    f,
+                    "    {} in {} at {}",
+                    call_site.function_name, call_site.caller_name, call_site.span
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod sync_registry_tests {
+    use super::*;
+
+    #[test]
+    fn sync_registry_new_is_empty() {
+        let registry = SyncRegistry::new();
+
+        assert!(registry.is_empty());
+        assert_eq!(registry.len(), 0);
+    }
+
+    #[test]
+    fn sync_registry_add_updates_counters() {
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.add(SyncKind::Mutex, "MUTEX_1", SpanInfo::default());
+        registry.add(SyncKind::Thread, "THREAD_0", SpanInfo::default());
+
+        assert_eq!(registry.len(), 3);
+        assert_eq!(registry.count(SyncKind::Mutex), 2);
+        assert_eq!(registry.count(SyncKind::Thread), 1);
+        assert_eq!(registry.count(SyncKind::Condvar), 0);
+    }
+
+    #[test]
+    fn sync_registry_add_call_site_is_stored_in_order() {
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.add_call_site("MUTEX_0", "lock_a", "main", SpanInfo::default());
+        registry.add_call_site("MUTEX_0", "lock_b", "main", SpanInfo::default());
+
+        let object = registry.get("MUTEX_0").unwrap();
+        assert_eq!(object.call_sites.len(), 2);
+        assert_eq!(object.call_sites[0].function_name, "lock_a");
+        assert_eq!(object.call_sites[1].function_name, "lock_b");
+    }
+
+    #[test]
+    fn sync_registry_display_lists_the_notes() {
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Thread, "THREAD_0", SpanInfo::default());
+        registry.add_note("THREAD_0", "worker of a pool");
+
+        assert_eq!(
+            registry.get("THREAD_0").unwrap().notes,
+            ["worker of a pool"]
+        );
+        assert!(registry
+            .to_string()
+            .ends_with("\n    note: worker of a pool\n"));
+    }
+
+    #[test]
+    fn sync_registry_mark_unknown_aliasing_records_every_span_once() {
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.mark_unknown_aliasing("MUTEX_0", SpanInfo::default());
+        registry.mark_unknown_aliasing("MUTEX_0", SpanInfo::default());
+
+        assert_eq!(registry.get("MUTEX_0").unwrap().unknown_aliasing.len(), 1);
+        assert!(registry
+            .to_string()
+            .contains("\n    unknown aliasing through `unsafe` code at "));
+    }
+
+    #[test]
+    #[should_panic(expected = "BUG: The synchronization primitive `MUTEX_0` should be registered")]
+    fn sync_registry_add_call_site_to_unknown_label_panics() {
+        let mut registry = SyncRegistry::new();
+
+        registry.add_call_site("MUTEX_0", "lock", "main", SpanInfo::default());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "BUG: The synchronization primitive `MUTEX_0` should only be registered once"
+    )]
+    fn sync_registry_add_same_label_twice_panics() {
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+    }
+}
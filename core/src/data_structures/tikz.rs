@@ -0,0 +1,246 @@
+//! Module that implements the export of the `NetData` to a standalone `TikZ` picture in `LaTeX`.
+//! See <https://tikz.dev/library-petri>.
+//!
+//! The document compiles on its own with `pdflatex` and the picture can be copied into a paper or a thesis.
+//! The places are drawn as circles with the initial marking inside and the transitions as rectangles,
+//! using the `petri` library of `TikZ`. The nodes are identified by their position in the net, e.g. `P0` or `T3`,
+//! and show the label next to them.
+//!
+//! The nodes are laid out in layers, one column per function:
+//! The places of the basic blocks of a function are stacked from top to bottom in the order of the net,
+//! each one followed by the transitions that leave it. The other places, e.g. the places of the mutexes,
+//! and the transitions without an input basic block go to the first column.
+
+use std::collections::HashMap;
+use std::io::{Error, Write};
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::naming::basic_block::function_of_place_label;
+
+/// The horizontal distance between two columns in centimeters.
+const COLUMN_WIDTH: usize = 5;
+
+/// Escapes the characters of a label that have a special meaning in `LaTeX`.
+fn escape_latex(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for character in label.chars() {
+        match character {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '_' | '&' | '%' | '$' | '#' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// A column of the picture with the nodes in it from top to bottom.
+#[derive(Default)]
+struct Column<'a> {
+    /// The name of the function, or `None` for the first column.
+    function: Option<&'a str>,
+    /// The nodes in the column, e.g. `P0` or `T3`.
+    nodes: Vec<String>,
+}
+
+/// Assigns every place and transition to a column and a row.
+fn columns<'a>(
+    net: &'a NetData,
+    place_indices: &HashMap<&str, usize>,
+    transition_indices: &HashMap<&str, usize>,
+) -> Vec<Column<'a>> {
+    // The transitions that leave each place, by the index of the place.
+    let mut leaving: Vec<Vec<usize>> = vec![Vec::new(); net.places.len()];
+    let mut placed = vec![false; net.transitions.len()];
+    for arc in &net.arcs {
+        if arc.direction != ArcDirection::PlaceToTransition
+            || function_of_place_label(&arc.place).is_none()
+        {
+            continue;
+        }
+        let transition = transition_indices[arc.transition.as_str()];
+        if !placed[transition] {
+            placed[transition] = true;
+            leaving[place_indices[arc.place.as_str()]].push(transition);
+        }
+    }
+
+    let mut columns = vec![Column::default()];
+    let mut function_columns: HashMap<&str, usize> = HashMap::new();
+    for (index, place) in net.places.iter().enumerate() {
+        let column = function_of_place_label(&place.label).map_or(0, |function| {
+            *function_columns.entry(function).or_insert_with(|| {
+                columns.push(Column {
+                    function: Some(function),
+                    nodes: Vec::new(),
+                });
+                columns.len() - 1
+            })
+        });
+        columns[column].nodes.push(format!("P{index}"));
+        columns[column].nodes.extend(
+            leaving[index]
+                .iter()
+                .map(|transition| format!("T{transition}")),
+        );
+    }
+    for (index, _) in placed.iter().enumerate().filter(|(_, placed)| !**placed) {
+        columns[0].nodes.push(format!("T{index}"));
+    }
+    columns
+}
+
+/// Writes the net as a standalone `LaTeX` document with a `TikZ` picture to the given writer.
+///
+/// # Errors
+///
+/// If writing to the writer fails, then an error is returned.
+pub fn write_tikz<T: Write>(net: &NetData, writer: &mut T) -> Result<(), Error> {
+    writeln!(writer, "\\documentclass[tikz]{{standalone}}")?;
+    writeln!(writer, "\\usetikzlibrary{{petri,arrows.meta}}")?;
+    writeln!(writer, "\\begin{{document}}")?;
+    writeln!(
+        writer,
+        "\\begin{{tikzpicture}}[y=-1.2cm, >=Stealth, every label/.style={{font=\\scriptsize}}]"
+    )?;
+
+    let place_indices: HashMap<&str, usize> = net
+        .places
+        .iter()
+        .enumerate()
+        .map(|(index, place)| (place.label.as_str(), index))
+        .collect();
+    let transition_indices: HashMap<&str, usize> = net
+        .transitions
+        .iter()
+        .enumerate()
+        .map(|(index, transition)| (transition.label.as_str(), index))
+        .collect();
+    let mut positions = HashMap::new();
+    for (column_index, column) in columns(net, &place_indices, &transition_indices)
+        .into_iter()
+        .enumerate()
+    {
+        let x = column_index * COLUMN_WIDTH;
+        if let Some(function) = column.function {
+            writeln!(
+                writer,
+                "  \\node[font=\\bfseries] at ({x}, -1) {{{}}};",
+                escape_latex(function)
+            )?;
+        }
+        for (row, node) in column.nodes.into_iter().enumerate() {
+            positions.insert(node, (x, row));
+        }
+    }
+    for (index, place) in net.places.iter().enumerate() {
+        let (x, y) = positions[&format!("P{index}")];
+        let marking = if place.marking > 0 {
+            place.marking.to_string()
+        } else {
+            String::new()
+        };
+        writeln!(
+            writer,
+            "  \\node[place, label=right:{{{}}}] (P{index}) at ({x}, {y}) {{{marking}}};",
+            escape_latex(&place.label)
+        )?;
+    }
+    for (index, transition) in net.transitions.iter().enumerate() {
+        let (x, y) = positions[&format!("T{index}")];
+        writeln!(
+            writer,
+            "  \\node[transition, label=right:{{{}}}] (T{index}) at ({x}, {y}) {{}};",
+            escape_latex(&transition.label)
+        )?;
+    }
+
+    // Repeated arcs are drawn once with the sum of their weights, in the order of their first appearance.
+    let mut arcs: Vec<((usize, usize, ArcDirection), usize)> = Vec::new();
+    for arc in &net.arcs {
+        let key = (
+            place_indices[arc.place.as_str()],
+            transition_indices[arc.transition.as_str()],
+            arc.direction,
+        );
+        match arcs.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, multiplicity)) => *multiplicity += arc.weight,
+            None => arcs.push((key, arc.weight)),
+        }
+    }
+    for ((place, transition, direction), multiplicity) in arcs {
+        let (source, target) = match direction {
+            ArcDirection::PlaceToTransition => (format!("P{place}"), format!("T{transition}")),
+            ArcDirection::TransitionToPlace => (format!("T{transition}"), format!("P{place}")),
+        };
+        if multiplicity == 1 {
+            writeln!(writer, "  \\draw[->] ({source}) -- ({target});")?;
+        } else {
+            writeln!(
+                writer,
+                "  \\draw[->] ({source}) -- node[auto, font=\\scriptsize] {{{multiplicity}}} ({target});"
+            )?;
+        }
+    }
+
+    writeln!(writer, "\\end{{tikzpicture}}")?;
+    writeln!(writer, "\\end{{document}}")
+}
+
+#[cfg(test)]
+mod tikz_tests {
+    use super::*;
+    use crate::data_structures::net_builder::NetBuilder;
+
+    #[test]
+    fn escape_latex_escapes_special_characters() {
+        assert_eq!(escape_latex("main_BB1"), "main\\_BB1");
+        assert_eq!(
+            escape_latex("a&b%c$d#e{f}g~h^i\\"),
+            "a\\&b\\%c\\$d\\#e\\{f\\}g\\textasciitilde{}h\\textasciicircum{}i\\textbackslash{}"
+        );
+    }
+
+    #[test]
+    fn write_tikz_lays_out_one_column_per_function() {
+        let net = NetBuilder::new()
+            .place("RWLOCK_0", 4)
+            .place("main_BB0", 1)
+            .place("main_BB1", 0)
+            .transition("main_WRITE")
+            .transition("main_DROP")
+            .weighted_arc("RWLOCK_0", "main_WRITE", ArcDirection::PlaceToTransition, 4)
+            .input("main_BB0", "main_WRITE")
+            .output("main_WRITE", "main_BB1")
+            .input("main_BB1", "main_DROP")
+            .build();
+        let mut output = Vec::new();
+        write_tikz(&net, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\
+\\documentclass[tikz]{standalone}
+\\usetikzlibrary{petri,arrows.meta}
+\\begin{document}
+\\begin{tikzpicture}[y=-1.2cm, >=Stealth, every label/.style={font=\\scriptsize}]
+  \\node[font=\\bfseries] at (5, -1) {main};
+  \\node[place, label=right:{RWLOCK\\_0}] (P0) at (0, 0) {4};
+  \\node[place, label=right:{main\\_BB0}] (P1) at (5, 0) {1};
+  \\node[place, label=right:{main\\_BB1}] (P2) at (5, 2) {};
+  \\node[transition, label=right:{main\\_WRITE}] (T0) at (5, 1) {};
+  \\node[transition, label=right:{main\\_DROP}] (T1) at (5, 3) {};
+  \\draw[->] (P0) -- node[auto, font=\\scriptsize] {4} (T0);
+  \\draw[->] (P1) -- (T0);
+  \\draw[->] (T0) -- (P2);
+  \\draw[->] (P2) -- (T1);
+\\end{tikzpicture}
+\\end{document}
+"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Module that implements the plan of a translation, the result of a dry run.
+//!
+//! A dry run resolves the entry point and follows the calls in the MIR representation
+//! like the translation does, but without building the net or tracking the memory.
+//! It lists the functions that would be translated, the threads that would be spawned
+//! and the synchronization primitives created in them, together with an estimate of the size of the net.
+//! This allows to check the configuration quickly on big projects before running the full translation.
+//!
+//! The plan is an approximation: Calls that are only resolved through the memory during the translation,
+//! e.g. closures stored in a `Box<dyn Fn()>`, are not followed.
+
+use crate::data_structures::span_info::SpanInfo;
+use crate::data_structures::sync_registry::SyncKind;
+
+/// A thread that would be spawned by the translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedThread {
+    /// The name of the function run by the thread, usually a closure.
+    pub function: String,
+    /// The span of the call to `std::thread::spawn`.
+    pub span: SpanInfo,
+}
+
+/// A synchronization primitive that would be created by the translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedPrimitive {
+    /// The kind of synchronization primitive.
+    pub kind: SyncKind,
+    /// The span of the function call that creates the primitive.
+    pub span: SpanInfo,
+}
+
+/// The plan of a translation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TranslationPlan {
+    /// The names of the functions where the translation starts.
+    pub entry_points: Vec<String>,
+    /// The names of the functions translated from their MIR representation, in the order they are first reached.
+    pub functions: Vec<String>,
+    /// The threads spawned, in the order they are reached.
+    pub threads: Vec<PlannedThread>,
+    /// The synchronization primitives created, in the order they are reached.
+    pub primitives: Vec<PlannedPrimitive>,
+    /// The estimated number of places in the net.
+    pub estimated_places: usize,
+    /// The estimated number of transitions in the net.
+    pub estimated_transitions: usize,
+}
+
+impl TranslationPlan {
+    /// Creates a new empty plan.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the function to the functions translated if it is not already in the list.
+    pub fn add_function(&mut self, name: &str) {
+        if !self.functions.iter().any(|function| function == name) {
+            self.functions.push(name.to_string());
+        }
+    }
+}
+
+impl std::fmt::Display for TranslationPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Entry points: {}", self.entry_points.join(", "))?;
+        writeln!(f, "Functions translated ({}):", self.functions.len())?;
+        for function in &self.functions {
+            writeln!(f, "    {function}")?;
+        }
+        writeln!(f, "Threads spawned ({}):", self.threads.len())?;
+        for thread in &self.threads {
+            writeln!(f, "    {} spawned at {}", thread.function, thread.span)?;
+        }
+        writeln!(
+            f,
+            "Synchronization primitives detected ({}):",
+            self.primitives.len()
+        )?;
+        for primitive in &self.primitives {
+            writeln!(f, "    {} created at {}", primitive.kind, primitive.span)?;
+        }
+        write!(
+            f,
+            "Estimated net size: {} places, {} transitions",
+            self.estimated_places, self.estimated_transitions
+        )
+    }
+}
+
+#[cfg(test)]
+mod translation_plan_tests {
+    use super::*;
+
+    fn span(line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: line,
+            start_column: 5,
+            end_line: line,
+            end_column: 20,
+        }
+    }
+
+    #[test]
+    fn add_function_keeps_the_order_of_first_appearance() {
+        let mut plan = TranslationPlan::new();
+        plan.add_function("main");
+        plan.add_function("worker");
+        plan.add_function("main");
+
+        assert_eq!(plan.functions, ["main", "worker"]);
+    }
+
+    #[test]
+    fn display_lists_every_section() {
+        let plan = TranslationPlan {
+            entry_points: vec!["main".to_string()],
+            functions: vec!["main".to_string(), "main::{closure#0}".to_string()],
+            threads: vec![PlannedThread {
+                function: "main::{closure#0}".to_string(),
+                span: span(4),
+            }],
+            primitives: vec![PlannedPrimitive {
+                kind: SyncKind::Mutex,
+                span: span(2),
+            }],
+            estimated_places: 12,
+            estimated_transitions: 15,
+        };
+
+        assert_eq!(
+            plan.to_string(),
+            "\
+Entry points: main
+Functions translated (2):
+    main
+    main::{closure#0}
+Threads spawned (1):
+    main::{closure#0} spawned at main.rs:4:5
+Synchronization primitives detected (1):
+    mutex created at main.rs:2:5
+Estimated net size: 12 places, 15 transitions"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Module for running Graphviz <https://graphviz.org/> to draw the net.
+//!
+//! The layout of the net is computed with `dot -Tplain` and stored in the cache folder
+//! under the `structure_hash` of the net. The next export of a net with the same structure
+//! reads the layout from the cache instead of computing it again, which takes most of the time
+//! for large nets. The SVG image is rendered by `neato -n2` with every node pinned to its position,
+//! so it never computes a layout itself.
+
+use log::info;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::data_structures::layout::{structure_hash, Layout};
+use crate::data_structures::net_data::NetData;
+
+/// Returns the layout of the net, given in DOT format.
+/// If a cache folder is given, the layout is read from it when the structure of the net did not change
+/// and written to it after computing it.
+///
+/// # Errors
+///
+/// If the command `dot` is not found or fails, then an error is returned.
+/// If the output of `dot` cannot be parsed, then an error is returned.
+pub fn layout(net: &NetData, dot: &str, cache_folder: Option<&Path>) -> Result<Layout, String> {
+    let cache_path = cache_folder.map(|folder| cache_path(folder, net));
+    if let Some(layout) = cache_path
+        .as_deref()
+        .and_then(|path| read_cached(path, net))
+    {
+        info!("Reusing the layout of the net from the cache");
+        return Ok(layout);
+    }
+
+    info!("Computing the layout of the net with Graphviz...");
+    let plain = run_graphviz("dot", &["-Tplain"], dot)?;
+    let layout = Layout::from_plain(&plain)?;
+    if let Some(path) = cache_path {
+        // The cache only speeds up the next export, so failing to write it is not an error.
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string(&layout).map_err(std::io::Error::other)?;
+                std::fs::write(&path, json)
+            });
+        if let Err(err) = written {
+            info!(
+                "The layout could not be cached at {}: {err}",
+                path.display()
+            );
+        }
+    }
+    Ok(layout)
+}
+
+/// Renders the net, given in DOT format, as an SVG image with the nodes at their positions in the layout.
+///
+/// # Errors
+///
+/// If the command `neato` is not found or fails, then an error is returned.
+pub fn render_svg(dot: &str, layout: &Layout) -> Result<String, String> {
+    run_graphviz("neato", &["-n2", "-Tsvg"], &layout.pin_dot(dot))
+}
+
+/// Returns the path of the cached layout of the net in the cache folder.
+fn cache_path(cache_folder: &Path, net: &NetData) -> PathBuf {
+    cache_folder.join(format!("{:016x}.json", structure_hash(net)))
+}
+
+/// Reads the cached layout from the file.
+/// Returns `None` if the file does not exist, cannot be parsed or does not cover every node of the net,
+/// which can only happen if two structures have the same hash.
+fn read_cached(path: &Path, net: &NetData) -> Option<Layout> {
+    let json = std::fs::read_to_string(path).ok()?;
+    let layout: Layout = serde_json::from_str(&json).ok()?;
+    layout.covers(net).then_some(layout)
+}
+
+/// Runs the Graphviz program with the given arguments and the input on `stdin`.
+/// Returns the output on `stdout`.
+fn run_graphviz(program: &str, args: &[&str], input: &str) -> Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            format!("Could not run `{program}`: {err}. Please check that Graphviz is installed and added to the $PATH")
+        })?;
+    child
+        .stdin
+        .take()
+        .expect("BUG: The standard input of Graphviz should be piped")
+        .write_all(input.as_bytes())
+        .map_err(|err| format!("Could not write the net to `{program}`: {err}"))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Could not read the output of `{program}`: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`{program}` failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|err| format!("The output of `{program}` is not valid UTF-8: {err}"))
+}
@@ -0,0 +1,67 @@
+//! Compiler-independent core of `cargo-check-deadlock`.
+//!
+//! It contains the Petri net data structures, the naming of places and transitions,
+//! the post-processing passes, the exporters, the reports, the internal analyses,
+//! the interface to the model checkers and Graphviz
+//! and the C ABI for plugins that model additional synchronization primitives.
+//! Nothing in this crate depends on `rustc` internals, so it compiles on stable Rust
+//! and to `wasm32-unknown-unknown`.
+//!
+//! The nets translated by `cargo check-deadlock` can be exported to JSON
+//! and loaded again with `NetData::from_json` to run the analyses elsewhere, e.g. in the browser.
+
+pub mod analysis;
+pub mod annotations;
+pub mod data_structures;
+pub mod graphviz;
+pub mod model_checker;
+pub mod naming;
+pub mod passes;
+pub mod plugin;
+pub mod report;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use analysis::cross_check::CrossCheckReport;
+pub use annotations::{AnnotatedProperty, Suppression};
+pub use data_structures::block_mapping::BlockMapping;
+pub use data_structures::coverage::{Coverage, FunctionCoverage, FunctionStatus};
+pub use data_structures::label_map::LabelMap;
+pub use data_structures::net_data::{ArcData, ArcDirection, NetData, PlaceData, TransitionData};
+pub use data_structures::petri_net_interface::PetriNet;
+pub use data_structures::profiler::Profiler;
+pub use data_structures::span_info::SpanInfo;
+pub use data_structures::sync_registry::{CallSite, SyncKind, SyncObject, SyncRegistry};
+
+/// The result of the translation of the Rust code to a Petri net.
+#[derive(Default)]
+pub struct Translation {
+    /// The Petri net model of the program.
+    pub net: PetriNet,
+    /// The synchronization primitives found in the program and the function calls that use them.
+    pub registry: SyncRegistry,
+    /// The user-defined functions reached during the translation and whether they were translated.
+    pub coverage: Coverage,
+    /// The properties annotated in the comments of the source code, see `annotations`.
+    pub annotated_properties: Vec<AnnotatedProperty>,
+    /// The suppressions of findings annotated in the comments of the source code, see `annotations`.
+    pub suppressions: Vec<Suppression>,
+    /// The names of the functions in the order they were translated, starting with `main`.
+    pub translation_order: Vec<String>,
+    /// The time spent translating each function and in each phase of the translation.
+    /// It records nothing unless profiling was enabled.
+    pub profile: Profiler,
+    /// The result of replaying an execution of the program on the net, if the cross-check was enabled.
+    pub cross_check: Option<CrossCheckReport>,
+    /// The nodes of the net that model every basic block, if the recording of the mapping was enabled.
+    pub block_mapping: Option<BlockMapping>,
+    /// The origin of every place and transition in the code, if the recording of the label map was enabled.
+    pub label_map: Option<LabelMap>,
+    /// The labels of the places that bound the state space of the net but not the program,
+    /// e.g. the free slots of a channel that is unbounded in the program.
+    /// A thread that only waits for a token in one of them would continue in the program.
+    pub bounded_places: Vec<String>,
+    /// Whether the translation was interrupted, e.g. with Ctrl-C.
+    /// The calls translated after the interruption were abstracted, so the net only models part of the program.
+    pub interrupted: bool,
+}
@@ -0,0 +1,299 @@
+//! Submodule for running the `LoLA` model checker.
+
+use log::info;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// The CTL* formula used to check for deadlocks.
+/// This excludes the `PROGRAM_PANIC` and `PROGRAM_END` from being considered as deadlock states.
+pub const DEADLOCK_FORMULA: &str = "EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))";
+
+/// Checks for deadlock using the `LoLA` model checker.
+/// Returns `true` if deadlock can be reached, otherwise returns `false`.
+///
+/// The CTL* formula used is `EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))`.
+/// This excludes the `PROGRAM_PANIC` and `PROGRAM_END` from being considered as deadlock states.
+///
+/// # Panics
+///
+/// If the command `lola` is not found, then the function panics.
+/// If the command `lola` produces an extraneous output, then the function panics.
+#[must_use]
+pub fn check_deadlock(net_filepath: &std::path::PathBuf) -> bool {
+    let output = run_lola(net_filepath, DEADLOCK_FORMULA, &[]);
+    parse_result(output)
+}
+
+/// A set of markings described by groups of places.
+/// A marking belongs to the set if at least one place of every group has a token.
+pub type MarkingPattern = Vec<Vec<String>>;
+
+/// Returns the formula used to check for deadlocks whose marking does not match any of the patterns.
+/// It is used to ignore the deadlocks that are suppressed in the source code or already known.
+#[must_use]
+pub fn deadlock_formula_excluding(patterns: &[MarkingPattern]) -> String {
+    if patterns.is_empty() {
+        return DEADLOCK_FORMULA.to_string();
+    }
+    let conditions: Vec<String> = patterns
+        .iter()
+        .map(|pattern| {
+            let groups: Vec<String> = pattern
+                .iter()
+                .map(|group| {
+                    let places: Vec<String> =
+                        group.iter().map(|place| format!("{place} > 0")).collect();
+                    format!("({})", places.join(" OR "))
+                })
+                .collect();
+            format!("NOT ({})", groups.join(" AND "))
+        })
+        .collect();
+    format!(
+        "EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0) AND {})",
+        conditions.join(" AND ")
+    )
+}
+
+/// Finds a witness path to a deadlock using the `LoLA` model checker.
+/// Returns the labels of the transitions fired from the initial marking to reach the deadlock,
+/// or `None` if no deadlock can be reached.
+///
+/// The witness path is written by `LoLA` to a file next to the net with the extension `path`,
+/// which is removed after reading it.
+///
+/// # Panics
+///
+/// If the command `lola` is not found, then the function panics.
+/// If the command `lola` produces an extraneous output, then the function panics.
+/// If the file with the witness path cannot be read, then the function panics.
+#[must_use]
+pub fn find_deadlock_path(net_filepath: &std::path::PathBuf) -> Option<Vec<String>> {
+    find_deadlock_path_excluding(net_filepath, &[])
+}
+
+/// Finds a witness path to a deadlock whose marking does not match any of the patterns.
+///
+/// Returns `None` if no such deadlock can be reached. See `find_deadlock_path` for the details.
+///
+/// # Panics
+///
+/// If the command `lola` is not found, then the function panics.
+/// If the command `lola` produces an extraneous output, then the function panics.
+/// If the file with the witness path cannot be read, then the function panics.
+#[must_use]
+pub fn find_deadlock_path_excluding(
+    net_filepath: &std::path::PathBuf,
+    patterns: &[MarkingPattern],
+) -> Option<Vec<String>> {
+    let mut path_filepath = net_filepath.clone();
+    path_filepath.set_extension("path");
+    let path_arg = format!("--path={}", path_filepath.to_string_lossy());
+
+    let formula = deadlock_formula_excluding(patterns);
+    let output = run_lola(net_filepath, &formula, &[&path_arg]);
+    if !parse_result(output) {
+        return None;
+    }
+
+    let path = std::fs::read_to_string(&path_filepath)
+        .expect("Failed to read the witness path generated by `lola`");
+    // The witness path is returned to the caller, the file is only an intermediate result.
+    let _ = std::fs::remove_file(&path_filepath);
+    Some(parse_witness_path(&path))
+}
+
+/// The answer of `LoLA` to a formula.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LolaAnswer {
+    /// The formula holds, e.g. a deadlock can be reached.
+    Yes,
+    /// The formula does not hold.
+    No,
+    /// `LoLA` gave up, e.g. because it ran out of memory.
+    Unknown,
+}
+
+/// The result of running a locally installed `lola` binary on a net and a formula file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LolaRun {
+    /// The answer to the formula.
+    pub answer: LolaAnswer,
+    /// The number of markings explored, if `LoLA` reported it.
+    pub markings: Option<usize>,
+    /// The names of the transitions fired to reach a marking that satisfies the formula,
+    /// if the answer is yes and `LoLA` found a witness path.
+    pub path: Option<Vec<String>>,
+}
+
+/// Runs the `lola` binary at the given path on the net with the formula read from a file.
+///
+/// Unlike `check_deadlock`, errors are returned instead of panicking,
+/// so the caller can explain how to install `LoLA` or point to it.
+///
+/// The witness path is written by `LoLA` to a file next to the net with the extension `path`,
+/// which is removed after reading it.
+///
+/// # Errors
+///
+/// If the binary cannot be run, then an error is returned.
+/// If the output of `lola` does not contain an answer, then an error is returned.
+pub fn verify(
+    lola_binary: &Path,
+    net_filepath: &Path,
+    formula_filepath: &Path,
+) -> Result<LolaRun, String> {
+    let path_filepath = net_filepath.with_extension("path");
+    let output = Command::new(lola_binary)
+        .arg(net_filepath)
+        .arg(format!("--formula={}", formula_filepath.to_string_lossy()))
+        .arg(format!("--path={}", path_filepath.to_string_lossy()))
+        .output()
+        .map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                format!(
+                    "`{}` was not found. Install `LoLA` or give the path to the binary with `--lola`",
+                    lola_binary.to_string_lossy()
+                )
+            } else {
+                format!("There was an unknown error while executing `lola`: {err}")
+            }
+        })?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (answer, markings) = parse_output(&stderr)?;
+    let path = std::fs::read_to_string(&path_filepath)
+        .ok()
+        .filter(|_| answer == LolaAnswer::Yes)
+        .map(|path| parse_witness_path(&path));
+    let _ = std::fs::remove_file(&path_filepath);
+    Ok(LolaRun {
+        answer,
+        markings,
+        path,
+    })
+}
+
+/// Parses the answer and the number of markings explored from the output of `lola`,
+/// e.g. the lines `lola: result: yes` and `lola: 12 markings, 15 edges`.
+///
+/// # Errors
+///
+/// If the output does not contain an answer, then an error with the output is returned.
+pub fn parse_output(stderr: &str) -> Result<(LolaAnswer, Option<usize>), String> {
+    let answer = if stderr.contains("result: yes") {
+        LolaAnswer::Yes
+    } else if stderr.contains("result: no") {
+        LolaAnswer::No
+    } else if stderr.contains("result: unknown") {
+        LolaAnswer::Unknown
+    } else {
+        return Err(format!("Unknown output in command `lola`: {stderr}"));
+    };
+    let markings = stderr.lines().find_map(|line| {
+        let (count, _) = line.split_once(" markings, ")?;
+        count.rsplit(' ').next()?.parse().ok()
+    });
+    Ok((answer, markings))
+}
+
+/// Parses the witness path written by `lola`, one transition per line.
+/// The transitions are referenced by their identifiers in the `LoLA` file of the net.
+#[must_use]
+pub fn parse_witness_path(path: &str) -> Vec<String> {
+    path.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Runs `lola` on the given net with the formula and the extra arguments.
+/// Looks for the binary in the $PATH first and in the `./assets/` folder second.
+///
+/// # Panics
+///
+/// If the command `lola` is not found, then the function panics.
+fn run_lola(net_filepath: &std::path::PathBuf, formula: &str, extra_args: &[&str]) -> Output {
+    let formula_arg = format!("--formula={formula}");
+
+    let mut cmd = Command::new("lola");
+    let cmd = cmd.arg(net_filepath).arg(&formula_arg).args(extra_args);
+
+    let mut backup_cmd = Command::new("./assets/lola");
+    let backup_cmd = backup_cmd
+        .arg(net_filepath)
+        .arg(&formula_arg)
+        .args(extra_args);
+
+    match cmd.output() {
+        Ok(output) => output,
+        Err(err) => {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                info!("`lola` was not found in the $PATH. Please check that the program is installed and added to the $PATH");
+            } else {
+                panic!("There was an unknown error while executing `lola`: {err}");
+            }
+            backup_cmd
+                .output()
+                .expect("`lola` was not found in the $PATH nor in the `./assets/` folder")
+        }
+    }
+}
+
+/// Parses the answer to the reachability analysis from the output of `lola`.
+///
+/// # Panics
+///
+/// If the command `lola` produces an extraneous output, then the function panics.
+fn parse_result(output: Output) -> bool {
+    // For some reason `LoLA` only generates output to `stderr`.
+    // Parse the answer to the reachability analysis and panic otherwise.
+    let stderr_string =
+        String::from_utf8(output.stderr).expect("Failed to convert the `lola` stderr to UTF-8");
+    if stderr_string.contains("result: yes") {
+        return true;
+    }
+    if stderr_string.contains("result: no") {
+        return false;
+    }
+    panic!("Unknown output in command `lola`: {stderr_string}");
+}
+
+#[cfg(test)]
+mod lola_tests {
+    use super::*;
+
+    #[test]
+    fn deadlock_formula_excluding_negates_every_pattern() {
+        let patterns = [
+            vec![vec!["main_BB1".to_string(), "main_BB2".to_string()]],
+            vec![vec!["main_BB3".to_string()], vec!["f_BB1".to_string()]],
+        ];
+
+        assert_eq!(deadlock_formula_excluding(&[]), DEADLOCK_FORMULA);
+        assert_eq!(
+            deadlock_formula_excluding(&patterns),
+            "EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0) AND NOT ((main_BB1 > 0 OR main_BB2 > 0)) AND NOT ((main_BB3 > 0) AND (f_BB1 > 0)))"
+        );
+    }
+
+    #[test]
+    fn parse_output_reads_the_answer_and_the_markings() {
+        let stderr = "lola: NET\nlola: 12 markings, 15 edges\nlola: result: yes\n";
+
+        assert_eq!(parse_output(stderr), Ok((LolaAnswer::Yes, Some(12))));
+        assert_eq!(
+            parse_output("lola: result: unknown\n"),
+            Ok((LolaAnswer::Unknown, None))
+        );
+        assert!(parse_output("lola: syntax error").is_err());
+    }
+
+    #[test]
+    fn parse_witness_path_skips_empty_lines() {
+        assert_eq!(
+            parse_witness_path("main_LOCK_0\n\n  main_LOCK_1\n"),
+            ["main_LOCK_0", "main_LOCK_1"]
+        );
+    }
+}
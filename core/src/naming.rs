@@ -8,10 +8,18 @@
 //! See the reference for more information:
 //! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
 
+pub mod atomic;
+pub mod barrier;
 pub mod basic_block;
+pub mod channel;
 pub mod condvar;
 pub mod function;
 pub mod mutex;
+pub mod once;
+pub mod plugin;
+pub mod property;
+pub mod rwlock;
+pub mod scheme;
 pub mod thread;
 
 /// Label of the place that models the program start state.
@@ -21,6 +29,35 @@ pub const PROGRAM_END: &str = "PROGRAM_END";
 /// Label of the place that models the program end state after a `panic!`.
 pub const PROGRAM_PANIC: &str = "PROGRAM_PANIC";
 
+/// Checks whether the label belongs to a transition on the unwind path of the program,
+/// i.e. a call to `panic!` or the cleanup path of a function call, a drop or an assert terminator.
+#[must_use]
+#[inline]
+pub fn is_unwind_transition_label(label: &str) -> bool {
+    let without_index = label
+        .rsplit_once('_')
+        .filter(|(_, index)| index.parse::<usize>().is_ok())
+        .map_or(label, |(rest, _)| rest);
+    label.ends_with("_PANIC")
+        || without_index.ends_with("_UNWIND")
+        || without_index.ends_with("_ASSERT_CLEANUP")
+}
+
+/// The prefixes of the labels of the places that model a synchronization primitive.
+const SYNC_PLACE_PREFIXES: [&str; 8] = [
+    "ATOMIC", "BARRIER", "CHANNEL", "CONDVAR", "MUTEX", "ONCE", "RWLOCK", "THREAD",
+];
+
+/// Checks whether the label belongs to a place that models a synchronization primitive,
+/// e.g. `MUTEX_0`, `CHANNEL_2_BUFFER` or `THREAD_1_END`.
+#[must_use]
+#[inline]
+pub fn is_sync_place_label(label: &str) -> bool {
+    label.split_once('_').is_some_and(|(prefix, rest)| {
+        SYNC_PLACE_PREFIXES.contains(&prefix) && rest.starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
 /// Sanitize the function name for the DOT and the `LoLA` format:
 /// - Replace generic types "<T>" with "T".
 /// - Replace lifetimes "'a" with simply "a".
@@ -35,6 +72,7 @@ fn sanitize(function_name: &str) -> String {
         .replace("<T>", "T")
         .replace("[T]", "T")
         .replace("<T, A>", "T_A")
+        .replace("<R, T>", "R_T")
         .replace("<'a>", "a")
         .replace("::", "_")
         .replace("Result_<T, E>", "Result")
@@ -0,0 +1,54 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to atomic variables.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label that identifies an atomic variable outside of the Petri net, e.g., in reports.
+#[must_use]
+#[inline]
+pub fn label(index: usize) -> String {
+    format!("ATOMIC_{index}")
+}
+
+/// Labels of the two places that model the values `false` (or 0) and `true` (or 1) of every atomic variable.
+#[must_use]
+#[inline]
+pub fn place_labels(index: usize) -> (String, String) {
+    (
+        format!("ATOMIC_{index}_FALSE"),
+        format!("ATOMIC_{index}_TRUE"),
+    )
+}
+
+/// Labels of the two transitions that model a store to an atomic variable,
+/// depending on whether the previous value was `false` or `true`.
+#[must_use]
+#[inline]
+pub fn store_transition_labels(function_name: &str, index: usize) -> (String, String) {
+    let function_name = sanitize(function_name);
+    (
+        format!("{function_name}_{index}_WAS_FALSE"),
+        format!("{function_name}_{index}_WAS_TRUE"),
+    )
+}
+
+/// Labels of the two transitions that model a compare-exchange on an atomic variable,
+/// depending on whether the current value matched the expected value.
+#[must_use]
+#[inline]
+pub fn compare_exchange_transition_labels(function_name: &str, index: usize) -> (String, String) {
+    let function_name = sanitize(function_name);
+    (
+        format!("{function_name}_{index}_SUCCESS"),
+        format!("{function_name}_{index}_FAILURE"),
+    )
+}
@@ -0,0 +1,51 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to barriers.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label that identifies a `Barrier` outside of the Petri net, e.g., in reports.
+#[must_use]
+#[inline]
+pub fn label(index: usize) -> String {
+    format!("BARRIER_{index}")
+}
+
+/// Labels of the two places that model every `Barrier`.
+#[must_use]
+#[inline]
+pub fn place_labels(index: usize) -> (String, String) {
+    (
+        format!("BARRIER_{index}_ARRIVED"),
+        format!("BARRIER_{index}_RELEASED"),
+    )
+}
+
+/// Label of the transition that releases the threads waiting at a `Barrier`.
+#[must_use]
+#[inline]
+pub fn release_transition_label(index: usize) -> String {
+    format!("BARRIER_{index}_RELEASE")
+}
+
+/// Label of the place where the thread waits during a call to `std::sync::Barrier::wait`.
+#[must_use]
+#[inline]
+pub fn wait_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_WAITING", sanitize(function_name))
+}
+
+/// Label of the transition that leaves the `Barrier` at the end of a call to `std::sync::Barrier::wait`.
+#[must_use]
+#[inline]
+pub fn leave_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_LEAVE", sanitize(function_name))
+}
@@ -0,0 +1,208 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the basic blocks inside a MIR function.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label of the place of any `BasicBlock`.
+#[must_use]
+#[inline]
+pub fn place_label(function_name: &str, index: usize) -> String {
+    format!("{}_BB{index}", sanitize(function_name))
+}
+
+/// Label of the transition that represents a statement inside a `BasicBlock`.
+/// Only used with the statement granularity of the translation.
+#[must_use]
+#[inline]
+pub fn statement_transition_label(
+    function_name: &str,
+    block_index: usize,
+    statement_index: usize,
+) -> String {
+    format!(
+        "{}_BLOCK_{block_index}_STATEMENT_{statement_index}",
+        sanitize(function_name)
+    )
+}
+
+/// Label of the place after a statement inside a `BasicBlock`.
+/// Only used with the statement granularity of the translation.
+#[must_use]
+#[inline]
+pub fn statement_place_label(
+    function_name: &str,
+    block_index: usize,
+    statement_index: usize,
+) -> String {
+    format!(
+        "{}_BLOCK_{block_index}_STATEMENT_{statement_index}_END",
+        sanitize(function_name)
+    )
+}
+
+/// Label of the transition that represents a goto terminator to another `BasicBlock`.
+#[must_use]
+#[inline]
+pub fn goto_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_GOTO_{index}", sanitize(function_name))
+}
+
+/// Label of the place that holds the iterations left of the loop whose header is a `BasicBlock`.
+/// Only used with the `loop_bound` option of the translation.
+#[must_use]
+#[inline]
+pub fn loop_bound_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_BB{index}_LOOP_BOUND", sanitize(function_name))
+}
+
+/// Label of the transition that starts an iteration of the loop whose header is a `BasicBlock`.
+/// Only used with the `loop_bound` option of the translation.
+#[must_use]
+#[inline]
+pub fn loop_iteration_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_BB{index}_LOOP_ITERATION", sanitize(function_name))
+}
+
+/// Label of the place after the start of an iteration of the loop whose header is a `BasicBlock`.
+/// Only used with the `loop_bound` option of the translation.
+#[must_use]
+#[inline]
+pub fn loop_iteration_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_BB{index}_LOOP_ITERATION_END", sanitize(function_name))
+}
+
+/// Label of the transition that represents a switch int terminator to another `BasicBlock`.
+#[must_use]
+#[inline]
+pub fn switch_int_transition_label(
+    function_name: &str,
+    from_index: usize,
+    to_index: usize,
+) -> String {
+    format!(
+        "{}_SWITCH_INT_FROM_BB{from_index}_TO_BB{to_index}",
+        sanitize(function_name)
+    )
+}
+
+/// Label of the transition that represents a switch int terminator to another `BasicBlock`
+/// when it branches on a nondeterministic choice marked in the source code.
+#[must_use]
+#[inline]
+pub fn nondet_branch_transition_label(
+    function_name: &str,
+    from_index: usize,
+    to_index: usize,
+) -> String {
+    format!(
+        "{}_NONDET_FROM_BB{from_index}_TO_BB{to_index}",
+        sanitize(function_name)
+    )
+}
+
+/// Label of the transition that represents a switch int terminator to another `BasicBlock`
+/// when it branches on a value that depends on the environment, e.g. on the command line arguments.
+#[must_use]
+#[inline]
+pub fn environment_branch_transition_label(
+    function_name: &str,
+    from_index: usize,
+    to_index: usize,
+) -> String {
+    format!(
+        "{}_ENV_FROM_BB{from_index}_TO_BB{to_index}",
+        sanitize(function_name)
+    )
+}
+
+/// Label of the transition that represents an unwind terminator to the general `PROGRAM_PANIC` place.
+#[must_use]
+#[inline]
+pub fn unwind_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_UNWIND_{index}", sanitize(function_name))
+}
+
+/// Label of the transition that represents a drop terminator.
+#[must_use]
+#[inline]
+pub fn drop_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_DROP_{index}", sanitize(function_name))
+}
+
+/// Label of the transition that represents the (optional) unwind path of a drop terminator.
+#[must_use]
+#[inline]
+pub fn drop_cleanup_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_DROP_UNWIND_{index}", sanitize(function_name))
+}
+
+/// Label of the transition that represents an assert terminator.
+#[must_use]
+#[inline]
+pub fn assert_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_ASSERT_{index}", sanitize(function_name))
+}
+
+/// Label of the transition that represents the (optional) unwind path of an assert terminator.
+#[must_use]
+#[inline]
+pub fn assert_cleanup_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_ASSERT_CLEANUP_{index}", sanitize(function_name))
+}
+
+/// Label of the transition that represents the `Unreachable` terminator.
+#[must_use]
+#[inline]
+pub fn unreachable_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_UNREACHABLE_{index}", sanitize(function_name))
+}
+
+/// Returns the sanitized name of the function of the place of a `BasicBlock`, e.g. `main` for `main_BB1`,
+/// or of the place after a statement inside it, e.g. `main` for `main_BLOCK_1_STATEMENT_0_END`.
+///
+/// Returns `None` if the label does not belong to any of these places.
+#[must_use]
+#[inline]
+pub fn function_of_place_label(label: &str) -> Option<&str> {
+    label
+        .rsplit_once("_BB")
+        .filter(|(_, index)| index.parse::<usize>().is_ok())
+        .map(|(function, _)| function)
+        .or_else(|| {
+            let (block, statement) = label.strip_suffix("_END")?.rsplit_once("_STATEMENT_")?;
+            let (function, block) = block.rsplit_once("_BLOCK_")?;
+            (block.parse::<usize>().is_ok() && statement.parse::<usize>().is_ok())
+                .then_some(function)
+        })
+}
+
+/// Checks whether the label belongs to the transition of a goto terminator, e.g. `main_GOTO_3`.
+#[must_use]
+#[inline]
+pub fn is_goto_transition_label(label: &str) -> bool {
+    label
+        .rsplit_once("_GOTO_")
+        .is_some_and(|(_, index)| index.parse::<usize>().is_ok())
+}
+
+/// Checks whether the label belongs to the transition of a statement, e.g. `main_BLOCK_1_STATEMENT_0`.
+#[must_use]
+#[inline]
+pub fn is_statement_transition_label(label: &str) -> bool {
+    label
+        .rsplit_once("_STATEMENT_")
+        .and_then(|(block, statement)| {
+            let (_, block) = block.rsplit_once("_BLOCK_")?;
+            Some(block.parse::<usize>().is_ok() && statement.parse::<usize>().is_ok())
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,58 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to channels.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label that identifies a channel outside of the Petri net, e.g., in reports.
+#[must_use]
+#[inline]
+pub fn label(index: usize) -> String {
+    format!("CHANNEL_{index}")
+}
+
+/// Label of the place that models the messages buffered in every channel.
+#[must_use]
+#[inline]
+pub fn buffer_place_label(index: usize) -> String {
+    format!("CHANNEL_{index}_BUFFER")
+}
+
+/// Label of the place that models the free slots in the buffer of every bounded channel.
+#[must_use]
+#[inline]
+pub fn free_slots_place_label(index: usize) -> String {
+    format!("CHANNEL_{index}_FREE_SLOTS")
+}
+
+/// Label of the place that models the messages taken by the receiver of every rendezvous channel,
+/// i.e., a bounded channel with a capacity of zero messages.
+#[must_use]
+#[inline]
+pub fn received_place_label(index: usize) -> String {
+    format!("CHANNEL_{index}_RECEIVED")
+}
+
+/// Label of the place where the thread waits for the receiver during a call to
+/// `std::sync::mpsc::SyncSender::<T>::send` on a rendezvous channel.
+#[must_use]
+#[inline]
+pub fn send_wait_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_WAITING", sanitize(function_name))
+}
+
+/// Label of the transition that ends a call to `std::sync::mpsc::SyncSender::<T>::send`
+/// on a rendezvous channel once the message was received.
+#[must_use]
+#[inline]
+pub fn send_leave_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_LEAVE", sanitize(function_name))
+}
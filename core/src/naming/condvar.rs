@@ -0,0 +1,89 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to condition variables.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+/// Label that identifies a `Condvar` outside of the Petri net, e.g., in reports.
+#[must_use]
+#[inline]
+pub fn label(index: usize) -> String {
+    format!("CONDVAR_{index}")
+}
+
+/// Labels of the four places that model every `Condvar`.
+#[must_use]
+#[inline]
+pub fn place_labels(index: usize) -> (String, String) {
+    (
+        format!("CONDVAR_{index}_WAIT_ENABLED"),
+        format!("CONDVAR_{index}_NOTIFY"),
+    )
+}
+
+/// Labels of the two transitions that model every `Condvar`.
+#[must_use]
+#[inline]
+pub fn transition_labels(index: usize) -> (String, String, String) {
+    (
+        format!("CONDVAR_{index}_WAIT_START"),
+        format!("CONDVAR_{index}_LOST_SIGNAL"),
+        format!("CONDVAR_{index}_NOTIFY_RECEIVED"),
+    )
+}
+
+/// Labels of the two places added to a `Condvar` when `notify_all` is called.
+#[must_use]
+#[inline]
+pub fn notify_all_place_labels(index: usize) -> (String, String) {
+    (
+        format!("CONDVAR_{index}_NOTIFY_ALL"),
+        format!("CONDVAR_{index}_WAITING"),
+    )
+}
+
+/// Labels of the two transitions added to a `Condvar` when `notify_all` is called.
+#[must_use]
+#[inline]
+pub fn notify_all_transition_labels(index: usize) -> (String, String) {
+    (
+        format!("CONDVAR_{index}_NOTIFY_ALL_WAKE"),
+        format!("CONDVAR_{index}_NOTIFY_ALL_END"),
+    )
+}
+
+/// Labels of the places for a position in the FIFO queue of waiting threads of a `Condvar`.
+///
+/// The first place means that the position is taken and the second one that it is free.
+/// The free place of the first position is the `CONDVAR_{index}_WAIT_ENABLED` place instead.
+#[must_use]
+#[inline]
+pub fn queue_place_labels(index: usize, position: usize) -> (String, String) {
+    (
+        format!("CONDVAR_{index}_QUEUE_{position}"),
+        format!("CONDVAR_{index}_QUEUE_{position}_FREE"),
+    )
+}
+
+/// Label of the transition that moves a waiting thread one position ahead
+/// in the queue of a `Condvar` with the FIFO wakeup policy.
+#[must_use]
+#[inline]
+pub fn queue_advance_transition_label(index: usize, position: usize) -> String {
+    format!("CONDVAR_{index}_QUEUE_ADVANCE_{position}")
+}
+
+/// Label of the transition that represents skipping a call
+/// to `std::sync::Condvar::wait` or `std::sync::Condvar::wait_while`
+/// because the condition was already set.
+#[must_use]
+#[inline]
+pub fn wait_skip_label(index: usize) -> String {
+    format!("CONDVAR_{index}_WAIT_SKIP")
+}
@@ -0,0 +1,100 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern function calls (with or without a MIR representation).
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Indexed function name for a call to a MIR function.
+#[must_use]
+#[inline]
+pub fn indexed_mir_function_name(function_name: &str, index: usize) -> String {
+    format!("{}_{index}", sanitize(function_name))
+}
+
+/// Label of the place where an exported function starts when a library is translated.
+#[must_use]
+#[inline]
+pub fn entry_place_label(function_name: &str) -> String {
+    format!("{}_ENTRY", sanitize(function_name))
+}
+
+/// Label of the transition that starts an exported function when a library is translated.
+#[must_use]
+#[inline]
+pub fn entry_transition_label(function_name: &str) -> String {
+    format!("{}_ENTRY_CALL", sanitize(function_name))
+}
+
+/// Label of the transition for the return statement of a function.
+#[must_use]
+#[inline]
+pub fn return_transition_label(function_name: &str) -> String {
+    format!("{}_RETURN", sanitize(function_name))
+}
+
+/// Label of the transitions for a call to a foreign function.
+#[must_use]
+#[inline]
+pub fn foreign_call_transition_labels(function_name: &str, index: usize) -> (String, String) {
+    (
+        format!("{}_{index}_CALL", sanitize(function_name)),
+        format!("{}_{index}_CALL_UNWIND", sanitize(function_name)),
+    )
+}
+
+/// Label of the transitions for a call to a marker of nondeterminism, e.g. `rand::random::<bool>`.
+#[must_use]
+#[inline]
+pub fn nondet_call_transition_labels(function_name: &str, index: usize) -> (String, String) {
+    (
+        format!("{}_{index}_NONDET", sanitize(function_name)),
+        format!("{}_{index}_NONDET_UNWIND", sanitize(function_name)),
+    )
+}
+
+/// Label of the transitions for a recursive call to a function that is already being translated.
+#[must_use]
+#[inline]
+pub fn recursive_call_transition_labels(function_name: &str, index: usize) -> (String, String) {
+    (
+        format!("{}_{index}_RECURSIVE_CALL", sanitize(function_name)),
+        format!("{}_{index}_RECURSIVE_CALL_UNWIND", sanitize(function_name)),
+    )
+}
+
+/// Label of the transition for a self-recursive call in tail position modelled as a loop.
+#[must_use]
+#[inline]
+pub fn recursive_loop_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_RECURSIVE_LOOP", sanitize(function_name))
+}
+
+/// Label of the transition that represents a diverging function call (a function that does not return).
+#[must_use]
+#[inline]
+pub fn diverging_call_transition_label(function_name: &str) -> String {
+    format!("{}_DIVERGING_CALL", sanitize(function_name))
+}
+
+/// Checks whether the label belongs to a transition that represents a diverging function call.
+/// These transitions have no output place since the function does not return.
+#[must_use]
+#[inline]
+pub fn is_diverging_call_transition_label(label: &str) -> bool {
+    label.ends_with("_DIVERGING_CALL")
+}
+
+/// Label of the transition that represents a call to a `panic!`.
+#[must_use]
+#[inline]
+pub fn panic_transition_label(function_name: &str) -> String {
+    format!("{}_PANIC", sanitize(function_name))
+}
@@ -11,13 +11,24 @@
 //! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
 
 /// Label of the single place that models every `Mutex`.
+#[must_use]
 #[inline]
 pub fn place_label(index: usize) -> String {
     format!("MUTEX_{index}")
 }
 
+/// Checks whether the label belongs to the place that models a `Mutex`, e.g. `MUTEX_0`.
+#[must_use]
+#[inline]
+pub fn is_place_label(label: &str) -> bool {
+    label
+        .strip_prefix("MUTEX_")
+        .is_some_and(|index| index.parse::<usize>().is_ok())
+}
+
 /// Labels of the two places that model the condition (the value)
 /// stored inside a `Mutex` used in conjunction with a condition variable.
+#[must_use]
 #[inline]
 pub fn condition_place_labels(index: usize) -> (String, String) {
     (
@@ -25,3 +36,11 @@ pub fn condition_place_labels(index: usize) -> (String, String) {
         format!("MUTEX_{index}_CONDITION_SET"),
     )
 }
+
+/// Label of the place that models whether a `Mutex` is poisoned,
+/// i.e. a thread panicked while holding the lock.
+#[must_use]
+#[inline]
+pub fn poisoned_place_label(index: usize) -> String {
+    format!("MUTEX_{index}_POISONED")
+}
@@ -0,0 +1,54 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to one-time initialization.
+//!
+//! They cover both `std::sync::Once` and `std::sync::OnceLock`.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label that identifies a `Once` or a `OnceLock` outside of the Petri net, e.g., in reports.
+#[must_use]
+#[inline]
+pub fn label(index: usize) -> String {
+    format!("ONCE_{index}")
+}
+
+/// Labels of the two places that model every `Once` or `OnceLock`.
+#[must_use]
+#[inline]
+pub fn place_labels(index: usize) -> (String, String) {
+    (
+        format!("ONCE_{index}_NOT_STARTED"),
+        format!("ONCE_{index}_DONE"),
+    )
+}
+
+/// Label of the place where the thread runs the initialization during a call to
+/// `std::sync::Once::call_once` or `std::sync::OnceLock::<T>::get_or_init`.
+#[must_use]
+#[inline]
+pub fn running_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_RUNNING", sanitize(function_name))
+}
+
+/// Label of the transition that completes the initialization.
+#[must_use]
+#[inline]
+pub fn finish_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_FINISH", sanitize(function_name))
+}
+
+/// Label of the transition that skips the initialization because it was already completed.
+#[must_use]
+#[inline]
+pub fn skip_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_SKIP", sanitize(function_name))
+}
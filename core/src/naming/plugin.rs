@@ -0,0 +1,42 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the primitives modelled by plugins.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label that identifies an instance of a primitive modelled by a plugin outside of the Petri net, e.g., in reports.
+/// It is prefixed with the name of the plugin to avoid collisions with other plugins.
+#[must_use]
+#[inline]
+pub fn label(plugin_name: &str, index: usize) -> String {
+    format!("PLUGIN_{}_{index}", sanitize(plugin_name))
+}
+
+/// Label of a place declared by a plugin for the instance whose label is based on `index`.
+#[must_use]
+#[inline]
+pub fn place_label(plugin_name: &str, index: usize, place_label: &str) -> String {
+    format!(
+        "PLUGIN_{}_{index}_{}",
+        sanitize(plugin_name),
+        sanitize(place_label)
+    )
+}
+
+/// Label of the transitions for a call to a function modelled by a plugin.
+#[must_use]
+#[inline]
+pub fn call_transition_labels(transition_label: &str, index: usize) -> (String, String) {
+    (
+        format!("{}_{index}", sanitize(transition_label)),
+        format!("{}_{index}_UNWIND", sanitize(transition_label)),
+    )
+}
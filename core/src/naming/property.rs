@@ -0,0 +1,35 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the properties annotated by the user in the source code.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+/// Name of a property annotated without a name, based on the line of the annotation.
+#[must_use]
+#[inline]
+pub fn default_name(line: usize) -> String {
+    format!("LINE_{line}")
+}
+
+/// Labels of the two places that record whether the code marked by the property was reached.
+#[must_use]
+#[inline]
+pub fn marker_place_labels(name: &str) -> (String, String) {
+    (
+        format!("PROPERTY_{name}_NOT_REACHED"),
+        format!("PROPERTY_{name}_REACHED"),
+    )
+}
+
+/// Label of the copy of a marked transition that fires once the marked code was already reached.
+#[must_use]
+#[inline]
+pub fn repeated_transition_label(transition_label: &str, name: &str) -> String {
+    format!("{transition_label}_{name}_AGAIN")
+}
@@ -1,5 +1,5 @@
 //! Submodule that defines the naming of places and transitions in the Petri net
-//! that concern the translation of functions related to threads.
+//! that concern the translation of functions related to read-write locks.
 //!
 //! These functions are called every time that a new place or transition
 //! in the resulting net is created.
@@ -10,15 +10,17 @@
 //! See the reference for more information:
 //! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
 
-/// Label of the place that models the thread start state.
+/// Label of the single place that models every `RwLock`.
+#[must_use]
 #[inline]
-pub fn start_place_label(index: usize) -> String {
-    format!("THREAD_{index}_START")
+pub fn place_label(index: usize) -> String {
+    format!("RWLOCK_{index}")
 }
 
-/// Label of the place that models the thread end state.
-/// This also models the thread end state after a `panic!`.
+/// Label of the place that models whether a `RwLock` is poisoned,
+/// i.e. a thread panicked while holding the lock for writing.
+#[must_use]
 #[inline]
-pub fn end_place_label(index: usize) -> String {
-    format!("THREAD_{index}_END")
+pub fn poisoned_place_label(index: usize) -> String {
+    format!("RWLOCK_{index}_POISONED")
 }
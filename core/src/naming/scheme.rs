@@ -0,0 +1,130 @@
+//! Submodule that defines the naming schemes for the identifiers of the nodes in the exported files.
+//!
+//! The labels of the translation are verbose on purpose: `std_sync_Mutex_T_lock_0_CALL` tells
+//! which call a transition models. The labels of the generic functions of the standard library
+//! can be very long, though, and `LoLA` and some PNML tools choke on them.
+//! A `NamingScheme` gives every node a shorter name in the `LoLA` and PNML files.
+//! The names are resolved to legal identifiers and back to the labels by the `IdentifierTable`,
+//! so the witness paths and the reports still refer to the labels of the translation.
+//!
+//! The built-in schemes are:
+//! - `Verbose`: The label itself, the default.
+//! - `HashedShort`: The labels longer than a maximum length are truncated and end with a hash of the full label.
+//! - `NumericIds`: `P<index>` for the places and `T<index>` for the transitions. The table that maps them
+//!   back to the labels is written next to the net.
+
+use crate::data_structures::label_registry::NodeKind;
+
+/// The default maximum length of a name in `HashedShort`.
+pub const DEFAULT_MAX_NAME_LENGTH: usize = 32;
+
+/// The number of hexadecimal digits of the hash in `HashedShort`.
+const HASH_DIGITS: usize = 8;
+
+/// A naming scheme for the identifiers of the nodes in the exported files.
+pub trait NamingScheme: Send {
+    /// The name of the scheme, as selected with `--naming`.
+    fn name(&self) -> &str;
+
+    /// Returns the name of a node given its label, its kind and its index among the nodes of the same kind.
+    /// The names should be unique. Collisions are resolved with a suffix, like the illegal identifiers.
+    fn rename(&self, label: &str, kind: NodeKind, index: usize) -> String;
+}
+
+/// The label itself.
+pub struct Verbose;
+
+impl NamingScheme for Verbose {
+    fn name(&self) -> &'static str {
+        "verbose"
+    }
+
+    fn rename(&self, label: &str, _kind: NodeKind, _index: usize) -> String {
+        label.to_string()
+    }
+}
+
+/// The label truncated to a maximum length, ending with a hash of the full label.
+/// Labels up to the maximum length are kept.
+pub struct HashedShort {
+    /// The maximum length of a name in bytes.
+    pub max_length: usize,
+}
+
+impl Default for HashedShort {
+    fn default() -> Self {
+        Self {
+            max_length: DEFAULT_MAX_NAME_LENGTH,
+        }
+    }
+}
+
+impl NamingScheme for HashedShort {
+    fn name(&self) -> &'static str {
+        "hashed"
+    }
+
+    fn rename(&self, label: &str, _kind: NodeKind, _index: usize) -> String {
+        if label.len() <= self.max_length {
+            return label.to_string();
+        }
+        let mut prefix_length = self.max_length.saturating_sub(HASH_DIGITS + 1);
+        while !label.is_char_boundary(prefix_length) {
+            prefix_length -= 1;
+        }
+        format!("{}_{:08x}", &label[..prefix_length], fnv1a(label))
+    }
+}
+
+/// A numeric identifier per kind of node: `P<index>` for the places and `T<index>` for the transitions.
+pub struct NumericIds;
+
+impl NamingScheme for NumericIds {
+    fn name(&self) -> &'static str {
+        "numeric"
+    }
+
+    fn rename(&self, _label: &str, kind: NodeKind, index: usize) -> String {
+        match kind {
+            NodeKind::Place => format!("P{index}"),
+            NodeKind::Transition => format!("T{index}"),
+        }
+    }
+}
+
+/// The 32-bit FNV-1a hash of the string.
+/// It is stable across platforms and versions of Rust, unlike the hasher of the standard library,
+/// so the same label always gets the same name.
+fn fnv1a(string: &str) -> u32 {
+    string.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod scheme_tests {
+    use super::*;
+
+    #[test]
+    fn hashed_short_keeps_the_short_labels() {
+        let scheme = HashedShort { max_length: 16 };
+
+        assert_eq!(scheme.rename("main_BB1", NodeKind::Place, 0), "main_BB1");
+        let name = scheme.rename("std_sync_Mutex_T_lock_0_CALL", NodeKind::Transition, 0);
+        assert_eq!(name.len(), 16);
+        assert!(name.starts_with("std_syn_"));
+        assert_ne!(
+            name,
+            scheme.rename("std_sync_Mutex_T_lock_1_CALL", NodeKind::Transition, 1)
+        );
+    }
+
+    #[test]
+    fn numeric_ids_depend_on_the_kind() {
+        assert_eq!(NumericIds.rename("main_BB1", NodeKind::Place, 3), "P3");
+        assert_eq!(
+            NumericIds.rename("main_CALL", NodeKind::Transition, 3),
+            "T3"
+        );
+    }
+}
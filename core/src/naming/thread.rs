@@ -0,0 +1,86 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to threads.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+/// Label that identifies a thread outside of the Petri net, e.g., in reports.
+#[must_use]
+#[inline]
+pub fn label(index: usize) -> String {
+    format!("THREAD_{index}")
+}
+
+/// Label of the place that models the thread start state.
+#[must_use]
+#[inline]
+pub fn start_place_label(index: usize) -> String {
+    format!("THREAD_{index}_START")
+}
+
+/// Checks whether the label belongs to the place that models a thread start state.
+#[must_use]
+#[inline]
+pub fn is_start_place_label(label: &str) -> bool {
+    label
+        .strip_prefix("THREAD_")
+        .and_then(|rest| rest.strip_suffix("_START"))
+        .is_some_and(|index| index.parse::<usize>().is_ok())
+}
+
+/// Label of the place that models the thread end state.
+/// This also models the thread end state after a `panic!`,
+/// unless the panics are modelled separately, see `panic_place_label`.
+#[must_use]
+#[inline]
+pub fn end_place_label(index: usize) -> String {
+    format!("THREAD_{index}_END")
+}
+
+/// Label of the transition that spawns the threads running the additional entry points at program start.
+#[must_use]
+#[inline]
+pub fn also_run_transition_label() -> String {
+    "ALSO_RUN_SPAWN".to_string()
+}
+
+/// Label of the place where the translation starts after spawning the threads of the additional entry points.
+#[must_use]
+#[inline]
+pub fn also_run_place_label() -> String {
+    "ALSO_RUN_STARTED".to_string()
+}
+
+/// Label of the place that holds the number of instances of a thread spawned in a loop that may still start.
+#[must_use]
+#[inline]
+pub fn instances_place_label(index: usize) -> String {
+    format!("THREAD_{index}_INSTANCES")
+}
+
+/// Label of the transition that models spawning a thread in a loop once all its instances started.
+#[must_use]
+#[inline]
+pub fn spawn_skip_transition_label(index: usize) -> String {
+    format!("THREAD_{index}_SPAWN_SKIP")
+}
+
+/// Label of the place that models the thread end state after a `panic!`.
+#[must_use]
+#[inline]
+pub fn panic_place_label(index: usize) -> String {
+    format!("THREAD_{index}_PANIC")
+}
+
+/// Label of the transition that models joining a thread that panicked, i.e. `join` returning `Err`.
+#[must_use]
+#[inline]
+pub fn join_err_transition_label(index: usize) -> String {
+    format!("THREAD_{index}_JOIN_ERR")
+}
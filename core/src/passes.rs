@@ -0,0 +1,466 @@
+//! Module that defines the post-processing passes applied to the net between the translation and the export.
+//!
+//! A pass is a transformation or a check of the plain description of the net (`NetData`),
+//! see the `NetPass` trait. The `PassManager` runs an ordered list of passes on the net of a `Translation`
+//! and rebuilds the net from the result, so every export sees the transformed net.
+//!
+//! The built-in passes are:
+//! - `Reduce`: The reduction rules of Murata, see `analysis::reduction::reduce`.
+//! - `CollapseGotoChains`: The collapse of the chains of gotos and statements,
+//!   see `analysis::reduction::collapse_goto_chains`.
+//! - `CheckStructure`: The check of the structural invariants, see `analysis::self_check`.
+//!   It does not modify the net.
+//! - `Rename`: Renames places and transitions, e.g. to give meaningful names to the places of an abstraction.
+//!
+//! Library users can implement `NetPass` to insert their own transformations,
+//! e.g. a project-specific abstraction that replaces a subnet with a simpler one.
+//! The reports look up the places of the synchronization primitives and the end states of the program
+//! by their labels, so a pass should keep these labels to keep the reports valid.
+//!
+//! `PassManager::run_on_data_with_log` records the reduction rules applied by every pass in a `ReductionLog`.
+//! It justifies that the verdict on the reduced net holds for the original net.
+//! A pass that changes the net without justifying it, e.g. a custom abstraction, is marked as unjustified.
+
+use log::info;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::analysis::reduction::{
+    collapse_goto_chains, collapse_goto_chains_with_steps, reduce, reduce_with_steps,
+    ReductionRule, ReductionStep,
+};
+use crate::analysis::self_check::check_structure;
+use crate::data_structures::net_data::NetData;
+use crate::data_structures::petri_net_interface::PetriNet;
+use crate::Translation;
+
+/// A transformation or a check of the net run by the `PassManager`.
+pub trait NetPass {
+    /// The name of the pass, used in the log and in the errors.
+    fn name(&self) -> &str;
+
+    /// Runs the pass on the net, modifying it in place.
+    ///
+    /// # Errors
+    ///
+    /// If the pass cannot be applied to the net or the net violates a property checked by the pass,
+    /// then an error is returned.
+    fn run(&self, net: &mut NetData) -> Result<(), String>;
+
+    /// Runs the pass on the net like `run` and returns the reduction rules applied, in order.
+    /// `None` means that the pass does not justify its changes.
+    /// The default implementation calls `run` and returns `None`.
+    ///
+    /// # Errors
+    ///
+    /// If the pass cannot be applied to the net or the net violates a property checked by the pass,
+    /// then an error is returned.
+    fn run_justified(&self, net: &mut NetData) -> Result<Option<Vec<ReductionStep>>, String> {
+        self.run(net)?;
+        Ok(None)
+    }
+}
+
+/// The reduction rules of Murata, see `analysis::reduction::reduce`.
+pub struct Reduce;
+
+impl NetPass for Reduce {
+    fn name(&self) -> &'static str {
+        "reduce"
+    }
+
+    fn run(&self, net: &mut NetData) -> Result<(), String> {
+        *net = reduce(net)?;
+        Ok(())
+    }
+
+    fn run_justified(&self, net: &mut NetData) -> Result<Option<Vec<ReductionStep>>, String> {
+        let (reduced, steps) = reduce_with_steps(net)?;
+        *net = reduced;
+        Ok(Some(steps))
+    }
+}
+
+/// The collapse of the chains of gotos and statements, see `analysis::reduction::collapse_goto_chains`.
+pub struct CollapseGotoChains;
+
+impl NetPass for CollapseGotoChains {
+    fn name(&self) -> &'static str {
+        "collapse-gotos"
+    }
+
+    fn run(&self, net: &mut NetData) -> Result<(), String> {
+        *net = collapse_goto_chains(net)?;
+        Ok(())
+    }
+
+    fn run_justified(&self, net: &mut NetData) -> Result<Option<Vec<ReductionStep>>, String> {
+        let (collapsed, steps) = collapse_goto_chains_with_steps(net)?;
+        *net = collapsed;
+        Ok(Some(steps))
+    }
+}
+
+/// The check of the structural invariants, see `analysis::self_check::check_structure`.
+pub struct CheckStructure;
+
+impl NetPass for CheckStructure {
+    fn name(&self) -> &'static str {
+        "self-check"
+    }
+
+    fn run(&self, net: &mut NetData) -> Result<(), String> {
+        check_structure(net)
+    }
+}
+
+/// Renames places and transitions. The arcs follow the new labels.
+#[derive(Clone, Debug, Default)]
+pub struct Rename {
+    /// The new label of every place or transition to rename, by its current label.
+    pub labels: BTreeMap<String, String>,
+}
+
+impl NetPass for Rename {
+    fn name(&self) -> &'static str {
+        "rename"
+    }
+
+    fn run(&self, net: &mut NetData) -> Result<(), String> {
+        let rename = |label: &mut String| {
+            if let Some(new_label) = self.labels.get(label.as_str()) {
+                label.clone_from(new_label);
+            }
+        };
+        for place in &mut net.places {
+            rename(&mut place.label);
+        }
+        for transition in &mut net.transitions {
+            rename(&mut transition.label);
+        }
+        for arc in &mut net.arcs {
+            rename(&mut arc.place);
+            rename(&mut arc.transition);
+        }
+
+        let mut places = HashSet::new();
+        if let Some(place) = net.places.iter().find(|place| !places.insert(&place.label)) {
+            return Err(format!("The place `{}` is declared twice", place.label));
+        }
+        let mut transitions = HashSet::new();
+        if let Some(transition) = net
+            .transitions
+            .iter()
+            .find(|transition| !transitions.insert(&transition.label))
+        {
+            return Err(format!(
+                "The transition `{}` is declared twice",
+                transition.label
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The changes made by a pass, as recorded by `PassManager::run_on_data_with_log`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassLog {
+    /// The name of the pass.
+    pub name: String,
+    /// Whether the pass changed the net.
+    pub changed: bool,
+    /// The reduction rules applied by the pass, in order, or `None` if the pass does not justify its changes.
+    pub steps: Option<Vec<ReductionStep>>,
+}
+
+impl PassLog {
+    /// Checks whether the verdict on the net after the pass holds for the net before it,
+    /// i.e. the pass did not change the net or every change is a reduction rule.
+    #[must_use]
+    pub fn is_justified(&self) -> bool {
+        !self.changed || self.steps.is_some()
+    }
+}
+
+/// The log of the reductions applied to the net by the passes, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReductionLog {
+    /// The changes made by every pass, in the order in which the passes ran.
+    pub passes: Vec<PassLog>,
+}
+
+impl ReductionLog {
+    /// Checks whether the verdict on the reduced net holds for the original net, see `PassLog::is_justified`.
+    #[must_use]
+    pub fn is_justified(&self) -> bool {
+        self.passes.iter().all(PassLog::is_justified)
+    }
+
+    /// Returns how many times every reduction rule fired.
+    #[must_use]
+    pub fn rule_counts(&self) -> BTreeMap<ReductionRule, usize> {
+        let mut counts = BTreeMap::new();
+        for step in self
+            .passes
+            .iter()
+            .filter_map(|pass| pass.steps.as_ref())
+            .flatten()
+        {
+            *counts.entry(step.rule).or_default() += 1;
+        }
+        counts
+    }
+}
+
+impl std::fmt::Display for ReductionLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for pass in &self.passes {
+            match &pass.steps {
+                Some(steps) => {
+                    writeln!(f, "Pass `{}`: {} reductions", pass.name, steps.len())?;
+                    for (index, step) in steps.iter().enumerate() {
+                        writeln!(f, "  {}. {step}", index + 1)?;
+                    }
+                }
+                None if pass.changed => writeln!(
+                    f,
+                    "Pass `{}`: changed the net without a justification",
+                    pass.name
+                )?,
+                None => writeln!(f, "Pass `{}`: did not change the net", pass.name)?,
+            }
+        }
+        if self.is_justified() {
+            write!(
+                f,
+                "Every change preserves the dead markings and the final places, \
+                so the verdict of the deadlock analysis on the reduced net holds for the original net."
+            )
+        } else {
+            write!(
+                f,
+                "Some changes are not justified, \
+                so the verdict of the deadlock analysis on the reduced net may not hold for the original net."
+            )
+        }
+    }
+}
+
+/// An ordered list of passes run on the net of a translation.
+#[derive(Default)]
+pub struct PassManager {
+    /// The passes in the order in which they run.
+    passes: Vec<Box<dyn NetPass>>,
+}
+
+impl PassManager {
+    /// Creates a new `PassManager` without passes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the pass to the end of the list.
+    pub fn add(&mut self, pass: Box<dyn NetPass>) {
+        self.passes.push(pass);
+    }
+
+    /// Inserts the pass right before the first pass with the given name.
+    ///
+    /// # Errors
+    ///
+    /// If there is no pass with the given name, then an error is returned.
+    pub fn insert_before(&mut self, name: &str, pass: Box<dyn NetPass>) -> Result<(), String> {
+        let index = self
+            .passes
+            .iter()
+            .position(|existing| existing.name() == name)
+            .ok_or_else(|| format!("There is no pass named `{name}`"))?;
+        self.passes.insert(index, pass);
+        Ok(())
+    }
+
+    /// Returns the names of the passes in the order in which they run.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.passes.iter().map(|pass| pass.name()).collect()
+    }
+
+    /// Checks whether the list of passes is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Runs the passes in order on the plain description of the net.
+    ///
+    /// # Errors
+    ///
+    /// If a pass fails, then an error naming the pass is returned and the remaining passes are not run.
+    pub fn run_on_data(&self, net: &mut NetData) -> Result<(), String> {
+        for pass in &self.passes {
+            info!("Running the pass `{}` on the net...", pass.name());
+            pass.run(net)
+                .map_err(|err_str| format!("The pass `{}` failed: {err_str}", pass.name()))?;
+        }
+        Ok(())
+    }
+
+    /// Runs the passes in order on the plain description of the net like `run_on_data`
+    /// and returns the log of the reduction rules applied by every pass.
+    ///
+    /// # Errors
+    ///
+    /// If a pass fails, then an error naming the pass is returned and the remaining passes are not run.
+    pub fn run_on_data_with_log(&self, net: &mut NetData) -> Result<ReductionLog, String> {
+        let mut log = ReductionLog::default();
+        for pass in &self.passes {
+            info!("Running the pass `{}` on the net...", pass.name());
+            let before = net.clone();
+            let steps = pass
+                .run_justified(net)
+                .map_err(|err_str| format!("The pass `{}` failed: {err_str}", pass.name()))?;
+            log.passes.push(PassLog {
+                name: pass.name().to_string(),
+                changed: *net != before,
+                steps,
+            });
+        }
+        Ok(log)
+    }
+
+    /// Runs the passes in order on the net of the translation and replaces it with the result.
+    /// The net is left unchanged if there are no passes.
+    ///
+    /// # Errors
+    ///
+    /// If a pass fails or its result is not a valid net, then an error is returned
+    /// and the net of the translation is left unchanged.
+    pub fn run(&self, translation: &mut Translation) -> Result<(), String> {
+        if self.passes.is_empty() {
+            return Ok(());
+        }
+        let mut net = translation.net.data();
+        self.run_on_data(&mut net)?;
+        translation.net = PetriNet::from_data(&net)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod passes_tests {
+    use super::*;
+    use crate::data_structures::net_data::{ArcData, ArcDirection, PlaceData, TransitionData};
+
+    fn net() -> NetData {
+        NetData {
+            places: vec![
+                PlaceData {
+                    label: "PROGRAM_START".to_string(),
+                    marking: 1,
+                    capacity: None,
+                    span: None,
+                },
+                PlaceData {
+                    label: "PROGRAM_END".to_string(),
+                    marking: 0,
+                    capacity: None,
+                    span: None,
+                },
+            ],
+            transitions: vec![TransitionData {
+                label: "main_RETURN".to_string(),
+                span: None,
+            }],
+            arcs: vec![
+                ArcData {
+                    place: "PROGRAM_START".to_string(),
+                    transition: "main_RETURN".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+                ArcData {
+                    place: "PROGRAM_END".to_string(),
+                    transition: "main_RETURN".to_string(),
+                    direction: ArcDirection::TransitionToPlace,
+                    weight: 1,
+                },
+            ],
+        }
+    }
+
+    /// A pass that always fails.
+    struct Fail;
+
+    impl NetPass for Fail {
+        fn name(&self) -> &'static str {
+            "fail"
+        }
+
+        fn run(&self, _net: &mut NetData) -> Result<(), String> {
+            Err("no".to_string())
+        }
+    }
+
+    #[test]
+    fn rename_updates_the_arcs() {
+        let mut net = net();
+        let rename = Rename {
+            labels: BTreeMap::from([("main_RETURN".to_string(), "FINISH".to_string())]),
+        };
+        rename.run(&mut net).unwrap();
+
+        assert_eq!(net.transitions[0].label, "FINISH");
+        assert!(net.arcs.iter().all(|arc| arc.transition == "FINISH"));
+    }
+
+    #[test]
+    fn rename_rejects_a_label_already_used() {
+        let mut net = net();
+        let rename = Rename {
+            labels: BTreeMap::from([("PROGRAM_START".to_string(), "PROGRAM_END".to_string())]),
+        };
+
+        assert_eq!(
+            rename.run(&mut net),
+            Err("The place `PROGRAM_END` is declared twice".to_string())
+        );
+    }
+
+    #[test]
+    fn pass_manager_runs_the_passes_in_order() {
+        let mut passes = PassManager::new();
+        passes.add(Box::new(CheckStructure));
+        passes.add(Box::new(Fail));
+        passes.insert_before("fail", Box::new(Reduce)).unwrap();
+
+        assert_eq!(passes.names(), ["self-check", "reduce", "fail"]);
+        assert_eq!(
+            passes.run_on_data(&mut net()),
+            Err("The pass `fail` failed: no".to_string())
+        );
+        assert!(passes.insert_before("missing", Box::new(Reduce)).is_err());
+    }
+
+    #[test]
+    fn reduction_log_marks_the_unjustified_changes() {
+        let mut passes = PassManager::new();
+        passes.add(Box::new(Reduce));
+        passes.add(Box::new(CheckStructure));
+        let mut reduced = net();
+        let log = passes.run_on_data_with_log(&mut reduced).unwrap();
+
+        assert!(log.is_justified());
+        assert_eq!(log.passes[0].steps.as_ref().map(Vec::len), Some(0));
+        assert!(!log.passes[1].changed);
+
+        passes.add(Box::new(Rename {
+            labels: BTreeMap::from([("main_RETURN".to_string(), "FINISH".to_string())]),
+        }));
+        let log = passes.run_on_data_with_log(&mut net()).unwrap();
+
+        assert!(!log.is_justified());
+        assert!(log
+            .to_string()
+            .contains("Pass `rename`: changed the net without a justification\n"));
+    }
+}
@@ -0,0 +1,362 @@
+//! Module that defines the interface for plugins that model additional synchronization primitives.
+//!
+//! A plugin is a dynamic library (e.g. `libmyprims.so`) loaded with `--plugin <PATH>`.
+//! It allows to model in-house concurrency primitives without modifying the translator.
+//!
+//! The interface is a C ABI, so the plugin can be written in any language
+//! and compiled with any version of the compiler. The plugin must export the function:
+//!
+//! ```c
+//! const PluginDescriptor *cargo_check_deadlock_plugin(void);
+//! ```
+//!
+//! The returned descriptor declares:
+//! - The type of the primitive, e.g. `my_crate::Semaphore`, and the function that creates an instance of it,
+//!   e.g. `my_crate::Semaphore::new`.
+//! - The places that model the state of one instance of the primitive, together with their initial marking.
+//!   For instance, a semaphore with 3 permits is a place with 3 tokens.
+//!   Every call to the constructor adds a new copy of these places to the net,
+//!   like every call to `std::sync::Mutex::new` adds a new mutex.
+//! - The functions handled by the plugin. They receive the instance as their first argument (the self reference).
+//!   A call to one of these functions is modelled as a transition that consumes one token from each place
+//!   of the instance in `consumes` and produces one token in each place of the instance in `produces`.
+//!   The transition is labelled with the naming scheme `transition_label`.
+//!
+//! The models are declarative: The plugin does not run any code during the translation.
+//! The naming of the rest of the net is not affected by the plugins, it is set with `--naming`.
+//!
+//! The descriptor and every string it references must remain valid until the plugin is unloaded.
+//! The content is copied into a `PluginModels` right after loading the plugin.
+//! The plugin must set `abi_version` to `PLUGIN_ABI_VERSION`.
+
+use std::ffi::{c_char, CStr};
+
+/// The version of the C ABI described in this module.
+/// It is incremented every time that the layout of the structs changes.
+pub const PLUGIN_ABI_VERSION: u32 = 2;
+
+/// The name of the function exported by every plugin that returns the `PluginDescriptor`.
+pub const PLUGIN_ENTRY_POINT: &str = "cargo_check_deadlock_plugin";
+
+/// The signature of the function exported by every plugin.
+pub type PluginEntryPoint = unsafe extern "C" fn() -> *const PluginDescriptor;
+
+/// A place of every instance of the primitive declared by a plugin.
+#[repr(C)]
+pub struct PluginPlace {
+    /// The label of the place as a null-terminated string.
+    pub label: *const c_char,
+    /// The number of tokens in the place when the instance is created.
+    pub initial_marking: usize,
+}
+
+/// A function call handled by a plugin.
+#[repr(C)]
+pub struct PluginFunctionModel {
+    /// The fully qualified name of the function, e.g. `my_crate::Semaphore::acquire`.
+    /// Its first argument must be the instance of the primitive.
+    pub function_name: *const c_char,
+    /// The label used to name the transitions that model the call, e.g. `SEMAPHORE_ACQUIRE`.
+    pub transition_label: *const c_char,
+    /// The labels of the places of the instance from which the call consumes a token.
+    pub consumes: *const *const c_char,
+    /// The number of elements in `consumes`.
+    pub consumes_len: usize,
+    /// The labels of the places of the instance in which the call produces a token.
+    pub produces: *const *const c_char,
+    /// The number of elements in `produces`.
+    pub produces_len: usize,
+}
+
+/// The description of everything a plugin models.
+#[repr(C)]
+pub struct PluginDescriptor {
+    /// Must be equal to `PLUGIN_ABI_VERSION`.
+    pub abi_version: u32,
+    /// The name of the plugin as a null-terminated string. It prefixes the labels of its places.
+    pub name: *const c_char,
+    /// The fully qualified path of the type of the primitive, e.g. `my_crate::Semaphore`.
+    /// The variables of this type are tracked like the synchronization primitives of the standard library.
+    pub type_name: *const c_char,
+    /// The fully qualified name of the function that creates an instance, e.g. `my_crate::Semaphore::new`.
+    pub constructor: *const c_char,
+    /// The places of every instance of the primitive.
+    pub places: *const PluginPlace,
+    /// The number of elements in `places`.
+    pub places_len: usize,
+    /// The function calls handled by the plugin.
+    pub functions: *const PluginFunctionModel,
+    /// The number of elements in `functions`.
+    pub functions_len: usize,
+}
+
+/// A place of every instance of the primitive, copied from the `PluginPlace`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrimitivePlace {
+    pub label: String,
+    pub initial_marking: usize,
+}
+
+/// A function call handled by a plugin, copied from the `PluginFunctionModel`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionModel {
+    pub function_name: String,
+    pub transition_label: String,
+    pub consumes: Vec<String>,
+    pub produces: Vec<String>,
+}
+
+/// The owned copy of a `PluginDescriptor`.
+/// It does not reference the memory of the plugin, so the library may be unloaded afterwards.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginModels {
+    pub name: String,
+    pub type_name: String,
+    pub constructor: String,
+    pub places: Vec<PrimitivePlace>,
+    pub functions: Vec<FunctionModel>,
+}
+
+impl PluginModels {
+    /// Copies the content of the descriptor returned by a plugin.
+    ///
+    /// # Errors
+    ///
+    /// If the ABI version does not match `PLUGIN_ABI_VERSION`, then an error is returned.
+    /// If a string is null or not valid UTF-8, then an error is returned.
+    /// If a function consumes or produces a token in a place that the plugin did not declare,
+    /// then an error is returned.
+    /// If the constructor is also modelled as a function, then an error is returned,
+    /// since it does not receive an instance of the primitive.
+    ///
+    /// # Safety
+    ///
+    /// Every pointer in the descriptor must be either null with a length of zero
+    /// or valid for reads of the given number of elements.
+    /// Every string must be null-terminated.
+    pub unsafe fn from_descriptor(descriptor: &PluginDescriptor) -> Result<Self, String> {
+        if descriptor.abi_version != PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "Plugin ABI version {} is not supported, expected version {PLUGIN_ABI_VERSION}",
+                descriptor.abi_version
+            ));
+        }
+        let name = copy_string(descriptor.name)?;
+        let type_name = copy_string(descriptor.type_name)?;
+        let constructor = copy_string(descriptor.constructor)?;
+
+        let mut places = Vec::with_capacity(descriptor.places_len);
+        for place in copy_slice(descriptor.places, descriptor.places_len) {
+            places.push(PrimitivePlace {
+                label: copy_string(place.label)?,
+                initial_marking: place.initial_marking,
+            });
+        }
+
+        let mut functions = Vec::with_capacity(descriptor.functions_len);
+        for function in copy_slice(descriptor.functions, descriptor.functions_len) {
+            let model = FunctionModel {
+                function_name: copy_string(function.function_name)?,
+                transition_label: copy_string(function.transition_label)?,
+                consumes: copy_strings(function.consumes, function.consumes_len)?,
+                produces: copy_strings(function.produces, function.produces_len)?,
+            };
+            if model.function_name == constructor {
+                return Err(format!(
+                    "Plugin {name} models the constructor {constructor} as a function on an instance"
+                ));
+            }
+            for label in model.consumes.iter().chain(&model.produces) {
+                if !places.iter().any(|place| &place.label == label) {
+                    return Err(format!(
+                        "Plugin {name} models the function {} with the undeclared place {label}",
+                        model.function_name
+                    ));
+                }
+            }
+            functions.push(model);
+        }
+
+        Ok(Self {
+            name,
+            type_name,
+            constructor,
+            places,
+            functions,
+        })
+    }
+
+    /// Returns the model for the function with the given name, if the plugin handles it.
+    #[must_use]
+    pub fn get_function(&self, function_name: &str) -> Option<&FunctionModel> {
+        self.functions
+            .iter()
+            .find(|function| function.function_name == function_name)
+    }
+}
+
+/// Copies a null-terminated string.
+///
+/// # Safety
+///
+/// The pointer must be null or point to a null-terminated string.
+unsafe fn copy_string(ptr: *const c_char) -> Result<String, String> {
+    if ptr.is_null() {
+        return Err("Plugin descriptor contains a null string".to_string());
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(str::to_string)
+        .map_err(|_| "Plugin descriptor contains a string that is not valid UTF-8".to_string())
+}
+
+/// Copies an array of null-terminated strings.
+///
+/// # Safety
+///
+/// Same requirements as `copy_slice` and `copy_string`.
+unsafe fn copy_strings(ptr: *const *const c_char, len: usize) -> Result<Vec<String>, String> {
+    copy_slice(ptr, len)
+        .iter()
+        .map(|string| copy_string(*string))
+        .collect()
+}
+
+/// Returns the array as a slice. A null pointer is treated as an empty array.
+///
+/// # Safety
+///
+/// The pointer must be null or valid for reads of `len` elements.
+unsafe fn copy_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        return &[];
+    }
+    std::slice::from_raw_parts(ptr, len)
+}
+
+#[cfg(test)]
+mod plugin_tests {
+    use super::*;
+
+    const SEMAPHORE: &CStr = c"SEMAPHORE";
+    const UNKNOWN: &CStr = c"UNKNOWN";
+
+    fn place() -> PluginPlace {
+        PluginPlace {
+            label: SEMAPHORE.as_ptr(),
+            initial_marking: 3,
+        }
+    }
+
+    fn function(consumes: &[*const c_char]) -> PluginFunctionModel {
+        PluginFunctionModel {
+            function_name: c"my_crate::Semaphore::acquire".as_ptr(),
+            transition_label: c"SEMAPHORE_ACQUIRE".as_ptr(),
+            consumes: consumes.as_ptr(),
+            consumes_len: consumes.len(),
+            produces: std::ptr::null(),
+            produces_len: 0,
+        }
+    }
+
+    fn descriptor(places: &[PluginPlace], functions: &[PluginFunctionModel]) -> PluginDescriptor {
+        PluginDescriptor {
+            abi_version: PLUGIN_ABI_VERSION,
+            name: c"my_prims".as_ptr(),
+            type_name: c"my_crate::Semaphore".as_ptr(),
+            constructor: c"my_crate::Semaphore::new".as_ptr(),
+            places: places.as_ptr(),
+            places_len: places.len(),
+            functions: functions.as_ptr(),
+            functions_len: functions.len(),
+        }
+    }
+
+    #[test]
+    fn from_descriptor_copies_places_and_functions() {
+        let places = [place()];
+        let consumes = [SEMAPHORE.as_ptr()];
+        let functions = [function(&consumes)];
+        let models = unsafe { PluginModels::from_descriptor(&descriptor(&places, &functions)) };
+
+        assert_eq!(
+            models,
+            Ok(PluginModels {
+                name: "my_prims".to_string(),
+                type_name: "my_crate::Semaphore".to_string(),
+                constructor: "my_crate::Semaphore::new".to_string(),
+                places: vec![PrimitivePlace {
+                    label: "SEMAPHORE".to_string(),
+                    initial_marking: 3,
+                }],
+                functions: vec![FunctionModel {
+                    function_name: "my_crate::Semaphore::acquire".to_string(),
+                    transition_label: "SEMAPHORE_ACQUIRE".to_string(),
+                    consumes: vec!["SEMAPHORE".to_string()],
+                    produces: Vec::new(),
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn from_descriptor_rejects_other_abi_version() {
+        let mut descriptor = descriptor(&[], &[]);
+        descriptor.abi_version = PLUGIN_ABI_VERSION + 1;
+        let models = unsafe { PluginModels::from_descriptor(&descriptor) };
+
+        assert!(models.is_err());
+    }
+
+    #[test]
+    fn from_descriptor_rejects_undeclared_place() {
+        let places = [place()];
+        let consumes = [UNKNOWN.as_ptr()];
+        let functions = [function(&consumes)];
+        let models = unsafe { PluginModels::from_descriptor(&descriptor(&places, &functions)) };
+
+        assert_eq!(
+            models,
+            Err("Plugin my_prims models the function my_crate::Semaphore::acquire with the undeclared place UNKNOWN".to_string())
+        );
+    }
+
+    #[test]
+    fn from_descriptor_rejects_constructor_as_function() {
+        let places = [place()];
+        let consumes = [SEMAPHORE.as_ptr()];
+        let mut functions = [function(&consumes)];
+        functions[0].function_name = c"my_crate::Semaphore::new".as_ptr();
+        let models = unsafe { PluginModels::from_descriptor(&descriptor(&places, &functions)) };
+
+        assert_eq!(
+            models,
+            Err("Plugin my_prims models the constructor my_crate::Semaphore::new as a function on an instance".to_string())
+        );
+    }
+
+    #[test]
+    fn from_descriptor_rejects_null_string() {
+        let mut descriptor = descriptor(&[], &[]);
+        descriptor.name = std::ptr::null();
+        let models = unsafe { PluginModels::from_descriptor(&descriptor) };
+
+        assert!(models.is_err());
+    }
+
+    #[test]
+    fn get_function_finds_model_by_name() {
+        let places = [place()];
+        let consumes = [SEMAPHORE.as_ptr()];
+        let functions = [function(&consumes)];
+        let models =
+            unsafe { PluginModels::from_descriptor(&descriptor(&places, &functions)) }.unwrap();
+
+        assert!(models
+            .get_function("my_crate::Semaphore::acquire")
+            .is_some());
+        assert!(models
+            .get_function("my_crate::Semaphore::release")
+            .is_none());
+    }
+}
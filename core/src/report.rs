@@ -0,0 +1,20 @@
+//! Module that implements the reports generated from the result of the translation.
+//!
+//! The reports do not depend on rustc internals.
+//! They only use the information collected in the `Translation`.
+
+pub mod annotated_source;
+pub mod baseline;
+pub mod coverage;
+pub mod findings;
+pub mod interactions;
+pub mod localization;
+pub mod lola_comments;
+pub mod mcc_bundle;
+pub mod net_diff;
+pub mod net_diff_dot;
+pub mod pnml_pages;
+pub mod property_templates;
+pub mod snippets;
+pub mod source_trace;
+pub mod trace;
@@ -0,0 +1,205 @@
+//! Submodule that generates a copy of the source code annotated with the Petri net elements
+//! that each line produced.
+//!
+//! Every line that produced transitions or uses a synchronization primitive
+//! is preceded by comments listing them, e.g.:
+//!
+//! ```text
+//!     // transitions: std_sync_Mutex_T_new_0_CALL
+//!     // sync: MUTEX_0 created
+//!     let data = std::sync::Mutex::new(0);
+//! ```
+//!
+//! This offers a code-centric view of the model for reviewers.
+//! For a crate whose modules live in several files, every file with annotations is annotated,
+//! see `annotated_filenames`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::Translation;
+
+/// The annotations collected for a single line of the source code.
+#[derive(Default)]
+struct LineAnnotation {
+    transitions: Vec<String>,
+    sync: Vec<String>,
+}
+
+/// Returns a copy of the source code interleaved with comments listing the transitions
+/// that each line produced and the synchronization primitives that each line uses.
+///
+/// Only the spans located in the file `filename` are considered,
+/// i.e., `filename` should be the path of the file whose contents are in `source_code`.
+#[must_use]
+pub fn annotate_source(source_code: &str, filename: &str, translation: &Translation) -> String {
+    let mut annotations: BTreeMap<usize, LineAnnotation> = BTreeMap::new();
+
+    for (label, span) in translation.net.transition_spans() {
+        if span.filename == filename {
+            let annotation = annotations.entry(span.start_line).or_default();
+            annotation.transitions.push(label.to_string());
+        }
+    }
+
+    for object in &translation.registry {
+        if object.span.filename == filename {
+            let annotation = annotations.entry(object.span.start_line).or_default();
+            annotation.sync.push(format!("{} created", object.label));
+        }
+        for call_site in &object.call_sites {
+            if call_site.span.filename == filename {
+                let annotation = annotations.entry(call_site.span.start_line).or_default();
+                annotation.sync.push(format!(
+                    "{} used by {}",
+                    object.label, call_site.function_name
+                ));
+            }
+        }
+    }
+
+    let mut output = String::new();
+    for (index, line) in source_code.lines().enumerate() {
+        // Lines in a `SpanInfo` are 1-based.
+        if let Some(annotation) = annotations.get(&(index + 1)) {
+            let indentation: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+            if !annotation.transitions.is_empty() {
+                writeln!(
+                    output,
+                    "{indentation}// transitions: {}",
+                    annotation.transitions.join(", ")
+                )
+                .expect("BUG: Writing to a `String` should not fail");
+            }
+            if !annotation.sync.is_empty() {
+                writeln!(
+                    output,
+                    "{indentation}// sync: {}",
+                    annotation.sync.join(", ")
+                )
+                .expect("BUG: Writing to a `String` should not fail");
+            }
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Returns the names of the files to annotate for a crate with the given root file.
+///
+/// The root file comes first, followed by the other files with spans in the net or in the registry,
+/// e.g. the files of the modules declared with `mod foo;`, sorted by name.
+#[must_use]
+pub fn annotated_filenames(root_filename: &str, translation: &Translation) -> Vec<String> {
+    let mut filenames = BTreeSet::new();
+    for (_, span) in translation.net.transition_spans() {
+        filenames.insert(span.filename.as_str());
+    }
+    for object in &translation.registry {
+        filenames.insert(object.span.filename.as_str());
+        for call_site in &object.call_sites {
+            filenames.insert(call_site.span.filename.as_str());
+        }
+    }
+    filenames.remove(root_filename);
+    std::iter::once(root_filename)
+        .chain(filenames)
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod annotated_source_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::PetriNet;
+    use crate::data_structures::span_info::SpanInfo;
+    use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+
+    fn span_at_line(filename: &str, line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: filename.to_string(),
+            start_line: line,
+            start_column: 1,
+            end_line: line,
+            end_column: 1,
+        }
+    }
+
+    #[test]
+    fn annotate_source_without_annotations_returns_same_source() {
+        let translation = Translation::default();
+        let source_code = "fn main() {}\n";
+
+        assert_eq!(
+            annotate_source(source_code, "main.rs", &translation),
+            source_code
+        );
+    }
+
+    #[test]
+    fn annotate_source_adds_comments_with_indentation_before_line() {
+        let mut net = PetriNet::new();
+        net.set_current_span(Some(span_at_line("main.rs", 2)));
+        net.add_transition("std_sync_Mutex_T_new_0_CALL");
+        net.set_current_span(None);
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", span_at_line("main.rs", 2));
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+
+        let source_code = "fn main() {\n    let data = std::sync::Mutex::new(0);\n}\n";
+        let expected = "fn main() {\n    // transitions: std_sync_Mutex_T_new_0_CALL\n    // sync: MUTEX_0 created\n    let data = std::sync::Mutex::new(0);\n}\n";
+
+        assert_eq!(
+            annotate_source(source_code, "main.rs", &translation),
+            expected
+        );
+    }
+
+    #[test]
+    fn annotate_source_ignores_spans_in_other_files() {
+        let mut net = PetriNet::new();
+        net.set_current_span(Some(span_at_line("other.rs", 1)));
+        net.add_transition("other_RETURN");
+        let translation = Translation {
+            net,
+            ..Default::default()
+        };
+        let source_code = "fn main() {}\n";
+
+        assert_eq!(
+            annotate_source(source_code, "main.rs", &translation),
+            source_code
+        );
+    }
+
+    #[test]
+    fn annotated_filenames_start_with_the_root_file() {
+        let mut net = PetriNet::new();
+        net.set_current_span(Some(span_at_line("src/worker.rs", 3)));
+        net.add_transition("worker_RETURN");
+        net.set_current_span(Some(span_at_line("src/main.rs", 1)));
+        net.add_transition("main_RETURN");
+        net.set_current_span(None);
+        let mut registry = SyncRegistry::new();
+        registry.add(
+            SyncKind::Mutex,
+            "MUTEX_0",
+            span_at_line("src/state/mod.rs", 2),
+        );
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            annotated_filenames("src/main.rs", &translation),
+            ["src/main.rs", "src/state/mod.rs", "src/worker.rs"]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Submodule that implements the baseline of known findings.
+//!
+//! The baseline is a JSON file that records the findings acknowledged by the user,
+//! which allows adopting the analysis in a codebase with accepted concurrency risks
+//! and failing only on the findings introduced later.
+//!
+//! A finding is recorded with its kind and the locations in the source code where the threads are blocked.
+//! The baseline matches a finding if the threads are blocked at least at the recorded locations.
+//! Moving the code of a known finding to another line therefore makes it a new finding.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_structures::net_data::NetData;
+use crate::data_structures::span_info::SpanInfo;
+use crate::model_checker::lola::MarkingPattern;
+use crate::report::findings::{control_places_at, Finding, FindingKind};
+use crate::Translation;
+
+/// A finding recorded in the baseline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// The kind of finding.
+    pub kind: FindingKind,
+    /// The locations in the source code where the threads are blocked.
+    pub locations: Vec<SpanInfo>,
+}
+
+impl BaselineEntry {
+    /// Checks whether the entry matches the finding.
+    #[must_use]
+    pub fn matches(&self, finding: &Finding) -> bool {
+        self.kind == finding.kind
+            && self
+                .locations
+                .iter()
+                .all(|location| finding.spans.contains(location))
+    }
+}
+
+/// The findings acknowledged by the user.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The findings recorded in the baseline.
+    pub findings: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Creates a baseline that acknowledges the given findings.
+    /// Findings that are suppressed in the source code are not recorded.
+    #[must_use]
+    pub fn from_findings(findings: &[Finding]) -> Self {
+        let findings = findings
+            .iter()
+            .filter(|finding| finding.suppressed_by.is_none())
+            .map(|finding| BaselineEntry {
+                kind: finding.kind,
+                locations: finding.spans.clone(),
+            })
+            .collect();
+        Self { findings }
+    }
+
+    /// Parses a baseline from its representation in JSON.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is not a valid baseline, then an error describing the problem is returned.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| format!("Invalid baseline: {err}"))
+    }
+
+    /// Returns the representation of the baseline in JSON.
+    ///
+    /// # Panics
+    ///
+    /// If the baseline cannot be serialized, then the function panics. This should never happen.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("BUG: The baseline should be serializable to JSON")
+    }
+
+    /// Checks whether the finding was recorded in the baseline.
+    #[must_use]
+    pub fn contains(&self, finding: &Finding) -> bool {
+        self.findings.iter().any(|entry| entry.matches(finding))
+    }
+
+    /// Returns the patterns of the markings of the findings of the given kind recorded in the baseline.
+    /// Entries whose locations no longer produce any transition in the net are skipped,
+    /// since they cannot match any finding.
+    #[must_use]
+    pub fn patterns(
+        &self,
+        translation: &Translation,
+        net: &NetData,
+        kind: FindingKind,
+    ) -> Vec<MarkingPattern> {
+        self.findings
+            .iter()
+            .filter(|entry| entry.kind == kind && !entry.locations.is_empty())
+            .map(|entry| {
+                entry
+                    .locations
+                    .iter()
+                    .map(|location| control_places_at(translation, net, |span| span == location))
+                    .collect::<MarkingPattern>()
+            })
+            .filter(|pattern| pattern.iter().all(|group| !group.is_empty()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod baseline_tests {
+    use super::*;
+
+    fn finding_at(lines: &[usize]) -> Finding {
+        Finding {
+            kind: FindingKind::Deadlock,
+            path: Vec::new(),
+            marked_places: Vec::new(),
+            blocked: Vec::new(),
+            spans: lines
+                .iter()
+                .map(|line| SpanInfo {
+                    filename: "main.rs".to_string(),
+                    start_line: *line,
+                    ..SpanInfo::default()
+                })
+                .collect(),
+            suppressed_by: None,
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn baseline_contains_the_findings_it_was_created_from() {
+        let baseline = Baseline::from_findings(&[finding_at(&[4, 9])]);
+
+        assert!(baseline.contains(&finding_at(&[4, 9])));
+        assert!(baseline.contains(&finding_at(&[4, 9, 12])));
+        assert!(!baseline.contains(&finding_at(&[4])));
+        assert!(!baseline.contains(&finding_at(&[5, 9])));
+    }
+
+    #[test]
+    fn baseline_round_trips_through_json() {
+        let baseline = Baseline::from_findings(&[finding_at(&[4])]);
+
+        let json = baseline.to_json();
+
+        assert!(json.contains("\"kind\": \"deadlock\""));
+        assert_eq!(Baseline::from_json(&json), Ok(baseline));
+        assert!(Baseline::from_json("{\"findings\": 1}").is_err());
+    }
+
+    #[test]
+    fn suppressed_findings_are_not_recorded() {
+        let mut finding = finding_at(&[4]);
+        finding.suppressed_by = Some(SpanInfo::default());
+
+        assert!(Baseline::from_findings(&[finding]).findings.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Submodule that generates a summary of how much of the program was translated.
+//!
+//! For every module, it reports the percentage of the reachable user-defined functions
+//! and of their lines that were fully translated instead of abstracted as foreign function calls.
+//! The verdict of the deadlock analysis only covers the translated code,
+//! the abstracted functions are assumed to never block.
+//!
+//! The report ends with the order in which the functions were translated.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::data_structures::coverage::{Coverage, FunctionStatus};
+
+/// The counters for a group of functions, e.g. a module.
+#[derive(Default)]
+struct Counters {
+    translated_functions: usize,
+    total_functions: usize,
+    translated_lines: usize,
+    total_lines: usize,
+}
+
+impl Counters {
+    fn add(&mut self, lines: usize, status: FunctionStatus) {
+        self.total_functions += 1;
+        self.total_lines += lines;
+        if status == FunctionStatus::Translated {
+            self.translated_functions += 1;
+            self.translated_lines += lines;
+        }
+    }
+}
+
+/// Formats a ratio as `part/total (percentage%)`.
+/// An empty total is reported as fully covered.
+#[allow(clippy::cast_precision_loss)]
+fn format_ratio(part: usize, total: usize) -> String {
+    let percentage = if total == 0 {
+        100.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    };
+    format!("{part}/{total} ({percentage:.1}%)")
+}
+
+/// Returns a human-readable report of the translated and abstracted functions per module.
+///
+/// The totals are followed by the list of abstracted functions, the functions skipped with the reason
+/// and the order in which the functions were translated.
+#[must_use]
+pub fn coverage_report(coverage: &Coverage, translation_order: &[String]) -> String {
+    let mut modules: BTreeMap<&str, Counters> = BTreeMap::new();
+    let mut total = Counters::default();
+    for function in coverage {
+        let lines = function.lines();
+        modules
+            .entry(&function.module)
+            .or_default()
+            .add(lines, function.status);
+        total.add(lines, function.status);
+    }
+
+    let mut output = String::new();
+    let mut write_row = |name: &str, counters: &Counters| {
+        writeln!(
+            output,
+            "{name}: functions translated {}, lines translated {}",
+            format_ratio(counters.translated_functions, counters.total_functions),
+            format_ratio(counters.translated_lines, counters.total_lines)
+        )
+        .expect("BUG: Writing to a `String` should not fail");
+    };
+    for (module, counters) in &modules {
+        write_row(module, counters);
+    }
+    write_row("total", &total);
+
+    for function in coverage {
+        if function.status == FunctionStatus::Abstracted {
+            writeln!(output, "abstracted: {} at {}", function.name, function.span)
+                .expect("BUG: Writing to a `String` should not fail");
+        }
+    }
+
+    for (name, reason) in coverage.skipped() {
+        writeln!(output, "skipped: {name}: {reason}")
+            .expect("BUG: Writing to a `String` should not fail");
+    }
+
+    for (position, function_name) in translation_order.iter().enumerate() {
+        writeln!(output, "translation order {position}: {function_name}")
+            .expect("BUG: Writing to a `String` should not fail");
+    }
+    output
+}
+
+#[cfg(test)]
+mod coverage_tests {
+    use super::*;
+    use crate::data_structures::span_info::SpanInfo;
+
+    fn span_with_lines(start_line: usize, end_line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line,
+            start_column: 1,
+            end_line,
+            end_column: 2,
+        }
+    }
+
+    #[test]
+    fn coverage_report_empty_coverage_is_fully_covered() {
+        let coverage = Coverage::new();
+
+        assert_eq!(
+            coverage_report(&coverage, &[]),
+            "total: functions translated 0/0 (100.0%), lines translated 0/0 (100.0%)\n"
+        );
+    }
+
+    #[test]
+    fn coverage_report_groups_functions_per_module() {
+        let mut coverage = Coverage::new();
+        coverage.record(
+            "main",
+            "crate",
+            span_with_lines(1, 4),
+            FunctionStatus::Translated,
+        );
+        coverage.record(
+            "utils::helper",
+            "utils",
+            span_with_lines(10, 12),
+            FunctionStatus::Translated,
+        );
+        coverage.record(
+            "utils::deep",
+            "utils",
+            span_with_lines(14, 14),
+            FunctionStatus::Abstracted,
+        );
+
+        let expected = "crate: functions translated 1/1 (100.0%), lines translated 4/4 (100.0%)\n\
+            utils: functions translated 1/2 (50.0%), lines translated 3/4 (75.0%)\n\
+            total: functions translated 2/3 (66.7%), lines translated 7/8 (87.5%)\n\
+            abstracted: utils::deep at main.rs:14:1\n\
+            translation order 0: main\n\
+            translation order 1: utils_helper_0\n";
+        let translation_order = vec!["main".to_string(), "utils_helper_0".to_string()];
+        assert_eq!(coverage_report(&coverage, &translation_order), expected);
+    }
+
+    #[test]
+    fn coverage_report_lists_skipped_functions_with_the_reason() {
+        let mut coverage = Coverage::new();
+        coverage.record(
+            "utils::slow",
+            "utils",
+            span_with_lines(3, 3),
+            FunctionStatus::Abstracted,
+        );
+        coverage.record_skipped("utils::slow", "exceeded the time budget of 5s");
+
+        let expected = "utils: functions translated 0/1 (0.0%), lines translated 0/1 (0.0%)\n\
+            total: functions translated 0/1 (0.0%), lines translated 0/1 (0.0%)\n\
+            abstracted: utils::slow at main.rs:3:1\n\
+            skipped: utils::slow: exceeded the time budget of 5s\n";
+        assert_eq!(coverage_report(&coverage, &[]), expected);
+    }
+}
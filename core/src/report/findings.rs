@@ -0,0 +1,486 @@
+//! Submodule that describes the findings of the deadlock analysis
+//! and matches them against the suppressions annotated in the source code.
+//!
+//! In a deadlock, every thread that did not finish has a token in a place of its control flow
+//! and waits at the transitions that consume from it, e.g. the call to `lock` of a mutex that is held.
+//! These are the blocked transitions of the finding and their spans locate the deadlock in the source code.
+//! The places that model the synchronization primitives and the marker places of the annotated properties
+//! are not part of the control flow, since they may have tokens without any thread waiting on them.
+//!
+//! A finding is suppressed if one of its blocked transitions originates in the code after
+//! an `allow` annotation of the same kind, see `crate::annotations`.
+//! A finding is acknowledged if it was recorded in the baseline, see `crate::report::baseline`.
+//!
+//! When many findings are reported, they are ranked by a heuristic severity,
+//! so that the deadlock most likely to happen in practice is listed first.
+//!
+//! Every verdict assumes that the synchronization primitives are only used through the variables tracked
+//! in the translation. The primitives that `unsafe` code may alias are listed as unverified assumptions.
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::simulation::Simulation;
+use crate::annotations::Suppression;
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::span_info::SpanInfo;
+use crate::data_structures::sync_registry::SyncObject;
+use crate::model_checker::lola::MarkingPattern;
+use crate::naming::is_unwind_transition_label;
+use crate::naming::property::marker_place_labels;
+use crate::Translation;
+
+/// The kinds of findings reported by the analysis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    Deadlock,
+}
+
+impl FindingKind {
+    /// Returns the kind of finding with the name used in the annotations, e.g. `deadlock`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "deadlock" => Some(Self::Deadlock),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deadlock => write!(f, "deadlock"),
+        }
+    }
+}
+
+/// A finding of the analysis, e.g. a reachable deadlock.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// The kind of finding.
+    pub kind: FindingKind,
+    /// The labels of the transitions fired from the initial marking to reach the finding.
+    pub path: Vec<String>,
+    /// The labels of the control flow places with a token, i.e. where the threads are blocked.
+    pub marked_places: Vec<String>,
+    /// The labels of the transitions at which the threads are blocked.
+    pub blocked: Vec<String>,
+    /// The spans of the blocked transitions that originate from the source code.
+    pub spans: Vec<SpanInfo>,
+    /// The location of the annotation that suppresses the finding, if any.
+    pub suppressed_by: Option<SpanInfo>,
+    /// Whether the finding was recorded in the baseline.
+    pub acknowledged: bool,
+}
+
+impl Finding {
+    /// Returns the pattern of the markings where the threads are blocked at the same places.
+    /// It is used to look for other findings of the same kind.
+    #[must_use]
+    pub fn pattern(&self) -> MarkingPattern {
+        self.marked_places
+            .iter()
+            .map(|place| vec![place.clone()])
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let locations: Vec<String> = self.spans.iter().map(ToString::to_string).collect();
+        write!(f, "{} blocked at {}", self.kind, locations.join(", "))?;
+        if let Some(annotation) = &self.suppressed_by {
+            write!(f, " (suppressed by the annotation at {annotation})")?;
+        }
+        if self.acknowledged {
+            write!(f, " (acknowledged in the baseline)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the synchronization primitive modeled by the place, if any.
+/// The labels of its places start with the label of the primitive, e.g. `MUTEX_0` or `CONDVAR_0_NOTIFY`.
+pub(crate) fn sync_object_of<'a>(
+    label: &str,
+    translation: &'a Translation,
+) -> Option<&'a SyncObject> {
+    translation.registry.iter().find(|object| {
+        label
+            .strip_prefix(object.label.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+    })
+}
+
+/// The heuristic severity of a finding, used to rank the findings for the triage.
+///
+/// A finding is more likely to happen in practice if it does not require a panic,
+/// blocks more threads and is reached in fewer steps. The findings are ordered by these criteria in that order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Severity {
+    /// Whether a `panic!` or an unwind path is necessary to reach the finding.
+    pub panic_path: bool,
+    /// The number of threads blocked, i.e. the number of control flow places with a token.
+    pub threads: usize,
+    /// The number of transitions fired to reach the finding.
+    pub depth: usize,
+}
+
+impl Severity {
+    /// Returns a key that sorts the most severe findings first.
+    fn sort_key(self) -> (bool, std::cmp::Reverse<usize>, usize) {
+        (self.panic_path, std::cmp::Reverse(self.threads), self.depth)
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let threads = if self.threads == 1 {
+            "thread"
+        } else {
+            "threads"
+        };
+        let steps = if self.depth == 1 { "step" } else { "steps" };
+        write!(
+            f,
+            "{} {threads} blocked after {} {steps}",
+            self.threads, self.depth
+        )?;
+        if self.panic_path {
+            write!(f, " on a panic path")?;
+        }
+        Ok(())
+    }
+}
+
+impl Finding {
+    /// Returns the heuristic severity of the finding.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        Severity {
+            panic_path: self
+                .path
+                .iter()
+                .any(|transition| is_unwind_transition_label(transition)),
+            threads: self.marked_places.len(),
+            depth: self.path.len(),
+        }
+    }
+}
+
+/// Sorts the findings by severity, the most severe first.
+/// The order of findings with the same severity is preserved.
+pub fn sort_by_severity(findings: &mut [Finding]) {
+    findings.sort_by_key(|finding| finding.severity().sort_key());
+}
+
+/// Checks whether the place belongs to the control flow of a thread,
+/// i.e. it does not model a synchronization primitive or record an annotated property.
+pub(crate) fn is_control_place(label: &str, translation: &Translation) -> bool {
+    let is_sync_place = sync_object_of(label, translation).is_some();
+    let is_marker_place = translation.annotated_properties.iter().any(|property| {
+        let (not_reached, reached) = marker_place_labels(&property.name);
+        label == not_reached || label == reached
+    });
+    !is_sync_place && !is_marker_place
+}
+
+/// Returns the control flow places from which the transitions that originate at the matching spans consume.
+/// A marking with a token in one of these places has a thread waiting in the matching code.
+pub fn control_places_at(
+    translation: &Translation,
+    net: &NetData,
+    matches: impl Fn(&SpanInfo) -> bool,
+) -> Vec<String> {
+    let transitions: Vec<&str> = net
+        .transitions
+        .iter()
+        .filter(|transition| transition.span.as_ref().is_some_and(&matches))
+        .map(|transition| transition.label.as_str())
+        .collect();
+
+    let mut places: Vec<String> = net
+        .arcs
+        .iter()
+        .filter(|arc| {
+            arc.direction == ArcDirection::PlaceToTransition
+                && transitions.contains(&arc.transition.as_str())
+                && is_control_place(&arc.place, translation)
+        })
+        .map(|arc| arc.place.clone())
+        .collect();
+    places.sort();
+    places.dedup();
+    places
+}
+
+/// Returns the control flow places of the code where the given kind of finding is suppressed.
+#[must_use]
+pub fn suppressed_places(
+    translation: &Translation,
+    net: &NetData,
+    kind: FindingKind,
+) -> Vec<String> {
+    let suppressions: Vec<&Suppression> = translation
+        .suppressions
+        .iter()
+        .filter(|suppression| suppression.kind == kind)
+        .collect();
+    control_places_at(translation, net, |span| {
+        suppressions
+            .iter()
+            .any(|suppression| suppression.covers(span))
+    })
+}
+
+/// Creates the finding for the deadlock reached by firing the transitions in the path.
+/// The finding is suppressed by the first suppression of deadlocks that covers a blocked transition.
+///
+/// # Errors
+///
+/// If the net is not valid or a transition in the path cannot be fired, then an error is returned.
+pub fn deadlock_finding(
+    path: Vec<String>,
+    translation: &Translation,
+    net: &NetData,
+) -> Result<Finding, String> {
+    let mut simulation = Simulation::new(net)?;
+    for transition in &path {
+        simulation.fire(transition)?;
+    }
+    let marking = simulation.marking();
+
+    let marked_places: Vec<String> = net
+        .places
+        .iter()
+        .zip(marking)
+        .filter(|(place, tokens)| **tokens > 0 && is_control_place(&place.label, translation))
+        .map(|(place, _)| place.label.clone())
+        .collect();
+    let mut blocked: Vec<String> = Vec::new();
+    for arc in &net.arcs {
+        if arc.direction == ArcDirection::PlaceToTransition
+            && marked_places.contains(&arc.place)
+            && !blocked.contains(&arc.transition)
+        {
+            blocked.push(arc.transition.clone());
+        }
+    }
+
+    let mut spans: Vec<SpanInfo> = net
+        .transitions
+        .iter()
+        .filter(|transition| blocked.contains(&transition.label))
+        .filter_map(|transition| transition.span.clone())
+        .collect();
+    spans.sort();
+    spans.dedup();
+
+    let suppressed_by = translation
+        .suppressions
+        .iter()
+        .filter(|suppression| suppression.kind == FindingKind::Deadlock)
+        .find(|suppression| spans.iter().any(|span| suppression.covers(span)))
+        .map(|suppression| suppression.span.clone());
+
+    Ok(Finding {
+        kind: FindingKind::Deadlock,
+        path,
+        marked_places,
+        blocked,
+        spans,
+        suppressed_by,
+        acknowledged: false,
+    })
+}
+
+/// Returns the assumptions of the verdict that the translation could not verify, one sentence each.
+///
+/// The synchronization primitives marked with unknown aliasing are assumed not to be used
+/// through an alias created by the `unsafe` code.
+/// The functions skipped during the translation are assumed not to use synchronization primitives.
+#[must_use]
+pub fn unverified_assumptions(translation: &Translation) -> Vec<String> {
+    let skipped = translation.coverage.skipped().map(|(name, reason)| {
+        format!(
+            "The verdict assumes that the skipped function {name} does not use synchronization primitives ({reason})"
+        )
+    });
+    translation
+        .registry
+        .iter()
+        .filter(|object| !object.unknown_aliasing.is_empty())
+        .map(|object| {
+            let locations: Vec<String> = object
+                .unknown_aliasing
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            format!(
+                "The verdict assumes that the {} {} created at {} is not used through the unknown aliases created by the `unsafe` code at {}",
+                object.kind,
+                object.label,
+                object.span,
+                locations.join(", ")
+            )
+        })
+        .chain(skipped)
+        .collect()
+}
+
+#[cfg(test)]
+mod findings_tests {
+    use super::*;
+    use crate::annotations::parse_suppressions;
+    use crate::data_structures::petri_net_interface::{
+        add_arc_place_transition, connect_places, PetriNet,
+    };
+    use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+
+    /// A thread that holds `MUTEX_0` and waits at a second lock of it in line 4.
+    fn double_lock(source_code: &str) -> Translation {
+        let mut net = PetriNet::new();
+        let start = net.add_place("START");
+        let locked = net.add_place("LOCKED");
+        let end = net.add_place("END");
+        let mutex = net.add_place("MUTEX_0");
+        net.add_token(&start, 1).unwrap();
+        net.add_token(&mutex, 1).unwrap();
+        net.set_current_span(Some(SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: 2,
+            ..SpanInfo::default()
+        }));
+        let first_lock = connect_places(&mut net, &start, &locked, "LOCK_0");
+        net.set_current_span(Some(SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: 4,
+            ..SpanInfo::default()
+        }));
+        let second_lock = connect_places(&mut net, &locked, &end, "LOCK_1");
+        net.set_current_span(None);
+        add_arc_place_transition(&mut net, &mutex, &first_lock);
+        add_arc_place_transition(&mut net, &mutex, &second_lock);
+
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        Translation {
+            net,
+            registry,
+            suppressions: parse_suppressions(source_code, "main.rs"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn deadlock_finding_reports_the_blocked_transitions() {
+        let translation = double_lock("");
+        let net = translation.net.data();
+
+        let finding = deadlock_finding(vec!["LOCK_0".to_string()], &translation, &net).unwrap();
+
+        assert_eq!(finding.marked_places, ["LOCKED"]);
+        assert_eq!(finding.pattern(), [["LOCKED"]]);
+        assert_eq!(finding.blocked, ["LOCK_1"]);
+        assert_eq!(finding.spans.len(), 1);
+        assert_eq!(finding.spans[0].start_line, 4);
+        assert_eq!(finding.suppressed_by, None);
+        assert_eq!(finding.to_string(), "deadlock blocked at main.rs:4:0");
+        assert_eq!(
+            finding.severity().to_string(),
+            "1 thread blocked after 1 step"
+        );
+    }
+
+    #[test]
+    fn deadlock_finding_is_suppressed_by_an_annotation_covering_a_blocked_transition() {
+        let source_code = "fn main() {\n    let _first = lock();\n    // granite: allow(deadlock)\n    let _second = lock();\n}\n";
+        let translation = double_lock(source_code);
+        let net = translation.net.data();
+
+        let finding = deadlock_finding(vec!["LOCK_0".to_string()], &translation, &net).unwrap();
+
+        assert!(finding.suppressed_by.is_some());
+        assert_eq!(
+            suppressed_places(&translation, &net, FindingKind::Deadlock),
+            ["LOCKED"]
+        );
+    }
+
+    #[test]
+    fn deadlock_finding_rejects_paths_that_cannot_be_fired() {
+        let translation = double_lock("");
+        let net = translation.net.data();
+
+        assert!(deadlock_finding(vec!["LOCK_1".to_string()], &translation, &net).is_err());
+    }
+
+    #[test]
+    fn unverified_assumptions_list_the_primitives_with_unknown_aliasing() {
+        let mut translation = double_lock("");
+        assert!(unverified_assumptions(&translation).is_empty());
+
+        translation.registry.mark_unknown_aliasing(
+            "MUTEX_0",
+            SpanInfo {
+                filename: "main.rs".to_string(),
+                start_line: 3,
+                ..SpanInfo::default()
+            },
+        );
+
+        assert_eq!(
+            unverified_assumptions(&translation),
+            ["The verdict assumes that the mutex MUTEX_0 created at :0:0 is not used through the unknown aliases created by the `unsafe` code at main.rs:3:0"]
+        );
+    }
+
+    #[test]
+    fn unverified_assumptions_list_the_skipped_functions() {
+        let mut translation = double_lock("");
+        translation
+            .coverage
+            .record_skipped("utils::parse", "not implemented: TerminatorKind::InlineAsm");
+
+        assert_eq!(
+            unverified_assumptions(&translation),
+            ["The verdict assumes that the skipped function utils::parse does not use synchronization primitives (not implemented: TerminatorKind::InlineAsm)"]
+        );
+    }
+
+    #[test]
+    fn findings_are_sorted_by_severity() {
+        let finding = |path: &[&str], marked_places: &[&str]| Finding {
+            kind: FindingKind::Deadlock,
+            path: path.iter().map(ToString::to_string).collect(),
+            marked_places: marked_places.iter().map(ToString::to_string).collect(),
+            blocked: Vec::new(),
+            spans: Vec::new(),
+            suppressed_by: None,
+            acknowledged: false,
+        };
+        let mut findings = vec![
+            finding(&["main_0_CALL", "main_0_CALL_UNWIND"], &["A", "B"]),
+            finding(&["main_0_CALL", "main_1_CALL", "main_2_CALL"], &["A"]),
+            finding(&["main_0_CALL", "main_1_CALL"], &["A"]),
+            finding(&["main_0_CALL", "main_1_CALL", "main_2_CALL"], &["A", "B"]),
+        ];
+
+        sort_by_severity(&mut findings);
+
+        let severities: Vec<String> = findings
+            .iter()
+            .map(|finding| finding.severity().to_string())
+            .collect();
+        assert_eq!(
+            severities,
+            [
+                "2 threads blocked after 3 steps",
+                "1 thread blocked after 2 steps",
+                "1 thread blocked after 3 steps",
+                "2 threads blocked after 2 steps on a panic path",
+            ]
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Submodule that generates the matrix of interactions between the threads and the synchronization primitives.
+//!
+//! The control flow of every thread is traversed in the net, starting from its start place:
+//! `PROGRAM_START` for the main thread and `THREAD_<index>_START` for the spawned threads.
+//! Every transition reached uses the synchronization primitives whose places it is connected to.
+//! The primitives are listed in the order in which the thread uses them for the first time,
+//! which is the order of a breadth-first traversal of the control flow.
+//!
+//! Two threads that lock two mutexes or read-write locks in opposite order are reported as an inversion,
+//! the classic cause of a deadlock. This is a heuristic that does not require running the model checker:
+//! The inversion may be harmless, e.g. if the locks are not held at the same time.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::data_structures::net_data::ArcDirection;
+use crate::data_structures::sync_registry::SyncKind;
+use crate::naming::thread::is_start_place_label;
+use crate::naming::PROGRAM_START;
+use crate::report::findings::{is_control_place, sync_object_of};
+use crate::Translation;
+
+/// The name used for the main thread in the report.
+const MAIN_THREAD: &str = "main";
+
+/// The synchronization primitives used by a thread.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ThreadInteractions {
+    /// The name of the thread, `main` or the label of the thread, e.g. `THREAD_0`.
+    pub thread: String,
+    /// The labels of the primitives in the order in which the thread uses them for the first time.
+    pub primitives: Vec<String>,
+}
+
+/// Two locks that are used in opposite order by two threads.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LockOrderInversion {
+    /// The lock used first by `first_thread`.
+    pub first_lock: String,
+    /// The lock used first by `second_thread`.
+    pub second_lock: String,
+    /// The thread that uses `first_lock` before `second_lock`.
+    pub first_thread: String,
+    /// The thread that uses `second_lock` before `first_lock`.
+    pub second_thread: String,
+}
+
+/// The matrix of interactions between the threads and the synchronization primitives.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct InteractionMatrix {
+    /// The labels of the primitives used by at least one thread, in the order of the registry.
+    pub primitives: Vec<String>,
+    /// The primitives used by every thread, starting with the main thread.
+    pub threads: Vec<ThreadInteractions>,
+    /// The pairs of locks used in opposite order by two threads.
+    pub inversions: Vec<LockOrderInversion>,
+}
+
+impl InteractionMatrix {
+    /// Computes the matrix of interactions from the net of the translation.
+    /// The threads themselves are not listed as primitives, only the primitives they share.
+    #[must_use]
+    pub fn new(translation: &Translation) -> Self {
+        let net = translation.net.data();
+        let mut consumers: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut outputs: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut connected: HashMap<&str, Vec<&str>> = HashMap::new();
+        for arc in &net.arcs {
+            match arc.direction {
+                ArcDirection::PlaceToTransition => consumers
+                    .entry(arc.place.as_str())
+                    .or_default()
+                    .push(arc.transition.as_str()),
+                ArcDirection::TransitionToPlace => outputs
+                    .entry(arc.transition.as_str())
+                    .or_default()
+                    .push(arc.place.as_str()),
+            }
+            connected
+                .entry(arc.transition.as_str())
+                .or_default()
+                .push(arc.place.as_str());
+        }
+
+        let mut start_places = vec![(MAIN_THREAD.to_string(), PROGRAM_START)];
+        for place in &net.places {
+            if let Some(thread) = is_start_place_label(&place.label)
+                .then(|| place.label.strip_suffix("_START"))
+                .flatten()
+            {
+                start_places.push((thread.to_string(), place.label.as_str()));
+            }
+        }
+
+        let threads: Vec<ThreadInteractions> = start_places
+            .into_iter()
+            .map(|(thread, start_place)| {
+                let mut primitives: Vec<String> = Vec::new();
+                let mut visited_places: HashSet<&str> = HashSet::from([start_place]);
+                let mut visited_transitions: HashSet<&str> = HashSet::new();
+                let mut queue = VecDeque::from([start_place]);
+                while let Some(place) = queue.pop_front() {
+                    for transition in consumers.get(place).into_iter().flatten() {
+                        if !visited_transitions.insert(transition) {
+                            continue;
+                        }
+                        for sync_place in connected.get(transition).into_iter().flatten() {
+                            let Some(object) = sync_object_of(sync_place, translation) else {
+                                continue;
+                            };
+                            if object.kind != SyncKind::Thread
+                                && !primitives.contains(&object.label)
+                            {
+                                primitives.push(object.label.clone());
+                            }
+                        }
+                        for output in outputs.get(transition).into_iter().flatten() {
+                            if is_control_place(output, translation)
+                                && visited_places.insert(output)
+                            {
+                                queue.push_back(output);
+                            }
+                        }
+                    }
+                }
+                ThreadInteractions { thread, primitives }
+            })
+            .collect();
+
+        let primitives: Vec<String> = translation
+            .registry
+            .iter()
+            .filter(|object| {
+                threads
+                    .iter()
+                    .any(|thread| thread.primitives.contains(&object.label))
+            })
+            .map(|object| object.label.clone())
+            .collect();
+        let inversions = lock_order_inversions(&threads, translation);
+
+        Self {
+            primitives,
+            threads,
+            inversions,
+        }
+    }
+
+    /// Returns the representation of the matrix in JSON.
+    ///
+    /// # Panics
+    ///
+    /// If the matrix cannot be serialized, then the function panics. This should never happen.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("BUG: The interaction matrix should be serializable to JSON")
+    }
+}
+
+/// Returns the pairs of locks used in opposite order by two threads.
+fn lock_order_inversions(
+    threads: &[ThreadInteractions],
+    translation: &Translation,
+) -> Vec<LockOrderInversion> {
+    let locks: Vec<Vec<&String>> = threads
+        .iter()
+        .map(|thread| {
+            thread
+                .primitives
+                .iter()
+                .filter(|label| {
+                    translation.registry.get(label).is_some_and(|object| {
+                        matches!(object.kind, SyncKind::Mutex | SyncKind::RwLock)
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut inversions = Vec::new();
+    for (i, first) in locks.iter().enumerate() {
+        for (j, second) in locks.iter().enumerate().skip(i + 1) {
+            for (a, first_lock) in first.iter().enumerate() {
+                for second_lock in &first[a + 1..] {
+                    let position = |lock| second.iter().position(|label| *label == lock);
+                    if let (Some(x), Some(y)) = (position(*first_lock), position(*second_lock)) {
+                        if y < x {
+                            inversions.push(LockOrderInversion {
+                                first_lock: (*first_lock).clone(),
+                                second_lock: (*second_lock).clone(),
+                                first_thread: threads[i].thread.clone(),
+                                second_thread: threads[j].thread.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    inversions
+}
+
+/// Formats the matrix as a table with a row per thread and a column per primitive.
+/// Every cell contains the position of the primitive in the order of first use by the thread,
+/// or `-` if the thread does not use it. The inversions are listed below the table.
+impl std::fmt::Display for InteractionMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let thread_width = self
+            .threads
+            .iter()
+            .map(|thread| thread.thread.len())
+            .chain(std::iter::once("Thread".len()))
+            .max()
+            .unwrap_or_default();
+
+        let mut header = format!("{:thread_width$}", "Thread");
+        for primitive in &self.primitives {
+            let _ = write!(header, "  {primitive}");
+        }
+        writeln!(f, "{}", header.trim_end())?;
+        for thread in &self.threads {
+            let mut row = format!("{:thread_width$}", thread.thread);
+            for primitive in &self.primitives {
+                let cell = thread
+                    .primitives
+                    .iter()
+                    .position(|label| label == primitive)
+                    .map_or_else(|| "-".to_string(), |position| (position + 1).to_string());
+                let _ = write!(row, "  {cell:width$}", width = primitive.len());
+            }
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        for inversion in &self.inversions {
+            writeln!(
+                f,
+                "{} and {} are locked in opposite order by {} and {}",
+                inversion.first_lock,
+                inversion.second_lock,
+                inversion.first_thread,
+                inversion.second_thread
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod interactions_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::{
+        add_arc_place_transition, add_arc_transition_place, connect_places, PetriNet, PlaceRef,
+    };
+    use crate::data_structures::span_info::SpanInfo;
+    use crate::data_structures::sync_registry::SyncRegistry;
+
+    /// Adds a thread that locks the mutexes in the given order.
+    fn add_thread(net: &mut PetriNet, start: &PlaceRef, name: &str, mutexes: &[&PlaceRef]) {
+        let mut place = start.clone();
+        for (index, mutex) in mutexes.iter().enumerate() {
+            let locked = net.add_place(&format!("{name}_BB{index}"));
+            let lock = connect_places(net, &place, &locked, &format!("{name}_LOCK_{index}"));
+            add_arc_place_transition(net, mutex, &lock);
+            place = locked;
+        }
+    }
+
+    /// Two threads that lock `MUTEX_0` and `MUTEX_1` in opposite order.
+    /// The main thread spawns `THREAD_0` and uses `CONDVAR_0`.
+    fn philosophers() -> Translation {
+        let mut net = PetriNet::new();
+        let program_start = net.add_place(PROGRAM_START);
+        let main_bb = net.add_place("main_BB0");
+        let thread_start = net.add_place("THREAD_0_START");
+        let mutex_0 = net.add_place("MUTEX_0");
+        let mutex_1 = net.add_place("MUTEX_1");
+        let notify = net.add_place("CONDVAR_0_NOTIFY");
+        net.add_token(&program_start, 1).unwrap();
+
+        let spawn = connect_places(&mut net, &program_start, &main_bb, "SPAWN");
+        add_arc_transition_place(&mut net, &spawn, &thread_start);
+        let notify_one = net.add_transition("NOTIFY_ONE");
+        add_arc_place_transition(&mut net, &main_bb, &notify_one);
+        add_arc_transition_place(&mut net, &notify_one, &notify);
+        add_thread(&mut net, &main_bb, "main", &[&mutex_0, &mutex_1]);
+        add_thread(&mut net, &thread_start, "thread", &[&mutex_1, &mutex_0]);
+
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.add(SyncKind::Mutex, "MUTEX_1", SpanInfo::default());
+        registry.add(SyncKind::Condvar, "CONDVAR_0", SpanInfo::default());
+        registry.add(SyncKind::Thread, "THREAD_0", SpanInfo::default());
+        Translation {
+            net,
+            registry,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matrix_lists_the_primitives_in_the_order_of_first_use() {
+        let matrix = InteractionMatrix::new(&philosophers());
+
+        assert_eq!(matrix.primitives, ["MUTEX_0", "MUTEX_1", "CONDVAR_0"]);
+        assert_eq!(matrix.threads.len(), 2);
+        assert_eq!(matrix.threads[0].thread, "main");
+        assert_eq!(
+            matrix.threads[0].primitives,
+            ["CONDVAR_0", "MUTEX_0", "MUTEX_1"]
+        );
+        assert_eq!(matrix.threads[1].thread, "THREAD_0");
+        assert_eq!(matrix.threads[1].primitives, ["MUTEX_1", "MUTEX_0"]);
+    }
+
+    #[test]
+    fn matrix_reports_locks_in_opposite_order() {
+        let matrix = InteractionMatrix::new(&philosophers());
+
+        assert_eq!(
+            matrix.inversions,
+            [LockOrderInversion {
+                first_lock: "MUTEX_0".to_string(),
+                second_lock: "MUTEX_1".to_string(),
+                first_thread: "main".to_string(),
+                second_thread: "THREAD_0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn matrix_is_formatted_as_a_table() {
+        let matrix = InteractionMatrix::new(&philosophers());
+
+        assert_eq!(
+            matrix.to_string(),
+            "Thread    MUTEX_0  MUTEX_1  CONDVAR_0
+main      2        3        1
+THREAD_0  2        1        -
+MUTEX_0 and MUTEX_1 are locked in opposite order by main and THREAD_0
+"
+        );
+        assert!(matrix.to_json().contains("\"first_lock\": \"MUTEX_0\""));
+    }
+}
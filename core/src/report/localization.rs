@@ -0,0 +1,431 @@
+//! Submodule that localizes a deadlock per thread and explains it as chains of waits,
+//! e.g. "`main` waits for the mutex `MUTEX_1` held by `THREAD_0`, which waits for the mutex `MUTEX_0` held by `main`".
+//!
+//! The net does not tell the threads apart, so the witness path of the finding is replayed
+//! while following the tokens of the control flow:
+//! - The tokens in the control flow places of the initial marking belong to the main thread, named `main`.
+//! - A transition that consumes the token of a thread passes it on to its first control flow output.
+//! - A transition that consumes no token of the control flow but produces one starts a new thread,
+//!   e.g. the first transition of a spawned closure. The thread is named after the primitive
+//!   of the thread from which the transition consumes, e.g. `THREAD_0`.
+//!
+//! In the dead marking, every token left in a control flow place is a blocked thread.
+//! It waits for the empty places of the synchronization primitives that the transitions leaving its place consume from:
+//! - A lock: The place of a mutex or a read-write lock is empty. It is held by the thread that took it last.
+//! - A message: The buffer of a channel is empty.
+//! - Free space: The free slots of a bounded channel are exhausted.
+//! - A notification: A place of a condition variable is empty.
+//! - A join: The end place of a thread is empty. The thread waited for is the one started from it.
+//!
+//! Following the threads that hold what a thread waits for gives its chain of waits.
+//! A chain that returns to a thread already in it is the cycle of the deadlock.
+//! Several instances of the same thread share the places of the net, so their tokens may be swapped.
+
+use std::collections::HashMap;
+
+use crate::analysis::simulation::Simulation;
+use crate::analysis::FINAL_PLACES;
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::sync_registry::SyncKind;
+use crate::report::findings::{is_control_place, sync_object_of, Finding};
+use crate::report::snippets::describe_place;
+use crate::Translation;
+
+/// The kinds of waits of a blocked thread, see the module documentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitKind {
+    Lock,
+    Message,
+    FreeSpace,
+    Notification,
+    Join,
+    Other,
+}
+
+/// A synchronization place that a blocked thread waits for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wait {
+    /// The label of the empty place.
+    pub place: String,
+    /// The kind of wait.
+    pub kind: WaitKind,
+    /// The description of the synchronization primitive, e.g. "the mutex `MUTEX_0`".
+    pub description: String,
+    /// The name of the thread that holds the lock or that is joined, if known.
+    pub holder: Option<String>,
+}
+
+impl std::fmt::Display for Wait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            WaitKind::Lock => {
+                write!(f, "{}", self.description)?;
+                if let Some(holder) = &self.holder {
+                    write!(f, " held by `{holder}`")?;
+                }
+                Ok(())
+            }
+            WaitKind::Message => write!(f, "a message on {}", self.description),
+            WaitKind::FreeSpace => write!(f, "free space in {}", self.description),
+            WaitKind::Notification => write!(f, "a notification of {}", self.description),
+            WaitKind::Join => write!(f, "{} to finish", self.description),
+            WaitKind::Other => write!(f, "{}", self.description),
+        }
+    }
+}
+
+/// A thread blocked in the dead marking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockedThread {
+    /// The name of the thread, e.g. `main` or `THREAD_0`.
+    pub name: String,
+    /// The label of the control flow place where the thread is blocked.
+    pub place: String,
+    /// The synchronization places that the thread waits for.
+    pub waits: Vec<Wait>,
+}
+
+/// The blocked threads of a deadlock.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Localization {
+    /// The blocked threads, in the order of the places of the net.
+    pub threads: Vec<BlockedThread>,
+}
+
+impl Localization {
+    /// Returns the chain of waits that starts at the thread with the given index,
+    /// as pairs of a thread and what it waits for, and whether the chain ends in a cycle.
+    ///
+    /// The chain follows the first wait of every thread whose holder is a blocked thread.
+    /// It ends at a thread without such a wait, whose first wait is the last pair, if any.
+    #[must_use]
+    pub fn wait_chain(&self, start: usize) -> (Vec<(&BlockedThread, &Wait)>, bool) {
+        let mut chain = Vec::new();
+        let mut visited = vec![start];
+        let mut current = start;
+        loop {
+            let thread = &self.threads[current];
+            let next = thread.waits.iter().find_map(|wait| {
+                let holder = wait.holder.as_ref()?;
+                let index = self
+                    .threads
+                    .iter()
+                    .position(|other| other.name == *holder)?;
+                Some((wait, index))
+            });
+            let Some((wait, index)) = next else {
+                if let Some(wait) = thread.waits.first() {
+                    chain.push((thread, wait));
+                }
+                return (chain, false);
+            };
+            chain.push((thread, wait));
+            if visited.contains(&index) {
+                return (chain, true);
+            }
+            visited.push(index);
+            current = index;
+        }
+    }
+}
+
+impl std::fmt::Display for Localization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "blocked threads:")?;
+        for (index, thread) in self.threads.iter().enumerate() {
+            write!(f, "\n- `{}` at `{}`: ", thread.name, thread.place)?;
+            let (chain, cycle) = self.wait_chain(index);
+            if chain.is_empty() {
+                write!(f, "no transition can fire")?;
+                continue;
+            }
+            for (position, (_, wait)) in chain.iter().enumerate() {
+                if position > 0 {
+                    write!(f, ", which ")?;
+                }
+                write!(f, "waits for {wait}")?;
+            }
+            if cycle {
+                write!(f, " (cycle)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the blocked threads of the deadlock reached by the path of the finding.
+///
+/// # Errors
+///
+/// If the net is not valid or a transition in the path of the finding cannot be fired,
+/// then an error is returned.
+pub fn localize_deadlock(
+    finding: &Finding,
+    translation: &Translation,
+    net: &NetData,
+) -> Result<Localization, String> {
+    let mut simulation = Simulation::new(net)?;
+    let is_control = |place: &str| is_control_place(place, translation);
+
+    let mut names: Vec<String> = Vec::new();
+    let mut tokens: HashMap<&str, Vec<usize>> = HashMap::new();
+    for place in &net.places {
+        if place.marking > 0 && is_control(&place.label) {
+            for _ in 0..place.marking {
+                let name = if names.is_empty() {
+                    "main".to_string()
+                } else {
+                    format!("thread {}", names.len())
+                };
+                tokens.entry(&place.label).or_default().push(names.len());
+                names.push(name);
+            }
+        }
+    }
+
+    let mut last_taker: HashMap<&str, usize> = HashMap::new();
+    for transition in &finding.path {
+        simulation.fire(transition)?;
+        let inputs: Vec<&str> = places_of(net, transition, ArcDirection::PlaceToTransition);
+        let outputs: Vec<&str> = places_of(net, transition, ArcDirection::TransitionToPlace)
+            .into_iter()
+            .filter(|place| is_control(place))
+            .collect();
+        let mut owner = None;
+        for place in inputs.iter().filter(|place| is_control(place)) {
+            let thread = tokens.get_mut(place).and_then(Vec::pop);
+            owner = owner.or(thread);
+        }
+        if owner.is_none() && !outputs.is_empty() {
+            owner = Some(start_thread(&mut names, &inputs, translation));
+        }
+        let Some(owner) = owner else {
+            continue;
+        };
+        for place in inputs.iter().filter(|place| !is_control(place)) {
+            last_taker.insert(place, owner);
+        }
+        for (index, place) in outputs.into_iter().enumerate() {
+            let thread = if index == 0 {
+                owner
+            } else {
+                start_thread(&mut names, &inputs, translation)
+            };
+            tokens.entry(place).or_default().push(thread);
+        }
+    }
+
+    let marking = simulation.marking();
+    let mut threads = Vec::new();
+    for (place, tokens_in_place) in net.places.iter().zip(marking) {
+        if *tokens_in_place == 0
+            || !is_control(&place.label)
+            || FINAL_PLACES.contains(&place.label.as_str())
+        {
+            continue;
+        }
+        let mut awaited: Vec<&str> = Vec::new();
+        for arc in &net.arcs {
+            if arc.direction != ArcDirection::PlaceToTransition || arc.place != place.label {
+                continue;
+            }
+            for input in places_of(net, &arc.transition, ArcDirection::PlaceToTransition) {
+                let empty = net
+                    .places
+                    .iter()
+                    .zip(marking)
+                    .any(|(other, tokens)| other.label == input && *tokens == 0);
+                if empty && !is_control(input) && !awaited.contains(&input) {
+                    awaited.push(input);
+                }
+            }
+        }
+        let waits: Vec<Wait> = awaited
+            .into_iter()
+            .map(|input| wait_for(input, translation, &names, &last_taker))
+            .collect();
+        let owners = tokens
+            .get(place.label.as_str())
+            .cloned()
+            .unwrap_or_default();
+        for index in 0..*tokens_in_place {
+            let name = owners.get(index).map_or_else(
+                || format!("thread at {}", place.label),
+                |id| names[*id].clone(),
+            );
+            threads.push(BlockedThread {
+                name,
+                place: place.label.clone(),
+                waits: waits.clone(),
+            });
+        }
+    }
+    Ok(Localization { threads })
+}
+
+/// Returns the labels of the places connected to the transition in the given direction,
+/// repeated according to the weight of the arc.
+fn places_of<'a>(net: &'a NetData, transition: &str, direction: ArcDirection) -> Vec<&'a str> {
+    net.arcs
+        .iter()
+        .filter(|arc| arc.direction == direction && arc.transition == transition)
+        .flat_map(|arc| std::iter::repeat_n(arc.place.as_str(), arc.weight))
+        .collect()
+}
+
+/// Adds a new thread started by a transition with the given input places and returns its index.
+/// It is named after the thread primitive that it consumes from, e.g. `THREAD_0`,
+/// followed by a number if another thread already has this name.
+fn start_thread(names: &mut Vec<String>, inputs: &[&str], translation: &Translation) -> usize {
+    let primitive = inputs.iter().find_map(|place| {
+        sync_object_of(place, translation).filter(|object| object.kind == SyncKind::Thread)
+    });
+    let name = primitive.map_or_else(
+        || format!("thread {}", names.len()),
+        |object| {
+            let instances = names
+                .iter()
+                .filter(|name| name.split(' ').next() == Some(object.label.as_str()))
+                .count();
+            if instances == 0 {
+                object.label.clone()
+            } else {
+                format!("{} #{}", object.label, instances + 1)
+            }
+        },
+    );
+    names.push(name);
+    names.len() - 1
+}
+
+/// Classifies the wait for the empty place and looks up the thread that holds it, see the module documentation.
+fn wait_for(
+    place: &str,
+    translation: &Translation,
+    names: &[String],
+    last_taker: &HashMap<&str, usize>,
+) -> Wait {
+    let object = sync_object_of(place, translation);
+    let is_primitive_place = object.is_some_and(|object| object.label == place);
+    let kind = match object.map(|object| object.kind) {
+        Some(SyncKind::Mutex | SyncKind::RwLock) if is_primitive_place => WaitKind::Lock,
+        Some(SyncKind::Channel) if place.ends_with("_FREE_SLOTS") => WaitKind::FreeSpace,
+        Some(SyncKind::Channel) => WaitKind::Message,
+        Some(SyncKind::Condvar) => WaitKind::Notification,
+        Some(SyncKind::Thread) if place.ends_with("_END") => WaitKind::Join,
+        _ => WaitKind::Other,
+    };
+    let holder = match (kind, object) {
+        (WaitKind::Lock, _) => last_taker.get(place).map(|thread| names[*thread].clone()),
+        (WaitKind::Join, Some(object)) => names.iter().find(|name| **name == object.label).cloned(),
+        _ => None,
+    };
+    Wait {
+        place: place.to_string(),
+        kind,
+        description: describe_place(place, translation),
+        holder,
+    }
+}
+
+#[cfg(test)]
+mod localization_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::{
+        add_arc_place_transition, add_arc_transition_place, connect_places, PetriNet,
+    };
+    use crate::data_structures::span_info::SpanInfo;
+    use crate::data_structures::sync_registry::SyncRegistry;
+    use crate::report::findings::deadlock_finding;
+
+    fn path(transitions: &[&str]) -> Vec<String> {
+        transitions.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn a_double_lock_is_a_cycle_of_one_thread() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("START");
+        let locked = net.add_place("LOCKED");
+        let end = net.add_place("END");
+        let mutex = net.add_place("MUTEX_0");
+        net.add_token(&start, 1).unwrap();
+        net.add_token(&mutex, 1).unwrap();
+        let first_lock = connect_places(&mut net, &start, &locked, "LOCK_0");
+        let second_lock = connect_places(&mut net, &locked, &end, "LOCK_1");
+        add_arc_place_transition(&mut net, &mutex, &first_lock);
+        add_arc_place_transition(&mut net, &mutex, &second_lock);
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+        let net = translation.net.data();
+        let finding = deadlock_finding(path(&["LOCK_0"]), &translation, &net).unwrap();
+
+        let localization = localize_deadlock(&finding, &translation, &net).unwrap();
+
+        assert_eq!(
+            localization.to_string(),
+            "blocked threads:\n- `main` at `LOCKED`: waits for the mutex `MUTEX_0` held by `main` (cycle)"
+        );
+    }
+
+    #[test]
+    fn locks_taken_in_opposite_order_form_a_cycle_of_two_threads() {
+        let mut net = PetriNet::new();
+        let places: Vec<_> = ["A0", "A1", "A2", "A3", "B0", "B1", "B2"]
+            .into_iter()
+            .map(|label| net.add_place(label))
+            .collect();
+        let thread_start = net.add_place("THREAD_0_START");
+        let first_mutex = net.add_place("MUTEX_0");
+        let second_mutex = net.add_place("MUTEX_1");
+        net.add_token(&places[0], 1).unwrap();
+        net.add_token(&first_mutex, 1).unwrap();
+        net.add_token(&second_mutex, 1).unwrap();
+        let spawn = connect_places(&mut net, &places[0], &places[1], "SPAWN");
+        add_arc_transition_place(&mut net, &spawn, &thread_start);
+        connect_places(&mut net, &thread_start, &places[4], "THREAD_0_RUN");
+        let locks = [
+            (1, 2, &first_mutex, "LOCK_A0"),
+            (2, 3, &second_mutex, "LOCK_A1"),
+            (4, 5, &second_mutex, "LOCK_B0"),
+            (5, 6, &first_mutex, "LOCK_B1"),
+        ];
+        for (from, to, mutex, label) in locks {
+            let lock = connect_places(&mut net, &places[from], &places[to], label);
+            add_arc_place_transition(&mut net, mutex, &lock);
+        }
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.add(SyncKind::Mutex, "MUTEX_1", SpanInfo::default());
+        registry.add(SyncKind::Thread, "THREAD_0", SpanInfo::default());
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+        let net = translation.net.data();
+        let finding = deadlock_finding(
+            path(&["SPAWN", "THREAD_0_RUN", "LOCK_A0", "LOCK_B0"]),
+            &translation,
+            &net,
+        )
+        .unwrap();
+
+        let localization = localize_deadlock(&finding, &translation, &net).unwrap();
+
+        assert_eq!(
+            localization.to_string(),
+            "blocked threads:\n\
+            - `main` at `A2`: waits for the mutex `MUTEX_1` held by `THREAD_0`, \
+            which waits for the mutex `MUTEX_0` held by `main` (cycle)\n\
+            - `THREAD_0` at `B1`: waits for the mutex `MUTEX_0` held by `main`, \
+            which waits for the mutex `MUTEX_1` held by `THREAD_0` (cycle)"
+        );
+        assert_eq!(localization.threads[0].waits[0].kind, WaitKind::Lock);
+    }
+}
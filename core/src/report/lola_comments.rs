@@ -0,0 +1,290 @@
+//! Submodule that generates the comments that explain the files handed to the model checker `LoLA`.
+//!
+//! The net in `LoLA` format is preceded by a header that maps the places
+//! to the entities of the source code they model, e.g.:
+//!
+//! ```text
+//! {
+//!   MUTEX_0: mutex created at src/main.rs:2:16
+//!     places: MUTEX_0, MUTEX_0_CONDITION_NOT_SET, MUTEX_0_CONDITION_SET
+//! }
+//! ```
+//!
+//! The formula file contains the deadlock formula preceded by an explanation in plain language.
+//! The companion formula files contain one canned property each, since `LoLA` checks one formula at a time,
+//! see `companion_formula_files`.
+//! Comments in `LoLA` are enclosed in curly braces, so the braces in the text are replaced with parentheses.
+//! This way, the files are self-explanatory when they are shared without the source code or the tool.
+
+use std::fmt::Write;
+
+use crate::data_structures::sync_registry::SyncKind;
+use crate::model_checker::lola::DEADLOCK_FORMULA;
+use crate::naming::{condvar, PROGRAM_END, PROGRAM_PANIC, PROGRAM_START};
+use crate::Translation;
+
+/// Returns the comment header for the net in `LoLA` format.
+///
+/// It explains the places that model the state of the program,
+/// the places that model each synchronization primitive with the span where it was created
+/// and the naming of the rest of the places.
+#[must_use]
+pub fn lola_header(translation: &Translation) -> String {
+    let place_labels = translation.net.place_labels();
+    let mut lines = vec![
+        "Petri net generated by cargo-check-deadlock.".to_string(),
+        "Each place models a state of the program.".to_string(),
+        "A token in a place means that a thread is in that state.".to_string(),
+        String::new(),
+        format!("{PROGRAM_START}: The program has not started yet."),
+        format!("{PROGRAM_END}: The program terminated normally."),
+        format!("{PROGRAM_PANIC}: The program terminated with a panic."),
+    ];
+
+    if !translation.registry.is_empty() {
+        lines.push(String::new());
+        lines.push("Synchronization primitives:".to_string());
+    }
+    for object in &translation.registry {
+        lines.push(format!(
+            "{}: {} created at {}",
+            object.label, object.kind, object.span
+        ));
+        let prefix = format!("{}_", object.label);
+        let places: Vec<&str> = place_labels
+            .iter()
+            .copied()
+            .filter(|label| *label == object.label || label.starts_with(&prefix))
+            .collect();
+        if !places.is_empty() {
+            lines.push(format!("  places: {}", places.join(", ")));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("The rest of the places model the control flow of the functions,".to_string());
+    lines.push("e.g. `main_BB1` is the basic block 1 of `main`.".to_string());
+    comment(&lines)
+}
+
+/// Returns the contents of the formula file for the deadlock analysis with `LoLA`.
+/// The formula is preceded by a comment that explains it in plain language.
+#[must_use]
+pub fn formula_file() -> String {
+    let lines = [
+        "Deadlock property checked by cargo-check-deadlock.".to_string(),
+        String::new(),
+        "EF: There is an execution of the program that eventually reaches a state where"
+            .to_string(),
+        "DEADLOCK: no transition can fire, i.e., no thread can make progress,".to_string(),
+        format!("{PROGRAM_END} = 0: the program did not terminate normally and"),
+        format!("{PROGRAM_PANIC} = 0: the program did not terminate with a panic."),
+        String::new(),
+        "If the property holds, the program can deadlock.".to_string(),
+    ];
+    formula_with_comment(&lines, DEADLOCK_FORMULA)
+}
+
+/// Returns the companion formula files with canned properties for `LoLA`,
+/// as tuples of the name of the property and the contents of the file:
+///
+/// - `deadlock`: The deadlock formula, like `formula_file`.
+/// - `program_end_reachable`: The program can terminate normally.
+/// - `program_panic_unreachable`: The program never panics.
+/// - `condvar_<index>_lost_signal`: A notification of the condition variable can be lost,
+///   for every condition variable in the net.
+///
+/// Every formula is preceded by a comment that explains it and the expected answer.
+#[must_use]
+pub fn companion_formula_files(translation: &Translation) -> Vec<(String, String)> {
+    let mut files = vec![
+        ("deadlock".to_string(), formula_file()),
+        (
+            "program_end_reachable".to_string(),
+            formula_with_comment(
+                &[
+                    "Reachability of the normal end of the program.".to_string(),
+                    String::new(),
+                    format!(
+                        "EF {PROGRAM_END} > 0: There is an execution that terminates normally."
+                    ),
+                    String::new(),
+                    "If the property does not hold, the program never terminates normally."
+                        .to_string(),
+                ],
+                &format!("EF ({PROGRAM_END} > 0)"),
+            ),
+        ),
+        (
+            "program_panic_unreachable".to_string(),
+            formula_with_comment(
+                &[
+                    "Unreachability of a panic.".to_string(),
+                    String::new(),
+                    format!("AG {PROGRAM_PANIC} = 0: No execution of the program panics."),
+                    String::new(),
+                    "If the property does not hold, the program can panic.".to_string(),
+                ],
+                &format!("AG ({PROGRAM_PANIC} = 0)"),
+            ),
+        ),
+    ];
+
+    let net = translation.net.data();
+    let identifiers = translation.net.lola_identifiers();
+    for object in &translation.registry {
+        if object.kind != SyncKind::Condvar {
+            continue;
+        }
+        let Some(index) = object
+            .label
+            .rsplit('_')
+            .next()
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        let (_, lost_signal, _) = condvar::transition_labels(index);
+        if !net
+            .transitions
+            .iter()
+            .any(|transition| transition.label == lost_signal)
+        {
+            continue;
+        }
+        files.push((
+            format!("condvar_{index}_lost_signal"),
+            formula_with_comment(
+                &[
+                    format!("Lost signal of {} created at {}.", object.label, object.span),
+                    String::new(),
+                    format!("EF FIREABLE({lost_signal}): A notification can be sent while no thread waits."),
+                    String::new(),
+                    "If the property holds, the signal can be lost and a later wait may block forever."
+                        .to_string(),
+                ],
+                &format!("EF FIREABLE({})", identifiers.identifier(&lost_signal)),
+            ),
+        ));
+    }
+    files
+}
+
+/// Returns the formula preceded by a comment with the given lines.
+fn formula_with_comment(lines: &[String], formula: &str) -> String {
+    format!("{}{formula}\n", comment(lines))
+}
+
+/// Encloses the lines in a comment in `LoLA` format.
+/// Curly braces in the lines are replaced with parentheses since they would end the comment.
+fn comment(lines: &[String]) -> String {
+    let mut output = String::from("{\n");
+    for line in lines {
+        let line = line.replace('{', "(").replace('}', ")");
+        if line.is_empty() {
+            output.push('\n');
+        } else {
+            writeln!(output, "  {line}").expect("BUG: Writing to a string should not fail");
+        }
+    }
+    output.push_str("}\n");
+    output
+}
+
+#[cfg(test)]
+mod lola_comments_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::PetriNet;
+    use crate::data_structures::span_info::SpanInfo;
+    use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+
+    #[test]
+    fn lola_header_maps_places_to_synchronization_primitives() {
+        let mut net = PetriNet::new();
+        net.add_place("MUTEX_0");
+        net.add_place("MUTEX_0_CONDITION_SET");
+        net.add_place("MUTEX_10");
+        net.add_place("main_BB1");
+        let mut registry = SyncRegistry::new();
+        let span = SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: 2,
+            start_column: 16,
+            end_line: 2,
+            end_column: 37,
+        };
+        registry.add(SyncKind::Mutex, "MUTEX_0", span);
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+
+        let header = lola_header(&translation);
+
+        assert!(header.starts_with("{\n"));
+        assert!(header.ends_with("}\n"));
+        assert!(header.contains("  MUTEX_0: mutex created at main.rs:2:16"));
+        assert!(header.contains("    places: MUTEX_0, MUTEX_0_CONDITION_SET\n"));
+    }
+
+    #[test]
+    fn lola_header_replaces_curly_braces() {
+        let mut registry = SyncRegistry::new();
+        let span = SpanInfo {
+            filename: "{main}.rs".to_string(),
+            ..Default::default()
+        };
+        registry.add(SyncKind::Condvar, "CONDVAR_0", span);
+        let translation = Translation {
+            registry,
+            ..Default::default()
+        };
+
+        let header = lola_header(&translation);
+
+        assert!(header.contains("(main).rs"));
+        assert_eq!(header.matches('{').count(), 1);
+        assert_eq!(header.matches('}').count(), 1);
+    }
+
+    #[test]
+    fn formula_file_ends_with_deadlock_formula() {
+        let formula_file = formula_file();
+
+        assert!(formula_file.starts_with("{\n"));
+        assert!(formula_file.ends_with(&format!("}}\n{DEADLOCK_FORMULA}\n")));
+    }
+
+    #[test]
+    fn companion_formula_files_check_lost_signals_of_every_condvar() {
+        let mut net = PetriNet::new();
+        net.add_transition("CONDVAR_0_LOST_SIGNAL");
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Condvar, "CONDVAR_0", SpanInfo::default());
+        registry.add(SyncKind::Condvar, "CONDVAR_1", SpanInfo::default());
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+
+        let files = companion_formula_files(&translation);
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            [
+                "deadlock",
+                "program_end_reachable",
+                "program_panic_unreachable",
+                "condvar_0_lost_signal"
+            ]
+        );
+        assert!(files[1].1.ends_with("}\nEF (PROGRAM_END > 0)\n"));
+        assert!(files[2].1.ends_with("}\nAG (PROGRAM_PANIC = 0)\n"));
+        assert!(files[3]
+            .1
+            .ends_with("}\nEF FIREABLE(CONDVAR_0_LOST_SIGNAL)\n"));
+    }
+}
@@ -0,0 +1,99 @@
+//! Submodule that writes the net in the folder layout of the Model Checking Contest (MCC).
+//! See <https://mcc.lip6.fr/>.
+//!
+//! The tools that take part in the contest read a model from a folder with the following files:
+//!
+//! - `model.pnml`: The net in PNML format.
+//! - `iscolored`: `FALSE`, since the net is a place/transition net.
+//! - `ReachabilityDeadlock.xml`: The deadlock formula checked with `LoLA`, see `crate::model_checker::lola`.
+//!   Unlike a plain `<deadlock/>`, it excludes the markings where the program ended.
+//! - `ReachabilityCardinality.xml` and `CTLCardinality.xml`: The property templates of the
+//!   synchronization primitives, see `crate::report::property_templates`.
+//!
+//! The places in the formulas are referenced by their identifiers in `model.pnml`.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::data_structures::identifiers::IdentifierFormat;
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC};
+use crate::report::property_templates::{mcc_properties, mcc_property_set, MccCategory};
+use crate::Translation;
+
+/// Returns the property set with the deadlock formula in the XML syntax of the MCC,
+/// i.e., `EF (deadlock AND PROGRAM_END <= 0 AND PROGRAM_PANIC <= 0)`.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn deadlock_properties(translation: &Translation) -> String {
+    let identifiers = translation.net.identifiers(IdentifierFormat::Pnml);
+    let mut formula = String::from(
+        "      <exists-path><finally>\n        <conjunction>\n          <deadlock/>\n",
+    );
+    for place in [PROGRAM_END, PROGRAM_PANIC] {
+        writeln!(
+            formula,
+            "          <integer-le><tokens-count><place>{}</place></tokens-count><integer-constant>0</integer-constant></integer-le>",
+            identifiers.identifier(place)
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    formula.push_str("        </conjunction>\n      </finally></exists-path>\n");
+    mcc_property_set([(
+        "DEADLOCK",
+        "A deadlock is reachable in which the program has neither ended nor panicked.",
+        formula,
+    )])
+}
+
+/// Writes the net and its properties to the given folder in the layout of the MCC.
+/// The folder is created if it does not exist.
+///
+/// # Errors
+///
+/// If the folder or one of the files cannot be created, then an error is returned.
+pub fn write_mcc_bundle(translation: &Translation, folder: &Path) -> Result<(), std::io::Error> {
+    std::fs::create_dir_all(folder)?;
+    let mut model = std::fs::File::create(folder.join("model.pnml"))?;
+    translation.net.to_pnml(&mut model)?;
+    std::fs::write(folder.join("iscolored"), "FALSE\n")?;
+    std::fs::write(
+        folder.join("ReachabilityDeadlock.xml"),
+        deadlock_properties(translation),
+    )?;
+    std::fs::write(
+        folder.join("ReachabilityCardinality.xml"),
+        mcc_properties(translation, MccCategory::ReachabilityCardinality),
+    )?;
+    std::fs::write(
+        folder.join("CTLCardinality.xml"),
+        mcc_properties(translation, MccCategory::CtlCardinality),
+    )
+}
+
+#[cfg(test)]
+mod mcc_bundle_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::PetriNet;
+
+    #[test]
+    fn deadlock_properties_exclude_the_end_of_the_program() {
+        let mut net = PetriNet::new();
+        net.add_place(PROGRAM_END);
+        net.add_place(PROGRAM_PANIC);
+        let translation = Translation {
+            net,
+            ..Default::default()
+        };
+
+        let properties = deadlock_properties(&translation);
+
+        assert!(properties.contains("    <id>DEADLOCK</id>\n"));
+        assert!(properties.contains(
+            "      <exists-path><finally>\n        <conjunction>\n          <deadlock/>\n          <integer-le><tokens-count><place>PROGRAM_END</place></tokens-count>"
+        ));
+        assert!(properties.ends_with("</property-set>\n"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Submodule that compares the nets of two versions of a program, e.g. before and after a patch.
+//!
+//! The transitions are grouped by the function whose basic blocks they leave,
+//! i.e. a transition belongs to a function if it consumes a token from the place of one of its basic blocks.
+//! The transitions that do not leave a basic block, e.g. the start of a thread, are grouped separately.
+//!
+//! Every transition is described by its label and the labels of the places it consumes from and produces in.
+//! Therefore, a transition connected to other places in the new version, e.g. locking another mutex,
+//! is listed both as removed and as added. Only the functions with differences are listed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::naming::basic_block::function_of_place_label;
+
+/// The name of the group of the transitions that do not leave a basic block.
+const OTHER_TRANSITIONS: &str = "(outside of functions)";
+
+/// The number of places, transitions and arcs of a net.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetSize {
+    pub places: usize,
+    pub transitions: usize,
+    pub arcs: usize,
+}
+
+impl NetSize {
+    /// Counts the places, transitions and arcs of the net.
+    #[must_use]
+    pub fn of(net: &NetData) -> Self {
+        Self {
+            places: net.places.len(),
+            transitions: net.transitions.len(),
+            arcs: net.arcs.len(),
+        }
+    }
+}
+
+impl std::fmt::Display for NetSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} places, {} transitions, {} arcs",
+            self.places, self.transitions, self.arcs
+        )
+    }
+}
+
+/// The transitions of a function that differ between the two versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionDiff {
+    /// The sanitized name of the function, as in the labels of its basic blocks.
+    pub function: String,
+    /// The descriptions of the transitions only present in the old version, sorted.
+    pub removed: Vec<String>,
+    /// The descriptions of the transitions only present in the new version, sorted.
+    pub added: Vec<String>,
+}
+
+/// The differences between the nets of two versions of a program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetDiff {
+    /// The size of the net of the old version.
+    pub old_size: NetSize,
+    /// The size of the net of the new version.
+    pub new_size: NetSize,
+    /// The functions with differences, sorted by name.
+    pub functions: Vec<FunctionDiff>,
+}
+
+impl NetDiff {
+    /// Compares the transitions of the two nets function by function.
+    #[must_use]
+    pub fn new(old: &NetData, new: &NetData) -> Self {
+        let old_transitions = transitions_by_function(old);
+        let new_transitions = transitions_by_function(new);
+        let empty = BTreeSet::new();
+
+        let names: BTreeSet<&String> = old_transitions
+            .keys()
+            .chain(new_transitions.keys())
+            .collect();
+        let functions = names
+            .into_iter()
+            .filter_map(|function| {
+                let old = old_transitions.get(function).unwrap_or(&empty);
+                let new = new_transitions.get(function).unwrap_or(&empty);
+                let removed: Vec<String> = old.difference(new).cloned().collect();
+                let added: Vec<String> = new.difference(old).cloned().collect();
+                if removed.is_empty() && added.is_empty() {
+                    return None;
+                }
+                Some(FunctionDiff {
+                    function: function.clone(),
+                    removed,
+                    added,
+                })
+            })
+            .collect();
+
+        Self {
+            old_size: NetSize::of(old),
+            new_size: NetSize::of(new),
+            functions,
+        }
+    }
+
+    /// Checks whether the transitions of both nets are the same.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+impl std::fmt::Display for NetDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Net of the old version: {}", self.old_size)?;
+        write!(f, "Net of the new version: {}", self.new_size)?;
+        if self.is_empty() {
+            return write!(f, "\nThe transitions of every function are the same");
+        }
+        for function in &self.functions {
+            write!(f, "\nFunction `{}`:", function.function)?;
+            for transition in &function.removed {
+                write!(f, "\n  - {transition}")?;
+            }
+            for transition in &function.added {
+                write!(f, "\n  + {transition}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the descriptions of the transitions of the net grouped by the function whose basic blocks they leave.
+fn transitions_by_function(net: &NetData) -> BTreeMap<String, BTreeSet<String>> {
+    let mut inputs: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut outputs: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for arc in &net.arcs {
+        let places = match arc.direction {
+            ArcDirection::PlaceToTransition => inputs.entry(&arc.transition).or_default(),
+            ArcDirection::TransitionToPlace => outputs.entry(&arc.transition).or_default(),
+        };
+        // A weighted arc is listed as many times as its weight.
+        places.extend(std::iter::repeat_n(arc.place.as_str(), arc.weight));
+    }
+
+    let mut grouped: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for transition in &net.transitions {
+        let label = transition.label.as_str();
+        let mut consumed = inputs.remove(label).unwrap_or_default();
+        let mut produced = outputs.remove(label).unwrap_or_default();
+        let function = consumed
+            .iter()
+            .find_map(|place| function_of_place_label(place))
+            .unwrap_or(OTHER_TRANSITIONS);
+        consumed.sort_unstable();
+        produced.sort_unstable();
+        grouped
+            .entry(function.to_string())
+            .or_default()
+            .insert(format!(
+                "{label}: {{{}}} -> {{{}}}",
+                consumed.join(", "),
+                produced.join(", ")
+            ));
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod net_diff_tests {
+    use super::*;
+    use crate::data_structures::net_data::{ArcData, PlaceData, TransitionData};
+
+    /// A net where `main` locks the given mutex between two basic blocks.
+    fn net(mutex: &str) -> NetData {
+        let place = |label: &str| PlaceData {
+            label: label.to_string(),
+            marking: 0,
+            capacity: None,
+            span: None,
+        };
+        let arc = |place: &str, transition: &str, direction| ArcData {
+            place: place.to_string(),
+            transition: transition.to_string(),
+            direction,
+            weight: 1,
+        };
+        NetData {
+            places: vec![
+                place("PROGRAM_START"),
+                place("main_BB0"),
+                place("main_BB1"),
+                place(mutex),
+            ],
+            transitions: vec![
+                TransitionData {
+                    label: "main_START".to_string(),
+                    span: None,
+                },
+                TransitionData {
+                    label: "std_sync_Mutex_T_lock_0_CALL".to_string(),
+                    span: None,
+                },
+            ],
+            arcs: vec![
+                arc(
+                    "PROGRAM_START",
+                    "main_START",
+                    ArcDirection::PlaceToTransition,
+                ),
+                arc("main_BB0", "main_START", ArcDirection::TransitionToPlace),
+                arc(
+                    "main_BB0",
+                    "std_sync_Mutex_T_lock_0_CALL",
+                    ArcDirection::PlaceToTransition,
+                ),
+                arc(
+                    mutex,
+                    "std_sync_Mutex_T_lock_0_CALL",
+                    ArcDirection::PlaceToTransition,
+                ),
+                arc(
+                    "main_BB1",
+                    "std_sync_Mutex_T_lock_0_CALL",
+                    ArcDirection::TransitionToPlace,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn same_nets_have_no_differences() {
+        let diff = NetDiff::new(&net("MUTEX_0"), &net("MUTEX_0"));
+
+        assert!(diff.is_empty());
+        assert!(diff
+            .to_string()
+            .ends_with("The transitions of every function are the same"));
+    }
+
+    #[test]
+    fn transition_connected_to_another_place_is_removed_and_added() {
+        let diff = NetDiff::new(&net("MUTEX_0"), &net("MUTEX_1"));
+
+        assert_eq!(
+            diff.functions,
+            [FunctionDiff {
+                function: "main".to_string(),
+                removed: vec![
+                    "std_sync_Mutex_T_lock_0_CALL: {MUTEX_0, main_BB0} -> {main_BB1}".to_string()
+                ],
+                added: vec![
+                    "std_sync_Mutex_T_lock_0_CALL: {MUTEX_1, main_BB0} -> {main_BB1}".to_string()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn function_of_place_label_strips_the_basic_block() {
+        assert_eq!(function_of_place_label("main_BB12"), Some("main"));
+        assert_eq!(
+            function_of_place_label("main_closure_0_BB1"),
+            Some("main_closure_0")
+        );
+        assert_eq!(function_of_place_label("MUTEX_0"), None);
+        assert_eq!(function_of_place_label("main_BB_END"), None);
+        assert_eq!(
+            function_of_place_label("main_BLOCK_1_STATEMENT_0_END"),
+            Some("main")
+        );
+    }
+}
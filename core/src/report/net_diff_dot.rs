@@ -0,0 +1,196 @@
+//! Submodule that draws the differences between two nets as a DOT graph, e.g. before and after a patch.
+//!
+//! The graph contains every place, transition and arc of both nets, matched by their labels:
+//!
+//! - The elements only present in the new net are drawn in green.
+//! - The elements only present in the old net are drawn in red.
+//! - The elements present in both nets are dimmed in gray, so they give context without drawing attention.
+//!
+//! Unlike `crate::report::net_diff`, which describes every transition with the places it is connected to,
+//! an arc that moved to another place is drawn as one removed arc and one added arc of the same transition.
+//! The nets are usually loaded from their JSON export, see `NetData::from_json`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::data_structures::identifiers::{escape_dot, IdentifierFormat};
+use crate::data_structures::net_data::{ArcDirection, NetData};
+
+/// Whether an element of the nets is present in the old net, the new net or both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Presence {
+    Removed,
+    Added,
+    Unchanged,
+}
+
+impl Presence {
+    /// Returns the presence of an element given whether it is in the old and in the new net.
+    const fn of(in_old: bool, in_new: bool) -> Self {
+        match (in_old, in_new) {
+            (true, false) => Self::Removed,
+            (false, true) => Self::Added,
+            _ => Self::Unchanged,
+        }
+    }
+
+    /// Returns the DOT attributes that color the element.
+    const fn attributes(self) -> &'static str {
+        match self {
+            Self::Removed => "color=\"red\" fontcolor=\"red\" penwidth=2",
+            Self::Added => "color=\"green3\" fontcolor=\"green3\" penwidth=2",
+            Self::Unchanged => "color=\"gray70\" fontcolor=\"gray70\"",
+        }
+    }
+}
+
+/// Returns the DOT graph of the union of both nets, colored by the presence of each element.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn net_diff_dot(old: &NetData, new: &NetData) -> String {
+    // The markings are taken from the new net, or from the old one for the removed places.
+    let mut places: BTreeMap<&str, ((bool, bool), usize)> = BTreeMap::new();
+    let mut transitions: BTreeMap<&str, (bool, bool)> = BTreeMap::new();
+    let mut arcs: BTreeMap<(&str, &str, bool), (bool, bool)> = BTreeMap::new();
+    for (net, is_new) in [(old, false), (new, true)] {
+        for place in &net.places {
+            let entry = places.entry(&place.label).or_default();
+            record(&mut entry.0, is_new);
+            entry.1 = place.marking;
+        }
+        for transition in &net.transitions {
+            record(transitions.entry(&transition.label).or_default(), is_new);
+        }
+        for arc in &net.arcs {
+            let is_input = arc.direction == ArcDirection::PlaceToTransition;
+            let key = (arc.place.as_str(), arc.transition.as_str(), is_input);
+            record(arcs.entry(key).or_default(), is_new);
+        }
+    }
+
+    let identifier = |label: &str| IdentifierFormat::Dot.legalize(label);
+    let mut dot = String::from("digraph petrinet {\n");
+    for (label, ((in_old, in_new), marking)) in &places {
+        writeln!(
+            dot,
+            "    {} [shape=\"circle\" xlabel=\"{}\" label=\"{}\" {}];",
+            identifier(label),
+            escape_dot(label),
+            "•".repeat(*marking),
+            Presence::of(*in_old, *in_new).attributes()
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    for (label, (in_old, in_new)) in &transitions {
+        writeln!(
+            dot,
+            "    {} [shape=\"box\" xlabel=\"\" label=\"{}\" {}];",
+            identifier(label),
+            escape_dot(label),
+            Presence::of(*in_old, *in_new).attributes()
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    for ((place, transition, is_input), (in_old, in_new)) in &arcs {
+        let (source, target) = if *is_input {
+            (place, transition)
+        } else {
+            (transition, place)
+        };
+        writeln!(
+            dot,
+            "    {} -> {} [{}];",
+            identifier(source),
+            identifier(target),
+            Presence::of(*in_old, *in_new).attributes()
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Records that an element is present in the old or in the new net.
+fn record(presence: &mut (bool, bool), is_new: bool) {
+    if is_new {
+        presence.1 = true;
+    } else {
+        presence.0 = true;
+    }
+}
+
+#[cfg(test)]
+mod net_diff_dot_tests {
+    use super::*;
+    use crate::data_structures::net_data::{ArcData, PlaceData, TransitionData};
+
+    /// A net where `main` locks the given mutex.
+    fn net(mutex: &str) -> NetData {
+        NetData {
+            places: vec![
+                PlaceData {
+                    label: "main_BB0".to_string(),
+                    marking: 1,
+                    capacity: None,
+                    span: None,
+                },
+                PlaceData {
+                    label: mutex.to_string(),
+                    marking: 1,
+                    capacity: None,
+                    span: None,
+                },
+            ],
+            transitions: vec![TransitionData {
+                label: "main_LOCK_0".to_string(),
+                span: None,
+            }],
+            arcs: vec![
+                ArcData {
+                    place: "main_BB0".to_string(),
+                    transition: "main_LOCK_0".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+                ArcData {
+                    place: mutex.to_string(),
+                    transition: "main_LOCK_0".to_string(),
+                    direction: ArcDirection::PlaceToTransition,
+                    weight: 1,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn net_diff_dot_colors_the_elements_by_presence() {
+        let dot = net_diff_dot(&net("MUTEX_0"), &net("MUTEX_1"));
+
+        assert_eq!(
+            dot,
+            "\
+digraph petrinet {
+    MUTEX_0 [shape=\"circle\" xlabel=\"MUTEX_0\" label=\"•\" color=\"red\" fontcolor=\"red\" penwidth=2];
+    MUTEX_1 [shape=\"circle\" xlabel=\"MUTEX_1\" label=\"•\" color=\"green3\" fontcolor=\"green3\" penwidth=2];
+    main_BB0 [shape=\"circle\" xlabel=\"main_BB0\" label=\"•\" color=\"gray70\" fontcolor=\"gray70\"];
+    main_LOCK_0 [shape=\"box\" xlabel=\"\" label=\"main_LOCK_0\" color=\"gray70\" fontcolor=\"gray70\"];
+    MUTEX_0 -> main_LOCK_0 [color=\"red\" fontcolor=\"red\" penwidth=2];
+    MUTEX_1 -> main_LOCK_0 [color=\"green3\" fontcolor=\"green3\" penwidth=2];
+    main_BB0 -> main_LOCK_0 [color=\"gray70\" fontcolor=\"gray70\"];
+}
+"
+        );
+    }
+
+    #[test]
+    fn net_diff_dot_quotes_illegal_identifiers() {
+        let dot = net_diff_dot(&NetData::default(), &net("new_display'_0"));
+
+        assert!(dot.contains(
+            "    \"new_display'_0\" [shape=\"circle\" xlabel=\"new_display'_0\" label=\"•\""
+        ));
+    }
+}
@@ -0,0 +1,411 @@
+//! Submodule that writes the net in PNML with the control flow of every thread in a nested page.
+//!
+//! PNML supports splitting a net into pages, which editors like PIPE or Renew show as separate views.
+//! <https://www.pnml.org/version-2009/version-2009.php>
+//!
+//! The control flow of every spawned thread is traversed in the net, starting from `THREAD_<index>_START`.
+//! The transitions and the control flow places reached are moved to a page `THREAD_<index>_PAGE`
+//! nested in the main page. The rest of the net stays in the main page: the control flow of the main thread,
+//! the synchronization primitives shared by the threads and the places of the threads themselves,
+//! e.g. `THREAD_0_START` and `THREAD_0_END`.
+//!
+//! An arc is placed in the page of its transition. PNML only allows arcs between nodes in the same page,
+//! so a place in another page is replaced with a reference place, e.g. `THREAD_0_PAGE_MUTEX_0` for `MUTEX_0`.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use crate::data_structures::identifiers::IdentifierFormat;
+use crate::data_structures::net_data::ArcDirection;
+use crate::naming::thread::is_start_place_label;
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC, PROGRAM_START};
+use crate::report::findings::is_control_place;
+use crate::Translation;
+
+/// A nested page of the net with the labels of the nodes that it contains.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page {
+    /// The identifier of the page, e.g. `THREAD_0_PAGE`.
+    pub id: String,
+    /// The name of the page shown by the editors, e.g. `THREAD_0`.
+    pub name: String,
+    /// The labels of the places and transitions in the page.
+    pub nodes: HashSet<String>,
+}
+
+/// Returns one page for every spawned thread with the transitions and the control flow places of the thread.
+/// The pages are sorted by the order of the start places of the threads in the net.
+///
+/// A node reachable from several threads is assigned to the first of them.
+/// The places shared by every thread, e.g. `PROGRAM_END`, are never assigned to a thread.
+#[must_use]
+pub fn thread_pages(translation: &Translation) -> Vec<Page> {
+    let net = translation.net.data();
+    let mut consumers: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut outputs: HashMap<&str, Vec<&str>> = HashMap::new();
+    for arc in &net.arcs {
+        match arc.direction {
+            ArcDirection::PlaceToTransition => consumers
+                .entry(arc.place.as_str())
+                .or_default()
+                .push(arc.transition.as_str()),
+            ArcDirection::TransitionToPlace => outputs
+                .entry(arc.transition.as_str())
+                .or_default()
+                .push(arc.place.as_str()),
+        }
+    }
+
+    let mut assigned: HashSet<&str> = HashSet::from([PROGRAM_START, PROGRAM_END, PROGRAM_PANIC]);
+    let mut pages = Vec::new();
+    for start_place in net
+        .places
+        .iter()
+        .map(|place| place.label.as_str())
+        .filter(|label| is_start_place_label(label))
+    {
+        let thread = start_place.trim_end_matches("_START");
+        let mut nodes: HashSet<String> = HashSet::new();
+        let mut queue = VecDeque::from([start_place]);
+        while let Some(place) = queue.pop_front() {
+            for transition in consumers.get(place).into_iter().flatten() {
+                if !assigned.insert(transition) {
+                    continue;
+                }
+                nodes.insert((*transition).to_string());
+                for output in outputs.get(transition).into_iter().flatten() {
+                    if is_control_place(output, translation) && assigned.insert(output) {
+                        nodes.insert((*output).to_string());
+                        queue.push_back(output);
+                    }
+                }
+            }
+        }
+        pages.push(Page {
+            id: format!("{thread}_PAGE"),
+            name: thread.to_string(),
+            nodes,
+        });
+    }
+    pages
+}
+
+/// Writes the net of the translation in PNML with a nested page for every spawned thread.
+///
+/// # Errors
+///
+/// If the net cannot be written in PNML format, then an error is returned.
+///
+/// # Panics
+///
+/// If the PNML output of `netcrab` is not valid UTF-8, then the function panics. This should never happen.
+pub fn pnml_with_thread_pages(translation: &Translation) -> Result<String, std::io::Error> {
+    let mut pnml = Vec::new();
+    translation.net.to_pnml(&mut pnml)?;
+    let pnml =
+        String::from_utf8(pnml).expect("BUG: The PNML output of `netcrab` should be valid UTF-8");
+    // The nodes are identified by their identifiers in PNML, which may differ from their labels.
+    let identifiers = translation.net.identifiers(IdentifierFormat::Pnml);
+    let pages: Vec<Page> = thread_pages(translation)
+        .into_iter()
+        .map(|page| Page {
+            nodes: page
+                .nodes
+                .iter()
+                .map(|label| identifiers.identifier(label).to_string())
+                .collect(),
+            ..page
+        })
+        .collect();
+    Ok(split_into_pages(&pnml, &pages))
+}
+
+/// An element of the main page of the PNML output, i.e. a place, a transition or an arc.
+struct Element<'a> {
+    /// The lines of the element, including the start and end tags.
+    lines: Vec<&'a str>,
+    /// The identifier of the place or transition, or the source of the arc.
+    source: &'a str,
+    /// The target of the arc. `None` for places and transitions.
+    target: Option<&'a str>,
+    /// Whether the element is a transition.
+    is_transition: bool,
+}
+
+/// Reads the places, transitions and arcs of the main page until its end tag.
+/// Returns the elements and the remaining lines, starting with the end tag of the main page.
+fn read_elements<'a>(lines: &mut std::str::Lines<'a>) -> (Vec<Element<'a>>, Vec<&'a str>) {
+    let mut elements: Vec<Element> = Vec::new();
+    let mut end_tag: Option<String> = None;
+    for line in lines.by_ref() {
+        let trimmed = line.trim_start();
+        if let Some(tag) = &end_tag {
+            if trimmed == tag {
+                end_tag = None;
+            }
+            if let Some(element) = elements.last_mut() {
+                element.lines.push(line);
+            }
+            continue;
+        }
+        let Some(tag) = ["place", "transition", "arc"]
+            .into_iter()
+            .find(|tag| trimmed.starts_with(&format!("<{tag} ")))
+        else {
+            let mut remaining_lines = vec![line];
+            remaining_lines.extend(lines.by_ref());
+            return (elements, remaining_lines);
+        };
+        if !trimmed.ends_with("/>") {
+            end_tag = Some(format!("</{tag}>"));
+        }
+        let (source, target) = if tag == "arc" {
+            (attribute(line, "source"), attribute(line, "target"))
+        } else {
+            (attribute(line, "id"), None)
+        };
+        elements.push(Element {
+            lines: vec![line],
+            source: source.unwrap_or_default(),
+            target,
+            is_transition: tag == "transition",
+        });
+    }
+    (elements, Vec::new())
+}
+
+/// Returns the value of the attribute `name` in the XML element of the line, if any.
+fn attribute<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(&format!(" {name}=\""))? + name.len() + 3;
+    let length = line[start..].find('"')?;
+    Some(&line[start..start + length])
+}
+
+/// Moves the nodes of the pages from the main page of a net in standard PNML format to nested pages.
+/// The arcs are moved to the page of their transition, see the module documentation.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn split_into_pages(pnml: &str, pages: &[Page]) -> String {
+    let mut output = String::with_capacity(pnml.len());
+    let mut lines = pnml.lines();
+    let mut main_page = "page0";
+    for line in lines.by_ref() {
+        output.push_str(line);
+        output.push('\n');
+        if line.trim_start().starts_with("<page ") {
+            main_page = attribute(line, "id").unwrap_or(main_page);
+            break;
+        }
+    }
+
+    let (elements, closing_lines) = read_elements(&mut lines);
+    let indent = elements.first().map_or("", |element| {
+        let line = element.lines[0];
+        &line[..line.len() - line.trim_start().len()]
+    });
+
+    // The page of a node is the index of the thread page that contains it, `None` for the main page.
+    let page_of = |node: &str| pages.iter().position(|page| page.nodes.contains(node));
+    let transitions: HashSet<&str> = elements
+        .iter()
+        .filter(|element| element.is_transition)
+        .map(|element| element.source)
+        .collect();
+    // The page of every element and, for the arcs to a place in another page, the place to reference.
+    let placed: Vec<(Option<usize>, Option<&str>)> = elements
+        .iter()
+        .map(|element| {
+            let Some(target) = element.target else {
+                return (page_of(element.source), None);
+            };
+            let (transition, place) = if transitions.contains(element.source) {
+                (element.source, target)
+            } else {
+                (target, element.source)
+            };
+            let page = page_of(transition);
+            (page, (page_of(place) != page).then_some(place))
+        })
+        .collect();
+
+    let page_ids: Vec<&str> = std::iter::once(main_page)
+        .chain(pages.iter().map(|page| page.id.as_str()))
+        .collect();
+    for page_index in std::iter::once(None).chain((0..pages.len()).map(Some)) {
+        let page_id = page_ids[page_index.map_or(0, |index| index + 1)];
+        let extra_indent = if page_index.is_some() { "  " } else { "" };
+        if let Some(index) = page_index {
+            writeln!(
+                output,
+                "{indent}<page id=\"{page_id}\">\n{indent}  <name>\n{indent}    <text>{}</text>\n{indent}  </name>",
+                pages[index].name
+            )
+            .expect("BUG: Writing to a string should not fail");
+        }
+        let mut references: Vec<&str> = Vec::new();
+        for (_, reference) in placed.iter().filter(|(page, _)| *page == page_index) {
+            if let Some(place) = reference.filter(|place| !references.contains(place)) {
+                references.push(place);
+                writeln!(
+                    output,
+                    "{indent}{extra_indent}<referencePlace id=\"{page_id}_{place}\" ref=\"{place}\"/>"
+                )
+                .expect("BUG: Writing to a string should not fail");
+            }
+        }
+        for (element, (_, reference)) in elements
+            .iter()
+            .zip(&placed)
+            .filter(|(_, (page, _))| *page == page_index)
+        {
+            for (line_index, line) in element.lines.iter().enumerate() {
+                let line = match reference {
+                    Some(place) if line_index == 0 => line
+                        .replacen(
+                            &format!("source=\"{place}\""),
+                            &format!("source=\"{page_id}_{place}\""),
+                            1,
+                        )
+                        .replacen(
+                            &format!("target=\"{place}\""),
+                            &format!("target=\"{page_id}_{place}\""),
+                            1,
+                        ),
+                    _ => (*line).to_string(),
+                };
+                writeln!(output, "{extra_indent}{line}")
+                    .expect("BUG: Writing to a string should not fail");
+            }
+        }
+        if page_index.is_some() {
+            writeln!(output, "{indent}</page>").expect("BUG: Writing to a string should not fail");
+        }
+    }
+    for line in closing_lines {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod pnml_pages_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::{
+        add_arc_place_transition, add_arc_transition_place, connect_places, PetriNet,
+    };
+    use crate::data_structures::span_info::SpanInfo;
+    use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+
+    const PNML: &str = "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">
+  <net id=\"net0\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">
+    <page id=\"page0\">
+      <place id=\"MUTEX_0\">
+      </place>
+      <place id=\"THREAD_0_BB1\">
+      </place>
+      <transition id=\"THREAD_0_LOCK\">
+      </transition>
+      <arc source=\"MUTEX_0\" target=\"THREAD_0_LOCK\" id=\"(MUTEX_0, THREAD_0_LOCK)\">
+      </arc>
+      <arc source=\"THREAD_0_LOCK\" target=\"THREAD_0_BB1\" id=\"(THREAD_0_LOCK, THREAD_0_BB1)\">
+      </arc>
+    </page>
+  </net>
+</pnml>
+";
+
+    fn thread_page() -> Page {
+        Page {
+            id: "THREAD_0_PAGE".to_string(),
+            name: "THREAD_0".to_string(),
+            nodes: HashSet::from(["THREAD_0_BB1".to_string(), "THREAD_0_LOCK".to_string()]),
+        }
+    }
+
+    #[test]
+    fn split_into_pages_without_pages_is_unchanged() {
+        assert_eq!(split_into_pages(PNML, &[]), PNML);
+    }
+
+    #[test]
+    fn split_into_pages_moves_the_nodes_to_a_nested_page() {
+        let pnml = split_into_pages(PNML, &[thread_page()]);
+
+        assert_eq!(
+            pnml,
+            "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<pnml xmlns=\"http://www.pnml.org/version-2009/grammar/pnml\">
+  <net id=\"net0\" type=\"http://www.pnml.org/version-2009/grammar/ptnet\">
+    <page id=\"page0\">
+      <place id=\"MUTEX_0\">
+      </place>
+      <page id=\"THREAD_0_PAGE\">
+        <name>
+          <text>THREAD_0</text>
+        </name>
+        <referencePlace id=\"THREAD_0_PAGE_MUTEX_0\" ref=\"MUTEX_0\"/>
+        <place id=\"THREAD_0_BB1\">
+        </place>
+        <transition id=\"THREAD_0_LOCK\">
+        </transition>
+        <arc source=\"THREAD_0_PAGE_MUTEX_0\" target=\"THREAD_0_LOCK\" id=\"(MUTEX_0, THREAD_0_LOCK)\">
+        </arc>
+        <arc source=\"THREAD_0_LOCK\" target=\"THREAD_0_BB1\" id=\"(THREAD_0_LOCK, THREAD_0_BB1)\">
+        </arc>
+      </page>
+    </page>
+  </net>
+</pnml>
+"
+        );
+    }
+
+    #[test]
+    fn thread_pages_contain_the_control_flow_of_the_thread() {
+        let mut net = PetriNet::new();
+        let program_start = net.add_place(PROGRAM_START);
+        let program_end = net.add_place(PROGRAM_END);
+        let thread_start = net.add_place("THREAD_0_START");
+        let thread_end = net.add_place("THREAD_0_END");
+        let mutex = net.add_place("MUTEX_0");
+        let locked = net.add_place("worker_BB1");
+        net.add_token(&program_start, 1).unwrap();
+
+        let spawn = connect_places(&mut net, &program_start, &program_end, "SPAWN");
+        add_arc_transition_place(&mut net, &spawn, &thread_start);
+        let lock = connect_places(&mut net, &thread_start, &locked, "worker_LOCK");
+        add_arc_place_transition(&mut net, &mutex, &lock);
+        let unlock = connect_places(&mut net, &locked, &thread_end, "worker_UNLOCK");
+        add_arc_transition_place(&mut net, &unlock, &mutex);
+
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.add(SyncKind::Thread, "THREAD_0", SpanInfo::default());
+        let translation = Translation {
+            net,
+            registry,
+            ..Default::default()
+        };
+
+        let pages = thread_pages(&translation);
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].id, "THREAD_0_PAGE");
+        assert_eq!(pages[0].name, "THREAD_0");
+        assert_eq!(
+            pages[0].nodes,
+            HashSet::from([
+                "worker_LOCK".to_string(),
+                "worker_BB1".to_string(),
+                "worker_UNLOCK".to_string(),
+            ])
+        );
+    }
+}
@@ -0,0 +1,632 @@
+//! Submodule that generates property templates for the synchronization primitives found in the program.
+//!
+//! Besides the deadlock formula, every primitive comes with a checklist of properties
+//! that are meaningful for its kind, e.g.:
+//!
+//! - A mutex is never held by two threads simultaneously and can always be released again.
+//! - A thread waiting on a condition variable is eventually notified.
+//! - A message sent to a channel is eventually received.
+//!
+//! The properties are expressed over the number of tokens in the places of the primitive
+//! and written in two syntaxes:
+//!
+//! - `LoLA`: One CTL formula per property, preceded by a comment that explains it.
+//!   `LoLA` checks one formula at a time, so copy the formula to a file or pass it with `--formula`.
+//! - SMV: One `CTLSPEC` or `LTLSPEC` per property, for SMV models of the net
+//!   in which every place is a variable holding its number of tokens.
+//!   The invariants are written as LTL, the rest as CTL.
+//! - The XML property sets of the Model Checking Contest (MCC), see <https://mcc.lip6.fr/>.
+//!   The reachability properties and the rest of the CTL properties go to separate files,
+//!   like the categories `ReachabilityCardinality` and `CTLCardinality` of the contest.
+//!
+//! The templates only refer to places that exist in the net,
+//! so the properties of a primitive whose model has other places are skipped.
+//!
+//! The properties annotated by the user in the source code are appended after the templates,
+//! see `crate::annotations`.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::annotations::{AnnotatedProperty, Reachability};
+use crate::data_structures::identifiers::{IdentifierFormat, IdentifierTable};
+use crate::data_structures::sync_registry::SyncKind;
+use crate::naming::{barrier, channel, condvar, once, thread};
+use crate::Translation;
+
+/// A comparison between the number of tokens in a place and a constant.
+#[derive(Clone, Copy)]
+enum Comparison {
+    Equal,
+    Greater,
+    LessOrEqual,
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Equal => write!(f, "="),
+            Self::Greater => write!(f, ">"),
+            Self::LessOrEqual => write!(f, "<="),
+        }
+    }
+}
+
+/// The syntaxes in which the formulas can be written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Lola,
+    Smv,
+}
+
+/// The categories of properties of the Model Checking Contest.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MccCategory {
+    /// Invariants and reachable states, i.e. `AG` or `EF` of a formula without temporal operators.
+    ReachabilityCardinality,
+    /// The other CTL formulas.
+    CtlCardinality,
+}
+
+/// A CTL formula over the number of tokens in the places of the net.
+enum Formula {
+    Tokens(String, Comparison, usize),
+    Or(Box<Self>, Box<Self>),
+    /// The formula holds in every reachable state.
+    Always(Box<Self>),
+    /// The formula eventually holds in every execution.
+    Eventually(Box<Self>),
+    /// The formula eventually holds in some execution.
+    Possibly(Box<Self>),
+}
+
+impl Formula {
+    fn tokens(place: &str, comparison: Comparison, value: usize) -> Self {
+        Self::Tokens(place.to_string(), comparison, value)
+    }
+
+    /// The number of tokens in the place always satisfies the comparison.
+    fn invariant(place: &str, comparison: Comparison, value: usize) -> Self {
+        Self::Always(Box::new(Self::tokens(place, comparison, value)))
+    }
+
+    /// Whenever the number of tokens in the place does not satisfy the comparison,
+    /// it eventually satisfies it again.
+    fn recurs(place: &str, comparison: Comparison, value: usize) -> Self {
+        Self::leads_to(
+            Self::tokens(place, comparison, value),
+            Self::tokens(place, comparison, value),
+        )
+    }
+
+    /// Whenever `condition` does not hold, `consequence` eventually holds,
+    /// i.e., `AG (condition OR AF consequence)`.
+    fn leads_to(condition: Self, consequence: Self) -> Self {
+        Self::Always(Box::new(Self::Or(
+            Box::new(condition),
+            Box::new(Self::Eventually(Box::new(consequence))),
+        )))
+    }
+
+    /// Checks whether the formula is an invariant, i.e., `AG` of a formula without temporal operators.
+    fn is_invariant(&self) -> bool {
+        match self {
+            Self::Always(formula) => formula.is_state_formula(),
+            _ => false,
+        }
+    }
+
+    /// Checks whether the formula belongs to the category `ReachabilityCardinality` of the MCC,
+    /// i.e., `AG` or `EF` of a formula without temporal operators.
+    fn is_reachability(&self) -> bool {
+        match self {
+            Self::Always(formula) | Self::Possibly(formula) => formula.is_state_formula(),
+            _ => false,
+        }
+    }
+
+    fn is_state_formula(&self) -> bool {
+        match self {
+            Self::Tokens(..) => true,
+            Self::Or(left, right) => left.is_state_formula() && right.is_state_formula(),
+            Self::Always(_) | Self::Eventually(_) | Self::Possibly(_) => false,
+        }
+    }
+
+    /// Returns the places the formula refers to.
+    fn places(&self) -> Vec<&str> {
+        match self {
+            Self::Tokens(place, _, _) => vec![place],
+            Self::Or(left, right) => {
+                let mut places = left.places();
+                places.extend(right.places());
+                places
+            }
+            Self::Always(formula) | Self::Eventually(formula) | Self::Possibly(formula) => {
+                formula.places()
+            }
+        }
+    }
+
+    /// Writes the formula in the given syntax.
+    /// Invariants are written as LTL formulas in SMV, so the path quantifier is omitted.
+    fn render(&self, syntax: Syntax, as_ltl: bool) -> String {
+        match self {
+            Self::Tokens(place, comparison, value) => format!("{place} {comparison} {value}"),
+            Self::Or(left, right) => {
+                let operator = if syntax == Syntax::Lola { "OR" } else { "|" };
+                format!(
+                    "({} {operator} {})",
+                    left.render(syntax, as_ltl),
+                    right.render(syntax, as_ltl)
+                )
+            }
+            Self::Always(formula) => {
+                let operator = if as_ltl { "G" } else { "AG" };
+                format!("{operator} ({})", formula.render(syntax, as_ltl))
+            }
+            Self::Eventually(formula) => {
+                let operator = if as_ltl { "F" } else { "AF" };
+                format!("{operator} ({})", formula.render(syntax, as_ltl))
+            }
+            Self::Possibly(formula) => format!("EF ({})", formula.render(syntax, as_ltl)),
+        }
+    }
+}
+
+impl Formula {
+    /// Writes the formula in the XML syntax of the MCC, one element per line starting at the given indentation.
+    /// The places are referenced by their identifiers in the PNML file.
+    /// The only comparison in the syntax is `<integer-le>`, so the others are expressed with it.
+    fn render_mcc(&self, identifiers: &IdentifierTable, indent: usize, output: &mut String) {
+        let pad = " ".repeat(indent);
+        let mut line = |text: &str| {
+            writeln!(output, "{pad}{text}").expect("BUG: Writing to a string should not fail");
+        };
+        match self {
+            Self::Tokens(place, comparison, value) => {
+                let tokens = format!(
+                    "<tokens-count><place>{}</place></tokens-count>",
+                    identifiers.identifier(place)
+                );
+                let constant = format!("<integer-constant>{value}</integer-constant>");
+                match comparison {
+                    Comparison::LessOrEqual => {
+                        line(&format!("<integer-le>{tokens}{constant}</integer-le>"));
+                    }
+                    Comparison::Greater => {
+                        line(&format!(
+                            "<negation><integer-le>{tokens}{constant}</integer-le></negation>"
+                        ));
+                    }
+                    Comparison::Equal => {
+                        line(&format!(
+                            "<conjunction><integer-le>{tokens}{constant}</integer-le><integer-le>{constant}{tokens}</integer-le></conjunction>"
+                        ));
+                    }
+                }
+            }
+            Self::Or(left, right) => {
+                line("<disjunction>");
+                left.render_mcc(identifiers, indent + 2, output);
+                right.render_mcc(identifiers, indent + 2, output);
+                writeln!(output, "{pad}</disjunction>")
+                    .expect("BUG: Writing to a string should not fail");
+            }
+            Self::Always(formula) | Self::Eventually(formula) | Self::Possibly(formula) => {
+                let (quantifier, operator) = match self {
+                    Self::Always(_) => ("all-paths", "globally"),
+                    Self::Eventually(_) => ("all-paths", "finally"),
+                    _ => ("exists-path", "finally"),
+                };
+                line(&format!("<{quantifier}><{operator}>"));
+                formula.render_mcc(identifiers, indent + 2, output);
+                writeln!(output, "{pad}</{operator}></{quantifier}>")
+                    .expect("BUG: Writing to a string should not fail");
+            }
+        }
+    }
+}
+
+/// Escapes the characters of a text that have a special meaning in XML.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Returns a property set in the XML syntax of the MCC with the given properties.
+///
+/// Each property is a tuple of the identifier, the description and the lines of the formula,
+/// which are expected to be indented by six spaces.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn mcc_property_set<'a>(
+    properties: impl IntoIterator<Item = (&'a str, &'a str, String)>,
+) -> String {
+    let mut output = String::new();
+    writeln!(output, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")
+        .expect("BUG: Writing to a string should not fail");
+    writeln!(output, "<property-set xmlns=\"http://mcc.lip6.fr/\">")
+        .expect("BUG: Writing to a string should not fail");
+    for (id, description, formula) in properties {
+        writeln!(
+            output,
+            "  <property>\n    <id>{}</id>\n    <description>{}</description>\n    <formula>",
+            escape_xml(id),
+            escape_xml(description)
+        )
+        .expect("BUG: Writing to a string should not fail");
+        output.push_str(&formula);
+        writeln!(output, "    </formula>\n  </property>")
+            .expect("BUG: Writing to a string should not fail");
+    }
+    writeln!(output, "</property-set>").expect("BUG: Writing to a string should not fail");
+    output
+}
+
+/// A property of a synchronization primitive.
+struct Property {
+    /// The identifier of the property, e.g. `MUTEX_0_EXCLUSIVE`.
+    name: String,
+    /// The explanation of the property in plain language.
+    description: String,
+    formula: Formula,
+}
+
+/// Returns the properties for a synchronization primitive of the given kind
+/// as tuples of the suffix of the name, the description and the formula.
+/// The capacity is the capacity of the place labelled like the primitive, if any.
+fn kind_properties(
+    kind: SyncKind,
+    label: &str,
+    capacity: Option<usize>,
+) -> Vec<(&'static str, String, Formula)> {
+    let index = label
+        .rsplit('_')
+        .next()
+        .and_then(|index| index.parse::<usize>().ok());
+    let mut properties = Vec::new();
+    let mut add = |suffix: &'static str, description: String, formula: Formula| {
+        properties.push((suffix, description, formula));
+    };
+
+    match (kind, index) {
+        (SyncKind::Mutex, _) => {
+            add(
+                "EXCLUSIVE",
+                format!("{label} is never held by two lock transitions simultaneously."),
+                Formula::invariant(label, Comparison::LessOrEqual, 1),
+            );
+            add(
+                "RELEASED",
+                format!("Whenever {label} is held, it is eventually released."),
+                Formula::leads_to(
+                    Formula::tokens(label, Comparison::Greater, 0),
+                    Formula::tokens(label, Comparison::Equal, 1),
+                ),
+            );
+        }
+        (SyncKind::RwLock, _) => {
+            let Some(capacity) = capacity else {
+                return properties;
+            };
+            add(
+                "RELEASED",
+                format!(
+                    "Whenever {label} is held by readers or a writer, it is eventually released."
+                ),
+                Formula::recurs(label, Comparison::Equal, capacity),
+            );
+        }
+        (SyncKind::Condvar, Some(index)) => {
+            let (wait_enabled, notify) = condvar::place_labels(index);
+            add(
+                "NOTIFIED",
+                format!("Every WAIT on {label} is eventually followed by a NOTIFY."),
+                Formula::recurs(&wait_enabled, Comparison::Greater, 0),
+            );
+            add(
+                "SINGLE_NOTIFY",
+                format!("At most one NOTIFY on {label} is pending at any time."),
+                Formula::invariant(&notify, Comparison::LessOrEqual, 1),
+            );
+        }
+        (SyncKind::Barrier, Some(index)) => {
+            let (arrived, _) = barrier::place_labels(index);
+            add(
+                "RELEASED",
+                format!("Every thread that arrives at {label} is eventually released."),
+                Formula::recurs(&arrived, Comparison::Equal, 0),
+            );
+        }
+        (SyncKind::Channel, Some(index)) => {
+            let buffer = channel::buffer_place_label(index);
+            add(
+                "RECEIVED",
+                format!("Every message sent to {label} is eventually received."),
+                Formula::recurs(&buffer, Comparison::Equal, 0),
+            );
+        }
+        (SyncKind::Once, Some(index)) => {
+            let (not_started, done) = once::place_labels(index);
+            add(
+                "COMPLETED",
+                format!("Once started, the initialization of {label} eventually completes."),
+                Formula::leads_to(
+                    Formula::tokens(&not_started, Comparison::Greater, 0),
+                    Formula::tokens(&done, Comparison::Greater, 0),
+                ),
+            );
+        }
+        (SyncKind::Thread, Some(index)) => {
+            let end = thread::end_place_label(index);
+            add(
+                "TERMINATES",
+                format!("{label} eventually terminates."),
+                Formula::Eventually(Box::new(Formula::tokens(&end, Comparison::Greater, 0))),
+            );
+        }
+        _ => {}
+    }
+    properties
+}
+
+/// Returns the property for a property annotated in the source code.
+fn annotated_property(property: &AnnotatedProperty) -> Property {
+    let reached = Formula::tokens(&property.marker, Comparison::Equal, 1);
+    let formula = match property.reachability {
+        Reachability::Eventually => Formula::Eventually(Box::new(reached)),
+        Reachability::Possibly => Formula::Possibly(Box::new(reached)),
+        Reachability::Never => Formula::invariant(&property.marker, Comparison::Equal, 0),
+    };
+    Property {
+        name: property.name.clone(),
+        description: format!(
+            "The code after the annotation at {} is {} reached.",
+            property.span, property.reachability
+        ),
+        formula,
+    }
+}
+
+/// Returns the properties for every synchronization primitive in the translation,
+/// followed by the properties annotated in the source code.
+fn properties(translation: &Translation) -> Vec<Property> {
+    let net = translation.net.data();
+    let capacities: HashMap<&str, Option<usize>> = net
+        .places
+        .iter()
+        .map(|place| (place.label.as_str(), place.capacity))
+        .collect();
+
+    let mut properties = Vec::new();
+    for object in &translation.registry {
+        let label = object.label.as_str();
+        let capacity = capacities.get(label).copied().flatten();
+        for (suffix, description, formula) in kind_properties(object.kind, label, capacity) {
+            properties.push(Property {
+                name: format!("{label}_{suffix}"),
+                description,
+                formula,
+            });
+        }
+    }
+
+    properties.extend(
+        translation
+            .annotated_properties
+            .iter()
+            .map(annotated_property),
+    );
+
+    properties.retain(|property| {
+        property
+            .formula
+            .places()
+            .iter()
+            .all(|place| capacities.contains_key(place))
+    });
+    properties
+}
+
+/// Returns the property templates in `LoLA` syntax.
+/// Every formula is preceded by a comment with its name and its explanation.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn lola_properties(translation: &Translation) -> String {
+    let mut output = String::new();
+    for property in properties(translation) {
+        writeln!(
+            output,
+            "{{ {}: {} }}\n{}\n",
+            property.name,
+            property.description,
+            property.formula.render(Syntax::Lola, false)
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    output
+}
+
+/// Returns the property templates in SMV syntax.
+/// Every specification is preceded by a comment with its explanation.
+///
+/// # Panics
+///
+/// If writing to the output string fails, then the function panics. This should never happen.
+#[must_use]
+pub fn smv_properties(translation: &Translation) -> String {
+    let mut output = String::new();
+    for property in properties(translation) {
+        let (keyword, as_ltl) = if property.formula.is_invariant() {
+            ("LTLSPEC", true)
+        } else {
+            ("CTLSPEC", false)
+        };
+        writeln!(
+            output,
+            "-- {}\n{keyword} NAME {} := {};\n",
+            property.description,
+            property.name,
+            property.formula.render(Syntax::Smv, as_ltl)
+        )
+        .expect("BUG: Writing to a string should not fail");
+    }
+    output
+}
+
+/// Returns the property templates of the given category as a property set in the XML syntax of the MCC.
+/// The places are referenced by their identifiers in the PNML file of the net.
+#[must_use]
+pub fn mcc_properties(translation: &Translation, category: MccCategory) -> String {
+    let identifiers = translation.net.identifiers(IdentifierFormat::Pnml);
+    let properties: Vec<Property> = properties(translation)
+        .into_iter()
+        .filter(|property| {
+            property.formula.is_reachability() == (category == MccCategory::ReachabilityCardinality)
+        })
+        .collect();
+    mcc_property_set(properties.iter().map(|property| {
+        let mut formula = String::new();
+        property.formula.render_mcc(&identifiers, 6, &mut formula);
+        (
+            property.name.as_str(),
+            property.description.as_str(),
+            formula,
+        )
+    }))
+}
+
+#[cfg(test)]
+mod property_templates_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::PetriNet;
+    use crate::data_structures::span_info::SpanInfo;
+    use crate::data_structures::sync_registry::SyncRegistry;
+
+    fn translation_with(places: &[&str], objects: &[(SyncKind, &str)]) -> Translation {
+        let mut net = PetriNet::new();
+        for place in places {
+            net.add_place(place);
+        }
+        let mut registry = SyncRegistry::new();
+        for (kind, label) in objects {
+            registry.add(*kind, label, SpanInfo::default());
+        }
+        Translation {
+            net,
+            registry,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mutex_properties_in_lola_syntax() {
+        let translation = translation_with(&["MUTEX_0"], &[(SyncKind::Mutex, "MUTEX_0")]);
+
+        let lola = lola_properties(&translation);
+
+        assert!(lola.contains(
+            "{ MUTEX_0_EXCLUSIVE: MUTEX_0 is never held by two lock transitions simultaneously. }\nAG (MUTEX_0 <= 1)\n"
+        ));
+        assert!(lola.contains("AG ((MUTEX_0 > 0 OR AF (MUTEX_0 = 1)))\n"));
+    }
+
+    #[test]
+    fn invariants_are_ltl_in_smv_syntax() {
+        let translation = translation_with(&["MUTEX_0"], &[(SyncKind::Mutex, "MUTEX_0")]);
+
+        let smv = smv_properties(&translation);
+
+        assert!(smv.contains("LTLSPEC NAME MUTEX_0_EXCLUSIVE := G (MUTEX_0 <= 1);\n"));
+        assert!(smv
+            .contains("CTLSPEC NAME MUTEX_0_RELEASED := AG ((MUTEX_0 > 0 | AF (MUTEX_0 = 1)));\n"));
+    }
+
+    #[test]
+    fn condvar_wait_is_followed_by_notify() {
+        let translation = translation_with(
+            &["CONDVAR_3_WAIT_ENABLED", "CONDVAR_3_NOTIFY"],
+            &[(SyncKind::Condvar, "CONDVAR_3")],
+        );
+
+        let lola = lola_properties(&translation);
+
+        assert!(lola.contains("Every WAIT on CONDVAR_3 is eventually followed by a NOTIFY."));
+        assert!(
+            lola.contains("AG ((CONDVAR_3_WAIT_ENABLED > 0 OR AF (CONDVAR_3_WAIT_ENABLED > 0)))\n")
+        );
+    }
+
+    #[test]
+    fn annotated_properties_follow_the_templates() {
+        let mut translation = translation_with(&["PROPERTY_READY_REACHED"], &[]);
+        translation.annotated_properties.push(AnnotatedProperty {
+            name: "READY".to_string(),
+            reachability: Reachability::Possibly,
+            marker: "PROPERTY_READY_REACHED".to_string(),
+            span: SpanInfo {
+                filename: "main.rs".to_string(),
+                start_line: 4,
+                start_column: 1,
+                end_line: 4,
+                end_column: 1,
+            },
+        });
+
+        let lola = lola_properties(&translation);
+        let smv = smv_properties(&translation);
+
+        assert_eq!(
+            lola,
+            "{ READY: The code after the annotation at main.rs:4:1 is possibly reached. }\nEF (PROPERTY_READY_REACHED = 1)\n\n"
+        );
+        assert!(smv.contains("CTLSPEC NAME READY := EF (PROPERTY_READY_REACHED = 1);\n"));
+    }
+
+    #[test]
+    fn mcc_properties_are_split_by_category() {
+        let translation = translation_with(&["MUTEX_0"], &[(SyncKind::Mutex, "MUTEX_0")]);
+
+        let reachability = mcc_properties(&translation, MccCategory::ReachabilityCardinality);
+        let ctl = mcc_properties(&translation, MccCategory::CtlCardinality);
+
+        assert_eq!(
+            reachability,
+            "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<property-set xmlns=\"http://mcc.lip6.fr/\">
+  <property>
+    <id>MUTEX_0_EXCLUSIVE</id>
+    <description>MUTEX_0 is never held by two lock transitions simultaneously.</description>
+    <formula>
+      <all-paths><globally>
+        <integer-le><tokens-count><place>MUTEX_0</place></tokens-count><integer-constant>1</integer-constant></integer-le>
+      </globally></all-paths>
+    </formula>
+  </property>
+</property-set>
+"
+        );
+        assert!(ctl.contains("<id>MUTEX_0_RELEASED</id>"));
+        assert!(ctl.contains(
+            "        <disjunction>\n          <negation><integer-le><tokens-count><place>MUTEX_0</place>"
+        ));
+        assert!(!ctl.contains("MUTEX_0_EXCLUSIVE"));
+    }
+
+    #[test]
+    fn properties_of_missing_places_are_skipped() {
+        let translation = translation_with(&[], &[(SyncKind::Channel, "CHANNEL_0")]);
+
+        assert_eq!(lola_properties(&translation), "");
+        assert_eq!(smv_properties(&translation), "");
+    }
+}
@@ -0,0 +1,336 @@
+//! Submodule that renders the findings of the analysis as annotated snippets of the source code,
+//! in the style of the diagnostics of `rustc`.
+//!
+//! The witness path of the finding is replayed to locate the code involved in it:
+//! - The blocked transitions are the primary labels, e.g. the second call to `lock` of a mutex.
+//! - For every synchronization place that a blocked transition waits for,
+//!   the last transition in the path that took a token from it is a secondary label,
+//!   e.g. the first call to `lock` of the same mutex.
+//!
+//! The output looks like this:
+//!
+//! ```text
+//! deadlock blocked at main.rs:4:15
+//!  --> main.rs:4:15
+//!   |
+//! 3 |     let _d1 = data.lock();
+//!   |               ----------- the mutex `MUTEX_0` is taken here
+//! 4 |     let _d2 = data.lock();
+//!   |               ^^^^^^^^^^^ blocked here, waiting for the mutex `MUTEX_0`
+//!   |
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::analysis::simulation::Simulation;
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::span_info::SpanInfo;
+use crate::report::findings::{is_control_place, sync_object_of, Finding};
+use crate::Translation;
+
+/// A label attached to a span of the source code in a snippet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnippetLabel {
+    /// The span of the source code that the label points to.
+    pub span: SpanInfo,
+    /// The message shown next to the span.
+    pub message: String,
+    /// Whether the span is the main location of the finding.
+    /// Primary spans are underlined with `^` and secondary spans with `-`.
+    pub primary: bool,
+}
+
+/// Returns the labels that locate the finding in the source code.
+///
+/// # Errors
+///
+/// If the net is not valid or a transition in the path of the finding cannot be fired,
+/// then an error is returned.
+pub fn finding_labels(
+    finding: &Finding,
+    translation: &Translation,
+    net: &NetData,
+) -> Result<Vec<SnippetLabel>, String> {
+    let mut simulation = Simulation::new(net)?;
+    for transition in &finding.path {
+        simulation.fire(transition)?;
+    }
+    let marking = simulation.marking();
+    let empty_sync_places: Vec<&str> = net
+        .places
+        .iter()
+        .zip(marking)
+        .filter(|(place, tokens)| **tokens == 0 && !is_control_place(&place.label, translation))
+        .map(|(place, _)| place.label.as_str())
+        .collect();
+
+    let mut labels: Vec<SnippetLabel> = Vec::new();
+    for blocked in &finding.blocked {
+        let Some(span) = transition_span(net, blocked) else {
+            continue;
+        };
+        let awaited: Vec<&str> = input_places(net, blocked)
+            .filter(|place| empty_sync_places.contains(place))
+            .collect();
+
+        let message = if awaited.is_empty() {
+            "blocked here".to_string()
+        } else {
+            let descriptions: Vec<String> = awaited
+                .iter()
+                .map(|place| describe_place(place, translation))
+                .collect();
+            format!("blocked here, waiting for {}", descriptions.join(" and "))
+        };
+        push_label(&mut labels, span.clone(), message, true);
+
+        for place in awaited {
+            let taken_by = finding.path.iter().rev().find(|transition| {
+                input_places(net, transition).any(|input| input == place)
+                    && transition_span(net, transition).is_some()
+            });
+            if let Some(span) = taken_by.and_then(|transition| transition_span(net, transition)) {
+                let message = format!("{} is taken here", describe_place(place, translation));
+                push_label(&mut labels, span.clone(), message, false);
+            }
+        }
+    }
+    Ok(labels)
+}
+
+/// Renders the labels as annotated snippets of the source code below the title.
+///
+/// The contents of the source files are looked up by the filename of the spans.
+/// Labels in files whose contents are not available are listed with their location only.
+#[must_use]
+pub fn render_snippet(
+    title: &str,
+    labels: &[SnippetLabel],
+    sources: &BTreeMap<String, String>,
+) -> String {
+    let mut files: BTreeMap<&str, Vec<&SnippetLabel>> = BTreeMap::new();
+    for label in labels {
+        files.entry(&label.span.filename).or_default().push(label);
+    }
+    let width = labels
+        .iter()
+        .map(|label| label.span.start_line.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let pad = " ".repeat(width);
+
+    let mut output = title.to_string();
+    for (index, (filename, mut labels)) in files.into_iter().enumerate() {
+        labels.sort_by_key(|label| (label.span.start_line, label.span.start_column));
+        let location = labels
+            .iter()
+            .find(|label| label.primary)
+            .unwrap_or(&labels[0])
+            .span
+            .clone();
+        let arrow = if index == 0 { "-->" } else { ":::" };
+        let _ = write!(output, "\n{pad}{arrow} {location}");
+
+        let Some(source_code) = sources.get(filename) else {
+            for label in labels {
+                let _ = write!(output, "\n{pad} = {}: {}", label.span, label.message);
+            }
+            continue;
+        };
+        let lines: Vec<&str> = source_code.lines().collect();
+
+        let _ = write!(output, "\n{pad} |");
+        let mut previous_line: Option<usize> = None;
+        for label in labels {
+            let line_number = label.span.start_line;
+            let Some(line) = line_number.checked_sub(1).and_then(|i| lines.get(i)) else {
+                let _ = write!(output, "\n{pad} = {}: {}", label.span, label.message);
+                continue;
+            };
+            if previous_line != Some(line_number) {
+                if previous_line.is_some_and(|previous| line_number > previous + 1) {
+                    output.push_str("\n...");
+                }
+                let _ = write!(output, "\n{line_number:>width$} | {line}");
+                previous_line = Some(line_number);
+            }
+            let (offset, length) = underline(&label.span, line);
+            let mark = if label.primary { "^" } else { "-" };
+            let _ = write!(
+                output,
+                "\n{pad} | {}{} {}",
+                " ".repeat(offset),
+                mark.repeat(length),
+                label.message
+            );
+        }
+        let _ = write!(output, "\n{pad} |");
+    }
+    output
+}
+
+/// Returns the offset and the length of the underline of the span in the line where it starts.
+/// Spans that continue in the next lines are underlined until the end of the line.
+fn underline(span: &SpanInfo, line: &str) -> (usize, usize) {
+    let offset = span.start_column.saturating_sub(1);
+    let end = if span.end_line == span.start_line && span.end_column > span.start_column {
+        span.end_column - 1
+    } else {
+        line.chars().count()
+    };
+    (offset, end.saturating_sub(offset).max(1))
+}
+
+/// Returns the span of the transition with the given label, if it originates from the source code.
+fn transition_span<'a>(net: &'a NetData, label: &str) -> Option<&'a SpanInfo> {
+    net.transitions
+        .iter()
+        .find(|transition| transition.label == label)
+        .and_then(|transition| transition.span.as_ref())
+}
+
+/// Returns the labels of the places from which the transition consumes.
+fn input_places<'a>(net: &'a NetData, transition: &'a str) -> impl Iterator<Item = &'a str> {
+    net.arcs
+        .iter()
+        .filter(move |arc| {
+            arc.direction == ArcDirection::PlaceToTransition && arc.transition == transition
+        })
+        .map(|arc| arc.place.as_str())
+}
+
+/// Describes the place with the synchronization primitive that it models, e.g. "the mutex `MUTEX_0`".
+/// Places that do not belong to a registered primitive are described with their label.
+pub(crate) fn describe_place(place: &str, translation: &Translation) -> String {
+    sync_object_of(place, translation).map_or_else(
+        || format!("`{place}`"),
+        |object| format!("the {} `{}`", object.kind, object.label),
+    )
+}
+
+/// Adds the label unless the same message is already attached to the span.
+fn push_label(labels: &mut Vec<SnippetLabel>, span: SpanInfo, message: String, primary: bool) {
+    if !labels
+        .iter()
+        .any(|label| label.span == span && label.message == message)
+    {
+        labels.push(SnippetLabel {
+            span,
+            message,
+            primary,
+        });
+    }
+}
+
+#[cfg(test)]
+mod snippets_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::{
+        add_arc_place_transition, connect_places, PetriNet,
+    };
+    use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+    use crate::report::findings::deadlock_finding;
+
+    const SOURCE_CODE: &str = "fn main() {\n    let data = std::sync::Mutex::new(0);\n    let _d1 = data.lock();\n    let _d2 = data.lock();\n}\n";
+
+    fn lock_span(line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: line,
+            start_column: 15,
+            end_line: line,
+            end_column: 26,
+        }
+    }
+
+    /// A thread that holds `MUTEX_0` since line 3 and waits at a second lock of it in line 4.
+    fn double_lock() -> Translation {
+        let mut net = PetriNet::new();
+        let start = net.add_place("START");
+        let locked = net.add_place("LOCKED");
+        let end = net.add_place("END");
+        let mutex = net.add_place("MUTEX_0");
+        net.add_token(&start, 1).unwrap();
+        net.add_token(&mutex, 1).unwrap();
+        net.set_current_span(Some(lock_span(3)));
+        let first_lock = connect_places(&mut net, &start, &locked, "LOCK_0");
+        net.set_current_span(Some(lock_span(4)));
+        let second_lock = connect_places(&mut net, &locked, &end, "LOCK_1");
+        net.set_current_span(None);
+        add_arc_place_transition(&mut net, &mutex, &first_lock);
+        add_arc_place_transition(&mut net, &mutex, &second_lock);
+
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        Translation {
+            net,
+            registry,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finding_labels_point_to_the_blocked_lock_and_the_lock_held() {
+        let translation = double_lock();
+        let net = translation.net.data();
+        let finding = deadlock_finding(vec!["LOCK_0".to_string()], &translation, &net).unwrap();
+
+        let labels = finding_labels(&finding, &translation, &net).unwrap();
+
+        assert_eq!(
+            labels,
+            [
+                SnippetLabel {
+                    span: lock_span(4),
+                    message: "blocked here, waiting for the mutex `MUTEX_0`".to_string(),
+                    primary: true,
+                },
+                SnippetLabel {
+                    span: lock_span(3),
+                    message: "the mutex `MUTEX_0` is taken here".to_string(),
+                    primary: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn render_snippet_underlines_the_spans() {
+        let translation = double_lock();
+        let net = translation.net.data();
+        let finding = deadlock_finding(vec!["LOCK_0".to_string()], &translation, &net).unwrap();
+        let labels = finding_labels(&finding, &translation, &net).unwrap();
+        let sources = BTreeMap::from([("main.rs".to_string(), SOURCE_CODE.to_string())]);
+
+        let snippet = render_snippet(&finding.to_string(), &labels, &sources);
+
+        assert_eq!(
+            snippet,
+            "deadlock blocked at main.rs:4:15
+ --> main.rs:4:15
+  |
+3 |     let _d1 = data.lock();
+  |               ----------- the mutex `MUTEX_0` is taken here
+4 |     let _d2 = data.lock();
+  |               ^^^^^^^^^^^ blocked here, waiting for the mutex `MUTEX_0`
+  |"
+        );
+    }
+
+    #[test]
+    fn render_snippet_lists_the_locations_if_the_source_is_missing() {
+        let labels = [SnippetLabel {
+            span: lock_span(4),
+            message: "blocked here".to_string(),
+            primary: true,
+        }];
+
+        let snippet = render_snippet("deadlock", &labels, &BTreeMap::new());
+
+        assert_eq!(
+            snippet,
+            "deadlock\n --> main.rs:4:15\n  = main.rs:4:15: blocked here"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Submodule that maps a witness path of the model checker back to the source code.
+//!
+//! A witness path is a sequence of transition labels fired from the initial marking, e.g. the path to a deadlock
+//! found by `LoLA`. Every step is resolved to the thread that fires it, the function whose control flow
+//! it belongs to and the span of the source code that produced the transition.
+//!
+//! The thread of a step is found by following the tokens of the control flow during the replay:
+//! `PROGRAM_START` belongs to the main thread and `THREAD_<index>_START` to the spawned thread.
+//! A transition belongs to the thread of the control place it consumes from,
+//! and the control places it produces in belong to the same thread.
+//!
+//! The output looks like this, with an empty line whenever the path switches to another thread:
+//!
+//! ```text
+//!   1. [main] main_CALL_0 in `main` at main.rs:3:15
+//!        3 |     let _d1 = data.lock();
+//!
+//!   2. [THREAD_0] thread_CALL_0 in `thread` at main.rs:8:19
+//!        8 |         let _d2 = data.lock();
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use crate::analysis::simulation::Simulation;
+use crate::data_structures::net_data::{ArcDirection, NetData};
+use crate::data_structures::span_info::SpanInfo;
+use crate::naming::basic_block::function_of_place_label;
+use crate::naming::thread::is_start_place_label;
+use crate::naming::PROGRAM_START;
+use crate::report::findings::is_control_place;
+use crate::Translation;
+
+/// The name used for the main thread in the trace.
+const MAIN_THREAD: &str = "main";
+
+/// A step of a witness path resolved to the source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceStep {
+    /// The label of the transition fired in this step.
+    pub transition: String,
+    /// The thread that fires the transition, `main` or the label of the thread, e.g. `THREAD_0`.
+    /// `None` if the transition does not consume from the control flow of any thread.
+    pub thread: Option<String>,
+    /// The sanitized name of the function whose control flow the transition belongs to, if known.
+    pub function: Option<String>,
+    /// The span of the source code that produced the transition, if any.
+    pub span: Option<SpanInfo>,
+}
+
+/// Replays the witness path on the net and resolves every step to the source code.
+///
+/// # Errors
+///
+/// If the net is not valid or a transition of the path cannot be fired, then an error is returned.
+pub fn source_steps(
+    path: &[String],
+    translation: &Translation,
+    net: &NetData,
+) -> Result<Vec<SourceStep>, String> {
+    let mut simulation = Simulation::new(net)?;
+    let mut threads: HashMap<&str, String> =
+        HashMap::from([(PROGRAM_START, MAIN_THREAD.to_string())]);
+    let mut steps = Vec::with_capacity(path.len());
+
+    for transition in path {
+        simulation.fire(transition)?;
+        let mut thread: Option<String> = None;
+        let mut function: Option<String> = None;
+        for arc in net.arcs.iter().filter(|arc| arc.transition == *transition) {
+            let place = arc.place.as_str();
+            if arc.direction != ArcDirection::PlaceToTransition {
+                continue;
+            }
+            if thread.is_none() {
+                thread = if is_start_place_label(place) {
+                    place.strip_suffix("_START").map(str::to_string)
+                } else {
+                    threads.get(place).cloned()
+                };
+            }
+            if function.is_none() {
+                function = function_of_place_label(place).map(str::to_string);
+            }
+        }
+        if let Some(thread) = &thread {
+            for arc in net.arcs.iter().filter(|arc| {
+                arc.transition == *transition
+                    && arc.direction == ArcDirection::TransitionToPlace
+                    && is_control_place(&arc.place, translation)
+            }) {
+                threads.insert(arc.place.as_str(), thread.clone());
+            }
+        }
+        let span = net
+            .transitions
+            .iter()
+            .find(|data| data.label == *transition)
+            .and_then(|data| data.span.clone());
+        steps.push(SourceStep {
+            transition: transition.clone(),
+            thread,
+            function,
+            span,
+        });
+    }
+    Ok(steps)
+}
+
+/// Renders the steps as a numbered interleaving of the threads, with the line of source code of every step.
+///
+/// The contents of the source files are looked up by the filename of the spans.
+/// Steps in files whose contents are not available are listed with their location only.
+#[must_use]
+pub fn render_source_trace(steps: &[SourceStep], sources: &BTreeMap<String, String>) -> String {
+    let mut output = String::new();
+    let mut previous_thread: Option<&Option<String>> = None;
+    for (index, step) in steps.iter().enumerate() {
+        if previous_thread.is_some_and(|previous| *previous != step.thread) {
+            output.push('\n');
+        }
+        previous_thread = Some(&step.thread);
+
+        let _ = write!(output, "  {}. ", index + 1);
+        if let Some(thread) = &step.thread {
+            let _ = write!(output, "[{thread}] ");
+        }
+        output.push_str(&step.transition);
+        if let Some(function) = &step.function {
+            let _ = write!(output, " in `{function}`");
+        }
+        if let Some(span) = &step.span {
+            let _ = write!(output, " at {span}");
+            // Lines in a `SpanInfo` are 1-based.
+            let line = sources.get(&span.filename).and_then(|source_code| {
+                span.start_line
+                    .checked_sub(1)
+                    .and_then(|i| source_code.lines().nth(i))
+            });
+            if let Some(line) = line {
+                let _ = write!(output, "\n       {} | {line}", span.start_line);
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod source_trace_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::{
+        add_arc_place_transition, add_arc_transition_place, connect_places, PetriNet,
+    };
+    use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+
+    fn span(line: usize) -> SpanInfo {
+        SpanInfo {
+            filename: "main.rs".to_string(),
+            start_line: line,
+            start_column: 15,
+            end_line: line,
+            end_column: 26,
+        }
+    }
+
+    /// The main thread spawns `THREAD_0` and both lock `MUTEX_0`.
+    fn spawn_and_lock() -> Translation {
+        let mut net = PetriNet::new();
+        let program_start = net.add_place(PROGRAM_START);
+        let main_bb0 = net.add_place("main_BB0");
+        let main_bb1 = net.add_place("main_BB1");
+        let thread_start = net.add_place("THREAD_0_START");
+        let thread_bb1 = net.add_place("thread_BB1");
+        let mutex = net.add_place("MUTEX_0");
+        net.add_token(&program_start, 1).unwrap();
+        net.add_token(&mutex, 1).unwrap();
+
+        net.set_current_span(Some(span(2)));
+        let spawn = connect_places(&mut net, &program_start, &main_bb0, "main_SPAWN_0");
+        add_arc_transition_place(&mut net, &spawn, &thread_start);
+        net.set_current_span(Some(span(3)));
+        let lock = connect_places(&mut net, &main_bb0, &main_bb1, "main_LOCK_0");
+        add_arc_place_transition(&mut net, &mutex, &lock);
+        net.set_current_span(Some(span(6)));
+        let lock = connect_places(&mut net, &thread_start, &thread_bb1, "thread_LOCK_0");
+        add_arc_place_transition(&mut net, &mutex, &lock);
+
+        let mut registry = SyncRegistry::new();
+        registry.add(SyncKind::Mutex, "MUTEX_0", SpanInfo::default());
+        registry.add(SyncKind::Thread, "THREAD_0", SpanInfo::default());
+        Translation {
+            net,
+            registry,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn source_steps_follow_the_threads() {
+        let translation = spawn_and_lock();
+        let net = translation.net.data();
+        let path = ["main_SPAWN_0", "thread_LOCK_0"].map(str::to_string);
+        let steps = source_steps(&path, &translation, &net).unwrap();
+
+        assert_eq!(steps[0].thread.as_deref(), Some("main"));
+        assert_eq!(steps[0].function, None);
+        assert_eq!(steps[1].thread.as_deref(), Some("THREAD_0"));
+        assert_eq!(steps[1].span, Some(span(6)));
+
+        let path = ["main_SPAWN_0", "main_LOCK_0"].map(str::to_string);
+        let steps = source_steps(&path, &translation, &net).unwrap();
+        assert_eq!(steps[1].thread.as_deref(), Some("main"));
+        assert_eq!(steps[1].function.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn source_steps_reject_a_transition_that_is_not_enabled() {
+        let translation = spawn_and_lock();
+        let net = translation.net.data();
+        let path = ["thread_LOCK_0".to_string()];
+
+        assert!(source_steps(&path, &translation, &net).is_err());
+    }
+
+    #[test]
+    fn render_source_trace_separates_the_threads() {
+        let translation = spawn_and_lock();
+        let net = translation.net.data();
+        let path = ["main_SPAWN_0", "thread_LOCK_0"].map(str::to_string);
+        let steps = source_steps(&path, &translation, &net).unwrap();
+        let sources = BTreeMap::from([(
+            "main.rs".to_string(),
+            "fn main() {\n    spawn();\n}\n\n\n    lock();\n".to_string(),
+        )]);
+
+        assert_eq!(
+            render_source_trace(&steps, &sources),
+            "  1. [main] main_SPAWN_0 at main.rs:2:15\n       2 |     spawn();\n\n  \
+             2. [THREAD_0] thread_LOCK_0 at main.rs:6:15\n       6 |     lock();\n"
+        );
+    }
+}
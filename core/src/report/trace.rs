@@ -0,0 +1,198 @@
+//! Submodule that exports the traces found by the analyses, so they can be replayed in other tools.
+//!
+//! A trace is a sequence of transitions fired from the initial marking, e.g. the path to a deadlock.
+//! It is replayed on the net to obtain the marking after every step and written in two formats:
+//!
+//! - The XML trace format used for the witnesses of the Model Checking Contest (MCC),
+//!   see <https://mcc.lip6.fr/>. The transitions and places are referenced by their identifiers
+//!   in the PNML file of the net, so the trace can be replayed on it.
+//! - A CSV file with one row per marking and one column per place, preceded by the step
+//!   and the transition fired to reach the marking. The first row is the initial marking.
+
+use std::io::{Error, Write};
+
+use crate::analysis::simulation::Simulation;
+use crate::analysis::Marking;
+use crate::data_structures::identifiers::IdentifierTable;
+use crate::data_structures::net_data::NetData;
+
+/// A sequence of transitions fired from the initial marking together with the markings reached.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MarkingTrace {
+    /// The labels of the places, in the order of the markings.
+    place_labels: Vec<String>,
+    /// The labels of the transitions fired, in order.
+    transitions: Vec<String>,
+    /// The initial marking followed by the marking after every transition.
+    markings: Vec<Marking>,
+}
+
+impl MarkingTrace {
+    /// Replays the path of transitions on the net starting at the initial marking.
+    ///
+    /// # Errors
+    ///
+    /// If the net is not valid or a transition of the path cannot be fired, then an error is returned.
+    pub fn replay(net: &NetData, path: &[String]) -> Result<Self, String> {
+        let mut simulation = Simulation::new(net)?;
+        let mut markings = vec![simulation.marking().to_vec()];
+        for transition in path {
+            simulation.fire(transition)?;
+            markings.push(simulation.marking().to_vec());
+        }
+        Ok(Self {
+            place_labels: net.places.iter().map(|place| place.label.clone()).collect(),
+            transitions: path.to_vec(),
+            markings,
+        })
+    }
+
+    /// Writes the trace in the XML format of the MCC witnesses to the given writer.
+    /// The identifiers must be the ones of the PNML file of the net.
+    /// The last marking is written after the transitions, listing only the places with tokens.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the trace has no markings, which is impossible, then the function panics.
+    pub fn write_mcc_witness<T: Write>(
+        &self,
+        identifiers: &IdentifierTable,
+        writer: &mut T,
+    ) -> Result<(), Error> {
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(writer, "<trace>")?;
+        for transition in &self.transitions {
+            writeln!(
+                writer,
+                "  <transition id=\"{}\"/>",
+                identifiers.identifier(transition)
+            )?;
+        }
+        writeln!(writer, "  <state>")?;
+        let last_marking = self
+            .markings
+            .last()
+            .expect("BUG: A trace should contain at least the initial marking");
+        for (place, tokens) in self.place_labels.iter().zip(last_marking) {
+            if *tokens > 0 {
+                writeln!(
+                    writer,
+                    "    <place id=\"{}\" tokens=\"{tokens}\"/>",
+                    identifiers.identifier(place)
+                )?;
+            }
+        }
+        writeln!(writer, "  </state>")?;
+        writeln!(writer, "</trace>")
+    }
+
+    /// Writes the markings of the trace as CSV to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the writer fails, then an error is returned.
+    pub fn write_csv<T: Write>(&self, writer: &mut T) -> Result<(), Error> {
+        write!(writer, "step,transition")?;
+        for place in &self.place_labels {
+            write!(writer, ",{}", csv_field(place))?;
+        }
+        writeln!(writer)?;
+        for (step, marking) in self.markings.iter().enumerate() {
+            let transition = step
+                .checked_sub(1)
+                .map_or(String::new(), |index| csv_field(&self.transitions[index]));
+            write!(writer, "{step},{transition}")?;
+            for tokens in marking {
+                write!(writer, ",{tokens}")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Quotes a field of a CSV file if it contains a comma, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod trace_tests {
+    use super::*;
+    use crate::data_structures::identifiers::IdentifierFormat;
+    use crate::data_structures::net_builder::NetBuilder;
+
+    /// A thread that locks a mutex in `BB0` and then blocks locking it again in `BB1`.
+    fn net() -> NetData {
+        NetBuilder::new()
+            .place("main_BB0", 1)
+            .place("main_BB1", 0)
+            .place("MUTEX_0", 1)
+            .transition("main_LOCK_0")
+            .input("main_BB0", "main_LOCK_0")
+            .input("MUTEX_0", "main_LOCK_0")
+            .output("main_LOCK_0", "main_BB1")
+            .build()
+    }
+
+    #[test]
+    fn replay_fails_for_a_transition_not_enabled() {
+        let path = vec!["main_LOCK_0".to_string(), "main_LOCK_0".to_string()];
+
+        assert!(MarkingTrace::replay(&net(), &path).is_err());
+    }
+
+    #[test]
+    fn write_csv_writes_every_marking() {
+        let trace = MarkingTrace::replay(&net(), &["main_LOCK_0".to_string()]).unwrap();
+        let mut output = Vec::new();
+        trace.write_csv(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "step,transition,main_BB0,main_BB1,MUTEX_0\n0,,1,0,1\n1,main_LOCK_0,0,1,0\n"
+        );
+    }
+
+    #[test]
+    fn write_mcc_witness_lists_transitions_and_last_marking() {
+        let net = net();
+        let trace = MarkingTrace::replay(&net, &["main_LOCK_0".to_string()]).unwrap();
+        let identifiers = IdentifierTable::new(
+            IdentifierFormat::Pnml,
+            net.places
+                .iter()
+                .map(|place| place.label.as_str())
+                .chain(std::iter::once("main_LOCK_0")),
+        );
+        let mut output = Vec::new();
+        trace.write_mcc_witness(&identifiers, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<trace>
+  <transition id=\"main_LOCK_0\"/>
+  <state>
+    <place id=\"main_BB1\" tokens=\"1\"/>
+  </state>
+</trace>
+"
+        );
+    }
+
+    #[test]
+    fn csv_field_quotes_special_characters() {
+        assert_eq!(csv_field("main_BB0"), "main_BB0");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,81 @@
+//! Module that exposes the analyses to JavaScript through `wasm-bindgen`.
+//!
+//! The nets are passed as JSON strings in the format produced by `cargo check-deadlock --json`.
+//! Only compiled with the `wasm` feature.
+
+use wasm_bindgen::prelude::*;
+
+use crate::analysis::reachability;
+use crate::analysis::simulation;
+use crate::data_structures::net_data::NetData;
+
+/// Searches the reachable markings of the net for a deadlock, visiting at most `max_states` markings.
+///
+/// Returns the result as a JSON object with a `result` field:
+/// `deadlock` (with the `path` to the deadlock), `capacity_exceeded` (with the `place` and the `path`),
+/// `deadlock_free` or `state_limit_reached`.
+///
+/// # Errors
+///
+/// If the JSON string is not a valid net, then an error is returned.
+#[wasm_bindgen]
+pub fn find_deadlock(net_json: &str, max_states: usize) -> Result<String, JsError> {
+    let net = NetData::from_json(net_json).map_err(|err| JsError::new(&err))?;
+    let result = reachability::find_deadlock(&net, max_states).map_err(|err| JsError::new(&err))?;
+    serde_json::to_string(&result).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// The token game on a net, fired one transition at a time.
+#[wasm_bindgen]
+pub struct Simulation(simulation::Simulation);
+
+#[wasm_bindgen]
+impl Simulation {
+    /// Creates a new simulation of the net given as a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON string is not a valid net, then an error is returned.
+    #[wasm_bindgen(constructor)]
+    pub fn new(net_json: &str) -> Result<Self, JsError> {
+        let net = NetData::from_json(net_json).map_err(|err| JsError::new(&err))?;
+        let simulation = simulation::Simulation::new(&net).map_err(|err| JsError::new(&err))?;
+        Ok(Self(simulation))
+    }
+
+    /// Returns the current marking, indexed like the places of the net.
+    #[must_use]
+    pub fn marking(&self) -> Vec<usize> {
+        self.0.marking().to_vec()
+    }
+
+    /// Returns the labels of the transitions that can fire in the current marking.
+    #[must_use]
+    pub fn enabled_transitions(&self) -> Vec<String> {
+        self.0
+            .enabled_transitions()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Fires the transition with the given label.
+    ///
+    /// # Errors
+    ///
+    /// If the transition does not exist or is not enabled, then an error is returned.
+    pub fn fire(&mut self, label: &str) -> Result<(), JsError> {
+        self.0.fire(label).map_err(|err| JsError::new(&err))
+    }
+
+    /// Checks whether the current marking is a deadlock.
+    #[must_use]
+    pub fn is_deadlock(&self) -> bool {
+        self.0.is_deadlock()
+    }
+
+    /// Goes back to the initial marking.
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+}
@@ -10,18 +10,18 @@ if command -v lola &>/dev/null; then
 fi
 
 echo "lola is not in the \$PATH."
-if [ ! -f ./assets/lola ]; then
-  echo "lola could not be found in ./assets"
+if [ ! -f ./cli/assets/lola ]; then
+  echo "lola could not be found in ./cli/assets"
   echo "Please make sure to run the script from the root folder of the project."
   exit 1
 fi
 
-echo "lola found in ./assets"
+echo "lola found in ./cli/assets"
 if [[ -z "$CARGO_HOME" ]]; then
   echo "\$CARGO_HOME is undefined. Installing lola in \$HOME/.cargo/bin ..."
-  cp ./assets/lola $HOME/.cargo/bin
+  cp ./cli/assets/lola $HOME/.cargo/bin
 else
   echo "\$CARGO_HOME is set. Installing lola in the bin subfolder..."
-  cp ./assets/lola $CARGO_HOME/bin
+  cp ./cli/assets/lola $CARGO_HOME/bin
 fi
 echo "lola is now installed"
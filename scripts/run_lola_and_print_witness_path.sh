@@ -10,10 +10,10 @@ if command -v lola &>/dev/null; then
 fi
 
 echo "lola could not be found in \$PATH. Looking for the version included in the repository..."
-if [ -f .assets/lola ]; then
-  echo "lola found in ./assets"
-  .assets/lola --formula="EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))" --path $1
+if [ -f ./cli/assets/lola ]; then
+  echo "lola found in ./cli/assets"
+  ./cli/assets/lola --formula="EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))" --path $1
 else
-  echo "lola could not be found in ./assets"
+  echo "lola could not be found in ./cli/assets"
   exit 1
 fi
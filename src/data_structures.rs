@@ -1,6 +0,0 @@
-//! Module that implements general data structures
-//! used for the translation. These do not depend on rustc internals.
-
-pub mod hash_map_counter;
-pub mod petri_net_interface;
-pub mod stack;
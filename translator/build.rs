@@ -0,0 +1,74 @@
+//! Build script that selects the shims for the nightly release of `rustc` used to compile the translator.
+//!
+//! The compiler internals change between nightly releases. The translator supports a range of releases
+//! by compiling the code for each API change conditionally. This script reads the commit date of the compiler
+//! from `rustc -vV` and enables a `cfg` flag for every API change that the compiler already includes.
+//!
+//! Differences that can be absorbed by the code itself, e.g. a new field in an enum variant
+//! matched with `..`, do not need a flag. For instance, the hash of a definition is read with
+//! `Fingerprint::split` and `Hash64::as_u64`, which are available in every supported release,
+//! instead of `Fingerprint::as_value`, which was removed.
+
+use std::process::Command;
+
+/// The changes to the compiler internals for which the translator contains a shim,
+/// together with the commit date of the first nightly release that includes them.
+/// The translator is compiled with `--cfg <name>` if the compiler is at least this recent.
+const API_CHANGES: [(&str, &str); 6] = [
+    // The arguments of `TerminatorKind::Call` are `Box<[Spanned<Operand>]>` instead of `Vec<Operand>`.
+    ("rustc_spanned_call_args", "2024-01-24"),
+    // The field `parse_sess_created` of `rustc_interface::Config` is renamed to `psess_created`.
+    ("rustc_psess_created", "2024-03-05"),
+    // The MIR contains the new terminator `TerminatorKind::TailCall`.
+    ("rustc_tail_call_terminator", "2024-07-08"),
+    // The function `Instance::resolve` is renamed to `Instance::try_resolve`.
+    ("rustc_instance_try_resolve", "2024-07-04"),
+    // The resolution of instances receives a `TypingEnv` instead of a `ParamEnv`.
+    ("rustc_typing_env", "2024-11-19"),
+    // The callback `after_analysis` of the driver receives the `TyCtxt` instead of the `Queries`.
+    ("rustc_after_analysis_tcx", "2024-11-27"),
+];
+
+/// The commit date of the oldest nightly release supported by the shims.
+const OLDEST_SUPPORTED_COMMIT_DATE: &str = "2024-01-01";
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    for (name, _) in API_CHANGES {
+        println!("cargo:rustc-check-cfg=cfg({name})");
+    }
+
+    let Some(commit_date) = rustc_commit_date() else {
+        // Assume the nightly release pinned in `rust-toolchain.toml`, which includes all the changes.
+        println!("cargo:warning=Could not determine the commit date of rustc, assuming a recent nightly release");
+        for (name, _) in API_CHANGES {
+            println!("cargo:rustc-cfg={name}");
+        }
+        return;
+    };
+
+    if commit_date.as_str() < OLDEST_SUPPORTED_COMMIT_DATE {
+        println!(
+            "cargo:warning=The nightly release of rustc from {commit_date} is older than the oldest supported release from {OLDEST_SUPPORTED_COMMIT_DATE}"
+        );
+    }
+    // The dates have the format `YYYY-MM-DD`, so they can be compared as strings.
+    for (name, date) in API_CHANGES {
+        if commit_date.as_str() >= date {
+            println!("cargo:rustc-cfg={name}");
+        }
+    }
+}
+
+/// Returns the commit date of the compiler, e.g. `2024-11-29`.
+/// Uses the compiler set by Cargo in the environment variable `RUSTC`.
+/// Returns `None` if the compiler cannot be run or its output does not contain the commit date.
+fn rustc_commit_date() -> Option<String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc).arg("-vV").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("commit-date: "))
+        .map(str::to_string)
+}
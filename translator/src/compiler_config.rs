@@ -0,0 +1,117 @@
+//! Submodule for handling the rustc config.
+//!
+//! The Config struct is documented here:
+//! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_interface/interface/struct.Config.html>
+//!
+//! It includes command-line options as well as internal rustc options.
+//! The relevant parts in this case are the `sysroot` and the input file.
+//! Additional command-line arguments, e.g. `--edition`, `--cfg` or `--extern`, are parsed by `rustc_driver`
+//! exactly like the arguments of `rustc`, so that every option of the compiler is supported.
+//!
+//! The fields of the Config struct change often between nightly releases.
+//! The differences between the supported releases are handled with the `cfg` flags set in `build.rs`.
+//!
+//! See the rustc driver examples for other possible example configurations:
+//! <https://rustc-dev-guide.rust-lang.org/rustc-driver.html>
+
+use rustc_errors::registry;
+use rustc_feature::UnstableFeatures;
+use rustc_session::config::{build_session_options, ErrorOutputType};
+use rustc_session::EarlyDiagCtxt;
+
+/// The options of the compiler parsed from the additional command-line arguments,
+/// together with the `--cfg` and `--check-cfg` flags, which are not part of the options.
+struct ParsedArgs {
+    opts: rustc_session::config::Options,
+    crate_cfg: Vec<String>,
+    crate_check_cfg: Vec<String>,
+}
+
+/// Prepares the config to compile the source code file with the given sysroot.
+/// If `is_library` is true, the file is compiled as a library, which does not need a `main` function.
+/// The additional arguments for the compiler, e.g. `--edition=2018`, are parsed like the arguments of `rustc`.
+///
+/// # Errors
+///
+/// If the additional arguments are not valid for `rustc`, then an error is returned.
+/// The compiler already reported the details.
+pub fn prepare_rustc_config(
+    sysroot: std::path::PathBuf,
+    source_code_filepath: std::path::PathBuf,
+    is_library: bool,
+    rustc_args: &[String],
+) -> Result<rustc_interface::Config, &'static str> {
+    let ParsedArgs {
+        opts,
+        crate_cfg,
+        crate_check_cfg,
+    } = parse_rustc_args(rustc_args)?;
+    let crate_types = if is_library {
+        vec![rustc_session::config::CrateType::Rlib]
+    } else {
+        opts.crate_types.clone()
+    };
+    Ok(rustc_interface::Config {
+        opts: rustc_session::config::Options {
+            maybe_sysroot: Some(sysroot),
+            crate_types,
+            ..opts
+        },
+        crate_cfg,
+        crate_check_cfg,
+        input: rustc_session::config::Input::File(source_code_filepath),
+        output_dir: None,
+        output_file: None,
+        hash_untracked_state: None,
+        ice_file: None,
+        file_loader: None,
+        locale_resources: Vec::new(),
+        lint_caps: rustc_hash::FxHashMap::default(),
+        #[cfg(rustc_psess_created)]
+        psess_created: None,
+        #[cfg(not(rustc_psess_created))]
+        parse_sess_created: None,
+        register_lints: None,
+        override_queries: None,
+        make_codegen_backend: None,
+        registry: registry::Registry::new(rustc_errors::codes::DIAGNOSTICS),
+        using_internal_features: std::sync::Arc::new(core::sync::atomic::AtomicBool::new(true)),
+        expanded_args: rustc_args.to_vec(),
+    })
+}
+
+/// Parses the additional command-line arguments like `rustc` does.
+/// Without additional arguments, the default options of the compiler are used,
+/// with the unstable features allowed like in `rustc`, e.g. `#![feature(exclusive_wrapper)]` on a nightly toolchain.
+///
+/// # Errors
+///
+/// If the arguments are not valid for `rustc`, or they only ask for information like `--help`,
+/// then an error is returned. The compiler already reported the details.
+fn parse_rustc_args(rustc_args: &[String]) -> Result<ParsedArgs, &'static str> {
+    if rustc_args.is_empty() {
+        return Ok(ParsedArgs {
+            opts: rustc_session::config::Options {
+                unstable_features: UnstableFeatures::from_environment(None),
+                ..rustc_session::config::Options::default()
+            },
+            crate_cfg: Vec::new(),
+            crate_check_cfg: Vec::new(),
+        });
+    }
+    // Invalid arguments are reported as fatal errors, which unwind instead of returning.
+    let parsed = rustc_driver::catch_fatal_errors(|| {
+        let mut early_dcx = EarlyDiagCtxt::new(ErrorOutputType::default());
+        // The arguments do not include the name of the program, like in `rustc_driver::run_compiler`.
+        let matches = rustc_driver::handle_options(&early_dcx, rustc_args)?;
+        Some(ParsedArgs {
+            opts: build_session_options(&mut early_dcx, &matches),
+            crate_cfg: matches.opt_strs("cfg"),
+            crate_check_cfg: matches.opt_strs("check-cfg"),
+        })
+    });
+    parsed
+        .ok()
+        .flatten()
+        .ok_or("Invalid arguments for the compiler")
+}
@@ -0,0 +1,501 @@
+//! Submodule that isolates the interaction with the compiler internals.
+//!
+//! The crates `rustc_middle`, `rustc_hir` and `rustc_span` are not stable and change between nightly releases.
+//! The rest of the translator should only use them through this submodule:
+//! - The MIR types used during the translation are re-exported here.
+//! - Functions and basic blocks are identified with the types `FunctionId` and `BlockId` defined here.
+//! - Spans are converted to `SpanInfo`, which does not depend on the compiler session.
+//! - The queries to the compiler are methods of `FunctionId` or free functions in this submodule.
+//!
+//! When a new nightly release breaks the build, the changes should be confined to this submodule.
+//! The differences between the supported nightly releases are handled with the `cfg` flags
+//! enabled by the build script of this crate, see `build.rs`.
+//! For an introduction to the query system see:
+//! <https://rustc-dev-guide.rust-lang.org/query.html>
+
+pub use rustc_middle::mir::visit::Visitor;
+pub use rustc_middle::mir::{
+    AggregateKind, BasicBlock, BasicBlockData, BinOp, Body, CastKind, Const, ConstValue, Local,
+    Location, NonDivergingIntrinsic, Operand, Place, ProjectionElem, Rvalue, Statement,
+    StatementKind, Terminator, TerminatorKind, UnOp, UnwindAction, RETURN_PLACE,
+};
+pub use rustc_middle::ty::{List, Ty, TyCtxt, TyKind};
+#[cfg(rustc_spanned_call_args)]
+pub use rustc_span::source_map::Spanned;
+pub use rustc_span::Span;
+
+use crate::data_structures::span_info::SpanInfo;
+
+/// The arguments of a function call in the `Call` MIR terminator.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/syntax/enum.TerminatorKind.html#variant.Call>
+#[cfg(rustc_spanned_call_args)]
+pub type CallArgs<'tcx> = [Spanned<Operand<'tcx>>];
+
+/// The arguments of a function call in the `Call` MIR terminator.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/syntax/enum.TerminatorKind.html#variant.Call>
+#[cfg(not(rustc_spanned_call_args))]
+pub type CallArgs<'tcx> = [Operand<'tcx>];
+
+/// The generic arguments of a function or closure, e.g. `[Wrapper]` in a call to `work::<Wrapper>`.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/type.GenericArgsRef.html>
+pub type GenericArgs<'tcx> = rustc_middle::ty::GenericArgsRef<'tcx>;
+
+/// Returns the operand of the given argument of a function call.
+#[cfg(rustc_spanned_call_args)]
+pub const fn argument_operand<'a, 'tcx>(argument: &'a Spanned<Operand<'tcx>>) -> &'a Operand<'tcx> {
+    &argument.node
+}
+
+/// Returns the operand of the given argument of a function call.
+#[cfg(not(rustc_spanned_call_args))]
+pub const fn argument_operand<'a, 'tcx>(argument: &'a Operand<'tcx>) -> &'a Operand<'tcx> {
+    argument
+}
+
+/// The ID that uniquely identifies a function, closure or other definition.
+/// It wraps the definition ID of the compiler.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_hir/def_id/struct.DefId.html>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionId(rustc_hir::def_id::DefId);
+
+impl From<rustc_hir::def_id::DefId> for FunctionId {
+    fn from(def_id: rustc_hir::def_id::DefId) -> Self {
+        Self(def_id)
+    }
+}
+
+impl FunctionId {
+    /// Returns the fully qualified name of the function, e.g. `std::sync::Mutex::<T>::lock`.
+    pub fn name(self, tcx: TyCtxt) -> String {
+        tcx.def_path_str(self.0)
+    }
+
+    /// Returns the optimized MIR body of the function.
+    /// The compiler computes it on-demand and caches the result.
+    pub fn body(self, tcx: TyCtxt) -> &Body {
+        tcx.optimized_mir(self.0)
+    }
+
+    /// Checks whether the function is a foreign item, i.e. linked via extern { ... }.
+    pub fn is_foreign_item(self, tcx: TyCtxt) -> bool {
+        tcx.is_foreign_item(self.0)
+    }
+
+    /// Checks whether the function is defined in the crate being translated.
+    pub fn is_local(self) -> bool {
+        self.0.is_local()
+    }
+
+    /// Checks whether the definition is a closure.
+    pub fn is_closure(self, tcx: TyCtxt) -> bool {
+        tcx.def_kind(self.0) == rustc_hir::def::DefKind::Closure
+    }
+
+    /// Returns the locals of the body of the function that receive every argument of a call with `argument_count` arguments.
+    /// The arguments are usually the locals after the return place: `_1`, `_2`, etc.
+    /// A closure is called with the `rust-call` ABI: The call passes the closure and a tuple with the arguments,
+    /// but the body receives the fields of the tuple as separate locals after the closure, unless it is spread.
+    /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/struct.Body.html#structfield.spread_arg>
+    pub fn argument_locals(self, argument_count: usize, tcx: TyCtxt) -> Vec<Vec<Local>> {
+        let mut locals: Vec<Vec<Local>> = (1..=argument_count)
+            .map(|index| vec![Local::from_usize(index)])
+            .collect();
+        let body = self.body(tcx);
+        if self.is_closure(tcx) && body.spread_arg.is_none() && argument_count == 2 {
+            locals[1] = (2..=body.arg_count).map(Local::from_usize).collect();
+        }
+        locals
+    }
+
+    /// Returns a key that identifies the function across compiler sessions.
+    /// It is the hash of the definition path, which also depends on the name and version of the crate.
+    /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_span/def_id/struct.DefPathHash.html>
+    pub fn stable_key(self, tcx: TyCtxt) -> String {
+        let (high, low) = tcx.def_path_hash(self.0).0.split();
+        format!("{:016x}{:016x}", high.as_u64(), low.as_u64())
+    }
+
+    /// Checks whether the MIR representation of the function is available.
+    pub fn is_mir_available(self, tcx: TyCtxt) -> bool {
+        tcx.is_mir_available(self.0)
+    }
+
+    /// Returns the span of the definition of the function.
+    /// Returns `None` if the function is not defined in the crate being translated.
+    pub fn span(self, tcx: TyCtxt) -> Option<SpanInfo> {
+        let span = tcx.hir().span_if_local(self.0)?;
+        Some(span_info(span, tcx))
+    }
+
+    /// Checks whether the basic block is part of a loop in the control flow graph of the function,
+    /// i.e. whether the block can be reached again from its successors.
+    pub fn is_block_in_loop(self, block: BlockId, tcx: TyCtxt) -> bool {
+        let basic_blocks = &self.body(tcx).basic_blocks;
+        let block = BasicBlock::from_usize(block.index());
+        let mut visited = std::collections::HashSet::new();
+        let mut stack: Vec<BasicBlock> = basic_blocks[block].terminator().successors().collect();
+        while let Some(current) = stack.pop() {
+            if current == block {
+                return true;
+            }
+            if visited.insert(current) {
+                stack.extend(basic_blocks[current].terminator().successors());
+            }
+        }
+        false
+    }
+
+    /// Checks whether the basic block is the header of a loop in the control flow graph of the function,
+    /// i.e. whether it dominates one of its predecessors, so it starts every iteration of the loop.
+    pub fn is_loop_header(self, block: BlockId, tcx: TyCtxt) -> bool {
+        let basic_blocks = &self.body(tcx).basic_blocks;
+        let block = BasicBlock::from_usize(block.index());
+        basic_blocks.predecessors()[block]
+            .iter()
+            .any(|predecessor| basic_blocks.dominators().dominates(block, *predecessor))
+    }
+
+    /// Returns the type and the span of the values that the future of the function keeps
+    /// across its suspension points, i.e. the values that live across an `.await`.
+    /// Returns an empty list if the function is not an `async` block or the body of an `async fn`.
+    /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/query/struct.CoroutineLayout.html>
+    pub fn values_held_across_await(self, tcx: TyCtxt) -> Vec<(String, SpanInfo)> {
+        if !tcx.is_coroutine(self.0) {
+            return Vec::new();
+        }
+        let Some(layout) = tcx.mir_coroutine_witnesses(self.0).as_ref() else {
+            return Vec::new();
+        };
+        layout
+            .field_tys
+            .iter()
+            .map(|saved| (saved.ty.to_string(), span_info(saved.source_info.span, tcx)))
+            .collect()
+    }
+
+    /// Returns the path of the module where the function is defined, e.g. `utils::math`.
+    /// Functions defined at the crate root return `crate`.
+    /// Returns `None` if the function is not defined in the crate being translated.
+    pub fn module_name(self, tcx: TyCtxt) -> Option<String> {
+        let local_def_id = self.0.as_local()?;
+        let module = tcx.parent_module_from_def_id(local_def_id);
+        let name = tcx.def_path_str(module.to_def_id());
+        if name.is_empty() {
+            Some("crate".to_string())
+        } else {
+            Some(name)
+        }
+    }
+}
+
+/// The index of a basic block in the MIR body of a function.
+/// <https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/struct.BasicBlock.html>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlockId(usize);
+
+impl From<BasicBlock> for BlockId {
+    fn from(block: BasicBlock) -> Self {
+        Self(block.index())
+    }
+}
+
+impl BlockId {
+    /// Returns the value of the index as a `usize`.
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Returns the name of the crate being compiled.
+pub fn crate_name(tcx: TyCtxt) -> String {
+    tcx.crate_name(rustc_hir::def_id::LOCAL_CRATE).to_string()
+}
+
+/// Returns the version of the compiler, e.g. `1.85.0-nightly (d4025ee45 2024-12-12)`.
+/// Returns `None` if the compiler was built without version information.
+pub fn compiler_version() -> Option<&'static str> {
+    rustc_interface::util::rustc_version_str()
+}
+
+/// Returns the entry function of the crate, i.e. the `main` function.
+/// Returns `None` if the crate does not have one.
+pub fn entry_function(tcx: TyCtxt) -> Option<FunctionId> {
+    let (def_id, _) = tcx.entry_fn(())?;
+    Some(FunctionId(def_id))
+}
+
+/// Returns the function defined in the crate with the given path, e.g. `my_mod::worker`.
+/// The path may start with `crate::` or the name of the crate. Methods are written as `Type::method`.
+/// Returns `None` if no function in the crate has this path.
+pub fn function_by_path(path: &str, tcx: TyCtxt) -> Option<FunctionId> {
+    let crate_prefix = format!("{}::", crate_name(tcx));
+    let path = path
+        .strip_prefix("crate::")
+        .or_else(|| path.strip_prefix(&crate_prefix))
+        .unwrap_or(path);
+    tcx.mir_keys(())
+        .iter()
+        .map(|local_def_id| local_def_id.to_def_id())
+        .filter(|def_id| is_function(*def_id, tcx))
+        .find(|def_id| tcx.def_path_str(*def_id) == path)
+        .map(FunctionId)
+}
+
+/// Returns the functions of the crate that are exported, i.e. reachable from other crates, sorted by name.
+/// Generic functions are skipped, since they cannot be translated without generic arguments.
+pub fn exported_functions(tcx: TyCtxt) -> Vec<FunctionId> {
+    let effective_visibilities = tcx.effective_visibilities(());
+    let mut functions: Vec<FunctionId> = tcx
+        .mir_keys(())
+        .iter()
+        .filter(|local_def_id| effective_visibilities.is_exported(**local_def_id))
+        .map(|local_def_id| local_def_id.to_def_id())
+        .filter(|def_id| {
+            is_function(*def_id, tcx) && !tcx.generics_of(*def_id).requires_monomorphization(tcx)
+        })
+        .map(FunctionId)
+        .collect();
+    functions.sort_by_cached_key(|function_id| function_id.name(tcx));
+    functions
+}
+
+/// Returns the functions, methods and closures of the crate whose MIR is available, sorted by name.
+/// This includes generic functions and the bodies of `async` blocks.
+pub fn local_functions(tcx: TyCtxt) -> Vec<FunctionId> {
+    let mut functions: Vec<FunctionId> = tcx
+        .mir_keys(())
+        .iter()
+        .map(|local_def_id| local_def_id.to_def_id())
+        .filter(|def_id| {
+            is_function(*def_id, tcx) || tcx.def_kind(*def_id) == rustc_hir::def::DefKind::Closure
+        })
+        .filter(|def_id| tcx.is_mir_available(*def_id))
+        .map(FunctionId)
+        .collect();
+    functions.sort_by_cached_key(|function_id| function_id.name(tcx));
+    functions
+}
+
+/// Checks whether the definition is a function or a method, as opposed to e.g. a closure or a constant.
+fn is_function(def_id: rustc_hir::def_id::DefId, tcx: TyCtxt) -> bool {
+    matches!(
+        tcx.def_kind(def_id),
+        rustc_hir::def::DefKind::Fn | rustc_hir::def::DefKind::AssocFn
+    )
+}
+
+/// A variable captured by a closure, described with the types that can be reached from it.
+pub struct Capture {
+    /// The captured variable as written in the source code, e.g. `data` or `state.data`.
+    pub name: String,
+    /// The fully qualified name of the type of the captured variable.
+    pub type_name: String,
+    /// The fully qualified names of the types reachable from the captured type
+    /// through the generic arguments and through the fields of the structs and enums defined in the crate.
+    /// The types defined outside the crate, e.g. `std::sync::Arc`, are not inspected.
+    pub reachable_types: Vec<String>,
+    /// Whether one of the reachable types is a raw pointer.
+    pub contains_raw_pointer: bool,
+}
+
+/// Returns the variables captured by the closure with the given ID.
+/// Returns an empty vector if the function is not a closure defined in the crate being translated.
+pub fn closure_captures(function_id: FunctionId, tcx: TyCtxt) -> Vec<Capture> {
+    let Some(local_def_id) = function_id.0.as_local() else {
+        return Vec::new();
+    };
+    if !function_id.is_closure(tcx) {
+        return Vec::new();
+    }
+    tcx.closure_captures(local_def_id)
+        .iter()
+        .map(|captured_place| {
+            let ty = captured_place.place.ty();
+            let mut reachable_types: Vec<String> = Vec::new();
+            let mut contains_raw_pointer = false;
+            let mut visited = std::collections::HashSet::new();
+            let mut stack = vec![ty];
+            while let Some(current) = stack.pop() {
+                if !visited.insert(current) {
+                    continue;
+                }
+                for inner in current.walk().filter_map(|arg| arg.as_type()) {
+                    contains_raw_pointer |= matches!(inner.kind(), TyKind::RawPtr(..));
+                    reachable_types.push(inner.to_string());
+                    if let TyKind::Adt(adt_def, args) = inner.kind() {
+                        if adt_def.did().is_local() {
+                            stack.extend(adt_def.all_fields().map(|field| field.ty(tcx, args)));
+                        }
+                    }
+                }
+            }
+            Capture {
+                name: captured_place.to_string(tcx),
+                type_name: ty.to_string(),
+                reachable_types,
+                contains_raw_pointer,
+            }
+        })
+        .collect()
+}
+
+/// Returns the places in the body of the closure that hold the variables captured by it,
+/// in the order of the captures, i.e. the order of the operands in the aggregate that creates the closure.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/closure/struct.CapturedPlace.html>
+///
+/// The closure receives itself in the local `_1`, by value (`FnOnce`) or by reference (`Fn` and `FnMut`).
+/// The captured variable with index `i` is then the place `_1.i` or `(*_1).i`, respectively.
+/// If it is captured by reference, or it is a reference itself, its dereference `(*_1.i)` is included too.
+/// In edition 2021, a closure may capture only a field of a variable, e.g. `state.data`.
+/// The field is then a capture on its own, with the same places as any other capture.
+/// <https://doc.rust-lang.org/edition-guide/rust-2021/disjoint-capture-in-closures.html>
+///
+/// Returns an empty vector if the function is not a closure.
+pub fn closure_capture_places(function_id: FunctionId, tcx: TyCtxt) -> Vec<Vec<Place>> {
+    if !function_id.is_closure(tcx) {
+        return Vec::new();
+    }
+    let closure_self = Local::from_usize(1);
+    let mut base_place = Place::from(closure_self);
+    let mut closure_ty = function_id.body(tcx).local_decls[closure_self].ty;
+    if let TyKind::Ref(_, inner_ty, _) = closure_ty.kind() {
+        base_place = tcx.mk_place_deref(base_place);
+        closure_ty = *inner_ty;
+    }
+    let TyKind::Closure(_, args) = closure_ty.kind() else {
+        return Vec::new();
+    };
+    args.as_closure()
+        .upvar_tys()
+        .iter()
+        .enumerate()
+        .map(|(index, upvar_ty)| {
+            let field = tcx.mk_place_field(base_place, index.into(), upvar_ty);
+            if matches!(upvar_ty.kind(), TyKind::Ref(..)) {
+                vec![field, tcx.mk_place_deref(field)]
+            } else {
+                vec![field]
+            }
+        })
+        .collect()
+}
+
+/// Resolves a call that depends on the generic parameters of the caller.
+/// The generic parameters in the arguments of the call are replaced with the generic arguments of the caller.
+/// A call to a trait method, e.g. `<T as Lockable>::lock` inside `fn work<T: Lockable>`,
+/// is then resolved to the method of the implementation for the concrete type, e.g. `<Wrapper as Lockable>::lock`.
+/// <https://rustc-dev-guide.rust-lang.org/mir/index.html#monomorphization>
+///
+/// Only implementations defined in the crate being translated replace the trait method.
+/// The calls to the traits of the standard library keep the name of the trait method, e.g. `std::ops::Deref::deref`,
+/// since the translation of the synchronization primitives relies on it.
+/// Returns the function and the generic arguments unchanged if the caller is not generic
+/// or the call cannot be resolved.
+pub fn resolve_call<'tcx>(
+    function_id: FunctionId,
+    call_args: GenericArgs<'tcx>,
+    caller_args: GenericArgs<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) -> (FunctionId, GenericArgs<'tcx>) {
+    use rustc_middle::ty::TypeVisitableExt;
+
+    if caller_args.is_empty() || !call_args.has_param() {
+        return (function_id, call_args);
+    }
+    let args = rustc_middle::ty::EarlyBinder::bind(call_args).instantiate(tcx, caller_args);
+    if tcx.trait_of_item(function_id.0).is_none() {
+        return (function_id, args);
+    }
+    match resolve_instance(function_id, args, tcx) {
+        Some(instance) if instance.def_id().is_local() && instance.def_id() != function_id.0 => {
+            (FunctionId(instance.def_id()), instance.args)
+        }
+        _ => (function_id, args),
+    }
+}
+
+/// Returns the instance called with the given generic arguments.
+/// Returns `None` if it cannot be resolved yet or the resolution fails.
+#[cfg(rustc_typing_env)]
+fn resolve_instance<'tcx>(
+    function_id: FunctionId,
+    args: GenericArgs<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) -> Option<rustc_middle::ty::Instance<'tcx>> {
+    let typing_env = rustc_middle::ty::TypingEnv::fully_monomorphized();
+    rustc_middle::ty::Instance::try_resolve(tcx, typing_env, function_id.0, args)
+        .ok()
+        .flatten()
+}
+
+/// Returns the instance called with the given generic arguments.
+/// Returns `None` if it cannot be resolved yet or the resolution fails.
+#[cfg(all(rustc_instance_try_resolve, not(rustc_typing_env)))]
+fn resolve_instance<'tcx>(
+    function_id: FunctionId,
+    args: GenericArgs<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) -> Option<rustc_middle::ty::Instance<'tcx>> {
+    let param_env = rustc_middle::ty::ParamEnv::reveal_all();
+    rustc_middle::ty::Instance::try_resolve(tcx, param_env, function_id.0, args)
+        .ok()
+        .flatten()
+}
+
+/// Returns the instance called with the given generic arguments.
+/// Returns `None` if it cannot be resolved yet or the resolution fails.
+#[cfg(not(rustc_instance_try_resolve))]
+fn resolve_instance<'tcx>(
+    function_id: FunctionId,
+    args: GenericArgs<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) -> Option<rustc_middle::ty::Instance<'tcx>> {
+    let param_env = rustc_middle::ty::ParamEnv::reveal_all();
+    rustc_middle::ty::Instance::resolve(tcx, param_env, function_id.0, args)
+        .ok()
+        .flatten()
+}
+
+/// Returns the name and the contents of the source files of the crate being compiled.
+/// Files whose source code is not available in the session are skipped.
+pub fn local_source_files(tcx: TyCtxt) -> Vec<(String, String)> {
+    tcx.sess
+        .source_map()
+        .files()
+        .iter()
+        .filter(|file| file.cnum == rustc_hir::def_id::LOCAL_CRATE)
+        .filter_map(|file| {
+            let source = file.src.as_ref()?;
+            Some((file.name.prefer_local().to_string(), source.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the source code of the span as written by the user, e.g. `data.lock()`.
+/// Returns `None` if the source code is not available in the session.
+pub fn span_snippet(span: Span, tcx: TyCtxt) -> Option<String> {
+    tcx.sess
+        .source_map()
+        .span_to_snippet(span.source_callsite())
+        .ok()
+}
+
+/// Converts a `rustc_span::Span` to a `SpanInfo` that does not depend on the compiler session.
+/// Looks up the start and end position of the span in the source map of the current session.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_span/source_map/struct.SourceMap.html>
+///
+/// Spans produced by macro expansions (e.g. `println!`) are mapped to the outermost macro call site,
+/// so that the result always points to the code written by the user.
+pub fn span_info(span: Span, tcx: TyCtxt) -> SpanInfo {
+    let span = span.source_callsite();
+    let source_map = tcx.sess.source_map();
+    let start = source_map.lookup_char_pos(span.lo());
+    let end = source_map.lookup_char_pos(span.hi());
+    SpanInfo {
+        filename: start.file.name.prefer_local().to_string(),
+        start_line: start.line,
+        start_column: start.col_display + 1,
+        end_line: end.line,
+        end_column: end.col_display + 1,
+    }
+}
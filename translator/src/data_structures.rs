@@ -0,0 +1,12 @@
+//! Module that implements general data structures
+//! used for the translation. These do not depend on rustc internals.
+//!
+//! The data structures that hold the result of the translation are defined
+//! in the crate `cargo_check_deadlock_core` and re-exported here.
+
+pub use cargo_check_deadlock_core::data_structures::{
+    coverage, function_classification, petri_net_interface, profiler, span_info, sync_registry,
+    translation_plan,
+};
+pub mod hash_map_counter;
+pub mod stack;
@@ -25,6 +25,19 @@ impl<T> Stack<T> {
         self.stack.pop()
     }
 
+    /// Returns the number of elements in the stack.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Returns an iterator over the elements of the stack, from the bottom to the top.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.stack.iter()
+    }
+
     /// Returns an immutable reference to the top element of the stack.
     /// Does not remove the element from the stack.
     ///
@@ -119,6 +132,30 @@ mod stack_tests {
         assert_eq!(stack.stack.len(), 10);
     }
 
+    #[test]
+    fn stack_len_matches_number_of_elements() {
+        let mut stack: Stack<usize> = Stack::new();
+        assert!(stack.stack.is_empty());
+
+        stack.push(1);
+        stack.push(2);
+
+        assert!(!stack.stack.is_empty());
+        assert_eq!(stack.len(), 2);
+    }
+
+    #[test]
+    fn stack_iter_goes_from_bottom_to_top() {
+        let mut stack: Stack<usize> = Stack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        let elements: Vec<&usize> = stack.iter().collect();
+
+        assert_eq!(elements, vec![&1, &2, &3]);
+    }
+
     #[test]
     fn stack_pop_updates_length() {
         let mut stack: Stack<usize> = Stack::new();
@@ -0,0 +1,135 @@
+//! Submodule for running the translation inside an existing build, like `clippy-driver` or Miri do.
+//!
+//! The driver behaves exactly like `rustc` and receives the same command line arguments.
+//! After the analysis of a crate that contains a `main` function, or the function given as the entry function,
+//! the crate is translated to a Petri net as a side effect of the compilation.
+//! This allows to run the translation on every crate of a Cargo build by setting
+//! `RUSTC=cargo-check-deadlock-driver` or `RUSTC_WORKSPACE_WRAPPER=cargo-check-deadlock-driver`.
+//!
+//! The compiler callbacks are documented here:
+//! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_driver/trait.Callbacks.html>
+
+use log::info;
+
+use crate::compiler_interface::{crate_name, entry_function, function_by_path, TyCtxt};
+use crate::sysroot;
+use crate::translator::translate;
+use crate::{Options, Translation};
+
+/// Callbacks that translate the crate after the analysis
+/// and pass the result to the given function together with the crate name.
+struct TranslationCallbacks<F> {
+    options: Options,
+    on_translation: F,
+}
+
+impl<F: FnMut(&str, Translation) + Send> rustc_driver::Callbacks for TranslationCallbacks<F> {
+    #[cfg(rustc_after_analysis_tcx)]
+    fn after_analysis(
+        &mut self,
+        _compiler: &rustc_interface::interface::Compiler,
+        tcx: TyCtxt,
+    ) -> rustc_driver::Compilation {
+        self.translate_crate(tcx);
+        // The translation is only a side effect: Continue with the normal compilation.
+        rustc_driver::Compilation::Continue
+    }
+
+    #[cfg(not(rustc_after_analysis_tcx))]
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &rustc_interface::interface::Compiler,
+        queries: &'tcx rustc_interface::Queries<'tcx>,
+    ) -> rustc_driver::Compilation {
+        queries
+            .global_ctxt()
+            .expect("BUG: Unable to get the global typing context needed for the `Translator`")
+            .enter(|tcx| self.translate_crate(tcx));
+        // The translation is only a side effect: Continue with the normal compilation.
+        rustc_driver::Compilation::Continue
+    }
+}
+
+impl<F: FnMut(&str, Translation) + Send> TranslationCallbacks<F> {
+    /// Translates the crate if it contains the entry function and passes the translation to `on_translation`.
+    fn translate_crate(&mut self, tcx: TyCtxt) {
+        let crate_name = crate_name(tcx);
+        match &self.options.entry_function {
+            Some(path) if function_by_path(path, tcx).is_none() => {
+                info!(
+                    "Skipping the translation of crate {crate_name}: It has no function `{path}`"
+                );
+                return;
+            }
+            None if entry_function(tcx).is_none() => {
+                info!("Skipping the translation of crate {crate_name}: It has no main function");
+                return;
+            }
+            _ => {}
+        }
+        let translation = translate(tcx, self.options.clone());
+        info!("Finished translating crate {crate_name}");
+        (self.on_translation)(&crate_name, translation);
+    }
+}
+
+/// Callbacks that do not modify the compilation.
+struct DefaultCallbacks;
+
+impl rustc_driver::Callbacks for DefaultCallbacks {}
+
+/// Runs the compiler with the given command line arguments, just like `rustc`.
+/// Crates with a `main` function are translated to a Petri net after the analysis.
+/// The translation and the name of the crate are passed to `on_translation`.
+///
+/// The first argument is the name of the program and is ignored, like in `rustc`.
+/// If the arguments do not contain a sysroot, the sysroot of the current toolchain is added.
+///
+/// # Errors
+///
+/// If the `sysroot` cannot be found, then an error is returned.
+/// If the compilation fails, then an error is returned. The compiler already reported the details.
+pub fn run_with_translation<F>(
+    args: Vec<String>,
+    options: Options,
+    on_translation: F,
+) -> Result<(), &'static str>
+where
+    F: FnMut(&str, Translation) + Send,
+{
+    let args = add_sysroot_if_missing(args)?;
+    let mut callbacks = TranslationCallbacks {
+        options,
+        on_translation,
+    };
+    rustc_driver::RunCompiler::new(&args, &mut callbacks)
+        .run()
+        .map_err(|_| "Compilation failed")
+}
+
+/// Runs the compiler with the given command line arguments, just like `rustc`, without translating anything.
+/// It is used for the crates that should not be translated, e.g. the dependencies.
+///
+/// # Errors
+///
+/// If the `sysroot` cannot be found, then an error is returned.
+/// If the compilation fails, then an error is returned. The compiler already reported the details.
+pub fn run_without_translation(args: Vec<String>) -> Result<(), &'static str> {
+    let args = add_sysroot_if_missing(args)?;
+    rustc_driver::RunCompiler::new(&args, &mut DefaultCallbacks)
+        .run()
+        .map_err(|_| "Compilation failed")
+}
+
+/// Adds the sysroot of the current toolchain to the arguments if they do not contain one.
+/// The driver is not located in the sysroot like `rustc`, so the compiler cannot find the standard library otherwise.
+fn add_sysroot_if_missing(mut args: Vec<String>) -> Result<Vec<String>, &'static str> {
+    let has_sysroot = args
+        .iter()
+        .any(|arg| arg == "--sysroot" || arg.starts_with("--sysroot="));
+    if !has_sysroot {
+        let sysroot = sysroot::get_from_rustc()?;
+        args.push(format!("--sysroot={}", sysroot.display()));
+    }
+    Ok(args)
+}
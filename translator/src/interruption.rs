@@ -0,0 +1,22 @@
+//! Module that allows to interrupt a long translation, e.g. when the user presses Ctrl-C.
+//!
+//! The interruption is requested from another thread, typically a signal handler,
+//! and checked by the `Translator` at a safe point: Before translating a call to a function
+//! with MIR representation. Once it is requested, these calls are modelled as foreign function calls,
+//! so the translation finishes quickly with a valid net that only models part of the program.
+//! The `Translation` is then flagged as interrupted.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the interruption of the translation was requested.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Requests the translation to stop at the next safe point.
+pub fn request_interruption() {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+}
+
+/// Checks whether the interruption of the translation was requested.
+pub fn interruption_requested() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
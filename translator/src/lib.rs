@@ -0,0 +1,191 @@
+//! Library code that implements the translation of Rust source code to a Petri net.
+//!
+//! It uses rustc's query system to navigate the AST.
+//!
+//! Adapted from the example in the rustc-dev guide:
+//! <https://rustc-dev-guide.rust-lang.org/rustc-driver-interacting-with-the-ast.html>
+//!
+//! NOTE: For the library code to compile, you will need to first run the following:
+//! `rustup component add rustc-dev llvm-tools-preview`
+
+// This feature gate is necessary to access the internal crates of the compiler.
+// It has existed for a long time and since the compiler internals will never be stabilized,
+// the situation will probably stay like this.
+// <https://doc.rust-lang.org/unstable-book/language-features/rustc-private.html>
+#![feature(rustc_private)]
+
+// Compiler crates need to be imported in this way because they are not published on crates.io.
+// These crates are only available when using the nightly toolchain.
+// It suffices to declare them once to use their types and methods in the whole crate.
+extern crate rustc_ast_pretty;
+extern crate rustc_const_eval;
+extern crate rustc_driver;
+extern crate rustc_error_codes;
+extern crate rustc_errors;
+extern crate rustc_feature;
+extern crate rustc_hash;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+mod compiler_config;
+mod compiler_interface;
+mod data_structures;
+pub mod driver;
+mod interruption;
+mod options;
+mod plugin_loader;
+mod sysroot;
+mod translator;
+mod utils;
+
+// The naming of places and transitions is defined in the core crate.
+use cargo_check_deadlock_core::analysis::lint::LintReport;
+use cargo_check_deadlock_core::data_structures::translation_plan::TranslationPlan;
+use cargo_check_deadlock_core::naming;
+use cargo_check_deadlock_core::Translation;
+pub use interruption::{interruption_requested, request_interruption};
+pub use options::{
+    default_cache_folder, default_lock_libraries, CondvarWakeup, Granularity, LockLibrary, Options,
+    DEFAULT_MAX_CALL_DEPTH, DEFAULT_THREAD_INSTANCES,
+};
+pub use plugin_loader::load_plugin;
+
+/// Entry point for the translation of the Rust code to a Petri net.
+/// The translation is configured through the given `Options`.
+///
+/// # Errors
+///
+/// If the `sysroot` cannot be found, then an error is returned.
+/// If the additional arguments for the compiler in the `Options` are not valid, then an error is returned.
+/// If the entry function or a function to run at program start is not found in the source code,
+/// then an error is returned.
+/// If the translation fails, then an error with the corresponding description is returned.
+///
+/// # Panics
+///
+/// If the global typing context `rustc_middle::ty::TyCtxt` cannot be found, then the function panics.
+/// If the translation failed due to a bug, then the function panics.
+pub fn run(
+    source_code_filepath: std::path::PathBuf,
+    options: Options,
+) -> Result<Translation, &'static str> {
+    let sysroot = sysroot::get_from_rustc()?;
+    // A different entry function allows to translate a library without a `main` function.
+    let is_library = options.library || options.entry_function.is_some();
+    let config = compiler_config::prepare_rustc_config(
+        sysroot,
+        source_code_filepath,
+        is_library,
+        &options.rustc_args,
+    )?;
+    let mut translation_result: Result<Translation, &'static str> = Err("Translation did not run");
+
+    rustc_interface::run_compiler(config, |compiler| {
+        compiler.enter(|queries| {
+            // Get the global typing context (tcx: TyCtxt), which is the central data structure in the compiler.
+            // <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/struct.TyCtxt.html>
+            let mut query = queries
+                .global_ctxt()
+                .expect("BUG: Unable to get the global typing context needed for the `Translator`");
+
+            // Run the translator as a query to the compiler.
+            // <https://rustc-dev-guide.rust-lang.org/rustc-driver.html>
+            query.enter(|tcx| {
+                translation_result = translator::check_entry_points(&options, tcx)
+                    .map(|()| translator::translate(tcx, options));
+            });
+        });
+    });
+
+    translation_result
+}
+
+/// Entry point for a dry run of the translation.
+/// Computes the plan of the translation configured through the given `Options` without building the net,
+/// see `TranslationPlan`.
+///
+/// # Errors
+///
+/// If the `sysroot` cannot be found, then an error is returned.
+/// If the additional arguments for the compiler in the `Options` are not valid, then an error is returned.
+/// If the entry function or a function to run at program start is not found in the source code,
+/// then an error is returned.
+///
+/// # Panics
+///
+/// If the global typing context `rustc_middle::ty::TyCtxt` cannot be found, then the function panics.
+pub fn plan(
+    source_code_filepath: std::path::PathBuf,
+    options: Options,
+) -> Result<TranslationPlan, &'static str> {
+    let sysroot = sysroot::get_from_rustc()?;
+    let is_library = options.library || options.entry_function.is_some();
+    let config = compiler_config::prepare_rustc_config(
+        sysroot,
+        source_code_filepath,
+        is_library,
+        &options.rustc_args,
+    )?;
+    let mut plan_result: Result<TranslationPlan, &'static str> = Err("Dry run did not run");
+
+    rustc_interface::run_compiler(config, |compiler| {
+        compiler.enter(|queries| {
+            let mut query = queries
+                .global_ctxt()
+                .expect("BUG: Unable to get the global typing context needed for the `Planner`");
+
+            query.enter(|tcx| {
+                plan_result = translator::check_entry_points(&options, tcx).map(|()| {
+                    let mut planner = translator::plan::Planner::new(tcx, options);
+                    planner.run()
+                });
+            });
+        });
+    });
+
+    plan_result
+}
+
+/// Entry point for the lint mode.
+/// Runs the structural detectors on the lock operations recorded in the MIR representation of every function
+/// of the crate without building the net, see `cargo_check_deadlock_core::analysis::lint`.
+/// Only the options `library` and `rustc_args` are used.
+///
+/// # Errors
+///
+/// If the `sysroot` cannot be found, then an error is returned.
+/// If the additional arguments for the compiler in the `Options` are not valid, then an error is returned.
+///
+/// # Panics
+///
+/// If the global typing context `rustc_middle::ty::TyCtxt` cannot be found, then the function panics.
+pub fn lint(
+    source_code_filepath: std::path::PathBuf,
+    options: &Options,
+) -> Result<LintReport, &'static str> {
+    let sysroot = sysroot::get_from_rustc()?;
+    let config = compiler_config::prepare_rustc_config(
+        sysroot,
+        source_code_filepath,
+        options.library,
+        &options.rustc_args,
+    )?;
+    let mut lint_result: Result<LintReport, &'static str> = Err("Lint did not run");
+
+    rustc_interface::run_compiler(config, |compiler| {
+        compiler.enter(|queries| {
+            let mut query = queries
+                .global_ctxt()
+                .expect("BUG: Unable to get the global typing context needed for the `Linter`");
+
+            query.enter(|tcx| {
+                lint_result = Ok(translator::lint::Linter::new(tcx, &options.lock_libraries).run());
+            });
+        });
+    });
+
+    lint_result
+}
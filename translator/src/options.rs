@@ -0,0 +1,204 @@
+//! Module that defines the options that configure the translation.
+//!
+//! The options are set by the user through the command-line interface
+//! and passed to the `Translator` unchanged.
+
+use cargo_check_deadlock_core::plugin::PluginModels;
+
+/// Default maximum depth of the call stack during the translation.
+/// Deep enough for the usual programs, shallow enough to avoid a blowup
+/// when translating long chains of utility functions.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 32;
+
+/// Default number of instances of a thread spawned in a loop that run concurrently in the net.
+/// Two instances are enough to find the deadlocks between workers that run the same code.
+pub const DEFAULT_THREAD_INSTANCES: usize = 2;
+
+/// The policy that selects the thread woken by `std::sync::Condvar::notify_one`
+/// when several threads wait on the same condition variable.
+///
+/// Only one call to `wait` or `wait_while` is supported per condition variable,
+/// so several threads only wait on it if they run the same code, e.g. threads spawned in a loop.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CondvarWakeup {
+    /// Any waiting thread may be woken, which covers every policy of the operating system.
+    /// The model has no queue, so a single thread waits at a time:
+    /// Another thread that calls `wait` blocks until the waiting thread is woken.
+    #[default]
+    Any,
+    /// The threads are woken in the order they started waiting.
+    /// Up to `FIFO_QUEUE_LENGTH` threads wait at the same time.
+    Fifo,
+}
+
+/// A library whose mutexes and read-write locks are modelled like the ones in `std::sync`.
+/// Its `Mutex` must provide `new` and `lock` and its `RwLock` must provide `new`, `read` and `write`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockLibrary {
+    /// The path to the module with the lock types, e.g. `std::sync`.
+    pub path: String,
+    /// The generic parameters of the lock types as they appear in the names of their functions, e.g. `<T>`.
+    pub generics: String,
+}
+
+impl LockLibrary {
+    /// Creates a new `LockLibrary` with the given path and generic parameters.
+    pub fn new(path: &str, generics: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            generics: generics.to_string(),
+        }
+    }
+}
+
+/// Returns the lock libraries supported by default: `std::sync` and `lock_api`.
+///
+/// `parking_lot` defines its locks as type aliases of the generic locks in `lock_api`,
+/// e.g. `parking_lot::Mutex<T>` is `lock_api::Mutex<parking_lot::RawMutex, T>`.
+/// Their functions are therefore named after `lock_api`, sometimes with the prefix `parking_lot::`
+/// if the crate does not depend on `lock_api` directly.
+pub fn default_lock_libraries() -> Vec<LockLibrary> {
+    vec![
+        LockLibrary::new("std::sync", "<T>"),
+        LockLibrary::new("lock_api", "<R, T>"),
+    ]
+}
+
+/// How much of the structure of the MIR is modelled in the net.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Granularity {
+    /// Every statement of a basic block is a transition followed by a place.
+    Statement,
+    /// Every basic block is a place. The statements do not appear in the net.
+    #[default]
+    Block,
+    /// The basic blocks joined by a goto terminator share a place, so the net only contains
+    /// the branches, the calls and the synchronization of every function.
+    Function,
+}
+
+/// The options that configure the translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// The path of the function where the translation starts, e.g. `my_mod::worker`.
+    /// If `None`, the translation starts at the `main` function of the crate.
+    pub entry_function: Option<String>,
+    /// Whether every exported function of the crate is translated instead of a single entry function.
+    /// The program start place branches off to the start of every exported function,
+    /// so a deadlock in any of them is a deadlock of the net.
+    pub library: bool,
+    /// The maximum number of nested user-defined functions translated from their MIR representation.
+    /// Calls beyond this depth are modelled as foreign function calls.
+    pub max_call_depth: usize,
+    /// Whether self-recursive calls in tail position are modelled as a loop,
+    /// i.e. as a back edge to the start of the function, instead of a single transition.
+    pub recursion_as_loop: bool,
+    /// The maximum number of instances of a thread spawned in a loop that start in the net.
+    /// The iterations of the loop after the last instance started do not start a new thread.
+    pub thread_instances: usize,
+    /// The number of instances of a thread recognized as the worker of a pool,
+    /// i.e. a thread spawned in a loop that waits for work in a loop, see `sync::thread::worker_queue_function`.
+    /// If `None`, the workers are bounded by `thread_instances` like any other thread spawned in a loop.
+    pub worker_pool_size: Option<usize>,
+    /// Whether a `panic!` in a thread is modelled separately from the normal end of the thread,
+    /// so that joining a panicked thread, i.e. `join` returning `Err`, is a distinct transition.
+    pub propagate_thread_panics: bool,
+    /// Whether a panic while holding a mutex guard or the write guard of a read-write lock poisons the lock.
+    /// The `Err` returned by `lock`, `read` or `write` on a poisoned lock is then only possible after such a panic.
+    pub model_poisoning: bool,
+    /// The thread woken by `notify_one` when several threads wait on the same condition variable.
+    pub condvar_wakeup: CondvarWakeup,
+    /// How much of the structure of the MIR is modelled in the net.
+    /// A coarser granularity produces a smaller state space at the cost of fidelity.
+    pub granularity: Granularity,
+    /// The capacity of the buffer of the channels created with `std::sync::mpsc::channel`.
+    /// If `None`, the buffer is unbounded like in the program.
+    /// A bound makes the state space finite, but a `send` on a full buffer blocks like on a `sync_channel`.
+    pub channel_bound: Option<usize>,
+    /// The maximum number of iterations of every loop in a translated function,
+    /// counted as the executions of the header of the loop, including the one that leaves the loop.
+    /// If `None`, the loops are unbounded like in the program.
+    /// A bound makes the state space smaller, but a thread stops at the header once the bound is reached.
+    pub loop_bound: Option<usize>,
+    /// The libraries whose mutexes and read-write locks are modelled like the ones in `std::sync`.
+    pub lock_libraries: Vec<LockLibrary>,
+    /// The models of additional synchronization primitives loaded from plugins.
+    /// See `cargo_check_deadlock_core::plugin` for the interface.
+    pub plugins: Vec<PluginModels>,
+    /// The folder where the classification of the functions of other crates, e.g. the standard library,
+    /// is persisted between runs. If `None`, the classification is only kept during the translation.
+    pub classification_cache: Option<std::path::PathBuf>,
+    /// Whether the time spent translating each function and in each phase is recorded.
+    pub profile: bool,
+    /// The maximum number of bytes of arcs kept in memory during the translation.
+    /// The rest of the arcs are moved to a temporary file. If `None`, every arc is kept in memory.
+    pub max_arc_memory: Option<usize>,
+    /// Additional arguments for the compiler in the syntax of `rustc`,
+    /// e.g. `--edition=2018`, `--cfg=feature="std"` or `--extern=rand=librand.rlib`.
+    pub rustc_args: Vec<String>,
+    /// Whether the translation is checked by replaying an execution of the program on the net.
+    /// See `translator::cross_check` for the supported subset of the programs.
+    pub cross_check: bool,
+    /// Whether the places and transitions added for every basic block are recorded,
+    /// see `Translation::block_mapping`.
+    pub block_mapping: bool,
+    /// Whether the function, the line and the MIR text that produced every place and transition are recorded,
+    /// see `Translation::label_map`.
+    pub label_map: bool,
+    /// Whether the branches on values that depend on the environment, e.g. on the command line arguments,
+    /// are tagged as controlled by the environment, see `translator::environment`.
+    pub environment_branches: bool,
+    /// The paths of the functions that are not translated, e.g. `my_mod::parse`.
+    /// The calls to them are modelled as foreign function calls.
+    pub skipped_functions: Vec<String>,
+    /// The paths of the functions run by a thread spawned at program start, e.g. `ffi::on_event`.
+    /// They model the concurrency of callbacks invoked by foreign code. Not supported by the cross-check.
+    pub also_run: Vec<String>,
+    /// The maximum time spent translating a single function.
+    /// A function that exceeds it is skipped and the translation starts again. If `None`, there is no limit.
+    pub function_time_budget: Option<std::time::Duration>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            entry_function: None,
+            library: false,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            recursion_as_loop: false,
+            thread_instances: DEFAULT_THREAD_INSTANCES,
+            worker_pool_size: None,
+            propagate_thread_panics: false,
+            model_poisoning: false,
+            condvar_wakeup: CondvarWakeup::Any,
+            granularity: Granularity::Block,
+            channel_bound: None,
+            loop_bound: None,
+            lock_libraries: default_lock_libraries(),
+            plugins: Vec::new(),
+            classification_cache: default_cache_folder(),
+            profile: false,
+            max_arc_memory: None,
+            rustc_args: Vec::new(),
+            cross_check: false,
+            block_mapping: false,
+            label_map: false,
+            environment_branches: false,
+            skipped_functions: Vec::new(),
+            also_run: Vec::new(),
+            function_time_budget: None,
+        }
+    }
+}
+
+/// Returns the default folder for the files cached between runs:
+/// `$XDG_CACHE_HOME/cargo-check-deadlock` or `$HOME/.cache/cargo-check-deadlock`.
+/// Returns `None` if neither environment variable is set.
+pub fn default_cache_folder() -> Option<std::path::PathBuf> {
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".cache"))
+        })?;
+    Some(cache_home.join("cargo-check-deadlock"))
+}
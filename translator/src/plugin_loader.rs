@@ -0,0 +1,40 @@
+//! Submodule for loading the plugins that model additional synchronization primitives.
+//!
+//! A plugin is a dynamic library that exports the entry point defined in `cargo_check_deadlock_core::plugin`.
+//! The descriptor returned by the plugin is copied right away,
+//! so the library is unloaded again before the translation starts.
+
+use cargo_check_deadlock_core::plugin::{PluginEntryPoint, PluginModels, PLUGIN_ENTRY_POINT};
+
+/// Loads the dynamic library at the given path and copies the models declared by the plugin.
+///
+/// # Errors
+///
+/// If the library cannot be loaded or does not export the entry point, then an error is returned.
+/// If the descriptor returned by the plugin is null or invalid, then an error is returned.
+pub fn load_plugin(path: &std::path::Path) -> Result<PluginModels, String> {
+    // SAFETY: Loading a library runs its initialization routines.
+    // The user is responsible for passing a trustworthy plugin.
+    let library = unsafe { libloading::Library::new(path) }
+        .map_err(|err| format!("Plugin at {} could not be loaded: {err}", path.display()))?;
+    // SAFETY: The signature of the entry point is fixed by the plugin interface.
+    let entry_point = unsafe { library.get::<PluginEntryPoint>(PLUGIN_ENTRY_POINT.as_bytes()) }
+        .map_err(|err| {
+            format!(
+                "Plugin at {} does not export the function {PLUGIN_ENTRY_POINT}: {err}",
+                path.display()
+            )
+        })?;
+    // SAFETY: The plugin interface requires the descriptor to be valid while the library is loaded.
+    // The library is unloaded only after copying the models.
+    unsafe {
+        let descriptor = entry_point();
+        if descriptor.is_null() {
+            return Err(format!(
+                "Plugin at {} returned a null descriptor",
+                path.display()
+            ));
+        }
+        PluginModels::from_descriptor(&*descriptor)
+    }
+}
@@ -0,0 +1,1942 @@
+//! Submodule for the main translation logic.
+//!
+//! The source code translation takes place on the level of the Mid-level Intermediate Representation (MIR).
+//! <https://rustc-dev-guide.rust-lang.org/mir/index.html>
+//!
+//! The `Translator` translates the MIR code of each function in approximately the order they are called.
+//! For this purpose a call stack is used to represent the functions being translated, similar to how the processor
+//! executes the code.
+//!
+//! Each MIR function consists of one or more basic blocks.
+//! Each basic block consists of 0 or more statements and exactly one terminator statement.
+//!
+//! Functions are uniquely identified through their `FunctionId`, which wraps the definition ID of the compiler.
+//! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_hir/def_id/struct.DefId.html>
+//! The interaction with the compiler internals is isolated in the `compiler_interface` module.
+//!
+//! It is possible to obtain the MIR representation for a specific function on-demand.
+//! `rustc` supports a query system that computes the result and caches it automatically, which
+//! saves us the work of storing everything we request from the compiler.
+//! More information on the query system: <https://rustc-dev-guide.rust-lang.org/query.html>
+//!
+//! The naming of the places and transitions in the net is globally unique,
+//! i.e. each function, block and statement receive a different label.
+//! It can be configured in the `naming` submodule.
+//!
+//! The translation order is deterministic: The threads are translated in the order they were discovered,
+//! which includes the threads spawned by other threads,
+//! and the postprocessing tasks with the same priority are run in the order they were created.
+//! It does not depend on the iteration order of hash maps, so unrelated changes to the program do not affect the net.
+//!
+//! Every function called is classified by a `FunctionClassifier` to select the handler.
+//! The classification of the functions of other crates, e.g. the standard library,
+//! is persisted between runs in the folder given by the `classification_cache` option.
+//!
+//! If the `profile` option is set, a `Profiler` records the time spent translating each function,
+//! split into querying the MIR body, visiting it and adding the calls to other functions to the net.
+//!
+//! A `HashMapCounter` keeps track of how many time each function name has been seen so far.
+//! After every call the counter for the corresponding function is incremented.
+//!
+//! Calls to trait methods through the generic parameters of the caller, e.g. `<T as Trait>::method`,
+//! are resolved to the method of the implementation for the concrete type.
+//! For this purpose every `MirFunction` stores the generic arguments with which it was called.
+//!
+//! Recursive calls, either direct (A calls A) or mutual (A calls B calls A), are detected through the call stack.
+//! The call that closes the cycle is modelled as a single transition instead of translating the function again.
+//!
+//! Self-recursive calls in tail position ("loops via recursion") may instead be modelled as a loop,
+//! i.e. a back edge to the start of the function, if the `recursion_as_loop` option is set.
+//!
+//! The depth of the call stack is limited by the `max_call_depth` option.
+//! Calls to user-defined functions beyond this depth are modelled as foreign function calls.
+//!
+//! Functions modelled by the plugins passed in the `plugins` option are translated
+//! according to the model of the plugin, see the `plugin` submodule.
+//!
+//! The `Coverage` keeps track of which user-defined functions were translated
+//! and which were abstracted as foreign function calls.
+//!
+//! The `plan` submodule follows the calls with the same rules without building the net,
+//! which gives an overview of the translation for a dry run.
+//!
+//! If the `cross_check` option is set, the `cross_check` submodule records the places of the basic blocks
+//! of every function translated and, after the translation, replays an execution of the program on the net.
+//!
+//! If the `block_mapping` option is set, the `block_mapping` submodule records the places and transitions
+//! added for every basic block of every function translated.
+//! Similarly, the `label_map` option enables the `label_map` submodule, which records the function,
+//! the line and the text of the MIR element that produced every place and transition.
+//!
+//! The functions in the `skipped_functions` option are abstracted as foreign function calls.
+//! The translation of a function stops with a `TranslationError` when it reaches an unsupported feature
+//! or exceeds the `function_time_budget` option. `translate` adds the function to the skipped functions
+//! and translates the program again, since the partial translation already changed the net.
+//! A panic is a bug in the translator and is never recovered from.
+//! The skipped functions are listed in the `Coverage` with the reason.
+//!
+//! If the interruption of the translation is requested, see the `interruption` module,
+//! the remaining calls to functions with MIR representation are abstracted as foreign function calls.
+//!
+//! The `granularity` option sets how fine the control flow inside a function is modelled:
+//! Every statement as a transition, every basic block as a place (the default),
+//! or only the terminators that are not a `Goto`, whose target blocks share the place of the source.
+
+mod block_mapping;
+mod classifier;
+mod closure;
+mod cross_check;
+mod environment;
+mod function;
+mod label_map;
+pub mod lint;
+mod mir_function;
+mod mir_visitor;
+mod nondet;
+pub mod plan;
+mod plugin;
+mod special_function;
+mod sync;
+mod unsafe_escape;
+
+use log::{debug, error, info, warn};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::rc::Rc;
+
+use crate::compiler_interface::{
+    argument_operand, entry_function, exported_functions, function_by_path, local_source_files,
+    resolve_call, span_info, BasicBlock, CallArgs, FunctionId, GenericArgs, List, Local, Operand,
+    Place, Span, TyCtxt, UnwindAction, Visitor, RETURN_PLACE,
+};
+use crate::data_structures::coverage::{Coverage, FunctionStatus};
+use crate::data_structures::function_classification::{ClassifiedFunction, FunctionClass};
+use crate::data_structures::hash_map_counter::HashMapCounter;
+use crate::data_structures::petri_net_interface::{connect_places, PetriNet, PlaceRef};
+use crate::data_structures::profiler::{
+    Profiler, MIR_QUERY_FRAME, NET_INSERTION_FRAME, TRANSLATION_FRAME, VISIT_FRAME,
+};
+use crate::data_structures::stack::Stack;
+use crate::data_structures::sync_registry::{SyncKind, SyncRegistry};
+use crate::interruption::interruption_requested;
+use crate::naming::channel::free_slots_place_label;
+use crate::naming::function::{
+    entry_place_label, entry_transition_label, indexed_mir_function_name,
+    nondet_call_transition_labels,
+};
+use crate::naming::thread::{also_run_place_label, also_run_transition_label};
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC, PROGRAM_START};
+use crate::options::Options;
+use crate::utils::{
+    check_substring_in_place_type, extract_closure, extract_function_id_from_operand,
+    extract_generic_args_from_operand, extract_nth_argument_as_place,
+    extract_nth_argument_as_usize, is_return_block,
+};
+use crate::Translation;
+use block_mapping::BlockMappingRecord;
+use cargo_check_deadlock_core::analysis::cross_check::{CrossCheckReport, CrossCheckVerdict};
+use cargo_check_deadlock_core::annotations::{
+    apply_property_annotations, parse_suppressions, AnnotatedProperty, Suppression,
+};
+use classifier::FunctionClassifier;
+use cross_check::{CallRecord, ControlFlowRecord, Interpreter, SyncEffect};
+use function::{Places, PostprocessingTask, Transitions};
+use label_map::LabelMapRecord;
+use mir_function::memory::{MutexRef, Value};
+use mir_function::MirFunction;
+use plugin::{call_constructor, call_plugin_function, PluginCalls};
+use special_function::{
+    call_diverging_function, call_foreign_function, call_panic_function, call_recursive_function,
+    call_recursive_function_as_loop, connect_call_places,
+};
+use sync::thread::Thread;
+use sync::{check_lock_type_in_place, mutex, rwlock, LockFunction};
+
+/// Checks that the functions given in the `Options` exist in the source code
+/// before the translation starts. The path of a missing function is logged.
+///
+/// # Errors
+///
+/// If the function given with the `entry_function` option is not found in the source code, then an error is returned.
+/// If a function in the `also_run` option is not found in the source code, then an error is returned.
+pub fn check_entry_points(options: &Options, tcx: TyCtxt) -> Result<(), &'static str> {
+    if let Some(path) = &options.entry_function {
+        if function_by_path(path, tcx).is_none() {
+            error!("The entry function `{path}` was not found in the source code");
+            return Err(
+                "The entry function given with `--entry-function` was not found in the source code",
+            );
+        }
+    }
+    for path in &options.also_run {
+        if function_by_path(path, tcx).is_none() {
+            error!(
+                "The function `{path}` to run at program start was not found in the source code"
+            );
+            return Err("A function given with `--also-run` was not found in the source code");
+        }
+    }
+    Ok(())
+}
+
+/// Returns the function where the translation starts:
+/// The function given with the `entry_function` option or else the `main` function of the crate.
+///
+/// # Panics
+///
+/// If the crate has no `main` function, then the function panics.
+fn entry_function_id(options: &Options, tcx: TyCtxt) -> FunctionId {
+    match &options.entry_function {
+        Some(path) => function_by_path(path, tcx)
+            .expect("BUG: The entry function should have been found by `check_entry_points`"),
+        None => entry_function(tcx).expect("ERROR: No main function found in the source code"),
+    }
+}
+
+/// The maximum number of functions skipped automatically before giving up on the translation.
+/// Every skipped function costs a new translation from the start.
+const MAX_SKIPPED_FUNCTIONS: usize = 16;
+
+/// An error that stops the translation of a function.
+#[derive(Clone, Debug)]
+pub enum TranslationError {
+    /// The function uses a feature that is not supported yet.
+    Unsupported(String),
+    /// The translation of the function took longer than the `function_time_budget` option.
+    TimeBudgetExceeded(std::time::Duration),
+    /// The translation of the function with the given path, called by another function, stopped.
+    /// The function can be skipped.
+    Callee {
+        path: String,
+        error: Box<TranslationError>,
+    },
+}
+
+impl TranslationError {
+    /// Attributes the error to the function with the given path, called by another function,
+    /// unless it was already attributed to a function called by it.
+    fn in_callee(self, path: String) -> Self {
+        match self {
+            Self::Callee { .. } => self,
+            error => Self::Callee {
+                path,
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported(feature) => write!(f, "not implemented: {feature}"),
+            Self::TimeBudgetExceeded(budget) => {
+                write!(f, "exceeded the time budget of {}s", budget.as_secs())
+            }
+            Self::Callee { path, error } => write!(f, "{error} in the function {path}"),
+        }
+    }
+}
+
+/// Translates the source code to a Petri net with the given `Options`.
+/// If the translation of a function called by another function stops with a `TranslationError`,
+/// the function is added to the `skipped_functions` and the translation starts again.
+/// The reason is recorded in the `Coverage` of the result.
+///
+/// # Panics
+///
+/// If the translation of an entry function stops with a `TranslationError`
+/// or `MAX_SKIPPED_FUNCTIONS` functions were skipped, then the function panics.
+pub fn translate(tcx: TyCtxt, mut options: Options) -> Translation {
+    let mut reasons: Vec<(String, String)> = Vec::new();
+    loop {
+        let mut translator = Translator::new(tcx, options.clone());
+        match translator.run() {
+            Ok(()) => {
+                for (path, reason) in &reasons {
+                    translator.coverage.record_skipped(path, reason);
+                }
+                return translator.get_result();
+            }
+            Err(TranslationError::Callee { path, error })
+                if reasons.len() < MAX_SKIPPED_FUNCTIONS =>
+            {
+                assert!(
+                    !options.skipped_functions.contains(&path),
+                    "BUG: The skipped function {path} should not be translated"
+                );
+                warn!("Skipping the function {path} and translating again: {error}");
+                options.skipped_functions.push(path.clone());
+                reasons.push((path, error.to_string()));
+            }
+            Err(error @ TranslationError::Callee { .. }) => panic!(
+                "ERROR: The translation stopped after skipping {MAX_SKIPPED_FUNCTIONS} functions: {error}"
+            ),
+            Err(error) => panic!("ERROR: The entry function cannot be translated: {error}"),
+        }
+    }
+}
+
+/// The central data structure and coordinator for the translation.
+pub struct Translator<'tcx> {
+    /// The global typing context that enables interaction with `rustc` during the translation.
+    tcx: TyCtxt<'tcx>,
+    /// The Petri net model of the program being translated.
+    net: PetriNet,
+    /// The place in the Petri net that models the program initial state.
+    program_start: PlaceRef,
+    /// The place in the Petri net that models a normal program end state, regardless of exit code.
+    program_end: PlaceRef,
+    /// The place in the Petri net that models the `panic!` end state.
+    program_panic: PlaceRef,
+    /// The call stack of user-defined functions with a MIR representation.
+    /// Other functions are translated in an abbreviated form.
+    call_stack: Stack<MirFunction<'tcx>>,
+    /// A counter that keeps track how many times each function was called so far.
+    /// Functions are identified by their name.
+    function_counter: HashMapCounter,
+    /// A vector of threads detected in the code.
+    /// They are translated after the main thread in the order they were discovered.
+    threads: VecDeque<Rc<Thread>>,
+    /// The number of threads detected so far, including the ones already translated.
+    /// It is the index of the next thread, since threads may also be spawned while translating another thread.
+    thread_count: usize,
+    /// Translation tasks performed after all threads have been translated.
+    /// These tasks usually require to make changes to the final Petri net.
+    /// Tasks with the same priority are run in the order they were created,
+    /// which is given by the sequence number stored next to each task.
+    postprocessing: BinaryHeap<(PostprocessingTask, Reverse<usize>)>,
+    /// The names of the functions in the order they were translated.
+    translation_order: Vec<String>,
+    /// The properties annotated in comments of the source code and applied to the net.
+    annotated_properties: Vec<AnnotatedProperty>,
+    /// The suppressions of findings annotated in comments of the source code.
+    suppressions: Vec<Suppression>,
+    /// The synchronization primitives found in the code and the calls that use them.
+    registry: SyncRegistry,
+    /// The user-defined functions reached during the translation and whether they were translated.
+    coverage: Coverage,
+    /// The constructors and the functions modelled by plugins, indexed by the function name.
+    plugin_calls: PluginCalls,
+    /// The classification of the functions called in the code.
+    classifier: FunctionClassifier,
+    /// The profiler that records the time spent in each function and phase of the translation.
+    profiler: Profiler,
+    /// The places and calls of the translated functions, recorded for the cross-check.
+    /// It is `None` unless the `cross_check` option is set.
+    control_flow: Option<ControlFlowRecord>,
+    /// The result of the cross-check of the translation against an execution of the program.
+    cross_check: Option<CrossCheckReport>,
+    /// The nodes of the net added for every basic block of the translated functions.
+    /// It is `None` unless the `block_mapping` option is set.
+    block_mapping: Option<BlockMappingRecord>,
+    /// The origin of every place and transition added inside the body of a function.
+    /// It is `None` unless the `label_map` option is set.
+    label_map: Option<LabelMapRecord>,
+    /// The labels of the places that bound the state space of the net but not the program,
+    /// e.g. the free slots of a channel with the `channel_bound` option.
+    bounded_places: Vec<String>,
+    /// The error that stopped the translation of the function on the top of the call stack, if any.
+    /// The MIR visitor does not visit the remaining basic blocks once it is set.
+    failure: Option<TranslationError>,
+    /// The options that configure the translation.
+    options: Options,
+}
+
+impl<'tcx> Translator<'tcx> {
+    /// Creates a new `Translator`.
+    /// Requires a global typing context `rustc_middle::ty::TyCtxt`, the main data structure of the compiler.
+    /// The initial Petri net contains three places representing the program start state,
+    /// the program end state and the abnormal end state after `panic!()`.
+    /// If the `max_arc_memory` option is set, the arcs beyond the limit are stored on disk.
+    /// The translation is configured through the given `Options`.
+    pub fn new(tcx: TyCtxt<'tcx>, options: Options) -> Self {
+        let mut net = options
+            .max_arc_memory
+            .map_or_else(PetriNet::new, PetriNet::with_arc_memory_limit);
+        let program_panic = net.add_place(PROGRAM_PANIC);
+        let program_end = net.add_place(PROGRAM_END);
+        let program_start = net.add_place(PROGRAM_START);
+        net.add_token(&program_start, 1).expect(
+            "BUG: Adding initial token to empty PROGRAM_START place should not cause an overflow",
+        );
+        let plugin_calls = PluginCalls::new(&options.plugins);
+        sync::set_lock_libraries(&options.lock_libraries);
+        sync::set_plugin_types(&options.plugins);
+        let classifier = FunctionClassifier::new(
+            options.classification_cache.as_deref(),
+            &options.lock_libraries,
+        );
+        let profiler = if options.profile {
+            Profiler::new()
+        } else {
+            Profiler::disabled()
+        };
+
+        Self {
+            tcx,
+            net,
+            program_start,
+            program_end,
+            program_panic,
+            call_stack: Stack::new(),
+            function_counter: HashMapCounter::new(),
+            threads: VecDeque::new(),
+            thread_count: 0,
+            postprocessing: BinaryHeap::new(),
+            translation_order: Vec::new(),
+            annotated_properties: Vec::new(),
+            suppressions: Vec::new(),
+            registry: SyncRegistry::new(),
+            coverage: Coverage::new(),
+            plugin_calls,
+            classifier,
+            profiler,
+            control_flow: options.cross_check.then(ControlFlowRecord::default),
+            cross_check: None,
+            block_mapping: options.block_mapping.then(BlockMappingRecord::default),
+            label_map: options.label_map.then(LabelMapRecord::default),
+            bounded_places: Vec::new(),
+            failure: None,
+            options,
+        }
+    }
+
+    /// Returns the result of the translation, i.e. the Petri net,
+    /// the registry of synchronization primitives, the coverage of the user-defined functions
+    /// and the order in which the functions were translated.
+    /// The ownership is transferred to the caller.
+    pub fn get_result(&mut self) -> Translation {
+        Translation {
+            net: std::mem::take(&mut self.net),
+            registry: std::mem::take(&mut self.registry),
+            coverage: std::mem::take(&mut self.coverage),
+            annotated_properties: std::mem::take(&mut self.annotated_properties),
+            suppressions: std::mem::take(&mut self.suppressions),
+            translation_order: std::mem::take(&mut self.translation_order),
+            profile: std::mem::take(&mut self.profiler),
+            cross_check: self.cross_check.take(),
+            block_mapping: self
+                .block_mapping
+                .take()
+                .map(BlockMappingRecord::into_mapping),
+            label_map: self.label_map.take().map(LabelMapRecord::into_map),
+            bounded_places: std::mem::take(&mut self.bounded_places),
+            interrupted: interruption_requested(),
+        }
+    }
+
+    /// Translates the source code to a Petri net.
+    ///
+    /// # Errors
+    ///
+    /// If the translation of a function stops, e.g. at an unsupported feature, then the error is returned.
+    pub fn run(&mut self) -> Result<(), TranslationError> {
+        self.profiler.enter(TRANSLATION_FRAME);
+        let start_place = self.spawn_additional_entry_points();
+        if self.options.library {
+            self.translate_exported_functions(&start_place)?;
+        } else {
+            let entry_function_id = entry_function_id(&self.options, self.tcx);
+            self.push_function_to_call_stack(
+                entry_function_id,
+                start_place,
+                self.program_end.clone(),
+            );
+            info!("Pushed entry function to the translation call stack");
+            self.translate_top_call_stack()?;
+        }
+        info!("Finished translating the main thread");
+        self.translate_threads()?;
+        info!("Running translation postprocessing...");
+        self.profiler.enter(NET_INSERTION_FRAME);
+        self.translation_postprocessing();
+        self.apply_annotations();
+        self.profiler.exit();
+        self.classifier.save();
+        self.profiler.exit();
+        if let Some(record) = &self.control_flow {
+            info!("Cross-checking the translation against an execution of the program...");
+            let report = Interpreter::new(self.tcx, record, &self.net.data()).map_or_else(
+                |err| CrossCheckReport {
+                    verdict: CrossCheckVerdict::Stopped {
+                        reason: err,
+                        span: None,
+                    },
+                    steps: 0,
+                    fired: Vec::new(),
+                },
+                Interpreter::run,
+            );
+            self.cross_check = Some(report);
+        }
+        Ok(())
+    }
+
+    /// Translates every exported function of the crate, see `exported_functions`.
+    /// Every function starts in its own place, which is connected to the given start place
+    /// through a separate transition. Only one of them fires, so every function is analyzed
+    /// on its own, as if the net contained one `main` function per exported function.
+    ///
+    /// # Errors
+    ///
+    /// If the translation of a function stops, then the error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the crate does not export any function that can be translated, then the function panics.
+    fn translate_exported_functions(
+        &mut self,
+        start_place: &PlaceRef,
+    ) -> Result<(), TranslationError> {
+        let functions = exported_functions(self.tcx);
+        if functions.is_empty() {
+            panic!(
+                "ERROR: No exported function without generic parameters found in the source code"
+            );
+        }
+        for function_id in functions {
+            let function_name = function_id.name(self.tcx);
+            let function_start_place = self.net.add_place(&entry_place_label(&function_name));
+            connect_places(
+                &mut self.net,
+                start_place,
+                &function_start_place,
+                &entry_transition_label(&function_name),
+            );
+            self.push_function_to_call_stack(
+                function_id,
+                function_start_place,
+                self.program_end.clone(),
+            );
+            info!("Pushed exported function {function_name} to the translation call stack");
+            self.translate_top_call_stack()?;
+        }
+        Ok(())
+    }
+
+    /// Spawns a thread at program start for every function in the `also_run` option,
+    /// e.g. a callback invoked by foreign code, which has no visible call to `std::thread::spawn`.
+    /// A transition moves the token from the program start place to a new place where the translation starts
+    /// and to the start place of every thread. The threads are never joined.
+    /// Nothing is known about the arguments of the functions, so their memory starts empty.
+    /// Returns the place where the translation of the entry function or the exported functions starts.
+    fn spawn_additional_entry_points(&mut self) -> PlaceRef {
+        if self.options.also_run.is_empty() {
+            return self.program_start.clone();
+        }
+        let start_place = self.net.add_place(&also_run_place_label());
+        let spawn_transition = connect_places(
+            &mut self.net,
+            &self.program_start,
+            &start_place,
+            &also_run_transition_label(),
+        );
+        for path in self.options.also_run.clone() {
+            let function_id = function_by_path(&path, self.tcx).expect(
+                "BUG: The functions to run at program start should have been found by `check_entry_points`",
+            );
+            let index = self.thread_count;
+            self.thread_count += 1;
+            let thread = Rc::new(sync::thread::Thread::new(
+                spawn_transition.clone(),
+                None,
+                function_id,
+                Vec::new(),
+                index,
+            ));
+            let span = function_id.span(self.tcx).unwrap_or_default();
+            self.registry.add(SyncKind::Thread, &thread.label(), span);
+            self.registry.add_note(
+                &thread.label(),
+                &format!("Runs `{path}` from the program start, see the `also_run` option"),
+            );
+            self.threads.push_back(thread);
+            info!("Spawned thread {index} running `{path}` at program start");
+        }
+        start_place
+    }
+
+    /// Main translation loop for the threads.
+    /// Iterate over the threads found and translate them.
+    /// If sync variables were passed to the thread, move them to the memory of the thread function.
+    /// Replaces the program panic place with the thread's end place
+    /// since abnormal thread termination does not affect the main thread.
+    ///
+    /// # Errors
+    ///
+    /// If the translation of a thread function stops, then the error is returned.
+    fn translate_threads(&mut self) -> Result<(), TranslationError> {
+        while let Some(thread) = self.threads.pop_front() {
+            let index = thread.index;
+
+            info!("Starting translating thread {}", index);
+            let thread_panic_place = self
+                .options
+                .propagate_thread_panics
+                .then(|| thread.add_panic_place(&mut self.net));
+            let (thread_function_id, thread_start_place, thread_end_place) =
+                thread.prepare_for_translation(&mut self.net);
+            // Replace the panic place so that unwind transitions and similar point to the thread's end place
+            // or to the thread's panic place if panics are propagated to `join`.
+            self.program_panic = thread_panic_place.unwrap_or_else(|| thread_end_place.clone());
+
+            self.push_function_to_call_stack(
+                thread_function_id,
+                thread_start_place,
+                thread_end_place,
+            );
+            info!("Pushed thread function to the translation call stack");
+
+            let new_function = self.call_stack.peek_mut();
+            info!("Moving sync variables to the thread function...");
+            thread.move_sync_variables(&mut new_function.memory, self.tcx);
+
+            let function = self.translate_top_call_stack()?;
+            if let Some(record) = &mut self.control_flow {
+                record.add_thread(index, function.instance);
+            }
+            info!("Finished translating thread {}", index);
+        }
+        Ok(())
+    }
+
+    /// Run the postprocessing tasks.
+    /// These tasks require knowledge of the whole Petri net.
+    /// For example: Adding arcs or places after all threads have been translated.
+    fn translation_postprocessing(&mut self) {
+        let mut mutexes: Vec<MutexRef> = Vec::new();
+        while let Some((task, _)) = self.postprocessing.pop() {
+            match task {
+                PostprocessingTask::LinkMutexToCondvar {
+                    index,
+                    start_place,
+                    end_place,
+                    wait_start,
+                    ..
+                } => {
+                    for mutex_ref in &mutexes {
+                        mutex_ref.link_to_condvar(
+                            index,
+                            &start_place,
+                            &end_place,
+                            &wait_start,
+                            &mut self.net,
+                        );
+                    }
+                }
+                PostprocessingTask::NewMutex { mutex_ref, .. } => {
+                    mutexes.push(mutex_ref);
+                }
+            }
+        }
+    }
+
+    /// Applies the property annotations in the comments of the source files of the crate to the net
+    /// and collects the suppressions of findings.
+    /// It must run after the translation since the annotations mark the transitions of the code that follows them.
+    fn apply_annotations(&mut self) {
+        for (filename, source_code) in local_source_files(self.tcx) {
+            let properties = apply_property_annotations(&source_code, &filename, &mut self.net);
+            if !properties.is_empty() {
+                info!(
+                    "Applied {} property annotations in {filename}",
+                    properties.len()
+                );
+            }
+            self.annotated_properties.extend(properties);
+            self.suppressions
+                .extend(parse_suppressions(&source_code, &filename));
+        }
+    }
+
+    /// Pushes a new function frame to the call stack.
+    /// The call stack is the preferred way to pass information between `Translator` methods.
+    fn push_function_to_call_stack(
+        &mut self,
+        function_id: FunctionId,
+        start_place: PlaceRef,
+        end_place: PlaceRef,
+    ) {
+        let function_name = function_id.name(self.tcx);
+        let function = MirFunction::new(function_id, function_name, start_place, end_place);
+        self.call_stack.push(function);
+    }
+
+    /// Main translation loop.
+    /// Translates the function from the top of the call stack.
+    /// Inside the MIR Visitor, when a call to another function happens, this method will be called again
+    /// to jump to the new function. Eventually a "leaf function" will be reached, the functions will exit and the
+    /// elements from the stack will be popped in order.
+    ///
+    /// Returns the translated function, whose memory contains the values linked to its return place.
+    ///
+    /// # Errors
+    ///
+    /// If the translation of the function or of a function called by it stops, then the error is returned.
+    fn translate_top_call_stack(&mut self) -> Result<MirFunction<'tcx>, TranslationError> {
+        let function = self.call_stack.peek();
+        let function_id = function.id;
+        self.translation_order.push(function.name.clone());
+        if let Some(record) = &mut self.control_flow {
+            let instance = record.add_instance(function);
+            self.call_stack.peek_mut().instance = instance;
+        }
+        if let Some(record) = &mut self.block_mapping {
+            record.start_function(&self.call_stack.peek().name, &self.net);
+        }
+        self.record_coverage(function_id, FunctionStatus::Translated);
+        if self.profiler.is_enabled() {
+            let function_name = self.classifier.classify(function_id, self.tcx).name;
+            self.profiler.enter(&function_name);
+        }
+        // Obtain the MIR representation of the function.
+        self.profiler.enter(MIR_QUERY_FRAME);
+        let body = function_id.body(self.tcx);
+        self.profiler.exit();
+        // Visit the MIR body of the function using the methods of `rustc_middle::mir::visit::Visitor`.
+        // <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/visit/trait.Visitor.html>
+        self.profiler.enter(VISIT_FRAME);
+        self.visit_body(body);
+        self.profiler.exit();
+        // Finished processing this function.
+        let function = self
+            .call_stack
+            .pop()
+            .expect("BUG: The translated function should be on the call stack");
+        if let Some(record) = &mut self.control_flow {
+            record.finish_instance(&function);
+        }
+        if let Some(record) = &mut self.block_mapping {
+            record.finish_function(&self.net);
+        }
+        self.profiler.exit();
+        match self.failure.take() {
+            Some(error) => Err(error),
+            None => Ok(function),
+        }
+    }
+
+    /// Jumps from the current function on the top of the stack
+    /// to a new function called inside the current function.
+    ///
+    /// Translates functions in a shortened way in the following cases:
+    /// - Foreign functions i.e., linked via extern { ... }).
+    /// - Functions that do not have a MIR representation.
+    /// - Functions that do not return (diverging functions).
+    /// - Functions that represent a `panic` i.e., functions that starts an unwind of the stack.
+    /// - Functions for mutexes: `std::sync::Mutex::new` and `std::sync::Mutex::lock`.
+    /// - Functions for read-write locks: `std::sync::RwLock::new`, `std::sync::RwLock::read` and `std::sync::RwLock::write`.
+    /// - Functions for threads: `std::thread::spawn` and `std::thread::JoinHandle::<T>::join`.
+    /// - Functions for condition variables: `std::sync::Condvar::new`, `std::sync::Condvar::wait`,
+    ///   `std::sync::Condvar::notify_one` and `std::sync::Condvar::notify_all`.
+    /// - Functions for barriers: `std::sync::Barrier::new` and `std::sync::Barrier::wait`.
+    /// - Functions for one-time initialization: `std::sync::Once::new`, `std::sync::Once::call_once`,
+    ///   `std::sync::OnceLock::new` and `std::sync::OnceLock::get_or_init`.
+    /// - Functions for channels: `std::sync::mpsc::channel`, `std::sync::mpsc::sync_channel`,
+    ///   `std::sync::mpsc::Sender::send`, `std::sync::mpsc::SyncSender::send` and `std::sync::mpsc::Receiver::recv`.
+    /// - Functions from the Rust standard library or the Rust core library.
+    ///
+    /// This is the handler for the enum variant `TerminatorKind::Call` in the MIR Visitor.
+    /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/enum.TerminatorKind.html#variant.Call>
+    ///
+    /// # Errors
+    ///
+    /// If the call uses an unsupported feature or the translation of the called function stops,
+    /// then the error is returned.
+    fn call_function(
+        &mut self,
+        func: &Operand<'tcx>,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        target: Option<BasicBlock>,
+        unwind: UnwindAction,
+        span: Span,
+    ) -> Result<(), TranslationError> {
+        let current_function = self.call_stack.peek_mut();
+        let function_id = extract_function_id_from_operand(func, current_function.id, self.tcx)?;
+        let generic_args = extract_generic_args_from_operand(func, current_function.id, self.tcx);
+        // A call to a trait method through the generic parameters of the caller
+        // is a call to the method of the implementation for the generic arguments of the caller.
+        let (function_id, generic_args) = resolve_call(
+            function_id,
+            generic_args,
+            current_function.generic_args,
+            self.tcx,
+        );
+        let function = self.classifier.classify(function_id, self.tcx);
+        // A call to a closure through the `Fn*` traits is a call to the body of the closure, if it is known.
+        let (function_id, function, generic_args) = match closure::called_closure(
+            &function.name,
+            args,
+            generic_args,
+            &current_function.memory,
+        ) {
+            Some(closure_id) => (
+                closure_id,
+                self.classifier.classify(closure_id, self.tcx),
+                List::empty(),
+            ),
+            None => (function_id, function, generic_args),
+        };
+        let function_name = function.name.clone();
+        let start_place = current_function.get_start_place_for_function_call();
+        info!("Encountered function call: {function_name}");
+        if unsafe_escape::is_pointer_copy_function(&function_name) {
+            let places: Vec<Place> = args
+                .iter()
+                .filter_map(|argument| match argument_operand(argument) {
+                    Operand::Copy(place) | Operand::Move(place) => Some(*place),
+                    Operand::Constant(_) => None,
+                })
+                .collect();
+            unsafe_escape::mark_unknown_aliasing(
+                &function_name,
+                &places,
+                &current_function.memory,
+                &mut self.registry,
+                current_function.id,
+                span,
+                self.tcx,
+            );
+        }
+
+        // Depending on whether a return or a unwind for the function are present,
+        // we have different possibilities for the function call end place and the (optional) cleanup place.
+        let places = match (target, unwind) {
+            (Some(return_block), UnwindAction::Continue) => {
+                // MIR function or foreign function calls without a cleanup block.
+                let end_place = current_function
+                    .get_end_place_for_function_call(return_block.into(), &mut self.net);
+                Places::Basic {
+                    start_place,
+                    end_place,
+                }
+            }
+            (Some(return_block), UnwindAction::Cleanup(cleanup_block)) => {
+                // The usual foreign function call case.
+                let end_place = current_function
+                    .get_end_place_for_function_call(return_block.into(), &mut self.net);
+                let cleanup_place = current_function
+                    .get_end_place_for_function_call(cleanup_block.into(), &mut self.net);
+                Places::WithCleanup {
+                    start_place,
+                    end_place,
+                    cleanup_place,
+                }
+            }
+            (Some(return_block), UnwindAction::Terminate(..)) => {
+                // Specific foreign function calls that terminate the program (abort).
+                let end_place = current_function
+                    .get_end_place_for_function_call(return_block.into(), &mut self.net);
+                // Connect cleanup to panic place
+                Places::WithCleanup {
+                    start_place,
+                    end_place,
+                    cleanup_place: self.program_panic.clone(),
+                }
+            }
+            (None, UnwindAction::Terminate(..)) => {
+                // Foreign function calls that simply terminate the program.
+                Places::Basic {
+                    start_place,
+                    end_place: self.program_panic.clone(),
+                }
+            }
+            (None, UnwindAction::Cleanup(cleanup_block)) => {
+                // A very special case seen in functions like `std::process::exit`
+                // where the return block is actually expressed as a cleanup.
+                // This needs to be modelled differently than a diverging function.
+                let end_place = current_function
+                    .get_end_place_for_function_call(cleanup_block.into(), &mut self.net);
+                Places::Basic {
+                    start_place,
+                    end_place,
+                }
+            }
+            (None, UnwindAction::Continue) => {
+                // Call to a function which does not return (Return type: -> !).
+                // Non-recursive call for the translation process.
+                // `panic!`-related functions are a special case of this.
+                if function.class == FunctionClass::Panic {
+                    call_panic_function(
+                        &start_place,
+                        &self.program_panic,
+                        &current_function.name,
+                        &mut self.net,
+                    );
+                } else {
+                    call_diverging_function(&start_place, &function_name, &mut self.net);
+                    self.record_coverage(function_id, FunctionStatus::Abstracted);
+                }
+                return Ok(());
+            }
+            (Some(return_block), UnwindAction::Unreachable) => {
+                // Support the unreachable case simply by matching the cleanup place to the program end place.
+                // This is a compromise solution to avoid polluting the panic state with these extraneous states
+                // that are actually not reachable during execution.
+                let end_place = current_function
+                    .get_end_place_for_function_call(return_block.into(), &mut self.net);
+                // Connect cleanup to program end place.
+                Places::WithCleanup {
+                    start_place,
+                    end_place,
+                    cleanup_place: self.program_end.clone(),
+                }
+            }
+            (None, UnwindAction::Unreachable) => {
+                // Support the unreachable case simply by matching the cleanup place to the program end place.
+                // This is a compromise solution to avoid polluting the panic state with these extraneous states
+                // that are actually not reachable during execution.
+                Places::Basic {
+                    start_place,
+                    end_place: self.program_end.clone(),
+                }
+            }
+        };
+
+        // Self-recursive call in tail position: The function loops via recursion.
+        let is_recursive_tail_call = function_id == current_function.id
+            && target.is_some_and(|return_block| {
+                is_return_block(return_block, function_id.body(self.tcx))
+            });
+        if is_recursive_tail_call && self.options.recursion_as_loop {
+            let index = self.function_counter.get_count(&function_name);
+            let (start_place, _) = places.get_start_end_place();
+            call_recursive_function_as_loop(
+                &start_place,
+                &current_function.start_place,
+                &function_name,
+                index,
+                &mut self.net,
+            );
+            info!("Modelled the recursive tail call to {function_name} as a loop");
+            self.function_counter.increment(&function_name);
+            self.record_call(CallRecord::Loop);
+            return Ok(());
+        }
+        if is_recursive_tail_call {
+            warn!(
+                "The function {function_name} loops via tail recursion: Use the option `--recursion-as-loop` to model it as a loop"
+            );
+        }
+
+        self.profiler.enter(NET_INSERTION_FRAME);
+        self.start_function_call(
+            function_id,
+            &function,
+            generic_args,
+            args,
+            destination,
+            places,
+            span,
+        )?;
+        self.profiler.exit();
+        self.function_counter.increment(&function_name);
+        Ok(())
+    }
+
+    /// Starts the corresponding handler for the function call.
+    /// Checks if the function is a marker of a nondeterministic choice,
+    /// then if the function is one of the
+    /// supported synchronization or multithreading functions,
+    /// then if the function is modelled by a plugin,
+    /// then if the function is a foreign function call,
+    /// then if the function is skipped through the `skipped_functions` option,
+    /// then if the function is already being translated (recursion),
+    /// then if the maximum call depth was reached,
+    /// then if the translation was interrupted and
+    /// lastly handle the standard MIR function case.
+    ///
+    /// # Errors
+    ///
+    /// If the call uses an unsupported feature or the translation of the called function stops,
+    /// then the error is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_function_call(
+        &mut self,
+        function_id: FunctionId,
+        function: &ClassifiedFunction,
+        generic_args: GenericArgs<'tcx>,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+        span: Span,
+    ) -> Result<(), TranslationError> {
+        let function_name = function.name.as_str();
+        // Special cases
+        if function_name == "std::mem::drop" {
+            self.call_mem_drop(function_name, args, destination, places);
+            return Ok(());
+        }
+        if (function_name == "std::ops::Deref::deref"
+            || function_name == "std::ops::DerefMut::deref_mut")
+            && self.is_self_ref_mutex(function_name, args)
+        {
+            self.call_deref_mutex(function_name, args, destination, places);
+            return Ok(());
+        }
+        if (function_name == "std::ops::Deref::deref"
+            || function_name == "std::ops::DerefMut::deref_mut")
+            && self.is_self_ref_barrier(function_name, args)
+        {
+            self.call_deref_barrier(function_name, args, destination, places);
+            return Ok(());
+        }
+        if function_name == "std::result::Result::<T, E>::unwrap"
+            && (self.is_self_ref_mutex(function_name, args)
+                || self.is_self_ref_rwlock(function_name, args))
+        {
+            self.call_unwrap_mutex(function_name, args, destination, places);
+            return Ok(());
+        }
+        if function_name == "std::thread::spawn" {
+            self.call_thread_spawn(function_name, args, destination, places, span)?;
+            return Ok(());
+        }
+        if function_name == "std::sync::Barrier::new" {
+            self.call_barrier_new(function_name, args, destination, places, span)?;
+            return Ok(());
+        }
+        if function_name == "std::sync::mpsc::sync_channel" {
+            self.call_sync_channel(function_name, args, destination, places, span)?;
+            return Ok(());
+        }
+        if function_name == "std::sync::mpsc::channel" {
+            if let Some(capacity) = self.options.channel_bound {
+                let index = self.call_bounded_channel(
+                    function_name,
+                    capacity,
+                    args,
+                    destination,
+                    places,
+                    span,
+                );
+                // The program never blocks on the free slots of an unbounded channel.
+                self.bounded_places.push(free_slots_place_label(index));
+                return Ok(());
+            }
+        }
+        if function_name == "std::sync::Condvar::new" {
+            self.call_condvar_new(function_name, args, destination, places, span);
+            return Ok(());
+        }
+        // Marker of a nondeterministic choice: Abstract the function call
+        if nondet::is_nondet_function(function_name, generic_args) {
+            self.record_coverage(function_id, FunctionStatus::Abstracted);
+            self.call_nondet_function(function_name, destination, places);
+            return Ok(());
+        }
+        // Sync or multithreading function
+        if function.class == FunctionClass::Sync {
+            // Index for transition and place labels
+            let index = self.function_counter.get_count(function_name);
+            // A reference to the memory of the current function
+            let current_function = self.call_stack.peek_mut();
+            let memory = &mut current_function.memory;
+            // A reference to the Petri net to add transitions and places
+            let net = &mut self.net;
+            if let Some(task) =
+                sync::call_function(function_name, index, args, destination, places, net, memory)?
+            {
+                // No task is removed before the postprocessing starts,
+                // so the length is the number of tasks created so far.
+                let sequence_number = self.postprocessing.len();
+                self.postprocessing.push((task, Reverse(sequence_number)));
+            }
+            self.register_sync_call(function_name, args, destination, span);
+            self.record_sync_effect(function_name, args);
+            return Ok(());
+        }
+        // Function modelled by a plugin
+        if let Some(plugin) = self.plugin_calls.get_constructor(function_name) {
+            let index = self.function_counter.get_count(function_name);
+            let memory = &mut self.call_stack.peek_mut().memory;
+            call_constructor(
+                function_name,
+                plugin,
+                index,
+                destination,
+                places,
+                &mut self.net,
+                memory,
+            );
+            info!("Modelled the call to {function_name} with a plugin");
+            return Ok(());
+        }
+        if let Some(function) = self.plugin_calls.get_function(function_name) {
+            let index = self.function_counter.get_count(function_name);
+            let memory = &self.call_stack.peek().memory;
+            call_plugin_function(function, index, args, places, &mut self.net, memory);
+            info!("Modelled the call to {function_name} with a plugin");
+            return Ok(());
+        }
+        // Default case for standard and core library calls.
+        // Panic functions that return, e.g. with an unwind action, are also abstracted.
+        if matches!(
+            function.class,
+            FunctionClass::Foreign | FunctionClass::Panic
+        ) {
+            self.record_coverage(function_id, FunctionStatus::Abstracted);
+            self.call_foreign_function(function_name, args, destination, places);
+            return Ok(());
+        }
+        // Skipped function: Abstract the function call
+        if self
+            .options
+            .skipped_functions
+            .iter()
+            .any(|skipped| skipped == function_name)
+        {
+            warn!("The function {function_name} is skipped: The call is modelled as a foreign function call");
+            self.record_coverage(function_id, FunctionStatus::Abstracted);
+            self.coverage
+                .record_skipped(function_name, "listed in the skipped functions");
+            self.call_foreign_function(function_name, args, destination, places);
+            return Ok(());
+        }
+        // Recursive call: Abstract the back edge of the cycle in the call graph
+        if self
+            .call_stack
+            .iter()
+            .any(|function| function.id == function_id)
+        {
+            warn!(
+                "Recursive call to {function_name} detected: The call is modelled as a single transition"
+            );
+            let index = self.function_counter.get_count(function_name);
+            call_recursive_function(function_name, index, places, &mut self.net);
+            return Ok(());
+        }
+        // The maximum call depth was reached: Abstract the function call
+        if self.call_stack.len() >= self.options.max_call_depth {
+            warn!(
+                "Maximum call depth of {} reached: The call to {function_name} is modelled as a foreign function call",
+                self.options.max_call_depth
+            );
+            self.record_coverage(function_id, FunctionStatus::Abstracted);
+            self.call_foreign_function(function_name, args, destination, places);
+            return Ok(());
+        }
+        // The translation was interrupted: Abstract the function call to finish quickly
+        if interruption_requested() {
+            debug!("The translation was interrupted: The call to {function_name} is modelled as a foreign function call");
+            self.record_coverage(function_id, FunctionStatus::Abstracted);
+            self.call_foreign_function(function_name, args, destination, places);
+            return Ok(());
+        }
+        // Default case: A function with MIR representation
+        self.call_mir_function(
+            function_id,
+            function_name,
+            generic_args,
+            args,
+            destination,
+            places,
+        )
+    }
+
+    /// Registers a call to one of the supported synchronization functions in the `SyncRegistry`.
+    /// Functions that create a new synchronization primitive register it with the span of the call.
+    /// Functions that operate on an existing synchronization primitive are added as its call sites.
+    ///
+    /// It must be called after the synchronization function was translated,
+    /// so that the memory already contains the primitives linked to the arguments and return value.
+    fn register_sync_call(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        span: Span,
+    ) {
+        let span = span_info(span, self.tcx);
+        let function = self.call_stack.peek();
+        let memory = &function.memory;
+        let self_ref = || {
+            extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+                panic!("BUG: `{function_name}` should receive the self reference as a place")
+            })
+        };
+
+        if let Some(lock_function) = LockFunction::from_name(function_name) {
+            match lock_function {
+                LockFunction::MutexNew => {
+                    let mutex_ref = memory.get_mutex(&destination);
+                    self.registry.add(SyncKind::Mutex, &mutex_ref.label(), span);
+                }
+                LockFunction::RwLockNew => {
+                    let rwlock_ref = memory.get_rwlock(&destination);
+                    self.registry
+                        .add(SyncKind::RwLock, &rwlock_ref.label(), span);
+                }
+                LockFunction::MutexLock => {
+                    let mutex_ref = memory.get_mutex(&self_ref());
+                    self.registry.add_call_site(
+                        &mutex_ref.label(),
+                        function_name,
+                        &function.name,
+                        span,
+                    );
+                }
+                LockFunction::RwLockRead | LockFunction::RwLockWrite => {
+                    let rwlock_ref = memory.get_rwlock(&self_ref());
+                    self.registry.add_call_site(
+                        &rwlock_ref.label(),
+                        function_name,
+                        &function.name,
+                        span,
+                    );
+                }
+            }
+            return;
+        }
+        match function_name {
+            "std::sync::Barrier::new" => {
+                let barrier_ref = memory.get_barrier(&destination);
+                self.registry
+                    .add(SyncKind::Barrier, barrier_ref.label(), span);
+            }
+            "std::sync::Once::new" | "std::sync::OnceLock::<T>::new" => {
+                let once_ref = memory.get_once(&destination);
+                self.registry.add(SyncKind::Once, once_ref.label(), span);
+            }
+            "std::sync::atomic::AtomicBool::new" | "std::sync::atomic::AtomicUsize::new" => {
+                let atomic_ref = memory.get_atomic(&destination);
+                self.registry
+                    .add(SyncKind::Atomic, atomic_ref.label(), span);
+            }
+            "std::sync::Condvar::new" => {
+                let condvar_ref = memory.get_condvar(&destination);
+                self.registry
+                    .add(SyncKind::Condvar, condvar_ref.label(), span);
+            }
+            "std::sync::mpsc::channel" | "std::sync::mpsc::sync_channel" => {
+                let channel_ref = memory.get_channel(&destination);
+                self.registry
+                    .add(SyncKind::Channel, channel_ref.label(), span);
+            }
+            "std::sync::Condvar::notify_one" | "std::sync::Condvar::notify_all" => {
+                let condvar_ref = memory.get_condvar(&self_ref());
+                self.registry.add_call_site(
+                    condvar_ref.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            "std::sync::Condvar::wait" | "std::sync::Condvar::wait_while" => {
+                let condvar_ref = memory.get_condvar(&self_ref());
+                self.registry.add_call_site(
+                    condvar_ref.label(),
+                    function_name,
+                    &function.name,
+                    span.clone(),
+                );
+                // The mutex is unlocked and locked again by the wait.
+                let mutex_guard = extract_nth_argument_as_place(args, 1).unwrap_or_else(|| {
+                    panic!("BUG: `{function_name}` should receive the first argument as a place")
+                });
+                let mutex_guard_ref = memory.get_mutex_guard(&mutex_guard);
+                self.registry.add_call_site(
+                    &mutex_guard_ref.mutex.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            "std::sync::Once::call_once" | "std::sync::OnceLock::<T>::get_or_init" => {
+                let once_ref = memory.get_once(&self_ref());
+                self.registry
+                    .add_call_site(once_ref.label(), function_name, &function.name, span);
+            }
+            "std::sync::atomic::AtomicBool::load"
+            | "std::sync::atomic::AtomicBool::store"
+            | "std::sync::atomic::AtomicBool::compare_exchange"
+            | "std::sync::atomic::AtomicUsize::load"
+            | "std::sync::atomic::AtomicUsize::store"
+            | "std::sync::atomic::AtomicUsize::compare_exchange" => {
+                let atomic_ref = memory.get_atomic(&self_ref());
+                self.registry.add_call_site(
+                    atomic_ref.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            "std::sync::Barrier::wait" => {
+                let barrier_ref = memory.get_barrier(&self_ref());
+                self.registry.add_call_site(
+                    barrier_ref.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            "std::sync::mpsc::Sender::<T>::send" | "std::sync::mpsc::SyncSender::<T>::send" => {
+                let channel_ref = memory.get_sender(&self_ref());
+                self.registry.add_call_site(
+                    channel_ref.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            "std::sync::mpsc::Receiver::<T>::recv" => {
+                let channel_ref = memory.get_receiver(&self_ref());
+                self.registry.add_call_site(
+                    channel_ref.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            "std::thread::JoinHandle::<T>::join" => {
+                let thread_ref = memory.get_join_handle(&self_ref());
+                self.registry.add_call_site(
+                    &thread_ref.label(),
+                    function_name,
+                    &function.name,
+                    span,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Records in the `Coverage` how the function was handled by the translation.
+    /// Functions that are not defined in the crate being translated are ignored.
+    fn record_coverage(&mut self, function_id: FunctionId, status: FunctionStatus) {
+        let (Some(module_name), Some(span)) = (
+            function_id.module_name(self.tcx),
+            function_id.span(self.tcx),
+        ) else {
+            return;
+        };
+        self.coverage
+            .record(&function_id.name(self.tcx), &module_name, span, status);
+    }
+
+    /// Records for the cross-check how the call in the active block of the function
+    /// on the top of the call stack was translated.
+    fn record_call(&mut self, call: CallRecord) {
+        let function = self.call_stack.peek();
+        if let (Some(record), Some(block)) = (&mut self.control_flow, function.active_block()) {
+            record.add_call(function.instance, block.index(), call);
+        }
+    }
+
+    /// Records for the cross-check the effect of the call to a synchronization function
+    /// in the active block of the function on the top of the call stack, see `SyncEffect`.
+    /// The calls that never block, e.g. `std::sync::Condvar::notify_one`, have no effect.
+    fn record_sync_effect(&mut self, function_name: &str, args: &CallArgs<'tcx>) {
+        let function = self.call_stack.peek();
+        let (Some(record), Some(block)) = (&mut self.control_flow, function.active_block()) else {
+            return;
+        };
+        let memory = &function.memory;
+        let self_ref = || {
+            extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+                panic!("BUG: `{function_name}` should receive the self reference as a place")
+            })
+        };
+        let effect = match (LockFunction::from_name(function_name), function_name) {
+            (Some(LockFunction::MutexLock), _) => {
+                SyncEffect::Lock(memory.get_mutex(&self_ref()).label())
+            }
+            (Some(LockFunction::RwLockRead | LockFunction::RwLockWrite), _) => {
+                SyncEffect::Untracked(memory.get_rwlock(&self_ref()).label())
+            }
+            (_, "std::thread::JoinHandle::<T>::join") => {
+                SyncEffect::Join(memory.get_join_handle(&self_ref()).index)
+            }
+            (_, "std::sync::Condvar::wait" | "std::sync::Condvar::wait_while") => {
+                let mutex_guard = extract_nth_argument_as_place(args, 1).unwrap_or_else(|| {
+                    panic!("BUG: `{function_name}` should receive the first argument as a place")
+                });
+                SyncEffect::Wait {
+                    condvar: memory.get_condvar(&self_ref()).label().to_string(),
+                    mutex: memory.get_mutex_guard(&mutex_guard).mutex.label(),
+                }
+            }
+            (_, "std::sync::Barrier::wait") => {
+                SyncEffect::Untracked(memory.get_barrier(&self_ref()).label().to_string())
+            }
+            (_, "std::sync::Once::call_once" | "std::sync::OnceLock::<T>::get_or_init") => {
+                SyncEffect::Untracked(memory.get_once(&self_ref()).label().to_string())
+            }
+            (
+                _,
+                "std::sync::mpsc::Sender::<T>::send" | "std::sync::mpsc::SyncSender::<T>::send",
+            ) => SyncEffect::Untracked(memory.get_sender(&self_ref()).label().to_string()),
+            (_, "std::sync::mpsc::Receiver::<T>::recv") => {
+                SyncEffect::Untracked(memory.get_receiver(&self_ref()).label().to_string())
+            }
+            _ => return,
+        };
+        record.add_sync_effect(function.instance, block.index(), effect);
+    }
+
+    /// Records for the cross-check that the drop in the active block of the function
+    /// on the top of the call stack releases the mutex of every guard in the place.
+    fn record_guard_drops(&mut self, place: Place<'tcx>) {
+        let function = self.call_stack.peek();
+        let (Some(record), Some(block)) = (&mut self.control_flow, function.active_block()) else {
+            return;
+        };
+        for mutex_guard_ref in function.memory.get_mutex_guards(&place) {
+            record.add_sync_effect(
+                function.instance,
+                block.index(),
+                SyncEffect::Unlock(mutex_guard_ref.mutex.label()),
+            );
+        }
+    }
+
+    /// Checks whether the first argument (the self reference) is a mutex or a mutex guard.
+    fn is_self_ref_mutex(&self, function_name: &str, args: &CallArgs<'tcx>) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let function = self.call_stack.peek();
+        check_lock_type_in_place(&self_ref, "MutexGuard", function.id, self.tcx)
+            || check_lock_type_in_place(&self_ref, "Mutex", function.id, self.tcx)
+    }
+
+    /// Checks whether the first argument (the self reference) is a barrier, e.g. an `Arc<Barrier>`.
+    fn is_self_ref_barrier(&self, function_name: &str, args: &CallArgs<'tcx>) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let function = self.call_stack.peek();
+        check_substring_in_place_type(&self_ref, "std::sync::Barrier", function.id, self.tcx)
+    }
+
+    /// Checks whether the first argument (the self reference) is a guard of a read-write lock.
+    fn is_self_ref_rwlock(&self, function_name: &str, args: &CallArgs<'tcx>) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let function = self.call_stack.peek();
+        check_lock_type_in_place(&self_ref, "RwLockReadGuard", function.id, self.tcx)
+            || check_lock_type_in_place(&self_ref, "RwLockWriteGuard", function.id, self.tcx)
+    }
+
+    /// Call to a MIR function. It is the default for user-defined functions in the code.
+    /// It is a recursive call for the translation process.
+    ///
+    /// A separate counter is incremented every time that
+    /// the function is called to generate a unique label.
+    ///
+    /// The values linked to the arguments are passed to the memory of the function,
+    /// including the sync variables captured by a closure.
+    /// The value linked to the return place, e.g. a sync variable or a closure, is passed back to the destination.
+    ///
+    /// # Errors
+    ///
+    /// If the translation of the function stops, then the error is returned attributed to the function.
+    fn call_mir_function(
+        &mut self,
+        function_id: FunctionId,
+        function_name: &str,
+        generic_args: GenericArgs<'tcx>,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+    ) -> Result<(), TranslationError> {
+        let index = self.function_counter.get_count(function_name);
+        // The values linked to the arguments, e.g. the aggregate of a `struct` passed as `&self`.
+        let caller_memory = &self.call_stack.peek().memory;
+        let mut arguments: Vec<Option<Value>> = args
+            .iter()
+            .map(|argument| match argument_operand(argument) {
+                Operand::Copy(place) | Operand::Move(place) => caller_memory.copy_value(place),
+                Operand::Constant(_) => None,
+            })
+            .collect();
+        let environment_locals = if self.options.environment_branches {
+            environment::environment_argument_locals(args, function_id, caller_memory, self.tcx)
+        } else {
+            Vec::new()
+        };
+        // A closure receives its arguments in a tuple after the closure itself.
+        // The fields of the tuple are the locals after the closure: `_2`, `_3`, etc.
+        if function_id.is_closure(self.tcx) && arguments.len() == 2 {
+            if let Some(Some(Value::Aggregate(values))) = arguments.pop() {
+                let fields = values
+                    .into_iter()
+                    .map(|value| (value != Value::Untracked).then_some(value));
+                arguments.extend(fields);
+            }
+        }
+
+        // The unwinding of the function continues in the cleanup block of the call, if there is one.
+        // The function can only unwind where its MIR unwinds, e.g. not while it waits for a lock.
+        let (start_place, end_place, cleanup_place) = match places {
+            Places::WithCleanup {
+                start_place,
+                end_place,
+                cleanup_place,
+            } => (start_place, end_place, Some(cleanup_place)),
+            Places::Basic {
+                start_place,
+                end_place,
+            } => (start_place, end_place, None),
+        };
+        self.call_stack.push(MirFunction::new(
+            function_id,
+            indexed_mir_function_name(function_name, index),
+            start_place,
+            end_place,
+        ));
+        info!("Pushed function {function_name} to the translation call stack");
+        let function = self.call_stack.peek_mut();
+        function.cleanup_place = cleanup_place;
+        function.generic_args = generic_args;
+        let memory = &mut function.memory;
+        for (argument_index, value) in arguments.into_iter().enumerate() {
+            if let Some(value) = value {
+                // The arguments are the locals after the return place: `_1`, `_2`, etc.
+                let argument = Place::from(Local::from_usize(argument_index + 1));
+                memory.link_value(argument, value);
+            }
+        }
+        for local in environment_locals {
+            memory.mark_environment(Place::from(local));
+        }
+        if function_id.is_closure(self.tcx) {
+            closure::link_captured_variables(function_id, memory, self.tcx);
+        }
+        // The translation of the function is not part of the net insertion of the call.
+        self.profiler.exit();
+        let function = self
+            .translate_top_call_stack()
+            .map_err(|error| error.in_callee(function_name.to_string()))?;
+        self.profiler.enter(NET_INSERTION_FRAME);
+        self.record_call(CallRecord::Function(function.instance));
+
+        // The return value may contain sync variables or a closure created in the function.
+        let return_place = Place::from(RETURN_PLACE);
+        let memory = &mut self.call_stack.peek_mut().memory;
+        if let Some(value) = function.memory.copy_value(&return_place) {
+            memory.link_value(destination, value);
+        }
+        if let Some(closure_id) = function.memory.get_closure(&return_place) {
+            memory.link_closure(destination, closure_id);
+            info!("The function {function_name} returned the closure {closure_id:?}");
+        }
+        if function.memory.depends_on_environment(&return_place) {
+            memory.mark_environment(destination);
+        }
+        Ok(())
+    }
+
+    /// Call to a foreign function. It is the default for standard and core library calls.
+    /// It is a non-recursive call for the translation process.
+    /// It is also reused by other handlers since this is the basic case.
+    ///
+    /// Translates a call to a function with given function name using
+    /// the same representation as in `special_function::call_foreign_function`.
+    ///
+    /// A separate counter is incremented every time that
+    /// the function is called to generate a unique label.
+    ///
+    /// Performs a check to keep track of synchronization primitives.
+    /// In case the first argument is a mutex, mutex guard, join handle or condition variable,
+    /// it links the first argument of the function to its return value.
+    ///
+    /// Returns the transitions representing the function call.
+    fn call_foreign_function(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+    ) -> Transitions {
+        let index = self.function_counter.get_count(function_name);
+        let transitions = call_foreign_function(function_name, index, places, &mut self.net);
+
+        let current_function = self.call_stack.peek_mut();
+        sync::link_return_value_if_sync_variable(
+            args,
+            destination,
+            &mut current_function.memory,
+            current_function.id,
+            self.tcx,
+        );
+        closure::link_return_value_if_closure(args, destination, &mut current_function.memory);
+        if self.options.environment_branches {
+            environment::handle_foreign_call(
+                function_name,
+                args,
+                destination,
+                &mut current_function.memory,
+            );
+        }
+
+        transitions
+    }
+
+    /// Call to a marker of a nondeterministic choice, see `nondet`.
+    /// Non-recursive call for the translation process.
+    /// The call is modelled like a foreign function call and its result is linked to a nondeterministic choice.
+    fn call_nondet_function(
+        &mut self,
+        function_name: &str,
+        destination: Place<'tcx>,
+        places: Places,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let labels = nondet_call_transition_labels(function_name, index);
+        connect_call_places(&labels, places, &mut self.net);
+        self.call_stack.peek_mut().memory.link_nondet(destination);
+        info!("Modelled the call to {function_name} as a nondeterministic choice");
+    }
+
+    /// Call to `std::mem::drop`.
+    /// Non-recursive call for the translation process.
+    fn call_mem_drop(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+    ) {
+        let transitions = self.call_foreign_function(function_name, args, destination, places);
+
+        let dropped_place = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the value to be dropped as a place")
+        });
+
+        let function = self.call_stack.peek_mut();
+        let memory = &mut function.memory;
+        let net = &mut self.net;
+        match transitions {
+            Transitions::Basic { default } => {
+                mutex::handle_mutex_guard_drop(dropped_place, &default, net, memory);
+                rwlock::handle_rwlock_guard_drop(dropped_place, &default, net, memory);
+            }
+            Transitions::WithCleanup { default, cleanup } => {
+                mutex::handle_mutex_guard_drop(dropped_place, &default, net, memory);
+                mutex::handle_mutex_guard_drop(dropped_place, &cleanup, net, memory);
+                rwlock::handle_rwlock_guard_drop(dropped_place, &default, net, memory);
+                rwlock::handle_rwlock_guard_drop(dropped_place, &cleanup, net, memory);
+            }
+        }
+        self.record_guard_drops(dropped_place);
+    }
+
+    /// Call to `std::ops::Deref::deref` or `std::ops::DerefMut::deref_mut`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// If a mutex guard is dereferenced mutably, add the transition to set the mutex condition later.
+    ///
+    /// In some cases, the `std::ops::Deref::deref` or `std::ops::DerefMut::deref_mut` functions contain a cleanup target.
+    /// This target is not called in practice but creates trouble for lost signal detection.
+    /// The reason is that any call may fail, which is equivalent to saying that the dereference operation
+    /// was never present in the program, leading to a false lost signal.
+    /// In conclusion: Ignore the cleanup place, do not model it.
+    /// Assume `deref` and `deref_mut` never unwind when dereferencing a variable linked to a mutex or a mutex guard.
+    fn call_deref_mutex(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+    ) {
+        let places = places.ignore_cleanup_place();
+        let transitions = self.call_foreign_function(function_name, args, destination, places);
+        let transition = transitions.default();
+
+        if function_name == "std::ops::DerefMut::deref_mut" {
+            let reference = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+                panic!("BUG: `{function_name}` should receive a reference as a place")
+            });
+            let function = self.call_stack.peek_mut();
+            let mutex_guard_ref = function.memory.get_mutex_guard(&reference);
+            mutex_guard_ref.mutex.add_deref_mut_transition(transition);
+            info!("Encountered a mutable dereference of a mutex guard");
+        }
+    }
+
+    /// Call to `std::ops::Deref::deref` or `std::ops::DerefMut::deref_mut` on a barrier, e.g. an `Arc<Barrier>`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Like for a mutex, the cleanup target is not called in practice, but a thread that unwinds there
+    /// never reaches the barrier and the other threads wait forever, leading to a false deadlock.
+    /// In conclusion: Ignore the cleanup place, do not model it.
+    /// Assume `deref` and `deref_mut` never unwind when dereferencing a barrier.
+    fn call_deref_barrier(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+    ) {
+        let places = places.ignore_cleanup_place();
+        self.call_foreign_function(function_name, args, destination, places);
+    }
+
+    /// Call to `std::result::Result::<T, E>::unwrap`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// In some cases, the `std::result::Result::<T, E>::unwrap` contain a cleanup target.
+    /// This target is not called in practice but creates trouble for lost signal detection.
+    /// The reason is that any call may fail, which is equivalent to saying that the lock operation
+    /// was never present in the program, leading to a false lost signal.
+    /// In conclusion: Ignore the cleanup place, do not model it.
+    /// Assume `unwrap` never unwinds when applied to a variable linked to a mutex or a mutex guard.
+    ///
+    /// With the `model_poisoning` option, the cleanup place is modelled for the result of `lock`, `read` or `write`,
+    /// but the cleanup transition is only enabled if the lock is poisoned.
+    fn call_unwrap_mutex(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+    ) {
+        let lock_result = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        let memory = &self.call_stack.peek().memory;
+        if !self.options.model_poisoning
+            || (memory.get_mutex_guards(&lock_result).is_empty()
+                && memory.get_rwlock_guards(&lock_result).is_empty())
+        {
+            let places = places.ignore_cleanup_place();
+            self.call_foreign_function(function_name, args, destination, places);
+            return;
+        }
+        let transitions = self.call_foreign_function(function_name, args, destination, places);
+        if let Transitions::WithCleanup { cleanup, .. } = transitions {
+            let memory = &self.call_stack.peek().memory;
+            mutex::handle_poisoned_unwrap(&lock_result, &cleanup, &mut self.net, memory);
+            rwlock::handle_poisoned_unwrap(&lock_result, &cleanup, &mut self.net, memory);
+        }
+    }
+
+    /// Call to `std::sync::Barrier::new`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// It is not handled together with the other synchronization functions
+    /// because the number of threads must be extracted from the constant passed as the argument.
+    ///
+    /// # Errors
+    ///
+    /// If the number of threads is not a constant, then an error is returned.
+    fn call_barrier_new(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+        span: Span,
+    ) -> Result<(), TranslationError> {
+        let n = extract_nth_argument_as_usize(args, 0, self.tcx).ok_or_else(|| {
+            TranslationError::Unsupported(format!(
+                "`{function_name}` with a number of threads that is not a constant is not supported yet"
+            ))
+        })?;
+        let index = self.function_counter.get_count(function_name);
+        let memory = &mut self.call_stack.peek_mut().memory;
+        sync::barrier::call_new(
+            function_name,
+            index,
+            n,
+            destination,
+            places,
+            &mut self.net,
+            memory,
+        );
+        self.register_sync_call(function_name, args, destination, span);
+        Ok(())
+    }
+
+    /// Call to `std::sync::Condvar::new`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// It is not handled together with the other synchronization functions
+    /// because the model of the condition variable depends on the `condvar_wakeup` option.
+    fn call_condvar_new(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+        span: Span,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let memory = &mut self.call_stack.peek_mut().memory;
+        sync::condvar::call_new(
+            function_name,
+            index,
+            self.options.condvar_wakeup,
+            destination,
+            places,
+            &mut self.net,
+            memory,
+        );
+        self.register_sync_call(function_name, args, destination, span);
+    }
+
+    /// Call to `std::sync::mpsc::sync_channel`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Like `std::sync::Barrier::new`, it is not handled together with the other synchronization functions
+    /// because the capacity of the buffer must be extracted from the constant passed as the argument.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity is not a constant, then an error is returned.
+    fn call_sync_channel(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+        span: Span,
+    ) -> Result<(), TranslationError> {
+        let capacity = extract_nth_argument_as_usize(args, 0, self.tcx).ok_or_else(|| {
+            TranslationError::Unsupported(format!(
+                "`{function_name}` with a capacity that is not a constant is not supported yet"
+            ))
+        })?;
+        self.call_bounded_channel(function_name, capacity, args, destination, places, span);
+        Ok(())
+    }
+
+    /// Creates a bounded channel with the given capacity for a call to `std::sync::mpsc::sync_channel`
+    /// or to `std::sync::mpsc::channel` when the `channel_bound` option is set.
+    /// Returns the index of the new channel.
+    fn call_bounded_channel(
+        &mut self,
+        function_name: &str,
+        capacity: usize,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+        span: Span,
+    ) -> usize {
+        let index = self.function_counter.get_count(function_name);
+        let memory = &mut self.call_stack.peek_mut().memory;
+        sync::channel::call_sync_channel(
+            function_name,
+            index,
+            capacity,
+            destination,
+            places,
+            &mut self.net,
+            memory,
+        );
+        self.register_sync_call(function_name, args, destination, span);
+        index
+    }
+
+    /// Call to `std::thread::spawn`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// - Extracts the `FunctionId` of the called function.
+    /// - Extracts the closure for the thread.
+    /// - Gets the sync variables passed in to the closure.
+    /// - Bounds the number of instances of the thread if the call is inside a loop.
+    /// - Adds the thread to the `ThreadManager`.
+    /// - Links the return place to the `ThreadRef`.
+    ///
+    /// # Errors
+    ///
+    /// If the function to be run cannot be extracted, then an error is returned.
+    fn call_thread_spawn(
+        &mut self,
+        function_name: &str,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        places: Places,
+        span: Span,
+    ) -> Result<(), TranslationError> {
+        // The return value is linked to the join handle below, not to the closure passed as the argument,
+        // which is not linked to a value if it captures no sync variables.
+        let index = self.function_counter.get_count(function_name);
+        let transitions = call_foreign_function(function_name, index, places, &mut self.net);
+        let transition = transitions.default();
+
+        // Extract the definition ID of the thread function.
+        // A closure returned from a function or stored in a `Box` is known from the memory.
+        let current_function = self.call_stack.peek_mut();
+        let function_to_be_run = args.first().unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the function to be run")
+        });
+        let thread_function_id = match extract_closure(args)
+            .and_then(|place| current_function.memory.get_closure(&place))
+        {
+            Some(closure_id) => closure_id,
+            None => extract_function_id_from_operand(
+                argument_operand(function_to_be_run),
+                current_function.id,
+                self.tcx,
+            )?,
+        };
+
+        let index = self.thread_count;
+        self.thread_count += 1;
+        sync::thread::warn_untracked_captures(thread_function_id, index, self.tcx);
+        // A thread spawned in a loop may run several times concurrently. Bound the number of instances.
+        // If it also waits for work in a loop, it is the worker of a pool.
+        let spawned_in_loop = current_function
+            .active_block()
+            .is_some_and(|block| current_function.id.is_block_in_loop(block, self.tcx));
+        let worker_queue = spawned_in_loop
+            .then(|| sync::thread::worker_queue_function(thread_function_id, self.tcx))
+            .flatten();
+        let instances = match worker_queue {
+            Some(_) => self
+                .options
+                .worker_pool_size
+                .unwrap_or(self.options.thread_instances),
+            None => self.options.thread_instances,
+        };
+        let spawn_skip_transition = spawned_in_loop
+            .then(|| sync::thread::bound_instances(&transition, index, instances, &mut self.net));
+
+        let closure = extract_closure(args);
+        // The sync variables captured by the closure are aggregated together in a single value in memory
+        // Get this vector of values that should be re-mapped in the new thread's memory.
+        let memory = &mut current_function.memory;
+        // A closure that only captures other values, e.g. integers or pointers, passes no sync variables.
+        let aggregate = closure
+            .filter(|place| memory.is_linked(place))
+            .map_or_else(Vec::new, |place| memory.copy_aggregate(&place));
+
+        // Create a new thread
+        let thread = sync::thread::Thread::new(
+            transition,
+            spawn_skip_transition,
+            thread_function_id,
+            aggregate,
+            index,
+        );
+
+        // The return value contains a new join handle. Link the local variable to it.
+        let thread_ref = memory.link_join_handle(destination, thread);
+        debug!("NEW JOIN HANDLE: {destination:?}");
+
+        // Register the thread with the span of the call to `std::thread::spawn`
+        self.registry.add(
+            SyncKind::Thread,
+            &thread_ref.label(),
+            span_info(span, self.tcx),
+        );
+        if let Some(queue_function) = worker_queue {
+            info!("The thread {index} is the worker of a pool waiting on `{queue_function}`, see the `worker_pool_size` option");
+            self.registry.add_note(
+                &thread_ref.label(),
+                &sync::thread::worker_pool_note(&queue_function, instances),
+            );
+        }
+
+        // Add the thread to the translator
+        self.threads.push_back(thread_ref.clone());
+        info!("Found thread {index} and pushed it to the back of the thread translation queue");
+        self.record_call(CallRecord::Spawn(index));
+        Ok(())
+    }
+}
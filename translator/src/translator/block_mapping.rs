@@ -0,0 +1,112 @@
+//! Submodule that records the mapping between the basic blocks and the nodes of the net,
+//! see `cargo_check_deadlock_core::data_structures::block_mapping`.
+//!
+//! The nodes of a block are the ones added to the net between the activation of the block
+//! and the end of its terminator. The functions called in the terminator are translated in between,
+//! so the node counts before and after every function instance are recorded and its nodes are skipped.
+//! The start places of the other blocks of the function, which the terminator adds when it jumps to them,
+//! are left to their own block.
+
+use std::collections::HashMap;
+
+use crate::data_structures::petri_net_interface::{NodeCounts, PetriNet};
+use crate::data_structures::span_info::SpanInfo;
+use cargo_check_deadlock_core::data_structures::block_mapping::{
+    BlockMapping, BlockNodes, FunctionNodes,
+};
+
+/// The state of the net when the translation of a basic block started.
+pub struct BlockStart {
+    /// The node counts when the block was activated.
+    counts: NodeCounts,
+    /// The number of function instances in `BlockMappingRecord::nested` at that moment.
+    nested: usize,
+}
+
+/// The mapping of the function instances, recorded during the translation.
+#[derive(Default)]
+pub struct BlockMappingRecord {
+    /// The function instances being translated, following the call stack,
+    /// with the node counts when their translation started.
+    open_functions: Vec<(FunctionNodes, NodeCounts)>,
+    /// The node counts before and after every function instance translated inside the blocks being translated.
+    nested: Vec<(NodeCounts, NodeCounts)>,
+    /// The function instances translated so far.
+    mapping: BlockMapping,
+}
+
+impl BlockMappingRecord {
+    /// Starts recording the blocks of a new function instance before translating it.
+    pub fn start_function(&mut self, name: &str, net: &PetriNet) {
+        let function = FunctionNodes {
+            function: name.to_string(),
+            blocks: Vec::new(),
+        };
+        self.open_functions.push((function, net.node_counts()));
+    }
+
+    /// Finishes recording the blocks of the function instance on top of the call stack.
+    pub fn finish_function(&mut self, net: &PetriNet) {
+        let (function, start) = self
+            .open_functions
+            .pop()
+            .expect("BUG: The function should have been started in the block mapping");
+        self.nested.push((start, net.node_counts()));
+        self.mapping.functions.push(function);
+    }
+
+    /// Returns the state of the net before translating a basic block.
+    pub fn start_block(&self, net: &PetriNet) -> BlockStart {
+        BlockStart {
+            counts: net.node_counts(),
+            nested: self.nested.len(),
+        }
+    }
+
+    /// Records the nodes added since the start of the block in the function instance on top of the call stack.
+    /// `block_places` contains the start place of every block of the function translated so far, by block number.
+    /// The start place of the block comes first, the start places of the other blocks are skipped.
+    pub fn finish_block(
+        &mut self,
+        start: BlockStart,
+        block: usize,
+        terminator: &str,
+        span: SpanInfo,
+        block_places: &HashMap<usize, String>,
+        net: &PetriNet,
+    ) {
+        let (places, transitions) = net.labels_added(
+            start.counts,
+            net.node_counts(),
+            &self.nested[start.nested..],
+        );
+        // The function instances translated inside the block are already excluded from the enclosing blocks.
+        self.nested.truncate(start.nested);
+
+        let start_place = block_places
+            .get(&block)
+            .expect("BUG: The block should have a start place");
+        let mut own_places = vec![start_place.clone()];
+        own_places.extend(places.into_iter().filter(|place| {
+            !block_places
+                .values()
+                .any(|block_place| block_place == place)
+        }));
+        let (function, _) = self
+            .open_functions
+            .last_mut()
+            .expect("BUG: The function of the block should have been started in the block mapping");
+        function.blocks.push(BlockNodes {
+            block,
+            terminator: terminator.to_string(),
+            span: Some(span),
+            places: own_places,
+            transitions,
+        });
+    }
+
+    /// Returns the mapping of the function instances translated.
+    pub fn into_mapping(self) -> BlockMapping {
+        self.mapping
+    }
+}
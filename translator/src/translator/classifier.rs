@@ -0,0 +1,158 @@
+//! Submodule for the classification of the functions called in the code.
+//!
+//! Every call is classified once per function to select the handler in the `Translator`,
+//! see `FunctionClass`. The result is kept in memory during the translation.
+//!
+//! The classification of the functions not defined in the crate being translated,
+//! e.g. the functions of the standard library, only depends on the toolchain.
+//! It is persisted in a `ClassificationCache` in the configured cache folder
+//! and reused in the next runs, which avoids resolving the names of these functions again.
+//! There is one cache file per toolchain and version of the translator.
+
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::compiler_interface::{compiler_version, FunctionId, TyCtxt};
+use crate::data_structures::function_classification::{
+    ClassificationCache, ClassifiedFunction, FunctionClass,
+};
+use crate::options::{default_lock_libraries, LockLibrary};
+use crate::translator::special_function::{is_foreign_function, is_panic_function};
+use crate::translator::sync;
+
+pub struct FunctionClassifier {
+    /// The classification of every function seen during the translation.
+    functions: HashMap<FunctionId, ClassifiedFunction>,
+    /// The persistent classification of the functions not defined in the crate being translated.
+    cache: ClassificationCache,
+    /// The file where the cache is persisted, if any.
+    cache_file: Option<PathBuf>,
+    /// Whether functions were added to the cache since it was loaded.
+    modified: bool,
+}
+
+impl FunctionClassifier {
+    /// Creates a new `FunctionClassifier`.
+    /// Loads the cache for the current toolchain from the given folder, if any.
+    /// If the cache cannot be loaded, the classifier starts with an empty cache.
+    ///
+    /// The functions of the lock libraries are classified as synchronization functions,
+    /// so the cache is kept apart for every configuration of the lock libraries other than the default.
+    pub fn new(cache_folder: Option<&Path>, lock_libraries: &[LockLibrary]) -> Self {
+        let mut toolchain = format!(
+            "{} / {} {}",
+            compiler_version().unwrap_or("unknown"),
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        );
+        if lock_libraries != default_lock_libraries() {
+            for library in lock_libraries {
+                toolchain.push_str(&format!(" / {}{}", library.path, library.generics));
+            }
+        }
+        // Without a version, the cache could be reused by a different toolchain.
+        let cache_file = cache_folder
+            .filter(|_| compiler_version().is_some())
+            .map(|folder| folder.join(cache_filename(&toolchain)));
+
+        let cache = cache_file
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| {
+                ClassificationCache::from_json(&json, &toolchain)
+                    .map_err(|err_str| warn!("Ignoring the classification cache: {err_str}"))
+                    .ok()
+            })
+            .unwrap_or_else(|| ClassificationCache::new(&toolchain));
+        debug!("Loaded the classification of {} functions", cache.len());
+
+        Self {
+            functions: HashMap::new(),
+            cache,
+            cache_file,
+            modified: false,
+        }
+    }
+
+    /// Returns the name and the class of the function.
+    /// The result is looked up in memory, then in the cache for the functions of other crates.
+    /// Otherwise, the function is classified and the result stored for the next calls.
+    pub fn classify(&mut self, function_id: FunctionId, tcx: TyCtxt) -> ClassifiedFunction {
+        if let Some(function) = self.functions.get(&function_id) {
+            return function.clone();
+        }
+
+        let key = (!function_id.is_local()).then(|| function_id.stable_key(tcx));
+        let cached = key.as_ref().and_then(|key| self.cache.get(key)).cloned();
+        let function = cached.unwrap_or_else(|| {
+            let name = function_id.name(tcx);
+            let class = classify_function(function_id, &name, tcx);
+            let function = ClassifiedFunction { name, class };
+            if let Some(key) = key {
+                self.cache.insert(key, function.clone());
+                self.modified = true;
+            }
+            function
+        });
+
+        self.functions.insert(function_id, function.clone());
+        function
+    }
+
+    /// Persists the cache in the cache folder if functions were added to it.
+    /// Errors are only logged since the cache is an optimization.
+    pub fn save(&self) {
+        let Some(cache_file) = &self.cache_file else {
+            return;
+        };
+        if !self.modified {
+            return;
+        }
+        let result = cache_file
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(cache_file, self.cache.to_json()));
+        match result {
+            Ok(()) => debug!(
+                "Saved the classification of {} functions to {}",
+                self.cache.len(),
+                cache_file.display()
+            ),
+            Err(err) => warn!(
+                "Could not save the classification cache to {}: {err}",
+                cache_file.display()
+            ),
+        }
+    }
+}
+
+/// Classifies the function according to its name.
+/// The panic functions are checked first since they also belong to the core library.
+fn classify_function(function_id: FunctionId, function_name: &str, tcx: TyCtxt) -> FunctionClass {
+    if is_panic_function(function_name) {
+        FunctionClass::Panic
+    } else if sync::is_supported_function(function_name) {
+        FunctionClass::Sync
+    } else if is_foreign_function(function_id, function_name, tcx) {
+        FunctionClass::Foreign
+    } else {
+        FunctionClass::Translated
+    }
+}
+
+/// Returns the name of the cache file for the given toolchain.
+/// The characters that are not valid in filenames are replaced with underscores.
+fn cache_filename(toolchain: &str) -> String {
+    let toolchain: String = toolchain
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("classification_{toolchain}.json")
+}
@@ -0,0 +1,159 @@
+//! Submodule for the translation of closures that are called indirectly.
+//!
+//! A closure returned from a function (e.g. as `impl Fn()`) or stored in a `Box<dyn Fn()>`
+//! is called through the methods of the traits `Fn`, `FnMut` and `FnOnce`.
+//! These trait methods do not have a MIR body, so the call would be translated as an opaque foreign call.
+//!
+//! Instead, the ID of the closure is linked to the place where the closure is created.
+//! The ID travels with the value through assignments, casts, calls to foreign functions like `Box::new`
+//! and returns from MIR functions. At the call site, the body of the closure linked to the self argument
+//! is translated like any other MIR function. The same applies to the function passed to `std::thread::spawn`.
+//!
+//! The sync variables captured by the closure are aggregated in the place of the closure.
+//! They are passed to the memory of the closure body with the closure itself, the first argument.
+
+use log::debug;
+
+use crate::compiler_interface::{
+    closure_capture_places, AggregateKind, CallArgs, FunctionId, GenericArgs, List, Local, Operand,
+    Place, ProjectionElem, Rvalue, TyCtxt, TyKind,
+};
+use crate::translator::mir_function::memory::{Memory, Value};
+use crate::utils::extract_nth_argument_as_place;
+
+/// Checks whether the function name corresponds to one of the methods that call a closure.
+pub fn is_closure_call(function_name: &str) -> bool {
+    matches!(
+        function_name,
+        "std::ops::Fn::call" | "std::ops::FnMut::call_mut" | "std::ops::FnOnce::call_once"
+    )
+}
+
+/// Handles MIR assignments that create a closure or move it to another place.
+/// - `_X = {closure@src/main.rs:4:5: 4:7} { data: move _Y }` links the closure to the place.
+/// - `_X = _Y`, `_X = &_Y` and `_X = move _Y as Box<dyn Fn()> (PointerCoercion(Unsize))`
+///   link the place to the same closure as the right-hand side.
+pub fn handle_closure_assignment<'tcx>(
+    place: &Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    match rvalue {
+        Rvalue::Aggregate(kind, _) => {
+            if let AggregateKind::Closure(def_id, _) = **kind {
+                memory.link_closure(*place, FunctionId::from(def_id));
+            }
+        }
+        Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs))
+        | Rvalue::Ref(_, _, rhs)
+        | Rvalue::Cast(_, Operand::Copy(rhs) | Operand::Move(rhs), _) => {
+            memory.link_place_to_same_closure(*place, without_deref(rhs));
+        }
+        // No other assignment moves a closure.
+        _ => {}
+    }
+}
+
+/// Links the return value of a function call to the closure passed as the first argument.
+/// This covers the functions that wrap the closure, e.g. `std::boxed::Box::new`.
+pub fn link_return_value_if_closure<'tcx>(
+    args: &CallArgs<'tcx>,
+    return_value: Place<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    if let Some(first_argument) = extract_nth_argument_as_place(args, 0) {
+        memory.link_place_to_same_closure(return_value, without_deref(&first_argument));
+    }
+}
+
+/// Returns the ID of the closure called by a call to the function with the given name.
+/// A closure that captures no variables is a constant instead of an aggregate, e.g. `const ZeroSized: {closure@...}`.
+/// If no closure is linked to the self argument, the closure is then taken from the `Self` type of the call.
+/// Returns `None` if the function does not call a closure
+/// or the closure linked to the self argument is unknown.
+pub fn called_closure<'tcx>(
+    function_name: &str,
+    args: &CallArgs<'tcx>,
+    generic_args: GenericArgs<'tcx>,
+    memory: &Memory<'tcx>,
+) -> Option<FunctionId> {
+    if !is_closure_call(function_name) {
+        return None;
+    }
+    let self_ref = extract_nth_argument_as_place(args, 0)?;
+    memory.get_closure(&without_deref(&self_ref)).or_else(|| {
+        match generic_args.types().next()?.kind() {
+            TyKind::Closure(def_id, _) => Some(FunctionId::from(*def_id)),
+            _ => None,
+        }
+    })
+}
+
+/// Moves the aggregate containing the sync variables captured by a closure to the memory of the closure body.
+/// Then links the captured variables, see `link_captured_variables`.
+pub fn move_captured_sync_variables<'tcx>(
+    function_id: FunctionId,
+    aggregate: Vec<Value>,
+    memory: &mut Memory<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) {
+    // Link the aggregate itself, the local _1
+    let base_place = Place::from(Local::from_usize(1));
+    memory.link_aggregate(base_place, aggregate);
+    link_captured_variables(function_id, memory, tcx);
+}
+
+/// Links the places of the variables captured by a closure to the fields of the aggregate linked to the closure, the local `_1`.
+/// The places are obtained from the list of captures of the closure, see `closure_capture_places`.
+/// This covers the captures by value and by reference as well as the disjoint captures of edition 2021,
+/// since the fields of the aggregate are always the captured places in the order of the captures.
+///
+/// If the closure does not capture sync variables, the aggregate is empty and nothing is linked.
+/// The captures that do not contain sync variables are not linked either.
+///
+/// # Examples
+///
+/// For a closure called through `Fn::call`, the place `(*_1).0` contains the first captured variable.
+/// If it is `data: &std::sync::Mutex<i32>`, both `(*_1).0` and `(*((*_1).0))` are linked to the mutex.
+pub fn link_captured_variables<'tcx>(
+    function_id: FunctionId,
+    memory: &mut Memory<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) {
+    let base_place = Place::from(Local::from_usize(1));
+    if !memory.is_aggregate(&base_place) {
+        return;
+    }
+    let fields = memory.copy_aggregate(&base_place);
+
+    for (index, places) in closure_capture_places(function_id, tcx)
+        .into_iter()
+        .enumerate()
+    {
+        if matches!(fields.get(index), None | Some(Value::Untracked)) {
+            // The capture does not contain sync variables
+            continue;
+        }
+        for place in places {
+            memory.link_field_in_aggregate(place, base_place, index);
+            debug!("LINKED CAPTURED VARIABLE {place:?} TO FIELD {index} IN AGGREGATE");
+        }
+    }
+}
+
+/// Returns the place without the dereferences, e.g. `_1` for `(*_1)`.
+/// Places with other projections are returned unchanged.
+fn without_deref<'tcx>(place: &Place<'tcx>) -> Place<'tcx> {
+    if place
+        .projection
+        .iter()
+        .all(|elem| matches!(elem, ProjectionElem::Deref))
+    {
+        Place {
+            local: place.local,
+            projection: List::empty(),
+        }
+    } else {
+        *place
+    }
+}
@@ -0,0 +1,734 @@
+//! Submodule for the cross-check of the translation against an execution of the program.
+//!
+//! While translating, the `ControlFlowRecord` keeps the place of every basic block of every function instance
+//! and which instance was created for every call to a MIR function or thread spawn.
+//! After the translation, the `Interpreter` executes the deterministic subset of the MIR of the program
+//! and replays every move between basic blocks on the net, see `cargo_check_deadlock_core::analysis::cross_check`.
+//! A move that the net cannot reproduce is a soundness bug of the translation.
+//!
+//! The interpreter tracks the integer and boolean values of the locals assigned from constants
+//! or from arithmetic and comparisons between tracked values. Everything else, e.g. the results of calls
+//! to the standard library, is unknown. The execution stops at the first branch on an unknown value.
+//! Arithmetic with overflow checks is not evaluated, so a program compiled without
+//! `-C overflow-checks=off` stops at the first checked operation that a branch depends on.
+//!
+//! The schedule is deterministic and bounded by `MAX_STEPS`: The threads run in the order they were spawned,
+//! each one until it finishes or is blocked, e.g. waiting for a mutex or for a thread to be joined.
+//!
+//! Whether a thread is blocked is derived from the execution, not from the net: The translator records
+//! the `SyncEffect` of every call to a lock or a join and of every drop of a mutex guard,
+//! and the interpreter keeps the owner of every mutex and which threads finished.
+//! The net must block exactly the moves on which the program blocks.
+//! The state of the other primitives, e.g. channels, is not tracked: If the net blocks a thread on one of them,
+//! the thread waits until the net fires the move, but a run that ends with such a wait is not a deadlock
+//! confirmed by the program, so the execution stops.
+
+use log::info;
+use std::collections::HashMap;
+
+use crate::compiler_interface::{
+    argument_operand, span_info, BasicBlock, BinOp, Body, CallArgs, FunctionId, Local, Operand,
+    Place, Rvalue, StatementKind, TerminatorKind, TyCtxt, UnOp, RETURN_PLACE,
+};
+use crate::naming::basic_block::place_label;
+use crate::naming::PROGRAM_START;
+use crate::translator::mir_function::MirFunction;
+use crate::utils::operand_as_integer;
+use cargo_check_deadlock_core::analysis::cross_check::{
+    CrossCheckReport, CrossCheckVerdict, Replay, StepOutcome,
+};
+use cargo_check_deadlock_core::data_structures::net_data::NetData;
+use cargo_check_deadlock_core::data_structures::span_info::SpanInfo;
+
+/// The maximum number of moves between basic blocks replayed on the net.
+pub const MAX_STEPS: usize = 10_000;
+
+/// How a call to a function was translated, if it was not abstracted.
+pub enum CallRecord {
+    /// The function was translated as the given instance.
+    Function(usize),
+    /// The self-recursive call in tail position was modelled as a loop to the start of the function.
+    Loop,
+    /// The call spawned the thread with the given index.
+    Spawn(usize),
+}
+
+/// The effect of a call or a drop on the synchronization state tracked by the interpreter.
+pub enum SyncEffect {
+    /// The thread locks the mutex with the given label. It blocks while a thread holds the mutex.
+    Lock(String),
+    /// The thread releases the mutex with the given label by dropping its guard.
+    Unlock(String),
+    /// The thread joins the thread with the given index. It blocks until the thread finished.
+    Join(usize),
+    /// The thread waits on the condition variable with the given label, which releases the mutex
+    /// with the given label until the thread wakes up. Whether the thread blocks is not tracked.
+    Wait { condvar: String, mutex: String },
+    /// The thread may block on the primitive with the given label, whose state is not tracked.
+    Untracked(String),
+}
+
+impl SyncEffect {
+    /// Returns the label of the primitive whose state is not tracked, if the thread may block on it.
+    fn untracked_primitive(&self) -> Option<&str> {
+        match self {
+            Self::Wait { condvar: label, .. } | Self::Untracked(label) => Some(label),
+            Self::Lock(_) | Self::Unlock(_) | Self::Join(_) => None,
+        }
+    }
+}
+
+/// A translated function instance.
+struct InstanceRecord {
+    /// The function translated.
+    function_id: FunctionId,
+    /// The name of the instance, used in the labels of its places.
+    name: String,
+    /// The labels of the places of the basic blocks, by block number.
+    block_places: HashMap<usize, String>,
+    /// The label of the place where the instance returns to.
+    end_place: String,
+}
+
+/// The places and the calls of the function instances, recorded during the translation.
+#[derive(Default)]
+pub struct ControlFlowRecord {
+    /// The instances in the order they were translated.
+    instances: Vec<InstanceRecord>,
+    /// The calls that were not abstracted, by instance and basic block of the call.
+    calls: HashMap<(usize, usize), CallRecord>,
+    /// The instance of the function run by every thread, by thread index.
+    threads: HashMap<usize, usize>,
+    /// The effects on the synchronization state, by instance and basic block of the call or drop.
+    sync_effects: HashMap<(usize, usize), Vec<SyncEffect>>,
+}
+
+impl ControlFlowRecord {
+    /// Adds a new function instance before translating it and returns its number.
+    pub fn add_instance(&mut self, function: &MirFunction) -> usize {
+        self.instances.push(InstanceRecord {
+            function_id: function.id,
+            name: function.name.clone(),
+            block_places: HashMap::new(),
+            end_place: function.end_place.label().to_string(),
+        });
+        self.instances.len() - 1
+    }
+
+    /// Records the places of the basic blocks of the translated function instance.
+    pub fn finish_instance(&mut self, function: &MirFunction) {
+        let instance = &mut self.instances[function.instance];
+        instance.block_places = function
+            .block_places()
+            .map(|(block, place)| (block, place.label().to_string()))
+            .collect();
+    }
+
+    /// Records how the call in the given instance and basic block was translated.
+    pub fn add_call(&mut self, instance: usize, block: usize, call: CallRecord) {
+        self.calls.insert((instance, block), call);
+    }
+
+    /// Records the effect on the synchronization state of the call or drop in the given instance and basic block.
+    pub fn add_sync_effect(&mut self, instance: usize, block: usize, effect: SyncEffect) {
+        self.sync_effects
+            .entry((instance, block))
+            .or_default()
+            .push(effect);
+    }
+
+    /// Returns the effects on the synchronization state of the call or drop in the given instance and basic block.
+    fn sync_effects(&self, instance: usize, block: usize) -> &[SyncEffect] {
+        self.sync_effects
+            .get(&(instance, block))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Records the instance of the function run by the thread with the given index.
+    pub fn add_thread(&mut self, thread_index: usize, instance: usize) {
+        self.threads.insert(thread_index, instance);
+    }
+
+    /// Returns the labels of the places of the basic blocks, which hold the control token of a thread.
+    /// The places where the threads end are not included, since joining a thread consumes from them.
+    fn control_places(&self) -> impl Iterator<Item = &str> {
+        self.instances
+            .iter()
+            .flat_map(|instance| instance.block_places.values().map(String::as_str))
+    }
+
+    /// Returns the label of the place of the basic block of the instance.
+    /// If the block was not translated, returns the label it should have, which is not in the net.
+    fn block_place(&self, instance: usize, block: usize) -> String {
+        let instance = &self.instances[instance];
+        instance
+            .block_places
+            .get(&block)
+            .cloned()
+            .unwrap_or_else(|| place_label(&instance.name, block))
+    }
+}
+
+/// A function being executed by a thread.
+struct Frame<'tcx> {
+    /// The number of the function instance.
+    instance: usize,
+    /// The current basic block.
+    block: usize,
+    /// The values of the locals that are known.
+    locals: HashMap<Local, i128>,
+    /// The place in the caller where the return value is stored and the block where the caller continues.
+    return_to: Option<(Place<'tcx>, usize)>,
+}
+
+impl<'tcx> Frame<'tcx> {
+    /// Creates the frame for a call to the function instance with the given values of the arguments.
+    fn new(
+        instance: usize,
+        arguments: Vec<Option<i128>>,
+        return_to: Option<(Place<'tcx>, usize)>,
+    ) -> Self {
+        let locals = arguments
+            .into_iter()
+            .enumerate()
+            // The arguments are the locals after the return place: `_1`, `_2`, etc.
+            .filter_map(|(index, value)| Some((Local::from_usize(index + 1), value?)))
+            .collect();
+        Self {
+            instance,
+            block: 0,
+            locals,
+            return_to,
+        }
+    }
+}
+
+/// The position reached by a thread once the net fired the move to the next place.
+enum Position<'tcx> {
+    /// The thread continues in the given block of the current function.
+    Block(usize),
+    /// The thread enters the called function.
+    Call(Frame<'tcx>),
+    /// The thread starts the current function again with new arguments.
+    Restart(Vec<Option<i128>>),
+    /// The thread returns the given value to the caller.
+    Return(Option<i128>),
+    /// The thread continues in the given block after spawning the thread with the given index,
+    /// which runs the given instance.
+    Spawn {
+        block: usize,
+        thread: usize,
+        instance: usize,
+    },
+    /// The thread returned from its first function.
+    Finish,
+}
+
+/// A move of a thread to the next place, not fired yet.
+struct Move<'tcx> {
+    /// The label of the place of the next position.
+    to: String,
+    /// The next position.
+    position: Position<'tcx>,
+    /// The span of the code that causes the move.
+    span: SpanInfo,
+}
+
+/// A thread of the execution.
+struct Thread<'tcx> {
+    /// The index of the thread in the translation, `None` for the main thread.
+    index: Option<usize>,
+    /// The call stack, the innermost function last.
+    frames: Vec<Frame<'tcx>>,
+    /// The label of the place that holds the control token of the thread.
+    control: String,
+    /// The move on which the thread is blocked, if any.
+    blocked_on: Option<Move<'tcx>>,
+    /// The label of the primitive whose state is not tracked on which the net blocks the thread, if any.
+    waiting_on: Option<String>,
+    /// Whether the thread finished.
+    finished: bool,
+}
+
+/// The result of running a thread until it cannot continue.
+enum ThreadState {
+    /// The thread waits for a transition that another thread enables.
+    Blocked,
+    /// The thread returned from its first function.
+    Finished,
+    /// The cross-check ended with the given verdict.
+    End(CrossCheckVerdict),
+}
+
+/// The interpreter of the deterministic subset of the MIR of the program.
+pub struct Interpreter<'a, 'tcx> {
+    /// The global typing context that enables interaction with `rustc`.
+    tcx: TyCtxt<'tcx>,
+    /// The places and calls recorded during the translation.
+    record: &'a ControlFlowRecord,
+    /// The replay of the execution on the net.
+    replay: Replay,
+    /// The threads spawned so far, starting with the main thread.
+    threads: Vec<Thread<'tcx>>,
+    /// The number of moves fired so far.
+    steps: usize,
+    /// The index in `threads` of the thread that holds every locked mutex, by the label of the mutex.
+    mutex_owners: HashMap<String, usize>,
+}
+
+impl<'a, 'tcx> Interpreter<'a, 'tcx> {
+    /// Creates the interpreter for the translated net.
+    /// The main thread starts in the first function instance translated, i.e. the entry function.
+    ///
+    /// # Errors
+    ///
+    /// If the net is not valid, then an error is returned.
+    pub fn new(
+        tcx: TyCtxt<'tcx>,
+        record: &'a ControlFlowRecord,
+        net: &NetData,
+    ) -> Result<Self, String> {
+        let main_thread = Thread {
+            index: None,
+            frames: Vec::new(),
+            control: PROGRAM_START.to_string(),
+            blocked_on: Some(Move {
+                to: record.block_place(0, 0),
+                position: Position::Call(Frame::new(0, Vec::new(), None)),
+                span: SpanInfo::default(),
+            }),
+            waiting_on: None,
+            finished: false,
+        };
+        Ok(Self {
+            tcx,
+            record,
+            replay: Replay::new(net)?.with_control_places(record.control_places()),
+            threads: vec![main_thread],
+            steps: 0,
+            mutex_owners: HashMap::new(),
+        })
+    }
+
+    /// Runs the threads in order until all of them finished or are blocked,
+    /// or the execution leaves the deterministic subset.
+    pub fn run(mut self) -> CrossCheckReport {
+        if self.record.instances.is_empty() {
+            return self.report(CrossCheckVerdict::Stopped {
+                reason: "No function was translated".to_string(),
+                span: None,
+            });
+        }
+        loop {
+            let progress = (self.steps, self.replay.history().len());
+            let mut index = 0;
+            // Threads spawned during the pass also run in it.
+            while index < self.threads.len() {
+                if !self.threads[index].finished {
+                    if let ThreadState::End(verdict) = self.run_thread(index) {
+                        return self.report(verdict);
+                    }
+                }
+                index += 1;
+            }
+            if self.threads.iter().all(|thread| thread.finished) {
+                return self.report(CrossCheckVerdict::Completed);
+            }
+            if progress == (self.steps, self.replay.history().len()) {
+                let waiting = self.threads.iter().find_map(|thread| {
+                    let next = thread.blocked_on.as_ref()?;
+                    Some((thread.waiting_on.clone()?, next.span.clone()))
+                });
+                let Some((label, span)) = waiting else {
+                    return self.report(CrossCheckVerdict::Deadlock);
+                };
+                return self.report(CrossCheckVerdict::Stopped {
+                    reason: format!("Every thread is blocked, but the net blocks a thread on `{label}`, whose state the interpreter does not track"),
+                    span: Some(span),
+                });
+            }
+        }
+    }
+
+    /// Runs the thread until it finishes or is blocked.
+    fn run_thread(&mut self, index: usize) -> ThreadState {
+        let record = self.record;
+        loop {
+            if self.steps >= MAX_STEPS {
+                return ThreadState::End(CrossCheckVerdict::StepLimit);
+            }
+            let next = match self.threads[index].blocked_on.take() {
+                Some(next) => next,
+                None => match next_move(self.tcx, record, &mut self.threads[index]) {
+                    Ok(next) => next,
+                    Err(verdict) => return ThreadState::End(verdict),
+                },
+            };
+            // The effects belong to the block of the terminator, which is still the current block of the thread.
+            let effects = self.threads[index].frames.last().map_or(&[][..], |frame| {
+                record.sync_effects(frame.instance, frame.block)
+            });
+            let blocked = self.is_blocked(effects);
+            let thread = &mut self.threads[index];
+            if blocked {
+                if self.replay.can_step(&thread.control, &next.to) {
+                    return ThreadState::End(CrossCheckVerdict::ProgramBlocked {
+                        from: thread.control.clone(),
+                        to: next.to,
+                        span: Some(next.span),
+                    });
+                }
+                thread.blocked_on = Some(next);
+                return ThreadState::Blocked;
+            }
+            match self.replay.step(&thread.control, &next.to) {
+                StepOutcome::Fired => {
+                    self.steps += 1;
+                    thread.control = next.to;
+                    thread.waiting_on = None;
+                    let spawned = apply(thread, next.position);
+                    self.apply_effects(index, effects);
+                    if let Some((thread_index, frame)) = spawned {
+                        let control = record.block_place(frame.instance, 0);
+                        info!(
+                            "Cross-check: Spawned the thread {thread_index} starting at {control}"
+                        );
+                        self.threads.push(Thread {
+                            index: Some(thread_index),
+                            frames: vec![frame],
+                            control,
+                            blocked_on: None,
+                            waiting_on: None,
+                            finished: false,
+                        });
+                    }
+                    if self.threads[index].finished {
+                        return ThreadState::Finished;
+                    }
+                }
+                StepOutcome::Blocked { at } => {
+                    let Some(label) = effects.iter().find_map(SyncEffect::untracked_primitive)
+                    else {
+                        return ThreadState::End(CrossCheckVerdict::NetBlocked {
+                            from: thread.control.clone(),
+                            to: next.to,
+                            span: Some(next.span),
+                        });
+                    };
+                    thread.control = at;
+                    thread.blocked_on = Some(next);
+                    thread.waiting_on = Some(label.to_string());
+                    // The mutex is released while waiting on the condition variable.
+                    for effect in effects {
+                        if let SyncEffect::Wait { mutex, .. } = effect {
+                            self.mutex_owners.remove(mutex);
+                        }
+                    }
+                    return ThreadState::Blocked;
+                }
+                StepOutcome::Missing => {
+                    return ThreadState::End(CrossCheckVerdict::Mismatch {
+                        from: thread.control.clone(),
+                        to: next.to,
+                        span: Some(next.span),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Checks whether the program blocks a thread on the given effects,
+    /// i.e. it locks a mutex that a thread holds or joins a thread that did not finish.
+    /// A thread that locks a mutex it holds already blocks forever.
+    fn is_blocked(&self, effects: &[SyncEffect]) -> bool {
+        effects.iter().any(|effect| match effect {
+            SyncEffect::Lock(mutex) => self.mutex_owners.contains_key(mutex),
+            SyncEffect::Join(joined) => !self
+                .threads
+                .iter()
+                .any(|thread| thread.index == Some(*joined) && thread.finished),
+            SyncEffect::Unlock(_) | SyncEffect::Wait { .. } | SyncEffect::Untracked(_) => false,
+        })
+    }
+
+    /// Updates the owners of the mutexes after the thread at the given index made a move with the given effects.
+    fn apply_effects(&mut self, index: usize, effects: &[SyncEffect]) {
+        for effect in effects {
+            match effect {
+                SyncEffect::Lock(mutex) | SyncEffect::Wait { mutex, .. } => {
+                    self.mutex_owners.insert(mutex.clone(), index);
+                }
+                SyncEffect::Unlock(mutex) => {
+                    self.mutex_owners.remove(mutex);
+                }
+                SyncEffect::Join(_) | SyncEffect::Untracked(_) => {}
+            }
+        }
+    }
+
+    /// Returns the report of the cross-check with the given verdict.
+    fn report(self, verdict: CrossCheckVerdict) -> CrossCheckReport {
+        CrossCheckReport {
+            verdict,
+            steps: self.steps,
+            fired: self.replay.history().to_vec(),
+        }
+    }
+}
+
+/// Executes the statements of the current basic block of the thread
+/// and returns the move to the position after its terminator.
+///
+/// # Errors
+///
+/// If the terminator is outside of the deterministic subset, e.g. a branch on an unknown value,
+/// then the verdict that stops the execution is returned.
+fn next_move<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    record: &ControlFlowRecord,
+    thread: &mut Thread<'tcx>,
+) -> Result<Move<'tcx>, CrossCheckVerdict> {
+    let frames_len = thread.frames.len();
+    let frame = thread
+        .frames
+        .last_mut()
+        .expect("BUG: A running thread should have a function on its call stack");
+    let instance = frame.instance;
+    let body = record.instances[instance].function_id.body(tcx);
+    let data = &body.basic_blocks[BasicBlock::from_usize(frame.block)];
+    for statement in &data.statements {
+        if let StatementKind::Assign(assign) = &statement.kind {
+            let (place, rvalue) = &**assign;
+            let value = evaluate_rvalue(tcx, rvalue, &frame.locals, body);
+            assign_local(&mut frame.locals, *place, value);
+        }
+    }
+
+    let terminator = data.terminator();
+    let span = span_info(terminator.source_info.span, tcx);
+    let stop = |reason: &str| CrossCheckVerdict::Stopped {
+        reason: reason.to_string(),
+        span: Some(span.clone()),
+    };
+    let block_move = |block: BasicBlock| Move {
+        to: record.block_place(instance, block.index()),
+        position: Position::Block(block.index()),
+        span: span.clone(),
+    };
+    match &terminator.kind {
+        TerminatorKind::Goto { target } | TerminatorKind::Drop { target, .. } => {
+            Ok(block_move(*target))
+        }
+        TerminatorKind::SwitchInt { discr, targets } => {
+            let value = evaluate_operand(discr, &frame.locals)
+                .and_then(|value| u128::try_from(value).ok())
+                .ok_or_else(|| {
+                    stop("The branch depends on a value that the interpreter does not track")
+                })?;
+            Ok(block_move(targets.target_for_value(value)))
+        }
+        TerminatorKind::Assert {
+            cond,
+            expected,
+            target,
+            ..
+        } => {
+            // An assertion on an unknown value, e.g. an overflow check, is assumed to hold.
+            if evaluate_operand(cond, &frame.locals).is_some_and(|value| (value != 0) != *expected)
+            {
+                return Err(stop("The assertion fails, so the program panics"));
+            }
+            Ok(block_move(*target))
+        }
+        TerminatorKind::Call {
+            args,
+            destination,
+            target,
+            ..
+        } => {
+            let arguments = evaluate_arguments(args, &frame.locals);
+            match (record.calls.get(&(instance, frame.block)), target) {
+                (Some(CallRecord::Function(callee)), Some(target)) => Ok(Move {
+                    to: record.block_place(*callee, 0),
+                    position: Position::Call(Frame::new(
+                        *callee,
+                        arguments,
+                        Some((*destination, target.index())),
+                    )),
+                    span,
+                }),
+                (Some(CallRecord::Loop), _) => Ok(Move {
+                    to: record.block_place(instance, 0),
+                    position: Position::Restart(arguments),
+                    span,
+                }),
+                (Some(CallRecord::Spawn(thread_index)), Some(target)) => {
+                    let spawned = *record
+                        .threads
+                        .get(thread_index)
+                        .ok_or_else(|| stop("The spawned thread was not translated"))?;
+                    Ok(Move {
+                        to: record.block_place(instance, target.index()),
+                        position: Position::Spawn {
+                            block: target.index(),
+                            thread: *thread_index,
+                            instance: spawned,
+                        },
+                        span,
+                    })
+                }
+                (_, Some(target)) => {
+                    // The result of an abstracted function is unknown.
+                    assign_local(&mut frame.locals, *destination, None);
+                    Ok(block_move(*target))
+                }
+                (_, None) => Err(stop("The called function does not return")),
+            }
+        }
+        TerminatorKind::Return => {
+            let value = frame.locals.get(&RETURN_PLACE).copied();
+            let return_to = frame.return_to;
+            let to = match return_to {
+                Some((_, block)) if frames_len > 1 => {
+                    let caller = &thread.frames[frames_len - 2];
+                    record.block_place(caller.instance, block)
+                }
+                _ => record.instances[instance].end_place.clone(),
+            };
+            let position = if frames_len > 1 {
+                Position::Return(value)
+            } else {
+                Position::Finish
+            };
+            Ok(Move { to, position, span })
+        }
+        TerminatorKind::Unreachable => Err(stop("The program reached unreachable code")),
+        _ => Err(stop("The terminator is not supported by the interpreter")),
+    }
+}
+
+/// Returns the value of the right-hand side of an assignment, if it is known.
+fn evaluate_rvalue<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    locals: &HashMap<Local, i128>,
+    body: &Body<'tcx>,
+) -> Option<i128> {
+    match rvalue {
+        Rvalue::Use(operand) => evaluate_operand(operand, locals),
+        Rvalue::BinaryOp(op, operands) => {
+            let (left, right) = &**operands;
+            let left = evaluate_operand(left, locals)?;
+            let right = evaluate_operand(right, locals)?;
+            evaluate_binary_op(*op, left, right)
+        }
+        Rvalue::UnaryOp(UnOp::Not, operand) => {
+            let value = evaluate_operand(operand, locals)?;
+            if operand.ty(body, tcx).is_bool() {
+                Some(i128::from(value == 0))
+            } else if operand.ty(body, tcx).is_signed() {
+                Some(!value)
+            } else {
+                // The complement of an unsigned integer depends on its width.
+                None
+            }
+        }
+        Rvalue::UnaryOp(UnOp::Neg, operand) => evaluate_operand(operand, locals)?.checked_neg(),
+        _ => None,
+    }
+}
+
+/// Moves the thread to the position after the move was fired.
+/// Returns the index and the first frame of the thread spawned by the move, if any.
+fn apply<'tcx>(
+    thread: &mut Thread<'tcx>,
+    position: Position<'tcx>,
+) -> Option<(usize, Frame<'tcx>)> {
+    match position {
+        Position::Block(block) => {
+            if let Some(frame) = thread.frames.last_mut() {
+                frame.block = block;
+            }
+        }
+        Position::Call(frame) => thread.frames.push(frame),
+        Position::Restart(arguments) => {
+            if let Some(frame) = thread.frames.last_mut() {
+                *frame = Frame::new(frame.instance, arguments, frame.return_to);
+            }
+        }
+        Position::Return(value) => {
+            let callee = thread.frames.pop();
+            if let (Some(frame), Some((destination, block))) = (
+                thread.frames.last_mut(),
+                callee.and_then(|callee| callee.return_to),
+            ) {
+                assign_local(&mut frame.locals, destination, value);
+                frame.block = block;
+            }
+        }
+        Position::Spawn {
+            block,
+            thread: spawned,
+            instance,
+        } => {
+            if let Some(frame) = thread.frames.last_mut() {
+                frame.block = block;
+            }
+            return Some((spawned, Frame::new(instance, Vec::new(), None)));
+        }
+        Position::Finish => {
+            thread.frames.clear();
+            thread.finished = true;
+        }
+    }
+    None
+}
+
+/// Stores the value in the local, or forgets the value of the local if the value is unknown.
+/// An assignment to a field or through a reference makes the whole local unknown.
+fn assign_local(locals: &mut HashMap<Local, i128>, place: Place, value: Option<i128>) {
+    match (place.as_local(), value) {
+        (Some(local), Some(value)) => {
+            locals.insert(local, value);
+        }
+        _ => {
+            locals.remove(&place.local);
+        }
+    }
+}
+
+/// Returns the value of the operand, if it is known.
+fn evaluate_operand(operand: &Operand, locals: &HashMap<Local, i128>) -> Option<i128> {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => locals.get(&place.as_local()?).copied(),
+        Operand::Constant(_) => operand_as_integer(operand),
+    }
+}
+
+/// Returns the values of the arguments of a call, if they are known.
+fn evaluate_arguments(args: &CallArgs, locals: &HashMap<Local, i128>) -> Vec<Option<i128>> {
+    args.iter()
+        .map(|argument| evaluate_operand(argument_operand(argument), locals))
+        .collect()
+}
+
+/// Returns the result of the binary operation, if it is supported and does not overflow.
+fn evaluate_binary_op(op: BinOp, left: i128, right: i128) -> Option<i128> {
+    match op {
+        BinOp::Add => left.checked_add(right),
+        BinOp::Sub => left.checked_sub(right),
+        BinOp::Mul => left.checked_mul(right),
+        BinOp::Div => left.checked_div(right),
+        BinOp::Rem => left.checked_rem(right),
+        BinOp::BitAnd => Some(left & right),
+        BinOp::BitOr => Some(left | right),
+        BinOp::BitXor => Some(left ^ right),
+        BinOp::Eq => Some(i128::from(left == right)),
+        BinOp::Ne => Some(i128::from(left != right)),
+        BinOp::Lt => Some(i128::from(left < right)),
+        BinOp::Le => Some(i128::from(left <= right)),
+        BinOp::Gt => Some(i128::from(left > right)),
+        BinOp::Ge => Some(i128::from(left >= right)),
+        _ => None,
+    }
+}
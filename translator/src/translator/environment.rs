@@ -0,0 +1,108 @@
+//! Submodule that tracks the values that depend on the inputs of the environment,
+//! i.e. the command line arguments and the environment variables.
+//!
+//! Every branch of a `SwitchInt` terminator is modelled regardless of the value it branches on,
+//! so a branch on a command line argument is already a free choice in the net.
+//! With the `environment_branches` option, these branches are tagged as controlled by the environment:
+//! Their transitions are labelled `_ENV_FROM_BB<N>_TO_BB<M>` instead of `_SWITCH_INT_FROM_BB<N>_TO_BB<M>`,
+//! so an analysis can tell the choices of the inputs apart from the choices of the scheduler.
+//!
+//! The results of the calls to `std::env::args` and similar functions depend on the environment.
+//! The dependency is propagated in the memory by local, without distinguishing the fields:
+//! - Through the assignments whose operands depend on the environment, e.g. `_3 = &_2` or `_5 = discriminant(_4)`.
+//! - Through the calls to foreign functions with an argument that depends on the environment,
+//!   e.g. `Iterator::nth` on the arguments or `str::eq` on one of them.
+//! - Through the calls to MIR functions, to the arguments and back from the return value.
+//!   The tuple of arguments of a closure is received as separate locals, which all depend on the environment
+//!   if the tuple does, see `FunctionId::argument_locals`.
+
+use crate::compiler_interface::{
+    argument_operand, CallArgs, FunctionId, Local, Operand, Place, Rvalue, TyCtxt,
+};
+use crate::translator::mir_function::memory::Memory;
+
+/// The functions that return an input of the environment.
+const ENVIRONMENT_FUNCTIONS: [&str; 5] = [
+    "std::env::args",
+    "std::env::args_os",
+    "std::env::var",
+    "std::env::var_os",
+    "std::env::vars",
+];
+
+/// Checks whether the function returns an input of the environment.
+pub fn is_environment_function(function_name: &str) -> bool {
+    ENVIRONMENT_FUNCTIONS.contains(&function_name)
+}
+
+/// Checks whether the operand is a place that depends on the environment.
+fn operand_depends_on_environment<'tcx>(operand: &Operand<'tcx>, memory: &Memory<'tcx>) -> bool {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => memory.depends_on_environment(place),
+        Operand::Constant(_) => false,
+    }
+}
+
+/// Returns the indices of the arguments of a call that depend on the environment.
+pub fn environment_arguments<'tcx>(args: &CallArgs<'tcx>, memory: &Memory<'tcx>) -> Vec<usize> {
+    args.iter()
+        .enumerate()
+        .filter(|(_, argument)| operand_depends_on_environment(argument_operand(argument), memory))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns the locals of the called MIR function that receive the arguments of the call that depend on the environment.
+pub fn environment_argument_locals<'tcx>(
+    args: &CallArgs<'tcx>,
+    function_id: FunctionId,
+    memory: &Memory<'tcx>,
+    tcx: TyCtxt,
+) -> Vec<Local> {
+    let argument_locals = function_id.argument_locals(args.len(), tcx);
+    environment_arguments(args, memory)
+        .into_iter()
+        .flat_map(|index| argument_locals[index].iter().copied())
+        .collect()
+}
+
+/// Handles the assignments whose value may depend on the environment:
+/// uses, references, casts, discriminants, unary and binary operations and aggregates.
+/// If one of the operands depends on the environment, the place assigned depends on it too.
+pub fn handle_environment_assignment<'tcx>(
+    place: &Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    let depends = match rvalue {
+        Rvalue::Use(operand) | Rvalue::Cast(_, operand, _) | Rvalue::UnaryOp(_, operand) => {
+            operand_depends_on_environment(operand, memory)
+        }
+        Rvalue::Ref(_, _, rhs) | Rvalue::Discriminant(rhs) => memory.depends_on_environment(rhs),
+        Rvalue::BinaryOp(_, operands) => {
+            let (first, second) = &**operands;
+            operand_depends_on_environment(first, memory)
+                || operand_depends_on_environment(second, memory)
+        }
+        Rvalue::Aggregate(_, operands) => operands
+            .iter()
+            .any(|operand| operand_depends_on_environment(operand, memory)),
+        _ => false,
+    };
+    if depends {
+        memory.mark_environment(*place);
+    }
+}
+
+/// Handles a call to a foreign function: The return value depends on the environment
+/// if the function returns an input of the environment or one of its arguments depends on the environment.
+pub fn handle_foreign_call<'tcx>(
+    function_name: &str,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    if is_environment_function(function_name) || !environment_arguments(args, memory).is_empty() {
+        memory.mark_environment(destination);
+    }
+}
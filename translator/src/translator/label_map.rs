@@ -0,0 +1,44 @@
+//! Submodule that records the map from the labels of the net to the code that produced them,
+//! see `cargo_check_deadlock_core::data_structures::label_map`.
+//!
+//! The node counts are taken before translating every statement, terminator and basic block,
+//! and the nodes added in between are recorded with the text of the MIR element being translated.
+
+use crate::data_structures::petri_net_interface::{NodeCounts, PetriNet};
+use crate::data_structures::span_info::SpanInfo;
+use cargo_check_deadlock_core::data_structures::label_map::{LabelEntry, LabelMap};
+
+/// The label map, recorded during the translation.
+#[derive(Default)]
+pub struct LabelMapRecord {
+    /// The nodes recorded so far.
+    map: LabelMap,
+}
+
+impl LabelMapRecord {
+    /// Records the origin of the places and transitions added to the net since `start`.
+    pub fn record(
+        &mut self,
+        start: NodeCounts,
+        net: &PetriNet,
+        function: &str,
+        span: &SpanInfo,
+        mir: &str,
+    ) {
+        let (places, transitions) = net.labels_added(start, net.node_counts(), &[]);
+        let entry = LabelEntry {
+            function: function.to_string(),
+            file: span.filename.clone(),
+            line: span.start_line,
+            mir: mir.to_string(),
+        };
+        for label in places.into_iter().chain(transitions) {
+            self.map.insert(label, &entry);
+        }
+    }
+
+    /// Returns the map of the labels recorded.
+    pub fn into_map(self) -> LabelMap {
+        self.map
+    }
+}
@@ -0,0 +1,262 @@
+//! Submodule for the lint mode, which records the lock operations of every function without building the net.
+//!
+//! The `Linter` visits the MIR representation of every function, method and closure of the crate once,
+//! following the basic blocks in reverse postorder and skipping the cleanup blocks of the unwinding.
+//! It records the operations on locks as `LockEvent`s, which are checked by the detectors
+//! in `cargo_check_deadlock_core::analysis::lint`.
+//!
+//! The calls are not followed and the memory is not tracked. A guard is followed through
+//! the locals it is moved to, e.g. from the `LockResult` to the result of `unwrap`, until it is dropped.
+//! The locks are identified by the expression before the method call in the source code,
+//! e.g. `self.data` for `self.data.lock()`.
+
+use log::info;
+use std::collections::HashMap;
+
+use super::sync::{set_lock_libraries, LockFunction};
+use crate::compiler_interface::{
+    argument_operand, local_functions, span_info, span_snippet, Body, CallArgs, FunctionId, Local,
+    Operand, Place, Rvalue, Span, StatementKind, TerminatorKind, TyCtxt,
+};
+use crate::options::LockLibrary;
+use crate::utils::function_id_of_operand;
+use cargo_check_deadlock_core::analysis::lint::{
+    FunctionEvents, LintReport, LockAccess, LockEvent,
+};
+
+/// The guard types whose values must not live across an `.await`.
+const GUARD_TYPES: [&str; 3] = ["MutexGuard<", "RwLockReadGuard<", "RwLockWriteGuard<"];
+
+pub struct Linter<'tcx> {
+    /// The global typing context that enables interaction with `rustc`.
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> Linter<'tcx> {
+    /// Creates a new `Linter` that recognizes the locks of the given libraries.
+    pub fn new(tcx: TyCtxt<'tcx>, lock_libraries: &[LockLibrary]) -> Self {
+        set_lock_libraries(lock_libraries);
+        Self { tcx }
+    }
+
+    /// Records the lock operations of every function of the crate and runs the detectors on them.
+    pub fn run(&self) -> LintReport {
+        let functions: Vec<FunctionEvents> = local_functions(self.tcx)
+            .into_iter()
+            .map(|function_id| self.visit_function(function_id))
+            .filter(|function| !function.events.is_empty())
+            .collect();
+        LintReport::new(&functions)
+    }
+
+    /// Records the lock operations in the basic blocks of the function.
+    fn visit_function(&self, function_id: FunctionId) -> FunctionEvents {
+        let function = function_id.name(self.tcx);
+        info!("Linting {function}");
+        let body = function_id.body(self.tcx);
+        let mut visitor = FunctionLinter {
+            tcx: self.tcx,
+            function_id,
+            body,
+            guards: HashMap::new(),
+            events: Vec::new(),
+        };
+        for block in body.basic_blocks.reverse_postorder() {
+            let block = &body.basic_blocks[*block];
+            if block.is_cleanup {
+                continue;
+            }
+            for statement in &block.statements {
+                if let StatementKind::Assign(assign) = &statement.kind {
+                    let (place, rvalue) = &**assign;
+                    visitor.visit_assign(place, rvalue);
+                }
+            }
+            let terminator = block.terminator();
+            match terminator.kind {
+                // The span of the terminator covers the whole call, including the receiver of a method call,
+                // while the `fn_span` starts at the name of the method.
+                TerminatorKind::Call {
+                    ref func,
+                    ref args,
+                    destination,
+                    ..
+                } => visitor.visit_call(func, args, destination, terminator.source_info.span),
+                TerminatorKind::Drop { place, .. } => visitor.release(place.local),
+                _ => {}
+            }
+        }
+        for (type_name, span) in function_id.values_held_across_await(self.tcx) {
+            if GUARD_TYPES.iter().any(|guard| type_name.contains(guard)) {
+                visitor
+                    .events
+                    .push(LockEvent::HeldAcrossAwait { type_name, span });
+            }
+        }
+        FunctionEvents {
+            function,
+            events: visitor.events,
+        }
+    }
+}
+
+/// The state of the visit of a single function.
+struct FunctionLinter<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    function_id: FunctionId,
+    body: &'a Body<'tcx>,
+    /// The locals that hold a guard, mapped to the lock of the guard.
+    guards: HashMap<Local, String>,
+    /// The lock operations recorded so far.
+    events: Vec<LockEvent>,
+}
+
+impl<'tcx> FunctionLinter<'_, 'tcx> {
+    /// Follows a guard moved or copied from one local to another.
+    fn visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>) {
+        if let Rvalue::Use(Operand::Move(source) | Operand::Copy(source)) = rvalue {
+            if source.projection.is_empty() && place.projection.is_empty() {
+                if let Some(lock) = self.guards.remove(&source.local) {
+                    self.guards.insert(place.local, lock);
+                }
+            }
+        }
+    }
+
+    /// Records the lock operation of the call, if any, and follows the guards passed to it.
+    fn visit_call(
+        &mut self,
+        func: &Operand<'tcx>,
+        args: &CallArgs<'tcx>,
+        destination: Place<'tcx>,
+        span: Span,
+    ) {
+        let Some(function_id) = function_id_of_operand(func, self.function_id, self.tcx) else {
+            return;
+        };
+        let function_name = function_id.name(self.tcx);
+        let access = match LockFunction::from_name(&function_name) {
+            Some(LockFunction::MutexLock | LockFunction::RwLockWrite) => {
+                Some(LockAccess::Exclusive)
+            }
+            Some(LockFunction::RwLockRead) => Some(LockAccess::Shared),
+            _ => None,
+        };
+        if let Some(access) = access {
+            let lock = self.receiver(span);
+            self.events.push(LockEvent::Acquire {
+                lock: lock.clone(),
+                access,
+                span: span_info(span, self.tcx),
+            });
+            self.guards.insert(destination.local, lock);
+            return;
+        }
+
+        let moved_guards: Vec<Local> = args
+            .iter()
+            .filter_map(|argument| match argument_operand(argument) {
+                Operand::Move(place) if place.projection.is_empty() => Some(place.local),
+                _ => None,
+            })
+            .filter(|local| self.guards.contains_key(local))
+            .collect();
+        match function_name.as_str() {
+            "std::sync::Condvar::wait" | "std::sync::Condvar::wait_while" => {
+                for local in moved_guards {
+                    let lock = self
+                        .guards
+                        .remove(&local)
+                        .expect("BUG: The moved guard should be in the map");
+                    self.events.push(LockEvent::Wait {
+                        condvar: self.receiver(span),
+                        lock: lock.clone(),
+                        span: span_info(span, self.tcx),
+                    });
+                    self.guards.insert(destination.local, lock);
+                }
+            }
+            _ => {
+                // A guard passed to a function that returns a guard, e.g. `unwrap`, is followed.
+                // Otherwise the function takes ownership of the guard and drops it.
+                let returns_guard = GUARD_TYPES
+                    .iter()
+                    .any(|guard| self.place_type_name(&destination).contains(guard));
+                for local in moved_guards {
+                    if returns_guard {
+                        let lock = self
+                            .guards
+                            .remove(&local)
+                            .expect("BUG: The moved guard should be in the map");
+                        self.guards.insert(destination.local, lock);
+                    } else {
+                        self.release(local);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records the release of the lock if the local holds a guard.
+    fn release(&mut self, local: Local) {
+        if let Some(lock) = self.guards.remove(&local) {
+            self.events.push(LockEvent::Release { lock });
+        }
+    }
+
+    /// Returns the expression on which the method is called in the span of the call,
+    /// see `receiver_expression`. Falls back to the location of the call if the source code is not available.
+    fn receiver(&self, span: Span) -> String {
+        span_snippet(span, self.tcx).map_or_else(
+            || span_info(span, self.tcx).to_string(),
+            |snippet| receiver_expression(&snippet).to_string(),
+        )
+    }
+
+    /// Returns the name of the type of the place in the body of the function.
+    fn place_type_name(&self, place: &Place<'tcx>) -> String {
+        place.ty(self.body, self.tcx).ty.to_string()
+    }
+}
+
+/// Returns the expression on which the method is called, e.g. `self.data` for `self.data.lock()`.
+/// The leading references and dereferences are removed, so `(&*data).lock()` and `data.lock()` are the same.
+/// If the method is not called with the dot syntax, e.g. `Mutex::lock(&data)`, the whole call is returned.
+fn receiver_expression(call: &str) -> &str {
+    let mut depth = 0_usize;
+    let mut last_dot = None;
+    for (index, character) in call.char_indices() {
+        match character {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '.' if depth == 0 => last_dot = Some(index),
+            _ => {}
+        }
+    }
+    let receiver = last_dot.map_or(call, |index| &call[..index]).trim();
+    let receiver = receiver
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+        .unwrap_or(receiver);
+    receiver.trim_start_matches(['&', '*']).trim()
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use super::*;
+
+    #[test]
+    fn receiver_expression_strips_the_method_call() {
+        assert_eq!(receiver_expression("data.lock()"), "data");
+        assert_eq!(
+            receiver_expression("self.state.data.write()"),
+            "self.state.data"
+        );
+        assert_eq!(receiver_expression("cvar.wait(guard.unwrap())"), "cvar");
+        assert_eq!(receiver_expression("(&*data).lock()"), "data");
+        assert_eq!(
+            receiver_expression("Mutex::lock(&data)"),
+            "Mutex::lock(&data)"
+        );
+    }
+}
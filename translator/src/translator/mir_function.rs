@@ -9,6 +9,7 @@
 //!
 //! The basic blocks are indexed by the type `rustc_middle::mir::BasicBlock` in the representation of the body.
 //! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/struct.Body.html>
+//! The `MirFunction` identifies them with the equivalent `BlockId` from the `compiler_interface` module.
 //! The order in which the blocks get visited during the translation is linear.
 //! But the terminators may refer to blocks previously seen or new blocks. Each basic block is visited only once.
 
@@ -17,46 +18,62 @@ pub mod memory;
 mod terminator;
 
 use std::collections::HashMap;
+use std::time::Instant;
 
+use crate::compiler_interface::{BlockId, FunctionId, GenericArgs, List};
 use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef};
 use basic_block::BasicBlock;
+pub use basic_block::BranchKind;
 use memory::Memory;
 
 pub struct MirFunction<'tcx> {
-    /// The ID that uniquely identifies the function in this crate in the HIR representation.
-    /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_hir/def_id/struct.DefId.html>
-    pub def_id: rustc_hir::def_id::DefId,
+    /// The ID that uniquely identifies the function.
+    pub id: FunctionId,
     /// The name of the function as a string.
     pub name: String,
     /// The start place of the function in the Petri net.
     pub start_place: PlaceRef,
     /// The end place of the function in the Petri net.
     pub end_place: PlaceRef,
+    /// The place in the caller where the unwinding of the function continues, i.e. the cleanup block of the call.
+    /// If it is `None`, the unwinding ends the program with a panic.
+    pub cleanup_place: Option<PlaceRef>,
     /// The index of the basic block currently being translated.
-    active_block: Option<rustc_middle::mir::BasicBlock>,
+    active_block: Option<BlockId>,
     /// A mapping between the basic block number and our representation of the basic block.
-    basic_blocks: HashMap<rustc_middle::mir::BasicBlock, BasicBlock>,
+    basic_blocks: HashMap<BlockId, BasicBlock>,
     /// A representation of the memory of the function.
     pub memory: Memory<'tcx>,
+    /// The generic arguments with which the function is called.
+    /// They are empty for the functions that are not generic and for the entry point of a thread.
+    pub generic_args: GenericArgs<'tcx>,
+    /// The number of the function among the functions translated so far, counting from 0.
+    /// Every call to a MIR function is translated again, so it is a different instance.
+    pub instance: usize,
+    /// The moment the translation of the function started, to enforce the `function_time_budget` option.
+    pub started: Instant,
 }
 
 impl MirFunction<'_> {
     /// Creates a new function.
-    /// Uses the `rustc_middle::ty::TyCtxt` to get the MIR body and the name of the function.
     pub fn new(
-        def_id: rustc_hir::def_id::DefId,
+        id: FunctionId,
         function_name: String,
         start_place: PlaceRef,
         end_place: PlaceRef,
     ) -> Self {
         Self {
-            def_id,
+            id,
             name: function_name,
             start_place,
             end_place,
+            cleanup_place: None,
             active_block: None,
             basic_blocks: HashMap::new(),
             memory: Memory::default(),
+            generic_args: List::empty(),
+            instance: 0,
+            started: Instant::now(),
         }
     }
 
@@ -81,7 +98,7 @@ impl MirFunction<'_> {
             self.start_place.clone()
         } else {
             let active_block = self.get_active_block();
-            active_block.place.clone()
+            active_block.end_place.clone()
         }
     }
 
@@ -102,14 +119,13 @@ impl MirFunction<'_> {
     }
 
     /// Adds a new basic block to the function.
-    /// Receives the block number (`rustc_middle::mir::BasicBlock`) which is just an index to a vector
+    /// Receives the block number (`BlockId`) which is just an index to a vector
     /// of `rustc_middle::mir::BasicBlockData` in the MIR body of the function.
-    /// <https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/struct.BasicBlock.html>
     ///
     /// # Panics
     ///
     /// If the block number was already present, then the function panics.
-    fn add_basic_block(&mut self, block_number: rustc_middle::mir::BasicBlock, net: &mut PetriNet) {
+    fn add_basic_block(&mut self, block_number: BlockId, net: &mut PetriNet) {
         // Extracts the value of this index as a usize.
         let index = block_number.index();
         let start_place = self.prepare_start_place_for_next_basic_block();
@@ -125,14 +141,9 @@ impl MirFunction<'_> {
 
     /// Checks if the block number is already present and adds the basic block to the function
     /// if it is not already present. Returns an immutable reference to the basic block.
-    /// Receives the block number (`rustc_middle::mir::BasicBlock`) which is just an index to a vector
+    /// Receives the block number (`BlockId`) which is just an index to a vector
     /// of `rustc_middle::mir::BasicBlockData` in the MIR body of the function.
-    /// <https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/struct.BasicBlock.html>
-    fn get_or_add_basic_block(
-        &mut self,
-        block_number: rustc_middle::mir::BasicBlock,
-        net: &mut PetriNet,
-    ) -> &BasicBlock {
+    fn get_or_add_basic_block(&mut self, block_number: BlockId, net: &mut PetriNet) -> &BasicBlock {
         if !self.basic_blocks.contains_key(&block_number) {
             self.add_basic_block(block_number, net);
         }
@@ -149,7 +160,7 @@ impl MirFunction<'_> {
     /// If either basic block cannot be retrieved, then the function panics.
     fn get_pair_active_block_target_block(
         &mut self,
-        block_number: rustc_middle::mir::BasicBlock,
+        block_number: BlockId,
         net: &mut PetriNet,
     ) -> (&BasicBlock, &BasicBlock) {
         if !self.basic_blocks.contains_key(&block_number) {
@@ -164,18 +175,61 @@ impl MirFunction<'_> {
     }
 
     /// Activates the given basic block. Adds it to the function if it is not present already.
-    pub fn activate_block(&mut self, block: rustc_middle::mir::BasicBlock, net: &mut PetriNet) {
+    pub fn activate_block(&mut self, block: BlockId, net: &mut PetriNet) {
         if !self.basic_blocks.contains_key(&block) {
             self.add_basic_block(block, net);
         };
         self.active_block = Some(block);
     }
 
+    /// Returns the number of the basic block currently being translated, if any.
+    pub const fn active_block(&self) -> Option<BlockId> {
+        self.active_block
+    }
+
+    /// Returns the place of every basic block translated so far, by block number.
+    pub fn block_places(&self) -> impl Iterator<Item = (usize, &PlaceRef)> {
+        self.basic_blocks
+            .iter()
+            .map(|(block, basic_block)| (block.index(), &basic_block.place))
+    }
+
     /// Returns the start place for a function call, i.e., the end place of the current active block.
     /// Clones the place reference to simplify using it.
     pub fn get_start_place_for_function_call(&self) -> PlaceRef {
         let active_block = self.get_active_block();
-        active_block.place.clone()
+        active_block.end_place.clone()
+    }
+
+    /// Appends the statement with the given index to the active basic block, see `BasicBlock::add_statement`.
+    ///
+    /// # Panics
+    ///
+    /// If the active basic block is not set, then the function panics.
+    pub fn add_statement(&mut self, statement_index: usize, net: &mut PetriNet) {
+        let active_block_index = self.active_block.expect(
+            "BUG: Function should have an active basic block set before calling methods that modify it.",
+        );
+        self.basic_blocks
+            .get_mut(&active_block_index)
+            .expect("BUG: The basic block cannot be retrieved. The index for the active block is invalid.")
+            .add_statement(statement_index, net);
+    }
+
+    /// Bounds the executions of the active basic block to `bound`, see `BasicBlock::add_loop_bound`.
+    /// Returns the place with the executions left.
+    ///
+    /// # Panics
+    ///
+    /// If the active basic block is not set, then the function panics.
+    pub fn add_loop_bound(&mut self, bound: usize, net: &mut PetriNet) -> PlaceRef {
+        let active_block_index = self.active_block.expect(
+            "BUG: Function should have an active basic block set before calling methods that modify it.",
+        );
+        self.basic_blocks
+            .get_mut(&active_block_index)
+            .expect("BUG: The basic block cannot be retrieved. The index for the active block is invalid.")
+            .add_loop_bound(bound, net)
     }
 
     /// Returns the end place for a function call, i.e., the start place of the given block number.
@@ -183,7 +237,7 @@ impl MirFunction<'_> {
     /// Clones the place reference to simplify using it.
     pub fn get_end_place_for_function_call(
         &mut self,
-        block_number: rustc_middle::mir::BasicBlock,
+        block_number: BlockId,
         net: &mut PetriNet,
     ) -> PlaceRef {
         let return_block = self.get_or_add_basic_block(block_number, net);
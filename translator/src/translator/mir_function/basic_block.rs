@@ -0,0 +1,164 @@
+//! Representation of a basic block of a MIR function in the Petri net.
+//! For an introduction to MIR see:
+//! <https://rustc-dev-guide.rust-lang.org/mir/index.html>
+//!
+//! The `BasicBlock` stores one reference to its start place in the Petri net and one to its end place,
+//! from which the terminator leaves the block. They are the same place unless the statements
+//! of the block are modelled as transitions, see `Granularity::Statement`.
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, connect_places, PetriNet, PlaceRef, TransitionRef,
+};
+use crate::naming::basic_block::{
+    assert_cleanup_transition_label, assert_transition_label, drop_cleanup_transition_label,
+    drop_transition_label, environment_branch_transition_label, goto_transition_label,
+    loop_bound_place_label, loop_iteration_place_label, loop_iteration_transition_label,
+    nondet_branch_transition_label, place_label, statement_place_label, statement_transition_label,
+    switch_int_transition_label, unreachable_transition_label, unwind_transition_label,
+};
+
+/// The kind of value that a switch int terminator branches on. It determines the labels of the branches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchKind {
+    /// A value computed by the program.
+    Data,
+    /// A nondeterministic choice marked in the source code, see `translator::nondet`.
+    Nondet,
+    /// A value that depends on the environment, see `translator::environment`.
+    Environment,
+}
+
+pub struct BasicBlock {
+    /// Name of the function to which this block belongs.
+    function_name: String,
+    /// Index of this block in the current function.
+    index: usize,
+    /// The start place of this basic block in the Petri net.
+    pub place: PlaceRef,
+    /// The place after the last statement modelled in this basic block, from which the terminator leaves it.
+    pub end_place: PlaceRef,
+}
+
+impl BasicBlock {
+    /// Creates a new basic block and adds its representation to the Petri net.
+    /// Creates a new start place for the basic block if it is not provided.
+    pub fn new(
+        function_name: &str,
+        index: usize,
+        place: Option<PlaceRef>,
+        net: &mut PetriNet,
+    ) -> Self {
+        let place = place.map_or_else(
+            || net.add_place(&place_label(function_name, index)),
+            |place: PlaceRef| place,
+        );
+        Self {
+            function_name: function_name.to_string(),
+            index,
+            end_place: place.clone(),
+            place,
+        }
+    }
+
+    /// Appends a statement to this block: Connects the end place of this block to a new place,
+    /// which becomes the end place of the block.
+    pub fn add_statement(&mut self, statement_index: usize, net: &mut PetriNet) {
+        let place = net.add_place(&statement_place_label(
+            &self.function_name,
+            self.index,
+            statement_index,
+        ));
+        let label = statement_transition_label(&self.function_name, self.index, statement_index);
+        connect_places(net, &self.end_place, &place, &label);
+        self.end_place = place;
+    }
+
+    /// Bounds the executions of this block, e.g. of the header of a loop, to `bound`:
+    /// Connects the end place of this block to a new place, which becomes the end place of the block,
+    /// with a transition that consumes a token from a new place with `bound` tokens.
+    /// Returns the place with the executions left.
+    ///
+    /// # Panics
+    ///
+    /// If the bound overflows the marking of the new place, then the function panics.
+    pub fn add_loop_bound(&mut self, bound: usize, net: &mut PetriNet) -> PlaceRef {
+        let bound_place = net.add_place(&loop_bound_place_label(&self.function_name, self.index));
+        net.add_token(&bound_place, bound).expect(
+            "BUG: Adding the bound to the new loop bound place should not cause an overflow",
+        );
+        let place = net.add_place(&loop_iteration_place_label(&self.function_name, self.index));
+        let label = loop_iteration_transition_label(&self.function_name, self.index);
+        let transition = connect_places(net, &self.end_place, &place, &label);
+        add_arc_place_transition(net, &bound_place, &transition);
+        self.end_place = place;
+        bound_place
+    }
+
+    /// Connects the end place of this block to the start place of the `target` basic block.
+    pub fn goto(&self, target: &Self, net: &mut PetriNet) {
+        let label = goto_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, &target.place, &label);
+    }
+
+    /// Connects the end place of this block to the start place of the `target` basic block.
+    /// Returns the new transition created to connect the two basic blocks.
+    pub fn switch_int(
+        &self,
+        target: &Self,
+        target_index: usize,
+        kind: BranchKind,
+        net: &mut PetriNet,
+    ) -> TransitionRef {
+        let label = match kind {
+            BranchKind::Data => {
+                switch_int_transition_label(&self.function_name, self.index, target_index)
+            }
+            BranchKind::Nondet => {
+                nondet_branch_transition_label(&self.function_name, self.index, target_index)
+            }
+            BranchKind::Environment => {
+                environment_branch_transition_label(&self.function_name, self.index, target_index)
+            }
+        };
+        connect_places(net, &self.end_place, &target.place, &label)
+    }
+
+    /// Connects the end place of this block to the unwind place.
+    /// Returns the new transition created to connect the basic block with the unwind place.
+    pub fn unwind(&self, unwind_place: &PlaceRef, net: &mut PetriNet) -> TransitionRef {
+        let label = unwind_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, unwind_place, &label)
+    }
+
+    /// Connects the end place of this block to the start place of the `target` basic block.
+    /// Returns the new transition created to connect the two basic blocks.
+    pub fn drop(&self, target: &Self, net: &mut PetriNet) -> TransitionRef {
+        let label = drop_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, &target.place, &label)
+    }
+
+    /// Connects the end place of this block to the start place of the `cleanup` basic block.
+    /// Returns the new transition created to connect the two basic blocks.
+    pub fn drop_cleanup(&self, cleanup: &Self, net: &mut PetriNet) -> TransitionRef {
+        let label = drop_cleanup_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, &cleanup.place, &label)
+    }
+
+    /// Connects the end place of this block to the start place of the `assert` basic block.
+    pub fn assert(&self, target: &Self, net: &mut PetriNet) {
+        let label = assert_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, &target.place, &label);
+    }
+
+    /// Connects the end place of this block to the start place of the `cleanup` basic block.
+    pub fn assert_cleanup(&self, cleanup: &Self, net: &mut PetriNet) {
+        let label = assert_cleanup_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, &cleanup.place, &label);
+    }
+
+    /// Connects the end place of this block to the end place.
+    pub fn unreachable(&self, end_place: &PlaceRef, net: &mut PetriNet) {
+        let label = unreachable_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.end_place, end_place, &label);
+    }
+}
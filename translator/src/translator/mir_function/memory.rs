@@ -0,0 +1,998 @@
+//! Representation of the local memory of the function.
+//!
+//! It implements a mapping between
+//! memory places (`rustc_middle::mir::Place`) and a variant of `Value`.
+//!
+//! It is used to keep track of the sync variables
+//! (mutexes, mutex guards, read-write locks and their guards, join handles, condition variables
+//! and the primitives modelled by plugins) in every MIR function.
+//! It also keeps track of the results of loading an atomic variable
+//! and of the discriminants of the results of `lock` for poisoning,
+//! so that branches on them can be modelled, and of the nondeterministic choices marked in the source code.
+//! With the `environment_branches` option, it also keeps track of the locals that depend on the environment,
+//! see `translator::environment`.
+//! Lastly, it keeps track of the closures stored in a place,
+//! so that calls to a closure returned from a function or stored in a `Box`
+//! can be translated with the body of the closure.
+//!
+//! The idea is to mark (link) a place
+//! to a given sync variable when it is created.
+//! When the sync variable gets assigned,
+//! mark the new value as also containing the sync variable.
+//! When the sync variables are packed into a tuple or an `std::sync::Arc`,
+//! create an aggregate and pass it to new threads.
+//! The aggregated value can be accessed
+//! and its fields mapped to the memory of the new function.
+//! A place that accesses a field of an aggregate, e.g. `(*_2).0`,
+//! is resolved to the value of the field even if it was never linked itself.
+//!
+//! More info:
+//! <https://rustc-dev-guide.rust-lang.org/mir/index.html#mir-data-types>
+
+use log::debug;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::compiler_interface::{FunctionId, Local, Place, ProjectionElem};
+use crate::translator::plugin::PluginPrimitive;
+use crate::translator::sync::{
+    Atomic, Barrier, Channel, Condvar, Mutex, MutexGuard, Once, RwLock, RwLockGuard, Thread,
+};
+
+/// A mutex reference is just a shared pointer to the mutex.
+pub type MutexRef = std::rc::Rc<Mutex>;
+
+/// A mutex guard reference is just a shared pointer to the mutex guard.
+pub type MutexGuardRef = std::rc::Rc<MutexGuard>;
+
+/// A read-write lock reference is just a shared pointer to the read-write lock.
+pub type RwLockRef = std::rc::Rc<RwLock>;
+
+/// A read-write lock guard reference is just a shared pointer to the guard.
+pub type RwLockGuardRef = std::rc::Rc<RwLockGuard>;
+
+/// A condvar reference is just a shared pointer to the condition variable.
+pub type CondvarRef = std::rc::Rc<Condvar>;
+
+/// A barrier reference is just a shared pointer to the barrier.
+pub type BarrierRef = std::rc::Rc<Barrier>;
+
+/// A one-time initialization reference is just a shared pointer to the `Once` or `OnceLock`.
+pub type OnceRef = std::rc::Rc<Once>;
+
+/// An atomic variable reference is just a shared pointer to the atomic variable.
+pub type AtomicRef = std::rc::Rc<Atomic>;
+
+/// A channel reference is just a shared pointer to the channel.
+/// It is shared by the sender and the receiver of the channel.
+pub type ChannelRef = std::rc::Rc<Channel>;
+
+/// A thread reference is just a shared pointer to the thread.
+pub type ThreadRef = std::rc::Rc<Thread>;
+
+/// A plugin primitive reference is just a shared pointer to the instance of the primitive modelled by a plugin.
+pub type PluginPrimitiveRef = std::rc::Rc<PluginPrimitive>;
+
+/// Print a debug message about a place that was linked to the same value twice.
+macro_rules! debug_same_type_same_value {
+    ($place:expr, $old_value:expr) => {
+        debug!("PLACE {:?} LINKED AGAIN TO SAME {:?}", $place, $old_value);
+    };
+}
+
+/// Print a debug message about a place that was linked to a different value of the same type.
+macro_rules! debug_same_type_different_value {
+    ($place:expr, $old_value:expr) => {
+        debug!(
+            "PLACE {:?} WAS LINKED TO A DIFFERENT {:?}",
+            $place, $old_value
+        );
+    };
+}
+
+/// Print a debug message about a place that was linked to a value of a different type.
+macro_rules! debug_different_type {
+    ($place:expr, $old_value:expr) => {
+        debug!("PLACE {:?} WAS LINKED TO A {:?}", $place, $old_value);
+    };
+}
+
+#[derive(Default)]
+pub struct Memory<'tcx> {
+    map: HashMap<Place<'tcx>, Value>,
+    closures: HashMap<Place<'tcx>, FunctionId>,
+    /// The locals whose value depends on the environment, e.g. on the command line arguments.
+    environment: HashSet<Local>,
+}
+
+impl<'tcx> Memory<'tcx> {
+    /// Links a given place to a given mutex.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked mutex.
+    pub fn link_mutex(&mut self, place: Place<'tcx>, mutex: Mutex) -> &MutexRef {
+        let mutex_ref = Rc::new(mutex);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Mutex(old_mutex_ref) = old_value {
+                if mutex_ref == *old_mutex_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Mutex(mutex_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_mutex()
+    }
+
+    /// Links a given place to a given mutex guard.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked mutex guard.
+    pub fn link_mutex_guard(
+        &mut self,
+        place: Place<'tcx>,
+        mutex_guard: MutexGuard,
+    ) -> &MutexGuardRef {
+        let mutex_guard_ref = Rc::new(mutex_guard);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::MutexGuard(old_mutex_guard_ref) = old_value {
+                if mutex_guard_ref == *old_mutex_guard_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::MutexGuard(mutex_guard_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_mutex_guard()
+    }
+
+    /// Links a given place to a given read-write lock.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked read-write lock.
+    pub fn link_rwlock(&mut self, place: Place<'tcx>, rwlock: RwLock) -> &RwLockRef {
+        let rwlock_ref = Rc::new(rwlock);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::RwLock(old_rwlock_ref) = old_value {
+                if rwlock_ref == *old_rwlock_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::RwLock(rwlock_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_rwlock()
+    }
+
+    /// Links a given place to a given read-write lock guard.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked read-write lock guard.
+    pub fn link_rwlock_guard(
+        &mut self,
+        place: Place<'tcx>,
+        rwlock_guard: RwLockGuard,
+    ) -> &RwLockGuardRef {
+        let rwlock_guard_ref = Rc::new(rwlock_guard);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::RwLockGuard(old_rwlock_guard_ref) = old_value {
+                if rwlock_guard_ref == *old_rwlock_guard_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::RwLockGuard(rwlock_guard_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_rwlock_guard()
+    }
+
+    /// Links a given place to a given join handle.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked join handle.
+    pub fn link_join_handle(&mut self, place: Place<'tcx>, thread: Thread) -> &ThreadRef {
+        let thread_ref = Rc::new(thread);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::JoinHandle(old_thread_ref) = old_value {
+                if thread_ref == *old_thread_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::JoinHandle(thread_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_join_handle()
+    }
+
+    /// Links a given place to a given condition variable.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked condition variable.
+    pub fn link_condvar(&mut self, place: Place<'tcx>, condvar: Condvar) -> &CondvarRef {
+        let condvar_ref = Rc::new(condvar);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Condvar(old_condvar_ref) = old_value {
+                if condvar_ref == *old_condvar_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Condvar(condvar_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_condvar()
+    }
+
+    /// Links a given place to a given barrier.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked barrier.
+    pub fn link_barrier(&mut self, place: Place<'tcx>, barrier: Barrier) -> &BarrierRef {
+        let barrier_ref = Rc::new(barrier);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Barrier(old_barrier_ref) = old_value {
+                if barrier_ref == *old_barrier_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Barrier(barrier_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_barrier()
+    }
+
+    /// Links a given place to a given instance of a primitive modelled by a plugin.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked instance.
+    pub fn link_plugin_primitive(
+        &mut self,
+        place: Place<'tcx>,
+        primitive: PluginPrimitive,
+    ) -> &PluginPrimitiveRef {
+        let primitive_ref = Rc::new(primitive);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::PluginPrimitive(old_primitive_ref) = old_value {
+                if primitive_ref == *old_primitive_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::PluginPrimitive(primitive_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_plugin_primitive()
+    }
+
+    /// Links a given place to a given one-time initialization.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked one-time initialization.
+    pub fn link_once(&mut self, place: Place<'tcx>, once: Once) -> &OnceRef {
+        let once_ref = Rc::new(once);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Once(old_once_ref) = old_value {
+                if once_ref == *old_once_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Once(once_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_once()
+    }
+
+    /// Links a given place to a given atomic variable.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked atomic variable.
+    pub fn link_atomic(&mut self, place: Place<'tcx>, atomic: Atomic) -> &AtomicRef {
+        let atomic_ref = Rc::new(atomic);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Atomic(old_atomic_ref) = old_value {
+                if atomic_ref == *old_atomic_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Atomic(atomic_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_atomic()
+    }
+
+    /// Links a given place to the result of loading a given atomic variable.
+    /// If `negated` is true, the place contains the negation of the loaded value.
+    /// The place is overwritten if it was already linked, since it is a plain value.
+    pub fn link_atomic_load(&mut self, place: Place<'tcx>, atomic_ref: AtomicRef, negated: bool) {
+        if let Some(old_value) = self
+            .map
+            .insert(place, Value::AtomicLoad(atomic_ref, negated))
+        {
+            debug_different_type!(place, old_value);
+        }
+    }
+
+    /// Links a given place to the discriminant of the result of `lock` for the given mutex,
+    /// i.e. whether the call returned `Ok` or `Err`.
+    /// The place is overwritten if it was already linked, since it is a plain value.
+    pub fn link_lock_result(&mut self, place: Place<'tcx>, mutex_ref: MutexRef) {
+        if let Some(old_value) = self.map.insert(place, Value::LockResult(mutex_ref)) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
+    /// Links a given place to the discriminant of the result of `read` or `write` for the given read-write lock,
+    /// i.e. whether the call returned `Ok` or `Err`.
+    /// The place is overwritten if it was already linked, since it is a plain value.
+    pub fn link_rwlock_result(&mut self, place: Place<'tcx>, rwlock_ref: RwLockRef) {
+        if let Some(old_value) = self.map.insert(place, Value::RwLockResult(rwlock_ref)) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
+    /// Links a given place to a nondeterministic choice, see `translator::nondet`.
+    /// The place is overwritten if it was already linked, since it is a plain value.
+    pub fn link_nondet(&mut self, place: Place<'tcx>) {
+        if let Some(old_value) = self.map.insert(place, Value::Nondet) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
+    /// Links a given place to the pair of sender and receiver of a given channel,
+    /// i.e., the value returned by `std::sync::mpsc::channel`.
+    /// The pair is linked as an aggregate with the sender and the receiver as fields.
+    ///
+    /// # Panics
+    ///
+    /// If the place was already linked, then the function panics.
+    pub fn link_channel(&mut self, place: Place<'tcx>, channel: Channel) {
+        let channel_ref = Rc::new(channel);
+        let values = vec![
+            Value::Sender(channel_ref.clone()),
+            Value::Receiver(channel_ref),
+        ];
+        self.link_aggregate(place, values);
+    }
+
+    /// Links a given place to a given aggregate.
+    ///
+    /// # Panics
+    ///
+    /// If the place was already linked, then the function panics.
+    pub fn link_aggregate(&mut self, place: Place<'tcx>, values: Vec<Value>) {
+        if let Some(old_value) = self.map.insert(place, Value::Aggregate(values)) {
+            panic!(
+                "BUG: There was a previous {old_value} linked to the place for the aggregate value"
+            );
+        }
+    }
+
+    /// Links two places to the same value.
+    /// After this operation, both places point to the same value, i.e.
+    /// the first place is an alias for the second place.
+    ///
+    /// # Panics
+    ///
+    /// If the `place_linked` is not linked to a value, then the function panics.
+    pub fn link_place_to_same_value(
+        &mut self,
+        place_to_link: Place<'tcx>,
+        place_linked: Place<'tcx>,
+    ) {
+        let cloned_value = self.get_linked_value(&place_linked).clone();
+
+        if let Some(old_value) = self.map.insert(place_to_link, cloned_value.clone()) {
+            if old_value == cloned_value {
+                debug_same_type_different_value!(place_to_link, old_value);
+            } else {
+                debug_different_type!(place_to_link, old_value);
+            }
+        }
+        debug!("SAME VALUE: {place_to_link:?} = {place_linked:?}",);
+    }
+
+    /// Links a given place to the closure with the given ID.
+    /// Prints a debug message if the place was already linked to a closure.
+    pub fn link_closure(&mut self, place: Place<'tcx>, function_id: FunctionId) {
+        if let Some(old_function_id) = self.closures.insert(place, function_id) {
+            debug!("PLACE {place:?} WAS LINKED TO THE CLOSURE {old_function_id:?}");
+        }
+        debug!("NEW CLOSURE {function_id:?} AT {place:?}");
+    }
+
+    /// Links the place to the closure linked to another place, if there is one.
+    /// The sync variables captured by the closure are linked too,
+    /// unless the place is already linked to a value.
+    /// This covers the types that hide the closure, e.g. `Box<dyn Fn()>`.
+    pub fn link_place_to_same_closure(
+        &mut self,
+        place_to_link: Place<'tcx>,
+        place_linked: Place<'tcx>,
+    ) {
+        let Some(function_id) = self.get_closure(&place_linked) else {
+            return;
+        };
+        self.link_closure(place_to_link, function_id);
+        if self.is_linked(&place_linked) && !self.is_linked(&place_to_link) {
+            self.link_place_to_same_value(place_to_link, place_linked);
+        }
+    }
+
+    /// Returns the ID of the closure linked to the given place, if there is one.
+    pub fn get_closure(&self, place: &Place<'tcx>) -> Option<FunctionId> {
+        self.closures.get(place).copied()
+    }
+
+    /// Returns an immutable reference to the value linked to the given place.
+    ///
+    /// # Panics
+    ///
+    /// If the place is not linked to a value, then the function panics.
+    fn get_linked_value(&self, place: &Place<'tcx>) -> &Value {
+        self.find_value(place)
+            .unwrap_or_else(|| panic!("BUG: The place {place:?} should be linked to a value"))
+    }
+
+    /// Returns the value linked to the given place, if there is one.
+    ///
+    /// If the place itself is not linked, the value is looked up through its projections.
+    /// The longest prefix of the place that is linked gives the base value.
+    /// Then every field projection selects the field of the aggregate and every dereference is skipped,
+    /// since a reference is linked to the same value as the place it points to.
+    /// For instance, `(*_2).0.1` is the second field of the first field of the aggregate linked to `_2`.
+    /// This covers the fields of nested `struct`s and the disjoint captures of closures in edition 2021,
+    /// e.g. `move (_1.0)` for a closure that captures only `state.data`.
+    /// The result of `lock` is linked to the guard itself, so the field of a variant of it is the same guard,
+    /// e.g. `((_9 as Ok).0)` in a `match` on the result.
+    ///
+    /// Returns `None` if no prefix is linked, the projection is not supported or the field is untracked.
+    fn find_value(&self, place: &Place<'tcx>) -> Option<&Value> {
+        let (base_place, mut value) = match self.map.get_key_value(place) {
+            Some(entry) => entry,
+            None => self
+                .map
+                .iter()
+                .filter(|(linked, _)| {
+                    linked.local == place.local && place.projection.starts_with(linked.projection)
+                })
+                .max_by_key(|(linked, _)| linked.projection.len())?,
+        };
+        let mut projection = place.projection[base_place.projection.len()..].iter();
+        while let Some(elem) = projection.next() {
+            value = match (elem, value) {
+                (ProjectionElem::Deref, _) => value,
+                (ProjectionElem::Downcast(..), Value::Aggregate(_)) => return None,
+                (ProjectionElem::Downcast(..), _) => {
+                    projection.next();
+                    value
+                }
+                (ProjectionElem::Field(field, _), Value::Aggregate(values)) => {
+                    values.get(field.as_usize())?
+                }
+                _ => return None,
+            };
+        }
+        (!matches!(value, Value::Untracked)).then_some(value)
+    }
+
+    /// Returns a reference to the mutex linked to the given place.
+    pub fn get_mutex(&self, place: &Place<'tcx>) -> &MutexRef {
+        self.get_linked_value(place).unpack_mutex()
+    }
+
+    /// Returns a reference to the mutex guard linked to the given place.
+    pub fn get_mutex_guard(&self, place: &Place<'tcx>) -> &MutexGuardRef {
+        self.get_linked_value(place).unpack_mutex_guard()
+    }
+
+    /// Returns a reference to the read-write lock linked to the given place.
+    pub fn get_rwlock(&self, place: &Place<'tcx>) -> &RwLockRef {
+        self.get_linked_value(place).unpack_rwlock()
+    }
+
+    /// Returns a reference to the join handle linked to the given place.
+    pub fn get_join_handle(&self, place: &Place<'tcx>) -> &ThreadRef {
+        self.get_linked_value(place).unpack_join_handle()
+    }
+
+    /// Returns a reference to the condition variable linked to the given place.
+    pub fn get_condvar(&self, place: &Place<'tcx>) -> &CondvarRef {
+        self.get_linked_value(place).unpack_condvar()
+    }
+
+    /// Returns a reference to the channel of the pair of sender and receiver linked to the given place.
+    ///
+    /// # Panics
+    ///
+    /// If the place is not linked to the aggregate returned by `std::sync::mpsc::channel`, then the function panics.
+    pub fn get_channel(&self, place: &Place<'tcx>) -> &ChannelRef {
+        self.get_linked_value(place)
+            .unpack_aggregate()
+            .first()
+            .unwrap_or_else(|| panic!("BUG: The place {place:?} should be linked to a sender"))
+            .unpack_sender()
+    }
+
+    /// Returns a reference to the channel of the sender linked to the given place.
+    pub fn get_sender(&self, place: &Place<'tcx>) -> &ChannelRef {
+        self.get_linked_value(place).unpack_sender()
+    }
+
+    /// Returns a reference to the channel of the receiver linked to the given place.
+    pub fn get_receiver(&self, place: &Place<'tcx>) -> &ChannelRef {
+        self.get_linked_value(place).unpack_receiver()
+    }
+
+    /// Returns a reference to the barrier linked to the given place.
+    pub fn get_barrier(&self, place: &Place<'tcx>) -> &BarrierRef {
+        self.get_linked_value(place).unpack_barrier()
+    }
+
+    /// Returns a reference to the instance of a primitive modelled by a plugin linked to the given place.
+    pub fn get_plugin_primitive(&self, place: &Place<'tcx>) -> &PluginPrimitiveRef {
+        self.get_linked_value(place).unpack_plugin_primitive()
+    }
+
+    /// Returns a reference to the one-time initialization linked to the given place.
+    pub fn get_once(&self, place: &Place<'tcx>) -> &OnceRef {
+        self.get_linked_value(place).unpack_once()
+    }
+
+    /// Returns a reference to the atomic variable linked to the given place.
+    pub fn get_atomic(&self, place: &Place<'tcx>) -> &AtomicRef {
+        self.get_linked_value(place).unpack_atomic()
+    }
+
+    /// Returns the atomic variable whose loaded value is linked to the given place
+    /// and whether the value is negated.
+    /// If the place is not linked to the result of a `load`, returns `None`.
+    pub fn get_atomic_load(&self, place: &Place<'tcx>) -> Option<(&AtomicRef, bool)> {
+        match self.map.get(place)? {
+            Value::AtomicLoad(atomic_ref, negated) => Some((atomic_ref, *negated)),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutex whose result of `lock` is linked to the given place, see `link_lock_result`.
+    /// If the place is not linked to the discriminant of the result of a `lock`, returns `None`.
+    pub fn get_lock_result(&self, place: &Place<'tcx>) -> Option<&MutexRef> {
+        match self.map.get(place)? {
+            Value::LockResult(mutex_ref) => Some(mutex_ref),
+            _ => None,
+        }
+    }
+
+    /// Returns the read-write lock whose result of `read` or `write` is linked to the given place,
+    /// see `link_rwlock_result`.
+    /// If the place is not linked to the discriminant of the result of a `read` or `write`, returns `None`.
+    pub fn get_rwlock_result(&self, place: &Place<'tcx>) -> Option<&RwLockRef> {
+        match self.map.get(place)? {
+            Value::RwLockResult(rwlock_ref) => Some(rwlock_ref),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the place is linked to a nondeterministic choice, see `link_nondet`.
+    pub fn is_nondet(&self, place: &Place<'tcx>) -> bool {
+        matches!(self.map.get(place), Some(Value::Nondet))
+    }
+
+    /// Marks the local of the given place as depending on the environment, see `translator::environment`.
+    pub fn mark_environment(&mut self, place: Place<'tcx>) {
+        self.environment.insert(place.local);
+    }
+
+    /// Checks whether the local of the given place depends on the environment, see `mark_environment`.
+    pub fn depends_on_environment(&self, place: &Place<'tcx>) -> bool {
+        self.environment.contains(&place.local)
+    }
+
+    /// Returns the vector of values contained inside the aggregate linked to the given place.
+    /// The vector is copied for the caller since the value may be used later by this function.
+    ///
+    /// # Panics
+    ///
+    /// If the place is not linked to a value, then the function panics.
+    pub fn copy_aggregate(&self, place: &Place<'tcx>) -> Vec<Value> {
+        self.get_linked_value(place).unpack_aggregate().clone()
+    }
+
+    /// Checks whether the place is linked to a value.
+    pub fn is_linked(&self, place: &Place<'tcx>) -> bool {
+        self.find_value(place).is_some()
+    }
+
+    /// Checks whether the place is linked to an aggregate.
+    /// The type of a `struct` defined in the code does not reveal whether it contains sync variables,
+    /// but the aggregate created for it does.
+    pub fn is_aggregate(&self, place: &Place<'tcx>) -> bool {
+        matches!(self.find_value(place), Some(Value::Aggregate(_)))
+    }
+
+    /// Returns a copy of the value linked to the given place, if there is one.
+    pub fn copy_value(&self, place: &Place<'tcx>) -> Option<Value> {
+        self.find_value(place).cloned()
+    }
+
+    /// Links a given place to a value, e.g. a value passed from the memory of another function.
+    /// Prints a debug message if the place was already linked.
+    pub fn link_value(&mut self, place: Place<'tcx>, value: Value) {
+        if let Some(old_value) = self.map.insert(place, value) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
+    /// Returns the labels of the sync variables linked to the given place, e.g. `MUTEX_0`.
+    /// If the place is linked to an aggregate, the labels of the sync variables in its fields are returned.
+    /// A guard returns the label of its lock, a sender or a receiver the label of its channel.
+    pub fn get_sync_labels(&self, place: &Place<'tcx>) -> Vec<String> {
+        let mut labels = Vec::new();
+        if let Some(value) = self.find_value(place) {
+            value.collect_labels(&mut labels);
+        }
+        labels
+    }
+
+    /// Returns the mutex guards linked to the given place.
+    /// If the place is linked to an aggregate, e.g. a `struct` with a field holding a guard,
+    /// the guards in the fields are returned, including those of nested aggregates.
+    pub fn get_mutex_guards(&self, place: &Place<'tcx>) -> Vec<&MutexGuardRef> {
+        let mut guards = Vec::new();
+        if let Some(value) = self.find_value(place) {
+            value.collect_mutex_guards(&mut guards);
+        }
+        guards
+    }
+
+    /// Returns the read-write lock guards linked to the given place.
+    /// If the place is linked to an aggregate, the guards in the fields are returned, see `get_mutex_guards`.
+    pub fn get_rwlock_guards(&self, place: &Place<'tcx>) -> Vec<&RwLockGuardRef> {
+        let mut guards = Vec::new();
+        if let Some(value) = self.find_value(place) {
+            value.collect_rwlock_guards(&mut guards);
+        }
+        guards
+    }
+
+    /// Creates a new aggregate value from the values linked to a vector of places.
+    /// The fields without a place are not tracked, see `Value::Untracked`.
+    /// Links the new aggregate value to the given place.
+    ///
+    /// # Panics
+    ///
+    /// If there is a value linked to the place for the aggregate, then the function panics.
+    pub fn create_aggregate(
+        &mut self,
+        place: Place<'tcx>,
+        places_to_aggregate: &[Option<Place<'tcx>>],
+    ) {
+        let mut values: Vec<Value> = Vec::new();
+
+        for place in places_to_aggregate {
+            let value = place.map_or(Value::Untracked, |place| {
+                self.get_linked_value(&place).clone()
+            });
+            values.push(value);
+        }
+
+        self.link_aggregate(place, values);
+    }
+
+    /// Links the field of an aggregate to a given place.
+    /// This is equivalent to moving out the value from the aggregate
+    /// and linking it to the place.
+    ///
+    /// # Panics
+    ///
+    /// If the place if not linked to an aggregate, then the function panics.
+    /// If the index is out of bounds, then the function panics.
+    pub fn link_field_in_aggregate(
+        &mut self,
+        place_to_link: Place<'tcx>,
+        place_linked: Place<'tcx>,
+        index: usize,
+    ) {
+        let values = self
+            .map
+            .get_mut(&place_linked)
+            .unwrap_or_else(|| {
+                panic!("BUG: The place {place_linked:?} should be linked to a value")
+            })
+            .unpack_aggregate();
+        let value = values
+            .get(index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "BUG: The place {place_linked:?} does not contain a field with index {index}"
+                )
+            })
+            .clone();
+
+        assert!(self.map.insert(place_to_link, value).is_none(), "BUG: Could not link the field of {place_linked:?} with index {index} because {place_to_link:?} was already linked");
+    }
+}
+
+/// Possible values that can be stored in the `Memory`.
+/// A place will be mapped to one of these.
+#[derive(PartialEq, Clone)]
+pub enum Value {
+    Mutex(MutexRef),
+    MutexGuard(MutexGuardRef),
+    RwLock(RwLockRef),
+    RwLockGuard(RwLockGuardRef),
+    JoinHandle(ThreadRef),
+    Condvar(CondvarRef),
+    Barrier(BarrierRef),
+    Once(OnceRef),
+    PluginPrimitive(PluginPrimitiveRef),
+    Atomic(AtomicRef),
+    AtomicLoad(AtomicRef, bool),
+    LockResult(MutexRef),
+    RwLockResult(RwLockRef),
+    /// A nondeterministic choice marked in the source code, e.g. the result of `rand::random::<bool>`.
+    Nondet,
+    Sender(ChannelRef),
+    Receiver(ChannelRef),
+    Aggregate(Vec<Value>),
+    /// A field of an aggregate that does not contain a sync variable, e.g. an integer.
+    /// It keeps the indices of the other fields aligned with the field numbers in the MIR.
+    Untracked,
+}
+
+impl Value {
+    fn unpack_mutex(&self) -> &MutexRef {
+        match self {
+            Self::Mutex(mutex_ref) => mutex_ref,
+            _ => panic!("BUG: The value does not contain a mutex, it contains a {self}."),
+        }
+    }
+
+    fn unpack_mutex_guard(&self) -> &MutexGuardRef {
+        match self {
+            Self::MutexGuard(mutex_guard_ref) => mutex_guard_ref,
+            _ => panic!("BUG: The value does not contain a mutex guard, it contains a {self}."),
+        }
+    }
+
+    fn unpack_rwlock(&self) -> &RwLockRef {
+        match self {
+            Self::RwLock(rwlock_ref) => rwlock_ref,
+            _ => panic!("BUG: The value does not contain a read-write lock, it contains a {self}."),
+        }
+    }
+
+    fn unpack_rwlock_guard(&self) -> &RwLockGuardRef {
+        match self {
+            Self::RwLockGuard(rwlock_guard_ref) => rwlock_guard_ref,
+            _ => panic!(
+                "BUG: The value does not contain a read-write lock guard, it contains a {self}."
+            ),
+        }
+    }
+
+    fn unpack_join_handle(&self) -> &ThreadRef {
+        match self {
+            Self::JoinHandle(thread_ref) => thread_ref,
+            _ => panic!("BUG: The value does not contain a join handle, it contains a {self}."),
+        }
+    }
+
+    fn unpack_condvar(&self) -> &CondvarRef {
+        match self {
+            Self::Condvar(condvar_ref) => condvar_ref,
+            _ => panic!(
+                "BUG: The value does not contain a condition variable, it contains a {self}."
+            ),
+        }
+    }
+
+    fn unpack_barrier(&self) -> &BarrierRef {
+        match self {
+            Self::Barrier(barrier_ref) => barrier_ref,
+            _ => panic!("BUG: The value does not contain a barrier, it contains a {self}."),
+        }
+    }
+
+    fn unpack_plugin_primitive(&self) -> &PluginPrimitiveRef {
+        match self {
+            Self::PluginPrimitive(primitive_ref) => primitive_ref,
+            _ => panic!(
+                "BUG: The value does not contain a primitive modelled by a plugin, it contains a {self}."
+            ),
+        }
+    }
+
+    fn unpack_once(&self) -> &OnceRef {
+        match self {
+            Self::Once(once_ref) => once_ref,
+            _ => panic!(
+                "BUG: The value does not contain a one-time initialization, it contains a {self}."
+            ),
+        }
+    }
+
+    fn unpack_atomic(&self) -> &AtomicRef {
+        match self {
+            Self::Atomic(atomic_ref) => atomic_ref,
+            _ => {
+                panic!("BUG: The value does not contain an atomic variable, it contains a {self}.")
+            }
+        }
+    }
+
+    fn unpack_sender(&self) -> &ChannelRef {
+        match self {
+            Self::Sender(channel_ref) => channel_ref,
+            _ => panic!("BUG: The value does not contain a sender, it contains a {self}."),
+        }
+    }
+
+    fn unpack_receiver(&self) -> &ChannelRef {
+        match self {
+            Self::Receiver(channel_ref) => channel_ref,
+            _ => panic!("BUG: The value does not contain a receiver, it contains a {self}."),
+        }
+    }
+
+    fn collect_mutex_guards<'a>(&'a self, guards: &mut Vec<&'a MutexGuardRef>) {
+        match self {
+            Self::MutexGuard(mutex_guard_ref) => guards.push(mutex_guard_ref),
+            Self::Aggregate(values) => {
+                for value in values {
+                    value.collect_mutex_guards(guards);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_rwlock_guards<'a>(&'a self, guards: &mut Vec<&'a RwLockGuardRef>) {
+        match self {
+            Self::RwLockGuard(rwlock_guard_ref) => guards.push(rwlock_guard_ref),
+            Self::Aggregate(values) => {
+                for value in values {
+                    value.collect_rwlock_guards(guards);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_labels(&self, labels: &mut Vec<String>) {
+        match self {
+            Self::Mutex(mutex_ref) => labels.push(mutex_ref.label()),
+            Self::MutexGuard(mutex_guard_ref) => labels.push(mutex_guard_ref.mutex.label()),
+            Self::RwLock(rwlock_ref) => labels.push(rwlock_ref.label()),
+            Self::RwLockGuard(rwlock_guard_ref) => labels.push(rwlock_guard_ref.rwlock.label()),
+            Self::JoinHandle(thread_ref) => labels.push(thread_ref.label()),
+            Self::Condvar(condvar_ref) => labels.push(condvar_ref.label().to_string()),
+            Self::Barrier(barrier_ref) => labels.push(barrier_ref.label().to_string()),
+            Self::Once(once_ref) => labels.push(once_ref.label().to_string()),
+            Self::PluginPrimitive(primitive_ref) => labels.push(primitive_ref.label().to_string()),
+            Self::Atomic(atomic_ref) => labels.push(atomic_ref.label().to_string()),
+            Self::Sender(channel_ref) | Self::Receiver(channel_ref) => {
+                labels.push(channel_ref.label().to_string());
+            }
+            Self::Aggregate(values) => {
+                for value in values {
+                    value.collect_labels(labels);
+                }
+            }
+            // The derived values are not aliased with the sync variable itself.
+            Self::AtomicLoad(..)
+            | Self::LockResult(_)
+            | Self::RwLockResult(_)
+            | Self::Nondet
+            | Self::Untracked => {}
+        }
+    }
+
+    fn unpack_aggregate(&self) -> &Vec<Self> {
+        match self {
+            Self::Aggregate(values) => values,
+            _ => {
+                panic!("BUG: The value does not contain an aggregate, it contains a {self}.")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mutex(_) => write!(f, "mutex"),
+            Self::MutexGuard(_) => write!(f, "mutex guard"),
+            Self::RwLock(_) => write!(f, "read-write lock"),
+            Self::RwLockGuard(_) => write!(f, "read-write lock guard"),
+            Self::JoinHandle(_) => write!(f, "join handle"),
+            Self::Condvar(_) => write!(f, "condition variable"),
+            Self::Barrier(_) => write!(f, "barrier"),
+            Self::Once(_) => write!(f, "one-time initialization"),
+            Self::PluginPrimitive(_) => write!(f, "primitive modelled by a plugin"),
+            Self::Atomic(_) => write!(f, "atomic variable"),
+            Self::AtomicLoad(..) => write!(f, "loaded value of an atomic variable"),
+            Self::LockResult(_) => write!(f, "discriminant of the result of a lock"),
+            Self::RwLockResult(_) => {
+                write!(f, "discriminant of the result of a read-write lock")
+            }
+            Self::Nondet => write!(f, "nondeterministic choice"),
+            Self::Sender(_) => write!(f, "sender"),
+            Self::Receiver(_) => write!(f, "receiver"),
+            Self::Aggregate(_) => write!(f, "aggregate"),
+            Self::Untracked => write!(f, "untracked value"),
+        }
+    }
+}
+
+impl std::fmt::Debug for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mutex(_) => write!(f, "MUTEX"),
+            Self::MutexGuard(_) => write!(f, "MUTEX GUARD"),
+            Self::RwLock(_) => write!(f, "RWLOCK"),
+            Self::RwLockGuard(_) => write!(f, "RWLOCK GUARD"),
+            Self::JoinHandle(_) => write!(f, "JOIN HANDLE"),
+            Self::Condvar(_) => write!(f, "CONDITION VARIABLE"),
+            Self::Barrier(_) => write!(f, "BARRIER"),
+            Self::Once(_) => write!(f, "ONCE"),
+            Self::PluginPrimitive(_) => write!(f, "PLUGIN PRIMITIVE"),
+            Self::Atomic(_) => write!(f, "ATOMIC"),
+            Self::AtomicLoad(..) => write!(f, "ATOMIC LOAD"),
+            Self::LockResult(_) => write!(f, "LOCK RESULT"),
+            Self::RwLockResult(_) => write!(f, "RWLOCK RESULT"),
+            Self::Nondet => write!(f, "NONDET"),
+            Self::Sender(_) => write!(f, "SENDER"),
+            Self::Receiver(_) => write!(f, "RECEIVER"),
+            Self::Aggregate(_) => write!(f, "AGGREGATE"),
+            Self::Untracked => write!(f, "UNTRACKED"),
+        }
+    }
+}
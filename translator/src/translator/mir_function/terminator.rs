@@ -1,8 +1,9 @@
 //! Submodule that defines the methods for handling each of the possible terminators for a basic block.
 //! <https://doc.rust-lang.org/nightly/nightly-rustc/rustc_middle/mir/enum.TerminatorKind.html>
 
-use super::MirFunction;
+use super::{BasicBlock, BranchKind, MirFunction};
 
+use crate::compiler_interface::BlockId;
 use crate::data_structures::petri_net_interface::{
     connect_places, PetriNet, PlaceRef, TransitionRef,
 };
@@ -14,25 +15,52 @@ impl MirFunction<'_> {
     /// # Panics
     ///
     /// If there is no active basic block set, then the function panics.
-    pub fn goto(&mut self, target: rustc_middle::mir::BasicBlock, net: &mut PetriNet) {
+    pub fn goto(&mut self, target: BlockId, net: &mut PetriNet) {
         let (active_block, target_block) = self.get_pair_active_block_target_block(target, net);
         active_block.goto(target_block, net);
     }
 
+    /// Merges the target basic block into the active basic block, i.e. the target block starts
+    /// at the end place of the active block without a transition in between.
+    /// If the target basic block is present already, e.g. for the back edge of a loop,
+    /// connects the blocks with a transition like `goto`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no active basic block set, then the function panics.
+    pub fn merge_goto_target(&mut self, target: BlockId, net: &mut PetriNet) {
+        if self.basic_blocks.contains_key(&target) {
+            self.goto(target, net);
+            return;
+        }
+        let place = self.get_active_block().end_place.clone();
+        let basic_block = BasicBlock::new(&self.name, target.index(), Some(place), net);
+        self.basic_blocks.insert(target, basic_block);
+    }
+
     /// Connects the active basic block to all the possible basic block targets in the switch int statement.
     /// This models the execution flow taking every possible path.
     /// Adds the corresponding block if it is not present already.
+    /// Returns the transitions that lead to each target, in the same order as the targets.
+    /// The kind of value branched on determines the labels of the transitions.
     ///
     /// # Panics
     ///
     /// If there is no active basic block set, then the function panics.
-    pub fn switch_int(&mut self, targets: Vec<rustc_middle::mir::BasicBlock>, net: &mut PetriNet) {
+    pub fn switch_int(
+        &mut self,
+        targets: Vec<BlockId>,
+        kind: BranchKind,
+        net: &mut PetriNet,
+    ) -> Vec<TransitionRef> {
+        let mut transitions = Vec::with_capacity(targets.len());
         for basic_block in targets {
             let (active_block, target_block) =
                 self.get_pair_active_block_target_block(basic_block, net);
             let index = basic_block.index();
-            active_block.switch_int(target_block, index, net);
+            transitions.push(active_block.switch_int(target_block, index, kind, net));
         }
+        transitions
     }
 
     /// Connects the active basic block to a given unwind place that models a `panic!` scenario or similar.
@@ -58,8 +86,8 @@ impl MirFunction<'_> {
     /// If there is no active basic block set, then the function panics.
     pub fn drop(
         &mut self,
-        target: rustc_middle::mir::BasicBlock,
-        cleanup: Option<rustc_middle::mir::BasicBlock>,
+        target: BlockId,
+        cleanup: Option<BlockId>,
         net: &mut PetriNet,
     ) -> (TransitionRef, Option<TransitionRef>) {
         let (active_block, target_block) = self.get_pair_active_block_target_block(target, net);
@@ -80,12 +108,7 @@ impl MirFunction<'_> {
     /// # Panics
     ///
     /// If there is no active basic block set, then the function panics.
-    pub fn assert(
-        &mut self,
-        target: rustc_middle::mir::BasicBlock,
-        cleanup: Option<rustc_middle::mir::BasicBlock>,
-        net: &mut PetriNet,
-    ) {
+    pub fn assert(&mut self, target: BlockId, cleanup: Option<BlockId>, net: &mut PetriNet) {
         let (active_block, target_block) = self.get_pair_active_block_target_block(target, net);
         active_block.assert(target_block, net);
 
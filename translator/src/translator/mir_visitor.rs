@@ -0,0 +1,412 @@
+//! Implementation of the trait `rustc_middle::mir::visit::Visitor` for Translator.
+//! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/visit/trait.Visitor.html>
+//!
+//! This trait defines a method for visiting every possible MIR element.
+//! It is not required to implement every method, only for the elements we care about.
+//! For an introduction to MIR see:
+//! <https://rustc-dev-guide.rust-lang.org/mir/index.html>
+
+use super::closure::handle_closure_assignment;
+use super::environment::handle_environment_assignment;
+use super::mir_function::BranchKind;
+use super::nondet::handle_nondet_assignment;
+use super::sync::{atomic, handle_aggregate_assignment, link_if_sync_variable, mutex, rwlock};
+use super::unsafe_escape;
+use super::{TranslationError, Translator};
+#[cfg(rustc_tail_call_terminator)]
+use crate::compiler_interface::TerminatorKind::TailCall;
+use crate::compiler_interface::TerminatorKind::{
+    Assert, Call, CoroutineDrop, Drop, FalseEdge, FalseUnwind, Goto, InlineAsm, Return, SwitchInt,
+    Unreachable, UnwindResume, UnwindTerminate, Yield,
+};
+use crate::compiler_interface::{
+    span_info, BasicBlock, BasicBlockData, Location, NonDivergingIntrinsic, Operand, Place, Rvalue,
+    Statement, StatementKind, Terminator, UnwindAction, Visitor,
+};
+use crate::options::Granularity;
+
+impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
+    /// Entering a new basic block of the current MIR function.
+    /// Activate it. This is the first step to start processing it.
+    /// The places added for the block record the span of its first statement as their origin.
+    /// If the `block_mapping` option is set, the nodes added for the block are recorded afterwards.
+    /// If the `label_map` option is set, the place of the block is recorded with its first MIR element.
+    /// If the `function_time_budget` option is set and the function exceeded it, the translation of the function stops
+    /// with a `TranslationError`, so that the function is skipped when translating again.
+    /// Once the translation of the function stopped, the remaining blocks are not visited.
+    fn visit_basic_block_data(&mut self, block: BasicBlock, data: &BasicBlockData<'tcx>) {
+        if self.failure.is_some() {
+            return;
+        }
+        let function = self.call_stack.peek_mut();
+        if let Some(budget) = self.options.function_time_budget {
+            if function.started.elapsed() > budget {
+                self.failure = Some(TranslationError::TimeBudgetExceeded(budget));
+                return;
+            }
+        }
+        let start = self
+            .block_mapping
+            .as_ref()
+            .map(|record| record.start_block(&self.net));
+        // The block is added with the span of its first statement, or of its terminator if it has none.
+        let first_statement = data.statements.first();
+        let span = first_statement.map_or(data.terminator().source_info.span, |statement| {
+            statement.source_info.span
+        });
+        let span = span_info(span, self.tcx);
+        let added_from = self.net.node_counts();
+        self.net.set_current_span(Some(span.clone()));
+        function.activate_block(block.into(), &mut self.net);
+        // Every iteration of a loop starts at its header.
+        if let Some(bound) = self.options.loop_bound {
+            if function.id.is_loop_header(block.into(), self.tcx) {
+                let place = function.add_loop_bound(bound, &mut self.net);
+                self.bounded_places.push(place.label().to_string());
+            }
+        }
+        self.net.set_current_span(None);
+        if let Some(record) = &mut self.label_map {
+            let mir = first_statement.map_or_else(
+                || format!("{:?}", data.terminator().kind),
+                |statement| format!("{statement:?}"),
+            );
+            record.record(added_from, &self.net, &function.name, &span, &mir);
+        }
+
+        self.super_basic_block_data(block, data);
+
+        if let (Some(record), Some(start)) = (&mut self.block_mapping, start) {
+            let terminator = data.terminator();
+            let block_places = self
+                .call_stack
+                .peek()
+                .block_places()
+                .map(|(block, place)| (block, place.label().to_string()))
+                .collect();
+            record.finish_block(
+                start,
+                block.index(),
+                terminator.kind.name(),
+                span_info(terminator.source_info.span, self.tcx),
+                &block_places,
+                &self.net,
+            );
+        }
+    }
+
+    /// With the statement granularity, model every statement of the active block as a transition,
+    /// except for the storage markers and the no-ops, which only add noise to the net.
+    /// A `copy_nonoverlapping` through raw pointers may alias the sync variables, see `unsafe_escape`.
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        if let StatementKind::Intrinsic(intrinsic) = &statement.kind {
+            if let NonDivergingIntrinsic::CopyNonOverlapping(copy) = intrinsic.as_ref() {
+                let places: Vec<Place> = [&copy.src, &copy.dst]
+                    .into_iter()
+                    .filter_map(Operand::place)
+                    .collect();
+                let function = self.call_stack.peek();
+                unsafe_escape::mark_unknown_aliasing(
+                    "copy_nonoverlapping",
+                    &places,
+                    &function.memory,
+                    &mut self.registry,
+                    function.id,
+                    statement.source_info.span,
+                    self.tcx,
+                );
+            }
+        }
+        if self.options.granularity == Granularity::Statement
+            && !matches!(
+                statement.kind,
+                StatementKind::StorageLive(_) | StatementKind::StorageDead(_) | StatementKind::Nop
+            )
+        {
+            let span = span_info(statement.source_info.span, self.tcx);
+            let added_from = self.net.node_counts();
+            self.net.set_current_span(Some(span.clone()));
+            self.call_stack
+                .peek_mut()
+                .add_statement(location.statement_index, &mut self.net);
+            self.net.set_current_span(None);
+            if let Some(record) = &mut self.label_map {
+                let function = &self.call_stack.peek().name;
+                record.record(
+                    added_from,
+                    &self.net,
+                    function,
+                    &span,
+                    &format!("{statement:?}"),
+                );
+            }
+        }
+        self.super_statement(statement, location);
+    }
+
+    /// Keep track of synchronization variables in assignments
+    /// (mutexes, mutex guards, join handles and condition variables).
+    /// The idea is to link the right-hand side with the left-hand side of the assignment
+    /// if a synchronization variable is involved.
+    /// The values derived from loading an atomic variable or from a nondeterministic choice and the closures
+    /// are tracked as well, and with the `model_poisoning` option also the discriminants of the results of `lock`.
+    /// With the `environment_branches` option, the values that depend on the environment are tracked too.
+    /// A transmute or a pointer cast may alias the sync variables, see `unsafe_escape`.
+    fn visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        let function = self.call_stack.peek();
+        if let Some((operation, operand)) =
+            unsafe_escape::escaping_cast(rvalue, function.id, self.tcx)
+        {
+            let span = function.id.body(self.tcx).source_info(location).span;
+            unsafe_escape::mark_unknown_aliasing(
+                operation,
+                &[operand],
+                &function.memory,
+                &mut self.registry,
+                function.id,
+                span,
+                self.tcx,
+            );
+        }
+        atomic::handle_load_assignment(place, rvalue, &mut self.call_stack.peek_mut().memory);
+        handle_nondet_assignment(place, rvalue, &mut self.call_stack.peek_mut().memory);
+        if self.options.environment_branches {
+            let memory = &mut self.call_stack.peek_mut().memory;
+            handle_environment_assignment(place, rvalue, memory);
+        }
+        if self.options.model_poisoning {
+            let memory = &mut self.call_stack.peek_mut().memory;
+            mutex::handle_discriminant_assignment(place, rvalue, memory);
+            rwlock::handle_discriminant_assignment(place, rvalue, memory);
+        }
+        match rvalue {
+            Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs))
+            | Rvalue::Ref(_, _, rhs)
+            | Rvalue::CopyForDeref(rhs)
+            | Rvalue::Cast(_, Operand::Copy(rhs) | Operand::Move(rhs), _) => {
+                let function = self.call_stack.peek_mut();
+                link_if_sync_variable(place, rhs, &mut function.memory, function.id, self.tcx);
+            }
+            Rvalue::Aggregate(_, operands) => {
+                let function = self.call_stack.peek_mut();
+                handle_aggregate_assignment(
+                    place,
+                    &operands.raw,
+                    &mut function.memory,
+                    function.id,
+                    self.tcx,
+                );
+            }
+            // No need to do anything for the other cases for now.
+            _ => {}
+        }
+        // Keep track of the closures, which may be called later through the `Fn*` traits.
+        handle_closure_assignment(place, rvalue, &mut self.call_stack.peek_mut().memory);
+
+        self.super_assign(place, rvalue, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        // Record the span of the terminator as the origin of the transitions added while translating it.
+        let span = span_info(terminator.source_info.span, self.tcx);
+        let added_from = self.net.node_counts();
+        self.net.set_current_span(Some(span.clone()));
+        // The guards dropped in a cleanup block, i.e. on the unwind path, poison their locks.
+        let poison_on_drop = self.options.model_poisoning
+            && self.call_stack.peek().id.body(self.tcx).basic_blocks[location.block].is_cleanup;
+        let function = self.call_stack.peek_mut();
+
+        match terminator.kind {
+            Goto { target } if self.options.granularity == Granularity::Function => {
+                function.merge_goto_target(target.into(), &mut self.net);
+            }
+            Goto { target } => {
+                function.goto(target.into(), &mut self.net);
+            }
+            SwitchInt {
+                ref discr,
+                ref targets,
+            } => {
+                // Convert the specific type for the targets vector into a `std::collections::Vec`
+                // <rustc_middle::mir::terminator::SwitchTargets>
+                let blocks = targets.all_targets().iter().map(|&target| target.into());
+                // Branches on a nondeterministic choice marked in the source code
+                // or on the environment are labelled as such.
+                let kind = match discr.place() {
+                    Some(discriminant) if function.memory.is_nondet(&discriminant) => {
+                        BranchKind::Nondet
+                    }
+                    Some(discriminant)
+                        if self.options.environment_branches
+                            && function.memory.depends_on_environment(&discriminant) =>
+                    {
+                        BranchKind::Environment
+                    }
+                    _ => BranchKind::Data,
+                };
+                let transitions = function.switch_int(blocks.collect(), kind, &mut self.net);
+                // Branches on the value loaded from an atomic variable depend on its current value.
+                if let Some(discriminant) = discr.place() {
+                    let values: Vec<u128> = targets.iter().map(|(value, _)| value).collect();
+                    atomic::guard_switch_int_branches(
+                        &discriminant,
+                        &values,
+                        &transitions,
+                        &mut self.net,
+                        &function.memory,
+                    );
+                    // Branches on the result of `lock` depend on whether the mutex is poisoned.
+                    mutex::guard_poisoned_branch(
+                        &discriminant,
+                        &values,
+                        &transitions,
+                        &mut self.net,
+                        &function.memory,
+                    );
+                    rwlock::guard_poisoned_branch(
+                        &discriminant,
+                        &values,
+                        &transitions,
+                        &mut self.net,
+                        &function.memory,
+                    );
+                }
+            }
+            // The unwinding continues in the caller, if the call has a cleanup block.
+            UnwindResume => {
+                let unwind_place = function
+                    .cleanup_place
+                    .clone()
+                    .unwrap_or_else(|| self.program_panic.clone());
+                function.unwind(&unwind_place, &mut self.net);
+            }
+            UnwindTerminate(..) => {
+                function.unwind(&self.program_panic, &mut self.net);
+            }
+            Return => {
+                function.return_statement(&mut self.net);
+            }
+            Unreachable => {
+                function.unreachable(&self.program_end, &mut self.net);
+            }
+            Drop {
+                place,
+                target,
+                unwind,
+                replace: _,
+            } => {
+                let (transition, cleanup_transition) = match unwind {
+                    UnwindAction::Cleanup(cleanup) => {
+                        function.drop(target.into(), Some(cleanup.into()), &mut self.net)
+                    }
+                    // Do NOT model the `Terminate` case.
+                    // It is not relevant for deadlock detection and makes the Petri nets unnecessarily bigger.
+                    UnwindAction::Continue | UnwindAction::Terminate(..) => {
+                        function.drop(target.into(), None, &mut self.net)
+                    }
+                    UnwindAction::Unreachable => {
+                        function.unreachable(&self.program_end, &mut self.net);
+                        function.drop(target.into(), None, &mut self.net)
+                    }
+                };
+
+                // Release the locks held by the dropped guards.
+                // The cleanup blocks drop the guards too, so the locks are also released on the unwind path
+                // before it reaches the panic place, e.g. when a thread panics while holding a lock.
+                let memory = &mut function.memory;
+                let net = &mut self.net;
+                mutex::handle_mutex_guard_drop(place, &transition, net, memory);
+                rwlock::handle_rwlock_guard_drop(place, &transition, net, memory);
+                if poison_on_drop {
+                    mutex::handle_mutex_guard_poisoning(place, &transition, net, memory);
+                    rwlock::handle_rwlock_guard_poisoning(place, &transition, net, memory);
+                }
+                if let Some(cleanup_transition) = cleanup_transition {
+                    mutex::handle_mutex_guard_drop(place, &cleanup_transition, net, memory);
+                    rwlock::handle_rwlock_guard_drop(place, &cleanup_transition, net, memory);
+                }
+                self.record_guard_drops(place);
+            }
+            Call {
+                ref func,
+                ref args,
+                destination,
+                target,
+                unwind,
+                fn_span,
+                call_source: _,
+            } => {
+                if let Err(error) =
+                    self.call_function(func, args, destination, target, unwind, fn_span)
+                {
+                    self.failure = Some(error);
+                }
+            }
+            Assert {
+                cond: _,
+                expected: _,
+                msg: _,
+                target,
+                unwind,
+            } => {
+                match unwind {
+                    UnwindAction::Cleanup(cleanup) => {
+                        function.assert(target.into(), Some(cleanup.into()), &mut self.net);
+                    }
+                    // Do NOT model the `Terminate` case.
+                    // It is not relevant for deadlock detection and makes the Petri nets unnecessarily bigger.
+                    UnwindAction::Continue | UnwindAction::Terminate(..) => {
+                        function.assert(target.into(), None, &mut self.net);
+                    }
+                    UnwindAction::Unreachable => {
+                        function.assert(target.into(), None, &mut self.net);
+                        function.unreachable(&self.program_end, &mut self.net);
+                    }
+                }
+            }
+            Yield { .. } => {
+                self.failure = Some(TranslationError::Unsupported(
+                    "TerminatorKind::Yield not implemented yet".to_string(),
+                ));
+            }
+            CoroutineDrop => {
+                self.failure = Some(TranslationError::Unsupported(
+                    "TerminatorKind::CoroutineDrop not implemented yet".to_string(),
+                ));
+            }
+            FalseEdge { .. } => {
+                self.failure = Some(TranslationError::Unsupported(
+                    "TerminatorKind::FalseEdge not implemented yet".to_string(),
+                ));
+            }
+            FalseUnwind { .. } => {
+                self.failure = Some(TranslationError::Unsupported(
+                    "TerminatorKind::FalseUnwind not implemented yet".to_string(),
+                ));
+            }
+            InlineAsm { .. } => {
+                self.failure = Some(TranslationError::Unsupported(
+                    "TerminatorKind::InlineAsm not implemented yet".to_string(),
+                ));
+            }
+            #[cfg(rustc_tail_call_terminator)]
+            TailCall {
+                func: _,
+                args: _,
+                fn_span: _,
+            } => {
+                self.failure = Some(TranslationError::Unsupported(
+                    "TerminatorKind::TailCall not implemented yet".to_string(),
+                ));
+            }
+        }
+
+        self.net.set_current_span(None);
+        if let Some(record) = &mut self.label_map {
+            let function = &self.call_stack.peek().name;
+            let mir = format!("{:?}", terminator.kind);
+            record.record(added_from, &self.net, function, &span, &mir);
+        }
+        self.super_terminator(terminator, location);
+    }
+}
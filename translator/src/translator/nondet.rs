@@ -0,0 +1,57 @@
+//! Submodule for the markers of nondeterminism in the source code.
+//!
+//! Every branch of a `SwitchInt` terminator is modelled, but a branch on a value computed by the program
+//! cannot be told apart from a decision of the environment that the program abstracts, e.g. whether a request arrives.
+//! The user can encode such a decision explicitly with a call to one of the markers:
+//! - A function named `granite_nondet_bool`, e.g. defined next to a macro `granite_nondet_bool!()` that calls it.
+//!   Its body is not translated.
+//! - `rand::random::<bool>()`.
+//!
+//! The call is modelled as a single transition and its result is linked to a nondeterministic choice in the memory.
+//! The copies and the negations of the result are linked as well.
+//! A `SwitchInt` terminator on the result is a free choice between its branches,
+//! whose transitions are labelled `_NONDET_FROM_BB<N>_TO_BB<M>` instead of `_SWITCH_INT_FROM_BB<N>_TO_BB<M>`,
+//! so the choices made by the environment are visible in the net and in the witness paths.
+
+use crate::compiler_interface::{BinOp, GenericArgs, Operand, Place, Rvalue, UnOp};
+use crate::translator::mir_function::memory::Memory;
+
+/// The name of the function that marks a nondeterministic choice, regardless of its module.
+const NONDET_MARKER: &str = "granite_nondet_bool";
+
+/// Checks whether the call to the function with the given name and generic arguments
+/// is a marker of a nondeterministic choice.
+pub fn is_nondet_function(function_name: &str, generic_args: GenericArgs) -> bool {
+    if function_name.rsplit("::").next() == Some(NONDET_MARKER) {
+        return true;
+    }
+    function_name == "rand::random" && generic_args.types().next().is_some_and(|ty| ty.is_bool())
+}
+
+/// Handles the assignments derived from a nondeterministic choice:
+/// - `_X = _Y` (copy or move)
+/// - `_X = Not(_Y)`
+/// - `_X = Eq(_Y, C)` and `_X = Ne(_Y, C)` with a constant `C`
+///
+/// If `_Y` is linked to a nondeterministic choice, links `_X` to a nondeterministic choice too.
+pub fn handle_nondet_assignment<'tcx>(
+    place: &Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    let rhs = match rvalue {
+        Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs))
+        | Rvalue::UnaryOp(UnOp::Not, Operand::Copy(rhs) | Operand::Move(rhs)) => rhs,
+        Rvalue::BinaryOp(BinOp::Eq | BinOp::Ne, operands) => {
+            let (Operand::Copy(rhs) | Operand::Move(rhs), Operand::Constant(_)) = &**operands
+            else {
+                return;
+            };
+            rhs
+        }
+        _ => return,
+    };
+    if memory.is_nondet(rhs) {
+        memory.link_nondet(*place);
+    }
+}
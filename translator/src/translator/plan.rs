@@ -0,0 +1,191 @@
+//! Submodule for the dry run of the translation, which computes a `TranslationPlan` without building the net.
+//!
+//! The `Planner` starts at the same entry points as the `Translator` and follows the calls
+//! in the MIR representation with the same rules: The functions are classified by the `FunctionClassifier`,
+//! recursive calls are not followed and the call depth is limited by the `max_call_depth` option.
+//! The threads are visited after the main thread in the order they were found.
+//!
+//! Neither the memory nor the net are tracked. This makes the dry run much faster than the translation,
+//! but the calls resolved through the memory, e.g. closures stored in a `Box<dyn Fn()>`, are not followed.
+//! The size of the net is estimated with one place per basic block and one transition per edge
+//! of the control flow graph, which is the bulk of the net for most programs.
+
+use log::info;
+use std::collections::VecDeque;
+
+use super::classifier::FunctionClassifier;
+use super::entry_function_id;
+use super::sync::{created_primitive, set_lock_libraries};
+use crate::compiler_interface::{
+    argument_operand, exported_functions, function_by_path, resolve_call, span_info, CallArgs,
+    FunctionId, GenericArgs, List, Operand, Span, TerminatorKind, TyCtxt,
+};
+use crate::data_structures::function_classification::FunctionClass;
+use crate::data_structures::translation_plan::{PlannedPrimitive, PlannedThread, TranslationPlan};
+use crate::options::Options;
+use crate::utils::{extract_generic_args_from_operand, function_id_of_operand};
+
+/// The places of the program start, the program end and the program panic, present in every net.
+const PROGRAM_PLACES: usize = 3;
+
+pub struct Planner<'tcx> {
+    /// The global typing context that enables interaction with `rustc`.
+    tcx: TyCtxt<'tcx>,
+    /// The functions being visited and their generic arguments, the innermost last.
+    call_stack: Vec<(FunctionId, GenericArgs<'tcx>)>,
+    /// The functions run by the threads found so far that were not visited yet.
+    threads: VecDeque<FunctionId>,
+    /// The classification of the functions called in the code.
+    classifier: FunctionClassifier,
+    /// The plan computed so far.
+    plan: TranslationPlan,
+    /// The options that configure the translation.
+    options: Options,
+}
+
+impl<'tcx> Planner<'tcx> {
+    /// Creates a new `Planner` for the translation configured through the given `Options`.
+    pub fn new(tcx: TyCtxt<'tcx>, options: Options) -> Self {
+        set_lock_libraries(&options.lock_libraries);
+        let classifier = FunctionClassifier::new(
+            options.classification_cache.as_deref(),
+            &options.lock_libraries,
+        );
+        Self {
+            tcx,
+            call_stack: Vec::new(),
+            threads: VecDeque::new(),
+            classifier,
+            plan: TranslationPlan::new(),
+            options,
+        }
+    }
+
+    /// Computes the plan of the translation.
+    /// The ownership of the plan is transferred to the caller.
+    ///
+    /// # Panics
+    ///
+    /// If the crate does not export any function in library mode, then the function panics.
+    pub fn run(&mut self) -> TranslationPlan {
+        self.plan.estimated_places += PROGRAM_PLACES;
+        self.plan_additional_entry_points();
+        if self.options.library {
+            let functions = exported_functions(self.tcx);
+            if functions.is_empty() {
+                panic!(
+                    "ERROR: No exported function without generic parameters found in the source code"
+                );
+            }
+            for function_id in functions {
+                // Every exported function has its own start place and transition.
+                self.plan.estimated_places += 1;
+                self.plan.estimated_transitions += 1;
+                self.plan.entry_points.push(function_id.name(self.tcx));
+                self.visit_function(function_id, List::empty());
+            }
+        } else {
+            let function_id = entry_function_id(&self.options, self.tcx);
+            self.plan.entry_points.push(function_id.name(self.tcx));
+            self.visit_function(function_id, List::empty());
+        }
+        while let Some(function_id) = self.threads.pop_front() {
+            self.visit_function(function_id, List::empty());
+        }
+        self.classifier.save();
+        std::mem::take(&mut self.plan)
+    }
+
+    /// Adds the threads spawned at program start for the functions in the `also_run` option,
+    /// see `Translator::spawn_additional_entry_points`.
+    fn plan_additional_entry_points(&mut self) {
+        if self.options.also_run.is_empty() {
+            return;
+        }
+        // The spawn transition and the place where the translation starts.
+        self.plan.estimated_places += 1;
+        self.plan.estimated_transitions += 1;
+        for path in &self.options.also_run {
+            let function_id = function_by_path(path, self.tcx).expect(
+                "BUG: The functions to run at program start should have been found by `check_entry_points`",
+            );
+            self.plan.threads.push(PlannedThread {
+                function: function_id.name(self.tcx),
+                span: function_id.span(self.tcx).unwrap_or_default(),
+            });
+            self.threads.push_back(function_id);
+        }
+    }
+
+    /// Visits the basic blocks of the function and the calls in them.
+    fn visit_function(&mut self, function_id: FunctionId, generic_args: GenericArgs<'tcx>) {
+        let function_name = function_id.name(self.tcx);
+        info!("Planning the translation of {function_name}");
+        self.plan.add_function(&function_name);
+        self.call_stack.push((function_id, generic_args));
+
+        let body = function_id.body(self.tcx);
+        for block in body.basic_blocks.iter() {
+            self.plan.estimated_places += 1;
+            let terminator = block.terminator();
+            self.plan.estimated_transitions += terminator.successors().count().max(1);
+            if let TerminatorKind::Call {
+                ref func,
+                ref args,
+                fn_span,
+                ..
+            } = terminator.kind
+            {
+                self.visit_call(func, args, fn_span);
+            }
+        }
+        self.call_stack.pop();
+    }
+
+    /// Visits a call following the same rules as the `Translator`.
+    /// Calls to functions that are not known statically, e.g. through a function pointer, are skipped.
+    fn visit_call(&mut self, func: &Operand<'tcx>, args: &CallArgs<'tcx>, span: Span) {
+        let (caller_id, caller_args) = *self
+            .call_stack
+            .last()
+            .expect("BUG: A call should be visited inside a function");
+        let Some(function_id) = function_id_of_operand(func, caller_id, self.tcx) else {
+            return;
+        };
+        let generic_args = extract_generic_args_from_operand(func, caller_id, self.tcx);
+        let (function_id, generic_args) =
+            resolve_call(function_id, generic_args, caller_args, self.tcx);
+        let function = self.classifier.classify(function_id, self.tcx);
+
+        match function.class {
+            FunctionClass::Sync if function.name == "std::thread::spawn" => {
+                let thread_id = args.first().and_then(|argument| {
+                    function_id_of_operand(argument_operand(argument), caller_id, self.tcx)
+                });
+                if let Some(thread_id) = thread_id {
+                    self.plan.threads.push(PlannedThread {
+                        function: thread_id.name(self.tcx),
+                        span: span_info(span, self.tcx),
+                    });
+                    self.threads.push_back(thread_id);
+                }
+            }
+            FunctionClass::Sync => {
+                if let Some(kind) = created_primitive(&function.name) {
+                    self.plan.primitives.push(PlannedPrimitive {
+                        kind,
+                        span: span_info(span, self.tcx),
+                    });
+                }
+            }
+            FunctionClass::Translated => {
+                let is_recursive = self.call_stack.iter().any(|(id, _)| *id == function_id);
+                let is_too_deep = self.call_stack.len() >= self.options.max_call_depth;
+                if !is_recursive && !is_too_deep {
+                    self.visit_function(function_id, generic_args);
+                }
+            }
+            FunctionClass::Foreign | FunctionClass::Panic => {}
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Submodule for the translation of calls to functions modelled by plugins.
+//!
+//! Every call to the constructor of a plugin creates a new `PluginPrimitive`
+//! with its own copy of the places declared by the plugin, like every call to `std::sync::Mutex::new`
+//! creates a new mutex. The return value of the constructor is linked to the instance in the memory.
+//!
+//! A call to a function modelled by a plugin is translated like a foreign function call,
+//! but the transition additionally consumes a token from each place in the `consumes` list
+//! and produces a token in each place in the `produces` list of the model.
+//! The places belong to the instance passed to the function as the first argument (the self reference).
+//!
+//! If several plugins model the same function, the plugin loaded first takes precedence.
+
+use log::{debug, warn};
+use std::collections::HashMap;
+
+use crate::compiler_interface::{CallArgs, Place};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, PetriNet, PlaceRef,
+};
+use crate::naming::plugin::{call_transition_labels, label, place_label};
+use crate::translator::function::{Places, Transitions};
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::{call_foreign_function, connect_call_places};
+use crate::utils::extract_nth_argument_as_place;
+use cargo_check_deadlock_core::plugin::{FunctionModel, PluginModels};
+
+/// The calls handled by the plugins, indexed by the function name.
+pub struct PluginCalls {
+    /// The plugins whose constructor is the function.
+    constructors: HashMap<String, PluginModels>,
+    /// The functions on an instance modelled by the plugins.
+    functions: HashMap<String, FunctionModel>,
+}
+
+impl PluginCalls {
+    /// Collects the constructors and the functions modelled by the plugins.
+    /// If several plugins model the same function, the model of the plugin loaded first is kept.
+    pub fn new(plugins: &[PluginModels]) -> Self {
+        let mut constructors = HashMap::new();
+        let mut functions = HashMap::new();
+        for plugin in plugins {
+            if constructors.contains_key(&plugin.constructor) {
+                warn!(
+                    "The constructor {} is modelled by several plugins: The model of plugin {} is ignored",
+                    plugin.constructor, plugin.name
+                );
+                continue;
+            }
+            constructors.insert(plugin.constructor.clone(), plugin.clone());
+            for model in &plugin.functions {
+                if functions.contains_key(&model.function_name) {
+                    warn!(
+                        "The function {} is modelled by several plugins: The model of plugin {} is ignored",
+                        model.function_name, plugin.name
+                    );
+                    continue;
+                }
+                functions.insert(model.function_name.clone(), model.clone());
+            }
+        }
+        Self {
+            constructors,
+            functions,
+        }
+    }
+
+    /// Returns the plugin whose constructor is the function with the given name.
+    pub fn get_constructor(&self, function_name: &str) -> Option<&PluginModels> {
+        self.constructors.get(function_name)
+    }
+
+    /// Returns the model of the function on an instance with the given name.
+    pub fn get_function(&self, function_name: &str) -> Option<&FunctionModel> {
+        self.functions.get(function_name)
+    }
+}
+
+/// An instance of a primitive modelled by a plugin.
+#[derive(PartialEq, Eq)]
+pub struct PluginPrimitive {
+    label: String,
+    /// The places of the instance indexed by the label declared by the plugin.
+    places: HashMap<String, PlaceRef>,
+}
+
+impl PluginPrimitive {
+    /// Creates a new instance of the primitive modelled by the plugin whose label is based on `index`.
+    /// Adds the places declared by the plugin to the net with their initial marking.
+    ///
+    /// # Panics
+    ///
+    /// If the initial marking of a place overflows, then the function panics.
+    pub fn new(plugin: &PluginModels, index: usize, net: &mut PetriNet) -> Self {
+        let mut places = HashMap::new();
+        for place in &plugin.places {
+            let place_ref = net.add_place(&place_label(&plugin.name, index, &place.label));
+            net.add_token(&place_ref, place.initial_marking)
+                .expect("BUG: Adding the initial marking to a new plugin place should not cause an overflow");
+            places.insert(place.label.clone(), place_ref);
+        }
+        Self {
+            label: label(&plugin.name, index),
+            places,
+        }
+    }
+
+    /// Returns the label that identifies the instance, e.g. in the reports.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the place of the instance with the label declared by the plugin.
+    ///
+    /// # Panics
+    ///
+    /// If the plugin did not declare the place, then the function panics.
+    fn get_place(&self, place_label: &str) -> &PlaceRef {
+        self.places.get(place_label).unwrap_or_else(|| {
+            panic!("BUG: The plugin place {place_label} should have been validated when loading the plugin")
+        })
+    }
+}
+
+/// Call to the constructor of a primitive modelled by a plugin.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `PluginPrimitive`.
+/// - Links the return place to the `PluginPrimitive`.
+pub fn call_constructor<'tcx>(
+    function_name: &str,
+    plugin: &PluginModels,
+    index: usize,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new instance of the primitive
+    let primitive = PluginPrimitive::new(plugin, index, net);
+    // The return value contains a new instance. Link the local variable to it.
+    let primitive_ref = memory.link_plugin_primitive(destination, primitive);
+    debug!("NEW {}: {destination:?}", primitive_ref.label());
+}
+
+/// Creates the Petri net representation of a call to a function modelled by a plugin.
+/// Connects the start place to the end place and to the optional cleanup place like a foreign function call.
+/// The default transition consumes and produces the tokens in the places given by the model
+/// of the instance linked to the first argument (the self reference).
+///
+/// Returns the transitions representing the function call.
+pub fn call_plugin_function<'tcx>(
+    function: &FunctionModel,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) -> Transitions {
+    // Retrieve the instance from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!(
+            "BUG: `{}` should receive the instance of the primitive as a place",
+            function.function_name
+        )
+    });
+    let primitive_ref = memory.get_plugin_primitive(&self_ref);
+
+    let labels = call_transition_labels(&function.transition_label, index);
+    let transitions = connect_call_places(&labels, places, net);
+    let default = transitions.get_default();
+    for place in &function.consumes {
+        add_arc_place_transition(net, primitive_ref.get_place(place), default);
+    }
+    for place in &function.produces {
+        add_arc_transition_place(net, default, primitive_ref.get_place(place));
+    }
+    debug!(
+        "{} ON {} AT CALL {index}",
+        function.transition_label,
+        primitive_ref.label()
+    );
+    transitions
+}
@@ -5,11 +5,13 @@
 //! or simply functions which we are not interested in translating.
 //! For example: Calls to standard library methods, iterators, etc.
 
+use crate::compiler_interface::{FunctionId, TyCtxt};
 use crate::data_structures::petri_net_interface::{
     add_arc_place_transition, connect_places, PetriNet, PlaceRef,
 };
 use crate::naming::function::{
     diverging_call_transition_label, foreign_call_transition_labels, panic_transition_label,
+    recursive_call_transition_labels, recursive_loop_transition_label,
 };
 use crate::translator::function::{Places, Transitions};
 
@@ -26,7 +28,7 @@ pub fn is_panic_function(function_name: &str) -> bool {
     )
 }
 
-/// Checks whether the function with the given `DefId` should be treated
+/// Checks whether the function with the given `FunctionId` should be treated
 /// as a foreign function call.
 ///
 /// A foreign function call occurs when:
@@ -35,16 +37,12 @@ pub fn is_panic_function(function_name: &str) -> bool {
 /// - the function belongs to the `alloc` crate, the core allocation and collections library.
 /// - the function does not have a MIR representation.
 /// - the function is a foreign item i.e., linked via extern { ... }).
-pub fn is_foreign_function(
-    function_def_id: rustc_hir::def_id::DefId,
-    function_name: &str,
-    tcx: rustc_middle::ty::TyCtxt,
-) -> bool {
+pub fn is_foreign_function(function_id: FunctionId, function_name: &str, tcx: TyCtxt) -> bool {
     function_name.starts_with("std::")
         || function_name.starts_with("core::")
         || function_name.starts_with("alloc::")
-        || tcx.is_foreign_item(function_def_id)
-        || !tcx.is_mir_available(function_def_id)
+        || function_id.is_foreign_item(tcx)
+        || !function_id.is_mir_available(tcx)
 }
 
 /// Creates an abridged Petri net representation of a function call.
@@ -60,13 +58,55 @@ pub fn call_foreign_function(
     places: Places,
     net: &mut PetriNet,
 ) -> Transitions {
-    let (default_label, cleanup_label) = foreign_call_transition_labels(function_name, index);
+    let labels = foreign_call_transition_labels(function_name, index);
+    connect_call_places(&labels, places, net)
+}
+
+/// Creates an abridged Petri net representation of a recursive function call,
+/// i.e. a call to a function that is already on the translation call stack.
+/// This covers direct recursion (A calls A) and mutual recursion (A calls B calls A).
+/// The back edge of the cycle is modelled like a foreign function call
+/// but with a dedicated label to distinguish it in the net.
+///
+/// Returns the transition representing the function call.
+pub fn call_recursive_function(
+    function_name: &str,
+    index: usize,
+    places: Places,
+    net: &mut PetriNet,
+) -> Transitions {
+    let labels = recursive_call_transition_labels(function_name, index);
+    connect_call_places(&labels, places, net)
+}
+
+/// Creates a loop in the Petri net for a self-recursive call in tail position.
+/// Connects the start place of the call back to the start place of the function being translated.
+/// The return of the recursive call is the return of the function itself,
+/// so the end place of the call is not needed.
+pub fn call_recursive_function_as_loop(
+    start_place: &PlaceRef,
+    function_start_place: &PlaceRef,
+    function_name: &str,
+    index: usize,
+    net: &mut PetriNet,
+) {
+    let label = recursive_loop_transition_label(function_name, index);
+    connect_places(net, start_place, function_start_place, &label);
+}
+
+/// Connects the start place to the end place and to the optional cleanup place
+/// through new transitions with the given default and cleanup labels.
+pub fn connect_call_places(
+    (default_label, cleanup_label): &(String, String),
+    places: Places,
+    net: &mut PetriNet,
+) -> Transitions {
     match places {
         Places::Basic {
             start_place,
             end_place,
         } => {
-            let default = connect_places(net, &start_place, &end_place, &default_label);
+            let default = connect_places(net, &start_place, &end_place, default_label);
             Transitions::Basic { default }
         }
         Places::WithCleanup {
@@ -74,8 +114,8 @@ pub fn call_foreign_function(
             end_place,
             cleanup_place,
         } => {
-            let default = connect_places(net, &start_place, &end_place, &default_label);
-            let cleanup = connect_places(net, &start_place, &cleanup_place, &cleanup_label);
+            let default = connect_places(net, &start_place, &end_place, default_label);
+            let cleanup = connect_places(net, &start_place, &cleanup_place, cleanup_label);
             Transitions::WithCleanup { default, cleanup }
         }
     }
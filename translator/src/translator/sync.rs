@@ -0,0 +1,609 @@
+//! Submodule for implementing the translation of synchronization primitives
+//! and the translation of thread primitives.
+
+pub mod atomic;
+pub mod barrier;
+pub mod channel;
+pub mod condvar;
+pub mod mutex;
+pub mod once;
+pub mod rwlock;
+pub mod thread;
+
+use log::debug;
+use std::cell::RefCell;
+
+use crate::compiler_interface::{CallArgs, FunctionId, Operand, Place, ProjectionElem, TyCtxt};
+use crate::data_structures::petri_net_interface::PetriNet;
+use crate::data_structures::sync_registry::SyncKind;
+use crate::options::{default_lock_libraries, LockLibrary};
+use crate::translator::function::{Places, PostprocessingTask};
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::TranslationError;
+use crate::utils::{check_substring_in_place_type, extract_nth_argument_as_place};
+use cargo_check_deadlock_core::plugin::PluginModels;
+
+// Re-export the types that the module contains.
+// It does not make assumptions about how they are stored.
+// That is the responsibility of the memory.
+pub use atomic::Atomic;
+pub use barrier::Barrier;
+pub use channel::Channel;
+pub use condvar::Condvar;
+pub use mutex::{Guard as MutexGuard, Mutex};
+pub use once::Once;
+pub use rwlock::{Guard as RwLockGuard, RwLock};
+pub use thread::Thread;
+
+thread_local! {
+    /// The libraries whose mutexes and read-write locks are modelled like the ones in `std::sync`,
+    /// see `Options::lock_libraries`. They are set with `set_lock_libraries` before the translation starts,
+    /// since the functions of this module that recognize the lock types are called without the options.
+    static LOCK_LIBRARIES: RefCell<Vec<LockLibrary>> = RefCell::new(default_lock_libraries());
+}
+
+/// Sets the libraries whose mutexes and read-write locks are modelled like the ones in `std::sync`
+/// for the translation that runs on the current thread.
+pub fn set_lock_libraries(libraries: &[LockLibrary]) {
+    LOCK_LIBRARIES.with_borrow_mut(|lock_libraries| libraries.clone_into(lock_libraries));
+}
+
+thread_local! {
+    /// The types of the primitives modelled by the plugins, see `Options::plugins`.
+    /// They are set with `set_plugin_types` before the translation starts, like the `LOCK_LIBRARIES`.
+    static PLUGIN_TYPES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Sets the types of the primitives modelled by the plugins
+/// for the translation that runs on the current thread.
+pub fn set_plugin_types(plugins: &[PluginModels]) {
+    PLUGIN_TYPES.with_borrow_mut(|plugin_types| {
+        *plugin_types = plugins
+            .iter()
+            .map(|plugin| plugin.type_name.clone())
+            .collect();
+    });
+}
+
+/// Checks whether the fully qualified name of a type contains one of the `PLUGIN_TYPES`,
+/// e.g. `std::sync::Arc<my_crate::Semaphore>`, but not a type whose path only starts with it,
+/// e.g. `my_crate::SemaphoreGuard`.
+fn contains_plugin_type(type_name: &str) -> bool {
+    PLUGIN_TYPES.with_borrow(|plugin_types| {
+        plugin_types.iter().any(|plugin_type| {
+            type_name
+                .match_indices(plugin_type.as_str())
+                .any(|(start, _)| {
+                    !type_name[start + plugin_type.len()..]
+                        .starts_with(|c: char| c.is_alphanumeric() || c == '_')
+                })
+        })
+    })
+}
+
+/// Checks whether any of the `LOCK_LIBRARIES` satisfies the predicate.
+fn any_lock_library(predicate: impl FnMut(&LockLibrary) -> bool) -> bool {
+    LOCK_LIBRARIES.with_borrow(|libraries| libraries.iter().any(predicate))
+}
+
+/// The wrapper types that pass the value they contain through unchanged,
+/// i.e. a sync variable inside them is linked to the same value as the wrapper itself.
+/// `std::cell::SyncUnsafeCell<T>` stores its value in a `std::cell::UnsafeCell<T>`, so both are listed.
+const PASS_THROUGH_WRAPPERS: [&str; 3] = [
+    "std::sync::Exclusive<",
+    "std::cell::SyncUnsafeCell<",
+    "std::cell::UnsafeCell<",
+];
+
+/// The supported functions of the mutexes and read-write locks in `LOCK_LIBRARIES`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockFunction {
+    MutexNew,
+    MutexLock,
+    RwLockNew,
+    RwLockRead,
+    RwLockWrite,
+}
+
+impl LockFunction {
+    /// Returns the lock function corresponding to the function name, if any.
+    /// For example: `std::sync::Mutex::<T>::lock`, `lock_api::Mutex::<R, T>::lock`
+    /// and `parking_lot::lock_api::Mutex::<R, T>::lock` are all `LockFunction::MutexLock`.
+    pub fn from_name(function_name: &str) -> Option<Self> {
+        let function_name = function_name
+            .strip_prefix("parking_lot::")
+            .unwrap_or(function_name);
+        LOCK_LIBRARIES.with_borrow(|libraries| {
+            libraries
+                .iter()
+                .find_map(|library| Self::from_name_in_library(function_name, library))
+        })
+    }
+
+    /// Returns the lock function corresponding to the function name in the given library, if any.
+    fn from_name_in_library(function_name: &str, library: &LockLibrary) -> Option<Self> {
+        let path = function_name
+            .strip_prefix(library.path.as_str())?
+            .strip_prefix("::")?;
+        let (type_name, method) = path.split_once(&format!("::{}::", library.generics))?;
+        match (type_name, method) {
+            ("Mutex", "new") => Some(Self::MutexNew),
+            ("Mutex", "lock") => Some(Self::MutexLock),
+            ("RwLock", "new") => Some(Self::RwLockNew),
+            ("RwLock", "read") => Some(Self::RwLockRead),
+            ("RwLock", "write") => Some(Self::RwLockWrite),
+            _ => None,
+        }
+    }
+}
+
+/// Checks whether the type of the place contains the lock type `type_name`
+/// (e.g. `Mutex` or `MutexGuard`) of one of the libraries in `LOCK_LIBRARIES`.
+pub fn check_lock_type_in_place<'tcx>(
+    place: &Place<'tcx>,
+    type_name: &str,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> bool {
+    any_lock_library(|library| {
+        check_substring_in_place_type(
+            place,
+            &format!("{}::{type_name}<", library.path),
+            caller_function_id,
+            tcx,
+        )
+    })
+}
+
+/// Returns the kind of synchronization primitive created by the function, if it is a constructor,
+/// e.g. `SyncKind::Mutex` for `std::sync::Mutex::<T>::new`.
+pub fn created_primitive(function_name: &str) -> Option<SyncKind> {
+    match LockFunction::from_name(function_name) {
+        Some(LockFunction::MutexNew) => return Some(SyncKind::Mutex),
+        Some(LockFunction::RwLockNew) => return Some(SyncKind::RwLock),
+        _ => {}
+    }
+    match function_name {
+        "std::sync::Barrier::new" => Some(SyncKind::Barrier),
+        "std::sync::Condvar::new" => Some(SyncKind::Condvar),
+        "std::sync::Once::new" | "std::sync::OnceLock::<T>::new" => Some(SyncKind::Once),
+        "std::sync::atomic::AtomicBool::new" | "std::sync::atomic::AtomicUsize::new" => {
+            Some(SyncKind::Atomic)
+        }
+        "std::sync::mpsc::channel" | "std::sync::mpsc::sync_channel" => Some(SyncKind::Channel),
+        _ => None,
+    }
+}
+
+/// Checks whether the function name corresponds to one of the
+/// supported synchronization or multithreading functions.
+pub fn is_supported_function(function_name: &str) -> bool {
+    LockFunction::from_name(function_name).is_some()
+        || matches!(
+            function_name,
+            "std::sync::Barrier::new"
+                | "std::sync::Barrier::wait"
+                | "std::sync::Condvar::new"
+                | "std::sync::Condvar::notify_all"
+                | "std::sync::Condvar::notify_one"
+                | "std::sync::Condvar::wait"
+                | "std::sync::Condvar::wait_while"
+                | "std::sync::Once::call_once"
+                | "std::sync::Once::new"
+                | "std::sync::OnceLock::<T>::get_or_init"
+                | "std::sync::OnceLock::<T>::new"
+                | "std::sync::mpsc::Receiver::<T>::recv"
+                | "std::sync::mpsc::Sender::<T>::send"
+                | "std::sync::mpsc::SyncSender::<T>::send"
+                | "std::sync::mpsc::channel"
+                | "std::sync::mpsc::sync_channel"
+                | "std::sync::atomic::AtomicBool::compare_exchange"
+                | "std::sync::atomic::AtomicBool::load"
+                | "std::sync::atomic::AtomicBool::new"
+                | "std::sync::atomic::AtomicBool::store"
+                | "std::sync::atomic::AtomicUsize::compare_exchange"
+                | "std::sync::atomic::AtomicUsize::load"
+                | "std::sync::atomic::AtomicUsize::new"
+                | "std::sync::atomic::AtomicUsize::store"
+                | "std::thread::spawn"
+                | "std::thread::JoinHandle::<T>::join"
+        )
+}
+
+/// Calls the corresponding handler for the supported synchronization or multithreading functions.
+///
+/// # Errors
+///
+/// If the call uses a feature of the function that is not supported yet, then an error is returned.
+pub fn call_function<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) -> Result<Option<PostprocessingTask>, TranslationError> {
+    if let Some(lock_function) = LockFunction::from_name(function_name) {
+        return Ok(match lock_function {
+            LockFunction::MutexLock => {
+                mutex::call_lock(function_name, index, args, destination, places, net, memory);
+                None
+            }
+            LockFunction::MutexNew => {
+                let task = mutex::call_new(function_name, index, destination, places, net, memory);
+                Some(task)
+            }
+            LockFunction::RwLockNew => {
+                rwlock::call_new(function_name, index, destination, places, net, memory);
+                None
+            }
+            LockFunction::RwLockRead | LockFunction::RwLockWrite => {
+                rwlock::call_lock(function_name, index, args, destination, places, net, memory);
+                None
+            }
+        });
+    }
+    Ok(match function_name {
+        "std::sync::Barrier::wait" => {
+            barrier::call_wait(function_name, index, args, places, net, memory);
+            None
+        }
+        "std::sync::Condvar::notify_all" => {
+            condvar::call_notify_all(function_name, index, args, places, net, memory);
+            None
+        }
+        "std::sync::Condvar::notify_one" => {
+            condvar::call_notify_one(function_name, index, args, places, net, memory);
+            None
+        }
+        "std::sync::Condvar::wait" | "std::sync::Condvar::wait_while" => {
+            let task =
+                condvar::call_wait(function_name, index, args, destination, places, net, memory)?;
+            Some(task)
+        }
+        "std::sync::Once::new" | "std::sync::OnceLock::<T>::new" => {
+            once::call_new(function_name, index, destination, places, net, memory);
+            None
+        }
+        "std::sync::Once::call_once" | "std::sync::OnceLock::<T>::get_or_init" => {
+            once::call_once(function_name, index, args, places, net, memory);
+            None
+        }
+        "std::sync::mpsc::Receiver::<T>::recv" => {
+            channel::call_recv(function_name, index, args, places, net, memory);
+            None
+        }
+        "std::sync::mpsc::Sender::<T>::send" | "std::sync::mpsc::SyncSender::<T>::send" => {
+            channel::call_send(function_name, index, args, places, net, memory);
+            None
+        }
+        "std::sync::mpsc::channel" => {
+            channel::call_channel(function_name, index, destination, places, net, memory);
+            None
+        }
+        "std::sync::atomic::AtomicBool::new" | "std::sync::atomic::AtomicUsize::new" => {
+            atomic::call_new(function_name, index, args, destination, places, net, memory)?;
+            None
+        }
+        "std::sync::atomic::AtomicBool::load" | "std::sync::atomic::AtomicUsize::load" => {
+            atomic::call_load(function_name, index, args, destination, places, net, memory);
+            None
+        }
+        "std::sync::atomic::AtomicBool::store" | "std::sync::atomic::AtomicUsize::store" => {
+            atomic::call_store(function_name, index, args, places, net, memory)?;
+            None
+        }
+        "std::sync::atomic::AtomicBool::compare_exchange"
+        | "std::sync::atomic::AtomicUsize::compare_exchange" => {
+            atomic::call_compare_exchange(function_name, index, args, places, net, memory)?;
+            None
+        }
+        "std::thread::JoinHandle::<T>::join" => {
+            thread::call_join(function_name, index, args, places, net, memory);
+            None
+        }
+        _ => panic!("BUG: Call handler for {function_name} is not defined"),
+    })
+}
+
+/// Checks whether a place contains a sync variable
+/// (mutex, mutex guard, read-write lock, read-write lock guard, join handle, condition variable, barrier,
+/// one-time initialization, atomic variable, sender or receiver)
+///
+/// The result of `std::sync::Barrier::wait` is not a sync variable even if its type starts with the same name.
+/// The same applies to the `std::sync::OnceState` passed to `std::sync::Once::call_once_force`.
+pub fn check_if_sync_variable<'tcx>(
+    place: &Place<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> bool {
+    let body = caller_function_id.body(tcx);
+    is_sync_type(&place.ty(body, tcx).ty.to_string())
+}
+
+/// Checks whether the fully qualified name of a type contains one of the sync variables
+/// that are tracked in the memory, see `check_if_sync_variable`.
+pub fn is_sync_type(type_name: &str) -> bool {
+    let contains_lock_type = |lock_type: &str| {
+        any_lock_library(|library| type_name.contains(&format!("{}::{lock_type}<", library.path)))
+    };
+    contains_lock_type("MutexGuard")
+        || contains_lock_type("Mutex")
+        || contains_lock_type("RwLockReadGuard")
+        || contains_lock_type("RwLockWriteGuard")
+        || contains_lock_type("RwLock")
+        || type_name.contains("std::thread::JoinHandle<")
+        || type_name.contains("std::sync::Condvar")
+        || type_name.contains("std::sync::mpsc::Sender<")
+        || type_name.contains("std::sync::mpsc::SyncSender<")
+        || type_name.contains("std::sync::mpsc::Receiver<")
+        || (type_name.contains("std::sync::Barrier")
+            && !type_name.contains("std::sync::BarrierWaitResult"))
+        || type_name.contains("std::sync::atomic::AtomicBool")
+        || type_name.contains("std::sync::atomic::AtomicUsize")
+        || (type_name.contains("std::sync::Once") && !type_name.contains("std::sync::OnceState"))
+        || contains_plugin_type(type_name)
+}
+
+/// Checks whether the fully qualified name of a type is one of the `PASS_THROUGH_WRAPPERS`,
+/// e.g. `std::sync::Exclusive<std::sync::Mutex<i32>>`.
+pub fn is_pass_through_wrapper(type_name: &str) -> bool {
+    PASS_THROUGH_WRAPPERS
+        .iter()
+        .any(|wrapper| type_name.starts_with(wrapper))
+}
+
+/// Removes the accesses to the field of a pass-through wrapper at the end of the place,
+/// e.g. `((*_1).0: std::sync::Mutex<i32>)` becomes `(*_1)` if `(*_1)` is a `std::sync::Exclusive<std::sync::Mutex<i32>>`.
+/// The wrapper is linked to the sync variable it contains, not to an aggregate,
+/// so the place of the field would not be resolved in the memory.
+pub fn strip_pass_through_fields<'tcx>(
+    place: Place<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> Place<'tcx> {
+    let body = caller_function_id.body(tcx);
+    let mut length = place.projection.len();
+    while length > 0 && matches!(place.projection[length - 1], ProjectionElem::Field(..)) {
+        let wrapper = Place::ty_from(place.local, &place.projection[..length - 1], body, tcx);
+        if !is_pass_through_wrapper(&wrapper.ty.to_string()) {
+            break;
+        }
+        length -= 1;
+    }
+    Place {
+        local: place.local,
+        projection: tcx.mk_place_elems(&place.projection[..length]),
+    }
+}
+
+/// Handles MIR assignments of the form: `_X = { copy_data: move _Y }`.
+/// Create a new aggregate value (tuple, array, `struct`, closure, etc.) from the sync variables in the operands.
+/// If the operand in the right hand side contains a sync variable or an aggregate, the function includes it in the aggregate.
+/// The other operands are included as untracked fields, so that the field numbers match the indices in the aggregate.
+pub fn handle_aggregate_assignment<'tcx>(
+    place: &Place<'tcx>,
+    operands: &Vec<Operand<'tcx>>,
+    memory: &mut Memory<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) {
+    let mut fields: Vec<Option<Place<'tcx>>> = Vec::new();
+
+    for operand in operands {
+        // Extract the place to be assigned
+        let rhs = match operand {
+            Operand::Copy(place) | Operand::Move(place) => place,
+            // A constant never contains a sync variable.
+            Operand::Constant(_) => {
+                fields.push(None);
+                continue;
+            }
+        };
+        if check_if_sync_variable(rhs, caller_function_id, tcx) || memory.is_aggregate(rhs) {
+            fields.push(Some(*rhs));
+        } else {
+            fields.push(None);
+        }
+    }
+
+    if fields.iter().any(Option::is_some) {
+        memory.create_aggregate(*place, &fields);
+        debug!("CREATED AGGREGATE AT {place:?} WITH PLACES {fields:?}");
+    }
+}
+
+/// Checks if `place_to_link` contains a synchronization variable, e.g., a mutex, a mutex guard,
+/// a join handle, a condition variable or an endpoint of a channel.
+/// If `place_to_link` contains a synchronization variable, links it to `place_linked`.
+///
+/// Receives a reference to the memory of the caller function to
+/// link the return local variable to the synchronization variable.
+///
+/// This handler works for MIR assignments of the form:
+/// - `_X = _Y`
+/// - `_X = &_Y`
+/// - `_X = move _Y`
+/// - `_X = (*_Y).Z:`
+/// - `_X = &((*_Y).Z)`
+/// - `_X = move (*_Y).Z`
+/// - `_X = move (_Y.Z)`
+/// - `_X = &((_Y.Z).W)`, the field of a nested aggregate, see `Memory::find_value`.
+/// - `_X = &(_Y.0)`, the field of a pass-through wrapper like `std::sync::Exclusive`, see `strip_pass_through_fields`.
+/// - `_X = move _Y as *mut T (PtrToPtr)`, e.g. the pointer returned by `std::cell::SyncUnsafeCell::get`.
+/// - `_X = deref_copy ((*_Y).Z)`, e.g. a variable captured by reference in a closure.
+///
+/// It also works for checking if a function argument is a sync variable
+/// and then linking the return value to the argument.
+///
+/// A `struct` containing sync variables is linked to an aggregate even if its type does not name them.
+/// Copies, moves and references of it are linked to the same aggregate, e.g. `_X = &_Y` for the `&self` of a method call.
+pub fn link_if_sync_variable<'tcx>(
+    place_to_link: &Place<'tcx>,
+    place_linked: &Place<'tcx>,
+    memory: &mut Memory<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) {
+    let place_linked = strip_pass_through_fields(*place_linked, caller_function_id, tcx);
+    if check_if_sync_variable(place_to_link, caller_function_id, tcx)
+        || memory.is_aggregate(&place_linked)
+    {
+        memory.link_place_to_same_value(*place_to_link, place_linked);
+    }
+}
+
+/// Checks if the first argument for a function call contains a mutex, a mutex guard,
+/// a join handle or a condition variable, i.e. a synchronization variable.
+/// If the first argument contains a synchronization variable, links it to the return value.
+/// If there is no first argument or it is a constant,
+/// then there is nothing to check, therefore the function simply returns.
+///
+/// Why check only the first argument?
+/// Because most function in the standard library involving synchronization primitives
+/// receive it through the first argument. For instance:
+///  * `std::clone::Clone::clone`
+///  * `std::ops::Deref::deref`
+///  * `std::ops::DerefMut::deref_mut`
+///  * `std::result::Result::<T, E>::unwrap`
+///  * `std::sync::Arc::<T>::new`
+///
+/// Receives a reference to the memory of the caller function to
+/// link the return local variable to the synchronization variable.
+pub fn link_return_value_if_sync_variable<'tcx>(
+    args: &CallArgs<'tcx>,
+    return_value: Place<'tcx>,
+    memory: &mut Memory<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) {
+    let Some(first_argument) = extract_nth_argument_as_place(args, 0) else {
+        // Nothing to check: Either the first argument is not present or it is a constant.
+        return;
+    };
+    link_if_sync_variable(
+        &return_value,
+        &first_argument,
+        memory,
+        caller_function_id,
+        tcx,
+    );
+}
+
+#[cfg(test)]
+mod sync_tests {
+    use super::*;
+
+    #[test]
+    fn lock_function_from_name_matches_std_functions() {
+        assert_eq!(
+            LockFunction::from_name("std::sync::Mutex::<T>::new"),
+            Some(LockFunction::MutexNew)
+        );
+        assert_eq!(
+            LockFunction::from_name("std::sync::RwLock::<T>::write"),
+            Some(LockFunction::RwLockWrite)
+        );
+    }
+
+    #[test]
+    fn lock_function_from_name_matches_parking_lot_functions() {
+        assert_eq!(
+            LockFunction::from_name("lock_api::Mutex::<R, T>::lock"),
+            Some(LockFunction::MutexLock)
+        );
+        assert_eq!(
+            LockFunction::from_name("parking_lot::lock_api::RwLock::<R, T>::read"),
+            Some(LockFunction::RwLockRead)
+        );
+        assert_eq!(
+            LockFunction::from_name("parking_lot::lock_api::RwLock::<R, T>::new"),
+            Some(LockFunction::RwLockNew)
+        );
+    }
+
+    #[test]
+    fn lock_function_from_name_rejects_other_functions() {
+        assert_eq!(
+            LockFunction::from_name("std::sync::Mutex::<T>::try_lock"),
+            None
+        );
+        assert_eq!(LockFunction::from_name("std::sync::Condvar::new"), None);
+        assert_eq!(LockFunction::from_name("lock_api::Mutex::<T>::lock"), None);
+        assert_eq!(
+            LockFunction::from_name("my_crate::sync::Mutex::<T>::lock"),
+            None
+        );
+    }
+
+    #[test]
+    fn lock_function_from_name_matches_the_configured_libraries() {
+        set_lock_libraries(&[LockLibrary::new("my_crate::sync", "<T>")]);
+
+        assert_eq!(
+            LockFunction::from_name("my_crate::sync::Mutex::<T>::lock"),
+            Some(LockFunction::MutexLock)
+        );
+        assert_eq!(LockFunction::from_name("std::sync::Mutex::<T>::lock"), None);
+        assert!(is_sync_type("my_crate::sync::MutexGuard<'_, i32>"));
+        assert!(!is_sync_type("std::sync::Mutex<i32>"));
+
+        set_lock_libraries(&default_lock_libraries());
+    }
+
+    #[test]
+    fn is_queue_function_matches_recv_and_lock() {
+        assert!(thread::is_queue_function(
+            "std::sync::mpsc::Receiver::<T>::recv"
+        ));
+        assert!(thread::is_queue_function("std::sync::Mutex::<T>::lock"));
+        assert!(thread::is_queue_function("lock_api::Mutex::<R, T>::lock"));
+        assert!(!thread::is_queue_function("std::sync::RwLock::<T>::read"));
+        assert!(!thread::is_queue_function(
+            "std::sync::mpsc::Sender::<T>::send"
+        ));
+    }
+
+    #[test]
+    fn is_sync_type_matches_the_tracked_types() {
+        assert!(is_sync_type("std::sync::Arc<std::sync::Mutex<i32>>"));
+        assert!(is_sync_type(
+            "lock_api::RwLock<parking_lot::RawRwLock, i32>"
+        ));
+        assert!(is_sync_type("(std::sync::Arc<std::sync::Barrier>, i32)"));
+        assert!(!is_sync_type("std::sync::BarrierWaitResult"));
+        assert!(!is_sync_type("std::sync::OnceState"));
+        assert!(!is_sync_type("SharedState"));
+    }
+
+    #[test]
+    fn is_sync_type_matches_the_plugin_types() {
+        set_plugin_types(&[PluginModels {
+            name: "my_prims".to_string(),
+            type_name: "my_crate::Semaphore".to_string(),
+            constructor: "my_crate::Semaphore::new".to_string(),
+            places: Vec::new(),
+            functions: Vec::new(),
+        }]);
+        assert!(is_sync_type("std::sync::Arc<my_crate::Semaphore>"));
+        assert!(is_sync_type("&my_crate::Semaphore"));
+        assert!(!is_sync_type("my_crate::SemaphoreGuard<'_>"));
+        set_plugin_types(&[]);
+        assert!(!is_sync_type("&my_crate::Semaphore"));
+    }
+
+    #[test]
+    fn is_pass_through_wrapper_matches_the_wrappers() {
+        assert!(is_pass_through_wrapper(
+            "std::sync::Exclusive<std::sync::Mutex<i32>>"
+        ));
+        assert!(is_pass_through_wrapper(
+            "std::cell::SyncUnsafeCell<std::sync::Condvar>"
+        ));
+        assert!(!is_pass_through_wrapper(
+            "std::sync::Arc<std::sync::Exclusive<std::sync::Mutex<i32>>>"
+        ));
+        assert!(!is_pass_through_wrapper("std::sync::Mutex<i32>"));
+    }
+}
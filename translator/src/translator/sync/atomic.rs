@@ -0,0 +1,360 @@
+//! Representation of an atomic variable in the Petri net,
+//! i.e., a `std::sync::atomic::AtomicBool` or a `std::sync::atomic::AtomicUsize`
+//! that only stores the values `false` (or 0) and `true` (or 1).
+//!
+//! The atomic variable is modelled using two complementary places, one for each value.
+//! Exactly one of them has a token at any time:
+//!
+//! - A call to `store` moves the token to the place of the stored value.
+//!   There is one transition for each possible previous value.
+//! - A call to `compare_exchange` either moves the token from the place of the expected value
+//!   to the place of the new value (success) or leaves the token in the other place (failure).
+//! - A call to `load` does not change the atomic variable. The result is linked to the atomic
+//!   variable in the memory. When a `SwitchInt` terminator branches on the result, every branch
+//!   reads the place of the value that leads to it.
+//!
+//! Branching on the result of `load` reads the current value of the atomic variable
+//! instead of the value at the time of the call. This is enough for spin-wait protocols
+//! like `while !flag.load(Ordering::Acquire) {}`, where the result is used immediately.
+//!
+//! The result of `compare_exchange` is not tracked.
+//! Stores of values that are not constants are not supported.
+
+use log::debug;
+
+use crate::compiler_interface::{BinOp, CallArgs, Operand, Place, Rvalue, UnOp};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::atomic::{
+    compare_exchange_transition_labels, label, place_labels, store_transition_labels,
+};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::call_foreign_function;
+use crate::translator::TranslationError;
+use crate::utils::{extract_nth_argument_as_flag, extract_nth_argument_as_place, operand_as_flag};
+
+#[derive(PartialEq, Eq)]
+pub struct Atomic {
+    label: String,
+    false_place: PlaceRef,
+    true_place: PlaceRef,
+}
+
+impl Atomic {
+    /// Creates a new atomic variable whose label is based on `index`.
+    /// Adds two places with a capacity of one token to the Petri net.
+    /// Only the place for the `initial` value starts with a token.
+    pub fn new(index: usize, initial: bool, net: &mut PetriNet) -> Self {
+        let (p1, p2) = place_labels(index);
+        let false_place = net.add_place(&p1);
+        let true_place = net.add_place(&p2);
+        for place in [&false_place, &true_place] {
+            net.set_capacity(place, 1)
+                .expect("BUG: Setting the capacity of the atomic variable place should not fail");
+        }
+        let atomic = Self {
+            label: label(index),
+            false_place,
+            true_place,
+        };
+        net.add_token(atomic.place(initial), 1).expect(
+            "BUG: Adding initial tokens to the atomic variable place should not cause an overflow",
+        );
+        atomic
+    }
+
+    /// Returns the label that identifies the atomic variable, e.g. in the `SyncRegistry`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the place that models the given value of the atomic variable.
+    pub const fn place(&self, value: bool) -> &PlaceRef {
+        if value {
+            &self.true_place
+        } else {
+            &self.false_place
+        }
+    }
+
+    /// Links the Petri net model of the atomic variable to the representation of
+    /// a call to `store` with the given `value`.
+    /// Connects the `start_place` and each place of the atomic variable to a new transition
+    /// that leads to the `end_place` and adds a token to the place for `value`.
+    pub fn link_to_store_call(
+        &self,
+        function_name: &str,
+        index: usize,
+        value: bool,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let (was_false_label, was_true_label) = store_transition_labels(function_name, index);
+        for (label, old_value) in [(was_false_label, false), (was_true_label, true)] {
+            let transition = net.add_transition(&label);
+            add_arc_place_transition(net, start_place, &transition);
+            add_arc_place_transition(net, self.place(old_value), &transition);
+            add_arc_transition_place(net, &transition, self.place(value));
+            add_arc_transition_place(net, &transition, end_place);
+        }
+    }
+
+    /// Links the Petri net model of the atomic variable to the representation of
+    /// a call to `compare_exchange` with the given `current` and `new` values.
+    /// Connects the `start_place` and the place for `current` to a new transition
+    /// that leads to the `end_place` and adds a token to the place for `new`.
+    /// Connects the `start_place` and the other place to a new transition
+    /// that leads to the `end_place` and gives the token back.
+    pub fn link_to_compare_exchange_call(
+        &self,
+        function_name: &str,
+        index: usize,
+        (current, new): (bool, bool),
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let (success_label, failure_label) =
+            compare_exchange_transition_labels(function_name, index);
+        let success = net.add_transition(&success_label);
+        add_arc_place_transition(net, start_place, &success);
+        add_arc_place_transition(net, self.place(current), &success);
+        add_arc_transition_place(net, &success, self.place(new));
+        add_arc_transition_place(net, &success, end_place);
+
+        let failure = net.add_transition(&failure_label);
+        add_arc_place_transition(net, start_place, &failure);
+        add_arc_place_transition(net, self.place(!current), &failure);
+        add_arc_transition_place(net, &failure, self.place(!current));
+        add_arc_transition_place(net, &failure, end_place);
+    }
+
+    /// Makes the transition depend on the atomic variable having the given `value`.
+    /// Connects the place for `value` to the transition and back,
+    /// so that the transition reads the token without consuming it.
+    pub fn guard_transition(&self, value: bool, transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_place_transition(net, self.place(value), transition);
+        add_arc_transition_place(net, transition, self.place(value));
+    }
+}
+
+/// Call to `std::sync::atomic::AtomicBool::new` or `std::sync::atomic::AtomicUsize::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Atomic` with the initial value passed as the first argument.
+/// - Links the return place to the `Atomic`.
+///
+/// # Errors
+///
+/// If the initial value is not a constant `false` (or 0) or `true` (or 1), then an error is returned.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) -> Result<(), TranslationError> {
+    let initial = extract_nth_argument_as_flag(args, 0).ok_or_else(|| {
+        TranslationError::Unsupported(format!(
+            "`{function_name}` with an initial value that is not a constant 0 or 1 is not supported yet"
+        ))
+    })?;
+    call_foreign_function(function_name, index, places, net);
+    // Create a new atomic variable
+    let atomic = Atomic::new(index, initial, net);
+    // The return value contains a new atomic variable. Link the local variable to it.
+    memory.link_atomic(destination, atomic);
+    debug!("NEW ATOMIC WITH VALUE {initial}: {destination:?}");
+    Ok(())
+}
+
+/// Call to `std::sync::atomic::AtomicBool::load` or `std::sync::atomic::AtomicUsize::load`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the atomic variable linked to the first argument (the self reference).
+/// - Links the return place to the atomic variable, so that branches on it can be guarded.
+pub fn call_load<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    // Retrieve the atomic variable from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let atomic_ref = memory.get_atomic(&self_ref).clone();
+
+    call_foreign_function(function_name, index, places, net);
+    debug!("LOAD FROM {} AT CALL {index}", atomic_ref.label());
+    memory.link_atomic_load(destination, atomic_ref, false);
+}
+
+/// Call to `std::sync::atomic::AtomicBool::store` or `std::sync::atomic::AtomicUsize::store`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the atomic variable linked to the first argument (the self reference).
+/// - Connects the start and end place to the atomic variable.
+///
+/// Like for `std::sync::Barrier::wait`, the cleanup target is ignored.
+/// Assume `store` never unwinds.
+///
+/// # Errors
+///
+/// If the stored value is not a constant `false` (or 0) or `true` (or 1), then an error is returned.
+///
+/// # Panics
+///
+/// If the self reference is not a place, then the function panics.
+pub fn call_store<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) -> Result<(), TranslationError> {
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let value = extract_nth_argument_as_flag(args, 1).ok_or_else(|| {
+        TranslationError::Unsupported(format!(
+            "`{function_name}` with a value that is not a constant 0 or 1 is not supported yet"
+        ))
+    })?;
+    let atomic_ref = memory.get_atomic(&self_ref);
+
+    let places = places.ignore_cleanup_place();
+    let (start_place, end_place) = places.get_start_end_place();
+    atomic_ref.link_to_store_call(function_name, index, value, &start_place, &end_place, net);
+    debug!("STORE {value} TO {} AT CALL {index}", atomic_ref.label());
+    Ok(())
+}
+
+/// Call to `std::sync::atomic::AtomicBool::compare_exchange`
+/// or `std::sync::atomic::AtomicUsize::compare_exchange`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the atomic variable linked to the first argument (the self reference).
+/// - Connects the start and end place to the atomic variable.
+///
+/// Like for `std::sync::Barrier::wait`, the cleanup target is ignored.
+/// Assume `compare_exchange` never unwinds.
+///
+/// # Errors
+///
+/// If the current or the new value is not a constant `false` (or 0) or `true` (or 1),
+/// then an error is returned.
+///
+/// # Panics
+///
+/// If the self reference is not a place, then the function panics.
+pub fn call_compare_exchange<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) -> Result<(), TranslationError> {
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let (Some(current), Some(new)) = (
+        extract_nth_argument_as_flag(args, 1),
+        extract_nth_argument_as_flag(args, 2),
+    ) else {
+        return Err(TranslationError::Unsupported(format!(
+            "`{function_name}` with values that are not constants 0 or 1 is not supported yet"
+        )));
+    };
+    let atomic_ref = memory.get_atomic(&self_ref);
+
+    let places = places.ignore_cleanup_place();
+    let (start_place, end_place) = places.get_start_end_place();
+    atomic_ref.link_to_compare_exchange_call(
+        function_name,
+        index,
+        (current, new),
+        &start_place,
+        &end_place,
+        net,
+    );
+    debug!(
+        "COMPARE EXCHANGE {current} FOR {new} ON {} AT CALL {index}",
+        atomic_ref.label()
+    );
+    Ok(())
+}
+
+/// Handles MIR assignments that derive a new value from the result of a call to `load`:
+/// - `_X = _Y` and `_X = move _Y`
+/// - `_X = Not(_Y)`
+/// - `_X = Eq(_Y, const C)` and `_X = Ne(_Y, const C)` where `C` is 0 or 1
+///
+/// If `_Y` is linked to the result of a `load`, links `_X` to the same atomic variable,
+/// negating the result if needed.
+pub fn handle_load_assignment<'tcx>(
+    place: &Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    let (rhs, negate) = match rvalue {
+        Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs)) => (rhs, false),
+        Rvalue::UnaryOp(UnOp::Not, Operand::Copy(rhs) | Operand::Move(rhs)) => (rhs, true),
+        Rvalue::BinaryOp(op @ (BinOp::Eq | BinOp::Ne), operands) => {
+            let (Operand::Copy(rhs) | Operand::Move(rhs), constant) = &**operands else {
+                return;
+            };
+            let Some(constant) = operand_as_flag(constant) else {
+                return;
+            };
+            // `_Y == 1` and `_Y != 0` are `_Y`, while `_Y == 0` and `_Y != 1` are `Not(_Y)`.
+            (rhs, constant != (*op == BinOp::Eq))
+        }
+        _ => return,
+    };
+    let Some((atomic_ref, negated)) = memory.get_atomic_load(rhs) else {
+        return;
+    };
+    let atomic_ref = atomic_ref.clone();
+    memory.link_atomic_load(*place, atomic_ref, negated != negate);
+}
+
+/// Guards the branches of a `SwitchInt` terminator on the result of a call to `load`.
+/// Every transition reads the place of the atomic variable for the value that leads to its branch.
+///
+/// Only the form `switchInt(_X) -> [V: bbA, otherwise: bbB]` with `V` equal to 0 or 1 is guarded,
+/// which is the form of an `if` or `while` condition.
+/// The transitions must be in the same order as the targets, with the `otherwise` branch last.
+pub fn guard_switch_int_branches<'tcx>(
+    discriminant: &Place<'tcx>,
+    values: &[u128],
+    transitions: &[TransitionRef],
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let Some((atomic_ref, negated)) = memory.get_atomic_load(discriminant) else {
+        return;
+    };
+    let ([value], [transition, otherwise]) = (values, transitions) else {
+        return;
+    };
+    let value = match *value {
+        0 => false,
+        1 => true,
+        _ => return,
+    };
+    atomic_ref.guard_transition(value != negated, transition, net);
+    atomic_ref.guard_transition(value == negated, otherwise, net);
+    debug!("GUARDED BRANCHES ON {}", atomic_ref.label());
+}
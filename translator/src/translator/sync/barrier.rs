@@ -0,0 +1,141 @@
+//! Representation of a barrier in the Petri net.
+//!
+//! The barrier is modelled using two places and one transition:
+//!
+//! - Every call to `wait` adds a token to `arrived` and the thread waits in a place of its own.
+//! - The `release` transition takes `n` tokens from `arrived` and adds `n` tokens to `released`,
+//!   where `n` is the number of threads passed to `std::sync::Barrier::new`.
+//!   It can only fire once all `n` threads reached the barrier.
+//! - Every waiting thread takes one token from `released` to leave the barrier.
+//!
+//! Since the release consumes exactly `n` tokens, the barrier can be reused
+//! by the same threads after all of them left it, like in the standard library.
+
+use log::debug;
+
+use crate::compiler_interface::{CallArgs, Place};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, add_weighted_arc_place_transition,
+    add_weighted_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef};
+use crate::naming::barrier::{
+    label, leave_transition_label, place_labels, release_transition_label, wait_place_label,
+};
+use crate::naming::function::foreign_call_transition_labels;
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+#[derive(PartialEq, Eq)]
+pub struct Barrier {
+    label: String,
+    arrived: PlaceRef,
+    released: PlaceRef,
+}
+
+impl Barrier {
+    /// Creates a new barrier for `n` threads whose label is based on `index`.
+    /// Adds its Petri net model to the net.
+    pub fn new(index: usize, n: usize, net: &mut PetriNet) -> Self {
+        let (p1, p2) = place_labels(index);
+        let arrived = net.add_place(&p1);
+        let released = net.add_place(&p2);
+
+        let release = net.add_transition(&release_transition_label(index));
+        // A barrier for zero threads behaves like a barrier for one thread, it never blocks.
+        let n = n.max(1);
+        // Release the threads only when `n` of them arrived at the barrier.
+        add_weighted_arc_place_transition(net, &arrived, &release, n);
+        add_weighted_arc_transition_place(net, &release, &released, n);
+
+        Self {
+            label: label(index),
+            arrived,
+            released,
+        }
+    }
+
+    /// Returns the label that identifies the barrier, e.g. in the `SyncRegistry`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Links the Petri net model of the barrier to the representation of
+    /// a call to `std::sync::Barrier::wait`.
+    /// Connects the `start_place` to a new transition that adds a token to `arrived`
+    /// and moves the thread to a new waiting place.
+    /// Connects the waiting place and `released` to a new transition that leads to the `end_place`.
+    pub fn link_to_wait_call(
+        &self,
+        function_name: &str,
+        index: usize,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let (arrive_label, _) = foreign_call_transition_labels(function_name, index);
+        let arrive = net.add_transition(&arrive_label);
+        let waiting = net.add_place(&wait_place_label(function_name, index));
+        let leave = net.add_transition(&leave_transition_label(function_name, index));
+
+        add_arc_place_transition(net, start_place, &arrive);
+        add_arc_transition_place(net, &arrive, &self.arrived);
+        add_arc_transition_place(net, &arrive, &waiting);
+
+        add_arc_place_transition(net, &waiting, &leave);
+        add_arc_place_transition(net, &self.released, &leave);
+        add_arc_transition_place(net, &leave, end_place);
+    }
+}
+
+/// Call to `std::sync::Barrier::new` with the number of threads `n`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Barrier`.
+/// - Links the return place to the `Barrier`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    n: usize,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new barrier
+    let barrier = Barrier::new(index, n, net);
+    // The return value contains a new barrier. Link the local variable to it.
+    memory.link_barrier(destination, barrier);
+    debug!("NEW BARRIER FOR {n} THREADS: {destination:?}");
+}
+
+/// Call to `std::sync::Barrier::wait`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the barrier linked to the first argument (the self reference).
+/// - Connects the start and end place to the barrier.
+///
+/// Like for `std::sync::Condvar::wait`, the cleanup target is ignored.
+/// Assume `wait` never unwinds.
+pub fn call_wait<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    // Retrieve the barrier from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let barrier_ref = memory.get_barrier(&self_ref);
+
+    let places = places.ignore_cleanup_place();
+    let (start_place, end_place) = places.get_start_end_place();
+    barrier_ref.link_to_wait_call(function_name, index, &start_place, &end_place, net);
+    debug!("WAIT ON {} AT CALL {index}", barrier_ref.label());
+}
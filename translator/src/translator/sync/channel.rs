@@ -0,0 +1,286 @@
+//! Representation of a channel and its endpoints in the Petri net.
+//!
+//! The channel stores one reference to the place in the Petri net
+//! that models the messages buffered in the channel. The place starts without tokens.
+//!
+//! - A call to `send` is a producer transition that adds a token to the buffer.
+//! - A call to `recv` is a consumer transition that takes a token from the buffer.
+//!   It can only fire if a message was sent before and not received yet.
+//!
+//! The sender and the receiver returned by `std::sync::mpsc::channel` simply contain
+//! a reference to the corresponding channel.
+//! Cloning the sender returns another reference to the same channel.
+//!
+//! A bounded channel created with `std::sync::mpsc::sync_channel(k)` stores an additional
+//! place for the free slots in the buffer, which starts with `k` tokens.
+//! The places of the buffer and of the free slots are complementary:
+//! `send` takes a token from the free slots and `recv` gives it back.
+//! A call to `send` on a full channel can therefore only fire once a message is received.
+//!
+//! If `k` is zero, the channel is a rendezvous channel and `send` blocks until
+//! the message is received. It is modelled with one free slot and a place for the received messages:
+//! The thread moves to a waiting place after adding the message to the buffer and
+//! leaves the call to `send` only after the receiver took the message.
+//!
+//! The disconnection of the channel is not modelled: A call to `recv` waits forever
+//! for a message even if every sender was dropped.
+
+use log::debug;
+
+use crate::compiler_interface::{CallArgs, Place};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::channel::{
+    buffer_place_label, free_slots_place_label, label, received_place_label,
+    send_leave_transition_label, send_wait_place_label,
+};
+use crate::naming::function::foreign_call_transition_labels;
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+#[derive(PartialEq, Eq)]
+pub struct Channel {
+    label: String,
+    buffer: PlaceRef,
+    free_slots: Option<PlaceRef>,
+    received: Option<PlaceRef>,
+}
+
+impl Channel {
+    /// Creates a new channel whose label is based on `index`.
+    /// Adds an empty place for the buffer to the Petri net.
+    pub fn new(index: usize, net: &mut PetriNet) -> Self {
+        let buffer = net.add_place(&buffer_place_label(index));
+
+        Self {
+            label: label(index),
+            buffer,
+            free_slots: None,
+            received: None,
+        }
+    }
+
+    /// Creates a new bounded channel for `capacity` messages whose label is based on `index`.
+    /// Adds an empty place for the buffer and a place for the free slots to the Petri net.
+    /// If the capacity is zero, adds an empty place for the received messages too.
+    pub fn new_bounded(index: usize, capacity: usize, net: &mut PetriNet) -> Self {
+        let buffer = net.add_place(&buffer_place_label(index));
+        let free_slots = net.add_place(&free_slots_place_label(index));
+        // A rendezvous channel holds the message in the buffer until the receiver takes it.
+        let slots = capacity.max(1);
+        net.add_token(&free_slots, slots).expect(
+            "BUG: Adding initial tokens to the free slots place should not cause an overflow",
+        );
+        net.set_capacity(&free_slots, slots)
+            .expect("BUG: Setting the capacity of the free slots place should not fail");
+        let received = if capacity == 0 {
+            Some(net.add_place(&received_place_label(index)))
+        } else {
+            None
+        };
+
+        Self {
+            label: label(index),
+            buffer,
+            free_slots: Some(free_slots),
+            received,
+        }
+    }
+
+    /// Returns the label that identifies the channel, e.g. in the `SyncRegistry`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Checks whether the channel is a rendezvous channel, i.e., a bounded channel with a capacity of zero.
+    pub fn is_rendezvous(&self) -> bool {
+        self.received.is_some()
+    }
+
+    /// Adds the send arcs for this channel.
+    /// Connects the transition to the place of the buffer, then the transition
+    /// will add a message to the buffer when it fires.
+    /// For a bounded channel, connects the place of the free slots to the transition,
+    /// then the transition will only fire if the buffer is not full.
+    pub fn add_send_arcs(&self, send_transition: &TransitionRef, net: &mut PetriNet) {
+        if let Some(free_slots) = &self.free_slots {
+            add_arc_place_transition(net, free_slots, send_transition);
+        }
+        add_arc_transition_place(net, send_transition, &self.buffer);
+    }
+
+    /// Adds the receive arcs for this channel.
+    /// Connects the place of the buffer to the transition, then the transition
+    /// will only fire if there is a message in the buffer.
+    /// For a bounded channel, connects the transition to the place of the free slots
+    /// and, for a rendezvous channel, to the place of the received messages.
+    pub fn add_recv_arcs(&self, recv_transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_place_transition(net, &self.buffer, recv_transition);
+        if let Some(free_slots) = &self.free_slots {
+            add_arc_transition_place(net, recv_transition, free_slots);
+        }
+        if let Some(received) = &self.received {
+            add_arc_transition_place(net, recv_transition, received);
+        }
+    }
+
+    /// Links the Petri net model of the rendezvous channel to the representation of
+    /// a call to `std::sync::mpsc::SyncSender::<T>::send`.
+    /// Connects the `start_place` to a new transition that adds the message to the buffer
+    /// and moves the thread to a new waiting place.
+    /// Connects the waiting place and the place of the received messages
+    /// to a new transition that leads to the `end_place`.
+    ///
+    /// # Panics
+    ///
+    /// If the channel is not a rendezvous channel, then the function panics.
+    pub fn link_to_rendezvous_send_call(
+        &self,
+        function_name: &str,
+        index: usize,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let received = self
+            .received
+            .as_ref()
+            .expect("BUG: Only a rendezvous channel should be linked to a rendezvous send");
+        let (send_label, _) = foreign_call_transition_labels(function_name, index);
+        let send = net.add_transition(&send_label);
+        let waiting = net.add_place(&send_wait_place_label(function_name, index));
+        let leave = net.add_transition(&send_leave_transition_label(function_name, index));
+
+        add_arc_place_transition(net, start_place, &send);
+        self.add_send_arcs(&send, net);
+        add_arc_transition_place(net, &send, &waiting);
+
+        add_arc_place_transition(net, &waiting, &leave);
+        add_arc_place_transition(net, received, &leave);
+        add_arc_transition_place(net, &leave, end_place);
+    }
+}
+
+/// Call to `std::sync::mpsc::channel`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Channel`.
+/// - Links the return place to the pair of sender and receiver of the `Channel`.
+pub fn call_channel<'tcx>(
+    function_name: &str,
+    index: usize,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new channel
+    let channel = Channel::new(index, net);
+    // The return value contains a new sender and receiver. Link the local variable to them.
+    memory.link_channel(destination, channel);
+    debug!("NEW CHANNEL: {destination:?}");
+}
+
+/// Call to `std::sync::mpsc::sync_channel` with the capacity of the buffer.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new bounded `Channel`.
+/// - Links the return place to the pair of sender and receiver of the `Channel`.
+pub fn call_sync_channel<'tcx>(
+    function_name: &str,
+    index: usize,
+    capacity: usize,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new bounded channel
+    let channel = Channel::new_bounded(index, capacity, net);
+    // The return value contains a new sender and receiver. Link the local variable to them.
+    memory.link_channel(destination, channel);
+    debug!("NEW SYNC CHANNEL WITH CAPACITY {capacity}: {destination:?}");
+}
+
+/// Call to `std::sync::mpsc::Sender::<T>::send` or `std::sync::mpsc::SyncSender::<T>::send`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the channel of the sender linked to the first argument (the self reference).
+/// - Adds the arcs from the transition of this function call to the buffer of the channel.
+/// - For a rendezvous channel, the thread additionally waits until the message is received.
+///
+/// Like for `std::sync::Mutex::<T>::lock`, the cleanup target is ignored.
+/// Assume `send` never unwinds.
+pub fn call_send<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    // Retrieve the channel from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let channel_ref = memory.get_sender(&self_ref);
+
+    let places = places.ignore_cleanup_place();
+    if channel_ref.is_rendezvous() {
+        let (start_place, end_place) = places.get_start_end_place();
+        channel_ref.link_to_rendezvous_send_call(
+            function_name,
+            index,
+            &start_place,
+            &end_place,
+            net,
+        );
+        debug!("SEND TO {} AT CALL {index}", channel_ref.label());
+        return;
+    }
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let send_transition = transitions.get_default();
+    channel_ref.add_send_arcs(send_transition, net);
+    debug!(
+        "SEND TO {} DUE TO TRANSITION {send_transition}",
+        channel_ref.label()
+    );
+}
+
+/// Call to `std::sync::mpsc::Receiver::<T>::recv`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the channel of the receiver linked to the first argument (the self reference).
+/// - Adds the arcs between the buffer of the channel and the transition of this function call.
+///
+/// Like for `std::sync::Mutex::<T>::lock`, the cleanup target is ignored.
+/// Assume `recv` never unwinds.
+pub fn call_recv<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let recv_transition = transitions.get_default();
+
+    // Retrieve the channel from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let channel_ref = memory.get_receiver(&self_ref);
+    channel_ref.add_recv_arcs(recv_transition, net);
+    debug!(
+        "RECV FROM {} DUE TO TRANSITION {recv_transition}",
+        channel_ref.label()
+    );
+}
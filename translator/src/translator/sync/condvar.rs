@@ -0,0 +1,457 @@
+//! Representation of a condition variable in the Petri net.
+//!
+//! The behavior of the condition variable is modelled using
+//! two places and three transitions, plus some additional places for the mutex
+//! and the variable containing the condition for the condition variable.
+//! The corresponding net can be found in `./cli/assets/condition_variable_model.dot`
+//! and `./cli/assets/condition_variable_model.svg`.
+//!
+//! `wait_enabled` and `lost_signal` model the behavior of lost signals.
+//! There is a conflict between the two transitions. If `wait()` is called before `notify()`,
+//! the token in `notify` will be consumed by `lost_signal`.
+//! But if `wait()` is called first, then the token from `wait_enabled` will be removed,
+//! preventing `lost_signal` from firing and ensuring that an output token is set,
+//! which will allow the waiting thread to continue.
+//!
+//! The mutex passed to `wait()` is unlocked by `wait_start` and locked again by `notify_received`,
+//! using the lock and unlock arcs of the `MutexRef` stored in the mutex guard.
+//! A thread that was notified therefore only continues once the mutex is unlocked.
+//! If the mutex is never unlocked again, the thread stays blocked at `notify_received`,
+//! which is reported as a deadlock even though the notification was sent.
+//!
+//! `notify_all` is modelled with a broadcast gadget that is added to the net on the first call.
+//! The gadget adds a place `waiting` that has a token while a thread waits
+//! and a place `notify_all` that keeps the broadcast active.
+//! While the broadcast is active, `notify_all_wake` wakes the waiting threads one by one.
+//! It ends with `notify_all_end` once `wait_enabled` has a token again, i.e. when no thread waits anymore.
+//! A broadcast with no waiting thread is lost like a signal from `notify_one`.
+//! Only one call to `wait` is supported per condition variable, so the waiting threads run the same code,
+//! e.g. threads spawned in a loop. Without the queue, a single thread waits at a time, so at most one thread is woken.
+//!
+//! By default, `notify_one` may wake any of the waiting threads, see `CondvarWakeup::Any`.
+//! With `CondvarWakeup::Fifo`, the waiting threads stand in a queue of `FIFO_QUEUE_LENGTH` ordered positions.
+//! Every position has a place that is marked while a thread stands there and a place that is marked while it is free.
+//! `wait_start` puts the thread in the last position, the `queue_advance` transitions move it ahead
+//! one position at a time and only the thread in the first position is woken.
+//! The free place of the first position is `wait_enabled`, so the model without a queue is the special case
+//! of a queue with a single position whose thread is not tracked.
+//! A thread that calls `wait` while the queue is full blocks until a position is free.
+//!
+//! The token of every position moves between its free place and its taken place,
+//! so each of them has a capacity of one token, like `wait_enabled` without a queue.
+//! The place `waiting` of the broadcast gadget holds a token per waiting thread,
+//! i.e. at most one, or `FIFO_QUEUE_LENGTH` with the queue.
+//! The places `notify` and `notify_all` have no capacity, since a call to `notify_one` or `notify_all`
+//! adds a token every time, even if the waiting thread was not woken yet.
+//!
+//! This Petri net model is a modified version of the one presented in the paper
+//! "Modelling Multithreaded Applications Using Petri Nets" by Kavi, Moshtaghi and Chen.
+//! <https://www.researchgate.net/publication/220091454_Modeling_Multithreaded_Applications_Using_Petri_Nets>
+//! The model was extended to model the condition on which the condition variable waits
+//! and unnecessary intermediate places were removed.
+
+use log::debug;
+use std::cell::OnceCell;
+
+use crate::compiler_interface::{CallArgs, Place};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::condvar::{
+    label, notify_all_place_labels, notify_all_transition_labels, place_labels,
+    queue_advance_transition_label, queue_place_labels, transition_labels,
+};
+use crate::translator::function::{Places, PostprocessingTask};
+use crate::translator::mir_function::memory::{Memory, MutexGuardRef, MutexRef};
+use crate::translator::special_function::call_foreign_function;
+use crate::translator::TranslationError;
+use crate::utils::extract_nth_argument_as_place;
+use crate::CondvarWakeup;
+
+/// The number of positions in the queue of waiting threads with the FIFO wakeup policy.
+pub const FIFO_QUEUE_LENGTH: usize = 4;
+
+#[derive(PartialEq, Eq)]
+pub struct Condvar {
+    index: usize,
+    label: String,
+    /// The places marked while a position in the queue of waiting threads is taken, the first position first.
+    /// It is empty unless the wakeup policy is `CondvarWakeup::Fifo`.
+    queue: Vec<PlaceRef>,
+    /// The places marked while a position in the queue is free. The first one is `wait_enabled`.
+    free: Vec<PlaceRef>,
+    wait_start: TransitionRef,
+    notify: PlaceRef,
+    notify_received: TransitionRef,
+    /// The end place of the call to `wait` and the mutex it unlocks, once linked.
+    wait_call: OnceCell<(PlaceRef, MutexRef)>,
+    /// The gadget that models `notify_all`, once a call to it was found.
+    broadcast: OnceCell<Broadcast>,
+}
+
+/// The places and transitions that model `notify_all` for a condition variable.
+#[derive(PartialEq, Eq)]
+struct Broadcast {
+    notify_all: PlaceRef,
+    notify_all_wake: TransitionRef,
+}
+
+impl Condvar {
+    /// Creates a new condition variable whose label is based on `index`.
+    /// Adds its Petri net model to the net, including the queue of waiting threads
+    /// if the wakeup policy is `CondvarWakeup::Fifo`.
+    pub fn new(index: usize, wakeup: CondvarWakeup, net: &mut PetriNet) -> Self {
+        let (p1, p2) = place_labels(index);
+        let wait_enabled = net.add_place(&p1);
+        let notify = net.add_place(&p2);
+
+        net.add_token(&wait_enabled, 1)
+            .expect("BUG: Adding initial token to `wait_enabled` should not cause an overflow");
+
+        let (t1, t2, t3) = transition_labels(index);
+        let wait_start = net.add_transition(&t1);
+        let lost_signal = net.add_transition(&t2);
+        let notify_received = net.add_transition(&t3);
+
+        let (queue, free) = match wakeup {
+            CondvarWakeup::Any => (Vec::new(), vec![wait_enabled]),
+            CondvarWakeup::Fifo => Self::add_queue(index, wait_enabled, net),
+        };
+        let condvar = Self {
+            index,
+            label: label(index),
+            queue,
+            free,
+            wait_start,
+            notify,
+            notify_received,
+            wait_call: OnceCell::new(),
+            broadcast: OnceCell::new(),
+        };
+        for place in condvar.free.iter().chain(&condvar.queue) {
+            net.set_capacity(place, 1)
+                .expect("BUG: Setting the capacity of the positions of the queue should not fail");
+        }
+
+        // Loop for consuming the token in `notify` when `wait()` has not been called yet.
+        for free in &condvar.free {
+            add_arc_place_transition(net, free, &lost_signal);
+        }
+        add_arc_place_transition(net, &condvar.notify, &lost_signal);
+        for free in &condvar.free {
+            add_arc_transition_place(net, &lost_signal, free);
+        }
+        // Start the wait only if the wait is enabled
+        condvar.add_start_waiting_arcs(net);
+        // Exit the wait only if the notify was received
+        add_arc_place_transition(net, &condvar.notify, &condvar.notify_received);
+        // Regenerate the token in `wait_enabled` when exiting the wait
+        condvar.add_wake_arcs(&condvar.notify_received, net);
+        condvar
+    }
+
+    /// Adds the queue of waiting threads for the FIFO wakeup policy to the net.
+    /// Returns the places of the taken positions and the places of the free positions.
+    fn add_queue(
+        index: usize,
+        wait_enabled: PlaceRef,
+        net: &mut PetriNet,
+    ) -> (Vec<PlaceRef>, Vec<PlaceRef>) {
+        let mut queue = Vec::with_capacity(FIFO_QUEUE_LENGTH);
+        let mut free = vec![wait_enabled];
+        for position in 0..FIFO_QUEUE_LENGTH {
+            let (taken_label, free_label) = queue_place_labels(index, position);
+            queue.push(net.add_place(&taken_label));
+            if position > 0 {
+                let free_place = net.add_place(&free_label);
+                net.add_token(&free_place, 1).expect(
+                    "BUG: Adding initial token to a free position of the queue should not cause an overflow",
+                );
+                free.push(free_place);
+            }
+        }
+        // Move a thread ahead when the position in front of it is free.
+        for position in 0..FIFO_QUEUE_LENGTH - 1 {
+            let queue_advance =
+                net.add_transition(&queue_advance_transition_label(index, position));
+            add_arc_place_transition(net, &queue[position + 1], &queue_advance);
+            add_arc_place_transition(net, &free[position], &queue_advance);
+            add_arc_transition_place(net, &queue_advance, &queue[position]);
+            add_arc_transition_place(net, &queue_advance, &free[position + 1]);
+        }
+        (queue, free)
+    }
+
+    /// Adds the arcs that put a thread that starts waiting in the last position of the queue.
+    /// Without a queue, it only takes the token from `wait_enabled`.
+    fn add_start_waiting_arcs(&self, net: &mut PetriNet) {
+        let last = self.free.len() - 1;
+        add_arc_place_transition(net, &self.free[last], &self.wait_start);
+        if let Some(taken) = self.queue.get(last) {
+            add_arc_transition_place(net, &self.wait_start, taken);
+        }
+    }
+
+    /// Adds the arcs that remove the woken thread from the first position of the queue.
+    /// Without a queue, it only returns the token to `wait_enabled`.
+    fn add_wake_arcs(&self, wake: &TransitionRef, net: &mut PetriNet) {
+        if let Some(taken) = self.queue.first() {
+            add_arc_place_transition(net, taken, wake);
+        }
+        add_arc_transition_place(net, wake, &self.free[0]);
+    }
+
+    /// Adds the arcs that enable the transition only while no thread waits,
+    /// i.e. while every position of the queue is free.
+    fn add_no_waiter_arcs(&self, transition: &TransitionRef, net: &mut PetriNet) {
+        for free in &self.free {
+            add_arc_place_transition(net, free, transition);
+            add_arc_transition_place(net, transition, free);
+        }
+    }
+
+    /// Returns the label that identifies the condition variable, e.g. in the `SyncRegistry`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Links the Petri net model of the condition variable to the representation of
+    /// a call to `std::sync::Condvar::wait`.
+    /// Connects the `start_place` place to the `wait_start` transition.
+    /// Connects the `notify_received` transition to the `end_place`.
+    /// Unlocks the mutex when the waiting starts, lock it when the waiting ends.
+    ///
+    /// # Errors
+    ///
+    /// If this function is called more than once, then an error is returned before modifying the net.
+    pub fn link_to_wait_call(
+        &self,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        mutex_guard_ref: &MutexGuardRef,
+        net: &mut PetriNet,
+    ) -> Result<(), TranslationError> {
+        if self.wait_call.get().is_some() {
+            return Err(TranslationError::Unsupported(
+                "Multiple calls to `wait` or `wait_while` are not supported yet".to_string(),
+            ));
+        }
+        add_arc_place_transition(net, start_place, &self.wait_start);
+        add_arc_transition_place(net, &self.notify_received, end_place);
+
+        mutex_guard_ref.mutex.add_unlock_arc(&self.wait_start, net);
+        mutex_guard_ref
+            .mutex
+            .add_lock_arc(&self.notify_received, net);
+        if let Some(broadcast) = self.broadcast.get() {
+            Self::link_broadcast_to_wait_call(broadcast, end_place, &mutex_guard_ref.mutex, net);
+        }
+        // Mark the condvar as already linked to call
+        self.wait_call
+            .set((end_place.clone(), mutex_guard_ref.mutex.clone()))
+            .unwrap_or_else(|_| {
+                panic!("BUG: The condvar was already linked to a wait call before calling `link_to_wait_call`")
+            });
+        Ok(())
+    }
+
+    /// Links the Petri net model of the condition variable to the representation of
+    /// a call to `std::sync::Condvar::notify_one`.
+    /// Connects the `notify_transition` transition to the `notify` place.
+    pub fn link_to_notify_one_call(&self, notify_transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_transition_place(net, notify_transition, &self.notify);
+    }
+
+    /// Links the Petri net model of the condition variable to the representation of
+    /// a call to `std::sync::Condvar::notify_all`.
+    /// Adds the broadcast gadget to the net on the first call.
+    /// Connects the `notify_transition` transition to the `notify_all` place.
+    pub fn link_to_notify_all_call(&self, notify_transition: &TransitionRef, net: &mut PetriNet) {
+        let broadcast = self.broadcast.get_or_init(|| self.add_broadcast(net));
+        add_arc_transition_place(net, notify_transition, &broadcast.notify_all);
+    }
+
+    /// Adds the broadcast gadget that models `notify_all` to the net.
+    /// If the call to `wait` was already linked, the gadget is linked to it too.
+    fn add_broadcast(&self, net: &mut PetriNet) -> Broadcast {
+        let (p1, p2) = notify_all_place_labels(self.index);
+        let notify_all = net.add_place(&p1);
+        let waiting = net.add_place(&p2);
+        net.set_capacity(&waiting, self.queue.len().max(1))
+            .expect("BUG: Setting the capacity of the `waiting` place should not fail");
+        let (t1, t2) = notify_all_transition_labels(self.index);
+        let notify_all_wake = net.add_transition(&t1);
+        let notify_all_end = net.add_transition(&t2);
+
+        // Keep track of the waiting thread, so that the broadcast only wakes a thread that waits.
+        add_arc_transition_place(net, &self.wait_start, &waiting);
+        add_arc_place_transition(net, &waiting, &self.notify_received);
+        // Wake the waiting thread and keep the broadcast active.
+        add_arc_place_transition(net, &notify_all, &notify_all_wake);
+        add_arc_transition_place(net, &notify_all_wake, &notify_all);
+        add_arc_place_transition(net, &waiting, &notify_all_wake);
+        self.add_wake_arcs(&notify_all_wake, net);
+        // End the broadcast when no thread waits. It is lost if no thread waited at all.
+        add_arc_place_transition(net, &notify_all, &notify_all_end);
+        self.add_no_waiter_arcs(&notify_all_end, net);
+
+        let broadcast = Broadcast {
+            notify_all,
+            notify_all_wake,
+        };
+        if let Some((end_place, mutex_ref)) = self.wait_call.get() {
+            Self::link_broadcast_to_wait_call(&broadcast, end_place, mutex_ref, net);
+        }
+        broadcast
+    }
+
+    /// Links the broadcast gadget to the call to `wait`.
+    /// The woken thread continues after the call and locks the mutex again.
+    fn link_broadcast_to_wait_call(
+        broadcast: &Broadcast,
+        end_place: &PlaceRef,
+        mutex_ref: &MutexRef,
+        net: &mut PetriNet,
+    ) {
+        add_arc_transition_place(net, &broadcast.notify_all_wake, end_place);
+        mutex_ref.add_lock_arc(&broadcast.notify_all_wake, net);
+    }
+}
+
+/// Call to `std::sync::Condvar::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Condvar` with the given wakeup policy.
+/// - Links the return place to the `Condvar`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    wakeup: CondvarWakeup,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new condvar
+    let condvar = Condvar::new(index, wakeup, net);
+    // The return value contains a new condition variable. Link the local variable to it.
+    memory.link_condvar(destination, condvar);
+    debug!("NEW CONDVAR: {destination:?}");
+}
+
+/// Call to `std::sync::Condvar::notify_one`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the condvar linked to the first argument (the self reference).
+/// - Creates an arc from the transition of this function call to the `notify`
+///   in the Petri net model of the condvar.
+///
+/// In some cases, the `std::sync::Condvar::notify_one` function contains a cleanup target.
+/// This target is not called in practice but creates trouble for lost signal detection.
+/// The reason is that any call may fail, which is equivalent to saying that the `notify_one`
+/// was never present in the program, leading to a false lost signal.
+/// In conclusion: Ignore the cleanup place, do not model it. Assume `notify_one` never unwinds.
+pub fn call_notify_one<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    // Retrieve the condvar from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let condvar_ref = memory.get_condvar(&self_ref);
+    condvar_ref.link_to_notify_one_call(transitions.get_default(), net);
+}
+
+/// Call to `std::sync::Condvar::notify_all`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the condvar linked to the first argument (the self reference).
+/// - Creates an arc from the transition of this function call to the `notify_all`
+///   place of the broadcast gadget of the condvar.
+///
+/// The cleanup target is ignored for the same reason as in `call_notify_one`.
+pub fn call_notify_all<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    // Retrieve the condvar from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let condvar_ref = memory.get_condvar(&self_ref);
+    condvar_ref.link_to_notify_all_call(transitions.get_default(), net);
+}
+
+/// Call to `std::sync::Condvar::wait`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the condvar linked to the first argument (the self reference).
+/// - Retrieves the mutex guard linked to the second argument.
+/// - Connects the start and end place to the condition variable.
+/// - Adds the arc for the unlocking of the mutex at the start of the `wait`.
+/// - Adds the arc for the locking of the mutex at the end of the `wait`.
+/// - Links the return place to the mutex guard.
+/// - Returns a postprocessing task to link the mutex to the condition variable.
+///
+/// In some cases, the `std::sync::Condvar::wait` function contains a cleanup target.
+/// This target is not called in practice but creates trouble for lost signal detection.
+/// The reason is that any call may fail, which is equivalent to saying that the `wait`
+/// was never present in the program, leading to a false model.
+/// In conclusion: Ignore the cleanup place, do not model it. Assume `wait` never unwinds.
+///
+/// # Errors
+///
+/// If the condition variable is already linked to another call to `wait`, then an error is returned.
+pub fn call_wait<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) -> Result<PostprocessingTask, TranslationError> {
+    // Retrieve the condvar from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let condvar_ref = memory.get_condvar(&self_ref);
+    // Retrieve the mutex guard from the local variable passed to the function as an argument.
+    let mutex_guard = extract_nth_argument_as_place(args, 1).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the first argument as a place")
+    });
+    let mutex_guard_ref = memory.get_mutex_guard(&mutex_guard);
+
+    // Connect the start and end place to the condition variable
+    let places = places.ignore_cleanup_place();
+    let (start_place, end_place) = places.get_start_end_place();
+    condvar_ref.link_to_wait_call(&start_place, &end_place, mutex_guard_ref, net)?;
+    let wait_start = condvar_ref.wait_start.clone();
+
+    // The return value contains the mutex guard passed to the function. Link the local variable to it.
+    memory.link_place_to_same_value(destination, mutex_guard);
+
+    // Create a postprocessing task to link the mutex to the condvar.
+    // This creates the condition and skip logic.
+    Ok(PostprocessingTask::link_mutex_to_condvar(
+        index,
+        start_place,
+        end_place,
+        wait_start,
+    ))
+}
@@ -11,18 +11,25 @@
 //! If the place does not have a token, the mutex is locked.
 //!
 //! A mutex guard simply contains a reference to the corresponding mutex.
+//!
+//! With the `model_poisoning` option, the mutex has a second place that models whether it is poisoned.
+//! Dropping a guard on the unwind path, i.e. during a panic, adds a token to it.
+//! The paths that a poisoned `lock` takes read the token: the `Err` branch of a `match`
+//! on the result of `lock` and the panic of `unwrap`. The `Ok` path is always possible,
+//! which over-approximates the behavior of a poisoned mutex.
 
 use log::debug;
-use std::cell::RefCell;
+use std::cell::{OnceCell, RefCell};
 
+use crate::compiler_interface::{CallArgs, Place, Rvalue};
 use crate::data_structures::petri_net_interface::{
     add_arc_place_transition, add_arc_transition_place, connect_places,
 };
 use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
 use crate::naming::condvar::wait_skip_label;
-use crate::naming::mutex::{condition_place_labels, place_label};
+use crate::naming::mutex::{condition_place_labels, place_label, poisoned_place_label};
 use crate::translator::function::{Places, PostprocessingTask};
-use crate::translator::mir_function::memory::{Memory, MutexRef};
+use crate::translator::mir_function::memory::{Memory, MutexRef, Value};
 use crate::translator::special_function::call_foreign_function;
 use crate::utils::extract_nth_argument_as_place;
 
@@ -30,23 +37,34 @@ use crate::utils::extract_nth_argument_as_place;
 pub struct Mutex {
     mutex: PlaceRef,
     deref_mut: RefCell<Vec<TransitionRef>>,
+    index: usize,
+    poisoned: OnceCell<PlaceRef>,
 }
 
 impl Mutex {
     /// Creates a new mutex whose label is based on `index`.
-    /// Adds a place to the Petri Net.
+    /// Adds a place to the Petri Net with a capacity of one token.
     pub fn new(index: usize, net: &mut PetriNet) -> Self {
         let label = place_label(index);
         let mutex = net.add_place(&label);
         net.add_token(&mutex, 1)
             .expect("BUG: Adding initial token to mutex place should not cause an overflow");
+        net.set_capacity(&mutex, 1)
+            .expect("BUG: Setting the capacity of the mutex place should not fail");
 
         Self {
             mutex,
             deref_mut: RefCell::new(Vec::new()),
+            index,
+            poisoned: OnceCell::new(),
         }
     }
 
+    /// Returns the label that identifies the mutex, i.e., the label of its place in the Petri net.
+    pub fn label(&self) -> String {
+        self.mutex.label().to_string()
+    }
+
     /// Adds a lock arc for this mutex.
     /// Connects the mutex's place to the transition, then the transition will only
     /// fire if the mutex is unlocked.
@@ -61,6 +79,30 @@ impl Mutex {
         add_arc_transition_place(net, unlock_transition, &self.mutex);
     }
 
+    /// Adds a poison arc for this mutex.
+    /// Connects the transition to the place that models the poisoning of the mutex,
+    /// then the mutex is poisoned after the transition fires.
+    pub fn add_poison_arc(&self, transition: &TransitionRef, net: &mut PetriNet) {
+        let poisoned = self.poisoned_place(net);
+        add_arc_transition_place(net, transition, poisoned);
+    }
+
+    /// Adds a poisoned condition for this mutex.
+    /// Connects the place that models the poisoning of the mutex to the transition and back,
+    /// then the transition will only fire if the mutex is poisoned.
+    pub fn add_poisoned_condition(&self, transition: &TransitionRef, net: &mut PetriNet) {
+        let poisoned = self.poisoned_place(net);
+        add_arc_place_transition(net, poisoned, transition);
+        add_arc_transition_place(net, transition, poisoned);
+    }
+
+    /// Returns the place that models the poisoning of the mutex.
+    /// The place is created the first time it is needed without a token, i.e. the mutex starts unpoisoned.
+    fn poisoned_place(&self, net: &mut PetriNet) -> &PlaceRef {
+        self.poisoned
+            .get_or_init(|| net.add_place(&poisoned_place_label(self.index)))
+    }
+
     /// Adds a transition of a call to `std::ops::DerefMut::deref_mut`.
     /// This transition has set a value for a mutex and must be used to disable the condition variable later.
     pub fn add_deref_mut_transition(&self, transition: TransitionRef) {
@@ -70,7 +112,7 @@ impl Mutex {
     /// Links the mutex to a condition variable.
     ///
     /// - Creates two new places `condition_not_set` and `condition_set` that model
-    ///   the value of the condition used for a condition variable.
+    ///   the value of the condition used for a condition variable. Both have a capacity of one token.
     /// - Connects `condition_not_set` through each transition saved before to `condition_set`.
     ///   This represents setting the condition at every function call discovered so far.
     /// - Connects `condition_set` and `start_place` to a new `wait_skip` transition.
@@ -95,6 +137,11 @@ impl Mutex {
         net.add_token(&condition_not_set, 1).expect(
             "BUG: Adding initial token to `condition_not_set` should not cause an overflow",
         );
+        // The token moves between the two places, so each of them holds at most one token.
+        for place in [&condition_not_set, &condition_set] {
+            net.set_capacity(place, 1)
+                .expect("BUG: Setting the capacity of the condition places should not fail");
+        }
 
         for transition_ref in self.deref_mut.borrow().iter() {
             debug!(
@@ -144,8 +191,8 @@ impl Guard {
 pub fn call_lock<'tcx>(
     function_name: &str,
     index: usize,
-    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
-    destination: rustc_middle::mir::Place<'tcx>,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
     places: Places,
     net: &mut PetriNet,
     memory: &mut Memory<'tcx>,
@@ -178,7 +225,7 @@ pub fn call_lock<'tcx>(
 pub fn call_new<'tcx>(
     function_name: &str,
     index: usize,
-    destination: rustc_middle::mir::Place<'tcx>,
+    destination: Place<'tcx>,
     places: Places,
     net: &mut PetriNet,
     memory: &mut Memory<'tcx>,
@@ -193,19 +240,91 @@ pub fn call_new<'tcx>(
     PostprocessingTask::new_mutex(mutex_ref.clone())
 }
 
-/// Checks whether the variable to be dropped is a mutex guard.
-/// If that is the case, adds an unlock arc for the mutex corresponding to the mutex guard.
-/// The unlock arc is added for the usual transition as well as the cleanup transition.
+/// Checks whether the variable to be dropped is a mutex guard or contains mutex guards, e.g. a `struct` holding one.
+/// If that is the case, adds an unlock arc for the mutex corresponding to each mutex guard.
+/// The caller invokes it for the usual transition as well as the cleanup transition.
 /// Otherwise do nothing.
+///
+/// This covers the explicit `std::mem::drop(guard)` and the `Drop` terminators
+/// that the compiler inserts at the end of the scope of the guard, also on the unwind path.
 pub fn handle_mutex_guard_drop<'tcx>(
-    place: rustc_middle::mir::Place<'tcx>,
+    place: Place<'tcx>,
     unlock_transition: &TransitionRef,
     net: &mut PetriNet,
     memory: &Memory<'tcx>,
 ) {
-    if memory.is_mutex_guard(&place) {
-        let mutex_guard_ref = memory.get_mutex_guard(&place);
+    for mutex_guard_ref in memory.get_mutex_guards(&place) {
         mutex_guard_ref.mutex.add_unlock_arc(unlock_transition, net);
         debug!("DROP MUTEX GUARD {place:?} DUE TO TRANSITION {unlock_transition}");
     }
 }
+
+/// Checks whether the variable dropped on the unwind path is a mutex guard or contains mutex guards.
+/// If that is the case, the mutex corresponding to each mutex guard is poisoned by the transition.
+/// Otherwise do nothing.
+pub fn handle_mutex_guard_poisoning<'tcx>(
+    place: Place<'tcx>,
+    unlock_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    for mutex_guard_ref in memory.get_mutex_guards(&place) {
+        mutex_guard_ref.mutex.add_poison_arc(unlock_transition, net);
+        debug!("POISON MUTEX DUE TO TRANSITION {unlock_transition}");
+    }
+}
+
+/// Call to `std::result::Result::<T, E>::unwrap` on the result of `lock`.
+/// The transition of the panic, i.e. the cleanup transition, is only enabled if the mutex is poisoned.
+/// The guard in the `PoisonError` is dropped inside `unwrap`, so the cleanup transition also unlocks the mutex.
+pub fn handle_poisoned_unwrap<'tcx>(
+    lock_result: &Place<'tcx>,
+    cleanup_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    for mutex_guard_ref in memory.get_mutex_guards(lock_result) {
+        let mutex_ref = &mutex_guard_ref.mutex;
+        mutex_ref.add_poisoned_condition(cleanup_transition, net);
+        mutex_ref.add_unlock_arc(cleanup_transition, net);
+        debug!("UNWRAP OF POISONED MUTEX DUE TO TRANSITION {cleanup_transition}");
+    }
+}
+
+/// Handles MIR assignments of the form `_X = discriminant(_Y)`
+/// where `_Y` is the result of `lock`, i.e. linked to a mutex guard.
+/// Links the place to the mutex, so that the `Err` branch on it can be guarded.
+pub fn handle_discriminant_assignment<'tcx>(
+    place: &Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    let Rvalue::Discriminant(rhs) = rvalue else {
+        return;
+    };
+    let Some(Value::MutexGuard(mutex_guard_ref)) = memory.copy_value(rhs) else {
+        return;
+    };
+    memory.link_lock_result(*place, mutex_guard_ref.mutex.clone());
+}
+
+/// Guards the `Err` branch of a `SwitchInt` terminator on the discriminant of the result of `lock`.
+/// The transition of the branch is only enabled if the mutex is poisoned.
+///
+/// The discriminant of `Err` is 1. The transitions must be in the same order as the targets.
+pub fn guard_poisoned_branch<'tcx>(
+    discriminant: &Place<'tcx>,
+    values: &[u128],
+    transitions: &[TransitionRef],
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let Some(mutex_ref) = memory.get_lock_result(discriminant) else {
+        return;
+    };
+    let Some(err_branch) = values.iter().position(|value| *value == 1) else {
+        return;
+    };
+    mutex_ref.add_poisoned_condition(&transitions[err_branch], net);
+    debug!("GUARDED ERR BRANCH ON {}", mutex_ref.label());
+}
@@ -0,0 +1,151 @@
+//! Representation of a one-time initialization in the Petri net,
+//! i.e., a `std::sync::Once` or a `std::sync::OnceLock`.
+//!
+//! The one-time initialization is modelled using two places:
+//!
+//! - `not_started` starts with one token. The first call to `call_once` or `get_or_init`
+//!   consumes it and moves the thread to a running place of its own.
+//! - `done` starts without tokens. The thread running the initialization adds a token to it
+//!   when it finishes. Every later call skips the initialization once `done` has a token.
+//!
+//! A thread that calls `call_once` while another thread runs the initialization
+//! waits until the initialization is done, like in the standard library.
+//! The closure that performs the initialization is not translated.
+
+use log::debug;
+
+use crate::compiler_interface::{CallArgs, Place};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef};
+use crate::naming::function::foreign_call_transition_labels;
+use crate::naming::once::{
+    finish_transition_label, label, place_labels, running_place_label, skip_transition_label,
+};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+#[derive(PartialEq, Eq)]
+pub struct Once {
+    label: String,
+    not_started: PlaceRef,
+    done: PlaceRef,
+}
+
+impl Once {
+    /// Creates a new one-time initialization whose label is based on `index`.
+    /// Adds two places with a capacity of one token to the Petri net.
+    /// Only the place `not_started` starts with a token.
+    pub fn new(index: usize, net: &mut PetriNet) -> Self {
+        let (p1, p2) = place_labels(index);
+        let not_started = net.add_place(&p1);
+        let done = net.add_place(&p2);
+        net.add_token(&not_started, 1).expect(
+            "BUG: Adding initial tokens to the one-time initialization place should not cause an overflow",
+        );
+        net.set_capacity(&not_started, 1).expect(
+            "BUG: Setting the capacity of the one-time initialization place should not fail",
+        );
+        net.set_capacity(&done, 1).expect(
+            "BUG: Setting the capacity of the one-time initialization place should not fail",
+        );
+
+        Self {
+            label: label(index),
+            not_started,
+            done,
+        }
+    }
+
+    /// Returns the label that identifies the one-time initialization, e.g. in the `SyncRegistry`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Links the Petri net model of the one-time initialization to the representation of
+    /// a call to `std::sync::Once::call_once` or `std::sync::OnceLock::<T>::get_or_init`.
+    /// Connects the `start_place` and `not_started` to a new transition that moves the thread
+    /// to a new running place. Connects the running place to a new transition
+    /// that adds a token to `done` and leads to the `end_place`.
+    /// Connects the `start_place` and `done` to a new transition that leads to the `end_place`
+    /// and gives the token back to `done`.
+    pub fn link_to_call(
+        &self,
+        function_name: &str,
+        index: usize,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let (init_label, _) = foreign_call_transition_labels(function_name, index);
+        let init = net.add_transition(&init_label);
+        let running = net.add_place(&running_place_label(function_name, index));
+        let finish = net.add_transition(&finish_transition_label(function_name, index));
+        let skip = net.add_transition(&skip_transition_label(function_name, index));
+
+        add_arc_place_transition(net, start_place, &init);
+        add_arc_place_transition(net, &self.not_started, &init);
+        add_arc_transition_place(net, &init, &running);
+
+        add_arc_place_transition(net, &running, &finish);
+        add_arc_transition_place(net, &finish, &self.done);
+        add_arc_transition_place(net, &finish, end_place);
+
+        add_arc_place_transition(net, start_place, &skip);
+        add_arc_place_transition(net, &self.done, &skip);
+        add_arc_transition_place(net, &skip, &self.done);
+        add_arc_transition_place(net, &skip, end_place);
+    }
+}
+
+/// Call to `std::sync::Once::new` or `std::sync::OnceLock::<T>::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Once`.
+/// - Links the return place to the `Once`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new one-time initialization
+    let once = Once::new(index, net);
+    // The return value contains a new one-time initialization. Link the local variable to it.
+    memory.link_once(destination, once);
+    debug!("NEW ONCE: {destination:?}");
+}
+
+/// Call to `std::sync::Once::call_once` or `std::sync::OnceLock::<T>::get_or_init`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the one-time initialization linked to the first argument (the self reference).
+/// - Connects the start and end place to the one-time initialization.
+///
+/// Like for `std::sync::Barrier::wait`, the cleanup target is ignored.
+/// Assume the initialization never unwinds.
+pub fn call_once<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    // Retrieve the one-time initialization from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let once_ref = memory.get_once(&self_ref);
+
+    let places = places.ignore_cleanup_place();
+    let (start_place, end_place) = places.get_start_end_place();
+    once_ref.link_to_call(function_name, index, &start_place, &end_place, net);
+    debug!("CALL ONCE ON {} AT CALL {index}", once_ref.label());
+}
@@ -0,0 +1,306 @@
+//! Representation of a read-write lock and its guards in the Petri net.
+//!
+//! The read-write lock stores one reference to the place in the Petri net
+//! that models the state of the lock. The place starts with `MAX_READERS` tokens,
+//! one for every reader that may hold the lock at the same time.
+//!
+//! - A call to `read` takes one token. Readers only exclude each other
+//!   when more than `MAX_READERS` of them hold the lock.
+//! - A call to `write` takes all the tokens at once. It can only fire if no reader
+//!   or writer holds the lock and it excludes every other reader and writer.
+//!
+//! Dropping a guard returns the tokens that the corresponding call took.
+//! The place has a capacity of `MAX_READERS` tokens.
+//!
+//! A guard contains a reference to the corresponding read-write lock and the kind of access.
+//!
+//! With the `model_poisoning` option, the read-write lock has a second place that models whether it is poisoned.
+//! Like in `std::sync::RwLock`, only dropping a write guard on the unwind path, i.e. during a panic, poisons it.
+//! Then both `read` and `write` may return `Err`, which is modelled like for the mutex, see `mutex`.
+
+use log::debug;
+use std::cell::OnceCell;
+
+use crate::compiler_interface::{CallArgs, Place, Rvalue};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, add_weighted_arc_place_transition,
+    add_weighted_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::rwlock::{place_label, poisoned_place_label};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{Memory, RwLockRef, Value};
+use crate::translator::special_function::call_foreign_function;
+use crate::translator::sync::LockFunction;
+use crate::utils::extract_nth_argument_as_place;
+
+/// The number of readers that may hold a read-write lock at the same time.
+/// It should be high enough to not introduce deadlocks between readers that do not exist in the program.
+pub const MAX_READERS: usize = 4;
+
+#[derive(PartialEq, Eq)]
+pub struct RwLock {
+    rwlock: PlaceRef,
+    index: usize,
+    poisoned: OnceCell<PlaceRef>,
+}
+
+impl RwLock {
+    /// Creates a new read-write lock whose label is based on `index`.
+    /// Adds a place to the Petri Net with `MAX_READERS` tokens and the same capacity.
+    pub fn new(index: usize, net: &mut PetriNet) -> Self {
+        let label = place_label(index);
+        let rwlock = net.add_place(&label);
+        net.add_token(&rwlock, MAX_READERS).expect(
+            "BUG: Adding initial tokens to read-write lock place should not cause an overflow",
+        );
+        net.set_capacity(&rwlock, MAX_READERS)
+            .expect("BUG: Setting the capacity of the read-write lock place should not fail");
+
+        Self {
+            rwlock,
+            index,
+            poisoned: OnceCell::new(),
+        }
+    }
+
+    /// Returns the label that identifies the read-write lock, i.e., the label of its place in the Petri net.
+    pub fn label(&self) -> String {
+        self.rwlock.label().to_string()
+    }
+
+    /// Adds the lock arcs for this read-write lock.
+    /// Connects the place of the read-write lock to the transition with an arc weighted by the tokens taken,
+    /// then the transition will only fire if there are enough tokens left for the kind of access.
+    pub fn add_lock_arcs(
+        &self,
+        access: Access,
+        lock_transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        add_weighted_arc_place_transition(net, &self.rwlock, lock_transition, access.tokens());
+    }
+
+    /// Adds the unlock arcs for this read-write lock.
+    /// Connects the transition to the place of the read-write lock with an arc weighted by the tokens taken,
+    /// then the transition will replenish the tokens when it fires.
+    pub fn add_unlock_arcs(
+        &self,
+        access: Access,
+        unlock_transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        add_weighted_arc_transition_place(net, unlock_transition, &self.rwlock, access.tokens());
+    }
+
+    /// Adds a poison arc for this read-write lock.
+    /// Connects the transition to the place that models the poisoning of the read-write lock,
+    /// then the read-write lock is poisoned after the transition fires.
+    pub fn add_poison_arc(&self, transition: &TransitionRef, net: &mut PetriNet) {
+        let poisoned = self.poisoned_place(net);
+        add_arc_transition_place(net, transition, poisoned);
+    }
+
+    /// Adds a poisoned condition for this read-write lock.
+    /// Connects the place that models the poisoning of the read-write lock to the transition and back,
+    /// then the transition will only fire if the read-write lock is poisoned.
+    pub fn add_poisoned_condition(&self, transition: &TransitionRef, net: &mut PetriNet) {
+        let poisoned = self.poisoned_place(net);
+        add_arc_place_transition(net, poisoned, transition);
+        add_arc_transition_place(net, transition, poisoned);
+    }
+
+    /// Returns the place that models the poisoning of the read-write lock.
+    /// The place is created the first time it is needed without a token, i.e. the lock starts unpoisoned.
+    fn poisoned_place(&self, net: &mut PetriNet) -> &PlaceRef {
+        self.poisoned
+            .get_or_init(|| net.add_place(&poisoned_place_label(self.index)))
+    }
+}
+
+/// The kind of access granted by a guard of a read-write lock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Shared access obtained with `std::sync::RwLock::<T>::read`.
+    Read,
+    /// Exclusive access obtained with `std::sync::RwLock::<T>::write`.
+    Write,
+}
+
+impl Access {
+    /// Returns the number of tokens taken from the place of the read-write lock.
+    const fn tokens(self) -> usize {
+        match self {
+            Self::Read => 1,
+            Self::Write => MAX_READERS,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq)]
+pub struct Guard {
+    pub rwlock: RwLockRef,
+    pub access: Access,
+}
+
+impl Guard {
+    /// Creates a new guard for a given read-write lock reference and kind of access.
+    pub const fn new(rwlock: RwLockRef, access: Access) -> Self {
+        Self { rwlock, access }
+    }
+}
+
+/// Call to `std::sync::RwLock::<T>::read` or `std::sync::RwLock::<T>::write`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the read-write lock linked to the first argument (the self reference).
+/// - Adds the arcs from the place of the read-write lock to the transition of this function call.
+/// - Creates a new `RwLockGuard` for the kind of access given by the function name.
+/// - Links the return place to the `RwLockGuard`.
+///
+/// Like for `std::sync::Mutex::<T>::lock`, the cleanup target is ignored.
+/// Assume `read` and `write` never unwind.
+pub fn call_lock<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let access = match LockFunction::from_name(function_name) {
+        Some(LockFunction::RwLockRead) => Access::Read,
+        Some(LockFunction::RwLockWrite) => Access::Write,
+        _ => panic!("BUG: `{function_name}` does not lock a read-write lock"),
+    };
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let lock_transition = transitions.get_default();
+
+    // Retrieve the read-write lock from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let rwlock_ref = memory.get_rwlock(&self_ref);
+    rwlock_ref.add_lock_arcs(access, lock_transition, net);
+
+    // Create a new guard
+    let rwlock_guard = Guard::new(rwlock_ref.clone(), access);
+
+    // The return value contains a new guard. Link the local variable to it.
+    memory.link_rwlock_guard(destination, rwlock_guard);
+    debug!("NEW {access:?} GUARD {destination:?} DUE TO TRANSITION {lock_transition}");
+}
+
+/// Call to `std::sync::RwLock::<T>::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `RwLock`.
+/// - Links the return place to the `RwLock`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    destination: Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new read-write lock
+    let rwlock = RwLock::new(index, net);
+    // The return value contains a new read-write lock. Link the local variable to it.
+    memory.link_rwlock(destination, rwlock);
+    debug!("NEW RWLOCK: {destination:?}");
+}
+
+/// Checks whether the variable to be dropped is a guard of a read-write lock or contains such guards.
+/// If that is the case, adds the unlock arcs for the read-write lock corresponding to each guard.
+/// Otherwise do nothing.
+pub fn handle_rwlock_guard_drop<'tcx>(
+    place: Place<'tcx>,
+    unlock_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    for rwlock_guard_ref in memory.get_rwlock_guards(&place) {
+        rwlock_guard_ref
+            .rwlock
+            .add_unlock_arcs(rwlock_guard_ref.access, unlock_transition, net);
+        debug!("DROP RWLOCK GUARD {place:?} DUE TO TRANSITION {unlock_transition}");
+    }
+}
+
+/// Checks whether the variable dropped on the unwind path is a write guard of a read-write lock
+/// or contains such guards. If that is the case, the corresponding read-write lock is poisoned by the transition.
+/// Read guards do not poison the lock.
+pub fn handle_rwlock_guard_poisoning<'tcx>(
+    place: Place<'tcx>,
+    unlock_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    for rwlock_guard_ref in memory.get_rwlock_guards(&place) {
+        if rwlock_guard_ref.access == Access::Write {
+            rwlock_guard_ref
+                .rwlock
+                .add_poison_arc(unlock_transition, net);
+            debug!("POISON RWLOCK DUE TO TRANSITION {unlock_transition}");
+        }
+    }
+}
+
+/// Call to `std::result::Result::<T, E>::unwrap` on the result of `read` or `write`.
+/// The transition of the panic, i.e. the cleanup transition, is only enabled if the read-write lock is poisoned.
+/// The guard in the `PoisonError` is dropped inside `unwrap`, so the cleanup transition also unlocks it.
+pub fn handle_poisoned_unwrap<'tcx>(
+    lock_result: &Place<'tcx>,
+    cleanup_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    for rwlock_guard_ref in memory.get_rwlock_guards(lock_result) {
+        let rwlock_ref = &rwlock_guard_ref.rwlock;
+        rwlock_ref.add_poisoned_condition(cleanup_transition, net);
+        rwlock_ref.add_unlock_arcs(rwlock_guard_ref.access, cleanup_transition, net);
+        debug!("UNWRAP OF POISONED RWLOCK DUE TO TRANSITION {cleanup_transition}");
+    }
+}
+
+/// Handles MIR assignments of the form `_X = discriminant(_Y)`
+/// where `_Y` is the result of `read` or `write`, i.e. linked to a guard of a read-write lock.
+/// Links the place to the read-write lock, so that the `Err` branch on it can be guarded.
+pub fn handle_discriminant_assignment<'tcx>(
+    place: &Place<'tcx>,
+    rvalue: &Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    let Rvalue::Discriminant(rhs) = rvalue else {
+        return;
+    };
+    let Some(Value::RwLockGuard(rwlock_guard_ref)) = memory.copy_value(rhs) else {
+        return;
+    };
+    memory.link_rwlock_result(*place, rwlock_guard_ref.rwlock.clone());
+}
+
+/// Guards the `Err` branch of a `SwitchInt` terminator on the discriminant of the result of `read` or `write`.
+/// The transition of the branch is only enabled if the read-write lock is poisoned.
+///
+/// The discriminant of `Err` is 1. The transitions must be in the same order as the targets.
+pub fn guard_poisoned_branch<'tcx>(
+    discriminant: &Place<'tcx>,
+    values: &[u128],
+    transitions: &[TransitionRef],
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let Some(rwlock_ref) = memory.get_rwlock_result(discriminant) else {
+        return;
+    };
+    let Some(err_branch) = values.iter().position(|value| *value == 1) else {
+        return;
+    };
+    rwlock_ref.add_poisoned_condition(&transitions[err_branch], net);
+    debug!("GUARDED ERR BRANCH ON {}", rwlock_ref.label());
+}
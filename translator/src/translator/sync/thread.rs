@@ -0,0 +1,294 @@
+//! Representation of a thread to be translated later.
+//!
+//! The thread involves a translation subprocess that connects
+//! to the main Petri net in two places:
+//! - The start place i.e, when `std::thread::spawn` is called.
+//! - The end place i.e, when `std::thread::JoinHandle::<T>::join` is called.
+//!
+//! The thread starts after the call to `std::thread::spawn`.
+//! The transition that represents this function call produces a second token
+//! and places it in the new thread's start place.
+//! This token models the execution flow of the new thread.
+//!
+//! The thread end place is connected to the transition that models
+//! the call to `std::thread::JoinHandle::<T>::join`.
+//!
+//! The Petri net for the new thread "spans" between the two transitions.
+//! We cannot connect this net to the net of the spawning thread until the
+//! call to `std::thread::JoinHandle::<T>::join` is translated.
+//! Therefore we store the basic information we need
+//! to translate the thread function and defer the translation.
+//! The function executed by the thread is translated to a Petri net just as any other.
+//!
+//! A thread spawned in a loop is translated once, but the spawn transition fires once per iteration.
+//! Every firing adds a token to the start place, so several instances of the thread run concurrently
+//! in the same subnet. The number of instances is bounded by a place with one token per instance,
+//! see the `thread_instances` option. Once all the instances started, the iterations take a skip transition
+//! that does not start a new thread. If the thread is joined, the skip transition also adds a token to the end place,
+//! as if the thread that was not modelled finished immediately.
+//! Since the instances share the subnet, the synchronization primitives created inside the thread are shared too.
+//!
+//! A thread spawned in a loop whose function waits for work in a loop, i.e. calls `recv` on a receiver
+//! or `lock` on a mutex inside a loop, is recognized as the worker of a pool. The workers consume the jobs
+//! from the shared queue: the buffer of the channel or the data protected by the mutex.
+//! The pool is modelled parametrically: the number of workers is the `worker_pool_size` option
+//! instead of `thread_instances`, independent of the number of iterations of the spawning loop.
+//! Like every channel, the queue is never disconnected, so a worker waiting on an empty queue never finishes.
+//! The thread is registered with a note describing the abstraction.
+//!
+//! By default, a `panic!` in the thread ends it in the same place as a normal return.
+//! With the `propagate_thread_panics` option, it ends in a separate panic place
+//! and joining the thread after a panic is a separate transition, i.e. `join` returning `Err`.
+//!
+//! The variables captured by the thread function that cannot be tracked, e.g. raw pointers,
+//! are reported with a warning when the thread is spawned.
+
+use log::{debug, info, warn};
+use std::cell::OnceCell;
+
+use crate::compiler_interface::{closure_captures, CallArgs, FunctionId, TerminatorKind, TyCtxt};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::thread::{
+    end_place_label, instances_place_label, join_err_transition_label, label, panic_place_label,
+    spawn_skip_transition_label, start_place_label,
+};
+use crate::translator::closure::move_captured_sync_variables;
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{Memory, Value};
+use crate::translator::special_function::call_foreign_function;
+use crate::translator::sync::{is_sync_type, LockFunction};
+use crate::utils::{extract_nth_argument_as_place, function_id_of_operand};
+
+/// The function on which the worker of a pool usually waits for the next job.
+const RECV_FUNCTION: &str = "std::sync::mpsc::Receiver::<T>::recv";
+
+pub struct Thread {
+    /// The transition from which the thread branches off at the start.
+    spawn_transition: TransitionRef,
+    /// The ID that uniquely identifies the function run by the thread.
+    function_id: FunctionId,
+    /// The aggregate value containing the sync variables passed to the thread.
+    aggregate: Vec<Value>,
+    /// The transition to which the thread joins in at the end.
+    join_transition: OnceCell<TransitionRef>,
+    /// The transition taken by a loop iteration once all the instances of the thread started.
+    /// It is only set for threads spawned in a loop.
+    spawn_skip_transition: Option<TransitionRef>,
+    /// An index to identify the thread.
+    pub index: usize,
+}
+
+impl std::cmp::PartialEq for Thread {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl std::cmp::Eq for Thread {}
+
+impl Thread {
+    /// Creates a new thread without a join transition.
+    /// The join transition must be set later.
+    pub const fn new(
+        spawn_transition: TransitionRef,
+        spawn_skip_transition: Option<TransitionRef>,
+        thread_function_id: FunctionId,
+        aggregate: Vec<Value>,
+        index: usize,
+    ) -> Self {
+        Self {
+            spawn_transition,
+            function_id: thread_function_id,
+            aggregate,
+            join_transition: OnceCell::new(),
+            spawn_skip_transition,
+            index,
+        }
+    }
+
+    /// Returns the label that identifies the thread, e.g. in the `SyncRegistry`.
+    pub fn label(&self) -> String {
+        label(self.index)
+    }
+
+    /// Sets the transition that models joining this thread.
+    pub fn set_join_transition(&self, join_transition: TransitionRef) {
+        let result = self.join_transition.set(join_transition);
+        assert!(
+            result.is_ok(),
+            "BUG: The join transition of a thread may only be set once"
+        );
+    }
+
+    /// Adds a place that models the thread end state after a `panic!`.
+    /// If the thread is joined, adds a copy of the join transition that consumes the token from it,
+    /// i.e. `join` returns `Err` after the thread panicked.
+    ///
+    /// It must be called before `prepare_for_translation`,
+    /// so that the copy does not consume from the end place too.
+    /// Returns the new panic place.
+    pub fn add_panic_place(&self, net: &mut PetriNet) -> PlaceRef {
+        let thread_panic_place = net.add_place(&panic_place_label(self.index));
+        if let Some(join_transition) = self.join_transition.get() {
+            let join_err_transition =
+                net.duplicate_transition(join_transition, &join_err_transition_label(self.index));
+            add_arc_place_transition(net, &thread_panic_place, &join_err_transition);
+        }
+        thread_panic_place
+    }
+
+    /// Prepares the thread for translation.
+    /// Adds a start and end place for the thread to the Petri net.
+    /// Connects the spawn transition to the start place and the end place to the join transition (if available).
+    /// Returns a 3-tuple containing the function ID, the start place and the end place.
+    pub fn prepare_for_translation(&self, net: &mut PetriNet) -> (FunctionId, PlaceRef, PlaceRef) {
+        let thread_start_place = net.add_place(&start_place_label(self.index));
+        let thread_end_place = net.add_place(&end_place_label(self.index));
+
+        add_arc_transition_place(net, &self.spawn_transition, &thread_start_place);
+        if let Some(join_transition) = self.join_transition.get() {
+            add_arc_place_transition(net, &thread_end_place, join_transition);
+            if let Some(spawn_skip_transition) = &self.spawn_skip_transition {
+                add_arc_transition_place(net, spawn_skip_transition, &thread_end_place);
+            }
+        }
+
+        (self.function_id, thread_start_place, thread_end_place)
+    }
+
+    /// Moves the aggregated value containing the sync variables to the new function's memory.
+    /// The sync variables passed to the thread are the ones captured by the closure,
+    /// see `closure::move_captured_sync_variables`.
+    pub fn move_sync_variables<'tcx>(&self, memory: &mut Memory<'tcx>, tcx: TyCtxt<'tcx>) {
+        move_captured_sync_variables(self.function_id, self.aggregate.clone(), memory, tcx);
+        debug!(
+            "MOVED AGGREGATE VALUE WITH SYNC VARIABLES TO THE THREAD {}",
+            self.index
+        );
+    }
+}
+
+/// Warns about the variables captured by the thread function that cannot be tracked:
+/// - Raw pointers, since the synchronization through the memory they point to is not modelled.
+/// - Types defined in the crate that contain a sync variable in their fields,
+///   since only the variables whose type names the sync variable are tracked in the memory.
+///
+/// In both cases, the threads may synchronize in ways that are missing in the model.
+pub fn warn_untracked_captures(function_id: FunctionId, index: usize, tcx: TyCtxt) {
+    for capture in closure_captures(function_id, tcx) {
+        if capture.contains_raw_pointer {
+            warn!(
+                "The thread {index} captures `{}`, which contains a raw pointer: The synchronization through it is not modelled and the result may be unsound",
+                capture.name
+            );
+        }
+        if is_sync_type(&capture.type_name) {
+            continue;
+        }
+        if let Some(sync_type) = capture
+            .reachable_types
+            .iter()
+            .find(|type_name| is_sync_type(type_name))
+        {
+            warn!(
+                "The thread {index} captures `{}` of type `{}`, which contains `{sync_type}`: The sync variable cannot be tracked and the result may be unsound",
+                capture.name, capture.type_name
+            );
+        }
+    }
+}
+
+/// Checks whether a call to the function takes the next job from the queue of a pool,
+/// i.e. receives from a channel or locks a mutex.
+pub fn is_queue_function(function_name: &str) -> bool {
+    function_name == RECV_FUNCTION
+        || LockFunction::from_name(function_name) == Some(LockFunction::MutexLock)
+}
+
+/// Returns the name of the function on which the thread function waits for work, if it is the worker of a pool:
+/// A call to `recv` on a receiver or to `lock` on a mutex inside a loop of the thread function.
+/// `recv` takes precedence, since the receiver of a pool is often shared behind a mutex.
+pub fn worker_queue_function(function_id: FunctionId, tcx: TyCtxt) -> Option<String> {
+    if !function_id.is_mir_available(tcx) {
+        return None;
+    }
+    let queue_functions: Vec<String> = function_id
+        .body(tcx)
+        .basic_blocks
+        .iter_enumerated()
+        .filter(|(block, _)| function_id.is_block_in_loop((*block).into(), tcx))
+        .filter_map(|(_, data)| match &data.terminator().kind {
+            TerminatorKind::Call { func, .. } => function_id_of_operand(func, function_id, tcx),
+            _ => None,
+        })
+        .map(|callee| callee.name(tcx))
+        .filter(|name| is_queue_function(name))
+        .collect();
+    queue_functions
+        .iter()
+        .find(|name| *name == RECV_FUNCTION)
+        .or_else(|| queue_functions.first())
+        .cloned()
+}
+
+/// Returns the note registered for the thread recognized as the worker of a pool.
+pub fn worker_pool_note(queue_function: &str, instances: usize) -> String {
+    format!("worker of a pool waiting on `{queue_function}` in a loop, modelled with {instances} instances")
+}
+
+/// Bounds the number of instances of a thread spawned in a loop.
+///
+/// - Adds a place with one token per instance, from which the spawn transition consumes.
+/// - Adds a copy of the spawn transition (before adding the arc above) that does not start the thread.
+///
+/// Returns the new skip transition.
+pub fn bound_instances(
+    spawn_transition: &TransitionRef,
+    index: usize,
+    instances: usize,
+    net: &mut PetriNet,
+) -> TransitionRef {
+    let spawn_skip_transition =
+        net.duplicate_transition(spawn_transition, &spawn_skip_transition_label(index));
+    let instances_place = net.add_place(&instances_place_label(index));
+    net.add_token(&instances_place, instances).expect(
+        "BUG: Adding the initial tokens to the instances place should not cause an overflow",
+    );
+    add_arc_place_transition(net, &instances_place, spawn_transition);
+    info!("Bounded the thread {index} spawned in a loop to {instances} instances");
+    spawn_skip_transition
+}
+
+/// Call to `std::thread::JoinHandle::<T>::join`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the join handle linked to the first argument (the self reference).
+/// - Sets the join transition for the thread.
+///
+/// In some cases, the `std::thread::JoinHandle::<T>::join` function contains a cleanup target.
+/// This target is not called in practice but creates trouble for deadlock detection.
+/// For instance, a thread that never returns will not cause a deadlock
+/// when joining it because the call could take the unwind path.
+/// In conclusion: Ignore the cleanup place, do not model it. Assume `join` never unwinds.
+pub fn call_join<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &CallArgs<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let transition = transitions.default();
+    // Retrieve the join handle from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let thread_ref = memory.get_join_handle(&self_ref);
+    thread_ref.set_join_transition(transition);
+    info!("Found join call for thread {}", thread_ref.index);
+}
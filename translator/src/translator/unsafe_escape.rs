@@ -0,0 +1,103 @@
+//! Submodule that detects the `unsafe` operations through which a sync variable may escape the memory.
+//!
+//! The memory only tracks the sync variables through places whose type names them, see `sync::is_sync_type`.
+//! The following operations can alias a sync variable through a place of a different type:
+//! - A transmute, e.g. `std::mem::transmute`, which reinterprets the value as another type.
+//! - A cast of a raw pointer to a sync variable to a pointer of another type, e.g. `*const u8`.
+//! - A copy through raw pointers, e.g. `std::ptr::read` or `std::ptr::copy_nonoverlapping`.
+//!
+//! The calls on the sync variable through the alias are then missing in the net.
+//! The sync variables linked to the operands are marked with unknown aliasing in the `SyncRegistry`,
+//! so the report can list which parts of the verdict rest on the assumption that they are not aliased.
+//! The operands whose type contains a sync variable that is not linked in the memory,
+//! e.g. a raw pointer created with `&raw const`, are reported with a warning since the primitive cannot be identified.
+
+use log::warn;
+
+use crate::compiler_interface::{
+    span_info, CastKind, FunctionId, Operand, Place, Rvalue, Span, TyCtxt,
+};
+use crate::data_structures::sync_registry::SyncRegistry;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::sync::is_sync_type;
+
+/// The functions that copy a value through raw pointers or reinterpret it as another type.
+const POINTER_COPY_FUNCTIONS: [&str; 7] = [
+    "std::mem::transmute_copy",
+    "std::ptr::copy",
+    "std::ptr::copy_nonoverlapping",
+    "std::ptr::read",
+    "std::ptr::read_unaligned",
+    "std::ptr::write",
+    "std::ptr::write_unaligned",
+];
+
+/// Checks whether a call to the function copies a value through raw pointers, see `POINTER_COPY_FUNCTIONS`.
+pub fn is_pointer_copy_function(function_name: &str) -> bool {
+    POINTER_COPY_FUNCTIONS.contains(&function_name)
+}
+
+/// Returns the name of the operation and the place of its operand if the right-hand side of an assignment
+/// is a cast that hides a sync variable behind another type:
+/// - A transmute from or to a type that contains a sync variable.
+/// - A cast of a pointer to a type that contains a sync variable to a pointer to a type that does not.
+pub fn escaping_cast<'tcx>(
+    rvalue: &Rvalue<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> Option<(&'static str, Place<'tcx>)> {
+    let Rvalue::Cast(kind, Operand::Copy(place) | Operand::Move(place), target_ty) = rvalue else {
+        return None;
+    };
+    let source_contains_sync = is_sync_type(&place_type_name(place, caller_function_id, tcx));
+    let target_contains_sync = is_sync_type(&target_ty.to_string());
+    match kind {
+        CastKind::Transmute if source_contains_sync || target_contains_sync => {
+            Some(("transmute", *place))
+        }
+        CastKind::PtrToPtr if source_contains_sync && !target_contains_sync => {
+            Some(("pointer cast", *place))
+        }
+        _ => None,
+    }
+}
+
+/// Marks the sync variables linked to the places with unknown aliasing in the registry.
+/// Warns about the places whose type contains a sync variable that is not linked in the memory.
+pub fn mark_unknown_aliasing<'tcx>(
+    operation: &str,
+    places: &[Place<'tcx>],
+    memory: &Memory<'tcx>,
+    registry: &mut SyncRegistry,
+    caller_function_id: FunctionId,
+    span: Span,
+    tcx: TyCtxt<'tcx>,
+) {
+    let span = span_info(span, tcx);
+    for place in places {
+        let labels = memory.get_sync_labels(place);
+        if labels.is_empty() {
+            let type_name = place_type_name(place, caller_function_id, tcx);
+            if is_sync_type(&type_name) {
+                warn!("The {operation} at {span} aliases a value of type `{type_name}` that cannot be tracked: The synchronization through it is not modelled and the result may be unsound");
+            }
+            continue;
+        }
+        for label in labels {
+            if registry.get(&label).is_some() {
+                warn!("The {operation} at {span} may alias {label}: The synchronization through the alias is not modelled");
+                registry.mark_unknown_aliasing(&label, span.clone());
+            }
+        }
+    }
+}
+
+/// Returns the name of the type of the place in the body of the given function.
+fn place_type_name<'tcx>(
+    place: &Place<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> String {
+    let body = caller_function_id.body(tcx);
+    place.ty(body, tcx).ty.to_string()
+}
@@ -0,0 +1,268 @@
+//! Submodule for miscellaneous utility functions.
+//!
+//! These functions should involve some kind of processing of the compiler types
+//! which does not need additional translation data structures.
+
+use crate::compiler_interface::{
+    argument_operand, BasicBlock, Body, CallArgs, Const, ConstValue, FunctionId, GenericArgs,
+    Operand, Place, StatementKind, TerminatorKind, Ty, TyCtxt, TyKind, RETURN_PLACE,
+};
+use crate::translator::TranslationError;
+
+/// Extracts the ID of the called function from the `rustc_middle::mir::Operand`.
+///
+/// First obtains the type (`rustc_middle::ty::Ty`) of the operand for every possible case.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/enum.Operand.html>
+///
+/// Then checks that the type is a function definition (`rustc_middle::ty::TyKind::FnDef`)
+/// or a closure (`rustc_middle::ty::TyKind::Closure`)
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/enum.TyKind.html>
+///
+/// This method is used to know which function will be called as part of the `Call` MIR Terminator.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/syntax/enum.TerminatorKind.html#variant.Call>
+///
+/// # Errors
+///
+/// If the operand is a function pointer, then an error is returned.
+///
+/// # Panics
+///
+/// If the operand is neither a function definition, a closure nor a function pointer, then the function panics.
+pub fn extract_function_id_from_operand<'tcx>(
+    operand: &Operand<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> Result<FunctionId, TranslationError> {
+    let function_type = operand_type(operand, caller_function_id, tcx);
+    match function_type.kind() {
+        TyKind::FnPtr(..) => Err(TranslationError::Unsupported(
+            "TyKind::FnPtr not implemented yet. Function pointers are present in the MIR"
+                .to_string(),
+        )),
+        TyKind::FnDef(def_id, _) | TyKind::Closure(def_id, _) => Ok(FunctionId::from(*def_id)),
+        _ => {
+            panic!("TyKind::FnDef, a function definition, but got: {function_type:?}");
+        }
+    }
+}
+
+/// Returns the ID of the function or closure that the operand refers to.
+/// Unlike `extract_function_id_from_operand`, it returns `None` for operands of other types,
+/// e.g. a function pointer or a `Box<dyn Fn()>`.
+pub fn function_id_of_operand<'tcx>(
+    operand: &Operand<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> Option<FunctionId> {
+    match operand_type(operand, caller_function_id, tcx).kind() {
+        TyKind::FnDef(def_id, _) | TyKind::Closure(def_id, _) => Some(FunctionId::from(*def_id)),
+        _ => None,
+    }
+}
+
+/// Extracts the generic arguments of the called function from the `rustc_middle::mir::Operand`.
+/// For instance, `[Wrapper]` for a call to `work::<Wrapper>` or `[T]` for a call to `<T as Lockable>::lock`.
+/// The operand is assumed to be a valid function, see `extract_function_id_from_operand`.
+pub fn extract_generic_args_from_operand<'tcx>(
+    operand: &Operand<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> GenericArgs<'tcx> {
+    let function_type = operand_type(operand, caller_function_id, tcx);
+    match function_type.kind() {
+        TyKind::FnDef(_, args) | TyKind::Closure(_, args) => args,
+        _ => {
+            panic!("TyKind::FnDef, a function definition, but got: {function_type:?}");
+        }
+    }
+}
+
+/// Returns the type of the operand in the body of the caller function.
+fn operand_type<'tcx>(
+    operand: &Operand<'tcx>,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> Ty<'tcx> {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => {
+            // Find the type through the local declarations of the caller function.
+            // The `Place` (memory location) of the called function should be declared there and we can query its type.
+            let body = caller_function_id.body(tcx);
+            let place_ty = place.ty(body, tcx);
+            place_ty.ty
+        }
+        Operand::Constant(constant) => constant.ty(),
+    }
+}
+
+/// Extracts the n-th argument from the arguments for the function call.
+/// Returns the place corresponding to that argument.
+///
+/// This is also useful for obtaining the self reference for method calls.
+/// For example: The call `mutex.lock()` desugars to `std::sync::Mutex::lock(&mutex)`
+/// where `&self` is the first argument.
+///
+/// If the argument can not be found (the array is shorter than the `index` argument)
+/// or the argument is a constant (which does not have a `Place` representation),
+/// then the function returns `None`.
+pub fn extract_nth_argument_as_place<'tcx>(
+    args: &CallArgs<'tcx>,
+    index: usize,
+) -> Option<Place<'tcx>> {
+    let operand = argument_operand(args.get(index)?);
+    match operand {
+        Operand::Move(place) | Operand::Copy(place) => Some(*place),
+        Operand::Constant(_) => None,
+    }
+}
+
+/// Extracts the n-th argument from the arguments for the function call.
+/// Returns the value of the argument if it is a constant that fits in a `usize`.
+///
+/// This is useful for obtaining parameters of the synchronization primitives
+/// that are known at compile time. For example: The call `Barrier::new(3)`
+/// passes the number of threads as a constant in the first argument.
+///
+/// If the argument can not be found, it is not a constant or its value is not an integer,
+/// then the function returns `None`.
+pub fn extract_nth_argument_as_usize(args: &CallArgs, index: usize, tcx: TyCtxt) -> Option<usize> {
+    let operand = argument_operand(args.get(index)?);
+    let Operand::Constant(constant) = operand else {
+        return None;
+    };
+    let scalar = constant.const_.try_to_scalar_int()?;
+    usize::try_from(scalar.to_target_usize(tcx)).ok()
+}
+
+/// Returns the value of the operand as a flag if it is a constant `false` (or 0) or `true` (or 1).
+///
+/// Atomic variables are only modelled when they store one of these two values.
+/// If the operand is not a constant or its value is a different integer,
+/// then the function returns `None`.
+pub fn operand_as_flag(operand: &Operand) -> Option<bool> {
+    let Operand::Constant(constant) = operand else {
+        return None;
+    };
+    let scalar = constant.const_.try_to_scalar_int()?;
+    match scalar.to_bits(scalar.size()) {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+/// Returns the value of the operand if it is a constant integer or boolean.
+/// Signed integers are sign-extended, so that comparisons between the values are correct.
+///
+/// If the operand is not a constant or its value does not fit in an `i128`, then the function returns `None`.
+pub fn operand_as_integer(operand: &Operand) -> Option<i128> {
+    let Operand::Constant(constant) = operand else {
+        return None;
+    };
+    let scalar = constant.const_.try_to_scalar_int()?;
+    let bits = scalar.to_bits(scalar.size());
+    let width = u32::try_from(scalar.size().bits()).ok()?;
+    if constant.const_.ty().is_signed() && width < u128::BITS {
+        // Move the sign bit to the top and shift back with sign extension.
+        let shift = u128::BITS - width;
+        #[allow(clippy::cast_possible_wrap)]
+        return Some(((bits << shift) as i128) >> shift);
+    }
+    i128::try_from(bits).ok()
+}
+
+/// Returns the value of the argument as a flag if it is a constant `false` (or 0) or `true` (or 1).
+/// For example: The call `AtomicBool::new(false)` passes the initial value as a constant in the first argument.
+///
+/// If the argument can not be found or it is not a constant flag, then the function returns `None`.
+pub fn extract_nth_argument_as_flag(args: &CallArgs, index: usize) -> Option<bool> {
+    operand_as_flag(argument_operand(args.get(index)?))
+}
+
+/// Extracts the closure passed as the 0-th argument to `std::thread::spawn`.
+/// Returns the place corresponding to that argument.
+///
+/// If a valid place cannot be found, then the operand was passed as a constant.
+/// If it is a `rustc_middle::mir::interpret::value::ConstValue::ZeroSized` return `None`.
+///
+/// # Panics
+///
+/// If the operand was passed a constant with user-defined type,
+/// a type constant (i.e. `T`) or an unevaluated constant, then the functions panics.
+pub fn extract_closure<'tcx>(args: &CallArgs<'tcx>) -> Option<Place<'tcx>> {
+    let operand = argument_operand(
+        args.first()
+            .expect("BUG: `std::thread::spawn` should receive at least one argument"),
+    );
+
+    match operand {
+        Operand::Move(place) | Operand::Copy(place) => Some(*place),
+        Operand::Constant(boxed_const) => {
+            let unboxed_const = **boxed_const;
+            assert!(unboxed_const.user_ty.is_none(), "BUG: The closure passed to `std::thread::spawn` should not be of type `Operand::Constant` with user-defined type");
+            match unboxed_const.const_ {
+                Const::Ty(..) => {
+                    panic!("BUG: The closure passed to `std::thread::spawn` should not be a constant containing a type");
+                }
+                Const::Unevaluated(_, _) => {
+                    panic!("BUG: The closure passed to `std::thread::spawn` should not be a unevaluated constant");
+                }
+                Const::Val(value, _ty) => {
+                    if value == ConstValue::ZeroSized {
+                        return None;
+                    }
+                    panic!("BUG: The closure passed to `std::thread::spawn` should not be a constant whose value is not a zero-sized type");
+                }
+            }
+        }
+    }
+}
+
+/// Checks whether a given substring appears in the type of a place.
+/// Uses the method `Place::ty` to get the type of the `place`.
+/// It finds the type of the place through the local declarations of the caller function where it is declared.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/struct.Place.html#method.ty>
+pub fn check_substring_in_place_type<'tcx>(
+    place: &Place<'tcx>,
+    expected_substring: &str,
+    caller_function_id: FunctionId,
+    tcx: TyCtxt<'tcx>,
+) -> bool {
+    let body = caller_function_id.body(tcx);
+    let place_ty = place.ty(body, tcx);
+    let ty_string = place_ty.ty.to_string();
+    ty_string.contains(expected_substring)
+}
+
+/// Checks whether the given basic block only returns from the function.
+/// This is the case if it contains no statements other than storage markers
+/// or assignments to the return place and its terminator is a `Return`,
+/// possibly after a chain of `Goto` terminators to blocks of the same kind.
+///
+/// It is used to detect function calls in tail position, i.e. calls whose return block is such a block.
+pub fn is_return_block(block: BasicBlock, body: &Body) -> bool {
+    let mut block = block;
+    // Visiting more blocks than there are in the body means that the chain of `Goto` is a cycle.
+    for _ in 0..body.basic_blocks.len() {
+        let data = &body.basic_blocks[block];
+        let only_trivial_statements =
+            data.statements
+                .iter()
+                .all(|statement| match &statement.kind {
+                    StatementKind::StorageLive(_)
+                    | StatementKind::StorageDead(_)
+                    | StatementKind::Nop => true,
+                    StatementKind::Assign(assign) => assign.0.local == RETURN_PLACE,
+                    _ => false,
+                });
+        if !only_trivial_statements {
+            return false;
+        }
+        match data.terminator().kind {
+            TerminatorKind::Return => return true,
+            TerminatorKind::Goto { target } => block = target,
+            _ => return false,
+        }
+    }
+    false
+}